@@ -1,6 +1,7 @@
 use anyhow::Result;
 use stellar_insights_backend::database::Database;
 use stellar_insights_backend::services::aggregation::{AggregationConfig, AggregationService};
+use stellar_insights_backend::services::rollup::{RollupConfig, RollupEngine};
 use sqlx::sqlite::SqlitePoolOptions;
 use std::sync::Arc;
 use tracing::{info, Level};
@@ -35,11 +36,18 @@ async fn main() -> Result<()> {
     let config = AggregationConfig {
         interval_hours: 1, // Run every hour
         lookback_hours: 2, // Process last 2 hours
-        batch_size: 10000, // Process 10k payments at a time
     };
 
+    // Roll up raw payments; the aggregation service copies the corridor rollups
+    let rollup_engine = Arc::new(RollupEngine::new(Arc::clone(&db), RollupConfig::default()));
+    rollup_engine.run_rollup(chrono::Utc::now()).await?;
+
     // Create aggregation service
-    let aggregation_service = Arc::new(AggregationService::new(Arc::clone(&db), config));
+    let aggregation_service = Arc::new(AggregationService::new(
+        Arc::clone(&db),
+        rollup_engine,
+        config,
+    ));
 
     info!("Aggregation service configured");
 
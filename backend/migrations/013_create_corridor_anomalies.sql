@@ -0,0 +1,17 @@
+-- Anomalies detected in hourly corridor metrics
+CREATE TABLE IF NOT EXISTS corridor_anomalies (
+    id TEXT PRIMARY KEY,
+    corridor_key TEXT NOT NULL,
+    metric TEXT NOT NULL, -- 'volume', 'success_rate', 'latency'
+    hour_bucket TEXT NOT NULL, -- hour in which the anomaly was observed
+    observed_value REAL NOT NULL,
+    expected_value REAL NOT NULL,
+    score REAL NOT NULL, -- signed robust z-score against the baseline
+    baseline TEXT NOT NULL, -- 'seasonal' or 'ewma'
+    severity TEXT NOT NULL, -- 'minor', 'major', 'critical'
+    detected_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(corridor_key, metric, hour_bucket)
+);
+
+CREATE INDEX idx_corridor_anomalies_corridor ON corridor_anomalies(corridor_key, hour_bucket DESC);
+CREATE INDEX idx_corridor_anomalies_severity ON corridor_anomalies(severity, detected_at DESC);
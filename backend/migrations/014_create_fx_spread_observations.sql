@@ -0,0 +1,23 @@
+-- Effective conversion rate of cross-asset path payments compared to the
+-- reference rate implied by USD price feeds
+CREATE TABLE IF NOT EXISTS fx_spread_observations (
+    payment_id TEXT PRIMARY KEY,
+    transaction_hash TEXT NOT NULL,
+    corridor_key TEXT NOT NULL, -- directional: SOURCE->DESTINATION
+    source_asset_code TEXT NOT NULL,
+    source_asset_issuer TEXT NOT NULL,
+    destination_asset_code TEXT NOT NULL,
+    destination_asset_issuer TEXT NOT NULL,
+    source_amount REAL NOT NULL,
+    destination_amount REAL NOT NULL,
+    effective_rate REAL NOT NULL, -- destination units per source unit
+    reference_rate REAL NOT NULL, -- destination units per source unit at USD prices
+    spread_bps REAL NOT NULL, -- positive when the sender received less than reference
+    source_amount_usd REAL NOT NULL,
+    observed_at TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_fx_spread_corridor ON fx_spread_observations(corridor_key, observed_at DESC);
+CREATE INDEX idx_fx_spread_source_asset ON fx_spread_observations(source_asset_code, source_asset_issuer);
+CREATE INDEX idx_fx_spread_destination_asset ON fx_spread_observations(destination_asset_code, destination_asset_issuer);
@@ -0,0 +1,22 @@
+-- Multi-resolution rollups for corridor, anchor, pool and asset metrics.
+-- Counters and gauges are stored as mergeable sums so that coarser buckets
+-- can be derived from finer ones without going back to raw events.
+CREATE TABLE IF NOT EXISTS metric_rollups (
+    entity_type TEXT NOT NULL, -- 'corridor', 'anchor', 'pool', 'asset'
+    entity_id TEXT NOT NULL,
+    resolution TEXT NOT NULL, -- '5m', '1h', '1d', '1w'
+    bucket_start TEXT NOT NULL,
+    total_transactions INTEGER NOT NULL DEFAULT 0,
+    successful_transactions INTEGER NOT NULL DEFAULT 0,
+    volume_usd REAL NOT NULL DEFAULT 0,
+    latency_sum_ms INTEGER NOT NULL DEFAULT 0,
+    latency_samples INTEGER NOT NULL DEFAULT 0,
+    gauge_sum REAL NOT NULL DEFAULT 0, -- e.g. pool TVL samples
+    gauge_samples INTEGER NOT NULL DEFAULT 0,
+    gauge_min REAL,
+    gauge_max REAL,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (entity_type, entity_id, resolution, bucket_start)
+);
+
+CREATE INDEX idx_metric_rollups_retention ON metric_rollups(resolution, bucket_start);
@@ -0,0 +1,55 @@
+-- SEP-1 stellar.toml data crawled from anchors' home domains
+CREATE TABLE IF NOT EXISTS anchor_stellar_toml (
+    anchor_id TEXT PRIMARY KEY REFERENCES anchors(id) ON DELETE CASCADE,
+    home_domain TEXT NOT NULL,
+    org_name TEXT,
+    org_dba TEXT,
+    org_url TEXT,
+    org_logo TEXT,
+    org_description TEXT,
+    org_official_email TEXT,
+    org_support_email TEXT,
+    network_passphrase TEXT,
+    signing_key TEXT,
+    transfer_server TEXT, -- SEP-6
+    transfer_server_sep0024 TEXT, -- SEP-24
+    kyc_server TEXT, -- SEP-12
+    web_auth_endpoint TEXT, -- SEP-10
+    direct_payment_server TEXT, -- SEP-31
+    anchor_quote_server TEXT, -- SEP-38
+    content_hash TEXT, -- sha256 of the last successfully parsed file
+    last_fetched_at TEXT NOT NULL,
+    last_success_at TEXT,
+    last_error TEXT, -- NULL when the last crawl succeeded
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- [[CURRENCIES]] entries accepted from the anchor's stellar.toml
+CREATE TABLE IF NOT EXISTS anchor_stellar_toml_currencies (
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    asset TEXT NOT NULL, -- 'CODE:ISSUER' or contract id
+    asset_code TEXT,
+    name TEXT,
+    description TEXT,
+    status TEXT,
+    display_decimals INTEGER,
+    is_asset_anchored INTEGER,
+    anchor_asset_type TEXT,
+    anchor_asset TEXT,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (anchor_id, asset)
+);
+
+-- One row per observed change of an anchor's stellar.toml
+CREATE TABLE IF NOT EXISTS anchor_stellar_toml_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    home_domain TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    fields TEXT NOT NULL, -- JSON object of tracked fields
+    changed_fields TEXT NOT NULL, -- JSON array of fields that differ from the previous row
+    raw_toml TEXT NOT NULL,
+    fetched_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_stellar_toml_history_anchor ON anchor_stellar_toml_history(anchor_id, fetched_at DESC);
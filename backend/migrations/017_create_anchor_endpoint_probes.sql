@@ -0,0 +1,26 @@
+-- SEP endpoints configured on an anchor; these take precedence over the
+-- endpoints discovered from its stellar.toml
+CREATE TABLE IF NOT EXISTS anchor_sep_endpoints (
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    sep TEXT NOT NULL, -- 'sep6', 'sep10', 'sep24', 'sep31' or 'sep38'
+    url TEXT NOT NULL, -- base URL as it would appear in stellar.toml
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (anchor_id, sep)
+);
+
+-- One row per probe of an anchor's SEP endpoint
+CREATE TABLE IF NOT EXISTS anchor_endpoint_probes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    sep TEXT NOT NULL,
+    url TEXT NOT NULL, -- URL that was requested
+    available INTEGER NOT NULL, -- responded with a 2xx status
+    schema_valid INTEGER NOT NULL, -- response body matched the SEP
+    status_code INTEGER,
+    latency_ms INTEGER,
+    error TEXT,
+    probed_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_endpoint_probes_anchor ON anchor_endpoint_probes(anchor_id, probed_at DESC);
+CREATE INDEX idx_endpoint_probes_probed_at ON anchor_endpoint_probes(probed_at);
@@ -0,0 +1,39 @@
+-- Current deposit/withdraw fee terms per anchor and asset, from SEP-6/24 /info
+CREATE TABLE IF NOT EXISTS anchor_fee_schedules (
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    sep TEXT NOT NULL, -- 'sep6' or 'sep24'
+    operation TEXT NOT NULL, -- 'deposit' or 'withdraw'
+    asset_code TEXT NOT NULL,
+    transfer_server TEXT NOT NULL,
+    enabled INTEGER NOT NULL,
+    fee_fixed REAL,
+    fee_percent REAL,
+    fee_minimum REAL,
+    min_amount REAL,
+    max_amount REAL,
+    fee_endpoint INTEGER NOT NULL DEFAULT 0, -- fees are only quoted by the /fee endpoint
+    collected_at TEXT NOT NULL,
+    PRIMARY KEY (anchor_id, sep, operation, asset_code)
+);
+
+CREATE INDEX idx_fee_schedules_asset ON anchor_fee_schedules(asset_code, operation);
+
+-- One row each time an anchor's fee terms for an asset first appear or change;
+-- a disappearing asset is recorded as disabled with no terms
+CREATE TABLE IF NOT EXISTS anchor_fee_schedule_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    sep TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    asset_code TEXT NOT NULL,
+    enabled INTEGER NOT NULL,
+    fee_fixed REAL,
+    fee_percent REAL,
+    fee_minimum REAL,
+    min_amount REAL,
+    max_amount REAL,
+    fee_endpoint INTEGER NOT NULL DEFAULT 0,
+    observed_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_fee_history_anchor ON anchor_fee_schedule_history(anchor_id, asset_code, observed_at);
@@ -0,0 +1,50 @@
+-- Accounts whose holdings of an issued asset are not in circulation (anchor
+-- treasuries and distribution accounts), in addition to the anchor's own account
+CREATE TABLE IF NOT EXISTS asset_distribution_accounts (
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    account TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (asset_code, asset_issuer, account),
+    FOREIGN KEY (asset_code, asset_issuer) REFERENCES assets(asset_code, asset_issuer) ON DELETE CASCADE
+);
+
+-- Latest supply of each tracked asset
+CREATE TABLE IF NOT EXISTS asset_supply (
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    total_supply REAL NOT NULL,
+    distribution_balance REAL NOT NULL,
+    circulating_supply REAL NOT NULL,
+    payments_cursor TEXT, -- paging token of the last issuer payment classified
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (asset_code, asset_issuer)
+);
+
+-- Supply observed on each sync, for daily deltas
+CREATE TABLE IF NOT EXISTS asset_supply_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    total_supply REAL NOT NULL,
+    distribution_balance REAL NOT NULL,
+    circulating_supply REAL NOT NULL,
+    snapshot_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_asset_supply_snapshots_asset ON asset_supply_snapshots(asset_code, asset_issuer, snapshot_at);
+
+-- Payments from the issuer (mint) or back to it (burn)
+CREATE TABLE IF NOT EXISTS asset_supply_events (
+    id TEXT PRIMARY KEY, -- Horizon operation id
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    kind TEXT NOT NULL, -- 'mint' or 'burn'
+    amount REAL NOT NULL,
+    counterparty TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    occurred_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_asset_supply_events_asset ON asset_supply_events(asset_code, asset_issuer, occurred_at);
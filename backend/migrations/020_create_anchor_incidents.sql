@@ -0,0 +1,31 @@
+-- A stretch of time an anchor spent outside green, from the transition that
+-- left green until the one that returned to it
+CREATE TABLE IF NOT EXISTS anchor_incidents (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    worst_status TEXT NOT NULL, -- 'yellow' or 'red'
+    causes TEXT NOT NULL, -- JSON array of causes seen during the incident
+    started_at TEXT NOT NULL,
+    ended_at TEXT -- NULL while the incident is open
+);
+
+CREATE INDEX idx_anchor_incidents_anchor ON anchor_incidents(anchor_id, started_at DESC);
+CREATE INDEX idx_anchor_incidents_started ON anchor_incidents(started_at DESC);
+
+-- Every change of an anchor's status with the measurements that caused it
+CREATE TABLE IF NOT EXISTS anchor_status_transitions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    anchor_id TEXT NOT NULL REFERENCES anchors(id) ON DELETE CASCADE,
+    incident_id INTEGER REFERENCES anchor_incidents(id) ON DELETE SET NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    causes TEXT NOT NULL, -- JSON array: success_rate_drop, endpoint_downtime, volume_collapse, recovery
+    success_rate REAL NOT NULL,
+    failure_rate REAL NOT NULL,
+    uptime_percent REAL,
+    volume_usd REAL,
+    transitioned_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_status_transitions_anchor ON anchor_status_transitions(anchor_id, transitioned_at DESC);
+CREATE INDEX idx_status_transitions_incident ON anchor_status_transitions(incident_id);
@@ -0,0 +1,40 @@
+-- A SEP-24 or SEP-31 transaction initiated through the backend's proxies,
+-- followed until the anchor reports a terminal status
+CREATE TABLE IF NOT EXISTS anchor_transfers (
+    id TEXT PRIMARY KEY,
+    anchor_id TEXT REFERENCES anchors(id) ON DELETE SET NULL, -- NULL if no known anchor serves transfer_server
+    sep TEXT NOT NULL, -- 'sep24' or 'sep31'
+    kind TEXT NOT NULL, -- 'deposit', 'withdrawal' or 'send'
+    transfer_server TEXT NOT NULL,
+    anchor_transaction_id TEXT NOT NULL, -- id assigned by the anchor
+    home_domain TEXT, -- for SEP-10 authentication with the backend's key while polling
+    owner TEXT, -- signed-in user who initiated the transfer
+    caller_token INTEGER NOT NULL DEFAULT 0, -- initiated with the caller's JWT; followed through callbacks only
+    callback_token TEXT NOT NULL, -- secret part of the callback URL given to the anchor
+    asset_code TEXT,
+    amount TEXT,
+    status TEXT NOT NULL,
+    terminal INTEGER NOT NULL DEFAULT 0, -- status is final; no longer polled
+    poll_error TEXT, -- error of the latest poll, NULL after a successful one
+    last_polled_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    completed_at TEXT, -- when a terminal status was reached
+    UNIQUE (transfer_server, anchor_transaction_id)
+);
+
+CREATE INDEX idx_anchor_transfers_anchor ON anchor_transfers(anchor_id, created_at DESC);
+CREATE INDEX idx_anchor_transfers_open ON anchor_transfers(terminal, created_at);
+CREATE INDEX idx_anchor_transfers_owner ON anchor_transfers(owner, created_at DESC);
+
+-- Every status a transfer passed through, as seen by polling or callbacks
+CREATE TABLE IF NOT EXISTS anchor_transfer_status_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transfer_id TEXT NOT NULL REFERENCES anchor_transfers(id) ON DELETE CASCADE,
+    from_status TEXT, -- NULL for the status the transfer was initiated with
+    to_status TEXT NOT NULL,
+    source TEXT NOT NULL, -- 'initiated', 'poll' or 'callback'
+    changed_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_transfer_status_changes_transfer ON anchor_transfer_status_changes(transfer_id, changed_at);
@@ -0,0 +1,8 @@
+-- How the SEP-6/SEP-24/SEP-31 proxies may reach an anchor. An enabled anchor
+-- is proxied through its configured or discovered SEP endpoints.
+ALTER TABLE anchors ADD COLUMN proxy_enabled INTEGER NOT NULL DEFAULT 0;
+-- 'none': only a caller's jwt is sent; 'optional': a caller's jwt, or a SEP-10 token for
+-- the request's home_domain; 'sep10': every request carries a token, obtained
+-- for the anchor's home domain unless the caller sends a jwt
+ALTER TABLE anchors ADD COLUMN proxy_auth TEXT NOT NULL DEFAULT 'optional';
+ALTER TABLE anchors ADD COLUMN proxy_timeout_secs INTEGER; -- NULL uses the proxy default
@@ -0,0 +1,3 @@
+-- Root of the Merkle tree over a snapshot's anchor and corridor metrics. This
+-- is the value submitted on-chain; `hash` remains the SHA-256 of `data`.
+ALTER TABLE snapshots ADD COLUMN merkle_root TEXT;
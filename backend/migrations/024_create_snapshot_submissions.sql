@@ -0,0 +1,20 @@
+-- On-chain submission of each snapshot epoch: 'generated' -> 'submitted' ->
+-- 'confirmed', or 'failed' until a later attempt succeeds
+CREATE TABLE IF NOT EXISTS snapshot_submissions (
+    epoch INTEGER PRIMARY KEY,
+    snapshot_id TEXT NOT NULL, -- snapshots.id of the submitted snapshot
+    merkle_root TEXT NOT NULL, -- hex root submitted to the contract
+    status TEXT NOT NULL, -- 'generated', 'submitted', 'confirmed' or 'failed'
+    attempts INTEGER NOT NULL DEFAULT 0, -- failed submissions so far
+    transaction_hash TEXT,
+    ledger INTEGER,
+    contract_timestamp INTEGER, -- timestamp returned by the contract
+    last_error TEXT, -- error of the latest attempt, NULL after a successful one
+    reconciliation TEXT, -- outcome of the latest comparison with the contract, e.g. 'verified' or 'root_mismatch'
+    on_chain_root TEXT, -- root the contract held at the latest comparison
+    reconciled_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_snapshot_submissions_status ON snapshot_submissions(status, epoch);
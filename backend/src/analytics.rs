@@ -1,4 +1,9 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
 use crate::models::{AnchorMetrics, AnchorStatus};
+use crate::scoring::{ScoreBreakdown, ScoringEngine, ANCHOR_RELIABILITY_MODEL};
 
 pub mod corridor;
 
@@ -24,6 +29,8 @@ pub struct AnchorReliabilityScore {
     pub total_assets: usize,
     pub total_volume_usd: f64,
     pub weighted_success_rate: f64,
+    /// Model, inputs and per-component contributions behind `composite_score`
+    pub breakdown: Option<ScoreBreakdown>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
@@ -33,6 +40,24 @@ pub fn compute_anchor_metrics(
     successful_transactions: i64,
     failed_transactions: i64,
     avg_settlement_time_ms: Option<i32>,
+) -> AnchorMetrics {
+    compute_anchor_metrics_with_uptime(
+        total_transactions,
+        successful_transactions,
+        failed_transactions,
+        avg_settlement_time_ms,
+        None,
+    )
+}
+
+/// Compute anchor reliability metrics from transaction data and the uptime
+/// percentage of the anchor's probed SEP endpoints, if any were probed
+pub fn compute_anchor_metrics_with_uptime(
+    total_transactions: i64,
+    successful_transactions: i64,
+    failed_transactions: i64,
+    avg_settlement_time_ms: Option<i32>,
+    uptime: Option<f64>,
 ) -> AnchorMetrics {
     if total_transactions == 0 {
         return AnchorMetrics {
@@ -57,10 +82,14 @@ pub fn compute_anchor_metrics(
     // Compute reliability score (0-100)
     // Formula: (success_rate * 0.5) + (settlement_time_score * 0.25) + (volume_consistency * 0.25)
     // For MVP, we'll use a simplified formula focused on success rate and settlement time
+    // Endpoint uptime takes a share of the success rate and settlement weights when known
     let settlement_time_score = calculate_settlement_time_score(avg_settlement_time_ms);
-    let reliability_score = (success_rate * 0.7) + (settlement_time_score * 0.3);
+    let reliability_score = match uptime {
+        Some(uptime) => (success_rate * 0.6) + (settlement_time_score * 0.2) + (uptime * 0.2),
+        None => (success_rate * 0.7) + (settlement_time_score * 0.3),
+    };
 
-    let status = AnchorStatus::from_metrics(success_rate, failure_rate);
+    let status = AnchorStatus::from_metrics(success_rate, failure_rate, uptime);
 
     AnchorMetrics {
         success_rate,
@@ -104,6 +133,9 @@ pub fn count_assets_per_anchor(assets: &[String]) -> usize {
 /// - Volume (logarithmically scaled)
 /// - Asset diversity
 ///
+/// Uses the built-in `anchor_reliability` scoring model; see
+/// [`compute_anchor_reliability_score_with`] to score with a configured engine.
+///
 /// # Arguments
 /// * `asset_performances` - Slice of asset performance metrics for the anchor
 /// * `network_max_volume` - Maximum volume across all anchors in the network for normalization
@@ -114,9 +146,28 @@ pub fn compute_anchor_reliability_score(
     asset_performances: &[AnchorAssetPerformance],
     network_max_volume: f64,
 ) -> AnchorReliabilityScore {
+    compute_anchor_reliability_score_with(
+        &ScoringEngine::default(),
+        asset_performances,
+        network_max_volume,
+    )
+    .expect("built-in anchor reliability model scores its own inputs")
+}
+
+/// Compute an anchor reliability score with the active `anchor_reliability`
+/// model of `engine`.
+///
+/// Fails if the configured model needs inputs other than
+/// `weighted_success_rate`, `total_volume_usd`, `network_max_volume_usd`
+/// and `total_assets`.
+pub fn compute_anchor_reliability_score_with(
+    engine: &ScoringEngine,
+    asset_performances: &[AnchorAssetPerformance],
+    network_max_volume: f64,
+) -> Result<AnchorReliabilityScore> {
     // Handle empty asset list
     if asset_performances.is_empty() {
-        return AnchorReliabilityScore {
+        return Ok(AnchorReliabilityScore {
             anchor_address: String::new(),
             composite_score: 0.0,
             asset_performance_score: 0.0,
@@ -125,8 +176,9 @@ pub fn compute_anchor_reliability_score(
             total_assets: 0,
             total_volume_usd: 0.0,
             weighted_success_rate: 0.0,
+            breakdown: None,
             timestamp: chrono::Utc::now(),
-        };
+        });
     }
 
     // Calculate total volume and weighted success rate
@@ -144,53 +196,41 @@ pub fn compute_anchor_reliability_score(
         }
     }
 
-    // 1. Calculate weighted_success_rate
     let weighted_success_rate = if total_volume_usd > 0.0 {
         weighted_success_sum / total_volume_usd
     } else {
         0.0
     };
-
-    // 2. Calculate asset_performance_score (0-100)
-    // Use weighted_success_rate directly as percentage
-    let asset_performance_score = weighted_success_rate;
-
-    // 3. Calculate volume_score (0-100)
-    // Logarithmic scale to handle wide range of volumes
-    let volume_score = if network_max_volume > 0.0 {
-        let log_volume = (total_volume_usd + 1.0).log10();
-        let log_max = (network_max_volume + 1.0).log10();
-        (log_volume / log_max) * 100.0
-    } else {
-        // If network_max_volume is 0, use a simpler approach
-        if total_volume_usd > 0.0 {
-            50.0 // Default middle score if we have volume but no comparison
-        } else {
-            0.0
-        }
-    };
-
-    // 4. Calculate asset_diversity_score (0-100)
-    // Rewards anchors with up to 10 assets, caps at 100
     let total_assets = asset_performances.len();
-    let asset_diversity_score = ((total_assets as f64 / 10.0).min(1.0)) * 100.0;
 
-    // 5. Calculate composite_score
-    // Weights: 60% performance, 30% volume, 10% diversity
-    let composite_score =
-        (0.6 * asset_performance_score) + (0.3 * volume_score) + (0.1 * asset_diversity_score);
+    let inputs = BTreeMap::from([
+        ("weighted_success_rate".to_string(), weighted_success_rate),
+        ("total_volume_usd".to_string(), total_volume_usd),
+        ("network_max_volume_usd".to_string(), network_max_volume),
+        ("total_assets".to_string(), total_assets as f64),
+    ]);
+    let breakdown = engine.score(ANCHOR_RELIABILITY_MODEL, None, &inputs)?;
+
+    let component_score = |input: &str| {
+        breakdown
+            .components
+            .iter()
+            .find(|c| c.input == input)
+            .map_or(0.0, |c| c.normalized_score)
+    };
 
-    AnchorReliabilityScore {
+    Ok(AnchorReliabilityScore {
         anchor_address: String::new(), // Caller will set this
-        composite_score,
-        asset_performance_score,
-        volume_score,
-        asset_diversity_score,
+        composite_score: breakdown.score,
+        asset_performance_score: component_score("weighted_success_rate"),
+        volume_score: component_score("total_volume_usd"),
+        asset_diversity_score: component_score("total_assets"),
         total_assets,
         total_volume_usd,
         weighted_success_rate,
+        breakdown: Some(breakdown),
         timestamp: chrono::Utc::now(),
-    }
+    })
 }
 
 #[cfg(test)]
@@ -238,6 +278,21 @@ mod tests {
         assert_eq!(metrics.status, AnchorStatus::Red);
     }
 
+    #[test]
+    fn test_compute_anchor_metrics_with_uptime() {
+        let unprobed = compute_anchor_metrics(1000, 995, 5, Some(2000));
+        let up = compute_anchor_metrics_with_uptime(1000, 995, 5, Some(2000), Some(100.0));
+        assert_eq!(up.status, AnchorStatus::Green);
+        assert!(up.reliability_score > unprobed.reliability_score);
+
+        // Flaky endpoints downgrade an anchor whose payments are healthy
+        let flaky = compute_anchor_metrics_with_uptime(1000, 995, 5, Some(2000), Some(97.0));
+        assert_eq!(flaky.status, AnchorStatus::Yellow);
+        let down = compute_anchor_metrics_with_uptime(1000, 995, 5, Some(2000), Some(50.0));
+        assert_eq!(down.status, AnchorStatus::Red);
+        assert!(down.reliability_score < unprobed.reliability_score);
+    }
+
     #[test]
     fn test_settlement_time_score_fast() {
         let score = calculate_settlement_time_score(Some(500));
@@ -3,20 +3,18 @@ use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
 pub fn compute_corridor_analytics(payments: &[PaymentRecord]) -> Vec<CorridorAnalytics> {
-    let mut corridor_payments: HashMap<String, Vec<&PaymentRecord>> = HashMap::new();
+    let mut corridor_payments: HashMap<Corridor, Vec<&PaymentRecord>> = HashMap::new();
 
     for payment in payments {
-        let corridor = payment.get_corridor();
-        let corridor_key = corridor.to_string_key();
         corridor_payments
-            .entry(corridor_key)
+            .entry(payment.get_corridor())
             .or_default()
             .push(payment);
     }
 
     let mut analytics = Vec::new();
 
-    for (corridor_key, corridor_payment_records) in corridor_payments {
+    for (corridor, corridor_payment_records) in corridor_payments {
         let total_transactions = corridor_payment_records.len() as i64;
         let successful_transactions = corridor_payment_records
             .iter()
@@ -32,8 +30,6 @@ pub fn compute_corridor_analytics(payments: &[PaymentRecord]) -> Vec<CorridorAna
 
         let volume_usd: f64 = corridor_payment_records.iter().map(|p| p.amount).sum();
 
-        let corridor = parse_corridor_key(&corridor_key);
-
         analytics.push(CorridorAnalytics {
             corridor,
             success_rate,
@@ -94,19 +90,6 @@ pub fn get_corridors_by_success_rate(
         .collect()
 }
 
-fn parse_corridor_key(corridor_key: &str) -> Corridor {
-    let parts: Vec<&str> = corridor_key.split("->").collect();
-    let asset_a_parts: Vec<&str> = parts[0].split(':').collect();
-    let asset_b_parts: Vec<&str> = parts[1].split(':').collect();
-
-    Corridor::new(
-        asset_a_parts[0].to_string(),
-        asset_a_parts[1].to_string(),
-        asset_b_parts[0].to_string(),
-        asset_b_parts[1].to_string(),
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
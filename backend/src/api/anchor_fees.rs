@@ -0,0 +1,199 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::services::anchor_fees::{
+    AnchorFeeCollector, AnchorFeeSchedule, FeeOperation, FeeQuote, FeeScheduleChange,
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeeComparisonQuery {
+    /// Asset code as listed in the anchors' `/info` (e.g., USDC)
+    #[param(example = "USDC")]
+    pub asset_code: String,
+    /// deposit or withdraw
+    #[param(example = "deposit")]
+    pub operation: String,
+    /// Amount to move, in units of the asset
+    #[param(example = 100.0)]
+    pub amount: f64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeeHistoryQuery {
+    /// Only changes for this asset code
+    pub asset_code: Option<String>,
+    /// Only changes for this operation (deposit or withdraw)
+    pub operation: Option<String>,
+    /// Maximum number of changes to return (default: 100)
+    #[serde(default = "default_limit")]
+    #[param(example = 100)]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeeComparisonResponse {
+    pub asset_code: String,
+    pub operation: String,
+    pub amount: f64,
+    /// Cost at each anchor, cheapest first; anchors that cannot quote follow
+    #[schema(value_type = Vec<Object>)]
+    pub quotes: Vec<FeeQuote>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnchorFeesResponse {
+    pub anchor_id: String,
+    /// Current fee terms per asset and operation
+    #[schema(value_type = Vec<Object>)]
+    pub schedules: Vec<AnchorFeeSchedule>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeeHistoryResponse {
+    pub anchor_id: String,
+    /// Fee changes ordered newest first
+    #[schema(value_type = Vec<Object>)]
+    pub changes: Vec<FeeScheduleChange>,
+}
+
+fn parse_operation(operation: &str) -> ApiResult<FeeOperation> {
+    operation.parse().map_err(ApiError::BadRequest)
+}
+
+/// Compare the cost of a deposit or withdrawal across anchors
+///
+/// Applies each anchor's published SEP-6/24 fee terms to the amount. Anchors
+/// that only quote fees through their `/fee` endpoint are asked live.
+///
+/// **DATA SOURCE: Database** (populated from anchors' `/info` endpoints)
+#[utoipa::path(
+    get,
+    path = "/api/fees/compare",
+    params(FeeComparisonQuery),
+    responses(
+        (status = 200, description = "Fees compared successfully", body = FeeComparisonResponse),
+        (status = 400, description = "Invalid operation or amount"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Fees"
+)]
+pub async fn compare_fees(
+    State(collector): State<Arc<AnchorFeeCollector>>,
+    Query(params): Query<FeeComparisonQuery>,
+) -> ApiResult<Json<FeeComparisonResponse>> {
+    let operation = parse_operation(&params.operation)?;
+    if !params.amount.is_finite() || params.amount <= 0.0 {
+        return Err(ApiError::BadRequest(
+            "amount must be greater than zero".to_string(),
+        ));
+    }
+    let asset_code = params.asset_code.trim();
+    if asset_code.is_empty() {
+        return Err(ApiError::BadRequest("asset_code is required".to_string()));
+    }
+
+    let quotes = collector
+        .compare(asset_code, operation, params.amount)
+        .await?;
+
+    Ok(Json(FeeComparisonResponse {
+        asset_code: asset_code.to_string(),
+        operation: operation.to_string(),
+        amount: params.amount,
+        quotes,
+    }))
+}
+
+/// Get an anchor's current fee schedule
+///
+/// **DATA SOURCE: Database** (populated from the anchor's `/info` endpoints)
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/fees",
+    params(
+        ("id" = String, Path, description = "Anchor ID")
+    ),
+    responses(
+        (status = 200, description = "Fee schedule retrieved successfully", body = AnchorFeesResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Fees"
+)]
+pub async fn get_anchor_fees(
+    State(collector): State<Arc<AnchorFeeCollector>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<AnchorFeesResponse>> {
+    let schedules = collector.get_schedules(&id).await?;
+
+    Ok(Json(AnchorFeesResponse {
+        anchor_id: id,
+        schedules,
+    }))
+}
+
+/// Get the history of an anchor's fee changes
+///
+/// Each entry holds the terms as first observed or after a change; assets
+/// that stopped being listed appear as disabled.
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/fees/history",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        FeeHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Fee history retrieved successfully", body = FeeHistoryResponse),
+        (status = 400, description = "Invalid operation"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Fees"
+)]
+pub async fn get_anchor_fee_history(
+    State(collector): State<Arc<AnchorFeeCollector>>,
+    Path(id): Path<String>,
+    Query(params): Query<FeeHistoryQuery>,
+) -> ApiResult<Json<FeeHistoryResponse>> {
+    let operation = params
+        .operation
+        .as_deref()
+        .map(parse_operation)
+        .transpose()?;
+    let changes = collector
+        .get_history(
+            &id,
+            params.asset_code.as_deref(),
+            operation,
+            params.limit.clamp(1, 1000),
+        )
+        .await?;
+
+    Ok(Json(FeeHistoryResponse {
+        anchor_id: id,
+        changes,
+    }))
+}
+
+/// Create fee routes
+pub fn routes(collector: Arc<AnchorFeeCollector>) -> Router {
+    Router::new()
+        .route("/api/fees/compare", get(compare_fees))
+        .route("/api/anchors/:id/fees", get(get_anchor_fees))
+        .route("/api/anchors/:id/fees/history", get(get_anchor_fee_history))
+        .with_state(collector)
+}
@@ -0,0 +1,137 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::services::anchor_registry::{
+    AnchorProxyConfig, AnchorRegistry, ProxyAuth, MAX_PROXY_TIMEOUT_SECS,
+};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnchorProxyResponse {
+    pub anchor_id: String,
+    /// Whether the SEP-6/SEP-24/SEP-31 proxies may call the anchor
+    pub enabled: bool,
+    /// Authentication towards the anchor (none, optional or sep10)
+    pub auth: String,
+    /// Timeout of proxied requests; null uses the proxy default
+    pub timeout_secs: Option<i64>,
+}
+
+impl From<AnchorProxyConfig> for AnchorProxyResponse {
+    fn from(config: AnchorProxyConfig) -> Self {
+        Self {
+            anchor_id: config.anchor_id,
+            enabled: config.enabled,
+            auth: config.auth.to_string(),
+            timeout_secs: config.timeout_secs,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetAnchorProxyRequest {
+    pub enabled: bool,
+    /// none, optional (default) or sep10
+    pub auth: Option<String>,
+    /// Timeout of proxied requests in seconds (1-120); omit for the default
+    pub timeout_secs: Option<i64>,
+}
+
+fn not_found(id: &str) -> ApiError {
+    ApiError::NotFound(format!("Anchor with id {} not found", id))
+}
+
+/// Get how the SEP proxies may reach an anchor
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/proxy",
+    params(
+        ("id" = String, Path, description = "Anchor ID")
+    ),
+    responses(
+        (status = 200, description = "Proxy settings retrieved successfully", body = AnchorProxyResponse),
+        (status = 404, description = "Anchor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn get_anchor_proxy(
+    State(registry): State<Arc<AnchorRegistry>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<AnchorProxyResponse>> {
+    let config = registry
+        .get_config(&id)
+        .await?
+        .ok_or_else(|| not_found(&id))?;
+
+    Ok(Json(config.into()))
+}
+
+/// Configure how the SEP proxies may reach an anchor
+///
+/// An enabled anchor is proxied through its configured or discovered SEP-6,
+/// SEP-24 and SEP-31 endpoints. Once any anchor is enabled for a SEP, the
+/// proxy only calls enabled anchors and `SEP*_ALLOWED_ORIGINS`. Changes apply
+/// to the next proxied request.
+#[utoipa::path(
+    put,
+    path = "/api/anchors/{id}/proxy",
+    params(
+        ("id" = String, Path, description = "Anchor ID")
+    ),
+    request_body = SetAnchorProxyRequest,
+    responses(
+        (status = 200, description = "Proxy settings updated", body = AnchorProxyResponse),
+        (status = 400, description = "Invalid auth or timeout"),
+        (status = 404, description = "Anchor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn set_anchor_proxy(
+    State(registry): State<Arc<AnchorRegistry>>,
+    Path(id): Path<String>,
+    Json(req): Json<SetAnchorProxyRequest>,
+) -> ApiResult<Json<AnchorProxyResponse>> {
+    let auth = match req.auth.as_deref() {
+        Some(auth) => auth.parse::<ProxyAuth>().map_err(ApiError::BadRequest)?,
+        None => ProxyAuth::default(),
+    };
+    if let Some(secs) = req.timeout_secs {
+        if !(1..=MAX_PROXY_TIMEOUT_SECS).contains(&secs) {
+            return Err(ApiError::BadRequest(format!(
+                "timeout_secs must be between 1 and {}",
+                MAX_PROXY_TIMEOUT_SECS
+            )));
+        }
+    }
+
+    let config = registry
+        .set_config(&id, req.enabled, auth, req.timeout_secs)
+        .await?
+        .ok_or_else(|| not_found(&id))?;
+
+    Ok(Json(config.into()))
+}
+
+/// Create anchor proxy settings routes
+pub fn routes(registry: Arc<AnchorRegistry>) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/proxy", get(get_anchor_proxy))
+        .with_state(registry)
+}
+
+/// Create anchor proxy settings routes that require authentication
+pub fn protected_routes(registry: Arc<AnchorRegistry>) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/proxy", put(set_anchor_proxy))
+        .with_state(registry)
+}
@@ -5,14 +5,20 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
 use utoipa::{IntoParams, ToSchema};
 
-use crate::cache::{keys, CacheManager};
+use crate::analytics::{
+    compute_anchor_reliability_score, compute_anchor_reliability_score_with, AnchorAssetPerformance,
+};
+use crate::cache::keys;
 use crate::cache_middleware::CacheAware;
-use crate::database::Database;
-use crate::rpc::StellarRpcClient;
+use crate::models::asset::AssetId;
+use crate::models::Anchor;
+use crate::rpc::Payment;
+use crate::scoring::{ScoreBreakdown, ScoringEngine};
 use crate::services::price_feed::PriceFeedClient;
+use crate::state::CachedState;
 
 pub type ApiResult<T> = Result<T, ApiError>;
 
@@ -78,6 +84,10 @@ pub struct AnchorMetricsResponse {
     /// Reliability score (0-100)
     #[schema(example = 99.5)]
     pub reliability_score: f64,
+    /// Scoring model, inputs and per-component contributions behind `reliability_score`
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub reliability_breakdown: Option<ScoreBreakdown>,
     /// Number of assets supported
     #[schema(example = 5)]
     pub asset_coverage: usize,
@@ -126,21 +136,23 @@ pub struct AnchorsResponse {
     tag = "Anchors"
 )]
 pub async fn get_anchors(
-    State((db, cache, rpc_client, _price_feed)): State<(
-        Arc<Database>,
-        Arc<CacheManager>,
-        Arc<StellarRpcClient>,
-        Arc<PriceFeedClient>,
-    )>,
+    State(state): State<CachedState>,
     Query(params): Query<ListAnchorsQuery>,
 ) -> ApiResult<Json<AnchorsResponse>> {
+    let CachedState {
+        db,
+        cache,
+        rpc_client,
+        price_feed,
+        scoring,
+    } = state;
     let cache_key = keys::anchor_list(params.limit, params.offset);
 
     let response = <()>::get_or_fetch(&cache, &cache_key, cache.config.get_ttl("anchor"), async {
         // Get anchor metadata from database (names, accounts, etc.)
         let anchors = db.list_anchors(params.limit, params.offset).await?;
 
-        let mut anchor_responses = Vec::new();
+        let mut activities = Vec::new();
 
         for anchor in anchors {
             let anchor_id = uuid::Uuid::parse_str(&anchor.id).unwrap_or_else(|_| uuid::Uuid::nil());
@@ -166,59 +178,40 @@ pub async fn get_anchors(
             };
 
             // Calculate metrics from RPC payment data
-            let (total_transactions, successful_transactions, failed_transactions) =
-                if !payments.is_empty() {
-                    let total = payments.len() as i64;
-                    // In Stellar, if a payment appears in the ledger, it was successful
-                    // Failed payments don't appear in the payment stream
-                    let successful = total;
-                    let failed = 0;
-                    (total, successful, failed)
-                } else {
-                    // Fallback to database values
-                    (
-                        anchor.total_transactions,
-                        anchor.successful_transactions,
-                        anchor.failed_transactions,
-                    )
-                };
-
-            let failure_rate = if total_transactions > 0 {
-                (failed_transactions as f64 / total_transactions as f64) * 100.0
-            } else {
-                0.0
-            };
-
-            let reliability_score = if total_transactions > 0 {
-                (successful_transactions as f64 / total_transactions as f64) * 100.0
+            let performances = if !payments.is_empty() {
+                // In Stellar, if a payment appears in the ledger, it was successful
+                // Failed payments don't appear in the payment stream
+                asset_performances(&payments, &price_feed).await
             } else {
-                anchor.reliability_score
+                // Fallback to database values
+                vec![AnchorAssetPerformance {
+                    asset_code: String::new(),
+                    asset_issuer: anchor.stellar_account.clone(),
+                    total_transactions: anchor.total_transactions,
+                    successful_transactions: anchor.successful_transactions,
+                    failed_transactions: anchor.failed_transactions,
+                    total_volume_usd: anchor.total_volume_usd,
+                }]
             };
 
-            let status = if reliability_score >= 99.0 {
-                "green".to_string()
-            } else if reliability_score >= 95.0 {
-                "yellow".to_string()
-            } else {
-                "red".to_string()
-            };
-
-            let anchor_response = AnchorMetricsResponse {
-                id: anchor.id.to_string(),
-                name: anchor.name,
-                stellar_account: anchor.stellar_account,
-                reliability_score,
+            activities.push(AnchorActivity {
+                anchor,
                 asset_coverage: assets.len(),
-                failure_rate,
-                total_transactions,
-                successful_transactions,
-                failed_transactions,
-                status,
-            };
-
-            anchor_responses.push(anchor_response);
+                performances,
+            });
         }
 
+        // Volume is scored relative to the busiest anchor on the page
+        let network_max_volume = activities
+            .iter()
+            .map(AnchorActivity::volume_usd)
+            .fold(0.0, f64::max);
+
+        let anchor_responses = activities
+            .into_iter()
+            .map(|activity| activity.into_response(&scoring, network_max_volume))
+            .collect::<Vec<_>>();
+
         let total = anchor_responses.len();
 
         Ok(AnchorsResponse {
@@ -231,6 +224,135 @@ pub async fn get_anchors(
     Ok(Json(response))
 }
 
+/// An anchor with the per-asset activity its score is computed from
+struct AnchorActivity {
+    anchor: Anchor,
+    asset_coverage: usize,
+    performances: Vec<AnchorAssetPerformance>,
+}
+
+impl AnchorActivity {
+    fn volume_usd(&self) -> f64 {
+        self.performances.iter().map(|p| p.total_volume_usd).sum()
+    }
+
+    fn into_response(
+        self,
+        scoring: &ScoringEngine,
+        network_max_volume: f64,
+    ) -> AnchorMetricsResponse {
+        let total_transactions: i64 = self.performances.iter().map(|p| p.total_transactions).sum();
+        let successful_transactions: i64 = self
+            .performances
+            .iter()
+            .map(|p| p.successful_transactions)
+            .sum();
+        let failed_transactions: i64 = self
+            .performances
+            .iter()
+            .map(|p| p.failed_transactions)
+            .sum();
+
+        let (failure_rate, success_rate) = if total_transactions > 0 {
+            (
+                (failed_transactions as f64 / total_transactions as f64) * 100.0,
+                (successful_transactions as f64 / total_transactions as f64) * 100.0,
+            )
+        } else {
+            (0.0, self.anchor.reliability_score)
+        };
+
+        let (reliability_score, reliability_breakdown) = if total_transactions > 0 {
+            let score = compute_anchor_reliability_score_with(
+                scoring,
+                &self.performances,
+                network_max_volume,
+            )
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Configured anchor reliability model failed, using built-in: {}",
+                    e
+                );
+                compute_anchor_reliability_score(&self.performances, network_max_volume)
+            });
+            (score.composite_score, score.breakdown)
+        } else {
+            (self.anchor.reliability_score, None)
+        };
+
+        let status = if success_rate >= 99.0 {
+            "green".to_string()
+        } else if success_rate >= 95.0 {
+            "yellow".to_string()
+        } else {
+            "red".to_string()
+        };
+
+        AnchorMetricsResponse {
+            id: self.anchor.id.to_string(),
+            name: self.anchor.name,
+            stellar_account: self.anchor.stellar_account,
+            reliability_score,
+            reliability_breakdown,
+            asset_coverage: self.asset_coverage,
+            failure_rate,
+            total_transactions,
+            successful_transactions,
+            failed_transactions,
+            status,
+        }
+    }
+}
+
+/// Group payments by asset, valuing each asset's volume at its USD price
+async fn asset_performances(
+    payments: &[Payment],
+    price_feed: &PriceFeedClient,
+) -> Vec<AnchorAssetPerformance> {
+    let mut by_asset: HashMap<String, (AnchorAssetPerformance, f64)> = HashMap::new();
+
+    for payment in payments {
+        let Ok(asset) = AssetId::from_horizon(
+            &payment.asset_type,
+            payment.asset_code.as_deref(),
+            payment.asset_issuer.as_deref(),
+        ) else {
+            continue;
+        };
+        let amount = payment.amount.parse::<f64>().unwrap_or(0.0);
+        let (performance, total_amount) = by_asset.entry(asset.key()).or_insert_with(|| {
+            let (code, issuer) = asset.classic_parts().unwrap_or((asset.code(), ""));
+            (
+                AnchorAssetPerformance {
+                    asset_code: code.to_string(),
+                    asset_issuer: issuer.to_string(),
+                    total_transactions: 0,
+                    successful_transactions: 0,
+                    failed_transactions: 0,
+                    total_volume_usd: 0.0,
+                },
+                0.0,
+            )
+        });
+        performance.total_transactions += 1;
+        performance.successful_transactions += 1;
+        *total_amount += amount;
+    }
+
+    let mut performances = Vec::with_capacity(by_asset.len());
+    for (key, (mut performance, total_amount)) in by_asset {
+        performance.total_volume_usd = match price_feed.get_price(&key).await {
+            Ok(price) => total_amount * price,
+            Err(e) => {
+                tracing::warn!("No USD price for {}: {}. Volume not counted.", key, e);
+                0.0
+            }
+        };
+        performances.push(performance);
+    }
+    performances
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +370,7 @@ mod tests {
             name: "Test Anchor".to_string(),
             stellar_account: "GA123".to_string(),
             reliability_score: 95.5,
+            reliability_breakdown: None,
             asset_coverage: 3,
             failure_rate: 5.0,
             total_transactions: 1000,
@@ -0,0 +1,94 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::ApiResult;
+use crate::models::asset::CorridorKey;
+use crate::services::anomaly_detector::{CorridorAnomaly, CorridorAnomalyDetector};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AnomaliesQuery {
+    /// Look back this many hours (default: 168)
+    #[serde(default = "default_hours")]
+    #[param(example = 168)]
+    pub hours: i64,
+    /// Maximum number of anomalies to return (default: 100)
+    #[serde(default = "default_limit")]
+    #[param(example = 100)]
+    pub limit: i64,
+}
+
+fn default_hours() -> i64 {
+    168
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnomaliesResponse {
+    #[schema(
+        value_type = String,
+        example = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN->XLM:native"
+    )]
+    pub corridor_key: CorridorKey,
+    /// Anomalies ordered newest first
+    #[schema(value_type = Vec<Object>)]
+    pub anomalies: Vec<CorridorAnomaly>,
+    pub total: usize,
+}
+
+/// Get detected anomalies for a corridor
+///
+/// Returns volume, success rate and latency anomalies detected in the
+/// corridor's hourly metrics.
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/anomalies",
+    params(
+        ("corridor_key" = String, Path, description = "Corridor identifier (e.g., USDC:native->XLM:native)"),
+        AnomaliesQuery
+    ),
+    responses(
+        (status = 200, description = "Anomalies retrieved successfully", body = AnomaliesResponse),
+        (status = 400, description = "Invalid corridor key"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Corridors"
+)]
+pub async fn get_corridor_anomalies(
+    State(detector): State<Arc<CorridorAnomalyDetector>>,
+    Path(corridor_key): Path<CorridorKey>,
+    Query(params): Query<AnomaliesQuery>,
+) -> ApiResult<Json<AnomaliesResponse>> {
+    let since = Utc::now() - Duration::hours(params.hours.clamp(1, 24 * 90));
+    let anomalies = detector
+        .get_anomalies(&corridor_key, since, params.limit.clamp(1, 1000))
+        .await?;
+
+    Ok(Json(AnomaliesResponse {
+        corridor_key,
+        total: anomalies.len(),
+        anomalies,
+    }))
+}
+
+/// Create corridor anomaly routes
+pub fn routes(detector: Arc<CorridorAnomalyDetector>) -> Router {
+    Router::new()
+        .route(
+            "/api/corridors/:corridor_key/anomalies",
+            get(get_corridor_anomalies),
+        )
+        .with_state(detector)
+}
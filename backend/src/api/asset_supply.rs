@@ -0,0 +1,322 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use stellar_xdr::curr as xdr;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::models::asset::AssetId;
+use crate::services::asset_supply::{
+    AnchorSupply, AssetSupply, AssetSupplyMonitor, DailySupplyDelta, SupplyEvent, SupplyEventKind,
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SupplyWindowQuery {
+    /// Days of history to cover (default: 30)
+    #[serde(default = "default_days")]
+    #[param(example = 30)]
+    pub days: i64,
+}
+
+fn default_days() -> i64 {
+    30
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SupplyEventsQuery {
+    /// Only mints or only burns
+    pub kind: Option<String>,
+    /// Maximum number of events to return (default: 100)
+    #[serde(default = "default_limit")]
+    #[param(example = 100)]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AssetSupplyResponse {
+    #[schema(value_type = Object)]
+    pub supply: AssetSupply,
+    /// Configured accounts excluded from circulation besides the anchor's own
+    pub distribution_accounts: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailySupplyResponse {
+    pub asset: String,
+    pub days: i64,
+    /// Days with mints, burns or syncs, oldest first
+    #[schema(value_type = Vec<Object>)]
+    pub deltas: Vec<DailySupplyDelta>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SupplyEventsResponse {
+    pub asset: String,
+    /// Mints and burns, newest first
+    #[schema(value_type = Vec<Object>)]
+    pub events: Vec<SupplyEvent>,
+}
+
+/// Code and issuer of an issued asset given as `CODE:ISSUER`
+fn parse_asset(asset: &str) -> ApiResult<(String, String)> {
+    let asset = AssetId::from_str(asset).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    match asset {
+        AssetId::Credit { code, issuer } => Ok((code, issuer)),
+        other => Err(ApiError::BadRequest(format!(
+            "{} is not an issued asset",
+            other
+        ))),
+    }
+}
+
+fn parse_kind(kind: &str) -> ApiResult<SupplyEventKind> {
+    match kind.trim().to_ascii_lowercase().as_str() {
+        "mint" => Ok(SupplyEventKind::Mint),
+        "burn" => Ok(SupplyEventKind::Burn),
+        other => Err(ApiError::BadRequest(format!(
+            "unknown event kind: {} (expected mint or burn)",
+            other
+        ))),
+    }
+}
+
+/// Get the total and circulating supply of an issued asset
+///
+/// Circulating supply excludes holdings of the issuing anchor's account and
+/// of configured distribution accounts.
+///
+/// **DATA SOURCE: Database** (synced from Horizon)
+#[utoipa::path(
+    get,
+    path = "/api/assets/{asset}/supply",
+    params(
+        ("asset" = String, Path, description = "Asset as CODE:ISSUER")
+    ),
+    responses(
+        (status = 200, description = "Supply retrieved successfully", body = AssetSupplyResponse),
+        (status = 400, description = "Invalid asset"),
+        (status = 404, description = "Asset supply not synced"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Assets"
+)]
+pub async fn get_asset_supply(
+    State(monitor): State<Arc<AssetSupplyMonitor>>,
+    Path(asset): Path<String>,
+) -> ApiResult<Json<AssetSupplyResponse>> {
+    let (code, issuer) = parse_asset(&asset)?;
+    let supply = monitor
+        .get_supply(&code, &issuer)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("No supply recorded for {}", asset)))?;
+    let distribution_accounts = monitor.get_distribution_accounts(&code, &issuer).await?;
+
+    Ok(Json(AssetSupplyResponse {
+        supply,
+        distribution_accounts,
+    }))
+}
+
+/// Get daily mints, burns and supply changes of an issued asset
+///
+/// **DATA SOURCE: Database** (synced from Horizon)
+#[utoipa::path(
+    get,
+    path = "/api/assets/{asset}/supply/daily",
+    params(
+        ("asset" = String, Path, description = "Asset as CODE:ISSUER"),
+        SupplyWindowQuery
+    ),
+    responses(
+        (status = 200, description = "Daily supply deltas retrieved successfully", body = DailySupplyResponse),
+        (status = 400, description = "Invalid asset"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Assets"
+)]
+pub async fn get_daily_supply(
+    State(monitor): State<Arc<AssetSupplyMonitor>>,
+    Path(asset): Path<String>,
+    Query(params): Query<SupplyWindowQuery>,
+) -> ApiResult<Json<DailySupplyResponse>> {
+    let (code, issuer) = parse_asset(&asset)?;
+    let days = params.days.clamp(1, 365);
+    let deltas = monitor.get_daily_deltas(&code, &issuer, days).await?;
+
+    Ok(Json(DailySupplyResponse {
+        asset,
+        days,
+        deltas,
+    }))
+}
+
+/// Get mints and burns of an issued asset
+///
+/// Mints are payments out of the issuer account, burns are payments back to it.
+///
+/// **DATA SOURCE: Database** (synced from Horizon)
+#[utoipa::path(
+    get,
+    path = "/api/assets/{asset}/supply/events",
+    params(
+        ("asset" = String, Path, description = "Asset as CODE:ISSUER"),
+        SupplyEventsQuery
+    ),
+    responses(
+        (status = 200, description = "Supply events retrieved successfully", body = SupplyEventsResponse),
+        (status = 400, description = "Invalid asset or event kind"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Assets"
+)]
+pub async fn get_supply_events(
+    State(monitor): State<Arc<AssetSupplyMonitor>>,
+    Path(asset): Path<String>,
+    Query(params): Query<SupplyEventsQuery>,
+) -> ApiResult<Json<SupplyEventsResponse>> {
+    let (code, issuer) = parse_asset(&asset)?;
+    let kind = params.kind.as_deref().map(parse_kind).transpose()?;
+    let events = monitor
+        .get_events(&code, &issuer, kind, params.limit.clamp(1, 1000))
+        .await?;
+
+    Ok(Json(SupplyEventsResponse { asset, events }))
+}
+
+/// Get the supply of every asset an anchor issues
+///
+/// **DATA SOURCE: Database** (synced from Horizon)
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/supply",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        SupplyWindowQuery
+    ),
+    responses(
+        (status = 200, description = "Anchor supply retrieved successfully", body = Object),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn get_anchor_supply(
+    State(monitor): State<Arc<AssetSupplyMonitor>>,
+    Path(id): Path<String>,
+    Query(params): Query<SupplyWindowQuery>,
+) -> ApiResult<Json<AnchorSupply>> {
+    let supply = monitor
+        .get_anchor_supply(&id, params.days.clamp(1, 365))
+        .await?;
+
+    Ok(Json(supply))
+}
+
+/// Exclude an account's holdings from an asset's circulating supply
+#[utoipa::path(
+    put,
+    path = "/api/assets/{asset}/distribution-accounts/{account}",
+    params(
+        ("asset" = String, Path, description = "Asset as CODE:ISSUER"),
+        ("account" = String, Path, description = "Distribution account (G...)")
+    ),
+    responses(
+        (status = 200, description = "Account excluded; returns the configured accounts", body = Vec<String>),
+        (status = 400, description = "Invalid asset or account"),
+        (status = 404, description = "Asset not tracked"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Assets"
+)]
+pub async fn add_distribution_account(
+    State(monitor): State<Arc<AssetSupplyMonitor>>,
+    Path((asset, account)): Path<(String, String)>,
+) -> ApiResult<Json<Vec<String>>> {
+    let (code, issuer) = parse_asset(&asset)?;
+    if xdr::AccountId::from_str(&account).is_err() {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid account: {}",
+            account
+        )));
+    }
+
+    if !monitor
+        .add_distribution_account(&code, &issuer, &account)
+        .await?
+    {
+        return Err(ApiError::NotFound(format!(
+            "Asset {} is not tracked",
+            asset
+        )));
+    }
+
+    Ok(Json(
+        monitor.get_distribution_accounts(&code, &issuer).await?,
+    ))
+}
+
+/// Count an account's holdings of an asset as circulating again
+#[utoipa::path(
+    delete,
+    path = "/api/assets/{asset}/distribution-accounts/{account}",
+    params(
+        ("asset" = String, Path, description = "Asset as CODE:ISSUER"),
+        ("account" = String, Path, description = "Distribution account (G...)")
+    ),
+    responses(
+        (status = 204, description = "Account removed"),
+        (status = 400, description = "Invalid asset"),
+        (status = 404, description = "Account not configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Assets"
+)]
+pub async fn remove_distribution_account(
+    State(monitor): State<Arc<AssetSupplyMonitor>>,
+    Path((asset, account)): Path<(String, String)>,
+) -> ApiResult<StatusCode> {
+    let (code, issuer) = parse_asset(&asset)?;
+    if !monitor
+        .remove_distribution_account(&code, &issuer, &account)
+        .await?
+    {
+        return Err(ApiError::NotFound(format!(
+            "{} is not a distribution account of {}",
+            account, asset
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create asset supply routes
+pub fn routes(monitor: Arc<AssetSupplyMonitor>) -> Router {
+    Router::new()
+        .route("/api/assets/:asset/supply", get(get_asset_supply))
+        .route("/api/assets/:asset/supply/daily", get(get_daily_supply))
+        .route("/api/assets/:asset/supply/events", get(get_supply_events))
+        .route("/api/anchors/:id/supply", get(get_anchor_supply))
+        .with_state(monitor)
+}
+
+/// Create routes that configure distribution accounts; callers add
+/// authentication
+pub fn protected_routes(monitor: Arc<AssetSupplyMonitor>) -> Router {
+    Router::new()
+        .route(
+            "/api/assets/:asset/distribution-accounts/:account",
+            put(add_distribution_account).delete(remove_distribution_account),
+        )
+        .with_state(monitor)
+}
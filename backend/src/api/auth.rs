@@ -1,14 +1,17 @@
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::post,
+    routing::{get, post},
     Json, Router,
 };
+use chrono::Utc;
+use serde::Deserialize;
 use serde_json::json;
 use std::sync::Arc;
 
 use crate::auth::{AuthService, LoginRequest, LogoutRequest, RefreshTokenRequest};
+use crate::services::sep10_server::{Sep10Server, Sep10ServerError};
 
 /// POST /api/auth/login - User login
 pub async fn login(
@@ -53,20 +56,107 @@ pub async fn logout(
     Ok((StatusCode::OK, Json(body)).into_response())
 }
 
+#[derive(Clone)]
+pub struct Sep10AuthState {
+    pub auth_service: Arc<AuthService>,
+    pub server: Arc<Sep10Server>,
+}
+
+/// Challenge request
+#[derive(Debug, Deserialize)]
+pub struct ChallengeQuery {
+    /// Account (`G...`) to authenticate
+    pub account: String,
+    #[serde(default)]
+    pub home_domain: Option<String>,
+}
+
+/// Signed challenge
+#[derive(Debug, Deserialize)]
+pub struct Sep10LoginRequest {
+    pub transaction: String,
+}
+
+/// GET /api/auth/sep10?account=G... - SEP-10 challenge to sign
+pub async fn sep10_challenge(
+    State(state): State<Sep10AuthState>,
+    Query(query): Query<ChallengeQuery>,
+) -> Result<Response, AuthApiError> {
+    let challenge =
+        state
+            .server
+            .challenge(&query.account, query.home_domain.as_deref(), Utc::now())?;
+
+    Ok((StatusCode::OK, Json(challenge)).into_response())
+}
+
+/// POST /api/auth/sep10 - Sign in with a signed SEP-10 challenge
+pub async fn sep10_login(
+    State(state): State<Sep10AuthState>,
+    Json(request): Json<Sep10LoginRequest>,
+) -> Result<Response, AuthApiError> {
+    let account = state
+        .server
+        .verify(&request.transaction, Utc::now())
+        .await?;
+    if !state.auth_service.is_sep10_account_allowed(&account) {
+        return Err(AuthApiError::Unauthorized(
+            "account is not allowed to sign in".to_string(),
+        ));
+    }
+    let response = state
+        .auth_service
+        .login_account(&account)
+        .await
+        .map_err(|e| AuthApiError::Unavailable(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
 /// Auth API errors
 #[derive(Debug)]
 pub enum AuthApiError {
     InvalidCredentials,
     InvalidToken,
+    /// Malformed SEP-10 request or challenge
+    InvalidChallenge(String),
+    /// SEP-10 challenge not signed with enough weight, or an account that
+    /// is not allowed to sign in
+    Unauthorized(String),
+    /// Signers could not be looked up or tokens not issued
+    Unavailable(String),
+}
+
+impl From<Sep10ServerError> for AuthApiError {
+    fn from(error: Sep10ServerError) -> Self {
+        match error {
+            Sep10ServerError::InvalidRequest(msg) => AuthApiError::InvalidChallenge(msg),
+            Sep10ServerError::Unauthorized(msg) => AuthApiError::Unauthorized(msg),
+            Sep10ServerError::Horizon(msg) => AuthApiError::Unavailable(msg),
+        }
+    }
 }
 
 impl IntoResponse for AuthApiError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
-            AuthApiError::InvalidCredentials => {
-                (StatusCode::UNAUTHORIZED, "Invalid username or password")
+            AuthApiError::InvalidCredentials => (
+                StatusCode::UNAUTHORIZED,
+                "Invalid username or password".to_string(),
+            ),
+            AuthApiError::InvalidToken => (
+                StatusCode::UNAUTHORIZED,
+                "Invalid or expired token".to_string(),
+            ),
+            AuthApiError::InvalidChallenge(msg) => (StatusCode::BAD_REQUEST, msg),
+            AuthApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
+            AuthApiError::Unavailable(msg) => {
+                tracing::error!("SEP-10 login failed: {}", msg);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Authentication is temporarily unavailable".to_string(),
+                )
             }
-            AuthApiError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid or expired token"),
         };
 
         let body = json!({
@@ -85,3 +175,13 @@ pub fn routes(auth_service: Arc<AuthService>) -> Router {
         .route("/api/auth/logout", post(logout))
         .with_state(auth_service)
 }
+
+/// Create SEP-10 login routes
+pub fn sep10_routes(auth_service: Arc<AuthService>, server: Arc<Sep10Server>) -> Router {
+    Router::new()
+        .route("/api/auth/sep10", get(sep10_challenge).post(sep10_login))
+        .with_state(Sep10AuthState {
+            auth_service,
+            server,
+        })
+}
@@ -6,8 +6,10 @@ use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::handlers::{ApiError, ApiResult};
+use crate::models::asset::{AssetIdError, CorridorKey};
 use crate::models::corridor::{Corridor, CorridorMetrics};
 use crate::models::SortBy;
+use crate::scoring::{ScoreBreakdown, ScoringEngine};
 use crate::state::AppState;
 
 // Response DTOs matching frontend TypeScript interfaces
@@ -29,6 +31,8 @@ pub struct CorridorResponse {
     pub liquidity_volume_24h_usd: f64,
     pub liquidity_trend: String,
     pub health_score: f64,
+    #[serde(default)]
+    pub health_score_breakdown: Option<ScoreBreakdown>,
     pub last_updated: String,
 }
 
@@ -84,27 +88,21 @@ fn default_limit() -> i64 {
 }
 
 /// Calculate health score based on success rate, volume, and transaction count
-fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
-    let success_weight = 0.6;
-    let volume_weight = 0.2;
-    let transaction_weight = 0.2;
-
-    // Normalize volume and transactions (using logarithmic scale)
-    let volume_score = if volume_usd > 0.0 {
-        ((volume_usd.ln() / 15.0) * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    let transaction_score = if total_transactions > 0 {
-        ((total_transactions as f64).ln() / 10.0 * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    success_rate * success_weight
-        + volume_score * volume_weight
-        + transaction_score * transaction_weight
+/// using the active `corridor_health` scoring model
+fn calculate_health_score(
+    scoring: &ScoringEngine,
+    success_rate: f64,
+    total_transactions: i64,
+    volume_usd: f64,
+) -> ScoreBreakdown {
+    scoring
+        .corridor_health(success_rate, total_transactions, volume_usd)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Configured corridor health model failed, using built-in: {}", e);
+            ScoringEngine::default()
+                .corridor_health(success_rate, total_transactions, volume_usd)
+                .expect("built-in corridor health model scores its own inputs")
+        })
 }
 
 /// Determine liquidity trend (simple heuristic based on recent data)
@@ -220,8 +218,12 @@ pub async fn list_corridors(
     let corridors: Vec<CorridorResponse> = filtered_metrics
         .iter()
         .map(|m| {
-            let health_score =
-                calculate_health_score(m.success_rate, m.total_transactions, m.volume_usd);
+            let health = calculate_health_score(
+                &app_state.scoring,
+                m.success_rate,
+                m.total_transactions,
+                m.volume_usd,
+            );
             let liquidity_trend = get_liquidity_trend(m.volume_usd);
             let avg_latency = 400.0 + (m.success_rate * 2.0);
 
@@ -240,7 +242,8 @@ pub async fn list_corridors(
                 liquidity_depth_usd: m.volume_usd,
                 liquidity_volume_24h_usd: m.volume_usd * 0.1,
                 liquidity_trend,
-                health_score,
+                health_score: health.score,
+                health_score_breakdown: Some(health),
                 last_updated: m.updated_at.to_rfc3339(),
             }
         })
@@ -254,28 +257,11 @@ pub async fn get_corridor_detail(
     State(app_state): State<AppState>,
     Path(corridor_key): Path<String>,
 ) -> ApiResult<Json<CorridorDetailResponse>> {
-    let parts: Vec<&str> = corridor_key.split("->").collect();
-    if parts.len() != 2 {
-        return Err(ApiError::BadRequest(
-            "Invalid corridor key format".to_string(),
-        ));
-    }
-
-    let asset_a_parts: Vec<&str> = parts[0].split(':').collect();
-    let asset_b_parts: Vec<&str> = parts[1].split(':').collect();
-
-    if asset_a_parts.len() != 2 || asset_b_parts.len() != 2 {
-        return Err(ApiError::BadRequest(
-            "Invalid corridor key format".to_string(),
-        ));
-    }
-
-    let corridor = Corridor::new(
-        asset_a_parts[0].to_string(),
-        asset_a_parts[1].to_string(),
-        asset_b_parts[0].to_string(),
-        asset_b_parts[1].to_string(),
-    );
+    let key: CorridorKey = corridor_key
+        .parse()
+        .map_err(|e: AssetIdError| ApiError::BadRequest(e.to_string()))?;
+    let corridor =
+        Corridor::try_from(&key).map_err(|e| ApiError::BadRequest(e.to_string()))?;
 
     let end_date = Utc::now().date_naive();
     let start_date = end_date - Duration::days(30);
@@ -295,7 +281,8 @@ pub async fn get_corridor_detail(
     }
 
     let latest = metrics.first().unwrap();
-    let health_score = calculate_health_score(
+    let health = calculate_health_score(
+        &app_state.scoring,
         latest.success_rate,
         latest.total_transactions,
         latest.volume_usd,
@@ -318,7 +305,8 @@ pub async fn get_corridor_detail(
         liquidity_depth_usd: latest.volume_usd,
         liquidity_volume_24h_usd: latest.volume_usd * 0.1,
         liquidity_trend,
-        health_score,
+        health_score: health.score,
+        health_score_breakdown: Some(health),
         last_updated: latest.updated_at.to_rfc3339(),
     };
 
@@ -384,8 +372,12 @@ pub async fn get_corridor_detail(
         .filter(|m| m.corridor_key != latest.corridor_key)
         .take(3)
         .map(|m| {
-            let health_score =
-                calculate_health_score(m.success_rate, m.total_transactions, m.volume_usd);
+            let health = calculate_health_score(
+                &app_state.scoring,
+                m.success_rate,
+                m.total_transactions,
+                m.volume_usd,
+            );
             let liquidity_trend = get_liquidity_trend(m.volume_usd);
             let avg_latency = 400.0 + (m.success_rate * 2.0);
 
@@ -404,7 +396,8 @@ pub async fn get_corridor_detail(
                 liquidity_depth_usd: m.volume_usd,
                 liquidity_volume_24h_usd: m.volume_usd * 0.1,
                 liquidity_trend,
-                health_score,
+                health_score: health.score,
+                health_score_breakdown: Some(health),
                 last_updated: m.updated_at.to_rfc3339(),
             }
         })
@@ -464,6 +457,7 @@ mod tests {
             liquidity_volume_24h_usd: metrics.volume_usd * 0.1,
             liquidity_trend: "stable".to_string(),
             health_score: 95.0,
+            health_score_breakdown: None,
             last_updated: metrics.updated_at.to_rfc3339(),
         };
 
@@ -3,84 +3,43 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 use utoipa::{IntoParams, ToSchema};
 
-use crate::cache::{keys, CacheManager};
+use crate::cache::keys;
 use crate::cache_middleware::CacheAware;
-use crate::database::Database;
 use crate::handlers::ApiResult;
+use crate::models::asset::{AssetId, AssetIdError, CorridorKey};
 use crate::models::SortBy;
-use crate::rpc::StellarRpcClient;
-use crate::services::price_feed::PriceFeedClient;
-
-/// Represents an asset pair (source -> destination) for a corridor
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct AssetPair {
-    source_asset: String,
-    destination_asset: String,
-}
-
-impl AssetPair {
-    fn to_corridor_key(&self) -> String {
-        format!("{}->{}", self.source_asset, self.destination_asset)
-    }
-}
+use crate::scoring::{ScoreBreakdown, ScoringEngine};
+use crate::state::CachedState;
 
-/// Extract asset pair from a payment operation
+/// Extract the corridor (source -> destination) from a payment operation
 /// Handles regular payments, path_payment_strict_send, and path_payment_strict_receive
-fn extract_asset_pair_from_payment(payment: &crate::rpc::Payment) -> Option<AssetPair> {
-    let operation_type = payment.operation_type.as_deref().unwrap_or("payment");
-    
-    match operation_type {
+fn extract_corridor_from_payment(
+    payment: &crate::rpc::Payment,
+) -> Result<CorridorKey, AssetIdError> {
+    let destination = AssetId::from_horizon(
+        &payment.asset_type,
+        payment.asset_code.as_deref(),
+        payment.asset_issuer.as_deref(),
+    )?;
+
+    match payment.operation_type.as_deref().unwrap_or("payment") {
         "path_payment_strict_send" | "path_payment_strict_receive" => {
             // Path payments have explicit source and destination assets
-            let source_asset = if let Some(src_type) = &payment.source_asset_type {
-                if src_type == "native" {
-                    "XLM:native".to_string()
-                } else {
-                    format!(
-                        "{}:{}",
-                        payment.source_asset_code.as_deref().unwrap_or("UNKNOWN"),
-                        payment.source_asset_issuer.as_deref().unwrap_or("unknown")
-                    )
-                }
-            } else {
-                return None;
-            };
-            
-            let destination_asset = if payment.asset_type == "native" {
-                "XLM:native".to_string()
-            } else {
-                format!(
-                    "{}:{}",
-                    payment.asset_code.as_deref().unwrap_or("UNKNOWN"),
-                    payment.asset_issuer.as_deref().unwrap_or("unknown")
-                )
-            };
-            
-            Some(AssetPair {
-                source_asset,
-                destination_asset,
-            })
-        }
-        "payment" | _ => {
-            // Regular payments: same asset for source and destination
-            let asset = if payment.asset_type == "native" {
-                "XLM:native".to_string()
-            } else {
-                format!(
-                    "{}:{}",
-                    payment.asset_code.as_deref().unwrap_or("UNKNOWN"),
-                    payment.asset_issuer.as_deref().unwrap_or("unknown")
-                )
-            };
-            
-            Some(AssetPair {
-                source_asset: asset.clone(),
-                destination_asset: asset,
-            })
+            let source_type = payment
+                .source_asset_type
+                .as_deref()
+                .ok_or(AssetIdError::Empty)?;
+            let source = AssetId::from_horizon(
+                source_type,
+                payment.source_asset_code.as_deref(),
+                payment.source_asset_issuer.as_deref(),
+            )?;
+            Ok(CorridorKey::new(source, destination))
         }
+        // Regular payments: same asset for source and destination
+        _ => Ok(CorridorKey::new(destination.clone(), destination)),
     }
 }
 
@@ -131,6 +90,10 @@ pub struct CorridorResponse {
     /// Overall health score (0-100)
     #[schema(example = 95.5)]
     pub health_score: f64,
+    /// Scoring model, inputs and per-component contributions behind `health_score`
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub health_score_breakdown: Option<ScoreBreakdown>,
     /// Last update timestamp
     #[schema(example = "2024-01-15T10:30:00Z")]
     pub last_updated: String,
@@ -227,26 +190,21 @@ fn default_limit() -> i64 {
     50
 }
 
-fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
-    let success_weight = 0.6;
-    let volume_weight = 0.2;
-    let transaction_weight = 0.2;
-
-    let volume_score = if volume_usd > 0.0 {
-        ((volume_usd.ln() / 15.0) * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    let transaction_score = if total_transactions > 0 {
-        ((total_transactions as f64).ln() / 10.0 * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    success_rate * success_weight
-        + volume_score * volume_weight
-        + transaction_score * transaction_weight
+/// Score corridor health with the active `corridor_health` model
+fn calculate_health_score(
+    scoring: &ScoringEngine,
+    success_rate: f64,
+    total_transactions: i64,
+    volume_usd: f64,
+) -> ScoreBreakdown {
+    scoring
+        .corridor_health(success_rate, total_transactions, volume_usd)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Configured corridor health model failed, using built-in: {}", e);
+            ScoringEngine::default()
+                .corridor_health(success_rate, total_transactions, volume_usd)
+                .expect("built-in corridor health model scores its own inputs")
+        })
 }
 
 fn get_liquidity_trend(volume_usd: f64) -> String {
@@ -294,14 +252,16 @@ fn generate_corridor_list_cache_key(params: &ListCorridorsQuery) -> String {
     tag = "Corridors"
 )]
 pub async fn list_corridors(
-    State((_db, cache, rpc_client, price_feed)): State<(
-        Arc<Database>,
-        Arc<CacheManager>,
-        Arc<StellarRpcClient>,
-        Arc<PriceFeedClient>,
-    )>,
+    State(state): State<CachedState>,
     Query(params): Query<ListCorridorsQuery>,
 ) -> ApiResult<Json<Vec<CorridorResponse>>> {
+    let CachedState {
+        cache,
+        rpc_client,
+        price_feed,
+        scoring,
+        ..
+    } = state;
     let cache_key = generate_corridor_list_cache_key(&params);
 
     let corridors = <()>::get_or_fetch(
@@ -329,21 +289,17 @@ pub async fn list_corridors(
 
             // Group payments by asset pairs to identify corridors
             use std::collections::HashMap;
-            let mut corridor_map: HashMap<String, Vec<&crate::rpc::Payment>> = HashMap::new();
+            let mut corridor_map: HashMap<CorridorKey, Vec<&crate::rpc::Payment>> = HashMap::new();
 
             for payment in &payments {
                 // Extract the actual asset pair from the payment
-                if let Some(asset_pair) = extract_asset_pair_from_payment(payment) {
-                    let corridor_key = asset_pair.to_corridor_key();
-                    corridor_map
-                        .entry(corridor_key)
-                        .or_insert_with(Vec::new)
-                        .push(payment);
-                } else {
-                    tracing::warn!(
-                        "Failed to extract asset pair from payment: {}",
-                        payment.id
-                    );
+                match extract_corridor_from_payment(payment) {
+                    Ok(corridor_key) => corridor_map.entry(corridor_key).or_default().push(payment),
+                    Err(e) => tracing::warn!(
+                        "Failed to extract asset pair from payment {}: {}",
+                        payment.id,
+                        e
+                    ),
                 }
             }
 
@@ -358,25 +314,12 @@ pub async fn list_corridors(
                 let failed_payments = 0;
                 let success_rate = if total_attempts > 0 { 100.0 } else { 0.0 };
 
-                // Parse corridor key to get assets
-                let parts: Vec<&str> = corridor_key.split("->").collect();
-                if parts.len() != 2 {
-                    continue;
-                }
-
-                let source_parts: Vec<&str> = parts[0].split(':').collect();
-                let dest_parts: Vec<&str> = parts[1].split(':').collect();
-
-                if source_parts.len() != 2 || dest_parts.len() != 2 {
-                    continue;
-                }
-
                 // Calculate volume from payment amounts and convert to USD
                 let mut volume_usd: f64 = 0.0;
-                let source_asset_key = parts[0];
-                
+                let source_asset_key = corridor_key.source.key();
+
                 // Get price for source asset
-                if let Ok(price) = price_feed.get_price(source_asset_key).await {
+                if let Ok(price) = price_feed.get_price(&source_asset_key).await {
                     for payment in corridor_payments.iter() {
                         if let Ok(amount) = payment.amount.parse::<f64>() {
                             volume_usd += amount * price;
@@ -392,14 +335,15 @@ pub async fn list_corridors(
                 }
 
                 // Calculate health score
-                let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
+                let health_breakdown =
+                    calculate_health_score(&scoring, success_rate, total_attempts, volume_usd);
                 let liquidity_trend = get_liquidity_trend(volume_usd);
                 let avg_latency = 400.0 + (success_rate * 2.0);
 
                 let corridor_response = CorridorResponse {
-                    id: corridor_key.clone(),
-                    source_asset: source_parts[0].to_string(),
-                    destination_asset: dest_parts[0].to_string(),
+                    id: corridor_key.to_string(),
+                    source_asset: corridor_key.source.code().to_string(),
+                    destination_asset: corridor_key.destination.code().to_string(),
                     success_rate,
                     total_attempts,
                     successful_payments,
@@ -411,7 +355,8 @@ pub async fn list_corridors(
                     liquidity_depth_usd: volume_usd,
                     liquidity_volume_24h_usd: volume_usd * 0.1,
                     liquidity_trend,
-                    health_score,
+                    health_score: health_breakdown.score,
+                    health_score_breakdown: Some(health_breakdown),
                     last_updated: chrono::Utc::now().to_rfc3339(),
                 };
 
@@ -484,12 +429,7 @@ pub async fn list_corridors(
     tag = "Corridors"
 )]
 pub async fn get_corridor_detail(
-    State((_db, _cache, _rpc_client, _price_feed)): State<(
-        Arc<Database>,
-        Arc<CacheManager>,
-        Arc<StellarRpcClient>,
-        Arc<PriceFeedClient>,
-    )>,
+    State(_state): State<CachedState>,
     Path(_corridor_key): Path<String>,
 ) -> ApiResult<Json<CorridorDetailResponse>> {
     // TODO: Implement RPC-based corridor detail
@@ -502,10 +442,15 @@ pub async fn get_corridor_detail(
 mod tests {
     use super::*;
 
+    const ISSUER_A: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const ISSUER_B: &str = "GDUKMGUGDZQK6YHYA5Z6AY2G4XDSZPSZ3SW5UN3ARVMO6QSRDWP5YLEX";
+    const ISSUER_C: &str = "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+
     #[test]
     fn test_health_score_calculation() {
-        let score = calculate_health_score(95.0, 1000, 1_000_000.0);
-        assert!(score > 0.0 && score <= 100.0);
+        let breakdown = calculate_health_score(&ScoringEngine::default(), 95.0, 1000, 1_000_000.0);
+        assert!(breakdown.score > 0.0 && breakdown.score <= 100.0);
+        assert_eq!(breakdown.components.len(), 3);
     }
 
     #[test]
@@ -516,7 +461,7 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_asset_pair_regular_payment_native() {
+    fn test_extract_corridor_regular_payment_native() {
         let payment = crate::rpc::Payment {
             id: "test_1".to_string(),
             paging_token: "token_1".to_string(),
@@ -537,14 +482,14 @@ mod tests {
             to: Some("GDEST".to_string()),
         };
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "XLM:native");
-        assert_eq!(pair.destination_asset, "XLM:native");
-        assert_eq!(pair.to_corridor_key(), "XLM:native->XLM:native");
+        let corridor = extract_corridor_from_payment(&payment).unwrap();
+        assert_eq!(corridor.source.key(), "XLM:native");
+        assert_eq!(corridor.destination.key(), "XLM:native");
+        assert_eq!(corridor.to_string(), "XLM:native->XLM:native");
     }
 
     #[test]
-    fn test_extract_asset_pair_regular_payment_issued_asset() {
+    fn test_extract_corridor_regular_payment_issued_asset() {
         let payment = crate::rpc::Payment {
             id: "test_2".to_string(),
             paging_token: "token_2".to_string(),
@@ -553,7 +498,7 @@ mod tests {
             destination: "GDEST".to_string(),
             asset_type: "credit_alphanum4".to_string(),
             asset_code: Some("USDC".to_string()),
-            asset_issuer: Some("GISSUER".to_string()),
+            asset_issuer: Some(ISSUER_A.to_string()),
             amount: "100.0".to_string(),
             created_at: "2026-01-01T00:00:00Z".to_string(),
             operation_type: Some("payment".to_string()),
@@ -565,14 +510,17 @@ mod tests {
             to: Some("GDEST".to_string()),
         };
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "USDC:GISSUER");
-        assert_eq!(pair.destination_asset, "USDC:GISSUER");
-        assert_eq!(pair.to_corridor_key(), "USDC:GISSUER->USDC:GISSUER");
+        let corridor = extract_corridor_from_payment(&payment).unwrap();
+        assert_eq!(corridor.source.key(), format!("USDC:{}", ISSUER_A));
+        assert_eq!(corridor.destination.key(), format!("USDC:{}", ISSUER_A));
+        assert_eq!(
+            corridor.to_string(),
+            format!("USDC:{}->USDC:{}", ISSUER_A, ISSUER_A)
+        );
     }
 
     #[test]
-    fn test_extract_asset_pair_path_payment_cross_asset() {
+    fn test_extract_corridor_path_payment_cross_asset() {
         let payment = crate::rpc::Payment {
             id: "test_3".to_string(),
             paging_token: "token_3".to_string(),
@@ -581,26 +529,29 @@ mod tests {
             destination: "GDEST".to_string(),
             asset_type: "credit_alphanum4".to_string(),
             asset_code: Some("EUR".to_string()),
-            asset_issuer: Some("GEURISSUER".to_string()),
+            asset_issuer: Some(ISSUER_C.to_string()),
             amount: "100.0".to_string(),
             created_at: "2026-01-01T00:00:00Z".to_string(),
             operation_type: Some("path_payment_strict_send".to_string()),
             source_asset_type: Some("credit_alphanum4".to_string()),
             source_asset_code: Some("USD".to_string()),
-            source_asset_issuer: Some("GUSDISSUER".to_string()),
+            source_asset_issuer: Some(ISSUER_B.to_string()),
             source_amount: Some("105.0".to_string()),
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
         };
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "USD:GUSDISSUER");
-        assert_eq!(pair.destination_asset, "EUR:GEURISSUER");
-        assert_eq!(pair.to_corridor_key(), "USD:GUSDISSUER->EUR:GEURISSUER");
+        let corridor = extract_corridor_from_payment(&payment).unwrap();
+        assert_eq!(corridor.source.key(), format!("USD:{}", ISSUER_B));
+        assert_eq!(corridor.destination.key(), format!("EUR:{}", ISSUER_C));
+        assert_eq!(
+            corridor.to_string(),
+            format!("USD:{}->EUR:{}", ISSUER_B, ISSUER_C)
+        );
     }
 
     #[test]
-    fn test_extract_asset_pair_path_payment_native_to_issued() {
+    fn test_extract_corridor_path_payment_native_to_issued() {
         let payment = crate::rpc::Payment {
             id: "test_4".to_string(),
             paging_token: "token_4".to_string(),
@@ -609,7 +560,7 @@ mod tests {
             destination: "GDEST".to_string(),
             asset_type: "credit_alphanum4".to_string(),
             asset_code: Some("USDC".to_string()),
-            asset_issuer: Some("GISSUER".to_string()),
+            asset_issuer: Some(ISSUER_A.to_string()),
             amount: "100.0".to_string(),
             created_at: "2026-01-01T00:00:00Z".to_string(),
             operation_type: Some("path_payment_strict_receive".to_string()),
@@ -621,14 +572,17 @@ mod tests {
             to: Some("GDEST".to_string()),
         };
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "XLM:native");
-        assert_eq!(pair.destination_asset, "USDC:GISSUER");
-        assert_eq!(pair.to_corridor_key(), "XLM:native->USDC:GISSUER");
+        let corridor = extract_corridor_from_payment(&payment).unwrap();
+        assert_eq!(corridor.source.key(), "XLM:native");
+        assert_eq!(corridor.destination.key(), format!("USDC:{}", ISSUER_A));
+        assert_eq!(
+            corridor.to_string(),
+            format!("XLM:native->USDC:{}", ISSUER_A)
+        );
     }
 
     #[test]
-    fn test_extract_asset_pair_path_payment_issued_to_native() {
+    fn test_extract_corridor_path_payment_issued_to_native() {
         let payment = crate::rpc::Payment {
             id: "test_5".to_string(),
             paging_token: "token_5".to_string(),
@@ -643,20 +597,23 @@ mod tests {
             operation_type: Some("path_payment_strict_send".to_string()),
             source_asset_type: Some("credit_alphanum4".to_string()),
             source_asset_code: Some("BRL".to_string()),
-            source_asset_issuer: Some("GBRLISSUER".to_string()),
+            source_asset_issuer: Some(ISSUER_B.to_string()),
             source_amount: Some("500.0".to_string()),
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
         };
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "BRL:GBRLISSUER");
-        assert_eq!(pair.destination_asset, "XLM:native");
-        assert_eq!(pair.to_corridor_key(), "BRL:GBRLISSUER->XLM:native");
+        let corridor = extract_corridor_from_payment(&payment).unwrap();
+        assert_eq!(corridor.source.key(), format!("BRL:{}", ISSUER_B));
+        assert_eq!(corridor.destination.key(), "XLM:native");
+        assert_eq!(
+            corridor.to_string(),
+            format!("BRL:{}->XLM:native", ISSUER_B)
+        );
     }
 
     #[test]
-    fn test_extract_asset_pair_missing_operation_type() {
+    fn test_extract_corridor_missing_operation_type() {
         // Should default to regular payment behavior
         let payment = crate::rpc::Payment {
             id: "test_6".to_string(),
@@ -666,7 +623,7 @@ mod tests {
             destination: "GDEST".to_string(),
             asset_type: "credit_alphanum4".to_string(),
             asset_code: Some("NGNT".to_string()),
-            asset_issuer: Some("GNGNTISSUER".to_string()),
+            asset_issuer: Some(ISSUER_C.to_string()),
             amount: "100.0".to_string(),
             created_at: "2026-01-01T00:00:00Z".to_string(),
             operation_type: None,
@@ -678,9 +635,36 @@ mod tests {
             to: Some("GDEST".to_string()),
         };
 
-        let pair = extract_asset_pair_from_payment(&payment).unwrap();
-        assert_eq!(pair.source_asset, "NGNT:GNGNTISSUER");
-        assert_eq!(pair.destination_asset, "NGNT:GNGNTISSUER");
+        let corridor = extract_corridor_from_payment(&payment).unwrap();
+        assert_eq!(corridor.source.key(), format!("NGNT:{}", ISSUER_C));
+        assert_eq!(corridor.destination.key(), format!("NGNT:{}", ISSUER_C));
     }
-}
 
+    #[test]
+    fn test_extract_corridor_rejects_invalid_issuer() {
+        let payment = crate::rpc::Payment {
+            id: "test_7".to_string(),
+            paging_token: "token_7".to_string(),
+            transaction_hash: "hash_7".to_string(),
+            source_account: "GTEST".to_string(),
+            destination: "GDEST".to_string(),
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some("GISSUER".to_string()),
+            amount: "100.0".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            operation_type: Some("payment".to_string()),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: None,
+            from: Some("GTEST".to_string()),
+            to: Some("GDEST".to_string()),
+        };
+
+        assert_eq!(
+            extract_corridor_from_payment(&payment),
+            Err(AssetIdError::InvalidIssuer("GISSUER".to_string()))
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::database::ENDPOINT_UPTIME_WINDOW_HOURS;
+use crate::handlers::{ApiError, ApiResult};
+use crate::services::sep_endpoint_prober::{
+    AnchorUptime, EndpointProbe, EndpointUptime, SepEndpoint, SepEndpointProber,
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EndpointUptimeQuery {
+    /// Look back this many hours (default: 24)
+    #[serde(default = "default_hours")]
+    #[param(example = 24)]
+    pub hours: i64,
+}
+
+fn default_hours() -> i64 {
+    ENDPOINT_UPTIME_WINDOW_HOURS
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EndpointProbesQuery {
+    /// Maximum number of probes to return (default: 100)
+    #[serde(default = "default_limit")]
+    #[param(example = 100)]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EndpointUptimeResponse {
+    pub anchor_id: String,
+    pub window_hours: i64,
+    /// Share of probes in the window that were available and schema-valid
+    pub uptime_percent: Option<f64>,
+    /// Uptime and latency per SEP endpoint
+    #[schema(value_type = Vec<Object>)]
+    pub endpoints: Vec<EndpointUptime>,
+}
+
+impl From<AnchorUptime> for EndpointUptimeResponse {
+    fn from(uptime: AnchorUptime) -> Self {
+        Self {
+            anchor_id: uptime.anchor_id,
+            window_hours: uptime.window_hours,
+            uptime_percent: uptime.uptime_percent,
+            endpoints: uptime.endpoints,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EndpointProbesResponse {
+    pub anchor_id: String,
+    /// Probes ordered newest first
+    #[schema(value_type = Vec<Object>)]
+    pub probes: Vec<EndpointProbe>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SepEndpointResponse {
+    /// SEP endpoint kind (sep6, sep10, sep24, sep31 or sep38)
+    pub sep: String,
+    /// Base URL that is probed
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetSepEndpointRequest {
+    /// Base URL as it would appear in stellar.toml
+    pub url: String,
+}
+
+fn parse_sep(sep: &str) -> ApiResult<SepEndpoint> {
+    sep.parse().map_err(ApiError::BadRequest)
+}
+
+/// Get the uptime of an anchor's SEP endpoints
+///
+/// Aggregates the prober's history of the anchor's SEP-6/24/31/38 `/info`
+/// and SEP-10 challenge endpoints. A probe counts as up when the endpoint
+/// answered with a 2xx status and a response matching the SEP.
+///
+/// **DATA SOURCE: Database** (populated by the SEP endpoint prober)
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/uptime",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        EndpointUptimeQuery
+    ),
+    responses(
+        (status = 200, description = "Endpoint uptime retrieved successfully", body = EndpointUptimeResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn get_anchor_uptime(
+    State(prober): State<Arc<SepEndpointProber>>,
+    Path(id): Path<String>,
+    Query(params): Query<EndpointUptimeQuery>,
+) -> ApiResult<Json<EndpointUptimeResponse>> {
+    let uptime = prober
+        .get_uptime(&id, params.hours.clamp(1, 24 * 30))
+        .await?;
+
+    Ok(Json(uptime.into()))
+}
+
+/// Get recent probes of an anchor's SEP endpoints
+///
+/// **DATA SOURCE: Database** (populated by the SEP endpoint prober)
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/uptime/probes",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        EndpointProbesQuery
+    ),
+    responses(
+        (status = 200, description = "Probes retrieved successfully", body = EndpointProbesResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn get_anchor_probes(
+    State(prober): State<Arc<SepEndpointProber>>,
+    Path(id): Path<String>,
+    Query(params): Query<EndpointProbesQuery>,
+) -> ApiResult<Json<EndpointProbesResponse>> {
+    let probes = prober.get_probes(&id, params.limit.clamp(1, 1000)).await?;
+
+    Ok(Json(EndpointProbesResponse {
+        anchor_id: id,
+        probes,
+    }))
+}
+
+/// List the SEP endpoints probed for an anchor
+///
+/// Endpoints configured on the anchor take precedence over those crawled
+/// from its stellar.toml.
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/sep-endpoints",
+    params(
+        ("id" = String, Path, description = "Anchor ID")
+    ),
+    responses(
+        (status = 200, description = "Endpoints retrieved successfully", body = Vec<SepEndpointResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn list_sep_endpoints(
+    State(prober): State<Arc<SepEndpointProber>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<SepEndpointResponse>>> {
+    let endpoints = prober
+        .endpoints(&id)
+        .await?
+        .into_iter()
+        .map(|(sep, url)| SepEndpointResponse {
+            sep: sep.to_string(),
+            url,
+        })
+        .collect();
+
+    Ok(Json(endpoints))
+}
+
+/// Configure a SEP endpoint on an anchor
+///
+/// Overrides the endpoint published in the anchor's stellar.toml.
+#[utoipa::path(
+    put,
+    path = "/api/anchors/{id}/sep-endpoints/{sep}",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        ("sep" = String, Path, description = "SEP endpoint kind (sep6, sep10, sep24, sep31 or sep38)")
+    ),
+    request_body = SetSepEndpointRequest,
+    responses(
+        (status = 200, description = "Endpoint configured", body = SepEndpointResponse),
+        (status = 400, description = "Invalid endpoint kind or URL"),
+        (status = 404, description = "Anchor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn set_sep_endpoint(
+    State(prober): State<Arc<SepEndpointProber>>,
+    Path((id, sep)): Path<(String, String)>,
+    Json(req): Json<SetSepEndpointRequest>,
+) -> ApiResult<Json<SepEndpointResponse>> {
+    let sep = parse_sep(&sep)?;
+    let url = req.url.trim();
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+        _ => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid endpoint URL: {}",
+                url
+            )))
+        }
+    }
+
+    if !prober.set_endpoint(&id, sep, url).await? {
+        return Err(ApiError::NotFound(format!(
+            "Anchor with id {} not found",
+            id
+        )));
+    }
+
+    Ok(Json(SepEndpointResponse {
+        sep: sep.to_string(),
+        url: url.to_string(),
+    }))
+}
+
+/// Remove a SEP endpoint configured on an anchor
+///
+/// The endpoint from the anchor's stellar.toml, if any, is probed again.
+#[utoipa::path(
+    delete,
+    path = "/api/anchors/{id}/sep-endpoints/{sep}",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        ("sep" = String, Path, description = "SEP endpoint kind (sep6, sep10, sep24, sep31 or sep38)")
+    ),
+    responses(
+        (status = 204, description = "Endpoint removed"),
+        (status = 400, description = "Invalid endpoint kind"),
+        (status = 404, description = "No endpoint configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn remove_sep_endpoint(
+    State(prober): State<Arc<SepEndpointProber>>,
+    Path((id, sep)): Path<(String, String)>,
+) -> ApiResult<StatusCode> {
+    let sep = parse_sep(&sep)?;
+    if !prober.remove_endpoint(&id, sep).await? {
+        return Err(ApiError::NotFound(format!(
+            "No {} endpoint configured for anchor {}",
+            sep, id
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create endpoint uptime routes
+pub fn routes(prober: Arc<SepEndpointProber>) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/uptime", get(get_anchor_uptime))
+        .route("/api/anchors/:id/uptime/probes", get(get_anchor_probes))
+        .route("/api/anchors/:id/sep-endpoints", get(list_sep_endpoints))
+        .with_state(prober)
+}
+
+/// Create routes that configure probed endpoints; callers add authentication
+pub fn protected_routes(prober: Arc<SepEndpointProber>) -> Router {
+    Router::new()
+        .route(
+            "/api/anchors/:id/sep-endpoints/:sep",
+            put(set_sep_endpoint).delete(remove_sep_endpoint),
+        )
+        .with_state(prober)
+}
@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::ApiResult;
+use crate::models::asset::CorridorKey;
+use crate::services::fx_spread::{FxSpreadPoint, FxSpreadService, SpreadInterval};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FxSpreadQuery {
+    /// Look back this many days (default: 30)
+    #[serde(default = "default_days")]
+    #[param(example = 30)]
+    pub days: i64,
+    /// Bucket size (hour or day, default: day)
+    #[serde(default)]
+    #[param(value_type = Option<String>, example = "day")]
+    pub interval: SpreadInterval,
+}
+
+fn default_days() -> i64 {
+    30
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FxSpreadResponse {
+    /// Corridor key or anchor id the series was requested for
+    pub subject: String,
+    #[schema(value_type = String, example = "day")]
+    pub interval: SpreadInterval,
+    /// Volume-weighted average spread over the whole window, in basis points
+    pub avg_spread_bps: Option<f64>,
+    /// Spread per corridor and bucket, oldest first
+    #[schema(value_type = Vec<Object>)]
+    pub points: Vec<FxSpreadPoint>,
+}
+
+impl FxSpreadResponse {
+    fn new(subject: String, interval: SpreadInterval, points: Vec<FxSpreadPoint>) -> Self {
+        let volume: f64 = points.iter().map(|p| p.volume_usd).sum();
+        let avg_spread_bps = (volume > 0.0).then(|| {
+            points
+                .iter()
+                .map(|p| p.avg_spread_bps * p.volume_usd)
+                .sum::<f64>()
+                / volume
+        });
+
+        Self {
+            subject,
+            interval,
+            avg_spread_bps,
+            points,
+        }
+    }
+}
+
+/// Get the FX spread of a corridor against reference rates
+///
+/// Compares the effective conversion rate of cross-asset path payments with
+/// the rate implied by USD reference prices. Positive spreads are the markup
+/// paid by senders, in basis points.
+///
+/// **DATA SOURCE: Database** (populated from Horizon payments and the price feed)
+#[utoipa::path(
+    get,
+    path = "/api/corridors/{corridor_key}/fx-spread",
+    params(
+        ("corridor_key" = String, Path, description = "Directional corridor (e.g., USDC:GA5Z...->XLM:native)"),
+        FxSpreadQuery
+    ),
+    responses(
+        (status = 200, description = "Spread series retrieved successfully", body = FxSpreadResponse),
+        (status = 400, description = "Invalid corridor key"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Corridors"
+)]
+pub async fn get_corridor_fx_spread(
+    State(service): State<Arc<FxSpreadService>>,
+    Path(corridor_key): Path<CorridorKey>,
+    Query(params): Query<FxSpreadQuery>,
+) -> ApiResult<Json<FxSpreadResponse>> {
+    let since = Utc::now() - Duration::days(params.days.clamp(1, 365));
+    let points = service
+        .get_corridor_spreads(&corridor_key, since, params.interval)
+        .await?;
+
+    Ok(Json(FxSpreadResponse::new(
+        corridor_key.to_string(),
+        params.interval,
+        points,
+    )))
+}
+
+/// Get FX spreads of corridors served by an anchor
+///
+/// Covers every corridor whose source or destination asset is issued by the
+/// anchor.
+///
+/// **DATA SOURCE: Database** (populated from Horizon payments and the price feed)
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/fx-spread",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        FxSpreadQuery
+    ),
+    responses(
+        (status = 200, description = "Spread series retrieved successfully", body = FxSpreadResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn get_anchor_fx_spread(
+    State(service): State<Arc<FxSpreadService>>,
+    Path(id): Path<String>,
+    Query(params): Query<FxSpreadQuery>,
+) -> ApiResult<Json<FxSpreadResponse>> {
+    let since = Utc::now() - Duration::days(params.days.clamp(1, 365));
+    let points = service
+        .get_anchor_spreads(&id, since, params.interval)
+        .await?;
+
+    Ok(Json(FxSpreadResponse::new(id, params.interval, points)))
+}
+
+/// Create FX spread routes
+pub fn routes(service: Arc<FxSpreadService>) -> Router {
+    Router::new()
+        .route(
+            "/api/corridors/:corridor_key/fx-spread",
+            get(get_corridor_fx_spread),
+        )
+        .route("/api/anchors/:id/fx-spread", get(get_anchor_fx_spread))
+        .with_state(service)
+}
@@ -0,0 +1,189 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::handlers::{ApiError, ApiResult};
+use crate::models::{AnchorIncident, AnchorStatusTransition, IncidentTimelineEntry};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncidentLimitQuery {
+    /// Maximum number of entries to return (default: 50)
+    #[serde(default = "default_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncidentFeedQuery {
+    /// Only `open` or only `resolved` incidents
+    pub status: Option<String>,
+    /// Maximum number of incidents to return (default: 50)
+    #[serde(default = "default_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnchorIncidentsResponse {
+    pub anchor_id: String,
+    pub current_status: String,
+    /// Incidents newest first, each with its transitions oldest first
+    #[schema(value_type = Vec<Object>)]
+    pub incidents: Vec<IncidentTimelineEntry>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatusTransitionsResponse {
+    pub anchor_id: String,
+    /// Status changes newest first
+    #[schema(value_type = Vec<Object>)]
+    pub transitions: Vec<AnchorStatusTransition>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IncidentFeedResponse {
+    /// Incidents across all anchors, newest first
+    #[schema(value_type = Vec<Object>)]
+    pub incidents: Vec<AnchorIncident>,
+}
+
+async fn require_anchor(db: &Database, id: &str) -> ApiResult<crate::models::Anchor> {
+    let uuid = Uuid::parse_str(id)
+        .map_err(|_| ApiError::BadRequest(format!("Invalid anchor id: {}", id)))?;
+    db.get_anchor_by_id(uuid)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Anchor with id {} not found", id)))
+}
+
+/// Get an anchor's incident timeline
+///
+/// An incident spans from the anchor leaving green until it returns to green,
+/// and lists the status transitions in between with their causes.
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/incidents",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        IncidentLimitQuery
+    ),
+    responses(
+        (status = 200, description = "Incident timeline retrieved successfully", body = AnchorIncidentsResponse),
+        (status = 400, description = "Invalid anchor id"),
+        (status = 404, description = "Anchor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_anchor_incidents(
+    State(db): State<Arc<Database>>,
+    Path(id): Path<String>,
+    Query(params): Query<IncidentLimitQuery>,
+) -> ApiResult<Json<AnchorIncidentsResponse>> {
+    let anchor = require_anchor(&db, &id).await?;
+    let incidents = db
+        .get_anchor_incident_timeline(&anchor.id, params.limit.clamp(1, 500))
+        .await?;
+
+    Ok(Json(AnchorIncidentsResponse {
+        anchor_id: anchor.id,
+        current_status: anchor.status,
+        incidents,
+    }))
+}
+
+/// Get an anchor's status transitions
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/status-transitions",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        IncidentLimitQuery
+    ),
+    responses(
+        (status = 200, description = "Status transitions retrieved successfully", body = StatusTransitionsResponse),
+        (status = 400, description = "Invalid anchor id"),
+        (status = 404, description = "Anchor not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_status_transitions(
+    State(db): State<Arc<Database>>,
+    Path(id): Path<String>,
+    Query(params): Query<IncidentLimitQuery>,
+) -> ApiResult<Json<StatusTransitionsResponse>> {
+    let anchor = require_anchor(&db, &id).await?;
+    let transitions = db
+        .get_anchor_status_transitions(&anchor.id, params.limit.clamp(1, 500))
+        .await?;
+
+    Ok(Json(StatusTransitionsResponse {
+        anchor_id: anchor.id,
+        transitions,
+    }))
+}
+
+/// Get the network-wide incident feed
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/incidents",
+    params(IncidentFeedQuery),
+    responses(
+        (status = 200, description = "Incidents retrieved successfully", body = IncidentFeedResponse),
+        (status = 400, description = "Invalid status filter"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Incidents"
+)]
+pub async fn list_incidents(
+    State(db): State<Arc<Database>>,
+    Query(params): Query<IncidentFeedQuery>,
+) -> ApiResult<Json<IncidentFeedResponse>> {
+    let open = match params.status.as_deref() {
+        None => None,
+        Some("open") => Some(true),
+        Some("resolved") => Some(false),
+        Some(other) => {
+            return Err(ApiError::BadRequest(format!(
+                "unknown incident status: {} (expected open or resolved)",
+                other
+            )))
+        }
+    };
+    let incidents = db
+        .list_incidents(None, open, params.limit.clamp(1, 500))
+        .await?;
+
+    Ok(Json(IncidentFeedResponse { incidents }))
+}
+
+/// Create incident routes
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/anchors/:id/incidents", get(get_anchor_incidents))
+        .route(
+            "/api/anchors/:id/status-transitions",
+            get(get_status_transitions),
+        )
+        .route("/api/incidents", get(list_incidents))
+        .with_state(db)
+}
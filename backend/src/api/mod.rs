@@ -1,16 +1,33 @@
 pub mod account_merges;
+pub mod anchor_fees;
+pub mod anchor_proxy;
+pub mod anomalies;
 pub mod anchors;
 pub mod anchors_cached;
+pub mod asset_supply;
 pub mod auth;
 pub mod cache_stats;
 pub mod corridors;
 pub mod corridors_cached;
+pub mod endpoint_uptime;
 pub mod fee_bump;
+pub mod fx_spreads;
+pub mod incidents;
 pub mod liquidity_pools;
 pub mod metrics;
 pub mod metrics_cached;
 pub mod prediction;
 pub mod price_feed;
+pub mod quote_comparison;
+pub mod quotes;
+pub mod rollups;
+pub mod routes;
+pub mod scoring;
+pub mod sep10;
 pub mod sep24_proxy;
 pub mod sep31_proxy;
+pub mod sep38_proxy;
+pub mod sep6_proxy;
+pub mod stellar_toml;
+pub mod transfers;
 pub mod trustlines;
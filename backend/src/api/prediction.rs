@@ -1,11 +1,21 @@
-use axum::{extract::Query, Json};
-use serde::{Deserialize, Serialize};
+use axum::{
+    extract::{Query, State},
+    Json,
+};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::models::asset::AssetId;
+use crate::services::route_finder::{RankedRoute, RouteFinder, RouteMode};
+
+/// Number of alternative routes returned with a prediction
+const MAX_ALTERNATIVE_ROUTES: usize = 3;
 
 #[derive(Debug, Deserialize)]
 pub struct PredictionQuery {
-    pub source_asset: String,
-    pub destination_asset: String,
+    pub source_asset: AssetId,
+    pub destination_asset: AssetId,
     pub amount: f64,
     pub time_of_day: String,
 }
@@ -19,20 +29,52 @@ pub struct PredictionResponse {
 
 /// POST /api/predict/success - Predict payment success
 pub async fn predict_success(
+    State(finder): State<Arc<RouteFinder>>,
     Query(params): Query<PredictionQuery>,
 ) -> Json<PredictionResponse> {
     // Mock implementation
-    let mut rng = rand::thread_rng();
-    let probability = rng.gen_range(0.8..0.98);
+    let probability = rand::thread_rng().gen_range(0.8..0.98);
+
+    let alternative_routes = match finder
+        .find_routes(
+            &params.source_asset,
+            &params.destination_asset,
+            params.amount,
+            RouteMode::StrictSend,
+        )
+        .await
+    {
+        Ok(routes) => routes
+            .iter()
+            .take(MAX_ALTERNATIVE_ROUTES)
+            .map(describe_route)
+            .collect(),
+        Err(e) => {
+            tracing::warn!(
+                "Route finding failed for {} -> {}: {}",
+                params.source_asset,
+                params.destination_asset,
+                e
+            );
+            Vec::new()
+        }
+    };
 
     let response = PredictionResponse {
         success_probability: probability,
         confidence_interval: (probability - 0.05, probability + 0.02),
-        alternative_routes: vec![
-            "Route via XLM".to_string(),
-            "Route via secondary anchor".to_string(),
-        ],
+        alternative_routes,
     };
 
     Json(response)
 }
+
+/// Route as its hop codes, e.g. `XLM -> USDC -> EURC`
+fn describe_route(route: &RankedRoute) -> String {
+    route
+        .hops
+        .iter()
+        .map(AssetId::code)
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
@@ -9,14 +9,15 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use utoipa::{IntoParams, ToSchema};
 
+use crate::models::asset::{AssetId, AssetIdError};
 use crate::services::price_feed::PriceFeedClient;
 
 #[derive(Debug, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct GetPriceQuery {
     /// Stellar asset identifier (e.g., "XLM:native", "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN")
-    #[param(example = "XLM:native")]
-    pub asset: String,
+    #[param(value_type = String, example = "XLM:native")]
+    pub asset: AssetId,
 }
 
 #[derive(Debug, Deserialize, IntoParams)]
@@ -31,8 +32,8 @@ pub struct GetPricesQuery {
 #[into_params(parameter_in = Query)]
 pub struct ConvertQuery {
     /// Stellar asset identifier
-    #[param(example = "XLM:native")]
-    pub asset: String,
+    #[param(value_type = String, example = "XLM:native")]
+    pub asset: AssetId,
     /// Amount to convert
     #[param(example = 100.0)]
     pub amount: f64,
@@ -117,10 +118,11 @@ pub async fn get_price(
     State(price_feed): State<Arc<PriceFeedClient>>,
     Query(params): Query<GetPriceQuery>,
 ) -> impl IntoResponse {
-    match price_feed.get_price(&params.asset).await {
+    let asset = params.asset.key();
+    match price_feed.get_price(&asset).await {
         Ok(price) => {
             let response = PriceResponse {
-                asset: params.asset,
+                asset,
                 price_usd: price,
                 timestamp: chrono::Utc::now().to_rfc3339(),
             };
@@ -155,12 +157,15 @@ pub async fn get_prices(
     State(price_feed): State<Arc<PriceFeedClient>>,
     Query(params): Query<GetPricesQuery>,
 ) -> impl IntoResponse {
-    let assets: Vec<String> = params
-        .assets
-        .split(',')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+    let assets = match parse_assets(&params.assets) {
+        Ok(assets) => assets,
+        Err(e) => {
+            let error = ErrorResponse {
+                error: format!("Invalid asset identifier: {}", e),
+            };
+            return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+        }
+    };
 
     if assets.is_empty() {
         let error = ErrorResponse {
@@ -199,11 +204,12 @@ pub async fn convert_to_usd(
     State(price_feed): State<Arc<PriceFeedClient>>,
     Query(params): Query<ConvertQuery>,
 ) -> impl IntoResponse {
-    match price_feed.convert_to_usd(&params.asset, params.amount).await {
+    let asset = params.asset.key();
+    match price_feed.convert_to_usd(&asset, params.amount).await {
         Ok(amount_usd) => {
             let price_usd = amount_usd / params.amount;
             let response = ConvertResponse {
-                asset: params.asset,
+                asset,
                 amount: params.amount,
                 amount_usd,
                 price_usd,
@@ -242,6 +248,16 @@ pub async fn get_cache_stats(State(price_feed): State<Arc<PriceFeedClient>>) ->
     (StatusCode::OK, Json(response)).into_response()
 }
 
+/// Price feed keys of a comma-separated asset list
+fn parse_assets(assets: &str) -> Result<Vec<String>, AssetIdError> {
+    assets
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<AssetId>().map(|asset| asset.key()))
+        .collect()
+}
+
 /// Create price feed routes
 pub fn routes(price_feed: Arc<PriceFeedClient>) -> Router {
     Router::new()
@@ -258,12 +274,11 @@ mod tests {
 
     #[test]
     fn test_parse_assets() {
-        let assets_str = "XLM:native,USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
-        let assets: Vec<String> = assets_str
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .collect();
+        let assets_str = "native, USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let assets = parse_assets(assets_str).unwrap();
         assert_eq!(assets.len(), 2);
         assert_eq!(assets[0], "XLM:native");
+
+        assert!(parse_assets("XLM:native,USDC").is_err());
     }
 }
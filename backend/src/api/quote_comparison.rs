@@ -0,0 +1,131 @@
+use axum::{
+    extract::{Extension, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::auth_middleware::AuthUser;
+use crate::handlers::{ApiError, ApiResult};
+use crate::services::quote_comparison::{AnchorQuote, QuoteComparer, QuoteComparisonRequest};
+use crate::services::sep38_client::QUOTE_CONTEXTS;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QuoteComparisonQuery {
+    /// Asset to sell, in SEP-38 format
+    #[param(example = "iso4217:USD")]
+    pub sell_asset: String,
+    /// Asset to buy, in SEP-38 format
+    #[param(example = "iso4217:BRL")]
+    pub buy_asset: String,
+    /// Decimal amount of `sell_asset`
+    #[param(example = "100")]
+    pub sell_amount: String,
+    /// sep6, sep24 or sep31 (default: sep31)
+    #[serde(default = "default_context")]
+    #[param(example = "sep31")]
+    pub context: String,
+    /// Request firm quotes through SEP-10 instead of indicative prices;
+    /// requires signing in
+    #[serde(default)]
+    pub firm: bool,
+}
+
+fn default_context() -> String {
+    "sep31".to_string()
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuoteComparisonResponse {
+    pub sell_asset: String,
+    pub buy_asset: String,
+    pub sell_amount: String,
+    pub context: String,
+    pub firm: bool,
+    /// Quotes ordered by amount delivered after fees, largest first; anchors
+    /// that could not quote follow
+    #[schema(value_type = Vec<Object>)]
+    pub quotes: Vec<AnchorQuote>,
+}
+
+/// Compare SEP-38 quotes across anchors
+///
+/// Asks every anchor whose quote server lists both assets for an indicative
+/// price, or a firm quote when `firm` is set, and ranks the answers by the
+/// amount delivered. SEP-38 prices include fees, so that is the `buy_amount`.
+///
+/// **DATA SOURCE: Anchors** (live SEP-38 requests)
+#[utoipa::path(
+    get,
+    path = "/api/sep38/compare",
+    params(QuoteComparisonQuery),
+    responses(
+        (status = 200, description = "Quotes compared successfully", body = QuoteComparisonResponse),
+        (status = 400, description = "Invalid asset, amount or context"),
+        (status = 401, description = "Firm quotes requested without signing in"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "SEP-38"
+)]
+pub async fn compare_quotes(
+    State(comparer): State<Arc<QuoteComparer>>,
+    caller: Option<Extension<AuthUser>>,
+    Query(params): Query<QuoteComparisonQuery>,
+) -> ApiResult<Json<QuoteComparisonResponse>> {
+    // Firm quotes authenticate with anchors using the backend's own key
+    if params.firm && caller.is_none() {
+        return Err(ApiError::Unauthorized(
+            "firm quotes require signing in".to_string(),
+        ));
+    }
+    let sell_asset = params.sell_asset.trim();
+    let buy_asset = params.buy_asset.trim();
+    if sell_asset.is_empty() || buy_asset.is_empty() {
+        return Err(ApiError::BadRequest(
+            "sell_asset and buy_asset are required".to_string(),
+        ));
+    }
+    let sell_amount = params.sell_amount.trim();
+    if !sell_amount
+        .parse::<f64>()
+        .is_ok_and(|amount| amount.is_finite() && amount > 0.0)
+    {
+        return Err(ApiError::BadRequest(
+            "sell_amount must be greater than zero".to_string(),
+        ));
+    }
+    if !QUOTE_CONTEXTS.contains(&params.context.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "context must be one of {}",
+            QUOTE_CONTEXTS.join(", ")
+        )));
+    }
+
+    let request = QuoteComparisonRequest {
+        sell_asset: sell_asset.to_string(),
+        buy_asset: buy_asset.to_string(),
+        sell_amount: sell_amount.to_string(),
+        context: params.context,
+        firm: params.firm,
+    };
+    let quotes = comparer.compare(&request).await?;
+
+    Ok(Json(QuoteComparisonResponse {
+        sell_asset: request.sell_asset,
+        buy_asset: request.buy_asset,
+        sell_amount: request.sell_amount,
+        context: request.context,
+        firm: request.firm,
+        quotes,
+    }))
+}
+
+/// Create quote comparison routes
+pub fn routes(comparer: Arc<QuoteComparer>) -> Router {
+    Router::new()
+        .route("/api/sep38/compare", get(compare_quotes))
+        .with_state(comparer)
+}
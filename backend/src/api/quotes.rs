@@ -0,0 +1,104 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::models::asset::AssetId;
+use crate::services::quote_simulator::{CorridorQuote, QuoteSimulator};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QuoteQuery {
+    /// Asset being sent ("native" or "CODE:ISSUER")
+    #[param(
+        value_type = String,
+        example = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+    )]
+    pub from: AssetId,
+    /// Asset being received ("native" or "CODE:ISSUER")
+    #[param(value_type = String, example = "native")]
+    pub to: AssetId,
+    /// Amount of `from` to send
+    #[param(example = 50000.0)]
+    pub amount: f64,
+    /// Maximum acceptable slippage in percent (default: 1.0)
+    #[serde(default = "default_tolerance")]
+    #[param(example = 1.0)]
+    pub tolerance_percent: f64,
+}
+
+fn default_tolerance() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuoteResponse {
+    /// Simulated fill, slippage curve and maximum size under the tolerance
+    #[schema(value_type = Object)]
+    pub quote: CorridorQuote,
+    /// Timestamp of the response
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub timestamp: String,
+}
+
+/// Simulate a corridor quote and its slippage
+///
+/// Fills the requested amount against the order book and the liquidity
+/// pools for the pair, returning the expected output, a slippage curve at
+/// several sizes and the largest size that stays within the tolerance.
+///
+/// **DATA SOURCE: RPC + Database**
+/// - Order book bids from Horizon
+/// - Pool reserves from the database
+#[utoipa::path(
+    get,
+    path = "/api/quotes",
+    params(QuoteQuery),
+    responses(
+        (status = 200, description = "Quote simulated successfully", body = QuoteResponse),
+        (status = 400, description = "Invalid asset, amount or tolerance"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Quotes"
+)]
+pub async fn simulate_quote(
+    State(simulator): State<Arc<QuoteSimulator>>,
+    Query(params): Query<QuoteQuery>,
+) -> ApiResult<Json<QuoteResponse>> {
+    if params.amount <= 0.0 || !params.amount.is_finite() {
+        return Err(ApiError::BadRequest(
+            "amount must be a positive number".to_string(),
+        ));
+    }
+    if !(0.0..100.0).contains(&params.tolerance_percent) {
+        return Err(ApiError::BadRequest(
+            "tolerance_percent must be between 0 and 100".to_string(),
+        ));
+    }
+
+    let quote = simulator
+        .quote(
+            &params.from,
+            &params.to,
+            params.amount,
+            params.tolerance_percent,
+        )
+        .await?;
+
+    Ok(Json(QuoteResponse {
+        quote,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }))
+}
+
+/// Create quote simulation routes
+pub fn routes(simulator: Arc<QuoteSimulator>) -> Router {
+    Router::new()
+        .route("/", get(simulate_quote))
+        .with_state(simulator)
+}
@@ -0,0 +1,157 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::models::asset::{AssetId, AssetIdError, CorridorKey};
+use crate::services::rollup::{Resolution, RollupBucket, RollupEngine, RollupEntity};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RollupQuery {
+    /// Range start, RFC 3339 (default: 7 days before `end`)
+    pub start: Option<DateTime<Utc>>,
+    /// Range end, RFC 3339 (default: now)
+    pub end: Option<DateTime<Utc>>,
+    /// Bucket size: 5m, 1h, 1d or 1w (default: chosen from the range)
+    #[param(example = "1h")]
+    pub resolution: Option<String>,
+    /// Maximum buckets when the resolution is chosen automatically (default: 500)
+    #[param(example = 500)]
+    pub max_points: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RollupPoint {
+    pub bucket_start: DateTime<Utc>,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub success_rate: Option<f64>,
+    pub volume_usd: f64,
+    pub avg_latency_ms: Option<f64>,
+    /// Average, minimum and maximum of gauge samples (pool TVL in USD)
+    pub gauge_avg: Option<f64>,
+    pub gauge_min: Option<f64>,
+    pub gauge_max: Option<f64>,
+}
+
+impl From<&RollupBucket> for RollupPoint {
+    fn from(bucket: &RollupBucket) -> Self {
+        Self {
+            bucket_start: bucket.bucket_start,
+            total_transactions: bucket.total_transactions,
+            successful_transactions: bucket.successful_transactions,
+            success_rate: bucket.success_rate(),
+            volume_usd: bucket.volume_usd,
+            avg_latency_ms: bucket.avg_latency_ms(),
+            gauge_avg: bucket.gauge_avg(),
+            gauge_min: bucket.gauge_min,
+            gauge_max: bucket.gauge_max,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RollupResponse {
+    #[schema(example = "corridor")]
+    pub entity_type: String,
+    pub entity_id: String,
+    /// Resolution the series was served at
+    #[schema(example = "1h")]
+    pub resolution: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Non-empty buckets, oldest first
+    pub points: Vec<RollupPoint>,
+}
+
+/// Get a metric time series at a given resolution
+///
+/// Serves corridor, anchor, pool and asset metrics from 5m, 1h, 1d or 1w
+/// rollups. Without an explicit resolution, the finest one that is still
+/// retained for the range and fits in `max_points` buckets is used.
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/rollups/{entity_type}/{entity_id}",
+    params(
+        ("entity_type" = String, Path, description = "corridor, anchor, pool or asset"),
+        ("entity_id" = String, Path, description = "Corridor key, anchor id, pool id or CODE:ISSUER"),
+        RollupQuery
+    ),
+    responses(
+        (status = 200, description = "Series retrieved successfully", body = RollupResponse),
+        (status = 400, description = "Invalid entity type, resolution or range"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rollups"
+)]
+pub async fn get_rollup_series(
+    State(engine): State<Arc<RollupEngine>>,
+    Path((entity_type, entity_id)): Path<(String, String)>,
+    Query(params): Query<RollupQuery>,
+) -> ApiResult<Json<RollupResponse>> {
+    let entity: RollupEntity = entity_type
+        .parse()
+        .map_err(|e: anyhow::Error| ApiError::BadRequest(e.to_string()))?;
+    let entity_id =
+        canonical_entity_id(entity, &entity_id).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let resolution = params
+        .resolution
+        .as_deref()
+        .map(str::parse::<Resolution>)
+        .transpose()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let end = params.end.unwrap_or_else(Utc::now);
+    let start = params.start.unwrap_or(end - Duration::days(7));
+    if start > end {
+        return Err(ApiError::BadRequest(
+            "start must not be after end".to_string(),
+        ));
+    }
+
+    let series = engine
+        .query(
+            entity,
+            &entity_id,
+            start,
+            end,
+            resolution,
+            params.max_points.map(|n| n.clamp(1, 10_000)),
+        )
+        .await?;
+
+    Ok(Json(RollupResponse {
+        entity_type: series.entity_type.as_str().to_string(),
+        entity_id: series.entity_id,
+        resolution: series.resolution.as_str().to_string(),
+        start: series.start,
+        end: series.end,
+        points: series.buckets.iter().map(RollupPoint::from).collect(),
+    }))
+}
+
+/// Entity id in the form rollups are keyed by: undirected corridor keys and
+/// asset keys (`XLM:native` for lumens)
+fn canonical_entity_id(entity: RollupEntity, entity_id: &str) -> Result<String, AssetIdError> {
+    match entity {
+        RollupEntity::Corridor => Ok(entity_id.parse::<CorridorKey>()?.normalized().to_string()),
+        RollupEntity::Asset => Ok(entity_id.parse::<AssetId>()?.key()),
+        RollupEntity::Anchor | RollupEntity::Pool => Ok(entity_id.to_string()),
+    }
+}
+
+/// Create rollup routes
+pub fn routes(engine: Arc<RollupEngine>) -> Router {
+    Router::new()
+        .route("/:entity_type/:entity_id", get(get_rollup_series))
+        .with_state(engine)
+}
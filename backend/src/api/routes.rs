@@ -0,0 +1,116 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::models::asset::AssetId;
+use crate::services::route_finder::{RankedRoute, RouteFinder, RouteMode};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RoutesQuery {
+    /// Source asset ("native", "CODE:ISSUER" or a contract id)
+    #[param(value_type = String, example = "native")]
+    pub from: AssetId,
+    /// Destination asset ("native", "CODE:ISSUER" or a contract id)
+    #[param(
+        value_type = String,
+        example = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+    )]
+    pub to: AssetId,
+    /// Amount to send (strict_send) or to deliver (strict_receive)
+    #[param(example = 100.0)]
+    pub amount: f64,
+    /// Which side of the payment `amount` fixes (strict_send or strict_receive)
+    #[serde(default)]
+    #[param(value_type = Option<String>, example = "strict_send")]
+    pub mode: RouteMode,
+    /// Maximum number of routes to return (default: 5)
+    #[serde(default = "default_limit")]
+    #[param(example = 5)]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    5
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RoutesResponse {
+    /// Source asset as requested
+    #[schema(value_type = String, example = "native")]
+    pub from: AssetId,
+    /// Destination asset as requested
+    #[schema(
+        value_type = String,
+        example = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+    )]
+    pub to: AssetId,
+    #[schema(example = 100.0)]
+    pub amount: f64,
+    #[schema(value_type = String, example = "strict_send")]
+    pub mode: RouteMode,
+    /// Routes ordered best first
+    #[schema(value_type = Vec<Object>)]
+    pub routes: Vec<RankedRoute>,
+    /// Timestamp of the response
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub timestamp: String,
+}
+
+/// Find the best payment routes between two assets
+///
+/// Ranks candidate routes by effective rate, hop count, historical corridor
+/// success rate and liquidity depth.
+///
+/// **DATA SOURCE: RPC + Database**
+/// - Path finding from Horizon `/paths/strict-send` and `/paths/strict-receive`
+/// - Order book depth from Horizon
+/// - Pool reserves and corridor history from the database
+#[utoipa::path(
+    get,
+    path = "/api/routes",
+    params(RoutesQuery),
+    responses(
+        (status = 200, description = "Routes ranked successfully", body = RoutesResponse),
+        (status = 400, description = "Invalid asset or amount"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Routes"
+)]
+pub async fn find_routes(
+    State(finder): State<Arc<RouteFinder>>,
+    Query(params): Query<RoutesQuery>,
+) -> ApiResult<Json<RoutesResponse>> {
+    if params.amount <= 0.0 || !params.amount.is_finite() {
+        return Err(ApiError::BadRequest(
+            "amount must be a positive number".to_string(),
+        ));
+    }
+
+    let mut routes = finder
+        .find_routes(&params.from, &params.to, params.amount, params.mode)
+        .await?;
+    routes.truncate(params.limit.clamp(1, 20));
+
+    Ok(Json(RoutesResponse {
+        from: params.from,
+        to: params.to,
+        amount: params.amount,
+        mode: params.mode,
+        routes,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }))
+}
+
+/// Create route finding routes
+pub fn routes(finder: Arc<RouteFinder>) -> Router {
+    Router::new()
+        .route("/", get(find_routes))
+        .with_state(finder)
+}
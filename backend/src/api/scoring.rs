@@ -0,0 +1,91 @@
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::scoring::{ScoreBreakdown, ScoringEngine, ScoringModel};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScoringModelsResponse {
+    /// Every registered model version with its weights and thresholds
+    #[schema(value_type = Vec<Object>)]
+    pub models: Vec<ScoringModel>,
+    /// Version used for each model when none is requested
+    pub active: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EvaluateScoreRequest {
+    #[schema(example = "corridor_health")]
+    pub model_id: String,
+    /// Model version to use (default: active version)
+    #[schema(example = 1)]
+    pub version: Option<u32>,
+    /// Named inputs, as returned in a score breakdown
+    #[schema(value_type = Object)]
+    pub inputs: BTreeMap<String, f64>,
+}
+
+/// List scoring models
+///
+/// Returns the weights, normalizations and bands of every scoring model
+/// version so published scores can be checked.
+#[utoipa::path(
+    get,
+    path = "/api/scoring/models",
+    responses(
+        (status = 200, description = "Scoring models retrieved successfully", body = ScoringModelsResponse)
+    ),
+    tag = "Scoring"
+)]
+pub async fn list_models(State(engine): State<Arc<ScoringEngine>>) -> Json<ScoringModelsResponse> {
+    let models: Vec<ScoringModel> = engine.models().into_iter().cloned().collect();
+    let active = models
+        .iter()
+        .filter_map(|m| {
+            engine
+                .model(&m.id, None)
+                .map(|active| (m.id.clone(), active.version))
+        })
+        .collect();
+
+    Json(ScoringModelsResponse { models, active })
+}
+
+/// Evaluate a scoring model
+///
+/// Scores the given inputs with a model version, returning the same
+/// breakdown that accompanies published scores.
+#[utoipa::path(
+    post,
+    path = "/api/scoring/evaluate",
+    request_body = EvaluateScoreRequest,
+    responses(
+        (status = 200, description = "Score evaluated successfully"),
+        (status = 400, description = "Unknown model or missing input")
+    ),
+    tag = "Scoring"
+)]
+pub async fn evaluate_score(
+    State(engine): State<Arc<ScoringEngine>>,
+    Json(request): Json<EvaluateScoreRequest>,
+) -> ApiResult<Json<ScoreBreakdown>> {
+    engine
+        .score(&request.model_id, request.version, &request.inputs)
+        .map(Json)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+/// Create scoring routes
+pub fn routes(engine: Arc<ScoringEngine>) -> Router {
+    Router::new()
+        .route("/models", get(list_models))
+        .route("/evaluate", post(evaluate_score))
+        .with_state(engine)
+}
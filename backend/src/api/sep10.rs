@@ -0,0 +1,54 @@
+use axum::{extract::State, routing::post, Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::services::sep10_client::{Sep10Client, Sep10Token};
+use crate::services::signer::Keypair;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct Sep10TokenRequest {
+    /// Home domain of the anchor, whose stellar.toml names its web auth endpoint
+    #[schema(example = "testanchor.stellar.org")]
+    pub home_domain: String,
+    /// Secret seed (`S...`) of the account to authenticate; it only signs the
+    /// challenge and is not stored
+    pub secret_key: String,
+}
+
+/// Obtain a SEP-10 token from an anchor
+///
+/// The backend fetches the anchor's challenge, validates it against the
+/// anchor's stellar.toml, signs it with the given key and returns the JWT the
+/// anchor issues. Tokens are reused until shortly before they expire.
+/// Requires signing in; the home domain must be a public host name.
+#[utoipa::path(
+    post,
+    path = "/api/sep10/token",
+    request_body = Sep10TokenRequest,
+    responses(
+        (status = 200, description = "Token obtained successfully", body = Object),
+        (status = 400, description = "Invalid secret key"),
+        (status = 401, description = "Not signed in"),
+        (status = 500, description = "Anchor authentication failed")
+    ),
+    tag = "SEP-10"
+)]
+pub async fn post_token(
+    State(client): State<Arc<Sep10Client>>,
+    Json(request): Json<Sep10TokenRequest>,
+) -> ApiResult<Json<Sep10Token>> {
+    let key = Keypair::from_secret(&request.secret_key)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let token = client.token(&request.home_domain, Some(&key)).await?;
+
+    Ok(Json(token))
+}
+
+/// Create SEP-10 routes; to be served behind the auth middleware
+pub fn protected_routes(client: Arc<Sep10Client>) -> Router {
+    Router::new()
+        .route("/api/sep10/token", post(post_token))
+        .with_state(client)
+}
@@ -2,7 +2,7 @@
 //! Proxies requests to anchor transfer servers to avoid CORS and centralize auth.
 
 use axum::{
-    extract::{Query, State},
+    extract::{Extension, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -12,6 +12,18 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
+use tracing::warn;
+
+use crate::auth_middleware::AuthUser;
+use crate::services::anchor_registry::{
+    service_auth_domain, AnchorAuth, AnchorRegistry, ProxyAccess, ProxyAnchor, ProxyAuth,
+    ServiceAuthError,
+};
+use crate::services::sep10_client::Sep10Client;
+use crate::services::sep_endpoint_prober::SepEndpoint;
+use crate::services::transfer_tracker::{
+    NewTransfer, TransferReservation, TransferSep, TransferTracker,
+};
 
 /// Allowed transfer server hosts (env: SEP24_ALLOWED_ORIGINS, comma-separated).
 /// If unset, any origin is allowed (use in dev only).
@@ -37,6 +49,9 @@ fn is_origin_allowed(transfer_server: &str) -> bool {
 #[derive(Clone)]
 pub struct Sep24State {
     pub client: Arc<Client>,
+    pub sep10: Option<Arc<Sep10Client>>,
+    pub registry: Option<Arc<AnchorRegistry>>,
+    pub tracker: Option<Arc<TransferTracker>>,
 }
 
 impl Sep24State {
@@ -47,6 +62,139 @@ impl Sep24State {
             .unwrap_or_else(|_| Client::new());
         Self {
             client: Arc::new(client),
+            sep10: None,
+            registry: None,
+            tracker: None,
+        }
+    }
+
+    /// Obtain tokens through SEP-10 with the backend's key for signed-in
+    /// callers whose requests carry no `jwt`
+    pub fn with_sep10(mut self, sep10: Arc<Sep10Client>) -> Self {
+        self.sep10 = Some(sep10);
+        self
+    }
+
+    /// Restrict the proxy to enabled anchors and apply their auth and timeout
+    /// settings
+    pub fn with_registry(mut self, registry: Arc<AnchorRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Check `transfer_server` against the anchor registry and
+    /// `SEP24_ALLOWED_ORIGINS`; returns the enabled anchor serving it
+    async fn authorize(&self, transfer_server: &str) -> Result<Option<ProxyAnchor>, Sep24Error> {
+        let access = match &self.registry {
+            Some(registry) => registry
+                .authorize(SepEndpoint::Sep24, transfer_server, &allowed_origins())
+                .await
+                .map_err(|e| Sep24Error::Internal(e.to_string()))?,
+            None if is_origin_allowed(transfer_server) => ProxyAccess::Allowed,
+            None => ProxyAccess::Denied,
+        };
+        match access {
+            ProxyAccess::Registered(anchor) => Ok(Some(anchor)),
+            ProxyAccess::Allowed => Ok(None),
+            ProxyAccess::Denied => Err(Sep24Error::Forbidden(
+                "Transfer server not in allowed list".to_string(),
+            )),
+        }
+    }
+
+    /// Record initiated deposits and withdrawals and follow their status
+    pub fn with_tracker(mut self, tracker: Arc<TransferTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Reserve a tracked transfer; tracking problems never fail a request
+    fn reserve(&self) -> Option<TransferReservation> {
+        let tracker = self.tracker.as_ref()?;
+        tracker
+            .reserve()
+            .map_err(|e| warn!("Not tracking SEP-24 transfer: {}", e))
+            .ok()
+    }
+
+    /// Record a transfer the anchor accepted with the id in its response
+    async fn track(
+        &self,
+        reservation: Option<TransferReservation>,
+        response: &Value,
+        transfer: impl FnOnce(String) -> NewTransfer,
+    ) {
+        let (Some(tracker), Some(reservation)) = (&self.tracker, reservation) else {
+            return;
+        };
+        let Some(id) = response.get("id").and_then(Value::as_str) else {
+            warn!("Not tracking SEP-24 transfer: anchor response has no id");
+            return;
+        };
+        if let Err(e) = tracker.record(&reservation, transfer(id.to_string())).await {
+            warn!("Failed to record SEP-24 transfer {}: {}", id, e);
+        }
+    }
+
+    /// Bearer token for a request to the anchor: the caller's `jwt`, or one
+    /// obtained through SEP-10 with the backend's key for signed-in callers
+    async fn bearer(
+        &self,
+        caller: Option<&AuthUser>,
+        anchor: Option<&ProxyAnchor>,
+        transfer_server: &str,
+        jwt: Option<&str>,
+        home_domain: Option<&str>,
+    ) -> Result<Option<String>, Sep24Error> {
+        self.authenticate(caller, anchor, transfer_server, jwt, home_domain)
+            .await
+            .map(|auth| auth.token)
+    }
+
+    /// Like `bearer`, also telling whose token it is
+    async fn authenticate(
+        &self,
+        caller: Option<&AuthUser>,
+        anchor: Option<&ProxyAnchor>,
+        transfer_server: &str,
+        jwt: Option<&str>,
+        home_domain: Option<&str>,
+    ) -> Result<AnchorAuth, Sep24Error> {
+        if let Some(anchor) = anchor {
+            anchor
+                .check_home_domain(home_domain)
+                .map_err(Sep24Error::Forbidden)?;
+        }
+        if let Some(jwt) = jwt {
+            return Ok(AnchorAuth {
+                token: Some(jwt.to_string()),
+                service_domain: None,
+            });
+        }
+        let Some(sep10) = &self.sep10 else {
+            return Ok(AnchorAuth::default());
+        };
+        let domain = service_auth_domain(
+            sep10,
+            SepEndpoint::Sep24,
+            transfer_server,
+            anchor,
+            home_domain,
+            caller.is_some(),
+        )
+        .await?;
+        match domain {
+            Some(domain) => {
+                let token = sep10
+                    .token(&domain, None)
+                    .await
+                    .map_err(|e| Sep24Error::Auth(e.to_string()))?;
+                Ok(AnchorAuth {
+                    token: Some(token.token),
+                    service_domain: Some(domain),
+                })
+            }
+            None => Ok(AnchorAuth::default()),
         }
     }
 }
@@ -66,15 +214,9 @@ pub async fn get_info(
     State(state): State<Sep24State>,
     Query(q): Query<InfoQuery>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&q.transfer_server) {
-        return Err(Sep24Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&q.transfer_server).await?;
     let url = format!("{}/info", base_url(&q.transfer_server));
-    let resp = state
-        .client
-        .get(&url)
+    let resp = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(&url))
         .send()
         .await
         .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
@@ -112,29 +254,40 @@ pub struct DepositInteractiveBody {
     /// JWT from SEP-10 (optional for some anchors)
     #[serde(default)]
     pub jwt: Option<String>,
+    /// Anchor home domain; without `jwt`, signed-in callers are authenticated
+    /// with the backend's own key through SEP-10. Only opts in: the domain
+    /// must be the anchor's own.
+    #[serde(default)]
+    pub home_domain: Option<String>,
     #[serde(flatten)]
     pub extra: Value,
 }
 
 pub async fn post_deposit_interactive(
     State(state): State<Sep24State>,
+    caller: Option<Extension<AuthUser>>,
     Json(body): Json<DepositInteractiveBody>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&body.transfer_server) {
-        return Err(Sep24Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&body.transfer_server).await?;
     let url = format!(
         "{}/transactions/deposit/interactive",
         base_url(&body.transfer_server)
     );
 
-    let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.post(&url));
+    let auth = state
+        .authenticate(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &body.transfer_server,
+            body.jwt.as_deref(),
+            body.home_domain.as_deref(),
+        )
+        .await?;
+    if let Some(jwt) = &auth.token {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
-    let payload = serde_json::json!({
+    let mut payload = serde_json::json!({
         "asset_code": body.asset_code,
         "account": body.account,
         "memo": body.memo,
@@ -143,6 +296,10 @@ pub async fn post_deposit_interactive(
         "amount": body.amount,
         "lang": body.lang,
     });
+    let reservation = state.reserve();
+    if let Some(url) = reservation.as_ref().and_then(|r| r.callback_url.as_ref()) {
+        payload["on_change_callback"] = Value::String(url.clone());
+    }
     let resp = req
         .json(&payload)
         .send()
@@ -158,6 +315,20 @@ pub async fn post_deposit_interactive(
     if !status.is_success() {
         return Err(Sep24Error::Anchor(status.as_u16(), data));
     }
+    state
+        .track(reservation, &data, |id| NewTransfer {
+            sep: TransferSep::Sep24,
+            kind: "deposit".to_string(),
+            transfer_server: body.transfer_server,
+            anchor_transaction_id: id,
+            owner: caller.map(|Extension(caller)| caller.user_id),
+            home_domain: auth.service_domain.clone(),
+            caller_token: auth.is_caller_token(),
+            asset_code: body.asset_code,
+            amount: body.amount,
+            status: "incomplete".to_string(),
+        })
+        .await;
     Ok(Json(data))
 }
 
@@ -183,29 +354,37 @@ pub struct WithdrawInteractiveBody {
     pub lang: Option<String>,
     #[serde(default)]
     pub jwt: Option<String>,
+    #[serde(default)]
+    pub home_domain: Option<String>,
     #[serde(flatten)]
     pub extra: Value,
 }
 
 pub async fn post_withdraw_interactive(
     State(state): State<Sep24State>,
+    caller: Option<Extension<AuthUser>>,
     Json(body): Json<WithdrawInteractiveBody>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&body.transfer_server) {
-        return Err(Sep24Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&body.transfer_server).await?;
     let url = format!(
         "{}/transactions/withdraw/interactive",
         base_url(&body.transfer_server)
     );
 
-    let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.post(&url));
+    let auth = state
+        .authenticate(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &body.transfer_server,
+            body.jwt.as_deref(),
+            body.home_domain.as_deref(),
+        )
+        .await?;
+    if let Some(jwt) = &auth.token {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
-    let payload = serde_json::json!({
+    let mut payload = serde_json::json!({
         "asset_code": body.asset_code,
         "account": body.account,
         "memo": body.memo,
@@ -215,6 +394,10 @@ pub async fn post_withdraw_interactive(
         "amount": body.amount,
         "lang": body.lang,
     });
+    let reservation = state.reserve();
+    if let Some(url) = reservation.as_ref().and_then(|r| r.callback_url.as_ref()) {
+        payload["on_change_callback"] = Value::String(url.clone());
+    }
     let resp = req
         .json(&payload)
         .send()
@@ -230,6 +413,20 @@ pub async fn post_withdraw_interactive(
     if !status.is_success() {
         return Err(Sep24Error::Anchor(status.as_u16(), data));
     }
+    state
+        .track(reservation, &data, |id| NewTransfer {
+            sep: TransferSep::Sep24,
+            kind: "withdrawal".to_string(),
+            transfer_server: body.transfer_server,
+            anchor_transaction_id: id,
+            owner: caller.map(|Extension(caller)| caller.user_id),
+            home_domain: auth.service_domain.clone(),
+            caller_token: auth.is_caller_token(),
+            asset_code: body.asset_code,
+            amount: body.amount,
+            status: "incomplete".to_string(),
+        })
+        .await;
     Ok(Json(data))
 }
 
@@ -240,6 +437,8 @@ pub struct TransactionsQuery {
     #[serde(default)]
     pub jwt: Option<String>,
     #[serde(default)]
+    pub home_domain: Option<String>,
+    #[serde(default)]
     pub asset_code: Option<String>,
     #[serde(default)]
     pub kind: Option<String>,
@@ -251,13 +450,10 @@ pub struct TransactionsQuery {
 
 pub async fn get_transactions(
     State(state): State<Sep24State>,
+    caller: Option<Extension<AuthUser>>,
     Query(q): Query<TransactionsQuery>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&q.transfer_server) {
-        return Err(Sep24Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&q.transfer_server).await?;
     let base = base_url(&q.transfer_server);
     let mut url = format!("{}/transactions?", base);
     if let Some(c) = &q.asset_code {
@@ -274,11 +470,23 @@ pub async fn get_transactions(
     }
     let url = url.trim_end_matches('&').trim_end_matches('?');
 
-    let mut req = state.client.get(url);
-    if let Some(jwt) = &q.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(url));
+    if let Some(jwt) = state
+        .bearer(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &q.transfer_server,
+            q.jwt.as_deref(),
+            q.home_domain.as_deref(),
+        )
+        .await?
+    {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
-    let resp = req.send().await.map_err(|e| Sep24Error::Proxy(e.to_string()))?;
+    let resp = req
+        .send()
+        .await
+        .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
 
     let status = resp.status();
     let data = resp
@@ -299,28 +507,39 @@ pub struct TransactionQuery {
     pub id: String,
     #[serde(default)]
     pub jwt: Option<String>,
+    #[serde(default)]
+    pub home_domain: Option<String>,
 }
 
 pub async fn get_transaction(
     State(state): State<Sep24State>,
+    caller: Option<Extension<AuthUser>>,
     Query(q): Query<TransactionQuery>,
 ) -> Result<Json<Value>, Sep24Error> {
-    if !is_origin_allowed(&q.transfer_server) {
-        return Err(Sep24Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&q.transfer_server).await?;
     let url = format!(
         "{}/transaction?id={}",
         base_url(&q.transfer_server),
         urlencoding::encode(&q.id)
     );
 
-    let mut req = state.client.get(&url);
-    if let Some(jwt) = &q.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(&url));
+    if let Some(jwt) = state
+        .bearer(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &q.transfer_server,
+            q.jwt.as_deref(),
+            q.home_domain.as_deref(),
+        )
+        .await?
+    {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
-    let resp = req.send().await.map_err(|e| Sep24Error::Proxy(e.to_string()))?;
+    let resp = req
+        .send()
+        .await
+        .map_err(|e| Sep24Error::Proxy(e.to_string()))?;
 
     let status = resp.status();
     let data = resp
@@ -334,31 +553,82 @@ pub async fn get_transaction(
     Ok(Json(data))
 }
 
-/// List known SEP-24-enabled anchors (from env or static list).
+/// List known SEP-24-enabled anchors (from the anchor registry and env).
 /// GET /api/sep24/anchors
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Sep24AnchorInfo {
     pub name: String,
     pub transfer_server: String,
     pub home_domain: Option<String>,
+    /// Registry anchor; absent for `SEP24_ANCHORS` presets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<ProxyAuth>,
 }
 
-pub async fn list_anchors() -> Json<Value> {
-    // Env: SEP24_ANCHORS = JSON array of { "name", "transfer_server", "home_domain" }
-    let anchors: Vec<Sep24AnchorInfo> = if let Ok(s) = std::env::var("SEP24_ANCHORS") {
-        serde_json::from_str(&s).unwrap_or_default()
-    } else {
-        // Default: no anchors; frontend can still use custom transfer_server
-        vec![]
+impl From<ProxyAnchor> for Sep24AnchorInfo {
+    fn from(anchor: ProxyAnchor) -> Self {
+        Self {
+            name: anchor.name,
+            transfer_server: anchor.url,
+            home_domain: anchor.home_domain,
+            anchor_id: Some(anchor.anchor_id),
+            auth: Some(anchor.auth),
+        }
+    }
+}
+
+pub async fn list_anchors(State(state): State<Sep24State>) -> Result<Json<Value>, Sep24Error> {
+    // Anchors enabled in the registry come first
+    let mut anchors: Vec<Sep24AnchorInfo> = match &state.registry {
+        Some(registry) => registry
+            .proxy_anchors(SepEndpoint::Sep24)
+            .await
+            .map_err(|e| Sep24Error::Internal(e.to_string()))?
+            .into_iter()
+            .map(Sep24AnchorInfo::from)
+            .collect(),
+        None => vec![],
     };
-    Json(serde_json::json!({ "anchors": anchors }))
+    // Env: SEP24_ANCHORS = JSON array of { "name", "transfer_server", "home_domain" }
+    if let Ok(s) = std::env::var("SEP24_ANCHORS") {
+        let presets: Vec<Sep24AnchorInfo> = serde_json::from_str(&s).unwrap_or_default();
+        for preset in presets {
+            let listed = anchors
+                .iter()
+                .any(|a| base_url(&a.transfer_server) == base_url(&preset.transfer_server));
+            if !listed {
+                anchors.push(preset);
+            }
+        }
+    }
+    Ok(Json(serde_json::json!({ "anchors": anchors })))
 }
 
 #[derive(Debug)]
 pub enum Sep24Error {
     Forbidden(String),
+    /// Only signed-in callers may use the backend's SEP-10 key
+    Unauthorized(String),
     Proxy(String),
+    /// SEP-10 authentication with the anchor failed
+    Auth(String),
     Anchor(u16, Value),
+    /// The anchor registry could not be read
+    Internal(String),
+}
+
+impl From<ServiceAuthError> for Sep24Error {
+    fn from(e: ServiceAuthError) -> Self {
+        match e {
+            ServiceAuthError::SignInRequired => Sep24Error::Unauthorized(
+                "sign in, or send a jwt, to authenticate with the anchor".to_string(),
+            ),
+            ServiceAuthError::Mismatch(msg) => Sep24Error::Forbidden(msg),
+            ServiceAuthError::Unavailable(msg) => Sep24Error::Auth(msg),
+        }
+    }
 }
 
 impl IntoResponse for Sep24Error {
@@ -368,10 +638,22 @@ impl IntoResponse for Sep24Error {
                 StatusCode::FORBIDDEN,
                 serde_json::json!({ "error": "forbidden", "message": msg }),
             ),
+            Sep24Error::Unauthorized(msg) => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({ "error": "unauthorized", "message": msg }),
+            ),
             Sep24Error::Proxy(msg) => (
                 StatusCode::BAD_GATEWAY,
                 serde_json::json!({ "error": "proxy", "message": msg }),
             ),
+            Sep24Error::Auth(msg) => (
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({ "error": "sep10", "message": msg }),
+            ),
+            Sep24Error::Internal(msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({ "error": "internal", "message": msg }),
+            ),
             Sep24Error::Anchor(code, data) => {
                 let status = StatusCode::from_u16(*code).unwrap_or(StatusCode::BAD_GATEWAY);
                 (status, data.clone())
@@ -382,8 +664,15 @@ impl IntoResponse for Sep24Error {
 }
 
 /// Build SEP-24 API router
-pub fn routes() -> axum::Router {
-    let state = Sep24State::new();
+pub fn routes(
+    sep10: Arc<Sep10Client>,
+    tracker: Arc<TransferTracker>,
+    registry: Arc<AnchorRegistry>,
+) -> axum::Router {
+    let state = Sep24State::new()
+        .with_sep10(sep10)
+        .with_tracker(tracker)
+        .with_registry(registry);
     axum::Router::new()
         .route("/api/sep24/info", axum::routing::get(get_info))
         .route(
@@ -394,8 +683,14 @@ pub fn routes() -> axum::Router {
             "/api/sep24/withdraw/interactive",
             axum::routing::post(post_withdraw_interactive),
         )
-        .route("/api/sep24/transactions", axum::routing::get(get_transactions))
-        .route("/api/sep24/transaction", axum::routing::get(get_transaction))
+        .route(
+            "/api/sep24/transactions",
+            axum::routing::get(get_transactions),
+        )
+        .route(
+            "/api/sep24/transaction",
+            axum::routing::get(get_transaction),
+        )
         .route("/api/sep24/anchors", axum::routing::get(list_anchors))
         .with_state(state)
 }
@@ -406,13 +701,22 @@ mod tests {
 
     #[test]
     fn test_base_url() {
-        assert_eq!(base_url("https://api.example.com"), "https://api.example.com");
-        assert_eq!(base_url("https://api.example.com/"), "https://api.example.com");
+        assert_eq!(
+            base_url("https://api.example.com"),
+            "https://api.example.com"
+        );
+        assert_eq!(
+            base_url("https://api.example.com/"),
+            "https://api.example.com"
+        );
     }
 
     #[test]
     fn test_base_url_trim() {
-        assert_eq!(base_url("  https://api.example.com  "), "https://api.example.com");
+        assert_eq!(
+            base_url("  https://api.example.com  "),
+            "https://api.example.com"
+        );
     }
 
     #[test]
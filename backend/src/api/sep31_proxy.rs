@@ -2,7 +2,7 @@
 //! Proxies requests to anchor SEP-31 endpoints for quotes, payments, and KYC.
 
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -12,6 +12,16 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
+use tracing::warn;
+
+use crate::auth_middleware::AuthUser;
+use crate::services::anchor_registry::{
+    service_auth_domain, AnchorAuth, AnchorRegistry, ProxyAccess, ProxyAnchor, ProxyAuth,
+    ServiceAuthError,
+};
+use crate::services::sep10_client::Sep10Client;
+use crate::services::sep_endpoint_prober::SepEndpoint;
+use crate::services::transfer_tracker::{NewTransfer, TransferSep, TransferTracker};
 
 fn allowed_origins() -> Vec<String> {
     std::env::var("SEP31_ALLOWED_ORIGINS")
@@ -32,6 +42,9 @@ fn is_origin_allowed(transfer_server: &str) -> bool {
 #[derive(Clone)]
 pub struct Sep31State {
     pub client: Arc<Client>,
+    pub sep10: Option<Arc<Sep10Client>>,
+    pub registry: Option<Arc<AnchorRegistry>>,
+    pub tracker: Option<Arc<TransferTracker>>,
 }
 
 impl Sep31State {
@@ -42,6 +55,111 @@ impl Sep31State {
             .unwrap_or_else(|_| Client::new());
         Self {
             client: Arc::new(client),
+            sep10: None,
+            registry: None,
+            tracker: None,
+        }
+    }
+
+    /// Obtain tokens through SEP-10 with the backend's key for signed-in
+    /// callers whose requests carry no `jwt`
+    pub fn with_sep10(mut self, sep10: Arc<Sep10Client>) -> Self {
+        self.sep10 = Some(sep10);
+        self
+    }
+
+    /// Restrict the proxy to enabled anchors and apply their auth and timeout
+    /// settings
+    pub fn with_registry(mut self, registry: Arc<AnchorRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Check `transfer_server` against the anchor registry and
+    /// `SEP31_ALLOWED_ORIGINS`; returns the enabled anchor serving it
+    async fn authorize(&self, transfer_server: &str) -> Result<Option<ProxyAnchor>, Sep31Error> {
+        let access = match &self.registry {
+            Some(registry) => registry
+                .authorize(SepEndpoint::Sep31, transfer_server, &allowed_origins())
+                .await
+                .map_err(|e| Sep31Error::Internal(e.to_string()))?,
+            None if is_origin_allowed(transfer_server) => ProxyAccess::Allowed,
+            None => ProxyAccess::Denied,
+        };
+        match access {
+            ProxyAccess::Registered(anchor) => Ok(Some(anchor)),
+            ProxyAccess::Allowed => Ok(None),
+            ProxyAccess::Denied => Err(Sep31Error::Forbidden(
+                "Transfer server not in allowed list".to_string(),
+            )),
+        }
+    }
+
+    /// Record created payments and follow their status
+    pub fn with_tracker(mut self, tracker: Arc<TransferTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Bearer token for a request to the anchor: the caller's `jwt`, or one
+    /// obtained through SEP-10 with the backend's key for signed-in callers
+    async fn bearer(
+        &self,
+        caller: Option<&AuthUser>,
+        anchor: Option<&ProxyAnchor>,
+        transfer_server: &str,
+        jwt: Option<&str>,
+        home_domain: Option<&str>,
+    ) -> Result<Option<String>, Sep31Error> {
+        self.authenticate(caller, anchor, transfer_server, jwt, home_domain)
+            .await
+            .map(|auth| auth.token)
+    }
+
+    /// Like `bearer`, also telling whose token it is
+    async fn authenticate(
+        &self,
+        caller: Option<&AuthUser>,
+        anchor: Option<&ProxyAnchor>,
+        transfer_server: &str,
+        jwt: Option<&str>,
+        home_domain: Option<&str>,
+    ) -> Result<AnchorAuth, Sep31Error> {
+        if let Some(anchor) = anchor {
+            anchor
+                .check_home_domain(home_domain)
+                .map_err(Sep31Error::Forbidden)?;
+        }
+        if let Some(jwt) = jwt {
+            return Ok(AnchorAuth {
+                token: Some(jwt.to_string()),
+                service_domain: None,
+            });
+        }
+        let Some(sep10) = &self.sep10 else {
+            return Ok(AnchorAuth::default());
+        };
+        let domain = service_auth_domain(
+            sep10,
+            SepEndpoint::Sep31,
+            transfer_server,
+            anchor,
+            home_domain,
+            caller.is_some(),
+        )
+        .await?;
+        match domain {
+            Some(domain) => {
+                let token = sep10
+                    .token(&domain, None)
+                    .await
+                    .map_err(|e| Sep31Error::Auth(e.to_string()))?;
+                Ok(AnchorAuth {
+                    token: Some(token.token),
+                    service_domain: Some(domain),
+                })
+            }
+            None => Ok(AnchorAuth::default()),
         }
     }
 }
@@ -60,15 +178,9 @@ pub async fn get_info(
     State(state): State<Sep31State>,
     Query(q): Query<InfoQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
-        return Err(Sep31Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&q.transfer_server).await?;
     let url = format!("{}/info", base_url(&q.transfer_server));
-    let resp = state
-        .client
-        .get(&url)
+    let resp = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(&url))
         .send()
         .await
         .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
@@ -91,22 +203,33 @@ pub struct QuoteBody {
     pub transfer_server: String,
     #[serde(default)]
     pub jwt: Option<String>,
+    /// Anchor home domain; without `jwt`, signed-in callers are authenticated
+    /// with the backend's own key through SEP-10. Only opts in: the domain
+    /// must be the anchor's own.
+    #[serde(default)]
+    pub home_domain: Option<String>,
     #[serde(flatten)]
     pub payload: Value,
 }
 
 pub async fn post_quote(
     State(state): State<Sep31State>,
+    caller: Option<Extension<AuthUser>>,
     Json(body): Json<QuoteBody>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&body.transfer_server) {
-        return Err(Sep31Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&body.transfer_server).await?;
     let url = format!("{}/quote", base_url(&body.transfer_server));
-    let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.post(&url));
+    if let Some(jwt) = state
+        .bearer(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &body.transfer_server,
+            body.jwt.as_deref(),
+            body.home_domain.as_deref(),
+        )
+        .await?
+    {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
     let resp = req
@@ -133,22 +256,30 @@ pub struct CreateTransactionBody {
     pub transfer_server: String,
     #[serde(default)]
     pub jwt: Option<String>,
+    #[serde(default)]
+    pub home_domain: Option<String>,
     #[serde(flatten)]
     pub payload: Value,
 }
 
 pub async fn post_transaction(
     State(state): State<Sep31State>,
+    caller: Option<Extension<AuthUser>>,
     Json(body): Json<CreateTransactionBody>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&body.transfer_server) {
-        return Err(Sep31Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&body.transfer_server).await?;
     let url = format!("{}/transactions", base_url(&body.transfer_server));
-    let mut req = state.client.post(&url);
-    if let Some(jwt) = &body.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.post(&url));
+    let auth = state
+        .authenticate(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &body.transfer_server,
+            body.jwt.as_deref(),
+            body.home_domain.as_deref(),
+        )
+        .await?;
+    if let Some(jwt) = &auth.token {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
     let resp = req
@@ -166,9 +297,71 @@ pub async fn post_transaction(
     if !status.is_success() {
         return Err(Sep31Error::Anchor(status.as_u16(), data));
     }
+    if let Some(tracker) = &state.tracker {
+        track_transaction(tracker, caller.as_deref(), &body, &data, &auth).await;
+    }
     Ok(Json(data))
 }
 
+/// Record a payment the anchor accepted and hand it the callback URL.
+/// Tracking problems never fail the request.
+async fn track_transaction(
+    tracker: &TransferTracker,
+    caller: Option<&AuthUser>,
+    body: &CreateTransactionBody,
+    response: &Value,
+    auth: &AnchorAuth,
+) {
+    let Some(id) = response.get("id").and_then(Value::as_str) else {
+        warn!("Not tracking SEP-31 transaction: anchor response has no id");
+        return;
+    };
+    let reservation = match tracker.reserve() {
+        Ok(reservation) => reservation,
+        Err(e) => {
+            warn!("Not tracking SEP-31 transaction {}: {}", id, e);
+            return;
+        }
+    };
+    let text = |field: &str| match body.payload.get(field) {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    };
+    let transfer = NewTransfer {
+        sep: TransferSep::Sep31,
+        kind: "send".to_string(),
+        transfer_server: body.transfer_server.clone(),
+        anchor_transaction_id: id.to_string(),
+        owner: caller.map(|caller| caller.user_id.clone()),
+        home_domain: auth.service_domain.clone(),
+        caller_token: auth.is_caller_token(),
+        asset_code: text("asset_code"),
+        amount: text("amount"),
+        status: "pending_sender".to_string(),
+    };
+    if let Err(e) = tracker.record(&reservation, transfer).await {
+        warn!("Failed to record SEP-31 transaction {}: {}", id, e);
+        return;
+    }
+    if let Some(callback_url) = &reservation.callback_url {
+        if let Err(e) = tracker
+            .register_sep31_callback(
+                &body.transfer_server,
+                id,
+                callback_url,
+                auth.token.as_deref(),
+            )
+            .await
+        {
+            warn!(
+                "Failed to register callback of SEP-31 transaction {}: {}",
+                id, e
+            );
+        }
+    }
+}
+
 /// GET /api/sep31/transactions?transfer_server=&jwt=&...
 #[derive(Debug, Deserialize)]
 pub struct ListTransactionsQuery {
@@ -176,6 +369,8 @@ pub struct ListTransactionsQuery {
     #[serde(default)]
     pub jwt: Option<String>,
     #[serde(default)]
+    pub home_domain: Option<String>,
+    #[serde(default)]
     pub status: Option<String>,
     #[serde(default)]
     pub limit: Option<u32>,
@@ -185,13 +380,10 @@ pub struct ListTransactionsQuery {
 
 pub async fn get_transactions(
     State(state): State<Sep31State>,
+    caller: Option<Extension<AuthUser>>,
     Query(q): Query<ListTransactionsQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
-        return Err(Sep31Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&q.transfer_server).await?;
     let base = base_url(&q.transfer_server);
     let mut url = format!("{}/transactions?", base);
     if let Some(s) = &q.status {
@@ -205,11 +397,23 @@ pub async fn get_transactions(
     }
     let url = url.trim_end_matches('&').trim_end_matches('?');
 
-    let mut req = state.client.get(url);
-    if let Some(jwt) = &q.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(url));
+    if let Some(jwt) = state
+        .bearer(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &q.transfer_server,
+            q.jwt.as_deref(),
+            q.home_domain.as_deref(),
+        )
+        .await?
+    {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
-    let resp = req.send().await.map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = req
+        .send()
+        .await
+        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
 
     let status = resp.status();
     let data = resp
@@ -229,25 +433,40 @@ pub struct GetTransactionQuery {
     pub transfer_server: String,
     #[serde(default)]
     pub jwt: Option<String>,
+    #[serde(default)]
+    pub home_domain: Option<String>,
 }
 
 pub async fn get_transaction(
     State(state): State<Sep31State>,
+    caller: Option<Extension<AuthUser>>,
     Path(id): Path<String>,
     Query(q): Query<GetTransactionQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
-        return Err(Sep31Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
-    let url = format!("{}/transactions/{}", base_url(&q.transfer_server), urlencoding::encode(&id));
+    let anchor = state.authorize(&q.transfer_server).await?;
+    let url = format!(
+        "{}/transactions/{}",
+        base_url(&q.transfer_server),
+        urlencoding::encode(&id)
+    );
 
-    let mut req = state.client.get(&url);
-    if let Some(jwt) = &q.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(&url));
+    if let Some(jwt) = state
+        .bearer(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &q.transfer_server,
+            q.jwt.as_deref(),
+            q.home_domain.as_deref(),
+        )
+        .await?
+    {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
-    let resp = req.send().await.map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = req
+        .send()
+        .await
+        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
 
     let status = resp.status();
     let data = resp
@@ -267,29 +486,40 @@ pub struct CustomerQuery {
     pub transfer_server: String,
     #[serde(default)]
     pub jwt: Option<String>,
+    #[serde(default)]
+    pub home_domain: Option<String>,
     pub id: String,
 }
 
 pub async fn get_customer(
     State(state): State<Sep31State>,
+    caller: Option<Extension<AuthUser>>,
     Query(q): Query<CustomerQuery>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&q.transfer_server) {
-        return Err(Sep31Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&q.transfer_server).await?;
     let url = format!(
         "{}/customer?id={}",
         base_url(&q.transfer_server),
         urlencoding::encode(&q.id)
     );
 
-    let mut req = state.client.get(&url);
-    if let Some(jwt) = &q.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(&url));
+    if let Some(jwt) = state
+        .bearer(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &q.transfer_server,
+            q.jwt.as_deref(),
+            q.home_domain.as_deref(),
+        )
+        .await?
+    {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
-    let resp = req.send().await.map_err(|e| Sep31Error::Proxy(e.to_string()))?;
+    let resp = req
+        .send()
+        .await
+        .map_err(|e| Sep31Error::Proxy(e.to_string()))?;
 
     let status = resp.status();
     let data = resp
@@ -309,22 +539,30 @@ pub struct PutCustomerBody {
     pub transfer_server: String,
     #[serde(default)]
     pub jwt: Option<String>,
+    #[serde(default)]
+    pub home_domain: Option<String>,
     #[serde(flatten)]
     pub payload: Value,
 }
 
 pub async fn put_customer(
     State(state): State<Sep31State>,
+    caller: Option<Extension<AuthUser>>,
     Json(body): Json<PutCustomerBody>,
 ) -> Result<Json<Value>, Sep31Error> {
-    if !is_origin_allowed(&body.transfer_server) {
-        return Err(Sep31Error::Forbidden(
-            "Transfer server not in allowed list".to_string(),
-        ));
-    }
+    let anchor = state.authorize(&body.transfer_server).await?;
     let url = format!("{}/customer", base_url(&body.transfer_server));
-    let mut req = state.client.put(&url);
-    if let Some(jwt) = &body.jwt {
+    let mut req = ProxyAnchor::with_timeout(anchor.as_ref(), state.client.put(&url));
+    if let Some(jwt) = state
+        .bearer(
+            caller.as_deref(),
+            anchor.as_ref(),
+            &body.transfer_server,
+            body.jwt.as_deref(),
+            body.home_domain.as_deref(),
+        )
+        .await?
+    {
         req = req.header("Authorization", format!("Bearer {}", jwt));
     }
     let resp = req
@@ -351,22 +589,75 @@ pub struct Sep31AnchorInfo {
     pub name: String,
     pub transfer_server: String,
     pub home_domain: Option<String>,
+    /// Registry anchor; absent for `SEP31_ANCHORS` presets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<ProxyAuth>,
+}
+
+impl From<ProxyAnchor> for Sep31AnchorInfo {
+    fn from(anchor: ProxyAnchor) -> Self {
+        Self {
+            name: anchor.name,
+            transfer_server: anchor.url,
+            home_domain: anchor.home_domain,
+            anchor_id: Some(anchor.anchor_id),
+            auth: Some(anchor.auth),
+        }
+    }
 }
 
-pub async fn list_anchors() -> Json<Value> {
-    let anchors: Vec<Sep31AnchorInfo> = if let Ok(s) = std::env::var("SEP31_ANCHORS") {
-        serde_json::from_str(&s).unwrap_or_default()
-    } else {
-        vec![]
+pub async fn list_anchors(State(state): State<Sep31State>) -> Result<Json<Value>, Sep31Error> {
+    // Anchors enabled in the registry come first
+    let mut anchors: Vec<Sep31AnchorInfo> = match &state.registry {
+        Some(registry) => registry
+            .proxy_anchors(SepEndpoint::Sep31)
+            .await
+            .map_err(|e| Sep31Error::Internal(e.to_string()))?
+            .into_iter()
+            .map(Sep31AnchorInfo::from)
+            .collect(),
+        None => vec![],
     };
-    Json(serde_json::json!({ "anchors": anchors }))
+    // Env: SEP31_ANCHORS = JSON array of { "name", "transfer_server", "home_domain" }
+    if let Ok(s) = std::env::var("SEP31_ANCHORS") {
+        let presets: Vec<Sep31AnchorInfo> = serde_json::from_str(&s).unwrap_or_default();
+        for preset in presets {
+            let listed = anchors
+                .iter()
+                .any(|a| base_url(&a.transfer_server) == base_url(&preset.transfer_server));
+            if !listed {
+                anchors.push(preset);
+            }
+        }
+    }
+    Ok(Json(serde_json::json!({ "anchors": anchors })))
 }
 
 #[derive(Debug)]
 pub enum Sep31Error {
     Forbidden(String),
+    /// Only signed-in callers may use the backend's SEP-10 key
+    Unauthorized(String),
     Proxy(String),
+    /// SEP-10 authentication with the anchor failed
+    Auth(String),
     Anchor(u16, Value),
+    /// The anchor registry could not be read
+    Internal(String),
+}
+
+impl From<ServiceAuthError> for Sep31Error {
+    fn from(e: ServiceAuthError) -> Self {
+        match e {
+            ServiceAuthError::SignInRequired => Sep31Error::Unauthorized(
+                "sign in, or send a jwt, to authenticate with the anchor".to_string(),
+            ),
+            ServiceAuthError::Mismatch(msg) => Sep31Error::Forbidden(msg),
+            ServiceAuthError::Unavailable(msg) => Sep31Error::Auth(msg),
+        }
+    }
 }
 
 impl IntoResponse for Sep31Error {
@@ -376,10 +667,22 @@ impl IntoResponse for Sep31Error {
                 StatusCode::FORBIDDEN,
                 serde_json::json!({ "error": "forbidden", "message": msg }),
             ),
+            Sep31Error::Unauthorized(msg) => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({ "error": "unauthorized", "message": msg }),
+            ),
             Sep31Error::Proxy(msg) => (
                 StatusCode::BAD_GATEWAY,
                 serde_json::json!({ "error": "proxy", "message": msg }),
             ),
+            Sep31Error::Auth(msg) => (
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({ "error": "sep10", "message": msg }),
+            ),
+            Sep31Error::Internal(msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({ "error": "internal", "message": msg }),
+            ),
             Sep31Error::Anchor(code, data) => {
                 let status = StatusCode::from_u16(*code).unwrap_or(StatusCode::BAD_GATEWAY);
                 (status, data.clone())
@@ -389,8 +692,15 @@ impl IntoResponse for Sep31Error {
     }
 }
 
-pub fn routes() -> axum::Router {
-    let state = Sep31State::new();
+pub fn routes(
+    sep10: Arc<Sep10Client>,
+    tracker: Arc<TransferTracker>,
+    registry: Arc<AnchorRegistry>,
+) -> axum::Router {
+    let state = Sep31State::new()
+        .with_sep10(sep10)
+        .with_tracker(tracker)
+        .with_registry(registry);
     axum::Router::new()
         .route("/api/sep31/info", axum::routing::get(get_info))
         .route("/api/sep31/quote", axum::routing::post(post_quote))
@@ -420,7 +730,10 @@ mod tests {
             base_url("https://api.example.com/sep31"),
             "https://api.example.com/sep31"
         );
-        assert_eq!(base_url("https://api.example.com/"), "https://api.example.com");
+        assert_eq!(
+            base_url("https://api.example.com/"),
+            "https://api.example.com"
+        );
     }
 
     #[test]
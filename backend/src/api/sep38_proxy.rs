@@ -0,0 +1,349 @@
+//! SEP-38 (Anchor RFQ API) proxy API.
+//! Proxies price and quote requests to anchor quote servers through the typed
+//! SEP-38 client to avoid CORS and centralize auth.
+
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::auth_middleware::AuthUser;
+use crate::services::anchor_registry::{service_auth_domain, ServiceAuthError};
+use crate::services::sep10_client::Sep10Client;
+use crate::services::sep38_client::{
+    PriceRequest, PriceResponse, PricesRequest, PricesResponse, QuoteRequest, QuoteResponse,
+    Sep38Client, Sep38ClientConfig, Sep38Error, Sep38Info,
+};
+use crate::services::sep_endpoint_prober::SepEndpoint;
+
+/// Allowed quote server hosts (env: SEP38_ALLOWED_ORIGINS, comma-separated).
+/// If unset, any origin is allowed (use in dev only).
+fn allowed_origins() -> Vec<String> {
+    std::env::var("SEP38_ALLOWED_ORIGINS")
+        .ok()
+        .map(|s| s.split(',').map(|x| x.trim().to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn is_origin_allowed(quote_server: &str) -> bool {
+    let allowed = allowed_origins();
+    if allowed.is_empty() {
+        return true;
+    }
+    let url = quote_server.trim().trim_end_matches('/');
+    allowed.iter().any(|o| url.starts_with(o) || o == "*")
+}
+
+fn check_origin(quote_server: &str) -> Result<(), Sep38ProxyError> {
+    if !is_origin_allowed(quote_server) {
+        return Err(Sep38ProxyError::Forbidden(
+            "Quote server not in allowed list".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct Sep38State {
+    pub client: Arc<Sep38Client>,
+    pub sep10: Option<Arc<Sep10Client>>,
+}
+
+impl Default for Sep38State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sep38State {
+    pub fn new() -> Self {
+        Self {
+            client: Arc::new(Sep38Client::new(Sep38ClientConfig::default())),
+            sep10: None,
+        }
+    }
+
+    /// Obtain tokens through SEP-10 with the backend's key for signed-in
+    /// callers whose requests carry no `jwt`
+    pub fn with_sep10(mut self, sep10: Arc<Sep10Client>) -> Self {
+        self.sep10 = Some(sep10);
+        self
+    }
+
+    /// Check the quote server and work out the bearer token to send: the
+    /// caller's `jwt`, or one obtained through SEP-10 with the backend's key
+    /// for signed-in callers
+    async fn prepare(
+        &self,
+        caller: Option<&AuthUser>,
+        quote_server: &str,
+        jwt: Option<&str>,
+        home_domain: Option<&str>,
+    ) -> Result<Option<String>, Sep38ProxyError> {
+        check_origin(quote_server)?;
+        if let Some(jwt) = jwt {
+            return Ok(Some(jwt.to_string()));
+        }
+        let Some(sep10) = &self.sep10 else {
+            return Ok(None);
+        };
+        let domain = service_auth_domain(
+            sep10,
+            SepEndpoint::Sep38,
+            quote_server,
+            None,
+            home_domain,
+            caller.is_some(),
+        )
+        .await?;
+        match domain {
+            Some(domain) => sep10
+                .token(&domain, None)
+                .await
+                .map(|token| Some(token.token))
+                .map_err(|e| Sep38ProxyError::Auth(e.to_string())),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Quote server and credentials of a proxied request
+#[derive(Debug, Deserialize)]
+pub struct Sep38Target {
+    pub quote_server: String,
+    /// JWT from SEP-10 (required by anchors for `/quote`)
+    #[serde(default)]
+    pub jwt: Option<String>,
+    /// Anchor home domain; without `jwt`, signed-in callers are authenticated
+    /// with the backend's own key through SEP-10. Only opts in: the domain
+    /// must be the host publishing `quote_server` in its stellar.toml.
+    #[serde(default)]
+    pub home_domain: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PricesQuery {
+    #[serde(flatten)]
+    pub target: Sep38Target,
+    #[serde(flatten)]
+    pub request: PricesRequest,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PriceQuery {
+    #[serde(flatten)]
+    pub target: Sep38Target,
+    #[serde(flatten)]
+    pub request: PriceRequest,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuoteBody {
+    #[serde(flatten)]
+    pub target: Sep38Target,
+    #[serde(flatten)]
+    pub request: QuoteRequest,
+}
+
+/// GET /api/sep38/info?quote_server=<url>
+pub async fn get_info(
+    State(state): State<Sep38State>,
+    Query(target): Query<Sep38Target>,
+) -> Result<Json<Sep38Info>, Sep38ProxyError> {
+    check_origin(&target.quote_server)?;
+    Ok(Json(state.client.info(&target.quote_server).await?))
+}
+
+/// GET /api/sep38/prices?quote_server=&sell_asset=&sell_amount=&...
+pub async fn get_prices(
+    State(state): State<Sep38State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<PricesQuery>,
+) -> Result<Json<PricesResponse>, Sep38ProxyError> {
+    let jwt = state
+        .prepare(
+            caller.as_deref(),
+            &q.target.quote_server,
+            q.target.jwt.as_deref(),
+            q.target.home_domain.as_deref(),
+        )
+        .await?;
+    let prices = state
+        .client
+        .prices(&q.target.quote_server, &q.request, jwt.as_deref())
+        .await?;
+    Ok(Json(prices))
+}
+
+/// GET /api/sep38/price?quote_server=&context=&sell_asset=&buy_asset=&sell_amount=&...
+pub async fn get_price(
+    State(state): State<Sep38State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<PriceQuery>,
+) -> Result<Json<PriceResponse>, Sep38ProxyError> {
+    let jwt = state
+        .prepare(
+            caller.as_deref(),
+            &q.target.quote_server,
+            q.target.jwt.as_deref(),
+            q.target.home_domain.as_deref(),
+        )
+        .await?;
+    let price = state
+        .client
+        .price(&q.target.quote_server, &q.request, jwt.as_deref())
+        .await?;
+    Ok(Json(price))
+}
+
+/// POST /api/sep38/quote - request a firm quote
+pub async fn post_quote(
+    State(state): State<Sep38State>,
+    caller: Option<Extension<AuthUser>>,
+    Json(body): Json<QuoteBody>,
+) -> Result<Json<QuoteResponse>, Sep38ProxyError> {
+    let jwt = state
+        .prepare(
+            caller.as_deref(),
+            &body.target.quote_server,
+            body.target.jwt.as_deref(),
+            body.target.home_domain.as_deref(),
+        )
+        .await?;
+    let quote = state
+        .client
+        .post_quote(&body.target.quote_server, &body.request, jwt.as_deref())
+        .await?;
+    Ok(Json(quote))
+}
+
+/// GET /api/sep38/quote/:id?quote_server=&jwt=
+pub async fn get_quote(
+    State(state): State<Sep38State>,
+    caller: Option<Extension<AuthUser>>,
+    Path(id): Path<String>,
+    Query(target): Query<Sep38Target>,
+) -> Result<Json<QuoteResponse>, Sep38ProxyError> {
+    let jwt = state
+        .prepare(
+            caller.as_deref(),
+            &target.quote_server,
+            target.jwt.as_deref(),
+            target.home_domain.as_deref(),
+        )
+        .await?;
+    let quote = state
+        .client
+        .get_quote(&target.quote_server, &id, jwt.as_deref())
+        .await?;
+    Ok(Json(quote))
+}
+
+#[derive(Debug)]
+pub enum Sep38ProxyError {
+    Forbidden(String),
+    /// Only signed-in callers may use the backend's SEP-10 key
+    Unauthorized(String),
+    Proxy(String),
+    /// SEP-10 authentication with the anchor failed
+    Auth(String),
+    Anchor(u16, Value),
+}
+
+impl From<Sep38Error> for Sep38ProxyError {
+    fn from(error: Sep38Error) -> Self {
+        match error {
+            Sep38Error::Request(msg) => Sep38ProxyError::Proxy(msg),
+            Sep38Error::Anchor(code, data) => Sep38ProxyError::Anchor(code, data),
+        }
+    }
+}
+
+impl From<ServiceAuthError> for Sep38ProxyError {
+    fn from(e: ServiceAuthError) -> Self {
+        match e {
+            ServiceAuthError::SignInRequired => Sep38ProxyError::Unauthorized(
+                "sign in, or send a jwt, to authenticate with the anchor".to_string(),
+            ),
+            ServiceAuthError::Mismatch(msg) => Sep38ProxyError::Forbidden(msg),
+            ServiceAuthError::Unavailable(msg) => Sep38ProxyError::Auth(msg),
+        }
+    }
+}
+
+impl IntoResponse for Sep38ProxyError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, body) = match &self {
+            Sep38ProxyError::Forbidden(msg) => (
+                StatusCode::FORBIDDEN,
+                serde_json::json!({ "error": "forbidden", "message": msg }),
+            ),
+            Sep38ProxyError::Unauthorized(msg) => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({ "error": "unauthorized", "message": msg }),
+            ),
+            Sep38ProxyError::Proxy(msg) => (
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({ "error": "proxy", "message": msg }),
+            ),
+            Sep38ProxyError::Auth(msg) => (
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({ "error": "sep10", "message": msg }),
+            ),
+            Sep38ProxyError::Anchor(code, data) => {
+                let status = StatusCode::from_u16(*code).unwrap_or(StatusCode::BAD_GATEWAY);
+                (status, data.clone())
+            }
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+/// Build SEP-38 API router
+pub fn routes(client: Arc<Sep38Client>, sep10: Arc<Sep10Client>) -> axum::Router {
+    let state = Sep38State {
+        client,
+        sep10: Some(sep10),
+    };
+    axum::Router::new()
+        .route("/api/sep38/info", axum::routing::get(get_info))
+        .route("/api/sep38/prices", axum::routing::get(get_prices))
+        .route("/api/sep38/price", axum::routing::get(get_price))
+        .route("/api/sep38/quote", axum::routing::post(post_quote))
+        .route("/api/sep38/quote/:id", axum::routing::get(get_quote))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_query_splits_target_and_request() {
+        let uri: axum::http::Uri = "/api/sep38/price?quote_server=https%3A%2F%2Fapi.test.com%2Fsep38&jwt=abc&context=sep31&sell_asset=iso4217%3AUSD&buy_asset=iso4217%3ABRL&sell_amount=100"
+            .parse()
+            .unwrap();
+        let Query(q) = Query::<PriceQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(q.target.quote_server, "https://api.test.com/sep38");
+        assert_eq!(q.target.jwt.as_deref(), Some("abc"));
+        assert_eq!(q.request.context, "sep31");
+        assert_eq!(q.request.buy_asset, "iso4217:BRL");
+        assert_eq!(q.request.sell_amount.as_deref(), Some("100"));
+        assert_eq!(q.request.buy_amount, None);
+    }
+
+    #[test]
+    fn test_quote_body_deserialize() {
+        let json = r#"{"quote_server":"https://api.test.com/sep38","home_domain":"test.com","context":"sep6","sell_asset":"iso4217:USD","buy_asset":"iso4217:BRL","buy_amount":"500","expire_after":"2026-01-01T00:00:00Z"}"#;
+        let body: QuoteBody = serde_json::from_str(json).unwrap();
+        assert_eq!(body.target.home_domain.as_deref(), Some("test.com"));
+        assert_eq!(body.request.price.context, "sep6");
+        assert_eq!(body.request.price.buy_amount.as_deref(), Some("500"));
+        assert!(body.request.expire_after.is_some());
+    }
+}
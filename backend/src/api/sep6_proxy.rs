@@ -0,0 +1,432 @@
+//! SEP-6 (Deposit and Withdrawal API) proxy API.
+//! Proxies programmatic deposit and withdrawal requests to anchor transfer
+//! servers to avoid CORS and centralize auth.
+
+use axum::{
+    extract::{Extension, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::auth_middleware::AuthUser;
+use crate::services::anchor_registry::{
+    service_auth_domain, AnchorRegistry, ProxyAccess, ProxyAnchor, ProxyAuth, ServiceAuthError,
+};
+use crate::services::sep10_client::Sep10Client;
+use crate::services::sep_endpoint_prober::SepEndpoint;
+
+/// Allowed transfer server hosts (env: SEP6_ALLOWED_ORIGINS, comma-separated).
+/// If unset, any origin is allowed (use in dev only).
+fn allowed_origins() -> Vec<String> {
+    std::env::var("SEP6_ALLOWED_ORIGINS")
+        .ok()
+        .map(|s| s.split(',').map(|x| x.trim().to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn is_origin_allowed(transfer_server: &str) -> bool {
+    let allowed = allowed_origins();
+    if allowed.is_empty() {
+        return true;
+    }
+    let url = transfer_server.trim().trim_end_matches('/');
+    allowed.iter().any(|o| url.starts_with(o) || o == "*")
+}
+
+#[derive(Clone)]
+pub struct Sep6State {
+    pub client: Arc<Client>,
+    pub sep10: Option<Arc<Sep10Client>>,
+    pub registry: Option<Arc<AnchorRegistry>>,
+}
+
+impl Default for Sep6State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sep6State {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self {
+            client: Arc::new(client),
+            sep10: None,
+            registry: None,
+        }
+    }
+
+    /// Obtain tokens through SEP-10 with the backend's key for signed-in
+    /// callers whose requests carry no `jwt`
+    pub fn with_sep10(mut self, sep10: Arc<Sep10Client>) -> Self {
+        self.sep10 = Some(sep10);
+        self
+    }
+
+    /// Restrict the proxy to enabled anchors and apply their auth and timeout
+    /// settings
+    pub fn with_registry(mut self, registry: Arc<AnchorRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Check `transfer_server` against the anchor registry and
+    /// `SEP6_ALLOWED_ORIGINS`; returns the enabled anchor serving it
+    async fn authorize(&self, transfer_server: &str) -> Result<Option<ProxyAnchor>, Sep6Error> {
+        let access = match &self.registry {
+            Some(registry) => registry
+                .authorize(SepEndpoint::Sep6, transfer_server, &allowed_origins())
+                .await
+                .map_err(|e| Sep6Error::Internal(e.to_string()))?,
+            None if is_origin_allowed(transfer_server) => ProxyAccess::Allowed,
+            None => ProxyAccess::Denied,
+        };
+        match access {
+            ProxyAccess::Registered(anchor) => Ok(Some(anchor)),
+            ProxyAccess::Allowed => Ok(None),
+            ProxyAccess::Denied => Err(Sep6Error::Forbidden(
+                "Transfer server not in allowed list".to_string(),
+            )),
+        }
+    }
+
+    /// Bearer token for a request to the anchor: the caller's `jwt`, or one
+    /// obtained through SEP-10 with the backend's key for signed-in callers
+    async fn bearer(
+        &self,
+        caller: Option<&AuthUser>,
+        anchor: Option<&ProxyAnchor>,
+        transfer_server: &str,
+        jwt: Option<&str>,
+        home_domain: Option<&str>,
+    ) -> Result<Option<String>, Sep6Error> {
+        if let Some(anchor) = anchor {
+            anchor
+                .check_home_domain(home_domain)
+                .map_err(Sep6Error::Forbidden)?;
+        }
+        if let Some(jwt) = jwt {
+            return Ok(Some(jwt.to_string()));
+        }
+        let Some(sep10) = &self.sep10 else {
+            return Ok(None);
+        };
+        let domain = service_auth_domain(
+            sep10,
+            SepEndpoint::Sep6,
+            transfer_server,
+            anchor,
+            home_domain,
+            caller.is_some(),
+        )
+        .await?;
+        match domain {
+            Some(domain) => sep10
+                .token(&domain, None)
+                .await
+                .map(|token| Some(token.token))
+                .map_err(|e| Sep6Error::Auth(e.to_string())),
+            None => Ok(None),
+        }
+    }
+}
+
+fn base_url(transfer_server: &str) -> String {
+    transfer_server.trim().trim_end_matches('/').to_string()
+}
+
+/// Query of every SEP-6 proxy endpoint. SEP-6 requests are GETs, so all
+/// parameters other than the ones consumed here are passed to the anchor.
+#[derive(Debug, Deserialize)]
+pub struct Sep6Query {
+    pub transfer_server: String,
+    /// JWT from SEP-10 (required by most anchors except for `/info`)
+    #[serde(default)]
+    pub jwt: Option<String>,
+    /// Anchor home domain; without `jwt`, signed-in callers are authenticated
+    /// with the backend's own key through SEP-10. Only opts in: the domain
+    /// must be the anchor's own.
+    #[serde(default)]
+    pub home_domain: Option<String>,
+    #[serde(flatten)]
+    pub params: BTreeMap<String, String>,
+}
+
+/// Forward a GET to `<transfer_server><path>` with the remaining parameters
+async fn proxy_get(
+    state: &Sep6State,
+    caller: Option<&AuthUser>,
+    path: &str,
+    q: Sep6Query,
+) -> Result<Json<Value>, Sep6Error> {
+    let anchor = state.authorize(&q.transfer_server).await?;
+    let url = format!("{}{}", base_url(&q.transfer_server), path);
+
+    let mut req =
+        ProxyAnchor::with_timeout(anchor.as_ref(), state.client.get(&url).query(&q.params));
+    if let Some(jwt) = state
+        .bearer(
+            caller,
+            anchor.as_ref(),
+            &q.transfer_server,
+            q.jwt.as_deref(),
+            q.home_domain.as_deref(),
+        )
+        .await?
+    {
+        req = req.header("Authorization", format!("Bearer {}", jwt));
+    }
+    let resp = req
+        .send()
+        .await
+        .map_err(|e| Sep6Error::Proxy(e.to_string()))?;
+
+    let status = resp.status();
+    let data = resp
+        .json::<Value>()
+        .await
+        .map_err(|e| Sep6Error::Proxy(e.to_string()))?;
+
+    if !status.is_success() {
+        return Err(Sep6Error::Anchor(status.as_u16(), data));
+    }
+    Ok(Json(data))
+}
+
+/// GET /api/sep6/info?transfer_server=<url>
+pub async fn get_info(
+    State(state): State<Sep6State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<Sep6Query>,
+) -> Result<Json<Value>, Sep6Error> {
+    proxy_get(&state, caller.as_deref(), "/info", q).await
+}
+
+/// GET /api/sep6/deposit?transfer_server=&asset_code=&account=&...
+pub async fn get_deposit(
+    State(state): State<Sep6State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<Sep6Query>,
+) -> Result<Json<Value>, Sep6Error> {
+    proxy_get(&state, caller.as_deref(), "/deposit", q).await
+}
+
+/// GET /api/sep6/withdraw?transfer_server=&asset_code=&type=&...
+pub async fn get_withdraw(
+    State(state): State<Sep6State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<Sep6Query>,
+) -> Result<Json<Value>, Sep6Error> {
+    proxy_get(&state, caller.as_deref(), "/withdraw", q).await
+}
+
+/// GET /api/sep6/deposit-exchange?transfer_server=&destination_asset=&source_asset=&amount=&...
+pub async fn get_deposit_exchange(
+    State(state): State<Sep6State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<Sep6Query>,
+) -> Result<Json<Value>, Sep6Error> {
+    proxy_get(&state, caller.as_deref(), "/deposit-exchange", q).await
+}
+
+/// GET /api/sep6/withdraw-exchange?transfer_server=&source_asset=&destination_asset=&amount=&type=&...
+pub async fn get_withdraw_exchange(
+    State(state): State<Sep6State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<Sep6Query>,
+) -> Result<Json<Value>, Sep6Error> {
+    proxy_get(&state, caller.as_deref(), "/withdraw-exchange", q).await
+}
+
+/// GET /api/sep6/transactions?transfer_server=&jwt=&asset_code=&...
+pub async fn get_transactions(
+    State(state): State<Sep6State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<Sep6Query>,
+) -> Result<Json<Value>, Sep6Error> {
+    proxy_get(&state, caller.as_deref(), "/transactions", q).await
+}
+
+/// GET /api/sep6/transaction?transfer_server=&id=&jwt=
+pub async fn get_transaction(
+    State(state): State<Sep6State>,
+    caller: Option<Extension<AuthUser>>,
+    Query(q): Query<Sep6Query>,
+) -> Result<Json<Value>, Sep6Error> {
+    proxy_get(&state, caller.as_deref(), "/transaction", q).await
+}
+
+/// List known SEP-6-enabled anchors. GET /api/sep6/anchors
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Sep6AnchorInfo {
+    pub name: String,
+    pub transfer_server: String,
+    pub home_domain: Option<String>,
+    /// Registry anchor; absent for `SEP6_ANCHORS` presets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<ProxyAuth>,
+}
+
+impl From<ProxyAnchor> for Sep6AnchorInfo {
+    fn from(anchor: ProxyAnchor) -> Self {
+        Self {
+            name: anchor.name,
+            transfer_server: anchor.url,
+            home_domain: anchor.home_domain,
+            anchor_id: Some(anchor.anchor_id),
+            auth: Some(anchor.auth),
+        }
+    }
+}
+
+pub async fn list_anchors(State(state): State<Sep6State>) -> Result<Json<Value>, Sep6Error> {
+    // Anchors enabled in the registry come first
+    let mut anchors: Vec<Sep6AnchorInfo> = match &state.registry {
+        Some(registry) => registry
+            .proxy_anchors(SepEndpoint::Sep6)
+            .await
+            .map_err(|e| Sep6Error::Internal(e.to_string()))?
+            .into_iter()
+            .map(Sep6AnchorInfo::from)
+            .collect(),
+        None => vec![],
+    };
+    // Env: SEP6_ANCHORS = JSON array of { "name", "transfer_server", "home_domain" }
+    if let Ok(s) = std::env::var("SEP6_ANCHORS") {
+        let presets: Vec<Sep6AnchorInfo> = serde_json::from_str(&s).unwrap_or_default();
+        for preset in presets {
+            let listed = anchors
+                .iter()
+                .any(|a| base_url(&a.transfer_server) == base_url(&preset.transfer_server));
+            if !listed {
+                anchors.push(preset);
+            }
+        }
+    }
+    Ok(Json(serde_json::json!({ "anchors": anchors })))
+}
+
+#[derive(Debug)]
+pub enum Sep6Error {
+    Forbidden(String),
+    /// Only signed-in callers may use the backend's SEP-10 key
+    Unauthorized(String),
+    Proxy(String),
+    /// SEP-10 authentication with the anchor failed
+    Auth(String),
+    Anchor(u16, Value),
+    /// The anchor registry could not be read
+    Internal(String),
+}
+
+impl From<ServiceAuthError> for Sep6Error {
+    fn from(e: ServiceAuthError) -> Self {
+        match e {
+            ServiceAuthError::SignInRequired => Sep6Error::Unauthorized(
+                "sign in, or send a jwt, to authenticate with the anchor".to_string(),
+            ),
+            ServiceAuthError::Mismatch(msg) => Sep6Error::Forbidden(msg),
+            ServiceAuthError::Unavailable(msg) => Sep6Error::Auth(msg),
+        }
+    }
+}
+
+impl IntoResponse for Sep6Error {
+    fn into_response(self) -> axum::response::Response {
+        let (status, body) = match &self {
+            Sep6Error::Forbidden(msg) => (
+                StatusCode::FORBIDDEN,
+                serde_json::json!({ "error": "forbidden", "message": msg }),
+            ),
+            Sep6Error::Unauthorized(msg) => (
+                StatusCode::UNAUTHORIZED,
+                serde_json::json!({ "error": "unauthorized", "message": msg }),
+            ),
+            Sep6Error::Proxy(msg) => (
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({ "error": "proxy", "message": msg }),
+            ),
+            Sep6Error::Auth(msg) => (
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({ "error": "sep10", "message": msg }),
+            ),
+            Sep6Error::Internal(msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                serde_json::json!({ "error": "internal", "message": msg }),
+            ),
+            Sep6Error::Anchor(code, data) => {
+                let status = StatusCode::from_u16(*code).unwrap_or(StatusCode::BAD_GATEWAY);
+                (status, data.clone())
+            }
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+/// Build SEP-6 API router
+pub fn routes(sep10: Arc<Sep10Client>, registry: Arc<AnchorRegistry>) -> axum::Router {
+    let state = Sep6State::new().with_sep10(sep10).with_registry(registry);
+    axum::Router::new()
+        .route("/api/sep6/info", axum::routing::get(get_info))
+        .route("/api/sep6/deposit", axum::routing::get(get_deposit))
+        .route("/api/sep6/withdraw", axum::routing::get(get_withdraw))
+        .route(
+            "/api/sep6/deposit-exchange",
+            axum::routing::get(get_deposit_exchange),
+        )
+        .route(
+            "/api/sep6/withdraw-exchange",
+            axum::routing::get(get_withdraw_exchange),
+        )
+        .route(
+            "/api/sep6/transactions",
+            axum::routing::get(get_transactions),
+        )
+        .route("/api/sep6/transaction", axum::routing::get(get_transaction))
+        .route("/api/sep6/anchors", axum::routing::get(list_anchors))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url() {
+        assert_eq!(
+            base_url("https://api.example.com/sep6/"),
+            "https://api.example.com/sep6"
+        );
+        assert_eq!(
+            base_url("  https://api.example.com  "),
+            "https://api.example.com"
+        );
+    }
+
+    #[test]
+    fn test_query_keeps_anchor_params() {
+        let uri: axum::http::Uri = "/api/sep6/withdraw?transfer_server=https%3A%2F%2Fapi.test.com&jwt=abc&asset_code=USDC&account=GABC&type=bank_account"
+            .parse()
+            .unwrap();
+        let Query(q) = Query::<Sep6Query>::try_from_uri(&uri).unwrap();
+        assert_eq!(q.transfer_server, "https://api.test.com");
+        assert_eq!(q.jwt.as_deref(), Some("abc"));
+        assert_eq!(q.home_domain, None);
+        assert_eq!(
+            q.params.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["account", "asset_code", "type"]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::handlers::{ApiError, ApiResult};
+use crate::services::stellar_toml_crawler::{
+    AnchorTomlChange, AnchorTomlCurrency, AnchorTomlInfo, StellarTomlCrawler,
+};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StellarTomlResponse {
+    /// Organization info, SEP endpoints and crawl state
+    #[schema(value_type = Object)]
+    pub info: AnchorTomlInfo,
+    /// Currencies from `[[CURRENCIES]]` attributed to the anchor
+    #[schema(value_type = Vec<Object>)]
+    pub currencies: Vec<AnchorTomlCurrency>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StellarTomlHistoryQuery {
+    /// Maximum number of changes to return (default: 50)
+    #[serde(default = "default_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StellarTomlHistoryResponse {
+    pub anchor_id: String,
+    /// Changes ordered newest first
+    #[schema(value_type = Vec<Object>)]
+    pub changes: Vec<AnchorTomlChange>,
+}
+
+/// Get an anchor's SEP-1 stellar.toml data
+///
+/// Returns the organization info, SEP endpoint URLs and currencies last
+/// crawled from the anchor's home domain, along with the crawl status.
+///
+/// **DATA SOURCE: Database** (populated from the anchor's `/.well-known/stellar.toml`)
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/stellar-toml",
+    params(
+        ("id" = String, Path, description = "Anchor ID")
+    ),
+    responses(
+        (status = 200, description = "stellar.toml data retrieved successfully", body = StellarTomlResponse),
+        (status = 404, description = "Anchor has not been crawled"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn get_anchor_stellar_toml(
+    State(crawler): State<Arc<StellarTomlCrawler>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<StellarTomlResponse>> {
+    let info = crawler
+        .get_info(&id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("No stellar.toml crawled for anchor {}", id)))?;
+    let currencies = crawler.get_currencies(&id).await?;
+
+    Ok(Json(StellarTomlResponse { info, currencies }))
+}
+
+/// Get the change history of an anchor's stellar.toml
+///
+/// Each entry is a crawl whose content differed from the previous one, with
+/// the fields that were added, removed or modified.
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/stellar-toml/history",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        StellarTomlHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Change history retrieved successfully", body = StellarTomlHistoryResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Anchors"
+)]
+pub async fn get_anchor_stellar_toml_history(
+    State(crawler): State<Arc<StellarTomlCrawler>>,
+    Path(id): Path<String>,
+    Query(params): Query<StellarTomlHistoryQuery>,
+) -> ApiResult<Json<StellarTomlHistoryResponse>> {
+    let changes = crawler.get_history(&id, params.limit.clamp(1, 500)).await?;
+
+    Ok(Json(StellarTomlHistoryResponse {
+        anchor_id: id,
+        changes,
+    }))
+}
+
+/// Create stellar.toml routes
+pub fn routes(crawler: Arc<StellarTomlCrawler>) -> Router {
+    Router::new()
+        .route(
+            "/api/anchors/:id/stellar-toml",
+            get(get_anchor_stellar_toml),
+        )
+        .route(
+            "/api/anchors/:id/stellar-toml/history",
+            get(get_anchor_stellar_toml_history),
+        )
+        .with_state(crawler)
+}
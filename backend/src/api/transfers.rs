@@ -0,0 +1,255 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::auth_middleware::AuthUser;
+use crate::handlers::{ApiError, ApiResult};
+use crate::services::transfer_tracker::{
+    AnchorTransfer, CallbackOutcome, TransferMetrics, TransferStatusChange, TransferTracker,
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TransferListQuery {
+    /// Only transfers with this anchor
+    pub anchor_id: Option<String>,
+    /// Only transfers currently in this status
+    pub status: Option<String>,
+    /// Maximum number of transfers to return (default: 50)
+    #[serde(default = "default_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TransferMetricsQuery {
+    /// Consider transfers initiated in this many days (default: 30)
+    #[serde(default = "default_days")]
+    #[param(example = 30)]
+    pub days: i64,
+}
+
+fn default_days() -> i64 {
+    30
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferListResponse {
+    /// Transfers newest first
+    #[schema(value_type = Vec<Object>)]
+    pub transfers: Vec<AnchorTransfer>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferDetailResponse {
+    #[schema(value_type = Object)]
+    pub transfer: AnchorTransfer,
+    /// Status changes oldest first, starting with the initial status
+    #[schema(value_type = Vec<Object>)]
+    pub status_changes: Vec<TransferStatusChange>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferMetricsResponse {
+    /// Metrics per anchor; transfers with no known anchor are grouped under
+    /// a null `anchor_id`
+    #[schema(value_type = Vec<Object>)]
+    pub anchors: Vec<TransferMetrics>,
+}
+
+/// List the SEP-24 and SEP-31 transfers the caller initiated through the
+/// proxies
+///
+/// **DATA SOURCE: Database** (populated by the SEP-24/SEP-31 proxies and the
+/// transfer tracker)
+#[utoipa::path(
+    get,
+    path = "/api/transfers",
+    params(TransferListQuery),
+    responses(
+        (status = 200, description = "Transfers retrieved successfully", body = TransferListResponse),
+        (status = 401, description = "Not signed in"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Transfers"
+)]
+pub async fn list_transfers(
+    State(tracker): State<Arc<TransferTracker>>,
+    Extension(caller): Extension<AuthUser>,
+    Query(params): Query<TransferListQuery>,
+) -> ApiResult<Json<TransferListResponse>> {
+    let transfers = tracker
+        .list_transfers(
+            &caller.user_id,
+            params.anchor_id.as_deref(),
+            params.status.as_deref(),
+            params.limit.clamp(1, 500),
+        )
+        .await?;
+
+    Ok(Json(TransferListResponse { transfers }))
+}
+
+/// Get a transfer the caller initiated with its status history
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/transfers/{id}",
+    params(
+        ("id" = String, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Transfer retrieved successfully", body = TransferDetailResponse),
+        (status = 401, description = "Not signed in"),
+        (status = 404, description = "Transfer not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Transfers"
+)]
+pub async fn get_transfer(
+    State(tracker): State<Arc<TransferTracker>>,
+    Extension(caller): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<TransferDetailResponse>> {
+    let transfer = tracker
+        .get_transfer(&id)
+        .await?
+        .filter(|transfer| transfer.owner.as_deref() == Some(caller.user_id.as_str()))
+        .ok_or_else(|| ApiError::NotFound(format!("Transfer with id {} not found", id)))?;
+    let status_changes = tracker.status_changes(&id).await?;
+
+    Ok(Json(TransferDetailResponse {
+        transfer,
+        status_changes,
+    }))
+}
+
+/// Get transfer metrics of every anchor
+///
+/// Completion, refund and error rates are shares of the transfers that
+/// reached a terminal status. Time in status is measured between
+/// consecutive status changes.
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/transfers/metrics",
+    params(TransferMetricsQuery),
+    responses(
+        (status = 200, description = "Metrics computed successfully", body = TransferMetricsResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Transfers"
+)]
+pub async fn get_transfer_metrics(
+    State(tracker): State<Arc<TransferTracker>>,
+    Query(params): Query<TransferMetricsQuery>,
+) -> ApiResult<Json<TransferMetricsResponse>> {
+    let anchors = tracker.metrics(None, params.days.clamp(1, 365)).await?;
+
+    Ok(Json(TransferMetricsResponse { anchors }))
+}
+
+/// Get an anchor's transfer metrics
+///
+/// **DATA SOURCE: Database**
+#[utoipa::path(
+    get,
+    path = "/api/anchors/{id}/transfer-metrics",
+    params(
+        ("id" = String, Path, description = "Anchor ID"),
+        TransferMetricsQuery
+    ),
+    responses(
+        (status = 200, description = "Metrics computed successfully", body = Object),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Transfers"
+)]
+pub async fn get_anchor_transfer_metrics(
+    State(tracker): State<Arc<TransferTracker>>,
+    Path(id): Path<String>,
+    Query(params): Query<TransferMetricsQuery>,
+) -> ApiResult<Json<TransferMetrics>> {
+    let days = params.days.clamp(1, 365);
+    let metrics = tracker
+        .metrics(Some(&id), days)
+        .await?
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| TransferMetrics::empty(Some(id), days));
+
+    Ok(Json(metrics))
+}
+
+/// Status change reported by an anchor
+///
+/// Anchors are handed this URL as the SEP-24 `on_change_callback` or the
+/// SEP-31 transaction callback. The token in the path authenticates them.
+#[utoipa::path(
+    post,
+    path = "/api/transfers/{id}/callback/{token}",
+    params(
+        ("id" = String, Path, description = "Transfer ID"),
+        ("token" = String, Path, description = "Callback token of the transfer")
+    ),
+    request_body = Object,
+    responses(
+        (status = 200, description = "Status change applied"),
+        (status = 400, description = "Body carries no status or is about another transaction"),
+        (status = 404, description = "Unknown transfer or token"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Transfers"
+)]
+pub async fn transfer_callback(
+    State(tracker): State<Arc<TransferTracker>>,
+    Path((id, token)): Path<(String, String)>,
+    Json(body): Json<Value>,
+) -> ApiResult<Json<Value>> {
+    match tracker.handle_callback(&id, &token, &body).await? {
+        CallbackOutcome::Unknown => Err(ApiError::NotFound(format!(
+            "Transfer with id {} not found",
+            id
+        ))),
+        CallbackOutcome::Invalid(reason) => Err(ApiError::BadRequest(reason)),
+        CallbackOutcome::Applied { changed } => Ok(Json(serde_json::json!({ "changed": changed }))),
+    }
+}
+
+/// Create public transfer tracking routes: metrics and the callbacks,
+/// which their token authenticates
+pub fn routes(tracker: Arc<TransferTracker>) -> Router {
+    Router::new()
+        .route("/api/transfers/metrics", get(get_transfer_metrics))
+        .route(
+            "/api/transfers/:id/callback/:token",
+            post(transfer_callback),
+        )
+        .route(
+            "/api/anchors/:id/transfer-metrics",
+            get(get_anchor_transfer_metrics),
+        )
+        .with_state(tracker)
+}
+
+/// Create transfer routes that require authentication; each caller sees
+/// the transfers they initiated
+pub fn protected_routes(tracker: Arc<TransferTracker>) -> Router {
+    Router::new()
+        .route("/api/transfers", get(list_transfers))
+        .route("/api/transfers/:id", get(get_transfer))
+        .with_state(tracker)
+}
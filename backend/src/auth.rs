@@ -4,6 +4,7 @@ use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation}
 use redis::aio::MultiplexedConnection;
 use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -70,6 +71,8 @@ pub struct Claims {
 pub struct AuthService {
     jwt_secret: String,
     redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>,
+    /// Stellar accounts allowed to sign in through SEP-10
+    sep10_accounts: HashSet<String>,
 }
 
 impl AuthService {
@@ -80,9 +83,22 @@ impl AuthService {
         Self {
             jwt_secret,
             redis_connection,
+            sep10_accounts: HashSet::new(),
         }
     }
 
+    /// Allow these Stellar accounts to sign in through SEP-10; no account
+    /// can until some are allowed
+    pub fn with_sep10_accounts(mut self, accounts: impl IntoIterator<Item = String>) -> Self {
+        self.sep10_accounts = accounts.into_iter().collect();
+        self
+    }
+
+    /// Whether `account_id` may sign in through SEP-10
+    pub fn is_sep10_account_allowed(&self, account_id: &str) -> bool {
+        self.sep10_accounts.contains(account_id)
+    }
+
     /// Authenticate user with credentials
     pub fn authenticate(&self, username: &str, password: &str) -> Result<User> {
         // Demo authentication - replace with database lookup in production
@@ -225,9 +241,29 @@ impl AuthService {
         // Authenticate user
         let user = self.authenticate(&request.username, &request.password)?;
 
+        self.issue_tokens(&user).await
+    }
+
+    /// Login flow for a Stellar account that proved control through SEP-10;
+    /// the account ID is the token subject. Only allowed accounts get tokens.
+    pub async fn login_account(&self, account_id: &str) -> Result<LoginResponse> {
+        if !self.is_sep10_account_allowed(account_id) {
+            return Err(anyhow!("Account {} is not allowed to sign in", account_id));
+        }
+
+        let user = User {
+            id: account_id.to_string(),
+            username: account_id.to_string(),
+        };
+
+        self.issue_tokens(&user).await
+    }
+
+    /// Generate and store the access and refresh tokens of a login
+    async fn issue_tokens(&self, user: &User) -> Result<LoginResponse> {
         // Generate tokens
-        let access_token = self.generate_access_token(&user)?;
-        let refresh_token = self.generate_refresh_token(&user)?;
+        let access_token = self.generate_access_token(user)?;
+        let refresh_token = self.generate_refresh_token(user)?;
 
         // Store refresh token
         self.store_refresh_token(&refresh_token, &user.id).await?;
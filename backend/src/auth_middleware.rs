@@ -17,6 +17,26 @@ pub struct AuthUser {
 
 /// Auth middleware - validates JWT from Authorization header
 pub async fn auth_middleware(mut req: Request, next: Next) -> Result<Response, AuthError> {
+    let auth_user = authenticate(&req)?;
+    req.extensions_mut().insert(auth_user);
+
+    Ok(next.run(req).await)
+}
+
+/// Optional auth middleware - requests without an Authorization header pass
+/// through anonymously; a header that is present must carry a valid JWT.
+/// Handlers read the caller as `Option<Extension<AuthUser>>`.
+pub async fn optional_auth_middleware(mut req: Request, next: Next) -> Result<Response, AuthError> {
+    if req.headers().contains_key(header::AUTHORIZATION) {
+        let auth_user = authenticate(&req)?;
+        req.extensions_mut().insert(auth_user);
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// User of the access token in the Authorization header
+fn authenticate(req: &Request) -> Result<AuthUser, AuthError> {
     // Extract Authorization header
     let auth_header = req
         .headers()
@@ -36,14 +56,10 @@ pub async fn auth_middleware(mut req: Request, next: Next) -> Result<Response, A
     // Validate token
     let claims = validate_access_token(token, &jwt_secret)?;
 
-    // Attach user to request extensions
-    let auth_user = AuthUser {
+    Ok(AuthUser {
         user_id: claims.sub,
         username: claims.username,
-    };
-    req.extensions_mut().insert(auth_user);
-
-    Ok(next.run(req).await)
+    })
 }
 
 /// Validate access token
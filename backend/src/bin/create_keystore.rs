@@ -0,0 +1,69 @@
+//! Create an encrypted keystore for the account that signs contract
+//! transactions
+//!
+//! ```text
+//! create_keystore <keystore.json> [--iterations <N>]
+//! ```
+//!
+//! The secret seed (`S...`) and the password are read from the first two
+//! lines of standard input. The keystore is written readable by the owner
+//! only and never overwrites an existing file. The account is printed.
+//! Point `STELLAR_KEYSTORE_PATH` at the keystore and provide the password in
+//! `STELLAR_KEYSTORE_PASSWORD_FILE`.
+
+use anyhow::{bail, Context, Result};
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use stellar_insights_backend::services::signer::{Keystore, DEFAULT_KEYSTORE_ITERATIONS};
+
+const USAGE: &str = "usage: create_keystore <keystore.json> [--iterations <N>]";
+
+fn run() -> Result<String> {
+    let mut path = None;
+    let mut iterations = DEFAULT_KEYSTORE_ITERATIONS;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => {
+                iterations = args.next().and_then(|n| n.parse().ok()).context(USAGE)?
+            }
+            "-h" | "--help" => bail!(USAGE),
+            _ if arg.starts_with('-') => bail!("unknown option {}\n{}", arg, USAGE),
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            _ => bail!("unexpected argument {}\n{}", arg, USAGE),
+        }
+    }
+    let path = path.context(USAGE)?;
+
+    let mut lines = std::io::stdin().lock().lines();
+    let secret = lines
+        .next()
+        .transpose()?
+        .context("Expected the secret seed on the first line of stdin")?;
+    let password = lines
+        .next()
+        .transpose()?
+        .context("Expected the password on the second line of stdin")?;
+    if password.is_empty() {
+        bail!("The password must not be empty");
+    }
+
+    let keystore = Keystore::encrypt(&secret, &password, iterations)?;
+    keystore.save(&path)?;
+    Ok(keystore.account)
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(account) => {
+            println!("{}", account);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Verify a downloaded analytics snapshot against the snapshot contract
+//!
+//! Recomputes the Merkle root of a snapshot file (as served by
+//! `GET /api/snapshots/:epoch`) and compares it with the root the contract
+//! holds for the snapshot's epoch. Only the file and the Soroban RPC are used.
+//!
+//! ```text
+//! verify_snapshot <snapshot.json> --contract-id <ID> [--rpc-url <URL>]
+//! ```
+//!
+//! `SNAPSHOT_CONTRACT_ID` and `SOROBAN_RPC_URL` are used when the flags are
+//! omitted. The report is printed as JSON. The exit code is 0 if the snapshot
+//! is verified, 1 if it is not and 2 if it could not be checked.
+
+use anyhow::{bail, Context, Result};
+use std::process::ExitCode;
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::snapshot::SnapshotService;
+
+const USAGE: &str = "usage: verify_snapshot <snapshot.json> --contract-id <ID> [--rpc-url <URL>]";
+
+struct Args {
+    path: String,
+    contract_id: String,
+    rpc_url: String,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut path = None;
+    let mut contract_id = std::env::var("SNAPSHOT_CONTRACT_ID").ok();
+    let mut rpc_url = std::env::var("SOROBAN_RPC_URL").ok();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--contract-id" => contract_id = args.next(),
+            "--rpc-url" => rpc_url = args.next(),
+            "-h" | "--help" => bail!(USAGE),
+            _ if arg.starts_with('-') => bail!("unknown option {}\n{}", arg, USAGE),
+            _ if path.is_none() => path = Some(arg),
+            _ => bail!("unexpected argument {}\n{}", arg, USAGE),
+        }
+    }
+
+    Ok(Args {
+        path: path.context(USAGE)?,
+        contract_id: contract_id.context(USAGE)?,
+        rpc_url: rpc_url.unwrap_or_else(|| "https://soroban-testnet.stellar.org".to_string()),
+    })
+}
+
+async fn run() -> Result<bool> {
+    let args = parse_args()?;
+    let canonical_json = std::fs::read_to_string(&args.path)
+        .with_context(|| format!("Failed to read {}", args.path))?;
+    let epoch = SnapshotService::snapshot_epoch(&canonical_json)?;
+
+    // Reading the contract needs no signer
+    let contract = ContractService::new(ContractConfig {
+        rpc_url: args.rpc_url,
+        contract_id: args.contract_id,
+        network_passphrase: String::new(),
+    })?;
+    let on_chain_root = contract.get_snapshot_root(epoch).await?;
+
+    let report = SnapshotService::verification_report(
+        &canonical_json,
+        contract.contract_id(),
+        on_chain_root,
+    )?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    Ok(report.verified)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("{:#}", e);
+            ExitCode::from(2)
+        }
+    }
+}
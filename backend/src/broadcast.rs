@@ -1,5 +1,6 @@
 use crate::models::corridor::Corridor;
 use crate::models::Anchor;
+use crate::services::anomaly_detector::CorridorAnomaly;
 use crate::websocket::{WsMessage, WsState};
 use std::sync::Arc;
 
@@ -26,6 +27,20 @@ pub fn broadcast_corridor_update(ws_state: &Arc<WsState>, corridor: &Corridor) {
     ws_state.broadcast(message);
 }
 
+/// Broadcast a detected corridor anomaly to all WebSocket clients
+pub fn broadcast_corridor_anomaly(ws_state: &Arc<WsState>, anomaly: &CorridorAnomaly) {
+    let message = WsMessage::CorridorAnomaly {
+        corridor_key: anomaly.corridor_key.clone(),
+        metric: anomaly.metric.clone(),
+        severity: anomaly.severity.clone(),
+        hour_bucket: anomaly.hour_bucket.clone(),
+        observed_value: anomaly.observed_value,
+        expected_value: anomaly.expected_value,
+        score: anomaly.score,
+    };
+    ws_state.broadcast(message);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,14 +1,19 @@
-use anyhow::Result;
-use chrono::Utc;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
-use crate::analytics::compute_anchor_metrics;
+use crate::analytics::compute_anchor_metrics_with_uptime;
 use crate::models::{
-    Anchor, AnchorDetailResponse, AnchorMetricsHistory, Asset, CorridorRecord, CreateAnchorRequest,
-    MetricRecord, MuxedAccountAnalytics, MuxedAccountUsage, SnapshotRecord,
+    volume_collapsed, Anchor, AnchorDetailResponse, AnchorIncident, AnchorMetricsHistory,
+    AnchorStatus, AnchorStatusTransition, Asset, CorridorRecord, CreateAnchorRequest,
+    IncidentTimelineEntry, MetricRecord, MuxedAccountAnalytics, MuxedAccountUsage, SnapshotRecord,
+    StatusCause, StatusInputs,
 };
 
+/// Window over which SEP endpoint probes count towards an anchor's uptime
+pub const ENDPOINT_UPTIME_WINDOW_HOURS: i64 = 24;
+
 /// Parameters for updating anchor from RPC data
 pub struct AnchorRpcUpdate {
     pub stellar_account: String,
@@ -21,6 +26,51 @@ pub struct AnchorRpcUpdate {
     pub status: String,
 }
 
+#[derive(sqlx::FromRow)]
+struct TransitionRow {
+    id: i64,
+    anchor_id: String,
+    incident_id: Option<i64>,
+    from_status: String,
+    to_status: String,
+    causes: String,
+    success_rate: f64,
+    failure_rate: f64,
+    uptime_percent: Option<f64>,
+    volume_usd: Option<f64>,
+    transitioned_at: DateTime<Utc>,
+}
+
+impl TransitionRow {
+    fn into_transition(self) -> Result<AnchorStatusTransition> {
+        Ok(AnchorStatusTransition {
+            id: self.id,
+            anchor_id: self.anchor_id,
+            incident_id: self.incident_id,
+            from_status: self.from_status,
+            to_status: self.to_status,
+            causes: serde_json::from_str(&self.causes)
+                .context("Invalid causes in anchor status transition")?,
+            success_rate: self.success_rate,
+            failure_rate: self.failure_rate,
+            uptime_percent: self.uptime_percent,
+            volume_usd: self.volume_usd,
+            transitioned_at: self.transitioned_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct IncidentRow {
+    id: i64,
+    anchor_id: String,
+    anchor_name: String,
+    worst_status: String,
+    causes: String,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+}
+
 /// Parameters for recording anchor metrics history
 pub struct AnchorMetricsParams {
     pub anchor_id: Uuid,
@@ -54,20 +104,27 @@ impl Database {
     // Anchor operations
     pub async fn create_anchor(&self, req: CreateAnchorRequest) -> Result<Anchor> {
         let id = Uuid::new_v4().to_string();
-        let anchor = sqlx::query_as::<_, Anchor>(
+        // Not `RETURNING *` with `fetch_one`: SQLite only commits the insert
+        // once the statement is stepped to completion, so other pooled
+        // connections might not see the new anchor yet
+        sqlx::query(
             r#"
             INSERT INTO anchors (id, name, stellar_account, home_domain)
             VALUES ($1, $2, $3, $4)
-            RETURNING *
             "#,
         )
-        .bind(id)
+        .bind(&id)
         .bind(&req.name)
         .bind(&req.stellar_account)
         .bind(&req.home_domain)
-        .fetch_one(&self.pool)
+        .execute(&self.pool)
         .await?;
 
+        let anchor = sqlx::query_as::<_, Anchor>("SELECT * FROM anchors WHERE id = $1")
+            .bind(&id)
+            .fetch_one(&self.pool)
+            .await?;
+
         Ok(anchor)
     }
 
@@ -125,13 +182,24 @@ impl Database {
         avg_settlement_time_ms: Option<i32>,
         volume_usd: Option<f64>,
     ) -> Result<Anchor> {
+        let previous = self.get_anchor_by_id(anchor_id).await?;
+
         // Compute metrics
-        let metrics = compute_anchor_metrics(
+        let uptime = self
+            .get_anchor_endpoint_uptime(&anchor_id.to_string())
+            .await?;
+        let metrics = compute_anchor_metrics_with_uptime(
             total_transactions,
             successful_transactions,
             failed_transactions,
             avg_settlement_time_ms,
+            uptime,
         );
+        let collapsed = previous
+            .as_ref()
+            .zip(volume_usd)
+            .is_some_and(|(previous, volume)| volume_collapsed(volume, previous.total_volume_usd));
+        let status = metrics.status.with_volume_collapse(collapsed);
 
         // Update anchor
         let anchor = sqlx::query_as::<_, Anchor>(
@@ -154,13 +222,27 @@ impl Database {
         .bind(failed_transactions)
         .bind(avg_settlement_time_ms.unwrap_or(0))
         .bind(metrics.reliability_score)
-        .bind(metrics.status.as_str())
+        .bind(status.as_str())
         .bind(volume_usd.unwrap_or(0.0))
         .bind(Utc::now())
         .bind(anchor_id.to_string())
         .fetch_one(&self.pool)
         .await?;
 
+        if let Some(previous) = previous {
+            self.record_anchor_status_transition(
+                &previous,
+                status,
+                StatusInputs {
+                    success_rate: metrics.success_rate,
+                    failure_rate: metrics.failure_rate,
+                    uptime,
+                    volume_usd,
+                },
+            )
+            .await?;
+        }
+
         // Record metrics history
         self.record_anchor_metrics_history(AnchorMetricsParams {
             anchor_id,
@@ -178,6 +260,315 @@ impl Database {
         Ok(anchor)
     }
 
+    /// Percentage of an anchor's SEP endpoint probes within the uptime window
+    /// that were available and returned a valid response, or `None` if the
+    /// anchor has not been probed
+    pub async fn get_anchor_endpoint_uptime(&self, anchor_id: &str) -> Result<Option<f64>> {
+        let since = Utc::now() - Duration::hours(ENDPOINT_UPTIME_WINDOW_HOURS);
+        let (total, healthy): (i64, Option<i64>) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), SUM(available AND schema_valid)
+            FROM anchor_endpoint_probes
+            WHERE anchor_id = $1 AND probed_at >= $2
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if total == 0 {
+            return Ok(None);
+        }
+        Ok(Some(healthy.unwrap_or(0) as f64 / total as f64 * 100.0))
+    }
+
+    /// Recompute an anchor's reliability score and status from its stored
+    /// transaction counts and current endpoint uptime.
+    ///
+    /// Anchors without recorded transactions are left untouched.
+    pub async fn refresh_anchor_status(&self, anchor: &Anchor) -> Result<()> {
+        if anchor.total_transactions == 0 {
+            return Ok(());
+        }
+
+        let uptime = self.get_anchor_endpoint_uptime(&anchor.id).await?;
+        let metrics = compute_anchor_metrics_with_uptime(
+            anchor.total_transactions,
+            anchor.successful_transactions,
+            anchor.failed_transactions,
+            (anchor.avg_settlement_time_ms > 0).then_some(anchor.avg_settlement_time_ms),
+            uptime,
+        );
+
+        sqlx::query(
+            r#"
+            UPDATE anchors
+            SET reliability_score = $1,
+                status = $2,
+                updated_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(metrics.reliability_score)
+        .bind(metrics.status.as_str())
+        .bind(Utc::now())
+        .bind(&anchor.id)
+        .execute(&self.pool)
+        .await?;
+
+        self.record_anchor_status_transition(
+            anchor,
+            metrics.status,
+            StatusInputs {
+                success_rate: metrics.success_rate,
+                failure_rate: metrics.failure_rate,
+                uptime,
+                volume_usd: None,
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    // Status transition and incident operations
+
+    /// Record an anchor's move from its stored status to `to`, opening an
+    /// incident when it leaves green, escalating the open incident while it
+    /// stays out of green and closing it on the return to green.
+    ///
+    /// `previous` is the anchor as it was before the update; nothing is
+    /// recorded when the status is unchanged.
+    pub async fn record_anchor_status_transition(
+        &self,
+        previous: &Anchor,
+        to: AnchorStatus,
+        inputs: StatusInputs,
+    ) -> Result<Option<AnchorStatusTransition>> {
+        let Ok(from) = previous.status.parse::<AnchorStatus>() else {
+            return Ok(None);
+        };
+        if from == to {
+            return Ok(None);
+        }
+
+        let causes = StatusCause::diagnose(from, to, &inputs, previous.total_volume_usd);
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        // Writing first takes the write lock before the incident is read
+        let transition_id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO anchor_status_transitions (
+                anchor_id, from_status, to_status, causes, success_rate, failure_rate,
+                uptime_percent, volume_usd, transitioned_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(&previous.id)
+        .bind(from.as_str())
+        .bind(to.as_str())
+        .bind(serde_json::to_string(&causes)?)
+        .bind(inputs.success_rate)
+        .bind(inputs.failure_rate)
+        .bind(inputs.uptime)
+        .bind(inputs.volume_usd)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let open: Option<(i64, String, String)> = sqlx::query_as(
+            r#"
+            SELECT id, worst_status, causes FROM anchor_incidents
+            WHERE anchor_id = $1 AND ended_at IS NULL
+            ORDER BY started_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&previous.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let incident_id = match (&to, open) {
+            (AnchorStatus::Green, Some((id, _, _))) => {
+                sqlx::query("UPDATE anchor_incidents SET ended_at = $1 WHERE id = $2")
+                    .bind(now)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                Some(id)
+            }
+            (AnchorStatus::Green, None) => None,
+            (_, Some((id, worst_status, incident_causes))) => {
+                let worst = match worst_status.parse::<AnchorStatus>() {
+                    Ok(worst) if worst.severity() >= to.severity() => worst,
+                    _ => to,
+                };
+                let mut incident_causes: Vec<StatusCause> =
+                    serde_json::from_str(&incident_causes)
+                        .context("Invalid causes in anchor incident")?;
+                incident_causes.extend(
+                    causes
+                        .iter()
+                        .copied()
+                        .filter(|cause| *cause != StatusCause::Recovery),
+                );
+                incident_causes.sort();
+                incident_causes.dedup();
+
+                sqlx::query(
+                    "UPDATE anchor_incidents SET worst_status = $1, causes = $2 WHERE id = $3",
+                )
+                .bind(worst.as_str())
+                .bind(serde_json::to_string(&incident_causes)?)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+                Some(id)
+            }
+            (_, None) => {
+                let id: i64 = sqlx::query_scalar(
+                    r#"
+                    INSERT INTO anchor_incidents (anchor_id, worst_status, causes, started_at)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING id
+                    "#,
+                )
+                .bind(&previous.id)
+                .bind(to.as_str())
+                .bind(serde_json::to_string(&causes)?)
+                .bind(now)
+                .fetch_one(&mut *tx)
+                .await?;
+                Some(id)
+            }
+        };
+
+        sqlx::query("UPDATE anchor_status_transitions SET incident_id = $1 WHERE id = $2")
+            .bind(incident_id)
+            .bind(transition_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(AnchorStatusTransition {
+            id: transition_id,
+            anchor_id: previous.id.clone(),
+            incident_id,
+            from_status: from.as_str().to_string(),
+            to_status: to.as_str().to_string(),
+            causes,
+            success_rate: inputs.success_rate,
+            failure_rate: inputs.failure_rate,
+            uptime_percent: inputs.uptime,
+            volume_usd: inputs.volume_usd,
+            transitioned_at: now,
+        }))
+    }
+
+    /// Status transitions of an anchor, newest first
+    pub async fn get_anchor_status_transitions(
+        &self,
+        anchor_id: &str,
+        limit: i64,
+    ) -> Result<Vec<AnchorStatusTransition>> {
+        let rows = sqlx::query_as::<_, TransitionRow>(
+            r#"
+            SELECT * FROM anchor_status_transitions
+            WHERE anchor_id = $1
+            ORDER BY transitioned_at DESC, id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(TransitionRow::into_transition).collect()
+    }
+
+    /// Incidents of an anchor with their transitions, newest first
+    pub async fn get_anchor_incident_timeline(
+        &self,
+        anchor_id: &str,
+        limit: i64,
+    ) -> Result<Vec<IncidentTimelineEntry>> {
+        let incidents = self.list_incidents(Some(anchor_id), None, limit).await?;
+
+        let mut timeline = Vec::with_capacity(incidents.len());
+        for incident in incidents {
+            let rows = sqlx::query_as::<_, TransitionRow>(
+                r#"
+                SELECT * FROM anchor_status_transitions
+                WHERE incident_id = $1
+                ORDER BY transitioned_at ASC, id ASC
+                "#,
+            )
+            .bind(incident.id)
+            .fetch_all(&self.pool)
+            .await?;
+            let transitions = rows
+                .into_iter()
+                .map(TransitionRow::into_transition)
+                .collect::<Result<_>>()?;
+            timeline.push(IncidentTimelineEntry {
+                incident,
+                transitions,
+            });
+        }
+
+        Ok(timeline)
+    }
+
+    /// Incidents across anchors, newest first; `open` selects only open
+    /// (`true`) or only resolved (`false`) incidents
+    pub async fn list_incidents(
+        &self,
+        anchor_id: Option<&str>,
+        open: Option<bool>,
+        limit: i64,
+    ) -> Result<Vec<AnchorIncident>> {
+        let rows = sqlx::query_as::<_, IncidentRow>(
+            r#"
+            SELECT i.*, a.name AS anchor_name
+            FROM anchor_incidents i
+            JOIN anchors a ON a.id = i.anchor_id
+            WHERE ($1 IS NULL OR i.anchor_id = $1)
+              AND ($2 IS NULL OR (i.ended_at IS NULL) = $2)
+            ORDER BY i.started_at DESC, i.id DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(open)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let now = Utc::now();
+        rows.into_iter()
+            .map(|row| {
+                let duration = row.ended_at.unwrap_or(now) - row.started_at;
+                Ok(AnchorIncident {
+                    id: row.id,
+                    anchor_id: row.anchor_id,
+                    anchor_name: row.anchor_name,
+                    worst_status: row.worst_status,
+                    causes: serde_json::from_str(&row.causes)
+                        .context("Invalid causes in anchor incident")?,
+                    started_at: row.started_at,
+                    ended_at: row.ended_at,
+                    duration_secs: duration.num_seconds(),
+                })
+            })
+            .collect()
+    }
+
     // Asset operations
     pub async fn create_asset(
         &self,
@@ -235,6 +626,15 @@ impl Database {
 
     // Update anchor metrics from RPC ingestion
     pub async fn update_anchor_from_rpc(&self, params: AnchorRpcUpdate) -> Result<()> {
+        let previous = self
+            .get_anchor_by_stellar_account(&params.stellar_account)
+            .await?;
+        let status = params.status.parse::<AnchorStatus>().ok().map(|status| {
+            status.with_volume_collapse(previous.as_ref().is_some_and(|previous| {
+                volume_collapsed(params.total_volume_usd, previous.total_volume_usd)
+            }))
+        });
+
         sqlx::query(
             r#"
             UPDATE anchors
@@ -255,12 +655,34 @@ impl Database {
         .bind(params.total_volume_usd)
         .bind(params.avg_settlement_time_ms)
         .bind(params.reliability_score)
-        .bind(&params.status)
+        .bind(status.map_or(params.status.as_str(), |status| status.as_str()))
         .bind(Utc::now())
         .bind(&params.stellar_account)
         .execute(&self.pool)
         .await?;
 
+        if let (Some(previous), Some(status)) = (previous, status) {
+            let rate = |count: i64| {
+                if params.total_transactions > 0 {
+                    count as f64 / params.total_transactions as f64 * 100.0
+                } else {
+                    0.0
+                }
+            };
+            let uptime = self.get_anchor_endpoint_uptime(&previous.id).await?;
+            self.record_anchor_status_transition(
+                &previous,
+                status,
+                StatusInputs {
+                    success_rate: rate(params.successful_transactions),
+                    failure_rate: rate(params.failed_transactions),
+                    uptime,
+                    volume_usd: Some(params.total_volume_usd),
+                },
+            )
+            .await?;
+        }
+
         Ok(())
     }
 
@@ -17,11 +17,11 @@ impl CorridorAggregates {
         &self,
         analytics: &CorridorAnalytics,
         date: NaiveDate,
-    ) -> Result<CorridorMetrics> {
+    ) -> Result<()> {
         let date_datetime = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
         let corridor_key = analytics.corridor.to_string_key();
 
-        let metrics = sqlx::query_as::<_, CorridorMetrics>(
+        sqlx::query(
             r#"
             INSERT INTO corridor_metrics (
                 corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
@@ -36,7 +36,6 @@ impl CorridorAggregates {
                 success_rate = EXCLUDED.success_rate,
                 volume_usd = EXCLUDED.volume_usd,
                 updated_at = CURRENT_TIMESTAMP
-            RETURNING *
             "#,
         )
         .bind(&corridor_key)
@@ -50,10 +49,10 @@ impl CorridorAggregates {
         .bind(analytics.failed_transactions)
         .bind(analytics.success_rate)
         .bind(analytics.volume_usd)
-        .fetch_one(&self.pool)
+        .execute(&self.pool)
         .await?;
 
-        Ok(metrics)
+        Ok(())
     }
 
     pub async fn get_corridor_metrics(
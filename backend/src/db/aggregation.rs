@@ -81,7 +81,7 @@ impl AggregationDb {
         Ok(payment_records)
     }
 
-    /// Upsert hourly corridor metric
+    /// Upsert hourly corridor metric, replacing any stored totals for the hour
     pub async fn upsert_hourly_corridor_metric(
         &self,
         metric: &HourlyCorridorMetrics,
@@ -110,18 +110,14 @@ impl AggregationDb {
                 updated_at
             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(corridor_key, hour_bucket) DO UPDATE SET
-                total_transactions = total_transactions + excluded.total_transactions,
-                successful_transactions = successful_transactions + excluded.successful_transactions,
-                failed_transactions = failed_transactions + excluded.failed_transactions,
-                success_rate = (successful_transactions * 100.0) / NULLIF(total_transactions, 0),
-                volume_usd = volume_usd + excluded.volume_usd,
-                avg_slippage_bps = (avg_slippage_bps + excluded.avg_slippage_bps) / 2.0,
-                avg_settlement_latency_ms = COALESCE(
-                    (avg_settlement_latency_ms + excluded.avg_settlement_latency_ms) / 2,
-                    avg_settlement_latency_ms,
-                    excluded.avg_settlement_latency_ms
-                ),
-                liquidity_depth_usd = (liquidity_depth_usd + excluded.liquidity_depth_usd) / 2.0,
+                total_transactions = excluded.total_transactions,
+                successful_transactions = excluded.successful_transactions,
+                failed_transactions = excluded.failed_transactions,
+                success_rate = excluded.success_rate,
+                volume_usd = excluded.volume_usd,
+                avg_slippage_bps = excluded.avg_slippage_bps,
+                avg_settlement_latency_ms = excluded.avg_settlement_latency_ms,
+                liquidity_depth_usd = excluded.liquidity_depth_usd,
                 updated_at = ?
             "#,
         )
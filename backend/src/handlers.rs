@@ -19,6 +19,7 @@ pub type ApiResult<T> = Result<T, ApiError>;
 pub enum ApiError {
     NotFound(String),
     BadRequest(String),
+    Unauthorized(String),
     InternalError(String),
 }
 
@@ -27,6 +28,7 @@ impl IntoResponse for ApiError {
         let (status, message) = match self {
             ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
             ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use tracing::{info, warn};
 
 use crate::database::Database;
+use crate::models::AnchorStatus;
 use crate::rpc::StellarRpcClient;
 
 pub struct DataIngestionService {
@@ -76,7 +77,12 @@ impl DataIngestionService {
             0.0
         };
 
-        let reliability_score = self.calculate_reliability_score(success_rate, failed as i64);
+        let uptime = match self.db.get_anchor_by_stellar_account(account_id).await? {
+            Some(anchor) => self.db.get_anchor_endpoint_uptime(&anchor.id).await?,
+            None => None,
+        };
+        let reliability_score =
+            self.calculate_reliability_score(success_rate, failed as i64, uptime);
 
         let avg_settlement_time = if !settlement_times.is_empty() {
             settlement_times.iter().sum::<i32>() / settlement_times.len() as i32
@@ -84,13 +90,12 @@ impl DataIngestionService {
             1000
         };
 
-        let status = if success_rate >= 98.0 {
-            "green"
-        } else if success_rate >= 95.0 {
-            "yellow"
+        let failure_rate = if total_transactions > 0 {
+            (failed as f64 / total_transactions as f64) * 100.0
         } else {
-            "red"
+            0.0
         };
+        let status = AnchorStatus::from_metrics(success_rate, failure_rate, uptime);
 
         self.db
             .update_anchor_from_rpc(crate::database::AnchorRpcUpdate {
@@ -101,15 +106,23 @@ impl DataIngestionService {
                 total_volume_usd: total_volume,
                 avg_settlement_time_ms: avg_settlement_time,
                 reliability_score,
-                status: status.to_string(),
+                status: status.as_str().to_string(),
             })
             .await?;
 
         Ok(())
     }
 
-    fn calculate_reliability_score(&self, success_rate: f64, failed_count: i64) -> f64 {
-        let base_score = success_rate / 100.0;
+    fn calculate_reliability_score(
+        &self,
+        success_rate: f64,
+        failed_count: i64,
+        uptime: Option<f64>,
+    ) -> f64 {
+        let base_score = match uptime {
+            Some(uptime) => (success_rate * 0.8 + uptime * 0.2) / 100.0,
+            None => success_rate / 100.0,
+        };
         let penalty = (failed_count as f64 * 0.01).min(0.2);
         (base_score - penalty).clamp(0.0, 1.0)
     }
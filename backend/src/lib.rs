@@ -16,6 +16,7 @@ pub mod models;
 pub mod muxed;
 pub mod openapi;
 pub mod rate_limit;
+pub mod scoring;
 pub mod services;
 pub mod shutdown;
 pub mod snapshot;
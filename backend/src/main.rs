@@ -19,7 +19,7 @@ use stellar_insights_backend::api::fee_bump;
 use stellar_insights_backend::api::liquidity_pools;
 use stellar_insights_backend::api::metrics_cached;
 use stellar_insights_backend::auth::AuthService;
-use stellar_insights_backend::auth_middleware::auth_middleware;
+use stellar_insights_backend::auth_middleware::{auth_middleware, optional_auth_middleware};
 use stellar_insights_backend::cache::{CacheConfig, CacheManager};
 use stellar_insights_backend::cache_invalidation::CacheInvalidationService;
 use stellar_insights_backend::database::Database;
@@ -36,9 +36,45 @@ use stellar_insights_backend::services::liquidity_pool_analyzer::LiquidityPoolAn
 use stellar_insights_backend::services::price_feed::{
     default_asset_mapping, PriceFeedClient, PriceFeedConfig,
 };
+use stellar_insights_backend::services::aggregation::{AggregationConfig, AggregationService};
+use stellar_insights_backend::services::anomaly_detector::{
+    AnomalyDetectorConfig, CorridorAnomalyDetector,
+};
+use stellar_insights_backend::scoring::ScoringEngine;
+use stellar_insights_backend::services::anchor_fees::{
+    AnchorFeeCollector, AnchorFeeCollectorConfig,
+};
+use stellar_insights_backend::services::anchor_registry::AnchorRegistry;
+use stellar_insights_backend::services::asset_supply::{
+    AssetSupplyMonitor, AssetSupplyMonitorConfig,
+};
+use stellar_insights_backend::services::contract::ContractService;
+use stellar_insights_backend::services::fx_spread::FxSpreadService;
+use stellar_insights_backend::services::quote_comparison::QuoteComparer;
+use stellar_insights_backend::services::quote_simulator::QuoteSimulator;
+use stellar_insights_backend::services::rollup::{RollupConfig, RollupEngine};
+use stellar_insights_backend::services::route_finder::RouteFinder;
+use stellar_insights_backend::services::sep10_client::{Sep10Client, Sep10ClientConfig};
+use stellar_insights_backend::services::sep10_server::{Sep10Server, Sep10ServerConfig};
+use stellar_insights_backend::services::signer::Keypair;
+use stellar_insights_backend::services::sep38_client::{Sep38Client, Sep38ClientConfig};
+use stellar_insights_backend::services::sep_endpoint_prober::{
+    SepEndpointProber, SepEndpointProberConfig,
+};
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::services::snapshot_scheduler::{
+    SnapshotScheduler, SnapshotSchedulerConfig,
+};
+use stellar_insights_backend::services::stellar_toml_crawler::{
+    StellarTomlCrawler, StellarTomlCrawlerConfig,
+};
+use stellar_insights_backend::services::transfer_tracker::{
+    TransferTracker, TransferTrackerConfig,
+};
 use stellar_insights_backend::services::trustline_analyzer::TrustlineAnalyzer;
 use stellar_insights_backend::shutdown::{ShutdownConfig, ShutdownCoordinator};
-use stellar_insights_backend::state::AppState;
+use stellar_insights_backend::snapshot_handlers::SnapshotAppState;
+use stellar_insights_backend::state::{AppState, CachedState};
 use stellar_insights_backend::websocket::WsState;
 
 #[tokio::main]
@@ -134,6 +170,148 @@ async fn main() -> Result<()> {
     let price_feed = Arc::new(PriceFeedClient::new(price_feed_config, asset_mapping));
     tracing::info!("Price feed client initialized");
 
+    // Initialize Scoring Engine
+    let scoring_engine = Arc::new(ScoringEngine::from_env()?);
+    tracing::info!("Scoring engine initialized");
+
+    // Initialize Route Finder
+    let route_finder = Arc::new(
+        RouteFinder::new(pool.clone(), Arc::clone(&rpc_client))
+            .with_price_feed(Arc::clone(&price_feed)),
+    );
+
+    // Initialize Quote Simulator
+    let quote_simulator = Arc::new(QuoteSimulator::new(pool.clone(), Arc::clone(&rpc_client)));
+
+    // Initialize FX Spread Service
+    let fx_spread_service = Arc::new(FxSpreadService::new(
+        pool.clone(),
+        Arc::clone(&rpc_client),
+        Arc::clone(&price_feed),
+    ));
+
+    // Initialize Corridor Anomaly Detector
+    let anomaly_detector = Arc::new(CorridorAnomalyDetector::new(
+        Arc::clone(&db),
+        Arc::clone(&ws_state),
+        AnomalyDetectorConfig::default(),
+    ));
+
+    // Initialize Metric Rollup Engine
+    let rollup_engine = Arc::new(RollupEngine::new(
+        Arc::clone(&db),
+        RollupConfig::default(),
+    ));
+
+    // Initialize hourly/daily corridor metrics, copied from the rollups
+    let aggregation_service = Arc::new(AggregationService::new(
+        Arc::clone(&db),
+        Arc::clone(&rollup_engine),
+        AggregationConfig::default(),
+    ));
+
+    // Initialize stellar.toml Crawler
+    let stellar_toml_crawler = Arc::new(StellarTomlCrawler::new(
+        Arc::clone(&db),
+        Arc::clone(&rpc_client),
+        StellarTomlCrawlerConfig::default(),
+    )?);
+
+    // Initialize SEP-10 Client; without SEP10_SIGNING_SECRET only
+    // client-supplied keys can authenticate
+    let sep10_signing_key = match std::env::var("SEP10_SIGNING_SECRET") {
+        Ok(secret) => Some(Keypair::from_secret(&secret)?),
+        Err(_) => None,
+    };
+    let mut sep10_config = Sep10ClientConfig::default();
+    if let Ok(passphrase) = std::env::var("SEP10_NETWORK_PASSPHRASE") {
+        sep10_config.network_passphrase = passphrase;
+    }
+    let sep10_client = Arc::new(Sep10Client::new(
+        Arc::clone(&stellar_toml_crawler),
+        sep10_config,
+        sep10_signing_key,
+    ));
+    if let Some(account) = sep10_client.account() {
+        tracing::info!("SEP-10 client authenticates as {}", account);
+    }
+
+    // Initialize SEP-38 Client and Quote Comparer
+    let sep38_client = Arc::new(Sep38Client::new(Sep38ClientConfig::default()));
+    let quote_comparer = Arc::new(QuoteComparer::new(
+        Arc::clone(&db),
+        Arc::clone(&sep38_client),
+        Arc::clone(&sep10_client),
+    ));
+
+    // Initialize Transfer Tracker; with TRANSFER_CALLBACK_BASE_URL anchors
+    // report status changes of proxied transfers themselves
+    let transfer_tracker = Arc::new(TransferTracker::new(
+        Arc::clone(&db),
+        Arc::clone(&sep10_client),
+        TransferTrackerConfig {
+            callback_base_url: std::env::var("TRANSFER_CALLBACK_BASE_URL").ok(),
+            ..TransferTrackerConfig::default()
+        },
+    ));
+
+    // Initialize SEP Endpoint Prober
+    let sep_endpoint_prober = Arc::new(SepEndpointProber::new(
+        Arc::clone(&db),
+        SepEndpointProberConfig::default(),
+    ));
+
+    // Initialize Anchor Registry (anchors the SEP proxies may call)
+    let anchor_registry = Arc::new(AnchorRegistry::new(Arc::clone(&db)));
+
+    // Initialize Snapshot Service (on-chain submission needs SNAPSHOT_CONTRACT_ID and
+    // a transaction signer, see services::signer::signer_from_env)
+    let contract_service = match ContractService::from_env() {
+        Ok(service) => Some(Arc::new(service)),
+        Err(e) => {
+            tracing::info!("Snapshot contract not configured: {}", e);
+            None
+        }
+    };
+    let snapshot_service = Arc::new(SnapshotService::new(
+        Arc::clone(&db),
+        contract_service.clone(),
+    ));
+    let snapshot_state = SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service,
+        snapshot_service: Arc::clone(&snapshot_service),
+    };
+
+    // Initialize Snapshot Scheduler; a snapshot is generated every
+    // SNAPSHOT_EPOCH_SECS seconds when set
+    let snapshot_scheduler = std::env::var("SNAPSHOT_EPOCH_SECS")
+        .ok()
+        .and_then(|secs| secs.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .map(|epoch_secs| {
+            Arc::new(SnapshotScheduler::new(
+                Arc::clone(&snapshot_service),
+                SnapshotSchedulerConfig {
+                    epoch_secs,
+                    ..SnapshotSchedulerConfig::default()
+                },
+            ))
+        });
+
+    // Initialize Anchor Fee Collector
+    let anchor_fee_collector = Arc::new(AnchorFeeCollector::new(
+        Arc::clone(&db),
+        AnchorFeeCollectorConfig::default(),
+    ));
+
+    // Initialize Asset Supply Monitor
+    let asset_supply_monitor = Arc::new(AssetSupplyMonitor::new(
+        Arc::clone(&db),
+        Arc::clone(&rpc_client),
+        AssetSupplyMonitorConfig::default(),
+    ));
+
     // Initialize Trustline Analyzer
     let trustline_analyzer = Arc::new(TrustlineAnalyzer::new(
         pool.clone(),
@@ -161,15 +339,17 @@ async fn main() -> Result<()> {
         Arc::clone(&db),
         Arc::clone(&ws_state),
         Arc::clone(&ingestion_service),
+        Arc::clone(&scoring_engine),
     );
 
-    // Create cached state tuple for cached API handlers
-    let cached_state = (
-        Arc::clone(&db),
-        Arc::clone(&cache),
-        Arc::clone(&rpc_client),
-        Arc::clone(&price_feed),
-    );
+    // Create cached state for cached API handlers
+    let cached_state = CachedState {
+        db: Arc::clone(&db),
+        cache: Arc::clone(&cache),
+        rpc_client: Arc::clone(&rpc_client),
+        price_feed: Arc::clone(&price_feed),
+        scoring: Arc::clone(&scoring_engine),
+    };
 
     let ingestion_clone = Arc::clone(&ingestion_service);
     let cache_invalidation_clone = Arc::clone(&cache_invalidation);
@@ -215,11 +395,54 @@ async fn main() -> Result<()> {
         tracing::warn!("Invalid Redis URL for auth service");
         None
     };
-    let auth_service = Arc::new(AuthService::new(Arc::new(tokio::sync::RwLock::new(
-        auth_redis_connection,
-    ))));
+    let sep10_login_accounts: Vec<String> = std::env::var("SEP10_LOGIN_ACCOUNTS")
+        .map(|accounts| {
+            accounts
+                .split(',')
+                .map(str::trim)
+                .filter(|account| !account.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let auth_service = Arc::new(
+        AuthService::new(Arc::new(tokio::sync::RwLock::new(auth_redis_connection)))
+            .with_sep10_accounts(sep10_login_accounts.clone()),
+    );
     tracing::info!("Auth service initialized");
 
+    // Initialize SEP-10 login; without SEP10_SERVER_SECRET only password
+    // login is available
+    let sep10_server = match std::env::var("SEP10_SERVER_SECRET") {
+        Ok(secret) => {
+            let mut config = Sep10ServerConfig::default();
+            if let Ok(passphrase) = std::env::var("SEP10_NETWORK_PASSPHRASE") {
+                config.network_passphrase = passphrase;
+            }
+            if let Ok(home_domain) = std::env::var("SEP10_HOME_DOMAIN") {
+                config.web_auth_domain = home_domain.clone();
+                config.home_domain = home_domain;
+            }
+            if let Ok(web_auth_domain) = std::env::var("SEP10_WEB_AUTH_DOMAIN") {
+                config.web_auth_domain = web_auth_domain;
+            }
+            let server = Sep10Server::new(
+                Keypair::from_secret(&secret)?,
+                Arc::clone(&rpc_client),
+                config,
+            );
+            tracing::info!(
+                "SEP-10 login enabled, challenges signed by {}",
+                server.signing_account()
+            );
+            if sep10_login_accounts.is_empty() {
+                tracing::warn!("SEP10_LOGIN_ACCOUNTS is empty, no account can sign in");
+            }
+            Some(Arc::new(server))
+        }
+        Err(_) => None,
+    };
+
     // ML Retraining task (commented out)
     /*
     let ml_service_clone = ml_service.clone();
@@ -289,6 +512,48 @@ async fn main() -> Result<()> {
         }
     });
 
+    // FX spread sync background task
+    let fx_spread_clone = Arc::clone(&fx_spread_service);
+    tokio::spawn(async move {
+        tracing::info!("Starting FX spread sync background task");
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
+        loop {
+            interval.tick().await;
+            if let Err(e) = fx_spread_clone.sync_spreads(200).await {
+                tracing::error!("FX spread sync failed: {}", e);
+            }
+        }
+    });
+
+    // Corridor anomaly detection background task
+    tokio::spawn(Arc::clone(&anomaly_detector).start_scheduler());
+
+    // Metric rollup and retention background task
+    tokio::spawn(Arc::clone(&rollup_engine).start_scheduler());
+
+    // Hourly and daily corridor metrics background task
+    tokio::spawn(Arc::clone(&aggregation_service).start_scheduler());
+
+    // stellar.toml crawl background task
+    tokio::spawn(Arc::clone(&stellar_toml_crawler).start_scheduler());
+
+    // SEP endpoint uptime probe background task
+    tokio::spawn(Arc::clone(&sep_endpoint_prober).start_scheduler());
+
+    // Anchor fee schedule collection background task
+    tokio::spawn(Arc::clone(&anchor_fee_collector).start_scheduler());
+
+    // Issued asset supply sync background task
+    tokio::spawn(Arc::clone(&asset_supply_monitor).start_scheduler());
+
+    // Proxied SEP-24/SEP-31 transfer polling background task
+    tokio::spawn(Arc::clone(&transfer_tracker).start_scheduler());
+
+    // Snapshot epoch generation, submission and reconciliation background task
+    if let Some(snapshot_scheduler) = &snapshot_scheduler {
+        tokio::spawn(Arc::clone(snapshot_scheduler).start_scheduler());
+    }
+
     // Run initial sync (skip on network errors)
     tracing::info!("Running initial metrics synchronization...");
     let _ = ingestion_service.sync_all_metrics().await;
@@ -384,6 +649,106 @@ async fn main() -> Result<()> {
         )
         .await;
 
+    rate_limiter
+        .register_endpoint(
+            "/api/routes".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 60,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/scoring".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 100,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/rollups".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 100,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/quotes".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 60,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/fees/compare".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 60,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/incidents".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 100,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/auth/sep10".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 30,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/sep10/token".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 30,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/sep38/compare".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 30,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
+    rate_limiter
+        .register_endpoint(
+            "/api/transfers".to_string(),
+            RateLimitConfig {
+                requests_per_minute: 100,
+                whitelist_ips: vec![],
+            },
+        )
+        .await;
+
     rate_limiter
         .register_endpoint(
             "/api/account-merges".to_string(),
@@ -422,7 +787,20 @@ async fn main() -> Result<()> {
     use tower::ServiceBuilder;
 
     // Build auth router
-    let auth_routes = stellar_insights_backend::api::auth::routes(auth_service.clone());
+    let mut auth_routes = stellar_insights_backend::api::auth::routes(auth_service.clone());
+    if let Some(sep10_server) = &sep10_server {
+        auth_routes = auth_routes.merge(
+            stellar_insights_backend::api::auth::sep10_routes(
+                auth_service.clone(),
+                Arc::clone(sep10_server),
+            )
+            .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            )))
+            .layer(cors.clone()),
+        );
+    }
 
     // Build cached routes (anchors list, corridors list/detail) with cache state
     let cached_routes = Router::new()
@@ -550,6 +928,258 @@ async fn main() -> Result<()> {
         )))
         .layer(cors.clone());
 
+    // Build route finding routes
+    let route_finder_routes = Router::new()
+        .nest(
+            "/api/routes",
+            stellar_insights_backend::api::routes::routes(Arc::clone(&route_finder)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build corridor anomaly routes
+    let anomaly_routes = stellar_insights_backend::api::anomalies::routes(Arc::clone(
+        &anomaly_detector,
+    ))
+    .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        rate_limiter.clone(),
+        rate_limit_middleware,
+    )))
+    .layer(cors.clone());
+
+    // Build FX spread routes
+    let fx_spread_routes = stellar_insights_backend::api::fx_spreads::routes(Arc::clone(
+        &fx_spread_service,
+    ))
+    .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        rate_limiter.clone(),
+        rate_limit_middleware,
+    )))
+    .layer(cors.clone());
+
+    // Build scoring model routes
+    let scoring_routes = Router::new()
+        .nest(
+            "/api/scoring",
+            stellar_insights_backend::api::scoring::routes(Arc::clone(&scoring_engine)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build metric rollup routes
+    let rollup_routes = Router::new()
+        .nest(
+            "/api/rollups",
+            stellar_insights_backend::api::rollups::routes(Arc::clone(&rollup_engine)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build quote simulation routes
+    let quote_routes = Router::new()
+        .nest(
+            "/api/quotes",
+            stellar_insights_backend::api::quotes::routes(Arc::clone(&quote_simulator)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build stellar.toml routes
+    let stellar_toml_routes = stellar_insights_backend::api::stellar_toml::routes(Arc::clone(
+        &stellar_toml_crawler,
+    ))
+    .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        rate_limiter.clone(),
+        rate_limit_middleware,
+    )))
+    .layer(cors.clone());
+
+    // Build endpoint uptime routes
+    let endpoint_uptime_routes = stellar_insights_backend::api::endpoint_uptime::routes(
+        Arc::clone(&sep_endpoint_prober),
+    )
+    .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        rate_limiter.clone(),
+        rate_limit_middleware,
+    )))
+    .layer(cors.clone());
+
+    let protected_endpoint_routes =
+        stellar_insights_backend::api::endpoint_uptime::protected_routes(Arc::clone(
+            &sep_endpoint_prober,
+        ))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build anchor proxy settings routes
+    let anchor_proxy_routes =
+        stellar_insights_backend::api::anchor_proxy::routes(Arc::clone(&anchor_registry))
+            .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            )))
+            .layer(cors.clone());
+
+    let protected_anchor_proxy_routes =
+        stellar_insights_backend::api::anchor_proxy::protected_routes(Arc::clone(&anchor_registry))
+            .layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(auth_middleware))
+                    .layer(middleware::from_fn_with_state(
+                        rate_limiter.clone(),
+                        rate_limit_middleware,
+                    )),
+            )
+            .layer(cors.clone());
+
+    // Build snapshot routes
+    let snapshot_routes =
+        stellar_insights_backend::snapshot_handlers::routes(snapshot_state.clone())
+            .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            )))
+            .layer(cors.clone());
+
+    let protected_snapshot_routes =
+        stellar_insights_backend::snapshot_handlers::protected_routes(snapshot_state)
+            .layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(auth_middleware))
+                    .layer(middleware::from_fn_with_state(
+                        rate_limiter.clone(),
+                        rate_limit_middleware,
+                    )),
+            )
+            .layer(cors.clone());
+
+    // Build anchor fee routes
+    let anchor_fee_routes = stellar_insights_backend::api::anchor_fees::routes(Arc::clone(
+        &anchor_fee_collector,
+    ))
+    .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        rate_limiter.clone(),
+        rate_limit_middleware,
+    )))
+    .layer(cors.clone());
+
+    // Build asset supply routes
+    let asset_supply_routes = stellar_insights_backend::api::asset_supply::routes(Arc::clone(
+        &asset_supply_monitor,
+    ))
+    .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+        rate_limiter.clone(),
+        rate_limit_middleware,
+    )))
+    .layer(cors.clone());
+
+    let protected_asset_supply_routes =
+        stellar_insights_backend::api::asset_supply::protected_routes(Arc::clone(
+            &asset_supply_monitor,
+        ))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build incident routes
+    let incident_routes = stellar_insights_backend::api::incidents::routes(Arc::clone(&db))
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build transfer tracking routes
+    let transfer_routes =
+        stellar_insights_backend::api::transfers::routes(Arc::clone(&transfer_tracker))
+            .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+                rate_limiter.clone(),
+                rate_limit_middleware,
+            )))
+            .layer(cors.clone());
+    let protected_transfer_routes =
+        stellar_insights_backend::api::transfers::protected_routes(Arc::clone(&transfer_tracker))
+            .layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(auth_middleware))
+                    .layer(middleware::from_fn_with_state(
+                        rate_limiter.clone(),
+                        rate_limit_middleware,
+                    )),
+            )
+            .layer(cors.clone());
+
+    // Build SEP-6/SEP-24/SEP-31/SEP-38 proxy, quote comparison and SEP-10 routes;
+    // signed-in callers may use the backend's SEP-10 key
+    let sep_routes = Router::new()
+        .merge(stellar_insights_backend::api::sep6_proxy::routes(
+            Arc::clone(&sep10_client),
+            Arc::clone(&anchor_registry),
+        ))
+        .merge(stellar_insights_backend::api::sep24_proxy::routes(
+            Arc::clone(&sep10_client),
+            Arc::clone(&transfer_tracker),
+            Arc::clone(&anchor_registry),
+        ))
+        .merge(stellar_insights_backend::api::sep31_proxy::routes(
+            Arc::clone(&sep10_client),
+            Arc::clone(&transfer_tracker),
+            Arc::clone(&anchor_registry),
+        ))
+        .merge(stellar_insights_backend::api::sep38_proxy::routes(
+            Arc::clone(&sep38_client),
+            Arc::clone(&sep10_client),
+        ))
+        .merge(stellar_insights_backend::api::quote_comparison::routes(
+            Arc::clone(&quote_comparer),
+        ))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(optional_auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    let protected_sep10_routes =
+        stellar_insights_backend::api::sep10::protected_routes(Arc::clone(&sep10_client))
+            .layer(
+                ServiceBuilder::new()
+                    .layer(middleware::from_fn(auth_middleware))
+                    .layer(middleware::from_fn_with_state(
+                        rate_limiter.clone(),
+                        rate_limit_middleware,
+                    )),
+            )
+            .layer(cors.clone());
+
     // Build trustline routes
     let trustline_routes = Router::new()
         .nest(
@@ -576,6 +1206,27 @@ async fn main() -> Result<()> {
         .merge(account_merge_routes)
         .merge(lp_routes)
         .merge(price_routes)
+        .merge(route_finder_routes)
+        .merge(quote_routes)
+        .merge(anomaly_routes)
+        .merge(fx_spread_routes)
+        .merge(scoring_routes)
+        .merge(rollup_routes)
+        .merge(stellar_toml_routes)
+        .merge(endpoint_uptime_routes)
+        .merge(protected_endpoint_routes)
+        .merge(anchor_proxy_routes)
+        .merge(protected_anchor_proxy_routes)
+        .merge(snapshot_routes)
+        .merge(protected_snapshot_routes)
+        .merge(anchor_fee_routes)
+        .merge(asset_supply_routes)
+        .merge(protected_asset_supply_routes)
+        .merge(incident_routes)
+        .merge(transfer_routes)
+        .merge(protected_transfer_routes)
+        .merge(sep_routes)
+        .merge(protected_sep10_routes)
         .merge(trustline_routes)
         .merge(cache_routes)
         .merge(metrics_routes)
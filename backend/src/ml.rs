@@ -1,4 +1,5 @@
 use crate::database::Database;
+use crate::models::asset::CorridorKey;
 use chrono::{DateTime, Datelike, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -112,33 +113,33 @@ impl MLService {
         Ok(training_data)
     }
 
-    fn hash_corridor(&self, asset_code: &Option<String>, asset_issuer: &Option<String>) -> f32 {
+    fn hash_corridor(&self, corridor: &CorridorKey) -> f32 {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
         let mut hasher = DefaultHasher::new();
-        asset_code.hash(&mut hasher);
-        asset_issuer.hash(&mut hasher);
+        corridor.source.hash(&mut hasher);
+        corridor.destination.hash(&mut hasher);
         (hasher.finish() % 1000) as f32 / 1000.0
     }
 
     pub async fn predict_payment_success(
         &self,
-        corridor: &str,
+        corridor: &CorridorKey,
         amount_usd: f64,
         timestamp: DateTime<Utc>,
     ) -> anyhow::Result<PredictionResult> {
-        let parts: Vec<&str> = corridor.split('-').collect();
-        let corridor_hash = self.hash_corridor(
-            &Some(parts.get(0).unwrap_or(&"").to_string()),
-            &Some(parts.get(1).unwrap_or(&"").to_string()),
-        );
+        let corridor_hash = self.hash_corridor(corridor);
+        let corridor_key = corridor.to_string();
 
         let liquidity = self
-            .get_corridor_liquidity(corridor)
+            .get_corridor_liquidity(&corridor_key)
             .await
             .unwrap_or(1000.0);
-        let recent_success = self.get_recent_success_rate(corridor).await.unwrap_or(0.8);
+        let recent_success = self
+            .get_recent_success_rate(&corridor_key)
+            .await
+            .unwrap_or(0.8);
 
         let features = PredictionFeatures {
             corridor_hash,
@@ -1,4 +1,5 @@
 use crate::ml::{MLService, PredictionResult};
+use crate::models::asset::CorridorKey;
 use axum::{extract::Query, http::StatusCode, response::Json, Extension};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -7,7 +8,8 @@ use tokio::sync::RwLock;
 
 #[derive(Debug, Deserialize)]
 pub struct PredictionQuery {
-    pub corridor: String,
+    /// Corridor as `SOURCE->DESTINATION` (e.g. `USDC:G...->native`)
+    pub corridor: CorridorKey,
     pub amount_usd: f64,
     #[serde(default = "default_timestamp")]
     pub timestamp: DateTime<Utc>,
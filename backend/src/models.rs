@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub mod asset;
 pub mod corridor;
 
 #[derive(Debug, Deserialize, Default)]
@@ -68,7 +69,7 @@ pub struct AnchorMetrics {
     pub status: AnchorStatus,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum AnchorStatus {
     Green,
     Yellow,
@@ -84,15 +85,163 @@ impl AnchorStatus {
         }
     }
 
-    pub fn from_metrics(success_rate: f64, failure_rate: f64) -> Self {
-        if success_rate > 98.0 && failure_rate <= 1.0 {
+    /// Classify an anchor from its payment rates and, when its SEP endpoints
+    /// have been probed, their uptime percentage.
+    pub fn from_metrics(success_rate: f64, failure_rate: f64, uptime: Option<f64>) -> Self {
+        let uptime_at_least = |min: f64| uptime.is_none_or(|uptime| uptime >= min);
+
+        if success_rate > 98.0 && failure_rate <= 1.0 && uptime_at_least(99.0) {
             AnchorStatus::Green
-        } else if success_rate >= 95.0 && failure_rate <= 5.0 {
+        } else if success_rate >= 95.0 && failure_rate <= 5.0 && uptime_at_least(95.0) {
             AnchorStatus::Yellow
         } else {
             AnchorStatus::Red
         }
     }
+
+    /// A volume collapse on its own takes a green anchor to yellow
+    pub fn with_volume_collapse(self, collapsed: bool) -> Self {
+        match self {
+            AnchorStatus::Green if collapsed => AnchorStatus::Yellow,
+            status => status,
+        }
+    }
+
+    /// 0 for green, 1 for yellow, 2 for red
+    pub fn severity(&self) -> u8 {
+        match self {
+            AnchorStatus::Green => 0,
+            AnchorStatus::Yellow => 1,
+            AnchorStatus::Red => 2,
+        }
+    }
+}
+
+impl std::str::FromStr for AnchorStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "green" => Ok(AnchorStatus::Green),
+            "yellow" => Ok(AnchorStatus::Yellow),
+            "red" => Ok(AnchorStatus::Red),
+            other => Err(format!("unknown anchor status: {}", other)),
+        }
+    }
+}
+
+/// Fraction of the previous volume an anchor must lose for a volume collapse
+pub const VOLUME_COLLAPSE_RATIO: f64 = 0.8;
+
+/// Whether `volume` fell by [`VOLUME_COLLAPSE_RATIO`] or more from `previous_volume`
+pub fn volume_collapsed(volume: f64, previous_volume: f64) -> bool {
+    previous_volume > 0.0 && volume <= previous_volume * (1.0 - VOLUME_COLLAPSE_RATIO)
+}
+
+/// Measurements an anchor's status was computed from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusInputs {
+    pub success_rate: f64,
+    pub failure_rate: f64,
+    pub uptime: Option<f64>,
+    /// `None` when the update did not measure volume
+    pub volume_usd: Option<f64>,
+}
+
+/// Why an anchor's status changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCause {
+    SuccessRateDrop,
+    EndpointDowntime,
+    VolumeCollapse,
+    Recovery,
+}
+
+impl StatusCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusCause::SuccessRateDrop => "success_rate_drop",
+            StatusCause::EndpointDowntime => "endpoint_downtime",
+            StatusCause::VolumeCollapse => "volume_collapse",
+            StatusCause::Recovery => "recovery",
+        }
+    }
+
+    /// Causes of a move from `from` to `to`.
+    ///
+    /// Upgrades are a recovery. A downgrade is attributed to every input that
+    /// on its own rules out the previous status, plus a volume collapse when
+    /// volume collapsed from `previous_volume`.
+    pub fn diagnose(
+        from: AnchorStatus,
+        to: AnchorStatus,
+        inputs: &StatusInputs,
+        previous_volume: f64,
+    ) -> Vec<StatusCause> {
+        if to.severity() < from.severity() {
+            return vec![StatusCause::Recovery];
+        }
+
+        let mut causes = Vec::new();
+        let rates = AnchorStatus::from_metrics(inputs.success_rate, inputs.failure_rate, None);
+        if rates.severity() > from.severity() {
+            causes.push(StatusCause::SuccessRateDrop);
+        }
+        if let Some(uptime) = inputs.uptime {
+            if AnchorStatus::from_metrics(100.0, 0.0, Some(uptime)).severity() > from.severity() {
+                causes.push(StatusCause::EndpointDowntime);
+            }
+        }
+        if inputs
+            .volume_usd
+            .is_some_and(|volume| volume_collapsed(volume, previous_volume))
+        {
+            causes.push(StatusCause::VolumeCollapse);
+        }
+        causes
+    }
+}
+
+/// A recorded change of an anchor's status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorStatusTransition {
+    pub id: i64,
+    pub anchor_id: String,
+    /// Incident the transition opened, escalated or closed
+    pub incident_id: Option<i64>,
+    pub from_status: String,
+    pub to_status: String,
+    pub causes: Vec<StatusCause>,
+    pub success_rate: f64,
+    pub failure_rate: f64,
+    pub uptime_percent: Option<f64>,
+    pub volume_usd: Option<f64>,
+    pub transitioned_at: DateTime<Utc>,
+}
+
+/// A period an anchor spent outside green
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorIncident {
+    pub id: i64,
+    pub anchor_id: String,
+    pub anchor_name: String,
+    pub worst_status: String,
+    /// Every cause of the downgrades during the incident
+    pub causes: Vec<StatusCause>,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the anchor has not returned to green
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Time spent outside green, up to now for open incidents
+    pub duration_secs: i64,
+}
+
+/// An incident with the transitions that make it up, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentTimelineEntry {
+    #[serde(flatten)]
+    pub incident: AnchorIncident,
+    pub transitions: Vec<AnchorStatusTransition>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -227,6 +376,22 @@ pub struct LiquidityPool {
     pub updated_at: DateTime<Utc>,
 }
 
+impl LiquidityPool {
+    /// Reserve assets in (a, b) order
+    pub fn reserve_assets(&self) -> Result<(asset::AssetId, asset::AssetId), asset::AssetIdError> {
+        Ok((
+            asset::AssetId::from_parts(
+                &self.reserve_a_asset_code,
+                self.reserve_a_asset_issuer.as_deref(),
+            )?,
+            asset::AssetId::from_parts(
+                &self.reserve_b_asset_code,
+                self.reserve_b_asset_issuer.as_deref(),
+            )?,
+        ))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct LiquidityPoolSnapshot {
     pub id: i64,
@@ -0,0 +1,521 @@
+//! Typed Stellar asset identifiers.
+//!
+//! [`AssetId`] is the single representation of an asset across the backend.
+//! It parses the SEP-11 string form (`native`, `CODE:ISSUER`), the `XLM` and
+//! `XLM:native` spellings used in stored corridor keys, and Soroban contract
+//! ids (`C...`), validating codes and strkeys instead of passing arbitrary
+//! strings through. [`CorridorKey`] is a directional pair of assets in the
+//! `SOURCE->DESTINATION` form used by corridor keys.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Encode, Type};
+use std::fmt;
+use std::str::FromStr;
+use stellar_xdr::curr as xdr;
+
+/// Issuer column value used for lumens in corridor keys and tables
+pub const NATIVE_ISSUER: &str = "native";
+/// Code column value used for lumens in corridor keys and tables
+pub const NATIVE_CODE: &str = "XLM";
+
+const MAX_CODE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetIdError {
+    Empty,
+    MissingIssuer(String),
+    InvalidCode(String),
+    InvalidIssuer(String),
+    InvalidContract(String),
+    /// Contract assets have no classic code/issuer or Horizon representation
+    NotClassic(String),
+    InvalidCorridorKey(String),
+    Xdr(String),
+}
+
+impl fmt::Display for AssetIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "asset identifier is empty"),
+            Self::MissingIssuer(s) => write!(
+                f,
+                "asset '{}' must be 'native', 'CODE:ISSUER' or a contract id",
+                s
+            ),
+            Self::InvalidCode(s) => write!(
+                f,
+                "invalid asset code '{}' (expected 1-12 alphanumeric characters)",
+                s
+            ),
+            Self::InvalidIssuer(s) => write!(f, "invalid issuer account '{}'", s),
+            Self::InvalidContract(s) => write!(f, "invalid contract id '{}'", s),
+            Self::NotClassic(s) => write!(f, "contract asset '{}' is not a classic asset", s),
+            Self::InvalidCorridorKey(s) => write!(
+                f,
+                "invalid corridor key '{}' (expected SOURCE->DESTINATION)",
+                s
+            ),
+            Self::Xdr(e) => write!(f, "invalid asset XDR: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AssetIdError {}
+
+/// A validated Stellar asset
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AssetId {
+    /// Lumens
+    Native,
+    /// Classic issued asset; `issuer` is a validated G... account
+    Credit { code: String, issuer: String },
+    /// Soroban token contract; holds a validated C... contract id
+    Contract(String),
+}
+
+impl AssetId {
+    /// Issued asset from a code and issuer, validating both
+    pub fn credit(code: &str, issuer: &str) -> Result<Self, AssetIdError> {
+        if code.is_empty()
+            || code.len() > MAX_CODE_LEN
+            || !code.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(AssetIdError::InvalidCode(code.to_string()));
+        }
+        xdr::AccountId::from_str(issuer)
+            .map_err(|_| AssetIdError::InvalidIssuer(issuer.to_string()))?;
+
+        Ok(Self::Credit {
+            code: code.to_string(),
+            issuer: issuer.to_string(),
+        })
+    }
+
+    /// Contract asset from a C... strkey
+    pub fn contract(contract_id: &str) -> Result<Self, AssetIdError> {
+        match xdr::ScAddress::from_str(contract_id) {
+            Ok(xdr::ScAddress::Contract(_)) => Ok(Self::Contract(contract_id.to_string())),
+            _ => Err(AssetIdError::InvalidContract(contract_id.to_string())),
+        }
+    }
+
+    /// Asset from code/issuer columns, where lumens are `XLM`/`native` or a
+    /// missing issuer (as in `liquidity_pools` and Horizon reserves).
+    pub fn from_parts(code: &str, issuer: Option<&str>) -> Result<Self, AssetIdError> {
+        match issuer {
+            None | Some(NATIVE_ISSUER) | Some("")
+                if code.eq_ignore_ascii_case(NATIVE_CODE)
+                    || code.eq_ignore_ascii_case(NATIVE_ISSUER) =>
+            {
+                Ok(Self::Native)
+            }
+            Some(issuer) if !issuer.is_empty() => Self::credit(code, issuer),
+            _ => Err(AssetIdError::MissingIssuer(code.to_string())),
+        }
+    }
+
+    /// Asset from Horizon's `asset_type`/`asset_code`/`asset_issuer` fields
+    pub fn from_horizon(
+        asset_type: &str,
+        code: Option<&str>,
+        issuer: Option<&str>,
+    ) -> Result<Self, AssetIdError> {
+        if asset_type == "native" {
+            return Ok(Self::Native);
+        }
+        let code = code.ok_or_else(|| AssetIdError::InvalidCode(asset_type.to_string()))?;
+        let issuer = issuer.ok_or_else(|| AssetIdError::MissingIssuer(code.to_string()))?;
+        Self::credit(code, issuer)
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self, Self::Native)
+    }
+
+    /// Whether the asset exists on the classic ledger (lumens or issued)
+    pub fn is_classic(&self) -> bool {
+        !matches!(self, Self::Contract(_))
+    }
+
+    /// Horizon asset type
+    pub fn asset_type(&self) -> &'static str {
+        match self {
+            Self::Native => "native",
+            Self::Credit { code, .. } if code.len() <= 4 => "credit_alphanum4",
+            Self::Credit { .. } => "credit_alphanum12",
+            Self::Contract(_) => "contract",
+        }
+    }
+
+    /// Display code: `XLM` for lumens, the contract id for contract assets
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Native => NATIVE_CODE,
+            Self::Credit { code, .. } => code,
+            Self::Contract(id) => id,
+        }
+    }
+
+    /// Code and issuer as stored in asset columns (`XLM`/`native` for lumens)
+    pub fn classic_parts(&self) -> Result<(&str, &str), AssetIdError> {
+        match self {
+            Self::Native => Ok((NATIVE_CODE, NATIVE_ISSUER)),
+            Self::Credit { code, issuer } => Ok((code, issuer)),
+            Self::Contract(id) => Err(AssetIdError::NotClassic(id.clone())),
+        }
+    }
+
+    /// Key form used in corridor keys and metric tables (`XLM:native` for lumens)
+    pub fn key(&self) -> String {
+        match self {
+            Self::Native => format!("{}:{}", NATIVE_CODE, NATIVE_ISSUER),
+            other => other.to_string(),
+        }
+    }
+
+    /// Classic asset as XDR
+    pub fn to_xdr(&self) -> Result<xdr::Asset, AssetIdError> {
+        match self {
+            Self::Native => Ok(xdr::Asset::Native),
+            Self::Credit { code, issuer } => {
+                let issuer = xdr::AccountId::from_str(issuer)
+                    .map_err(|_| AssetIdError::InvalidIssuer(issuer.clone()))?;
+                let mut bytes = [0u8; MAX_CODE_LEN];
+                bytes[..code.len()].copy_from_slice(code.as_bytes());
+
+                if code.len() <= 4 {
+                    Ok(xdr::Asset::CreditAlphanum4(xdr::AlphaNum4 {
+                        asset_code: xdr::AssetCode4([bytes[0], bytes[1], bytes[2], bytes[3]]),
+                        issuer,
+                    }))
+                } else {
+                    Ok(xdr::Asset::CreditAlphanum12(xdr::AlphaNum12 {
+                        asset_code: xdr::AssetCode12(bytes),
+                        issuer,
+                    }))
+                }
+            }
+            Self::Contract(id) => Err(AssetIdError::NotClassic(id.clone())),
+        }
+    }
+
+    /// Contract address as XDR
+    pub fn to_sc_address(&self) -> Result<xdr::ScAddress, AssetIdError> {
+        match self {
+            Self::Contract(id) => {
+                xdr::ScAddress::from_str(id).map_err(|_| AssetIdError::InvalidContract(id.clone()))
+            }
+            other => Err(AssetIdError::InvalidContract(other.to_string())),
+        }
+    }
+
+    /// Classic asset from base64 `Asset` XDR
+    pub fn from_xdr_base64(value: &str) -> Result<Self, AssetIdError> {
+        use base64::Engine;
+        use xdr::ReadXdr;
+
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(value.trim())
+            .map_err(|e| AssetIdError::Xdr(e.to_string()))?;
+        let asset = xdr::Asset::from_xdr(bytes, xdr::Limits::none())
+            .map_err(|e| AssetIdError::Xdr(e.to_string()))?;
+        Self::try_from(&asset)
+    }
+
+    /// Classic asset as base64 `Asset` XDR
+    pub fn to_xdr_base64(&self) -> Result<String, AssetIdError> {
+        use base64::Engine;
+        use xdr::WriteXdr;
+
+        let bytes = self
+            .to_xdr()?
+            .to_xdr(xdr::Limits::none())
+            .map_err(|e| AssetIdError::Xdr(e.to_string()))?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+}
+
+impl fmt::Display for AssetId {
+    /// SEP-11 form: `native`, `CODE:ISSUER` or the contract id
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native => f.write_str(NATIVE_ISSUER),
+            Self::Credit { code, issuer } => write!(f, "{}:{}", code, issuer),
+            Self::Contract(id) => f.write_str(id),
+        }
+    }
+}
+
+impl FromStr for AssetId {
+    type Err = AssetIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(AssetIdError::Empty);
+        }
+
+        match s.split_once(':') {
+            Some((code, issuer)) => Self::from_parts(code, Some(issuer)),
+            None if s.eq_ignore_ascii_case(NATIVE_ISSUER)
+                || s.eq_ignore_ascii_case(NATIVE_CODE) =>
+            {
+                Ok(Self::Native)
+            }
+            None if s.starts_with('C') => Self::contract(s),
+            None => Err(AssetIdError::MissingIssuer(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&xdr::Asset> for AssetId {
+    type Error = AssetIdError;
+
+    fn try_from(asset: &xdr::Asset) -> Result<Self, Self::Error> {
+        match asset {
+            xdr::Asset::Native => Ok(Self::Native),
+            xdr::Asset::CreditAlphanum4(a) => {
+                Self::credit(&a.asset_code.to_string(), &a.issuer.to_string())
+            }
+            xdr::Asset::CreditAlphanum12(a) => {
+                Self::credit(&a.asset_code.to_string(), &a.issuer.to_string())
+            }
+        }
+    }
+}
+
+impl TryFrom<&xdr::ScAddress> for AssetId {
+    type Error = AssetIdError;
+
+    fn try_from(address: &xdr::ScAddress) -> Result<Self, Self::Error> {
+        match address {
+            xdr::ScAddress::Contract(_) => Ok(Self::Contract(address.to_string())),
+            xdr::ScAddress::Account(_) => Err(AssetIdError::InvalidContract(address.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&crate::rpc::Asset> for AssetId {
+    type Error = AssetIdError;
+
+    fn try_from(asset: &crate::rpc::Asset) -> Result<Self, Self::Error> {
+        Self::from_horizon(
+            &asset.asset_type,
+            asset.asset_code.as_deref(),
+            asset.asset_issuer.as_deref(),
+        )
+    }
+}
+
+impl TryFrom<&AssetId> for crate::rpc::Asset {
+    type Error = AssetIdError;
+
+    fn try_from(asset: &AssetId) -> Result<Self, Self::Error> {
+        match asset {
+            AssetId::Native => Ok(Self {
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+            }),
+            AssetId::Credit { code, issuer } => Ok(Self {
+                asset_type: asset.asset_type().to_string(),
+                asset_code: Some(code.clone()),
+                asset_issuer: Some(issuer.clone()),
+            }),
+            AssetId::Contract(id) => Err(AssetIdError::NotClassic(id.clone())),
+        }
+    }
+}
+
+impl Serialize for AssetId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AssetId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Stored as its key form, so lumens bind as `XLM:native`
+impl Type<Sqlite> for AssetId {
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for AssetId {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        <String as Encode<Sqlite>>::encode(self.key(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for AssetId {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <&str as Decode<Sqlite>>::decode(value)?;
+        Ok(s.parse()?)
+    }
+}
+
+/// Directional corridor between two assets (`SOURCE->DESTINATION`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CorridorKey {
+    pub source: AssetId,
+    pub destination: AssetId,
+}
+
+impl CorridorKey {
+    pub fn new(source: AssetId, destination: AssetId) -> Self {
+        Self {
+            source,
+            destination,
+        }
+    }
+
+    /// The same pair ordered by key, matching undirected corridor keys
+    pub fn normalized(self) -> Self {
+        if self.source.key() > self.destination.key() {
+            Self::new(self.destination, self.source)
+        } else {
+            self
+        }
+    }
+}
+
+impl fmt::Display for CorridorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}->{}", self.source.key(), self.destination.key())
+    }
+}
+
+impl FromStr for CorridorKey {
+    type Err = AssetIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (source, destination) = s
+            .split_once("->")
+            .ok_or_else(|| AssetIdError::InvalidCorridorKey(s.to_string()))?;
+        Ok(Self::new(source.parse()?, destination.parse()?))
+    }
+}
+
+impl Serialize for CorridorKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CorridorKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Type<Sqlite> for CorridorKey {
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for CorridorKey {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        <String as Encode<Sqlite>>::encode(self.to_string(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for CorridorKey {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <&str as Decode<Sqlite>>::decode(value)?;
+        Ok(s.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const CONTRACT: &str = "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA";
+
+    #[test]
+    fn test_parse_sep11_forms() {
+        for native in ["native", "XLM", "xlm", "XLM:native"] {
+            assert_eq!(native.parse::<AssetId>().unwrap(), AssetId::Native);
+        }
+
+        let usdc: AssetId = format!("USDC:{}", USDC_ISSUER).parse().unwrap();
+        assert_eq!(usdc.asset_type(), "credit_alphanum4");
+        assert_eq!(usdc.to_string(), format!("USDC:{}", USDC_ISSUER));
+
+        let long: AssetId = format!("LONGASSET:{}", USDC_ISSUER).parse().unwrap();
+        assert_eq!(long.asset_type(), "credit_alphanum12");
+
+        let contract: AssetId = CONTRACT.parse().unwrap();
+        assert_eq!(contract, AssetId::Contract(CONTRACT.to_string()));
+        assert!(contract.classic_parts().is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<AssetId>(), Err(AssetIdError::Empty));
+        assert!(matches!(
+            "USDC".parse::<AssetId>(),
+            Err(AssetIdError::MissingIssuer(_))
+        ));
+        assert!(matches!(
+            "USDC:GA5Z".parse::<AssetId>(),
+            Err(AssetIdError::InvalidIssuer(_))
+        ));
+        assert!(matches!(
+            format!("TOOLONGASSETCODE:{}", USDC_ISSUER).parse::<AssetId>(),
+            Err(AssetIdError::InvalidCode(_))
+        ));
+        assert!(matches!(
+            format!("US-D:{}", USDC_ISSUER).parse::<AssetId>(),
+            Err(AssetIdError::InvalidCode(_))
+        ));
+        assert!(matches!(
+            "CABC".parse::<AssetId>(),
+            Err(AssetIdError::InvalidContract(_))
+        ));
+    }
+
+    #[test]
+    fn test_xdr_round_trip() {
+        for value in [
+            "native".to_string(),
+            format!("USDC:{}", USDC_ISSUER),
+            format!("LONGASSET:{}", USDC_ISSUER),
+        ] {
+            let asset: AssetId = value.parse().unwrap();
+            let encoded = asset.to_xdr_base64().unwrap();
+            assert_eq!(AssetId::from_xdr_base64(&encoded).unwrap(), asset);
+        }
+
+        let contract = AssetId::contract(CONTRACT).unwrap();
+        let address = contract.to_sc_address().unwrap();
+        assert_eq!(AssetId::try_from(&address).unwrap(), contract);
+    }
+
+    #[test]
+    fn test_corridor_key() {
+        let key: CorridorKey = format!("USDC:{}->native", USDC_ISSUER).parse().unwrap();
+        assert_eq!(key.destination, AssetId::Native);
+        assert_eq!(key.to_string(), format!("USDC:{}->XLM:native", USDC_ISSUER));
+        let reversed = CorridorKey::new(key.destination.clone(), key.source.clone());
+        assert_eq!(reversed.normalized(), key.clone().normalized());
+        assert_eq!(key.clone().normalized(), key);
+
+        assert!(matches!(
+            "USDC-XLM".parse::<CorridorKey>(),
+            Err(AssetIdError::InvalidCorridorKey(_))
+        ));
+    }
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::asset::{AssetId, AssetIdError, CorridorKey};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::FromRow)]
 pub struct Corridor {
     pub asset_a_code: String,
@@ -45,6 +47,23 @@ impl Corridor {
     }
 }
 
+impl TryFrom<&CorridorKey> for Corridor {
+    type Error = AssetIdError;
+
+    /// Corridor rows are stored as classic code/issuer columns, so contract
+    /// assets are rejected.
+    fn try_from(key: &CorridorKey) -> Result<Self, Self::Error> {
+        let (a_code, a_issuer) = key.source.classic_parts()?;
+        let (b_code, b_issuer) = key.destination.classic_parts()?;
+        Ok(Corridor::new(
+            a_code.to_string(),
+            a_issuer.to_string(),
+            b_code.to_string(),
+            b_issuer.to_string(),
+        ))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct CorridorMetrics {
     pub id: String,
@@ -130,6 +149,17 @@ impl PaymentRecord {
             self.destination_asset_issuer.clone(),
         )
     }
+
+    /// Validated source and destination assets of the payment.
+    pub fn corridor_key(&self) -> Result<CorridorKey, AssetIdError> {
+        Ok(CorridorKey::new(
+            AssetId::from_parts(&self.source_asset_code, Some(&self.source_asset_issuer))?,
+            AssetId::from_parts(
+                &self.destination_asset_code,
+                Some(&self.destination_asset_issuer),
+            )?,
+        ))
+    }
 }
 
 /// Computes the median value from a slice of i64 latency measurements.
@@ -23,10 +23,46 @@ use utoipa::OpenApi;
         crate::api::anchors_cached::get_anchors,
         crate::api::corridors_cached::list_corridors,
         crate::api::corridors_cached::get_corridor_detail,
+        crate::api::anomalies::get_corridor_anomalies,
+        crate::api::fx_spreads::get_corridor_fx_spread,
+        crate::api::fx_spreads::get_anchor_fx_spread,
         crate::api::price_feed::get_price,
         crate::api::price_feed::get_prices,
         crate::api::price_feed::convert_to_usd,
         crate::api::price_feed::get_cache_stats,
+        crate::api::quotes::simulate_quote,
+        crate::api::rollups::get_rollup_series,
+        crate::api::routes::find_routes,
+        crate::api::scoring::list_models,
+        crate::api::scoring::evaluate_score,
+        crate::api::stellar_toml::get_anchor_stellar_toml,
+        crate::api::stellar_toml::get_anchor_stellar_toml_history,
+        crate::api::endpoint_uptime::get_anchor_uptime,
+        crate::api::endpoint_uptime::get_anchor_probes,
+        crate::api::endpoint_uptime::list_sep_endpoints,
+        crate::api::endpoint_uptime::set_sep_endpoint,
+        crate::api::endpoint_uptime::remove_sep_endpoint,
+        crate::api::anchor_proxy::get_anchor_proxy,
+        crate::api::anchor_proxy::set_anchor_proxy,
+        crate::api::anchor_fees::compare_fees,
+        crate::api::anchor_fees::get_anchor_fees,
+        crate::api::anchor_fees::get_anchor_fee_history,
+        crate::api::asset_supply::get_asset_supply,
+        crate::api::asset_supply::get_daily_supply,
+        crate::api::asset_supply::get_supply_events,
+        crate::api::asset_supply::get_anchor_supply,
+        crate::api::asset_supply::add_distribution_account,
+        crate::api::asset_supply::remove_distribution_account,
+        crate::api::incidents::get_anchor_incidents,
+        crate::api::incidents::get_status_transitions,
+        crate::api::incidents::list_incidents,
+        crate::api::transfers::list_transfers,
+        crate::api::transfers::get_transfer,
+        crate::api::transfers::get_transfer_metrics,
+        crate::api::transfers::get_anchor_transfer_metrics,
+        crate::api::transfers::transfer_callback,
+        crate::api::sep10::post_token,
+        crate::api::quote_comparison::compare_quotes,
     ),
     components(
         schemas(
@@ -37,17 +73,57 @@ use utoipa::OpenApi;
             crate::api::corridors_cached::SuccessRateDataPoint,
             crate::api::corridors_cached::LatencyDataPoint,
             crate::api::corridors_cached::LiquidityDataPoint,
+            crate::api::anomalies::AnomaliesResponse,
+            crate::api::fx_spreads::FxSpreadResponse,
             crate::api::price_feed::PriceResponse,
             crate::api::price_feed::PricesResponse,
             crate::api::price_feed::ConvertResponse,
             crate::api::price_feed::CacheStatsResponse,
+            crate::api::quotes::QuoteResponse,
+            crate::api::rollups::RollupResponse,
+            crate::api::rollups::RollupPoint,
+            crate::api::routes::RoutesResponse,
+            crate::api::scoring::ScoringModelsResponse,
+            crate::api::scoring::EvaluateScoreRequest,
+            crate::api::stellar_toml::StellarTomlResponse,
+            crate::api::stellar_toml::StellarTomlHistoryResponse,
+            crate::api::endpoint_uptime::EndpointUptimeResponse,
+            crate::api::endpoint_uptime::EndpointProbesResponse,
+            crate::api::endpoint_uptime::SepEndpointResponse,
+            crate::api::endpoint_uptime::SetSepEndpointRequest,
+            crate::api::anchor_proxy::AnchorProxyResponse,
+            crate::api::anchor_proxy::SetAnchorProxyRequest,
+            crate::api::anchor_fees::FeeComparisonResponse,
+            crate::api::anchor_fees::AnchorFeesResponse,
+            crate::api::anchor_fees::FeeHistoryResponse,
+            crate::api::asset_supply::AssetSupplyResponse,
+            crate::api::asset_supply::DailySupplyResponse,
+            crate::api::asset_supply::SupplyEventsResponse,
+            crate::api::incidents::AnchorIncidentsResponse,
+            crate::api::incidents::StatusTransitionsResponse,
+            crate::api::incidents::IncidentFeedResponse,
+            crate::api::transfers::TransferListResponse,
+            crate::api::transfers::TransferDetailResponse,
+            crate::api::transfers::TransferMetricsResponse,
+            crate::api::sep10::Sep10TokenRequest,
+            crate::api::quote_comparison::QuoteComparisonResponse,
         )
     ),
     tags(
         (name = "Anchors", description = "Anchor management and metrics endpoints"),
         (name = "Corridors", description = "Payment corridor analytics endpoints"),
         (name = "Prices", description = "Real-time asset price feed endpoints"),
+        (name = "Quotes", description = "Corridor quote and slippage simulation"),
+        (name = "Rollups", description = "Multi-resolution metric time series"),
+        (name = "Routes", description = "Payment path finding and route ranking"),
+        (name = "Scoring", description = "Health and reliability scoring models"),
         (name = "RPC", description = "Stellar RPC integration endpoints"),
+        (name = "Fees", description = "Anchor deposit and withdrawal fee schedules"),
+        (name = "Assets", description = "Issued asset supply, mints and burns"),
+        (name = "Incidents", description = "Anchor status transitions and incidents"),
+        (name = "Transfers", description = "SEP-24 and SEP-31 transfers initiated through the proxies"),
+        (name = "SEP-10", description = "Web authentication with anchors"),
+        (name = "SEP-38", description = "Anchor price and quote comparison"),
         (name = "Fee Bumps", description = "Fee bump transaction tracking"),
         (name = "Cache", description = "Cache management and statistics"),
         (name = "Metrics", description = "System metrics and monitoring")
@@ -1,8 +1,8 @@
 pub mod stellar;
 
 pub use stellar::{
-    Asset, FeeBumpTransactionInfo, GetLedgersResult, HealthResponse, HorizonAsset, HorizonEffect,
-    HorizonLiquidityPool, HorizonOperation, HorizonPoolReserve, HorizonTransaction,
-    InnerTransaction, LedgerInfo, OrderBook, OrderBookEntry, Payment, Price, RpcLedger,
-    StellarRpcClient, Trade,
+    Asset, FeeBumpTransactionInfo, GetLedgersResult, HealthResponse, HorizonAccount, HorizonAsset,
+    HorizonBalance, HorizonEffect, HorizonLiquidityPool, HorizonOperation, HorizonPoolReserve,
+    HorizonSigner, HorizonThresholds, HorizonTransaction, InnerTransaction, LedgerInfo, OrderBook,
+    OrderBookEntry, Payment, PaymentPath, Price, RpcLedger, StellarRpcClient, Trade,
 };
@@ -111,9 +111,12 @@ pub struct Payment {
     pub source_account: String,
     #[serde(default)]
     pub destination: String,
+    // Missing on create_account records, which share the payments endpoints
+    #[serde(default)]
     pub asset_type: String,
     pub asset_code: Option<String>,
     pub asset_issuer: Option<String>,
+    #[serde(default)]
     pub amount: String,
     pub created_at: String,
     // Path payment fields
@@ -272,6 +275,62 @@ pub struct GetLedgersResult {
     pub cursor: Option<String>,
 }
 
+// ============================================================================
+// Account Models (Horizon API)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonAccount {
+    pub account_id: String,
+    pub sequence: String,
+    /// Domain hosting the account's stellar.toml (SEP-1)
+    #[serde(default)]
+    pub home_domain: Option<String>,
+    #[serde(default)]
+    pub balances: Vec<HorizonBalance>,
+    #[serde(default)]
+    pub signers: Vec<HorizonSigner>,
+    #[serde(default)]
+    pub thresholds: HorizonThresholds,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonBalance {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonSigner {
+    pub key: String,
+    pub weight: u8,
+    /// `ed25519_public_key`, `sha256_hash` or `preauth_tx`
+    #[serde(rename = "type")]
+    pub signer_type: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HorizonThresholds {
+    pub low_threshold: u8,
+    pub med_threshold: u8,
+    pub high_threshold: u8,
+}
+
+impl HorizonAccount {
+    /// Balance held of an issued asset, if the account trusts it
+    pub fn balance_of(&self, asset_code: &str, asset_issuer: &str) -> Option<f64> {
+        self.balances
+            .iter()
+            .find(|b| {
+                b.asset_code.as_deref() == Some(asset_code)
+                    && b.asset_issuer.as_deref() == Some(asset_issuer)
+            })
+            .and_then(|b| b.balance.parse().ok())
+    }
+}
+
 // ============================================================================
 // Liquidity Pool Models (Horizon API)
 // ============================================================================
@@ -297,6 +356,24 @@ pub struct HorizonLiquidityPool {
     pub paging_token: Option<String>,
 }
 
+// ============================================================================
+// Path Finding Models (Horizon API)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentPath {
+    pub source_asset_type: String,
+    pub source_asset_code: Option<String>,
+    pub source_asset_issuer: Option<String>,
+    pub source_amount: String,
+    pub destination_asset_type: String,
+    pub destination_asset_code: Option<String>,
+    pub destination_asset_issuer: Option<String>,
+    pub destination_amount: String,
+    /// Intermediate assets, excluding source and destination
+    pub path: Vec<Asset>,
+}
+
 // ============================================================================
 // Implementation
 // ============================================================================
@@ -701,6 +778,136 @@ impl StellarRpcClient {
         Ok(payments)
     }
 
+    /// Fetch an account's payments oldest first, starting after `cursor`
+    pub async fn fetch_account_payments_after(
+        &self,
+        account_id: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<Payment>> {
+        if self.mock_mode {
+            return Ok(Self::mock_payments(limit));
+        }
+
+        info!(
+            "Fetching {} payments for account {} after cursor {:?} from Horizon API",
+            limit, account_id, cursor
+        );
+
+        let mut url = format!(
+            "{}/accounts/{}/payments?order=asc&limit={}",
+            self.horizon_url, account_id, limit
+        );
+        if let Some(cursor) = cursor {
+            url.push_str(&format!("&cursor={}", cursor));
+        }
+
+        let response = self
+            .retry_request(|| async { self.client.get(&url).send().await })
+            .await
+            .context("Failed to fetch account payments")?;
+
+        let horizon_response: HorizonResponse<Payment> = response
+            .json()
+            .await
+            .context("Failed to parse payments response")?;
+
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    // ============================================================================
+    // Path Finding Methods
+    // ============================================================================
+
+    /// Find payment paths that deliver the most destination asset for a fixed
+    /// source amount (Horizon `/paths/strict-send`)
+    pub async fn fetch_strict_send_paths(
+        &self,
+        source_asset: &Asset,
+        source_amount: &str,
+        destination_asset: &Asset,
+    ) -> Result<Vec<PaymentPath>> {
+        if self.mock_mode {
+            return Ok(Self::mock_strict_send_paths(
+                source_asset,
+                source_amount,
+                destination_asset,
+            ));
+        }
+
+        info!("Fetching strict-send paths from Horizon API");
+
+        let source_params = Self::asset_to_query_params("source", source_asset);
+        let url = format!(
+            "{}/paths/strict-send?{}&source_amount={}&destination_assets={}",
+            self.horizon_url,
+            source_params,
+            source_amount,
+            Self::asset_to_canonical(destination_asset)
+        );
+
+        let response = self
+            .retry_request(|| async { self.client.get(&url).send().await })
+            .await
+            .context("Failed to fetch strict-send paths")?;
+
+        let horizon_response: HorizonResponse<PaymentPath> = response
+            .json()
+            .await
+            .context("Failed to parse strict-send paths response")?;
+
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    /// Find payment paths that need the least source asset to deliver a fixed
+    /// destination amount (Horizon `/paths/strict-receive`)
+    pub async fn fetch_strict_receive_paths(
+        &self,
+        source_asset: &Asset,
+        destination_asset: &Asset,
+        destination_amount: &str,
+    ) -> Result<Vec<PaymentPath>> {
+        if self.mock_mode {
+            return Ok(Self::mock_strict_receive_paths(
+                source_asset,
+                destination_asset,
+                destination_amount,
+            ));
+        }
+
+        info!("Fetching strict-receive paths from Horizon API");
+
+        let destination_params = Self::asset_to_query_params("destination", destination_asset);
+        let url = format!(
+            "{}/paths/strict-receive?source_assets={}&{}&destination_amount={}",
+            self.horizon_url,
+            Self::asset_to_canonical(source_asset),
+            destination_params,
+            destination_amount
+        );
+
+        let response = self
+            .retry_request(|| async { self.client.get(&url).send().await })
+            .await
+            .context("Failed to fetch strict-receive paths")?;
+
+        let horizon_response: HorizonResponse<PaymentPath> = response
+            .json()
+            .await
+            .context("Failed to parse strict-receive paths response")?;
+
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
     // ============================================================================
     // Helper Methods
     // ============================================================================
@@ -722,6 +929,19 @@ impl StellarRpcClient {
         }
     }
 
+    /// Convert asset to Horizon's canonical string form ("native" or "CODE:ISSUER")
+    fn asset_to_canonical(asset: &Asset) -> String {
+        if asset.asset_type == "native" {
+            "native".to_string()
+        } else {
+            format!(
+                "{}:{}",
+                asset.asset_code.as_deref().unwrap_or_default(),
+                asset.asset_issuer.as_deref().unwrap_or_default()
+            )
+        }
+    }
+
     /// Retry a request with exponential backoff
     async fn retry_request<F, Fut>(&self, request_fn: F) -> Result<reqwest::Response>
     where
@@ -1143,6 +1363,82 @@ impl StellarRpcClient {
         Vec::new()
     }
 
+    // ============================================================================
+    // Account Methods
+    // ============================================================================
+
+    /// Fetch an account from Horizon API
+    pub async fn fetch_account(&self, account_id: &str) -> Result<HorizonAccount> {
+        if self.mock_mode {
+            return Ok(Self::mock_account(account_id));
+        }
+
+        info!("Fetching account {} from Horizon API", account_id);
+
+        let url = format!("{}/accounts/{}", self.horizon_url, account_id);
+
+        let response = self
+            .retry_request(|| async { self.client.get(&url).send().await })
+            .await
+            .context("Failed to fetch account")?;
+
+        let account: HorizonAccount = response
+            .json()
+            .await
+            .context("Failed to parse account response")?;
+
+        Ok(account)
+    }
+
+    /// Fetch an account from Horizon API, or `None` if it does not exist.
+    ///
+    /// Unlike [`Self::fetch_account`] a missing account is not retried.
+    pub async fn fetch_account_if_exists(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<HorizonAccount>> {
+        if self.mock_mode {
+            return Ok(Some(Self::mock_account(account_id)));
+        }
+
+        let url = format!("{}/accounts/{}", self.horizon_url, account_id);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch account")?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = response
+            .error_for_status()
+            .context("Failed to fetch account")?;
+
+        let account: HorizonAccount = response
+            .json()
+            .await
+            .context("Failed to parse account response")?;
+
+        Ok(Some(account))
+    }
+
+    /// A new account controlled by its master key alone
+    fn mock_account(account_id: &str) -> HorizonAccount {
+        HorizonAccount {
+            account_id: account_id.to_string(),
+            sequence: "1".to_string(),
+            home_domain: None,
+            balances: Vec::new(),
+            signers: vec![HorizonSigner {
+                key: account_id.to_string(),
+                weight: 1,
+                signer_type: "ed25519_public_key".to_string(),
+            }],
+            thresholds: HorizonThresholds::default(),
+        }
+    }
+
     // ============================================================================
     // Liquidity Pool Methods
     // ============================================================================
@@ -1272,6 +1568,133 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    /// Fetch a single issued asset; `None` if Horizon does not know it
+    pub async fn fetch_asset(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<Option<HorizonAsset>> {
+        if self.mock_mode {
+            return Ok(Self::mock_assets(1).into_iter().next().map(|mut asset| {
+                asset.asset_code = asset_code.to_string();
+                asset.asset_issuer = asset_issuer.to_string();
+                asset
+            }));
+        }
+
+        info!(
+            "Fetching asset {}:{} from Horizon API",
+            asset_code, asset_issuer
+        );
+        let url = format!(
+            "{}/assets?asset_code={}&asset_issuer={}&limit=1",
+            self.horizon_url, asset_code, asset_issuer
+        );
+
+        let response = self
+            .retry_request(|| async { self.client.get(&url).send().await })
+            .await
+            .context("Failed to fetch asset")?;
+
+        let horizon_response: HorizonResponse<HorizonAsset> = response
+            .json()
+            .await
+            .context("Failed to parse assets response")?;
+
+        Ok(horizon_response
+            .embedded
+            .and_then(|e| e.records.into_iter().next()))
+    }
+
+    // ============================================================================
+    // Path Finding Mock Data
+    // ============================================================================
+
+    /// Mock intermediate hops: a direct route, a route via XLM and a route via USDC
+    fn mock_path_hops() -> Vec<(Vec<Asset>, f64)> {
+        vec![
+            (Vec::new(), 0.990),
+            (
+                vec![Asset {
+                    asset_type: "native".to_string(),
+                    asset_code: None,
+                    asset_issuer: None,
+                }],
+                0.995,
+            ),
+            (
+                vec![Asset {
+                    asset_type: "credit_alphanum4".to_string(),
+                    asset_code: Some("USDC".to_string()),
+                    asset_issuer: Some(
+                        "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string(),
+                    ),
+                }],
+                0.985,
+            ),
+        ]
+    }
+
+    fn mock_payment_path(
+        source_asset: &Asset,
+        source_amount: f64,
+        destination_asset: &Asset,
+        destination_amount: f64,
+        path: Vec<Asset>,
+    ) -> PaymentPath {
+        PaymentPath {
+            source_asset_type: source_asset.asset_type.clone(),
+            source_asset_code: source_asset.asset_code.clone(),
+            source_asset_issuer: source_asset.asset_issuer.clone(),
+            source_amount: format!("{:.7}", source_amount),
+            destination_asset_type: destination_asset.asset_type.clone(),
+            destination_asset_code: destination_asset.asset_code.clone(),
+            destination_asset_issuer: destination_asset.asset_issuer.clone(),
+            destination_amount: format!("{:.7}", destination_amount),
+            path,
+        }
+    }
+
+    fn mock_strict_send_paths(
+        source_asset: &Asset,
+        source_amount: &str,
+        destination_asset: &Asset,
+    ) -> Vec<PaymentPath> {
+        let amount: f64 = source_amount.parse().unwrap_or(0.0);
+        Self::mock_path_hops()
+            .into_iter()
+            .map(|(path, rate)| {
+                Self::mock_payment_path(
+                    source_asset,
+                    amount,
+                    destination_asset,
+                    amount * rate,
+                    path,
+                )
+            })
+            .collect()
+    }
+
+    fn mock_strict_receive_paths(
+        source_asset: &Asset,
+        destination_asset: &Asset,
+        destination_amount: &str,
+    ) -> Vec<PaymentPath> {
+        let amount: f64 = destination_amount.parse().unwrap_or(0.0);
+        Self::mock_path_hops()
+            .into_iter()
+            .map(|(path, rate)| {
+                Self::mock_payment_path(
+                    source_asset,
+                    amount / rate,
+                    destination_asset,
+                    amount,
+                    path,
+                )
+            })
+            .collect()
+    }
+
     // ============================================================================
     // Liquidity Pool Mock Data
     // ============================================================================
@@ -1291,7 +1714,7 @@ impl StellarRpcClient {
                 "USDC",
                 "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
                 "EURC",
-                "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y36DAVIZA67CE7BKBHP4V2OA",
+                "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2",
                 "320000.0",
                 "295000.0",
                 "610000.0",
@@ -1300,7 +1723,7 @@ impl StellarRpcClient {
                 "XLM",
                 "",
                 "BTC",
-                "GDPJALI4AZKUU2W426U5WKMAT6CN3AJRPIIRYR2YM54TL2GDEMNQERFT",
+                "GDPJALI4AZKUU2W426U5WKMAT6CN3AJRPIIRYR2YM54TL2GDEMNQFBDW",
                 "450000.0",
                 "12.5",
                 "750000.0",
@@ -1309,7 +1732,7 @@ impl StellarRpcClient {
                 "USDC",
                 "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
                 "yUSDC",
-                "GDGTVWSM4MGS2T7Z7GVZE5SAEVLSWM5SGY5Q2EMUQWRMEV2RNYY3YFG6",
+                "GDGTVWSM4MGS2T7Z7GVZE5SAEVLSWM5SGY5Q2EMUQWRMEV2RNYY3YRMT",
                 "180000.0",
                 "179500.0",
                 "360000.0",
@@ -1382,7 +1805,7 @@ impl StellarRpcClient {
             ),
             (
                 "BTC",
-                "GDPJALI4AZKUU2W426U5WKMAT6CN3AJRPIIRYR2YM54TL2GDEMNQERFT",
+                "GDPJALI4AZKUU2W426U5WKMAT6CN3AJRPIIRYR2YM54TL2GDEMNQFBDW",
             ),
         ];
 
@@ -1518,6 +1941,56 @@ mod tests {
         assert!(!trades[0].id.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_mock_fetch_strict_send_paths() {
+        let client = StellarRpcClient::new_with_defaults(true);
+
+        let source = Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let destination = Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("EURC".to_string()),
+            asset_issuer: Some("GBXXXXXXX".to_string()),
+        };
+
+        let paths = client
+            .fetch_strict_send_paths(&source, "100", &destination)
+            .await
+            .unwrap();
+
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0].source_amount, "100.0000000");
+        assert!(paths[0].path.is_empty());
+        assert_eq!(paths[1].path.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mock_fetch_strict_receive_paths() {
+        let client = StellarRpcClient::new_with_defaults(true);
+
+        let source = Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let destination = Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("EURC".to_string()),
+            asset_issuer: Some("GBXXXXXXX".to_string()),
+        };
+
+        let paths = client
+            .fetch_strict_receive_paths(&source, &destination, "100")
+            .await
+            .unwrap();
+
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().all(|p| p.destination_amount == "100.0000000"));
+    }
+
     #[tokio::test]
     async fn test_mock_fetch_operations_for_ledger() {
         let client = StellarRpcClient::new_with_defaults(true);
@@ -0,0 +1,507 @@
+//! Configurable scoring engine for corridor health and anchor reliability.
+//!
+//! Each score is produced by a versioned [`ScoringModel`]: a list of weighted
+//! components, each normalizing one named input onto a 0-100 scale, plus
+//! bands that label the final score. Scores come back as a
+//! [`ScoreBreakdown`] holding every input and per-component contribution so
+//! a score can be reproduced from the response alone.
+//!
+//! Built-in models match the formulas the API has always used. Additional
+//! models or new versions can be loaded from a JSON file named by
+//! `SCORING_MODELS_PATH`:
+//!
+//! ```json
+//! {
+//!   "active": { "corridor_health": 2 },
+//!   "models": [{
+//!     "id": "corridor_health",
+//!     "version": 2,
+//!     "components": [
+//!       { "input": "success_rate", "weight": 0.7, "normalization": { "type": "percent" } },
+//!       { "input": "volume_usd", "weight": 0.3, "normalization": { "type": "log", "divisor": 15.0 } }
+//!     ],
+//!     "bands": [{ "min_score": 85.0, "label": "healthy" }, { "min_score": 0.0, "label": "critical" }]
+//!   }]
+//! }
+//! ```
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const CORRIDOR_HEALTH_MODEL: &str = "corridor_health";
+pub const ANCHOR_RELIABILITY_MODEL: &str = "anchor_reliability";
+
+/// Tolerance when checking that component weights sum to one
+const WEIGHT_SUM_TOLERANCE: f64 = 1e-6;
+
+/// How a raw input is mapped onto a 0-100 component score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Normalization {
+    /// Input is already a percentage
+    Percent,
+    /// `ln(value) / divisor * 100`
+    Log { divisor: f64 },
+    /// `log10(value + 1) / log10(reference + 1) * 100`, where `reference`
+    /// names another input. Scores 50 when the reference is zero but the
+    /// value is not.
+    LogRatio { reference: String },
+    /// `value / cap * 100`
+    Ratio { cap: f64 },
+    /// Straight line from `worst` (0) to `best` (100); `best` may be below
+    /// `worst` for inputs where lower is better, such as latency
+    Linear { worst: f64, best: f64 },
+}
+
+impl Normalization {
+    fn apply(&self, value: f64, inputs: &BTreeMap<String, f64>) -> Result<f64> {
+        let score = match self {
+            Self::Percent => value,
+            Self::Log { divisor } => {
+                if value > 0.0 {
+                    value.ln() / divisor * 100.0
+                } else {
+                    0.0
+                }
+            }
+            Self::LogRatio { reference } => {
+                let reference_value = *inputs
+                    .get(reference)
+                    .ok_or_else(|| anyhow!("Missing scoring input '{}'", reference))?;
+                if reference_value > 0.0 {
+                    (value + 1.0).log10() / (reference_value + 1.0).log10() * 100.0
+                } else if value > 0.0 {
+                    50.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Ratio { cap } => value / cap * 100.0,
+            Self::Linear { worst, best } => (value - worst) / (best - worst) * 100.0,
+        };
+
+        Ok(score.clamp(0.0, 100.0))
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Log { divisor } if *divisor <= 0.0 => bail!("log divisor must be positive"),
+            Self::Ratio { cap } if *cap <= 0.0 => bail!("ratio cap must be positive"),
+            Self::Linear { worst, best } if worst == best => {
+                bail!("linear worst and best must differ")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreComponent {
+    /// Name of the input this component scores
+    pub input: String,
+    pub weight: f64,
+    pub normalization: Normalization,
+}
+
+/// Label applied to scores at or above `min_score`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBand {
+    pub min_score: f64,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringModel {
+    pub id: String,
+    pub version: u32,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub components: Vec<ScoreComponent>,
+    #[serde(default)]
+    pub bands: Vec<ScoreBand>,
+}
+
+impl ScoringModel {
+    /// Check weights are non-negative and sum to one
+    pub fn validate(&self) -> Result<()> {
+        if self.components.is_empty() {
+            bail!("model {} v{} has no components", self.id, self.version);
+        }
+
+        let mut total = 0.0;
+        for component in &self.components {
+            if component.weight < 0.0 {
+                bail!(
+                    "model {} v{}: weight for '{}' is negative",
+                    self.id,
+                    self.version,
+                    component.input
+                );
+            }
+            component
+                .normalization
+                .validate()
+                .with_context(|| format!("model {} v{}", self.id, self.version))?;
+            total += component.weight;
+        }
+
+        if (total - 1.0).abs() > WEIGHT_SUM_TOLERANCE {
+            bail!(
+                "model {} v{}: weights sum to {} instead of 1",
+                self.id,
+                self.version,
+                total
+            );
+        }
+        Ok(())
+    }
+
+    /// Score `inputs`, returning the full breakdown
+    pub fn score(&self, inputs: &BTreeMap<String, f64>) -> Result<ScoreBreakdown> {
+        let mut components = Vec::with_capacity(self.components.len());
+        let mut score = 0.0;
+
+        for component in &self.components {
+            let input_value = *inputs
+                .get(&component.input)
+                .ok_or_else(|| anyhow!("Missing scoring input '{}'", component.input))?;
+            let normalized = component.normalization.apply(input_value, inputs)?;
+            let contribution = normalized * component.weight;
+            score += contribution;
+
+            components.push(ComponentContribution {
+                input: component.input.clone(),
+                input_value,
+                normalized_score: normalized,
+                weight: component.weight,
+                contribution,
+            });
+        }
+
+        let band = self
+            .bands
+            .iter()
+            .filter(|band| score >= band.min_score)
+            .max_by(|a, b| a.min_score.total_cmp(&b.min_score))
+            .map(|band| band.label.clone());
+
+        Ok(ScoreBreakdown {
+            model_id: self.id.clone(),
+            model_version: self.version,
+            score,
+            band,
+            components,
+            inputs: inputs.clone(),
+        })
+    }
+}
+
+/// How much one component added to a score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentContribution {
+    pub input: String,
+    pub input_value: f64,
+    /// Input mapped onto 0-100
+    pub normalized_score: f64,
+    pub weight: f64,
+    /// `normalized_score * weight`; contributions sum to the score
+    pub contribution: f64,
+}
+
+/// A score together with the model and inputs that produced it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub model_id: String,
+    pub model_version: u32,
+    pub score: f64,
+    pub band: Option<String>,
+    pub components: Vec<ComponentContribution>,
+    pub inputs: BTreeMap<String, f64>,
+}
+
+/// Contents of a `SCORING_MODELS_PATH` file
+#[derive(Debug, Default, Deserialize)]
+struct ScoringConfigFile {
+    #[serde(default)]
+    active: HashMap<String, u32>,
+    #[serde(default)]
+    models: Vec<ScoringModel>,
+}
+
+/// Registry of scoring models keyed by id and version
+#[derive(Debug, Clone)]
+pub struct ScoringEngine {
+    models: HashMap<String, BTreeMap<u32, ScoringModel>>,
+    /// Version used when a caller does not ask for one; latest otherwise
+    active: HashMap<String, u32>,
+}
+
+impl Default for ScoringEngine {
+    fn default() -> Self {
+        let mut engine = Self {
+            models: HashMap::new(),
+            active: HashMap::new(),
+        };
+        for model in default_models() {
+            engine.insert(model);
+        }
+        engine
+    }
+}
+
+impl ScoringEngine {
+    /// Built-in models, extended by `SCORING_MODELS_PATH` when set
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("SCORING_MODELS_PATH") {
+            Ok(path) => {
+                let contents = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read scoring models from {}", path))?;
+                Self::from_json(&contents)
+                    .with_context(|| format!("Invalid scoring models in {}", path))
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Built-in models extended (or overridden per id and version) by a JSON config
+    pub fn from_json(contents: &str) -> Result<Self> {
+        let config: ScoringConfigFile =
+            serde_json::from_str(contents).context("Failed to parse scoring config")?;
+
+        let mut engine = Self::default();
+        for model in config.models {
+            model.validate()?;
+            engine.insert(model);
+        }
+        for (id, version) in config.active {
+            if engine.model(&id, Some(version)).is_none() {
+                bail!("active version {} of model {} is not defined", version, id);
+            }
+            engine.active.insert(id, version);
+        }
+        Ok(engine)
+    }
+
+    fn insert(&mut self, model: ScoringModel) {
+        self.models
+            .entry(model.id.clone())
+            .or_default()
+            .insert(model.version, model);
+    }
+
+    /// A model by id; `None` for `version` selects the active version
+    pub fn model(&self, id: &str, version: Option<u32>) -> Option<&ScoringModel> {
+        let versions = self.models.get(id)?;
+        match version.or_else(|| self.active.get(id).copied()) {
+            Some(version) => versions.get(&version),
+            None => versions.values().next_back(),
+        }
+    }
+
+    /// Every registered model, ordered by id then version
+    pub fn models(&self) -> Vec<&ScoringModel> {
+        let mut models: Vec<&ScoringModel> =
+            self.models.values().flat_map(|v| v.values()).collect();
+        models.sort_by(|a, b| a.id.cmp(&b.id).then(a.version.cmp(&b.version)));
+        models
+    }
+
+    pub fn score(
+        &self,
+        id: &str,
+        version: Option<u32>,
+        inputs: &BTreeMap<String, f64>,
+    ) -> Result<ScoreBreakdown> {
+        let model = self.model(id, version).ok_or_else(|| match version {
+            Some(v) => anyhow!("Unknown scoring model {} v{}", id, v),
+            None => anyhow!("Unknown scoring model {}", id),
+        })?;
+        model.score(inputs)
+    }
+
+    /// Corridor health from success rate, transaction count and USD volume
+    pub fn corridor_health(
+        &self,
+        success_rate: f64,
+        total_transactions: i64,
+        volume_usd: f64,
+    ) -> Result<ScoreBreakdown> {
+        let inputs = BTreeMap::from([
+            ("success_rate".to_string(), success_rate),
+            ("total_transactions".to_string(), total_transactions as f64),
+            ("volume_usd".to_string(), volume_usd),
+        ]);
+        self.score(CORRIDOR_HEALTH_MODEL, None, &inputs)
+    }
+}
+
+fn default_bands() -> Vec<ScoreBand> {
+    vec![
+        ScoreBand {
+            min_score: 80.0,
+            label: "healthy".to_string(),
+        },
+        ScoreBand {
+            min_score: 50.0,
+            label: "degraded".to_string(),
+        },
+        ScoreBand {
+            min_score: 0.0,
+            label: "critical".to_string(),
+        },
+    ]
+}
+
+/// Models matching the formulas used before scoring was configurable
+pub fn default_models() -> Vec<ScoringModel> {
+    vec![
+        ScoringModel {
+            id: CORRIDOR_HEALTH_MODEL.to_string(),
+            version: 1,
+            description: Some(
+                "Success rate with log-scaled volume and transaction count".to_string(),
+            ),
+            components: vec![
+                ScoreComponent {
+                    input: "success_rate".to_string(),
+                    weight: 0.6,
+                    normalization: Normalization::Percent,
+                },
+                ScoreComponent {
+                    input: "volume_usd".to_string(),
+                    weight: 0.2,
+                    normalization: Normalization::Log { divisor: 15.0 },
+                },
+                ScoreComponent {
+                    input: "total_transactions".to_string(),
+                    weight: 0.2,
+                    normalization: Normalization::Log { divisor: 10.0 },
+                },
+            ],
+            bands: default_bands(),
+        },
+        ScoringModel {
+            id: ANCHOR_RELIABILITY_MODEL.to_string(),
+            version: 1,
+            description: Some(
+                "Volume-weighted asset success rate, relative volume and asset diversity"
+                    .to_string(),
+            ),
+            components: vec![
+                ScoreComponent {
+                    input: "weighted_success_rate".to_string(),
+                    weight: 0.6,
+                    normalization: Normalization::Percent,
+                },
+                ScoreComponent {
+                    input: "total_volume_usd".to_string(),
+                    weight: 0.3,
+                    normalization: Normalization::LogRatio {
+                        reference: "network_max_volume_usd".to_string(),
+                    },
+                },
+                ScoreComponent {
+                    input: "total_assets".to_string(),
+                    weight: 0.1,
+                    normalization: Normalization::Ratio { cap: 10.0 },
+                },
+            ],
+            bands: default_bands(),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_models_are_valid() {
+        for model in default_models() {
+            model.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_corridor_health_breakdown_sums_to_score() {
+        let engine = ScoringEngine::default();
+        let breakdown = engine.corridor_health(95.0, 1000, 1_000_000.0).unwrap();
+
+        assert_eq!(breakdown.model_id, CORRIDOR_HEALTH_MODEL);
+        assert_eq!(breakdown.model_version, 1);
+        assert_eq!(breakdown.components.len(), 3);
+        let total: f64 = breakdown.components.iter().map(|c| c.contribution).sum();
+        assert!((total - breakdown.score).abs() < 1e-9);
+        assert_eq!(breakdown.components[0].contribution, 95.0 * 0.6);
+        assert_eq!(breakdown.inputs["total_transactions"], 1000.0);
+        assert_eq!(breakdown.band.as_deref(), Some("healthy"));
+    }
+
+    #[test]
+    fn test_config_adds_active_version() {
+        let engine = ScoringEngine::from_json(
+            r#"{
+                "active": { "corridor_health": 2 },
+                "models": [{
+                    "id": "corridor_health",
+                    "version": 2,
+                    "components": [
+                        { "input": "success_rate", "weight": 1.0, "normalization": { "type": "percent" } }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let breakdown = engine.corridor_health(90.0, 10, 10.0).unwrap();
+        assert_eq!(breakdown.model_version, 2);
+        assert_eq!(breakdown.score, 90.0);
+        assert!(breakdown.band.is_none());
+
+        // Older versions stay available for reproducing past scores
+        let inputs = breakdown.inputs.clone();
+        let v1 = engine
+            .score(CORRIDOR_HEALTH_MODEL, Some(1), &inputs)
+            .unwrap();
+        assert_eq!(v1.model_version, 1);
+    }
+
+    #[test]
+    fn test_config_rejects_bad_weights() {
+        let result = ScoringEngine::from_json(
+            r#"{ "models": [{
+                "id": "corridor_health",
+                "version": 3,
+                "components": [
+                    { "input": "success_rate", "weight": 0.5, "normalization": { "type": "percent" } }
+                ]
+            }] }"#,
+        );
+        assert!(result.is_err());
+        assert!(ScoringEngine::from_json(r#"{ "active": { "corridor_health": 9 } }"#).is_err());
+    }
+
+    #[test]
+    fn test_normalizations() {
+        let inputs = BTreeMap::from([("max".to_string(), 0.0)]);
+        let linear = Normalization::Linear {
+            worst: 10_000.0,
+            best: 1_000.0,
+        };
+        assert_eq!(linear.apply(500.0, &inputs).unwrap(), 100.0);
+        assert_eq!(linear.apply(5_500.0, &inputs).unwrap(), 50.0);
+
+        let ratio = Normalization::LogRatio {
+            reference: "max".to_string(),
+        };
+        assert_eq!(ratio.apply(10.0, &inputs).unwrap(), 50.0);
+        assert_eq!(ratio.apply(0.0, &inputs).unwrap(), 0.0);
+        assert_eq!(
+            Normalization::Ratio { cap: 10.0 }
+                .apply(15.0, &inputs)
+                .unwrap(),
+            100.0
+        );
+    }
+}
@@ -6,8 +6,9 @@ use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::database::Database;
-use crate::models::corridor::CorridorMetrics;
-use crate::services::analytics::compute_metrics_from_payments;
+use crate::models::asset::CorridorKey;
+use crate::models::corridor::{Corridor, CorridorAnalytics};
+use crate::services::rollup::{Resolution, RollupBucket, RollupEngine, RollupEntity};
 
 const MAX_RETRIES: i32 = 3;
 const RETRY_DELAY_SECS: u64 = 60;
@@ -16,7 +17,6 @@ const RETRY_DELAY_SECS: u64 = 60;
 pub struct AggregationConfig {
     pub interval_hours: u64,
     pub lookback_hours: i64,
-    pub batch_size: i64,
 }
 
 impl Default for AggregationConfig {
@@ -24,19 +24,29 @@ impl Default for AggregationConfig {
         Self {
             interval_hours: 1, // Run every hour
             lookback_hours: 2, // Process last 2 hours of data
-            batch_size: 10000, // Process 10k payments at a time
         }
     }
 }
 
+/// Keeps `corridor_metrics_hourly` and the daily `corridor_metrics` table
+/// in step with the rollup engine.
+///
+/// Corridor buckets are aggregated once, by `RollupEngine`; this job only
+/// copies its hourly and daily corridor buckets into the tables read by the
+/// anomaly detector and the corridor endpoints.
 pub struct AggregationService {
     db: Arc<Database>,
+    rollups: Arc<RollupEngine>,
     config: AggregationConfig,
 }
 
 impl AggregationService {
-    pub fn new(db: Arc<Database>, config: AggregationConfig) -> Self {
-        Self { db, config }
+    pub fn new(db: Arc<Database>, rollups: Arc<RollupEngine>, config: AggregationConfig) -> Self {
+        Self {
+            db,
+            rollups,
+            config,
+        }
     }
 
     /// Start the hourly aggregation job scheduler
@@ -102,9 +112,8 @@ impl AggregationService {
         }
     }
 
-    /// Execute the actual aggregation logic
+    /// Copy corridor rollups touched by the lookback window
     async fn execute_aggregation(&self, job_id: &str, now: DateTime<Utc>) -> Result<usize> {
-        // Calculate time window for aggregation
         let end_time = now;
         let start_time = end_time - Duration::hours(self.config.lookback_hours);
 
@@ -114,33 +123,33 @@ impl AggregationService {
             end_time.to_rfc3339()
         );
 
-        // Fetch payments from the time window
-        let payments = self
-            .db
-            .fetch_payments_by_timerange(start_time, end_time, self.config.batch_size)
+        let hourly = self
+            .rollups
+            .buckets(
+                RollupEntity::Corridor,
+                Resolution::Hour,
+                Resolution::Hour.bucket_start(start_time),
+                end_time,
+            )
             .await
-            .context("Failed to fetch payments for aggregation")?;
-
-        if payments.is_empty() {
-            info!("No payments found in time window");
-            return Ok(0);
-        }
-
-        info!("Processing {} payments", payments.len());
-
-        // Compute metrics for each corridor
-        let corridor_metrics = compute_metrics_from_payments(&payments);
-
-        if corridor_metrics.is_empty() {
-            info!("No corridor metrics computed");
-            return Ok(0);
-        }
-
-        // Group metrics by hour bucket
-        let hourly_metrics = self.group_by_hour_bucket(corridor_metrics, start_time);
+            .context("Failed to fetch hourly corridor rollups")?;
+        let daily = self
+            .rollups
+            .buckets(
+                RollupEntity::Corridor,
+                Resolution::Day,
+                Resolution::Day.bucket_start(start_time),
+                end_time,
+            )
+            .await
+            .context("Failed to fetch daily corridor rollups")?;
 
-        // Store aggregated metrics
-        let stored_count = self.store_hourly_metrics(hourly_metrics).await?;
+        let hourly_metrics: Vec<HourlyCorridorMetrics> = hourly
+            .iter()
+            .filter_map(hourly_metrics_from_rollup)
+            .collect();
+        let mut stored_count = self.store_hourly_metrics(hourly_metrics).await?;
+        stored_count += self.store_daily_metrics(&daily).await?;
 
         // Update last processed hour
         let last_hour = self.truncate_to_hour(end_time);
@@ -149,75 +158,6 @@ impl AggregationService {
         Ok(stored_count)
     }
 
-    /// Group metrics by hour bucket
-    fn group_by_hour_bucket(
-        &self,
-        metrics: Vec<CorridorMetrics>,
-        _start_time: DateTime<Utc>,
-    ) -> Vec<HourlyCorridorMetrics> {
-        use std::collections::HashMap;
-
-        let mut hourly_map: HashMap<(String, String), HourlyCorridorMetrics> = HashMap::new();
-
-        for metric in metrics {
-            let hour_bucket = self.truncate_to_hour(metric.date);
-            let key = (metric.corridor_key.clone(), hour_bucket.to_rfc3339());
-
-            hourly_map
-                .entry(key)
-                .and_modify(|existing| {
-                    existing.total_transactions += metric.total_transactions;
-                    existing.successful_transactions += metric.successful_transactions;
-                    existing.failed_transactions += metric.failed_transactions;
-                    existing.volume_usd += metric.volume_usd;
-
-                    // Update averages (weighted by transaction count)
-                    if let Some(latency) = metric.avg_settlement_latency_ms {
-                        let total_latency = existing.avg_settlement_latency_ms.unwrap_or(0) as i64
-                            * existing.total_transactions
-                            + latency as i64 * metric.total_transactions;
-                        existing.avg_settlement_latency_ms = Some(
-                            (total_latency
-                                / (existing.total_transactions + metric.total_transactions))
-                                as i32,
-                        );
-                    }
-
-                    existing.liquidity_depth_usd =
-                        (existing.liquidity_depth_usd + metric.liquidity_depth_usd) / 2.0;
-                })
-                .or_insert_with(|| HourlyCorridorMetrics {
-                    id: Uuid::new_v4().to_string(),
-                    corridor_key: metric.corridor_key.clone(),
-                    asset_a_code: metric.asset_a_code.clone(),
-                    asset_a_issuer: metric.asset_a_issuer.clone(),
-                    asset_b_code: metric.asset_b_code.clone(),
-                    asset_b_issuer: metric.asset_b_issuer.clone(),
-                    hour_bucket,
-                    total_transactions: metric.total_transactions,
-                    successful_transactions: metric.successful_transactions,
-                    failed_transactions: metric.failed_transactions,
-                    success_rate: metric.success_rate,
-                    volume_usd: metric.volume_usd,
-                    avg_slippage_bps: 0.0, // TODO: Calculate from order book data
-                    avg_settlement_latency_ms: metric.avg_settlement_latency_ms,
-                    liquidity_depth_usd: metric.liquidity_depth_usd,
-                });
-        }
-
-        // Recalculate success rates
-        hourly_map
-            .into_values()
-            .map(|mut m| {
-                if m.total_transactions > 0 {
-                    m.success_rate =
-                        (m.successful_transactions as f64 / m.total_transactions as f64) * 100.0;
-                }
-                m
-            })
-            .collect()
-    }
-
     /// Store hourly metrics in the database
     async fn store_hourly_metrics(&self, metrics: Vec<HourlyCorridorMetrics>) -> Result<usize> {
         let count = metrics.len();
@@ -233,6 +173,34 @@ impl AggregationService {
         Ok(count)
     }
 
+    /// Store daily corridor rollups in `corridor_metrics`
+    async fn store_daily_metrics(&self, buckets: &[RollupBucket]) -> Result<usize> {
+        let aggregates = self.db.corridor_aggregates();
+        let mut count = 0;
+
+        for bucket in buckets {
+            let Some(corridor) = rollup_corridor(bucket) else {
+                continue;
+            };
+            let analytics = CorridorAnalytics {
+                corridor,
+                success_rate: bucket.success_rate().unwrap_or(0.0),
+                total_transactions: bucket.total_transactions,
+                successful_transactions: bucket.successful_transactions,
+                failed_transactions: bucket.total_transactions - bucket.successful_transactions,
+                volume_usd: bucket.volume_usd,
+            };
+            aggregates
+                .store_daily_corridor_metrics(&analytics, bucket.bucket_start.date_naive())
+                .await
+                .context("Failed to store daily corridor metric")?;
+            count += 1;
+        }
+
+        info!("Stored {} daily corridor metrics", count);
+        Ok(count)
+    }
+
     /// Truncate datetime to hour boundary
     fn truncate_to_hour(&self, dt: DateTime<Utc>) -> DateTime<Utc> {
         dt.with_minute(0)
@@ -390,11 +358,45 @@ impl Clone for AggregationService {
     fn clone(&self) -> Self {
         Self {
             db: Arc::clone(&self.db),
+            rollups: Arc::clone(&self.rollups),
             config: self.config.clone(),
         }
     }
 }
 
+/// Corridor of a corridor rollup bucket, `None` if it can't be stored in
+/// the classic code/issuer columns
+fn rollup_corridor(bucket: &RollupBucket) -> Option<Corridor> {
+    bucket
+        .entity_id
+        .parse::<CorridorKey>()
+        .and_then(|key| Corridor::try_from(&key))
+        .map_err(|e| warn!("Skipping corridor rollup {}: {}", bucket.entity_id, e))
+        .ok()
+}
+
+fn hourly_metrics_from_rollup(bucket: &RollupBucket) -> Option<HourlyCorridorMetrics> {
+    let corridor = rollup_corridor(bucket)?;
+
+    Some(HourlyCorridorMetrics {
+        id: Uuid::new_v4().to_string(),
+        corridor_key: bucket.entity_id.clone(),
+        asset_a_code: corridor.asset_a_code,
+        asset_a_issuer: corridor.asset_a_issuer,
+        asset_b_code: corridor.asset_b_code,
+        asset_b_issuer: corridor.asset_b_issuer,
+        hour_bucket: bucket.bucket_start,
+        total_transactions: bucket.total_transactions,
+        successful_transactions: bucket.successful_transactions,
+        failed_transactions: bucket.total_transactions - bucket.successful_transactions,
+        success_rate: bucket.success_rate().unwrap_or(0.0),
+        volume_usd: bucket.volume_usd,
+        avg_slippage_bps: 0.0, // TODO: Calculate from order book data
+        avg_settlement_latency_ms: bucket.avg_latency_ms().map(|ms| ms as i32),
+        liquidity_depth_usd: 0.0,
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct HourlyCorridorMetrics {
     pub id: String,
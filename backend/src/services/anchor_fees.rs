@@ -0,0 +1,657 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::database::Database;
+use crate::models::Anchor;
+use crate::services::sep_endpoint_prober::{resolve_endpoints, SepEndpoint};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeOperation {
+    Deposit,
+    Withdraw,
+}
+
+impl FeeOperation {
+    pub const ALL: [FeeOperation; 2] = [FeeOperation::Deposit, FeeOperation::Withdraw];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeeOperation::Deposit => "deposit",
+            FeeOperation::Withdraw => "withdraw",
+        }
+    }
+}
+
+impl fmt::Display for FeeOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FeeOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "deposit" => Ok(FeeOperation::Deposit),
+            "withdraw" => Ok(FeeOperation::Withdraw),
+            other => Err(format!(
+                "unknown operation: {} (expected deposit or withdraw)",
+                other
+            )),
+        }
+    }
+}
+
+/// Fee terms an anchor publishes for one asset and operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeeTerms {
+    pub enabled: bool,
+    pub fee_fixed: Option<f64>,
+    /// Percentage of the amount, e.g. `0.5` for 0.5%
+    pub fee_percent: Option<f64>,
+    pub fee_minimum: Option<f64>,
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+    /// No terms are published; fees are quoted by the anchor's `/fee` endpoint
+    pub fee_endpoint: bool,
+}
+
+impl FeeTerms {
+    /// Fee charged on `amount`, if the published terms state one
+    pub fn fee_for(&self, amount: f64) -> Option<f64> {
+        if self.fee_fixed.is_none() && self.fee_percent.is_none() {
+            return None;
+        }
+        let percent_fee = amount * self.fee_percent.unwrap_or(0.0) / 100.0;
+        let fee = self.fee_fixed.unwrap_or(0.0) + percent_fee;
+        Some(fee.max(self.fee_minimum.unwrap_or(0.0)))
+    }
+
+    pub fn within_limits(&self, amount: f64) -> bool {
+        self.min_amount.is_none_or(|min| amount >= min)
+            && self.max_amount.is_none_or(|max| amount <= max)
+    }
+}
+
+/// Fee terms read from an `/info` response
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedFee {
+    pub operation: FeeOperation,
+    pub asset_code: String,
+    pub terms: FeeTerms,
+}
+
+/// Extract per-asset fee terms from a SEP-6 or SEP-24 `/info` response
+pub fn parse_info_fees(body: &Value) -> Vec<ParsedFee> {
+    let fee_endpoint_enabled = body
+        .pointer("/fee/enabled")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut fees = Vec::new();
+    for operation in FeeOperation::ALL {
+        let Some(Value::Object(assets)) = body.get(operation.as_str()) else {
+            continue;
+        };
+        for (asset_code, entry) in assets {
+            let fee_fixed = number(entry, "fee_fixed");
+            let fee_percent = number(entry, "fee_percent");
+            fees.push(ParsedFee {
+                operation,
+                asset_code: asset_code.clone(),
+                terms: FeeTerms {
+                    enabled: entry
+                        .get("enabled")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                    fee_fixed,
+                    fee_percent,
+                    fee_minimum: number(entry, "fee_minimum"),
+                    min_amount: number(entry, "min_amount"),
+                    max_amount: number(entry, "max_amount"),
+                    fee_endpoint: fee_endpoint_enabled
+                        && fee_fixed.is_none()
+                        && fee_percent.is_none(),
+                },
+            });
+        }
+    }
+
+    fees
+}
+
+/// Anchors publish amounts as JSON numbers or numeric strings
+fn number(entry: &Value, field: &str) -> Option<f64> {
+    match entry.get(field)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|n: &f64| n.is_finite())
+}
+
+#[derive(Debug, Clone)]
+pub struct AnchorFeeCollectorConfig {
+    /// How often every anchor's fee schedule is collected
+    pub interval_secs: u64,
+    pub timeout_secs: u64,
+}
+
+impl Default for AnchorFeeCollectorConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 6 * 3600,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// Current fee terms of an anchor for one asset and operation
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnchorFeeSchedule {
+    pub anchor_id: String,
+    pub sep: String,
+    pub operation: String,
+    pub asset_code: String,
+    pub transfer_server: String,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub terms: FeeTerms,
+    pub collected_at: DateTime<Utc>,
+}
+
+/// Fee terms as first observed or after a change
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FeeScheduleChange {
+    pub id: i64,
+    pub anchor_id: String,
+    pub sep: String,
+    pub operation: String,
+    pub asset_code: String,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub terms: FeeTerms,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// Cost of moving an amount through one anchor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeQuote {
+    pub anchor_id: String,
+    pub anchor_name: String,
+    pub sep: String,
+    pub transfer_server: String,
+    pub fee_fixed: Option<f64>,
+    pub fee_percent: Option<f64>,
+    pub fee_minimum: Option<f64>,
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+    /// `schedule` when computed from published terms, `fee_endpoint` when
+    /// quoted by the anchor
+    pub fee_source: Option<String>,
+    pub fee: Option<f64>,
+    /// Amount left after the fee
+    pub amount_out: Option<f64>,
+    pub within_limits: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeCollectionSummary {
+    pub anchors: usize,
+    pub changes: usize,
+    pub failed: usize,
+}
+
+#[derive(sqlx::FromRow)]
+struct FeeScheduleRow {
+    anchor_name: String,
+    #[sqlx(flatten)]
+    schedule: AnchorFeeSchedule,
+}
+
+/// Collects deposit and withdrawal fees from anchors' SEP-6 and SEP-24
+/// `/info` endpoints.
+///
+/// Current terms are kept per anchor, asset and operation, and every change
+/// is appended to a history so fee movements can be charted. Anchors are
+/// compared on the cost of moving a given amount, asking SEP-6/24 `/fee`
+/// endpoints when no terms are published.
+pub struct AnchorFeeCollector {
+    db: Arc<Database>,
+    client: Client,
+    config: AnchorFeeCollectorConfig,
+}
+
+impl AnchorFeeCollector {
+    pub fn new(db: Arc<Database>, config: AnchorFeeCollectorConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self { db, client, config }
+    }
+
+    /// Start the collection scheduler
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting anchor fee collector (interval: {} seconds)",
+            self.config.interval_secs
+        );
+
+        let mut ticker = interval(Duration::from_secs(self.config.interval_secs));
+
+        loop {
+            ticker.tick().await;
+            match self.run_collection().await {
+                Ok(summary) => info!(
+                    "Fee collection finished: {} anchors, {} changes, {} failed",
+                    summary.anchors, summary.changes, summary.failed
+                ),
+                Err(e) => error!("Fee collection failed: {}", e),
+            }
+        }
+    }
+
+    /// Collect the fee schedule of every known anchor
+    pub async fn run_collection(&self) -> Result<FeeCollectionSummary> {
+        let mut summary = FeeCollectionSummary::default();
+
+        let anchors = self.db.list_anchors(i64::MAX, 0).await?;
+        for anchor in &anchors {
+            match self.collect_anchor(anchor).await {
+                Ok(None) => {}
+                Ok(Some(changes)) => {
+                    summary.anchors += 1;
+                    summary.changes += changes;
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    warn!("Failed to collect fees of anchor {}: {}", anchor.id, e);
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Collect an anchor's fee schedule from its SEP-6 and SEP-24 servers.
+    ///
+    /// Returns the number of recorded changes, or `None` if the anchor has
+    /// no transfer server.
+    pub async fn collect_anchor(&self, anchor: &Anchor) -> Result<Option<usize>> {
+        let servers: Vec<(SepEndpoint, String)> = resolve_endpoints(&self.db, &anchor.id)
+            .await?
+            .into_iter()
+            .filter(|(sep, _)| matches!(sep, SepEndpoint::Sep6 | SepEndpoint::Sep24))
+            .collect();
+        if servers.is_empty() {
+            return Ok(None);
+        }
+
+        let mut changes = 0;
+        for (sep, transfer_server) in servers {
+            let url = format!("{}/info", transfer_server.trim_end_matches('/'));
+            let body = self
+                .get_json(&url)
+                .await
+                .with_context(|| format!("{} /info", sep))?;
+            let fees = parse_info_fees(&body);
+            changes += self.store(&anchor.id, sep, &transfer_server, &fees).await?;
+        }
+
+        Ok(Some(changes))
+    }
+
+    /// Current fee schedules of an anchor
+    pub async fn get_schedules(&self, anchor_id: &str) -> Result<Vec<AnchorFeeSchedule>> {
+        let schedules = sqlx::query_as::<_, AnchorFeeSchedule>(
+            r#"
+            SELECT * FROM anchor_fee_schedules
+            WHERE anchor_id = $1
+            ORDER BY asset_code, operation, sep
+            "#,
+        )
+        .bind(anchor_id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(schedules)
+    }
+
+    /// Fee changes of an anchor, newest first
+    pub async fn get_history(
+        &self,
+        anchor_id: &str,
+        asset_code: Option<&str>,
+        operation: Option<FeeOperation>,
+        limit: i64,
+    ) -> Result<Vec<FeeScheduleChange>> {
+        let changes = sqlx::query_as::<_, FeeScheduleChange>(
+            r#"
+            SELECT * FROM anchor_fee_schedule_history
+            WHERE anchor_id = $1
+              AND ($2 IS NULL OR asset_code = $2)
+              AND ($3 IS NULL OR operation = $3)
+            ORDER BY observed_at DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(asset_code)
+        .bind(operation.map(|op| op.as_str()))
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(changes)
+    }
+
+    /// Cost of a deposit or withdrawal of `amount` of an asset at every
+    /// anchor that has it enabled, cheapest first.
+    ///
+    /// Anchors that cannot take the amount or whose fee is unknown follow.
+    pub async fn compare(
+        &self,
+        asset_code: &str,
+        operation: FeeOperation,
+        amount: f64,
+    ) -> Result<Vec<FeeQuote>> {
+        let rows = sqlx::query_as::<_, FeeScheduleRow>(
+            r#"
+            SELECT s.*, a.name AS anchor_name
+            FROM anchor_fee_schedules s
+            JOIN anchors a ON a.id = s.anchor_id
+            WHERE s.asset_code = $1 AND s.operation = $2 AND s.enabled = 1
+            "#,
+        )
+        .bind(asset_code)
+        .bind(operation.as_str())
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut quotes = Vec::with_capacity(rows.len());
+        for row in rows {
+            let schedule = row.schedule;
+            let terms = &schedule.terms;
+            let within_limits = terms.within_limits(amount);
+
+            let (fee_source, fee, error) = if !within_limits {
+                (None, None, None)
+            } else if let Some(fee) = terms.fee_for(amount) {
+                (Some("schedule"), Some(fee), None)
+            } else if terms.fee_endpoint {
+                match self
+                    .fetch_fee(&schedule.transfer_server, operation, asset_code, amount)
+                    .await
+                {
+                    Ok(fee) => (Some("fee_endpoint"), Some(fee), None),
+                    Err(e) => (None, None, Some(e.to_string())),
+                }
+            } else {
+                (None, None, Some("anchor publishes no fee".to_string()))
+            };
+
+            quotes.push(FeeQuote {
+                anchor_id: schedule.anchor_id,
+                anchor_name: row.anchor_name,
+                sep: schedule.sep,
+                transfer_server: schedule.transfer_server,
+                fee_fixed: terms.fee_fixed,
+                fee_percent: terms.fee_percent,
+                fee_minimum: terms.fee_minimum,
+                min_amount: terms.min_amount,
+                max_amount: terms.max_amount,
+                fee_source: fee_source.map(str::to_string),
+                fee,
+                amount_out: fee.map(|fee| (amount - fee).max(0.0)),
+                within_limits,
+                error,
+            });
+        }
+
+        quotes.sort_by(|a, b| match (a.fee, b.fee) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        Ok(quotes)
+    }
+
+    /// Ask a SEP-6/24 `/fee` endpoint for the fee on `amount`
+    async fn fetch_fee(
+        &self,
+        transfer_server: &str,
+        operation: FeeOperation,
+        asset_code: &str,
+        amount: f64,
+    ) -> Result<f64> {
+        let url = format!("{}/fee", transfer_server.trim_end_matches('/'));
+        let amount = amount.to_string();
+        let request = self.client.get(&url).query(&[
+            ("operation", operation.as_str()),
+            ("asset_code", asset_code),
+            ("amount", amount.as_str()),
+        ]);
+        let body = self.send_json(request).await?;
+
+        number(&body, "fee").ok_or_else(|| anyhow!("/fee response has no `fee`"))
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value> {
+        self.send_json(self.client.get(url)).await
+    }
+
+    async fn send_json(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("HTTP {}", status);
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Replace the stored schedule of one transfer server, recording changes
+    async fn store(
+        &self,
+        anchor_id: &str,
+        sep: SepEndpoint,
+        transfer_server: &str,
+        fees: &[ParsedFee],
+    ) -> Result<usize> {
+        let collected_at = Utc::now();
+        let mut tx = self.db.pool().begin().await?;
+
+        let current: HashMap<(String, String), FeeTerms> = sqlx::query_as::<_, AnchorFeeSchedule>(
+            "SELECT * FROM anchor_fee_schedules WHERE anchor_id = $1 AND sep = $2",
+        )
+        .bind(anchor_id)
+        .bind(sep.as_str())
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|s| ((s.operation, s.asset_code), s.terms))
+        .collect();
+
+        let mut changed: Vec<(&str, &str, FeeTerms)> = Vec::new();
+        for fee in fees {
+            let key = (fee.operation.as_str().to_string(), fee.asset_code.clone());
+            if current.get(&key) != Some(&fee.terms) {
+                changed.push((
+                    fee.operation.as_str(),
+                    fee.asset_code.as_str(),
+                    fee.terms.clone(),
+                ));
+            }
+
+            sqlx::query(
+                r#"
+                INSERT INTO anchor_fee_schedules (
+                    anchor_id, sep, operation, asset_code, transfer_server, enabled,
+                    fee_fixed, fee_percent, fee_minimum, min_amount, max_amount,
+                    fee_endpoint, collected_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                ON CONFLICT (anchor_id, sep, operation, asset_code) DO UPDATE
+                SET transfer_server = excluded.transfer_server,
+                    enabled = excluded.enabled,
+                    fee_fixed = excluded.fee_fixed,
+                    fee_percent = excluded.fee_percent,
+                    fee_minimum = excluded.fee_minimum,
+                    min_amount = excluded.min_amount,
+                    max_amount = excluded.max_amount,
+                    fee_endpoint = excluded.fee_endpoint,
+                    collected_at = excluded.collected_at
+                "#,
+            )
+            .bind(anchor_id)
+            .bind(sep.as_str())
+            .bind(fee.operation.as_str())
+            .bind(&fee.asset_code)
+            .bind(transfer_server)
+            .bind(fee.terms.enabled)
+            .bind(fee.terms.fee_fixed)
+            .bind(fee.terms.fee_percent)
+            .bind(fee.terms.fee_minimum)
+            .bind(fee.terms.min_amount)
+            .bind(fee.terms.max_amount)
+            .bind(fee.terms.fee_endpoint)
+            .bind(collected_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Assets no longer listed are dropped and recorded as disabled
+        let removed = FeeTerms {
+            enabled: false,
+            fee_fixed: None,
+            fee_percent: None,
+            fee_minimum: None,
+            min_amount: None,
+            max_amount: None,
+            fee_endpoint: false,
+        };
+        for (operation, asset_code) in current.keys() {
+            let listed = fees
+                .iter()
+                .any(|f| f.operation.as_str() == operation && &f.asset_code == asset_code);
+            if listed {
+                continue;
+            }
+            sqlx::query(
+                r#"
+                DELETE FROM anchor_fee_schedules
+                WHERE anchor_id = $1 AND sep = $2 AND operation = $3 AND asset_code = $4
+                "#,
+            )
+            .bind(anchor_id)
+            .bind(sep.as_str())
+            .bind(operation)
+            .bind(asset_code)
+            .execute(&mut *tx)
+            .await?;
+            changed.push((operation, asset_code, removed.clone()));
+        }
+
+        for (operation, asset_code, terms) in &changed {
+            sqlx::query(
+                r#"
+                INSERT INTO anchor_fee_schedule_history (
+                    anchor_id, sep, operation, asset_code, enabled, fee_fixed,
+                    fee_percent, fee_minimum, min_amount, max_amount, fee_endpoint,
+                    observed_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                "#,
+            )
+            .bind(anchor_id)
+            .bind(sep.as_str())
+            .bind(operation)
+            .bind(asset_code)
+            .bind(terms.enabled)
+            .bind(terms.fee_fixed)
+            .bind(terms.fee_percent)
+            .bind(terms.fee_minimum)
+            .bind(terms.min_amount)
+            .bind(terms.max_amount)
+            .bind(terms.fee_endpoint)
+            .bind(collected_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(changed.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_info_fees() {
+        let body = json!({
+            "deposit": {
+                "USDC": {
+                    "enabled": true,
+                    "fee_fixed": 5,
+                    "fee_percent": "1.5",
+                    "min_amount": 10,
+                    "max_amount": 10000
+                }
+            },
+            "withdraw": {
+                "USDC": { "enabled": true }
+            },
+            "fee": { "enabled": true }
+        });
+
+        let fees = parse_info_fees(&body);
+        assert_eq!(fees.len(), 2);
+        assert_eq!(fees[0].operation, FeeOperation::Deposit);
+        assert_eq!(fees[0].asset_code, "USDC");
+        assert_eq!(fees[0].terms.fee_fixed, Some(5.0));
+        assert_eq!(fees[0].terms.fee_percent, Some(1.5));
+        assert!(!fees[0].terms.fee_endpoint);
+        assert_eq!(fees[1].operation, FeeOperation::Withdraw);
+        assert!(fees[1].terms.fee_endpoint);
+        assert_eq!(fees[1].terms.fee_for(100.0), None);
+    }
+
+    #[test]
+    fn test_fee_terms() {
+        let terms = FeeTerms {
+            enabled: true,
+            fee_fixed: Some(1.0),
+            fee_percent: Some(0.5),
+            fee_minimum: Some(2.0),
+            min_amount: Some(10.0),
+            max_amount: Some(1000.0),
+            fee_endpoint: false,
+        };
+
+        assert_eq!(terms.fee_for(100.0), Some(2.0));
+        assert_eq!(terms.fee_for(1000.0), Some(6.0));
+        assert!(terms.within_limits(10.0));
+        assert!(!terms.within_limits(5.0));
+        assert!(!terms.within_limits(1000.5));
+    }
+}
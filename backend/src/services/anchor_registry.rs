@@ -0,0 +1,444 @@
+use anyhow::Result;
+use chrono::Utc;
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::database::Database;
+use crate::services::sep10_client::Sep10Client;
+use crate::services::sep_endpoint_prober::{resolve_endpoints, SepEndpoint};
+
+/// Longest per-anchor proxy timeout that can be configured
+pub const MAX_PROXY_TIMEOUT_SECS: i64 = 120;
+
+/// Authentication the proxies use towards an anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyAuth {
+    /// Only a caller's `jwt` is forwarded; no token is obtained through SEP-10
+    None,
+    /// A caller's `jwt` is forwarded, or a token is obtained through SEP-10
+    /// for the anchor's home domain when the request names it
+    #[default]
+    Optional,
+    /// Every request carries a token: the caller's `jwt`, or one obtained
+    /// through SEP-10 for the anchor's home domain
+    Sep10,
+}
+
+impl ProxyAuth {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProxyAuth::None => "none",
+            ProxyAuth::Optional => "optional",
+            ProxyAuth::Sep10 => "sep10",
+        }
+    }
+}
+
+impl fmt::Display for ProxyAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ProxyAuth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(ProxyAuth::None),
+            "optional" => Ok(ProxyAuth::Optional),
+            "sep10" => Ok(ProxyAuth::Sep10),
+            _ => Err(format!(
+                "unknown proxy auth: {} (expected none, optional or sep10)",
+                s
+            )),
+        }
+    }
+}
+
+/// Proxy settings of an anchor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorProxyConfig {
+    pub anchor_id: String,
+    pub enabled: bool,
+    pub auth: ProxyAuth,
+    /// `None` uses the proxy's default timeout
+    pub timeout_secs: Option<i64>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AnchorProxyRow {
+    id: String,
+    name: String,
+    home_domain: Option<String>,
+    proxy_enabled: bool,
+    proxy_auth: String,
+    proxy_timeout_secs: Option<i64>,
+}
+
+impl AnchorProxyRow {
+    fn config(&self) -> AnchorProxyConfig {
+        AnchorProxyConfig {
+            anchor_id: self.id.clone(),
+            enabled: self.proxy_enabled,
+            // The API only stores known values
+            auth: self.proxy_auth.parse().unwrap_or_default(),
+            timeout_secs: self.proxy_timeout_secs,
+        }
+    }
+}
+
+/// An enabled anchor serving one SEP endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyAnchor {
+    pub anchor_id: String,
+    pub name: String,
+    pub home_domain: Option<String>,
+    pub sep: SepEndpoint,
+    /// Base URL of the endpoint, without a trailing slash
+    pub url: String,
+    pub auth: ProxyAuth,
+    pub timeout_secs: Option<i64>,
+}
+
+impl ProxyAnchor {
+    /// Apply the anchor's timeout, if one is configured
+    pub fn with_timeout(anchor: Option<&ProxyAnchor>, request: RequestBuilder) -> RequestBuilder {
+        match anchor.and_then(|a| a.timeout_secs) {
+            Some(secs) => request.timeout(Duration::from_secs(secs as u64)),
+            None => request,
+        }
+    }
+
+    /// Reject a caller's `home_domain` that is not the anchor's own
+    pub fn check_home_domain(&self, requested: Option<&str>) -> Result<(), String> {
+        match (requested.map(str::trim), self.home_domain.as_deref()) {
+            (Some(requested), Some(home)) if requested.eq_ignore_ascii_case(home) => Ok(()),
+            (Some(requested), _) => Err(format!(
+                "home_domain {} does not belong to anchor {}",
+                requested, self.anchor_id
+            )),
+            (None, _) => Ok(()),
+        }
+    }
+
+    /// Domain to authenticate with through SEP-10 when the caller sent no
+    /// `jwt`: always the anchor's own home domain. A `requested` domain only
+    /// opts in to authentication for `Optional` anchors. `Err` when the
+    /// anchor requires a token but none can be obtained.
+    pub fn auth_domain(&self, requested: Option<&str>) -> Result<Option<&str>, String> {
+        let required = match self.auth {
+            ProxyAuth::None => return Ok(None),
+            ProxyAuth::Optional if requested.is_none() => return Ok(None),
+            ProxyAuth::Optional => "anchor has no home domain to authenticate with",
+            ProxyAuth::Sep10 => "anchor requires SEP-10 authentication but has no home domain",
+        };
+        self.home_domain
+            .as_deref()
+            .map(Some)
+            .ok_or_else(|| required.to_string())
+    }
+}
+
+/// Home domain to obtain a SEP-10 token for with the backend's own key when
+/// a caller sends no `jwt`, or `None` when no token is needed.
+///
+/// The domain never comes from the request: registered anchors use the home
+/// domain in the registry, other servers the host of `url`, provided its
+/// stellar.toml publishes `url` as the `sep` endpoint. A `requested` domain
+/// only opts in to authentication and must name the same domain. The
+/// backend's key is only used for `signed_in` callers.
+pub async fn service_auth_domain(
+    sep10: &Sep10Client,
+    sep: SepEndpoint,
+    url: &str,
+    anchor: Option<&ProxyAnchor>,
+    requested: Option<&str>,
+    signed_in: bool,
+) -> Result<Option<String>, ServiceAuthError> {
+    if let Some(anchor) = anchor {
+        let domain = anchor
+            .auth_domain(requested)
+            .map_err(ServiceAuthError::Unavailable)?;
+        if domain.is_some() && !signed_in {
+            return Err(ServiceAuthError::SignInRequired);
+        }
+        return Ok(domain.map(str::to_string));
+    }
+    let Some(requested) = requested else {
+        return Ok(None);
+    };
+    if !signed_in {
+        return Err(ServiceAuthError::SignInRequired);
+    }
+    let domain = sep10
+        .server_home_domain(sep, url)
+        .await
+        .map_err(|e| ServiceAuthError::Unavailable(format!("{:#}", e)))?;
+    if !requested.trim().eq_ignore_ascii_case(&domain) {
+        return Err(ServiceAuthError::Mismatch(format!(
+            "home_domain {} does not serve {}",
+            requested, url
+        )));
+    }
+
+    Ok(Some(domain))
+}
+
+/// How a proxied request authenticates with the anchor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnchorAuth {
+    /// Bearer token sent to the anchor
+    pub token: Option<String>,
+    /// Home domain the backend's key authenticated with; `None` when the
+    /// caller's own `jwt` was used or no token was needed
+    pub service_domain: Option<String>,
+}
+
+impl AnchorAuth {
+    /// The token is the caller's own, which the backend must not keep
+    pub fn is_caller_token(&self) -> bool {
+        self.token.is_some() && self.service_domain.is_none()
+    }
+}
+
+/// Why no SEP-10 token can be obtained for a proxied request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceAuthError {
+    /// Only signed-in callers may use the backend's key
+    SignInRequired,
+    /// The requested home domain does not serve the called URL
+    Mismatch(String),
+    /// No home domain to authenticate with could be determined
+    Unavailable(String),
+}
+
+/// Whether a proxy may call a URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProxyAccess {
+    /// The URL belongs to an enabled anchor
+    Registered(ProxyAnchor),
+    /// The URL is in the proxy's allowed origins, or nothing restricts it
+    Allowed,
+    Denied,
+}
+
+fn normalize(url: &str) -> &str {
+    url.trim().trim_end_matches('/')
+}
+
+/// `url` is `base` or a path below it
+fn is_under(url: &str, base: &str) -> bool {
+    let (url, base) = (normalize(url), normalize(base));
+    url.strip_prefix(base)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Anchors the SEP proxies may call, with how to call them.
+///
+/// Anchors are enabled for proxying in the `anchors` table and reached
+/// through their configured or discovered SEP endpoints. Every lookup reads
+/// the database, so changes apply to the next proxied request.
+pub struct AnchorRegistry {
+    db: Arc<Database>,
+}
+
+impl AnchorRegistry {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    async fn row(&self, anchor_id: &str) -> Result<Option<AnchorProxyRow>> {
+        Ok(sqlx::query_as::<_, AnchorProxyRow>(
+            r#"
+            SELECT id, name, home_domain, proxy_enabled, proxy_auth, proxy_timeout_secs
+            FROM anchors WHERE id = $1
+            "#,
+        )
+        .bind(anchor_id)
+        .fetch_optional(self.db.pool())
+        .await?)
+    }
+
+    pub async fn get_config(&self, anchor_id: &str) -> Result<Option<AnchorProxyConfig>> {
+        Ok(self.row(anchor_id).await?.map(|row| row.config()))
+    }
+
+    /// Replace an anchor's proxy settings; `None` if the anchor does not exist
+    pub async fn set_config(
+        &self,
+        anchor_id: &str,
+        enabled: bool,
+        auth: ProxyAuth,
+        timeout_secs: Option<i64>,
+    ) -> Result<Option<AnchorProxyConfig>> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE anchors
+            SET proxy_enabled = $1, proxy_auth = $2, proxy_timeout_secs = $3, updated_at = $4
+            WHERE id = $5
+            "#,
+        )
+        .bind(enabled)
+        .bind(auth.as_str())
+        .bind(timeout_secs)
+        .bind(Utc::now())
+        .bind(anchor_id)
+        .execute(self.db.pool())
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Ok(None);
+        }
+
+        self.get_config(anchor_id).await
+    }
+
+    /// Enabled anchors with a `sep` endpoint, by name
+    pub async fn proxy_anchors(&self, sep: SepEndpoint) -> Result<Vec<ProxyAnchor>> {
+        let rows = sqlx::query_as::<_, AnchorProxyRow>(
+            r#"
+            SELECT id, name, home_domain, proxy_enabled, proxy_auth, proxy_timeout_secs
+            FROM anchors WHERE proxy_enabled = 1
+            ORDER BY name, id
+            "#,
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut anchors = Vec::new();
+        for row in rows {
+            let endpoint = resolve_endpoints(&self.db, &row.id)
+                .await?
+                .into_iter()
+                .find(|(endpoint, _)| *endpoint == sep);
+            if let Some((_, url)) = endpoint {
+                let config = row.config();
+                anchors.push(ProxyAnchor {
+                    anchor_id: row.id,
+                    name: row.name,
+                    home_domain: row.home_domain,
+                    sep,
+                    url: normalize(&url).to_string(),
+                    auth: config.auth,
+                    timeout_secs: config.timeout_secs,
+                });
+            }
+        }
+
+        Ok(anchors)
+    }
+
+    /// Decide whether a proxy may call `url` for `sep`.
+    ///
+    /// URLs of enabled anchors are always allowed, as are URLs under one of
+    /// the proxy's `allowed_origins`. When no anchor is enabled for `sep` and
+    /// no origins are configured, any URL is allowed (development only).
+    pub async fn authorize(
+        &self,
+        sep: SepEndpoint,
+        url: &str,
+        allowed_origins: &[String],
+    ) -> Result<ProxyAccess> {
+        let anchors = self.proxy_anchors(sep).await?;
+        let restricted = !anchors.is_empty() || !allowed_origins.is_empty();
+        // The most specific endpoint wins when one URL prefixes another
+        let registered = anchors
+            .into_iter()
+            .filter(|anchor| is_under(url, &anchor.url))
+            .max_by_key(|anchor| anchor.url.len());
+        if let Some(anchor) = registered {
+            return Ok(ProxyAccess::Registered(anchor));
+        }
+
+        if allowed_origins
+            .iter()
+            .any(|origin| origin == "*" || is_under(url, origin))
+        {
+            return Ok(ProxyAccess::Allowed);
+        }
+        if !restricted {
+            return Ok(ProxyAccess::Allowed);
+        }
+
+        Ok(ProxyAccess::Denied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor(auth: ProxyAuth, home_domain: Option<&str>) -> ProxyAnchor {
+        ProxyAnchor {
+            anchor_id: "a".to_string(),
+            name: "Anchor".to_string(),
+            home_domain: home_domain.map(str::to_string),
+            sep: SepEndpoint::Sep24,
+            url: "https://anchor.example/sep24".to_string(),
+            auth,
+            timeout_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_is_under() {
+        assert!(is_under(
+            "https://anchor.example/sep24",
+            "https://anchor.example/sep24/"
+        ));
+        assert!(is_under(
+            "https://anchor.example/sep24/x",
+            "https://anchor.example/sep24"
+        ));
+        assert!(!is_under(
+            "https://anchor.example/sep240",
+            "https://anchor.example/sep24"
+        ));
+        assert!(!is_under(
+            "https://anchor.example.evil",
+            "https://anchor.example"
+        ));
+    }
+
+    #[test]
+    fn test_auth_domain() {
+        let none = anchor(ProxyAuth::None, Some("anchor.example"));
+        assert_eq!(none.auth_domain(Some("anchor.example")), Ok(None));
+        let optional = anchor(ProxyAuth::Optional, Some("anchor.example"));
+        assert_eq!(optional.auth_domain(None), Ok(None));
+        assert_eq!(
+            optional.auth_domain(Some("anchor.example")),
+            Ok(Some("anchor.example"))
+        );
+        let required = anchor(ProxyAuth::Sep10, Some("anchor.example"));
+        assert_eq!(required.auth_domain(None), Ok(Some("anchor.example")));
+        assert_eq!(
+            required.auth_domain(Some("x.example")),
+            Ok(Some("anchor.example"))
+        );
+        assert!(anchor(ProxyAuth::Sep10, None).auth_domain(None).is_err());
+    }
+
+    #[test]
+    fn test_check_home_domain() {
+        let registered = anchor(ProxyAuth::Sep10, Some("anchor.example"));
+        assert!(registered.check_home_domain(None).is_ok());
+        assert!(registered.check_home_domain(Some("Anchor.Example")).is_ok());
+        assert!(registered.check_home_domain(Some("x.example")).is_err());
+        assert!(anchor(ProxyAuth::Sep10, None)
+            .check_home_domain(Some("x.example"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_proxy_auth() {
+        assert_eq!("SEP10".parse::<ProxyAuth>(), Ok(ProxyAuth::Sep10));
+        assert!("jwt".parse::<ProxyAuth>().is_err());
+    }
+}
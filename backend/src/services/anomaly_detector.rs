@@ -0,0 +1,557 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::time::{interval, Duration as TokioDuration};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::broadcast::broadcast_corridor_anomaly;
+use crate::database::Database;
+use crate::models::asset::CorridorKey;
+use crate::services::aggregation::HourlyCorridorMetrics;
+use crate::websocket::WsState;
+
+/// Scale factor turning a median absolute deviation into a standard deviation
+const MAD_SCALE: f64 = 1.4826;
+
+#[derive(Debug, Clone)]
+pub struct AnomalyDetectorConfig {
+    pub interval_minutes: u64,
+    /// Weeks of history used for hour-of-week baselines
+    pub baseline_weeks: i64,
+    /// Same-hour-of-week samples needed before the seasonal baseline is used
+    pub min_seasonal_samples: usize,
+    /// Hourly samples needed before the EWMA fallback is used
+    pub min_ewma_samples: usize,
+    pub ewma_alpha: f64,
+    /// Absolute score at which a deviation is recorded
+    pub z_threshold: f64,
+}
+
+impl Default for AnomalyDetectorConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 60,
+            baseline_weeks: 4,
+            min_seasonal_samples: 3,
+            min_ewma_samples: 12,
+            ewma_alpha: 0.3,
+            z_threshold: 3.5,
+        }
+    }
+}
+
+/// Corridor metric watched for anomalies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    Volume,
+    SuccessRate,
+    Latency,
+}
+
+impl AnomalyMetric {
+    pub const ALL: [AnomalyMetric; 3] = [Self::Volume, Self::SuccessRate, Self::Latency];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Volume => "volume",
+            Self::SuccessRate => "success_rate",
+            Self::Latency => "latency",
+        }
+    }
+
+    fn value(&self, metric: &HourlyCorridorMetrics) -> Option<f64> {
+        match self {
+            Self::Volume => Some(metric.volume_usd),
+            Self::SuccessRate => (metric.total_transactions > 0).then_some(metric.success_rate),
+            Self::Latency => metric.avg_settlement_latency_ms.map(|ms| ms as f64),
+        }
+    }
+
+    /// Whether a deviation with this sign is a problem for the corridor.
+    ///
+    /// Volume is flagged both ways, success rate only when it drops and
+    /// latency only when it rises.
+    fn is_adverse(&self, score: f64) -> bool {
+        match self {
+            Self::Volume => true,
+            Self::SuccessRate => score < 0.0,
+            Self::Latency => score > 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalySeverity {
+    Minor,
+    Major,
+    Critical,
+}
+
+impl AnomalySeverity {
+    /// Severity for an absolute score relative to the detection threshold
+    pub fn from_score(score: f64, threshold: f64) -> Self {
+        let magnitude = score.abs();
+        if magnitude >= threshold * 3.0 {
+            Self::Critical
+        } else if magnitude >= threshold * 2.0 {
+            Self::Major
+        } else {
+            Self::Minor
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Minor => "minor",
+            Self::Major => "major",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// Baseline a value was scored against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BaselineKind {
+    /// Same hour-of-week in previous weeks, scored with median/MAD
+    Seasonal,
+    /// Exponentially weighted mean and deviation of recent hours
+    Ewma,
+}
+
+impl BaselineKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Seasonal => "seasonal",
+            Self::Ewma => "ewma",
+        }
+    }
+}
+
+/// Expected value and signed score of an observation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BaselineScore {
+    pub expected: f64,
+    pub score: f64,
+    pub kind: BaselineKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CorridorAnomaly {
+    pub id: String,
+    pub corridor_key: String,
+    pub metric: String,
+    pub hour_bucket: String,
+    pub observed_value: f64,
+    pub expected_value: f64,
+    pub score: f64,
+    pub baseline: String,
+    pub severity: String,
+    pub detected_at: String,
+}
+
+pub struct CorridorAnomalyDetector {
+    db: Arc<Database>,
+    ws_state: Arc<WsState>,
+    config: AnomalyDetectorConfig,
+}
+
+impl CorridorAnomalyDetector {
+    pub fn new(db: Arc<Database>, ws_state: Arc<WsState>, config: AnomalyDetectorConfig) -> Self {
+        Self {
+            db,
+            ws_state,
+            config,
+        }
+    }
+
+    /// Start the periodic anomaly detection job
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting corridor anomaly detector (interval: {} minutes)",
+            self.config.interval_minutes
+        );
+
+        let mut ticker = interval(TokioDuration::from_secs(self.config.interval_minutes * 60));
+
+        loop {
+            ticker.tick().await;
+
+            match self.run_detection(Utc::now()).await {
+                Ok(anomalies) if !anomalies.is_empty() => {
+                    info!("Detected {} corridor anomalies", anomalies.len())
+                }
+                Ok(_) => {}
+                Err(e) => error!("Corridor anomaly detection failed: {}", e),
+            }
+        }
+    }
+
+    /// Score the last complete hour of every corridor against its history,
+    /// store new anomalies and broadcast them. Returns the anomalies recorded
+    /// by this run; ones already stored for the same hour are skipped.
+    ///
+    /// The hour `now` falls in is still open and is left for the next run.
+    /// Hours without a bucket had no traffic and count as zero volume, so a
+    /// corridor that stops entirely is flagged too.
+    pub async fn run_detection(&self, now: DateTime<Utc>) -> Result<Vec<CorridorAnomaly>> {
+        let scored_hour = truncate_to_hour(now) - Duration::hours(1);
+        let start = scored_hour - Duration::weeks(self.config.baseline_weeks);
+        let metrics = self
+            .db
+            .fetch_hourly_metrics_by_timerange(start, scored_hour)
+            .await
+            .context("Failed to fetch hourly metrics for anomaly detection")?;
+
+        let mut by_corridor: HashMap<String, Vec<HourlyCorridorMetrics>> = HashMap::new();
+        for metric in metrics {
+            if metric.hour_bucket > scored_hour {
+                continue;
+            }
+            by_corridor
+                .entry(metric.corridor_key.clone())
+                .or_default()
+                .push(metric);
+        }
+
+        let mut recorded = Vec::new();
+        for (corridor_key, history) in by_corridor {
+            let mut history = fill_quiet_hours(history, scored_hour);
+            let Some(latest) = history.pop() else {
+                continue;
+            };
+
+            for metric in AnomalyMetric::ALL {
+                let Some(anomaly) = self.evaluate(&corridor_key, metric, &latest, &history) else {
+                    continue;
+                };
+                if self.store_anomaly(&anomaly).await? {
+                    broadcast_corridor_anomaly(&self.ws_state, &anomaly);
+                    recorded.push(anomaly);
+                }
+            }
+        }
+
+        Ok(recorded)
+    }
+
+    fn evaluate(
+        &self,
+        corridor_key: &str,
+        metric: AnomalyMetric,
+        latest: &HourlyCorridorMetrics,
+        history: &[HourlyCorridorMetrics],
+    ) -> Option<CorridorAnomaly> {
+        let observed = metric.value(latest)?;
+        let latest_slot = hour_of_week(latest.hour_bucket);
+
+        let seasonal: Vec<f64> = history
+            .iter()
+            .filter(|m| hour_of_week(m.hour_bucket) == latest_slot)
+            .filter_map(|m| metric.value(m))
+            .collect();
+        let recent: Vec<f64> = history.iter().filter_map(|m| metric.value(m)).collect();
+
+        let baseline = if seasonal.len() >= self.config.min_seasonal_samples {
+            robust_z_score(observed, &seasonal)
+        } else if recent.len() >= self.config.min_ewma_samples {
+            ewma_z_score(observed, &recent, self.config.ewma_alpha)
+        } else {
+            None
+        }?;
+
+        if baseline.score.abs() < self.config.z_threshold || !metric.is_adverse(baseline.score) {
+            return None;
+        }
+
+        Some(CorridorAnomaly {
+            id: Uuid::new_v4().to_string(),
+            corridor_key: corridor_key.to_string(),
+            metric: metric.as_str().to_string(),
+            hour_bucket: latest.hour_bucket.to_rfc3339(),
+            observed_value: observed,
+            expected_value: baseline.expected,
+            score: baseline.score,
+            baseline: baseline.kind.as_str().to_string(),
+            severity: AnomalySeverity::from_score(baseline.score, self.config.z_threshold)
+                .as_str()
+                .to_string(),
+            detected_at: Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Insert an anomaly, returning false if one was already recorded for the hour
+    async fn store_anomaly(&self, anomaly: &CorridorAnomaly) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO corridor_anomalies (
+                id, corridor_key, metric, hour_bucket, observed_value,
+                expected_value, score, baseline, severity, detected_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&anomaly.id)
+        .bind(&anomaly.corridor_key)
+        .bind(&anomaly.metric)
+        .bind(&anomaly.hour_bucket)
+        .bind(anomaly.observed_value)
+        .bind(anomaly.expected_value)
+        .bind(anomaly.score)
+        .bind(&anomaly.baseline)
+        .bind(&anomaly.severity)
+        .bind(&anomaly.detected_at)
+        .execute(self.db.pool())
+        .await
+        .context("Failed to store corridor anomaly")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Recorded anomalies for a corridor, newest first
+    pub async fn get_anomalies(
+        &self,
+        corridor_key: &CorridorKey,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<CorridorAnomaly>> {
+        let anomalies = sqlx::query_as::<_, CorridorAnomaly>(
+            r#"
+            SELECT id, corridor_key, metric, hour_bucket, observed_value,
+                   expected_value, score, baseline, severity, detected_at
+            FROM corridor_anomalies
+            WHERE corridor_key = ? AND hour_bucket >= ?
+            ORDER BY hour_bucket DESC, metric ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(since.to_rfc3339())
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to fetch corridor anomalies")?;
+
+        Ok(anomalies)
+    }
+}
+
+/// Start of the hour `dt` falls in
+fn truncate_to_hour(dt: DateTime<Utc>) -> DateTime<Utc> {
+    dt - Duration::seconds(i64::from(dt.minute() * 60 + dt.second()))
+        - Duration::nanoseconds(i64::from(dt.nanosecond()))
+}
+
+/// Hours of a corridor from its first bucket through `until`, oldest first,
+/// with a zero-traffic bucket for every hour that has none
+fn fill_quiet_hours(
+    mut history: Vec<HourlyCorridorMetrics>,
+    until: DateTime<Utc>,
+) -> Vec<HourlyCorridorMetrics> {
+    history.sort_by_key(|m| m.hour_bucket);
+
+    let mut filled: Vec<HourlyCorridorMetrics> = Vec::new();
+    for bucket in history {
+        let hour = truncate_to_hour(bucket.hour_bucket);
+        if let Some(last) = filled.last() {
+            if last.hour_bucket >= hour {
+                continue;
+            }
+            let mut gap = last.hour_bucket + Duration::hours(1);
+            while gap < hour {
+                filled.push(quiet_hour(&bucket, gap));
+                gap += Duration::hours(1);
+            }
+        }
+        filled.push(HourlyCorridorMetrics {
+            hour_bucket: hour,
+            ..bucket
+        });
+    }
+    if let Some(last) = filled.last().cloned() {
+        let mut gap = last.hour_bucket + Duration::hours(1);
+        while gap <= until {
+            filled.push(quiet_hour(&last, gap));
+            gap += Duration::hours(1);
+        }
+    }
+    filled
+}
+
+/// A corridor hour without transactions
+fn quiet_hour(
+    template: &HourlyCorridorMetrics,
+    hour_bucket: DateTime<Utc>,
+) -> HourlyCorridorMetrics {
+    HourlyCorridorMetrics {
+        id: String::new(),
+        hour_bucket,
+        total_transactions: 0,
+        successful_transactions: 0,
+        failed_transactions: 0,
+        success_rate: 0.0,
+        volume_usd: 0.0,
+        avg_slippage_bps: 0.0,
+        avg_settlement_latency_ms: None,
+        ..template.clone()
+    }
+}
+
+/// Hour within the week, 0 being Monday 00:00 UTC
+pub fn hour_of_week(dt: DateTime<Utc>) -> u32 {
+    dt.weekday().num_days_from_monday() * 24 + dt.hour()
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Deviation floor so flat baselines still produce finite scores
+fn scale_floor(center: f64) -> f64 {
+    (center.abs() * 0.01).max(1e-6)
+}
+
+/// Score `value` against `samples` using the median and median absolute deviation
+pub fn robust_z_score(value: f64, samples: &[f64]) -> Option<BaselineScore> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let mut sorted = samples.to_vec();
+    let center = median(&mut sorted);
+    let mut deviations: Vec<f64> = samples.iter().map(|s| (s - center).abs()).collect();
+    let spread = (median(&mut deviations) * MAD_SCALE).max(scale_floor(center));
+
+    Some(BaselineScore {
+        expected: center,
+        score: (value - center) / spread,
+        kind: BaselineKind::Seasonal,
+    })
+}
+
+/// Score `value` against the exponentially weighted mean and deviation of `series`
+pub fn ewma_z_score(value: f64, series: &[f64], alpha: f64) -> Option<BaselineScore> {
+    let (&first, rest) = series.split_first()?;
+
+    let mut mean = first;
+    let mut variance = 0.0;
+    for &x in rest {
+        let diff = x - mean;
+        mean += alpha * diff;
+        variance = (1.0 - alpha) * (variance + alpha * diff * diff);
+    }
+    let spread = variance.sqrt().max(scale_floor(mean));
+
+    Some(BaselineScore {
+        expected: mean,
+        score: (value - mean) / spread,
+        kind: BaselineKind::Ewma,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_hour_of_week() {
+        // 2024-01-01 was a Monday
+        let monday = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(hour_of_week(monday), 0);
+        assert_eq!(hour_of_week(monday + Duration::hours(30)), 30);
+        assert_eq!(hour_of_week(monday + Duration::weeks(1)), 0);
+    }
+
+    #[test]
+    fn test_fill_quiet_hours() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let bucket = |hour: DateTime<Utc>, volume_usd: f64| HourlyCorridorMetrics {
+            id: "id".to_string(),
+            corridor_key: "USDC:G->XLM:native".to_string(),
+            asset_a_code: "USDC".to_string(),
+            asset_a_issuer: "G".to_string(),
+            asset_b_code: "XLM".to_string(),
+            asset_b_issuer: "native".to_string(),
+            hour_bucket: hour,
+            total_transactions: 10,
+            successful_transactions: 10,
+            failed_transactions: 0,
+            success_rate: 100.0,
+            volume_usd,
+            avg_slippage_bps: 0.0,
+            avg_settlement_latency_ms: Some(1_000),
+            liquidity_depth_usd: 0.0,
+        };
+
+        let filled = fill_quiet_hours(
+            vec![
+                bucket(start + Duration::hours(2), 30.0),
+                bucket(start, 10.0),
+            ],
+            start + Duration::hours(3),
+        );
+        let volumes: Vec<f64> = filled.iter().map(|m| m.volume_usd).collect();
+        assert_eq!(volumes, vec![10.0, 0.0, 30.0, 0.0]);
+        assert_eq!(filled[3].hour_bucket, start + Duration::hours(3));
+        assert_eq!(AnomalyMetric::Volume.value(&filled[1]), Some(0.0));
+        assert_eq!(AnomalyMetric::SuccessRate.value(&filled[1]), None);
+        assert_eq!(AnomalyMetric::Latency.value(&filled[1]), None);
+
+        assert_eq!(truncate_to_hour(start + Duration::minutes(59)), start);
+    }
+
+    #[test]
+    fn test_robust_z_score_ignores_outliers_in_baseline() {
+        let samples = [100.0, 102.0, 98.0, 101.0, 5_000.0];
+        let normal = robust_z_score(101.0, &samples).unwrap();
+        assert_eq!(normal.expected, 101.0);
+        assert!(normal.score.abs() < 1.0);
+
+        let drop = robust_z_score(10.0, &samples).unwrap();
+        assert!(drop.score < -20.0);
+        assert_eq!(drop.kind, BaselineKind::Seasonal);
+    }
+
+    #[test]
+    fn test_ewma_z_score() {
+        let series = [100.0, 104.0, 96.0, 102.0, 98.0, 100.0];
+        let normal = ewma_z_score(101.0, &series, 0.3).unwrap();
+        assert!(normal.score.abs() < 1.0);
+
+        let spike = ewma_z_score(200.0, &series, 0.3).unwrap();
+        assert!(spike.score > 10.0);
+        assert!(ewma_z_score(1.0, &[], 0.3).is_none());
+    }
+
+    #[test]
+    fn test_severity_and_direction() {
+        assert_eq!(
+            AnomalySeverity::from_score(-4.0, 3.5),
+            AnomalySeverity::Minor
+        );
+        assert_eq!(
+            AnomalySeverity::from_score(8.0, 3.5),
+            AnomalySeverity::Major
+        );
+        assert_eq!(
+            AnomalySeverity::from_score(-11.0, 3.5),
+            AnomalySeverity::Critical
+        );
+
+        assert!(AnomalyMetric::SuccessRate.is_adverse(-5.0));
+        assert!(!AnomalyMetric::SuccessRate.is_adverse(5.0));
+        assert!(AnomalyMetric::Latency.is_adverse(5.0));
+        assert!(AnomalyMetric::Volume.is_adverse(-5.0));
+    }
+}
@@ -0,0 +1,798 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::database::Database;
+use crate::models::Asset;
+use crate::rpc::{HorizonAsset, Payment, StellarRpcClient};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SupplyEventKind {
+    Mint,
+    Burn,
+}
+
+impl SupplyEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SupplyEventKind::Mint => "mint",
+            SupplyEventKind::Burn => "burn",
+        }
+    }
+}
+
+/// A payment that changed an asset's supply
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassifiedPayment {
+    pub kind: SupplyEventKind,
+    pub amount: f64,
+    /// Receiver of a mint or sender of a burn
+    pub counterparty: String,
+}
+
+/// Classify an issuer payment as a mint or burn of the issuer's asset.
+///
+/// Payments of the asset out of the issuer account create it and payments
+/// back to the issuer destroy it. For path payments the issuer's side of the
+/// conversion counts: the source asset for mints, the destination asset for
+/// burns.
+pub fn classify_payment(
+    payment: &Payment,
+    asset_code: &str,
+    asset_issuer: &str,
+) -> Option<ClassifiedPayment> {
+    let from = payment.from.as_deref().unwrap_or(&payment.source_account);
+    let to = payment.to.as_deref().unwrap_or(&payment.destination);
+    if from == to {
+        return None;
+    }
+
+    let is_asset = |code: Option<&str>, issuer: Option<&str>| {
+        code == Some(asset_code) && issuer == Some(asset_issuer)
+    };
+    let parse = |amount: &str| amount.parse::<f64>().ok().filter(|a| *a > 0.0);
+
+    if to == asset_issuer
+        && is_asset(
+            payment.asset_code.as_deref(),
+            payment.asset_issuer.as_deref(),
+        )
+    {
+        return Some(ClassifiedPayment {
+            kind: SupplyEventKind::Burn,
+            amount: parse(&payment.amount)?,
+            counterparty: from.to_string(),
+        });
+    }
+
+    if from == asset_issuer {
+        let (code, issuer, amount) = match payment.source_amount.as_deref() {
+            Some(source_amount) => (
+                payment.source_asset_code.as_deref(),
+                payment.source_asset_issuer.as_deref(),
+                source_amount,
+            ),
+            None => (
+                payment.asset_code.as_deref(),
+                payment.asset_issuer.as_deref(),
+                payment.amount.as_str(),
+            ),
+        };
+        if is_asset(code, issuer) {
+            return Some(ClassifiedPayment {
+                kind: SupplyEventKind::Mint,
+                amount: parse(amount)?,
+                counterparty: to.to_string(),
+            });
+        }
+    }
+
+    None
+}
+
+/// Units of an asset held anywhere on the ledger: trustlines in every
+/// authorization state, claimable balances, liquidity pools and contracts.
+///
+/// The issuer cannot hold its own asset, so this is already net of issuer
+/// holdings.
+pub fn total_supply(asset: &HorizonAsset) -> f64 {
+    [
+        &asset.balances.authorized,
+        &asset.balances.authorized_to_maintain_liabilities,
+        &asset.balances.unauthorized,
+        &asset.claimable_balances_amount,
+        &asset.liquidity_pools_amount,
+        &asset.contracts_amount,
+    ]
+    .iter()
+    .filter_map(|amount| amount.parse::<f64>().ok())
+    .sum()
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetSupplyMonitorConfig {
+    /// How often the supply of every tracked asset is synced
+    pub interval_secs: u64,
+    /// Issuer payments requested per Horizon page
+    pub page_limit: u32,
+    /// Pages of issuer payments classified per asset and sync; the rest are
+    /// picked up on the next sync
+    pub max_pages: usize,
+}
+
+impl Default for AssetSupplyMonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 3600,
+            page_limit: 200,
+            max_pages: 10,
+        }
+    }
+}
+
+/// Latest supply of an issued asset
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetSupply {
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub anchor_id: String,
+    pub total_supply: f64,
+    /// Held by the anchor's account and configured distribution accounts
+    pub distribution_balance: f64,
+    pub circulating_supply: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A mint or burn of an issued asset
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SupplyEvent {
+    pub id: String,
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub kind: String,
+    pub amount: f64,
+    pub counterparty: String,
+    pub transaction_hash: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Issuance and supply movement of an asset over one UTC day
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailySupplyDelta {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub mints: i64,
+    pub burns: i64,
+    pub minted: f64,
+    pub burned: f64,
+    pub net_issuance: f64,
+    /// Supply at the last sync of the day
+    pub total_supply: Option<f64>,
+    pub circulating_supply: Option<f64>,
+    /// Change from the previous day with a sync
+    pub total_supply_change: Option<f64>,
+    pub circulating_supply_change: Option<f64>,
+}
+
+/// Supply of one of an anchor's assets with its recent issuance
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssetSupplyOverview {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub supply: AssetSupply,
+    pub minted: f64,
+    pub burned: f64,
+    pub net_issuance: f64,
+}
+
+/// Supply of every asset an anchor issues
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorSupply {
+    pub anchor_id: String,
+    /// Days covered by `minted`, `burned` and `net_issuance`
+    pub window_days: i64,
+    pub assets: Vec<AssetSupplyOverview>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SupplySyncSummary {
+    pub assets: usize,
+    pub mints: usize,
+    pub burns: usize,
+    pub failed: usize,
+}
+
+#[derive(sqlx::FromRow)]
+struct DailyIssuanceRow {
+    day: String,
+    mints: i64,
+    burns: i64,
+    minted: f64,
+    burned: f64,
+}
+
+#[derive(sqlx::FromRow)]
+struct SupplySnapshotRow {
+    day: String,
+    total_supply: f64,
+    circulating_supply: f64,
+}
+
+/// Tracks the supply of anchors' issued assets.
+///
+/// Issuer account payments are classified as mints and burns, and each sync
+/// records the asset's total supply from Horizon along with its circulating
+/// supply: what is left once the anchor's own account and configured
+/// distribution accounts are excluded.
+pub struct AssetSupplyMonitor {
+    db: Arc<Database>,
+    rpc_client: Arc<StellarRpcClient>,
+    config: AssetSupplyMonitorConfig,
+}
+
+impl AssetSupplyMonitor {
+    pub fn new(
+        db: Arc<Database>,
+        rpc_client: Arc<StellarRpcClient>,
+        config: AssetSupplyMonitorConfig,
+    ) -> Self {
+        Self {
+            db,
+            rpc_client,
+            config,
+        }
+    }
+
+    /// Start the supply sync scheduler
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting asset supply monitor (interval: {} seconds)",
+            self.config.interval_secs
+        );
+
+        let mut ticker = interval(Duration::from_secs(self.config.interval_secs));
+
+        loop {
+            ticker.tick().await;
+            match self.run_sync().await {
+                Ok(summary) => info!(
+                    "Supply sync finished: {} assets, {} mints, {} burns, {} failed",
+                    summary.assets, summary.mints, summary.burns, summary.failed
+                ),
+                Err(e) => error!("Supply sync failed: {}", e),
+            }
+        }
+    }
+
+    /// Sync the supply of every tracked asset
+    pub async fn run_sync(&self) -> Result<SupplySyncSummary> {
+        let mut summary = SupplySyncSummary::default();
+
+        let assets = sqlx::query_as::<_, Asset>("SELECT * FROM assets ORDER BY asset_code")
+            .fetch_all(self.db.pool())
+            .await?;
+        for asset in &assets {
+            match self.sync_asset(asset).await {
+                Ok(None) => {}
+                Ok(Some((mints, burns))) => {
+                    summary.assets += 1;
+                    summary.mints += mints;
+                    summary.burns += burns;
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    warn!(
+                        "Failed to sync supply of {}:{}: {}",
+                        asset.asset_code, asset.asset_issuer, e
+                    );
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Classify new issuer payments and record the asset's supply.
+    ///
+    /// Returns the number of new mints and burns, or `None` when Horizon does
+    /// not know the asset.
+    pub async fn sync_asset(&self, asset: &Asset) -> Result<Option<(usize, usize)>> {
+        let code = asset.asset_code.as_str();
+        let issuer = asset.asset_issuer.as_str();
+
+        let Some(horizon_asset) = self.rpc_client.fetch_asset(code, issuer).await? else {
+            return Ok(None);
+        };
+        let total = total_supply(&horizon_asset);
+
+        let mut cursor: Option<String> = sqlx::query_scalar(
+            "SELECT payments_cursor FROM asset_supply WHERE asset_code = $1 AND asset_issuer = $2",
+        )
+        .bind(code)
+        .bind(issuer)
+        .fetch_optional(self.db.pool())
+        .await?
+        .flatten();
+
+        let mut events = Vec::new();
+        for _ in 0..self.config.max_pages {
+            let payments = self
+                .rpc_client
+                .fetch_account_payments_after(issuer, cursor.as_deref(), self.config.page_limit)
+                .await?;
+            for payment in &payments {
+                if let Some(classified) = classify_payment(payment, code, issuer) {
+                    events.push((payment.clone(), classified));
+                }
+            }
+            if let Some(last) = payments.last() {
+                cursor = Some(last.paging_token.clone());
+            }
+            if payments.len() < self.config.page_limit as usize {
+                break;
+            }
+        }
+
+        let mut distribution_balance = 0.0;
+        for account in self.distribution_accounts(asset).await? {
+            let holding = self.rpc_client.fetch_account(&account).await?;
+            distribution_balance += holding.balance_of(code, issuer).unwrap_or(0.0);
+        }
+        let circulating = (total - distribution_balance).max(0.0);
+
+        let now = Utc::now();
+        let (mut mints, mut burns) = (0, 0);
+        let mut tx = self.db.pool().begin().await?;
+
+        for (payment, classified) in &events {
+            let occurred_at = DateTime::parse_from_rfc3339(&payment.created_at)
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or(now);
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO asset_supply_events (
+                    id, asset_code, asset_issuer, kind, amount, counterparty,
+                    transaction_hash, occurred_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (id) DO NOTHING
+                "#,
+            )
+            .bind(&payment.id)
+            .bind(code)
+            .bind(issuer)
+            .bind(classified.kind.as_str())
+            .bind(classified.amount)
+            .bind(&classified.counterparty)
+            .bind(&payment.transaction_hash)
+            .bind(occurred_at)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+                > 0;
+
+            match (inserted, classified.kind) {
+                (false, _) => {}
+                (true, SupplyEventKind::Mint) => mints += 1,
+                (true, SupplyEventKind::Burn) => burns += 1,
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO asset_supply (
+                asset_code, asset_issuer, anchor_id, total_supply, distribution_balance,
+                circulating_supply, payments_cursor, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (asset_code, asset_issuer) DO UPDATE
+            SET anchor_id = excluded.anchor_id,
+                total_supply = excluded.total_supply,
+                distribution_balance = excluded.distribution_balance,
+                circulating_supply = excluded.circulating_supply,
+                payments_cursor = excluded.payments_cursor,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(code)
+        .bind(issuer)
+        .bind(&asset.anchor_id)
+        .bind(total)
+        .bind(distribution_balance)
+        .bind(circulating)
+        .bind(&cursor)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO asset_supply_snapshots (
+                asset_code, asset_issuer, total_supply, distribution_balance,
+                circulating_supply, snapshot_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(code)
+        .bind(issuer)
+        .bind(total)
+        .bind(distribution_balance)
+        .bind(circulating)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE assets SET total_supply = $1, updated_at = $2 WHERE asset_code = $3 AND asset_issuer = $4",
+        )
+        .bind(total)
+        .bind(now)
+        .bind(code)
+        .bind(issuer)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some((mints, burns)))
+    }
+
+    /// Accounts whose holdings are excluded from circulation: the configured
+    /// distribution accounts and the anchor's own account when it is not the
+    /// issuer
+    async fn distribution_accounts(&self, asset: &Asset) -> Result<Vec<String>> {
+        let accounts = sqlx::query_scalar(
+            r#"
+            SELECT account FROM asset_distribution_accounts
+            WHERE asset_code = $1 AND asset_issuer = $2
+            UNION
+            SELECT stellar_account FROM anchors
+            WHERE id = $3 AND stellar_account != $2
+            "#,
+        )
+        .bind(&asset.asset_code)
+        .bind(&asset.asset_issuer)
+        .bind(&asset.anchor_id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// Configured distribution accounts of an asset
+    pub async fn get_distribution_accounts(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<Vec<String>> {
+        let accounts = sqlx::query_scalar(
+            r#"
+            SELECT account FROM asset_distribution_accounts
+            WHERE asset_code = $1 AND asset_issuer = $2
+            ORDER BY account
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// Exclude an account's holdings from an asset's circulating supply;
+    /// returns false if the asset is not tracked
+    pub async fn add_distribution_account(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        account: &str,
+    ) -> Result<bool> {
+        // The assets foreign key doubles as the existence check
+        let result = sqlx::query(
+            r#"
+            INSERT INTO asset_distribution_accounts (asset_code, asset_issuer, account)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(account)
+        .execute(self.db.pool())
+        .await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stop excluding an account; returns whether it was configured
+    pub async fn remove_distribution_account(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        account: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM asset_distribution_accounts
+            WHERE asset_code = $1 AND asset_issuer = $2 AND account = $3
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(account)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Latest supply of an asset
+    pub async fn get_supply(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<Option<AssetSupply>> {
+        let supply = sqlx::query_as::<_, AssetSupply>(
+            "SELECT * FROM asset_supply WHERE asset_code = $1 AND asset_issuer = $2",
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        Ok(supply)
+    }
+
+    /// Mints and burns of an asset, newest first
+    pub async fn get_events(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        kind: Option<SupplyEventKind>,
+        limit: i64,
+    ) -> Result<Vec<SupplyEvent>> {
+        let events = sqlx::query_as::<_, SupplyEvent>(
+            r#"
+            SELECT * FROM asset_supply_events
+            WHERE asset_code = $1 AND asset_issuer = $2
+              AND ($3 IS NULL OR kind = $3)
+            ORDER BY occurred_at DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(kind.map(|k| k.as_str()))
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(events)
+    }
+
+    /// Daily issuance and supply changes over the last `days` days, oldest
+    /// first; days without mints, burns or syncs are omitted
+    pub async fn get_daily_deltas(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        days: i64,
+    ) -> Result<Vec<DailySupplyDelta>> {
+        let since = (Utc::now() - ChronoDuration::days(days)).date_naive();
+        let since = since.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+
+        let issuance = sqlx::query_as::<_, DailyIssuanceRow>(
+            r#"
+            SELECT
+                date(occurred_at) AS day,
+                SUM(kind = 'mint') AS mints,
+                SUM(kind = 'burn') AS burns,
+                COALESCE(SUM(CASE WHEN kind = 'mint' THEN amount END), 0.0) AS minted,
+                COALESCE(SUM(CASE WHEN kind = 'burn' THEN amount END), 0.0) AS burned
+            FROM asset_supply_events
+            WHERE asset_code = $1 AND asset_issuer = $2 AND occurred_at >= $3
+            GROUP BY day
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(since)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let snapshots = sqlx::query_as::<_, SupplySnapshotRow>(
+            r#"
+            SELECT date(snapshot_at) AS day, total_supply, circulating_supply
+            FROM asset_supply_snapshots
+            WHERE asset_code = $1 AND asset_issuer = $2 AND snapshot_at >= $3
+            ORDER BY snapshot_at, id
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(since)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut deltas: BTreeMap<String, DailySupplyDelta> = BTreeMap::new();
+        for row in issuance {
+            let delta = deltas.entry(row.day.clone()).or_default();
+            delta.mints = row.mints;
+            delta.burns = row.burns;
+            delta.minted = row.minted;
+            delta.burned = row.burned;
+            delta.net_issuance = row.minted - row.burned;
+        }
+        // Snapshots are ordered, so the last one of each day wins
+        for row in snapshots {
+            let delta = deltas.entry(row.day).or_default();
+            delta.total_supply = Some(row.total_supply);
+            delta.circulating_supply = Some(row.circulating_supply);
+        }
+
+        let mut previous: Option<(f64, f64)> = None;
+        let mut result = Vec::with_capacity(deltas.len());
+        for (date, mut delta) in deltas {
+            delta.date = date;
+            if let (Some(total), Some(circulating)) = (delta.total_supply, delta.circulating_supply)
+            {
+                if let Some((prev_total, prev_circulating)) = previous {
+                    delta.total_supply_change = Some(total - prev_total);
+                    delta.circulating_supply_change = Some(circulating - prev_circulating);
+                }
+                previous = Some((total, circulating));
+            }
+            result.push(delta);
+        }
+
+        Ok(result)
+    }
+
+    /// Supply of every asset issued by an anchor with issuance over the last
+    /// `days` days
+    pub async fn get_anchor_supply(&self, anchor_id: &str, days: i64) -> Result<AnchorSupply> {
+        let since = Utc::now() - ChronoDuration::days(days);
+
+        let assets = sqlx::query_as::<_, AssetSupplyOverview>(
+            r#"
+            SELECT
+                s.asset_code, s.asset_issuer, s.anchor_id, s.total_supply,
+                s.distribution_balance, s.circulating_supply, s.updated_at,
+                COALESCE(SUM(CASE WHEN e.kind = 'mint' THEN e.amount END), 0.0) AS minted,
+                COALESCE(SUM(CASE WHEN e.kind = 'burn' THEN e.amount END), 0.0) AS burned,
+                COALESCE(SUM(CASE WHEN e.kind = 'mint' THEN e.amount ELSE -e.amount END), 0.0)
+                    AS net_issuance
+            FROM asset_supply s
+            LEFT JOIN asset_supply_events e
+              ON e.asset_code = s.asset_code
+             AND e.asset_issuer = s.asset_issuer
+             AND e.occurred_at >= $2
+            WHERE s.anchor_id = $1
+            GROUP BY s.asset_code, s.asset_issuer
+            ORDER BY s.asset_code, s.asset_issuer
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(since)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(AnchorSupply {
+            anchor_id: anchor_id.to_string(),
+            window_days: days,
+            assets,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::stellar::{AssetAccounts, AssetBalances, AssetFlags};
+
+    const ISSUER: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+    const HOLDER: &str = "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+
+    fn payment(from: &str, to: &str, code: &str, amount: &str) -> Payment {
+        Payment {
+            id: "1".to_string(),
+            paging_token: "1".to_string(),
+            transaction_hash: "tx".to_string(),
+            source_account: from.to_string(),
+            destination: String::new(),
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: Some(ISSUER.to_string()),
+            amount: amount.to_string(),
+            created_at: "2026-10-18T12:00:00Z".to_string(),
+            operation_type: Some("payment".to_string()),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: None,
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_classify_payment() {
+        let mint = classify_payment(&payment(ISSUER, HOLDER, "USDC", "100"), "USDC", ISSUER);
+        assert_eq!(
+            mint,
+            Some(ClassifiedPayment {
+                kind: SupplyEventKind::Mint,
+                amount: 100.0,
+                counterparty: HOLDER.to_string(),
+            })
+        );
+
+        let burn = classify_payment(&payment(HOLDER, ISSUER, "USDC", "40.5"), "USDC", ISSUER);
+        assert_eq!(
+            burn.map(|b| (b.kind, b.amount)),
+            Some((SupplyEventKind::Burn, 40.5))
+        );
+
+        // Another asset of the same issuer
+        assert_eq!(
+            classify_payment(&payment(ISSUER, HOLDER, "EURC", "100"), "USDC", ISSUER),
+            None
+        );
+
+        // A path payment minting USDC that arrives as lumens
+        let mut path = payment(ISSUER, HOLDER, "USDC", "250");
+        path.asset_type = "native".to_string();
+        path.asset_code = None;
+        path.asset_issuer = None;
+        path.source_asset_code = Some("USDC".to_string());
+        path.source_asset_issuer = Some(ISSUER.to_string());
+        path.source_amount = Some("25".to_string());
+        let mint = classify_payment(&path, "USDC", ISSUER).unwrap();
+        assert_eq!((mint.kind, mint.amount), (SupplyEventKind::Mint, 25.0));
+    }
+
+    #[test]
+    fn test_total_supply() {
+        let asset = HorizonAsset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: "USDC".to_string(),
+            asset_issuer: ISSUER.to_string(),
+            num_claimable_balances: 1,
+            num_liquidity_pools: 1,
+            num_contracts: 0,
+            accounts: AssetAccounts {
+                authorized: 10,
+                authorized_to_maintain_liabilities: 1,
+                unauthorized: 1,
+            },
+            claimable_balances_amount: "5.0000000".to_string(),
+            liquidity_pools_amount: "20.0000000".to_string(),
+            contracts_amount: "0.0000000".to_string(),
+            balances: AssetBalances {
+                authorized: "1000.0000000".to_string(),
+                authorized_to_maintain_liabilities: "50.0000000".to_string(),
+                unauthorized: "25.0000000".to_string(),
+            },
+            flags: AssetFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+        };
+
+        assert_eq!(total_supply(&asset), 1100.0);
+    }
+}
@@ -2,21 +2,34 @@
 //!
 //! This service handles:
 //! - Connecting to Soroban RPC endpoints
+//! - Building `InvokeHostFunction` transactions as XDR and signing them
 //! - Submitting snapshot hashes on-chain
 //! - Retry logic with exponential backoff
 //! - Comprehensive error handling and logging
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use chrono::Utc;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::sync::Arc;
 use std::time::Duration;
+use stellar_xdr::curr as xdr;
 use tracing::{debug, error, info, warn};
+use xdr::{ReadXdr, WriteXdr};
+
+use crate::services::signer::{signer_from_env, transaction_hash, Signer};
+use crate::snapshot::merkle::hex_hash;
 
 const MAX_RETRIES: u32 = 3;
 const INITIAL_BACKOFF_MS: u64 = 1000;
 const BACKOFF_MULTIPLIER: u64 = 2;
 const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Inclusion fee offered on top of the simulated resource fee, in stroops
+const BASE_FEE: u32 = 100;
+/// Submitted transactions are valid for this long
+pub const TRANSACTION_TIMEOUT_SECS: u64 = 300;
 
 /// Configuration for the contract service
 #[derive(Clone, Debug)]
@@ -27,8 +40,6 @@ pub struct ContractConfig {
     pub contract_id: String,
     /// Network passphrase (e.g., "Test SDF Network ; September 2015" for testnet)
     pub network_passphrase: String,
-    /// Source account secret key for signing transactions
-    pub source_secret_key: String,
 }
 
 /// Service for interacting with the Soroban snapshot contract
@@ -36,6 +47,8 @@ pub struct ContractConfig {
 pub struct ContractService {
     client: Client,
     config: ContractConfig,
+    /// Key of the source account of submissions; reads need none
+    signer: Option<Arc<dyn Signer>>,
 }
 
 /// RPC request structure for Soroban
@@ -77,6 +90,42 @@ impl std::fmt::Display for RpcError {
 
 impl std::error::Error for RpcError {}
 
+/// `simulateTransaction` result
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulationResult {
+    /// Set if the invocation failed, e.g. because the contract panicked
+    #[serde(default)]
+    error: Option<String>,
+    /// `SorobanTransactionData` with the footprint and resources, base64 XDR
+    #[serde(default)]
+    transaction_data: Option<String>,
+    #[serde(default)]
+    min_resource_fee: Option<String>,
+    #[serde(default)]
+    results: Vec<SimulatedInvocation>,
+}
+
+/// Outcome of the simulated host function
+#[derive(Debug, Deserialize)]
+struct SimulatedInvocation {
+    /// `SorobanAuthorizationEntry`s the invocation needs, base64 XDR
+    #[serde(default)]
+    auth: Vec<String>,
+    /// Return value as `ScVal`, base64 XDR
+    xdr: String,
+}
+
+impl SimulationResult {
+    fn return_value(&self) -> Result<xdr::ScVal> {
+        let invocation = self
+            .results
+            .first()
+            .context("Simulation returned no result")?;
+        decode_xdr(&invocation.xdr)
+    }
+}
+
 /// Result of a successful snapshot submission
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct SubmissionResult {
@@ -91,7 +140,7 @@ pub struct SubmissionResult {
 }
 
 impl ContractService {
-    /// Create a new contract service instance
+    /// Create a new contract service instance that can only read the contract
     pub fn new(config: ContractConfig) -> Result<Self> {
         let client = Client::builder()
             .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
@@ -103,7 +152,30 @@ impl ContractService {
             config.rpc_url, config.contract_id
         );
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            signer: None,
+        })
+    }
+
+    /// Create a contract service that submits transactions signed by `signer`
+    pub fn with_signer(config: ContractConfig, signer: Arc<dyn Signer>) -> Result<Self> {
+        info!(
+            "Contract transactions are signed by {} ({})",
+            signer.account(),
+            signer.name()
+        );
+
+        Ok(Self {
+            signer: Some(signer),
+            ..Self::new(config)?
+        })
+    }
+
+    /// ID of the snapshot contract
+    pub fn contract_id(&self) -> &str {
+        &self.config.contract_id
     }
 
     /// Create from environment variables
@@ -115,11 +187,9 @@ impl ContractService {
                 .context("SNAPSHOT_CONTRACT_ID environment variable not set")?,
             network_passphrase: std::env::var("STELLAR_NETWORK_PASSPHRASE")
                 .unwrap_or_else(|_| "Test SDF Network ; September 2015".to_string()),
-            source_secret_key: std::env::var("STELLAR_SOURCE_SECRET_KEY")
-                .context("STELLAR_SOURCE_SECRET_KEY environment variable not set")?,
         };
 
-        Self::new(config)
+        Self::with_signer(config, signer_from_env()?)
     }
 
     /// Submit a snapshot hash to the on-chain contract
@@ -207,17 +277,35 @@ impl ContractService {
 
     /// Single attempt to submit snapshot (without retry logic)
     async fn try_submit_snapshot(&self, hash: [u8; 32], epoch: u64) -> Result<SubmissionResult> {
+        let signer = self
+            .signer
+            .as_deref()
+            .context("No transaction signer configured")?;
+
         // Step 1: Build the contract invocation
         debug!("Building contract invocation for epoch {}", epoch);
-        let invoke_args = self.build_invoke_args(hash, epoch)?;
+        let sequence = self.account_sequence(signer).await? + 1;
+        let operation = self.invoke_operation(
+            "submit_snapshot",
+            vec![
+                xdr::ScVal::Bytes(xdr::ScBytes(hash.to_vec().try_into()?)),
+                xdr::ScVal::U64(epoch),
+            ],
+        )?;
+        let transaction = self.build_transaction(signer.public_key(), sequence, operation)?;
 
         // Step 2: Simulate the transaction
         debug!("Simulating transaction");
-        let simulated = self.simulate_transaction(&invoke_args).await?;
+        let simulated = self.simulate_transaction(&transaction).await?;
+        if let Some(error) = &simulated.error {
+            bail!("Transaction simulation failed: {}", error);
+        }
 
         // Step 3: Prepare and sign the transaction
         debug!("Preparing and signing transaction");
-        let signed_xdr = self.prepare_and_sign_transaction(&simulated)?;
+        let signed_xdr = self
+            .prepare_and_sign_transaction(transaction, &simulated, signer)
+            .await?;
 
         // Step 4: Send the transaction
         debug!("Sending transaction to network");
@@ -230,95 +318,199 @@ impl ContractService {
         Ok(result)
     }
 
-    /// Build contract invocation arguments
-    fn build_invoke_args(&self, hash: [u8; 32], epoch: u64) -> Result<serde_json::Value> {
-        // Convert hash to hex for the contract call
-        let hash_hex = hex::encode(hash);
-
-        // Build Soroban contract invocation parameters
-        // Format: invoke contract_id submit_snapshot [hash_bytes, epoch_u64]
-        Ok(json!({
-            "contractId": self.config.contract_id,
-            "function": "submit_snapshot",
-            "args": [
-                {
-                    "type": "bytes",
-                    "value": hash_hex
-                },
-                {
-                    "type": "u64",
-                    "value": epoch.to_string()
-                }
-            ]
-        }))
+    /// Operation invoking `function` of the snapshot contract
+    fn invoke_operation(&self, function: &str, args: Vec<xdr::ScVal>) -> Result<xdr::Operation> {
+        let contract = stellar_strkey::Contract::from_string(&self.config.contract_id)
+            .map_err(|_| anyhow::anyhow!("Invalid contract ID: {}", self.config.contract_id))?;
+
+        Ok(xdr::Operation {
+            source_account: None,
+            body: xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+                host_function: xdr::HostFunction::InvokeContract(xdr::InvokeContractArgs {
+                    contract_address: xdr::ScAddress::Contract(xdr::Hash(contract.0)),
+                    function_name: xdr::ScSymbol(function.try_into()?),
+                    args: args.try_into()?,
+                }),
+                auth: xdr::VecM::default(),
+            }),
+        })
     }
 
-    /// Simulate the transaction to get resource estimates
-    async fn simulate_transaction(
+    /// Transaction of `source` with a single operation, before simulation
+    fn build_transaction(
         &self,
-        invoke_args: &serde_json::Value,
-    ) -> Result<serde_json::Value> {
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: "simulateTransaction".to_string(),
-            params: json!({
-                "transaction": invoke_args
+        source: [u8; 32],
+        sequence: i64,
+        operation: xdr::Operation,
+    ) -> Result<xdr::Transaction> {
+        let max_time = Utc::now().timestamp().max(0) as u64 + TRANSACTION_TIMEOUT_SECS;
+
+        Ok(xdr::Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(source)),
+            fee: BASE_FEE,
+            seq_num: xdr::SequenceNumber(sequence),
+            cond: xdr::Preconditions::Time(xdr::TimeBounds {
+                min_time: xdr::TimePoint(0),
+                max_time: xdr::TimePoint(max_time),
             }),
-        };
-
-        let response = self
-            .client
-            .post(&self.config.rpc_url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send simulation request")?;
-
-        let status = response.status();
-        let body: JsonRpcResponse<serde_json::Value> = response
-            .json()
-            .await
-            .context("Failed to parse simulation response")?;
+            memo: xdr::Memo::None,
+            operations: vec![operation].try_into()?,
+            ext: xdr::TransactionExt::V0,
+        })
+    }
 
-        if let Some(error) = body.error {
-            return Err(anyhow::anyhow!(
-                "Transaction simulation failed: {} (code: {})",
-                error.message,
-                error.code
-            ));
+    /// Current sequence number of the source account
+    async fn account_sequence(&self, signer: &dyn Signer) -> Result<i64> {
+        let key = xdr::LedgerKey::Account(xdr::LedgerKeyAccount {
+            account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(
+                signer.public_key(),
+            ))),
+        });
+        let result = self
+            .rpc_request("getLedgerEntries", json!({ "keys": [encode_xdr(&key)?] }))
+            .await?;
+
+        let entry = result
+            .get("entries")
+            .and_then(|entries| entries.as_array())
+            .and_then(|entries| entries.first())
+            .and_then(|entry| entry.get("xdr"))
+            .and_then(|xdr| xdr.as_str())
+            .with_context(|| format!("Source account {} not found", signer.account()))?;
+
+        match decode_xdr::<xdr::LedgerEntryData>(entry)? {
+            xdr::LedgerEntryData::Account(account) => Ok(account.seq_num.0),
+            _ => bail!("Ledger entry of {} is not an account", signer.account()),
         }
+    }
 
-        body.result
-            .ok_or_else(|| anyhow::anyhow!("No simulation result returned (status: {})", status))
+    /// Simulate the transaction to get its footprint, resource fee and
+    /// return value
+    async fn simulate_transaction(
+        &self,
+        transaction: &xdr::Transaction,
+    ) -> Result<SimulationResult> {
+        let envelope = transaction_envelope(transaction.clone(), Vec::new())?;
+        let result = self
+            .rpc_request("simulateTransaction", json!({ "transaction": envelope }))
+            .await?;
+
+        serde_json::from_value(result).context("Failed to parse simulation result")
     }
 
-    /// Prepare and sign the transaction
-    fn prepare_and_sign_transaction(&self, _simulated: &serde_json::Value) -> Result<String> {
-        // In a real implementation, this would:
-        // 1. Extract the transaction envelope from simulation
-        // 2. Set appropriate fees and sequence number
-        // 3. Sign with the source account's secret key
-        // 4. Return the signed XDR
+    /// Simulate a read-only call of a contract function
+    ///
+    /// The simulation is never submitted, so it does not need to be signed
+    /// and the source account defaults to the all-zero key when no signer is
+    /// configured.
+    async fn simulate_call(
+        &self,
+        function: &str,
+        args: Vec<xdr::ScVal>,
+    ) -> Result<SimulationResult> {
+        let source = self
+            .signer
+            .as_ref()
+            .map(|signer| signer.public_key())
+            .unwrap_or_default();
+        let transaction =
+            self.build_transaction(source, 0, self.invoke_operation(function, args)?)?;
+
+        self.simulate_transaction(&transaction).await
+    }
 
-        // For now, return a placeholder that would need stellar-sdk integration
-        // TODO: Integrate stellar-sdk for proper transaction signing
+    /// Apply the simulated footprint, resource fee and authorizations to the
+    /// transaction and sign it with the source key
+    async fn prepare_and_sign_transaction(
+        &self,
+        mut transaction: xdr::Transaction,
+        simulated: &SimulationResult,
+        signer: &dyn Signer,
+    ) -> Result<String> {
+        let transaction_data: xdr::SorobanTransactionData = decode_xdr(
+            simulated
+                .transaction_data
+                .as_deref()
+                .context("Simulation returned no transaction data")?,
+        )?;
+        let resource_fee: u32 = simulated
+            .min_resource_fee
+            .as_deref()
+            .unwrap_or("0")
+            .parse()
+            .context("Invalid minimum resource fee")?;
+
+        let auth = simulated
+            .results
+            .first()
+            .map(|invocation| invocation.auth.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| decode_xdr::<xdr::SorobanAuthorizationEntry>(entry))
+            .collect::<Result<Vec<_>>>()?;
+        // Only the source account can authorize, by signing the transaction
+        if auth
+            .iter()
+            .any(|entry| !matches!(entry.credentials, xdr::SorobanCredentials::SourceAccount))
+        {
+            bail!("Contract invocation requires authorization by another account");
+        }
 
-        warn!("Transaction signing not yet implemented - requires stellar-sdk integration");
-        Err(anyhow::anyhow!(
-            "Transaction signing requires stellar-sdk library integration"
-        ))
+        let mut operations = transaction.operations.to_vec();
+        for operation in &mut operations {
+            if let xdr::OperationBody::InvokeHostFunction(invoke) = &mut operation.body {
+                invoke.auth = auth.clone().try_into()?;
+            }
+        }
+        transaction.operations = operations.try_into()?;
+        transaction.fee = BASE_FEE
+            .checked_add(resource_fee)
+            .context("Transaction fee overflows")?;
+        transaction.ext = xdr::TransactionExt::V1(transaction_data);
+
+        let hash = transaction_hash(&transaction, &self.config.network_passphrase)?;
+        let signature = signer.sign_decorated(&hash).await?;
+        transaction_envelope(transaction, vec![signature])
     }
 
     /// Send the signed transaction to the network
     async fn send_transaction(&self, signed_xdr: &str) -> Result<String> {
+        let result = self
+            .rpc_request("sendTransaction", json!({ "transaction": signed_xdr }))
+            .await?;
+
+        // Extract transaction hash from result
+        let tx_hash = result
+            .get("hash")
+            .and_then(|h| h.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Transaction hash not found in response"))?
+            .to_string();
+
+        match result.get("status").and_then(|s| s.as_str()) {
+            Some("PENDING") | Some("DUPLICATE") => Ok(tx_hash),
+            Some("TRY_AGAIN_LATER") => bail!("Transaction {} was not accepted yet", tx_hash),
+            status => bail!(
+                "Transaction submission failed with status {}: {}",
+                status.unwrap_or("unknown"),
+                result
+                    .get("errorResultXdr")
+                    .and_then(|x| x.as_str())
+                    .unwrap_or("no error result")
+            ),
+        }
+    }
+
+    /// Call a Soroban RPC method and return its result
+    async fn rpc_request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
         let request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id: 1,
-            method: "sendTransaction".to_string(),
-            params: json!({
-                "transaction": signed_xdr
-            }),
+            method: method.to_string(),
+            params,
         };
 
         let response = self
@@ -327,33 +519,25 @@ impl ContractService {
             .json(&request)
             .send()
             .await
-            .context("Failed to send transaction")?;
+            .with_context(|| format!("Failed to send {} request", method))?;
 
+        let status = response.status();
         let body: JsonRpcResponse<serde_json::Value> = response
             .json()
             .await
-            .context("Failed to parse send transaction response")?;
+            .with_context(|| format!("Failed to parse {} response", method))?;
 
         if let Some(error) = body.error {
-            return Err(anyhow::anyhow!(
-                "Transaction submission failed: {} (code: {})",
+            bail!(
+                "{} failed: {} (code: {})",
+                method,
                 error.message,
                 error.code
-            ));
+            );
         }
 
-        let result = body
-            .result
-            .ok_or_else(|| anyhow::anyhow!("No transaction hash returned"))?;
-
-        // Extract transaction hash from result
-        let tx_hash = result
-            .get("hash")
-            .and_then(|h| h.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Transaction hash not found in response"))?
-            .to_string();
-
-        Ok(tx_hash)
+        body.result
+            .ok_or_else(|| anyhow::anyhow!("No {} result returned (status: {})", method, status))
     }
 
     /// Wait for transaction to be confirmed and return the result
@@ -411,10 +595,14 @@ impl ContractService {
                             .ok_or_else(|| anyhow::anyhow!("Ledger number not found"))?;
 
                         // Get timestamp from contract return value
-                        let timestamp = result
-                            .get("returnValue")
-                            .and_then(|rv| rv.as_u64())
-                            .unwrap_or(0);
+                        let timestamp = match result
+                            .get("resultMetaXdr")
+                            .and_then(|meta| meta.as_str())
+                            .and_then(contract_return_value)
+                        {
+                            Some(xdr::ScVal::U64(timestamp)) => timestamp,
+                            _ => 0,
+                        };
 
                         return Ok(SubmissionResult {
                             transaction_hash: tx_hash.to_string(),
@@ -487,144 +675,231 @@ impl ContractService {
             return Err(anyhow::anyhow!("Hash must be exactly 32 bytes"));
         }
 
-        let mut hash_array = [0u8; 32];
-        hash_array.copy_from_slice(&hash_bytes);
-
         // Call the contract's verify_snapshot function
-        let verify_args = json!({
-            "contractId": self.config.contract_id,
-            "function": "verify_snapshot",
-            "args": [
-                {
-                    "type": "bytes",
-                    "value": hash
-                }
-            ]
-        });
-
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: "simulateTransaction".to_string(),
-            params: json!({
-                "transaction": verify_args
-            }),
-        };
-
-        let response = self
-            .client
-            .post(&self.config.rpc_url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send verification request")?;
-
-        let body: JsonRpcResponse<serde_json::Value> = response
-            .json()
-            .await
-            .context("Failed to parse verification response")?;
-
-        if let Some(error) = body.error {
-            warn!("Verification request failed: {}", error.message);
+        let simulated = self
+            .simulate_call(
+                "verify_snapshot",
+                vec![xdr::ScVal::Bytes(xdr::ScBytes(hash_bytes.try_into()?))],
+            )
+            .await?;
+
+        if let Some(error) = simulated.error {
+            warn!("Verification request failed: {}", error);
             return Ok(false);
         }
 
-        if let Some(result) = body.result {
-            // Extract the return value from the simulation
-            let return_value = result
-                .get("returnValue")
-                .and_then(|rv| rv.as_bool())
-                .unwrap_or(false);
-
-            debug!("Verification result for epoch {}: {}", epoch, return_value);
-            Ok(return_value)
-        } else {
-            Ok(false)
-        }
+        // Extract the return value from the simulation
+        let return_value = matches!(simulated.return_value()?, xdr::ScVal::Bool(true));
+
+        debug!("Verification result for epoch {}: {}", epoch, return_value);
+        Ok(return_value)
     }
 
     /// Get snapshot data for a specific epoch from the contract
     pub async fn get_snapshot_by_epoch(&self, epoch: u64) -> Result<Option<String>> {
         debug!("Getting snapshot for epoch {}", epoch);
 
-        let get_args = json!({
-            "contractId": self.config.contract_id,
-            "function": "get_snapshot",
-            "args": [
-                {
-                    "type": "u64",
-                    "value": epoch.to_string()
-                }
-            ]
-        });
-
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: "simulateTransaction".to_string(),
-            params: json!({
-                "transaction": get_args
-            }),
-        };
-
-        let response = self
-            .client
-            .post(&self.config.rpc_url)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send get snapshot request")?;
-
-        let body: JsonRpcResponse<serde_json::Value> = response
-            .json()
-            .await
-            .context("Failed to parse get snapshot response")?;
-
-        if let Some(error) = body.error {
-            if error.message.contains("not found") {
+        let simulated = self
+            .simulate_call("get_snapshot", vec![xdr::ScVal::U64(epoch)])
+            .await?;
+
+        if let Some(error) = simulated.error {
+            // The contract panics for unknown epochs. Builds without debug
+            // info drop the panic message, leaving only the WASM trap.
+            if error.contains("No snapshot found")
+                || error.contains("not found")
+                || error.contains("Error(WasmVm, InvalidAction)")
+            {
                 return Ok(None);
             }
-            return Err(anyhow::anyhow!("Get snapshot failed: {}", error.message));
+            return Err(anyhow::anyhow!("Get snapshot failed: {}", error));
         }
 
-        if let Some(result) = body.result {
-            let hash_hex = result
-                .get("returnValue")
-                .and_then(|rv| rv.as_str())
-                .map(|s| s.to_string());
+        match simulated.return_value()? {
+            xdr::ScVal::Bytes(hash) => Ok(Some(hex::encode(hash.as_slice()))),
+            other => Err(anyhow::anyhow!(
+                "Unexpected get_snapshot result: {:?}",
+                other
+            )),
+        }
+    }
 
-            Ok(hash_hex)
-        } else {
-            Ok(None)
+    /// Get the snapshot root submitted for an epoch as bytes
+    pub async fn get_snapshot_root(&self, epoch: u64) -> Result<Option<[u8; 32]>> {
+        match self.get_snapshot_by_epoch(epoch).await? {
+            Some(hash_hex) => hex_hash::parse(&hash_hex)
+                .map(Some)
+                .map_err(|e| anyhow::anyhow!("Invalid snapshot root for epoch {}: {}", epoch, e)),
+            None => Ok(None),
         }
     }
 }
 
+/// Base64 XDR of a transaction envelope with the given signatures
+fn transaction_envelope(
+    transaction: xdr::Transaction,
+    signatures: Vec<xdr::DecoratedSignature>,
+) -> Result<String> {
+    encode_xdr(&xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+        tx: transaction,
+        signatures: signatures.try_into()?,
+    }))
+}
+
+/// Return value of a contract invocation, from its `TransactionMeta`
+fn contract_return_value(meta: &str) -> Option<xdr::ScVal> {
+    match decode_xdr::<xdr::TransactionMeta>(meta).ok()? {
+        xdr::TransactionMeta::V3(meta) => meta.soroban_meta.map(|meta| meta.return_value),
+        _ => None,
+    }
+}
+
+fn encode_xdr<T: WriteXdr>(value: &T) -> Result<String> {
+    Ok(base64::engine::general_purpose::STANDARD.encode(value.to_xdr(xdr::Limits::none())?))
+}
+
+fn decode_xdr<T: ReadXdr>(value: &str) -> Result<T> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .context("Invalid base64 XDR")?;
+    Ok(T::from_xdr(bytes, xdr::Limits::none())?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::signer::InMemorySigner;
 
-    #[test]
-    fn test_build_invoke_args() {
-        let config = ContractConfig {
-            rpc_url: "https://soroban-testnet.stellar.org".to_string(),
-            contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
-            network_passphrase: "Test SDF Network ; September 2015".to_string(),
-            source_secret_key: "S...".to_string(),
-        };
+    const CONTRACT: [u8; 32] = [7u8; 32];
+    const NETWORK: &str = "Test SDF Network ; September 2015";
 
-        let service = ContractService::new(config).unwrap();
-        let hash = [0u8; 32];
-        let epoch = 123;
+    fn config() -> ContractConfig {
+        ContractConfig {
+            rpc_url: "https://soroban-testnet.stellar.org".to_string(),
+            contract_id: stellar_strkey::Contract(CONTRACT).to_string(),
+            network_passphrase: NETWORK.to_string(),
+        }
+    }
 
-        let args = service.build_invoke_args(hash, epoch).unwrap();
+    fn invocation(transaction: &xdr::Transaction) -> (xdr::InvokeContractArgs, usize) {
+        match &transaction.operations[0].body {
+            xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+                host_function: xdr::HostFunction::InvokeContract(args),
+                auth,
+            }) => (args.clone(), auth.len()),
+            other => panic!("unexpected operation {:?}", other),
+        }
+    }
 
+    #[test]
+    fn test_invoke_operation() {
+        let service = ContractService::new(config()).unwrap();
+        let operation = service
+            .invoke_operation(
+                "submit_snapshot",
+                vec![
+                    xdr::ScVal::Bytes(xdr::ScBytes([1u8; 32].to_vec().try_into().unwrap())),
+                    xdr::ScVal::U64(123),
+                ],
+            )
+            .unwrap();
+        let transaction = service.build_transaction([2u8; 32], 8, operation).unwrap();
+
+        let (args, _) = invocation(&transaction);
         assert_eq!(
-            args["contractId"],
-            "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA"
+            args.contract_address,
+            xdr::ScAddress::Contract(xdr::Hash(CONTRACT))
         );
-        assert_eq!(args["function"], "submit_snapshot");
-        assert!(args["args"].is_array());
+        assert_eq!(args.function_name.0.to_string(), "submit_snapshot");
+        assert_eq!(args.args[1], xdr::ScVal::U64(123));
+        assert_eq!(transaction.seq_num, xdr::SequenceNumber(8));
+        assert_eq!(transaction.fee, BASE_FEE);
+
+        // Contract IDs are checked when the invocation is built
+        assert!(ContractService::new(ContractConfig {
+            contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+            ..config()
+        })
+        .unwrap()
+        .invoke_operation("submit_snapshot", Vec::new())
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prepare_and_sign_transaction_applies_the_simulation() {
+        let signer = InMemorySigner::from_seed([9u8; 32]).unwrap();
+        let service = ContractService::new(config()).unwrap();
+        let operation = service
+            .invoke_operation("submit_snapshot", vec![xdr::ScVal::U64(1)])
+            .unwrap();
+        let transaction = service
+            .build_transaction(signer.public_key(), 5, operation)
+            .unwrap();
+
+        let transaction_data = xdr::SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: xdr::SorobanResources {
+                footprint: xdr::LedgerFootprint {
+                    read_only: xdr::VecM::default(),
+                    read_write: xdr::VecM::default(),
+                },
+                instructions: 1000,
+                read_bytes: 10,
+                write_bytes: 10,
+            },
+            resource_fee: 5000,
+        };
+        let source_auth = xdr::SorobanAuthorizationEntry {
+            credentials: xdr::SorobanCredentials::SourceAccount,
+            root_invocation: xdr::SorobanAuthorizedInvocation {
+                function: xdr::SorobanAuthorizedFunction::ContractFn(invocation(&transaction).0),
+                sub_invocations: xdr::VecM::default(),
+            },
+        };
+        let mut simulated = SimulationResult {
+            error: None,
+            transaction_data: Some(encode_xdr(&transaction_data).unwrap()),
+            min_resource_fee: Some("5000".to_string()),
+            results: vec![SimulatedInvocation {
+                auth: vec![encode_xdr(&source_auth).unwrap()],
+                xdr: encode_xdr(&xdr::ScVal::Void).unwrap(),
+            }],
+        };
+
+        let signed = service
+            .prepare_and_sign_transaction(transaction.clone(), &simulated, &signer)
+            .await
+            .unwrap();
+        let xdr::TransactionEnvelope::Tx(envelope) = decode_xdr(&signed).unwrap() else {
+            panic!("expected a v1 envelope");
+        };
+        assert_eq!(envelope.tx.fee, BASE_FEE + 5000);
+        assert_eq!(envelope.tx.ext, xdr::TransactionExt::V1(transaction_data));
+        assert_eq!(invocation(&envelope.tx).1, 1);
+        let hash = transaction_hash(&envelope.tx, NETWORK).unwrap();
+        assert!(crate::services::signer::is_signed_by(
+            &signer.public_key(),
+            &hash,
+            &envelope.signatures
+        ));
+
+        // Signatures of other accounts cannot be provided
+        let other_auth = xdr::SorobanAuthorizationEntry {
+            credentials: xdr::SorobanCredentials::Address(xdr::SorobanAddressCredentials {
+                address: xdr::ScAddress::Account(xdr::AccountId(
+                    xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256([3u8; 32])),
+                )),
+                nonce: 1,
+                signature_expiration_ledger: 100,
+                signature: xdr::ScVal::Void,
+            }),
+            ..source_auth
+        };
+        simulated.results[0].auth = vec![encode_xdr(&other_auth).unwrap()];
+        assert!(service
+            .prepare_and_sign_transaction(transaction, &simulated, &signer)
+            .await
+            .is_err());
     }
 
     #[tokio::test]
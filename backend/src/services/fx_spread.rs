@@ -0,0 +1,382 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::models::asset::{AssetId, CorridorKey};
+use crate::rpc::{Payment, StellarRpcClient};
+use crate::services::price_feed::PriceFeedClient;
+
+/// Granularity of spread time series
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpreadInterval {
+    Hour,
+    #[default]
+    Day,
+}
+
+impl SpreadInterval {
+    /// Length of the `observed_at` prefix identifying a bucket
+    fn prefix_len(&self) -> i64 {
+        match self {
+            Self::Hour => 13,
+            Self::Day => 10,
+        }
+    }
+}
+
+/// Spread of a single cross-asset path payment against the reference rate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxSpreadObservation {
+    pub payment_id: String,
+    pub transaction_hash: String,
+    pub corridor_key: String,
+    pub source_asset_code: String,
+    pub source_asset_issuer: String,
+    pub destination_asset_code: String,
+    pub destination_asset_issuer: String,
+    pub source_amount: f64,
+    pub destination_amount: f64,
+    pub effective_rate: f64,
+    pub reference_rate: f64,
+    pub spread_bps: f64,
+    pub source_amount_usd: f64,
+    pub observed_at: DateTime<Utc>,
+}
+
+/// Volume-weighted spread for one corridor and time bucket
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FxSpreadPoint {
+    pub corridor_key: String,
+    pub bucket: String,
+    pub sample_count: i64,
+    pub volume_usd: f64,
+    pub avg_spread_bps: f64,
+    pub min_spread_bps: f64,
+    pub max_spread_bps: f64,
+}
+
+pub struct FxSpreadService {
+    pool: Pool<Sqlite>,
+    rpc_client: Arc<StellarRpcClient>,
+    price_feed: Arc<PriceFeedClient>,
+}
+
+impl FxSpreadService {
+    pub fn new(
+        pool: Pool<Sqlite>,
+        rpc_client: Arc<StellarRpcClient>,
+        price_feed: Arc<PriceFeedClient>,
+    ) -> Self {
+        Self {
+            pool,
+            rpc_client,
+            price_feed,
+        }
+    }
+
+    /// Fetch recent payments and record spreads for the cross-asset path payments
+    pub async fn sync_spreads(&self, limit: u32) -> Result<usize> {
+        let payments = self
+            .rpc_client
+            .fetch_payments(limit, None)
+            .await
+            .context("Failed to fetch payments for FX spread sync")?;
+        self.record_payments(&payments).await
+    }
+
+    /// Record spreads for path payments whose assets both have a USD price.
+    ///
+    /// Returns the number of new observations; payments seen before are skipped.
+    pub async fn record_payments(&self, payments: &[Payment]) -> Result<usize> {
+        let assets: Vec<String> = payments
+            .iter()
+            .filter_map(payment_corridor)
+            .flat_map(|corridor| [corridor.source.key(), corridor.destination.key()])
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if assets.is_empty() {
+            return Ok(0);
+        }
+
+        let prices = self.price_feed.get_prices(&assets).await;
+
+        let mut recorded = 0;
+        for payment in payments {
+            let Some(observation) = observation_from_payment(payment, &prices) else {
+                continue;
+            };
+            if self.store_observation(&observation).await? {
+                recorded += 1;
+            }
+        }
+
+        if recorded > 0 {
+            info!("Recorded {} FX spread observations", recorded);
+        } else {
+            debug!("No new FX spread observations");
+        }
+        Ok(recorded)
+    }
+
+    async fn store_observation(&self, observation: &FxSpreadObservation) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO fx_spread_observations (
+                payment_id, transaction_hash, corridor_key,
+                source_asset_code, source_asset_issuer,
+                destination_asset_code, destination_asset_issuer,
+                source_amount, destination_amount, effective_rate, reference_rate,
+                spread_bps, source_amount_usd, observed_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&observation.payment_id)
+        .bind(&observation.transaction_hash)
+        .bind(&observation.corridor_key)
+        .bind(&observation.source_asset_code)
+        .bind(&observation.source_asset_issuer)
+        .bind(&observation.destination_asset_code)
+        .bind(&observation.destination_asset_issuer)
+        .bind(observation.source_amount)
+        .bind(observation.destination_amount)
+        .bind(observation.effective_rate)
+        .bind(observation.reference_rate)
+        .bind(observation.spread_bps)
+        .bind(observation.source_amount_usd)
+        .bind(observation.observed_at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to store FX spread observation")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Spread time series for a directional corridor ("SOURCE->DESTINATION")
+    pub async fn get_corridor_spreads(
+        &self,
+        corridor_key: &CorridorKey,
+        since: DateTime<Utc>,
+        interval: SpreadInterval,
+    ) -> Result<Vec<FxSpreadPoint>> {
+        let points = sqlx::query_as::<_, FxSpreadPoint>(
+            r#"
+            SELECT
+                corridor_key,
+                substr(observed_at, 1, $3) AS bucket,
+                COUNT(*) AS sample_count,
+                SUM(source_amount_usd) AS volume_usd,
+                SUM(spread_bps * source_amount_usd) / NULLIF(SUM(source_amount_usd), 0) AS avg_spread_bps,
+                MIN(spread_bps) AS min_spread_bps,
+                MAX(spread_bps) AS max_spread_bps
+            FROM fx_spread_observations
+            WHERE corridor_key = $1 AND observed_at >= $2
+            GROUP BY corridor_key, bucket
+            ORDER BY bucket ASC
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(since.to_rfc3339())
+        .bind(interval.prefix_len())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to query corridor FX spreads")?;
+
+        Ok(points)
+    }
+
+    /// Spread time series for every corridor touching an asset issued by the anchor
+    pub async fn get_anchor_spreads(
+        &self,
+        anchor_id: &str,
+        since: DateTime<Utc>,
+        interval: SpreadInterval,
+    ) -> Result<Vec<FxSpreadPoint>> {
+        let points = sqlx::query_as::<_, FxSpreadPoint>(
+            r#"
+            SELECT
+                o.corridor_key,
+                substr(o.observed_at, 1, $3) AS bucket,
+                COUNT(*) AS sample_count,
+                SUM(o.source_amount_usd) AS volume_usd,
+                SUM(o.spread_bps * o.source_amount_usd) / NULLIF(SUM(o.source_amount_usd), 0) AS avg_spread_bps,
+                MIN(o.spread_bps) AS min_spread_bps,
+                MAX(o.spread_bps) AS max_spread_bps
+            FROM fx_spread_observations o
+            WHERE o.observed_at >= $2
+              AND EXISTS (
+                  SELECT 1 FROM assets a
+                  WHERE a.anchor_id = $1
+                    AND ((a.asset_code = o.source_asset_code AND a.asset_issuer = o.source_asset_issuer)
+                      OR (a.asset_code = o.destination_asset_code AND a.asset_issuer = o.destination_asset_issuer))
+              )
+            GROUP BY o.corridor_key, bucket
+            ORDER BY bucket ASC, o.corridor_key ASC
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(since.to_rfc3339())
+        .bind(interval.prefix_len())
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to query anchor FX spreads")?;
+
+        Ok(points)
+    }
+}
+
+/// Source and destination assets of a cross-asset path payment
+fn payment_corridor(payment: &Payment) -> Option<CorridorKey> {
+    match payment.operation_type.as_deref() {
+        Some("path_payment_strict_send") | Some("path_payment_strict_receive") => {}
+        _ => return None,
+    }
+
+    let source = AssetId::from_horizon(
+        payment.source_asset_type.as_deref()?,
+        payment.source_asset_code.as_deref(),
+        payment.source_asset_issuer.as_deref(),
+    );
+    let destination = AssetId::from_horizon(
+        &payment.asset_type,
+        payment.asset_code.as_deref(),
+        payment.asset_issuer.as_deref(),
+    );
+    let (source, destination) = match (source, destination) {
+        (Ok(source), Ok(destination)) => (source, destination),
+        (Err(e), _) | (_, Err(e)) => {
+            warn!("Skipping payment {} with invalid asset: {}", payment.id, e);
+            return None;
+        }
+    };
+
+    (source != destination).then(|| CorridorKey::new(source, destination))
+}
+
+/// Spread in basis points of an effective rate against a reference rate.
+///
+/// Positive values mean the sender received less than the reference rate,
+/// i.e. the markup paid for the conversion.
+pub fn spread_bps(effective_rate: f64, reference_rate: f64) -> f64 {
+    (reference_rate - effective_rate) / reference_rate * 10_000.0
+}
+
+/// Build an observation from a path payment given USD prices keyed by asset
+pub fn observation_from_payment(
+    payment: &Payment,
+    prices_usd: &HashMap<String, f64>,
+) -> Option<FxSpreadObservation> {
+    let corridor = payment_corridor(payment)?;
+    let source_amount: f64 = payment.source_amount.as_deref()?.parse().ok()?;
+    let destination_amount: f64 = payment.amount.parse().ok()?;
+    let source_price = *prices_usd.get(&corridor.source.key())?;
+    let destination_price = *prices_usd.get(&corridor.destination.key())?;
+
+    if source_amount <= 0.0
+        || destination_amount <= 0.0
+        || source_price <= 0.0
+        || destination_price <= 0.0
+    {
+        return None;
+    }
+
+    let observed_at = DateTime::parse_from_rfc3339(&payment.created_at)
+        .ok()?
+        .with_timezone(&Utc);
+    let effective_rate = destination_amount / source_amount;
+    let reference_rate = source_price / destination_price;
+    let (source_code, source_issuer) = corridor.source.classic_parts().ok()?;
+    let (destination_code, destination_issuer) = corridor.destination.classic_parts().ok()?;
+
+    Some(FxSpreadObservation {
+        payment_id: payment.id.clone(),
+        transaction_hash: payment.transaction_hash.clone(),
+        corridor_key: corridor.to_string(),
+        source_asset_code: source_code.to_string(),
+        source_asset_issuer: source_issuer.to_string(),
+        destination_asset_code: destination_code.to_string(),
+        destination_asset_issuer: destination_issuer.to_string(),
+        source_amount,
+        destination_amount,
+        effective_rate,
+        reference_rate,
+        spread_bps: spread_bps(effective_rate, reference_rate),
+        source_amount_usd: source_amount * source_price,
+        observed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn path_payment(source_amount: &str, amount: &str) -> Payment {
+        Payment {
+            id: "op1".to_string(),
+            paging_token: "op1".to_string(),
+            transaction_hash: "tx1".to_string(),
+            source_account: "GSOURCE".to_string(),
+            destination: "GDEST".to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: amount.to_string(),
+            created_at: "2026-01-22T10:15:00Z".to_string(),
+            operation_type: Some("path_payment_strict_send".to_string()),
+            source_asset_type: Some("credit_alphanum4".to_string()),
+            source_asset_code: Some("USDC".to_string()),
+            source_asset_issuer: Some(USDC.to_string()),
+            source_amount: Some(source_amount.to_string()),
+            from: None,
+            to: None,
+        }
+    }
+
+    fn prices() -> HashMap<String, f64> {
+        HashMap::from([
+            (format!("USDC:{}", USDC), 1.0),
+            ("XLM:native".to_string(), 0.10),
+        ])
+    }
+
+    #[test]
+    fn test_spread_bps() {
+        assert_eq!(spread_bps(10.0, 10.0), 0.0);
+        assert!((spread_bps(9.9, 10.0) - 100.0).abs() < 1e-9);
+        assert!(spread_bps(10.1, 10.0) < 0.0);
+    }
+
+    #[test]
+    fn test_observation_from_path_payment() {
+        // 100 USDC at $1 should buy 1000 XLM at $0.10; 990 is a 1% markup
+        let observation = observation_from_payment(&path_payment("100.0", "990.0"), &prices())
+            .expect("path payment with known prices");
+
+        assert_eq!(
+            observation.corridor_key,
+            format!("USDC:{}->XLM:native", USDC)
+        );
+        assert!((observation.reference_rate - 10.0).abs() < 1e-9);
+        assert!((observation.effective_rate - 9.9).abs() < 1e-9);
+        assert!((observation.spread_bps - 100.0).abs() < 1e-6);
+        assert!((observation.source_amount_usd - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_observation_requires_cross_asset_path_payment_and_prices() {
+        let mut regular = path_payment("100.0", "990.0");
+        regular.operation_type = Some("payment".to_string());
+        assert!(observation_from_payment(&regular, &prices()).is_none());
+
+        let unpriced = HashMap::from([("XLM:native".to_string(), 0.10)]);
+        assert!(observation_from_payment(&path_payment("100.0", "990.0"), &unpriced).is_none());
+        assert!(observation_from_payment(&path_payment("0", "990.0"), &prices()).is_none());
+    }
+}
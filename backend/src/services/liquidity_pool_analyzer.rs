@@ -2,8 +2,9 @@ use anyhow::Result;
 use chrono::Utc;
 use sqlx::{Pool, Sqlite};
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::models::asset::AssetId;
 use crate::models::{LiquidityPool, LiquidityPoolSnapshot, LiquidityPoolStats};
 use crate::rpc::StellarRpcClient;
 
@@ -28,8 +29,17 @@ impl LiquidityPoolAnalyzer {
         let mut count = 0u64;
 
         for hp in &horizon_pools {
-            let (asset_a_code, asset_a_issuer) = Self::parse_asset(&hp.reserves[0].asset);
-            let (asset_b_code, asset_b_issuer) = Self::parse_asset(&hp.reserves[1].asset);
+            let ((asset_a_code, asset_a_issuer), (asset_b_code, asset_b_issuer)) = match (
+                Self::reserve_columns(&hp.reserves[0].asset),
+                Self::reserve_columns(&hp.reserves[1].asset),
+            ) {
+                (Ok(a), Ok(b)) => (a, b),
+                (Err(e), _) | (_, Err(e)) => {
+                    // One unparseable pool shouldn't stop the rest from syncing
+                    warn!("Skipping pool {} with an invalid reserve asset: {}", hp.id, e);
+                    continue;
+                }
+            };
             let reserve_a: f64 = hp.reserves[0].amount.parse().unwrap_or(0.0);
             let reserve_b: f64 = hp.reserves[1].amount.parse().unwrap_or(0.0);
 
@@ -325,14 +335,11 @@ impl LiquidityPoolAnalyzer {
         }
     }
 
-    /// Parse a Horizon asset string ("native" or "CODE:ISSUER")
-    fn parse_asset(asset_str: &str) -> (String, Option<String>) {
-        if asset_str == "native" {
-            ("XLM".to_string(), None)
-        } else if let Some((code, issuer)) = asset_str.split_once(':') {
-            (code.to_string(), Some(issuer.to_string()))
-        } else {
-            (asset_str.to_string(), None)
-        }
+    /// Code and issuer columns for a Horizon reserve asset ("native" or "CODE:ISSUER").
+    /// Lumens are stored as code "XLM" with no issuer.
+    fn reserve_columns(asset_str: &str) -> Result<(String, Option<String>)> {
+        let asset: AssetId = asset_str.parse()?;
+        let (code, issuer) = asset.classic_parts()?;
+        Ok((code.to_string(), (!asset.is_native()).then(|| issuer.to_string())))
     }
 }
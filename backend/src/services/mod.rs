@@ -1,12 +1,30 @@
 pub mod account_merge_detector;
 pub mod aggregation;
 pub mod analytics;
+pub mod anchor_fees;
+pub mod anchor_registry;
+pub mod anomaly_detector;
+pub mod asset_supply;
 pub mod contract;
 pub mod fee_bump_tracker;
+pub mod fx_spread;
 pub mod indexing;
 pub mod liquidity_pool_analyzer;
 pub mod price_feed;
+pub mod quote_comparison;
+pub mod quote_simulator;
+pub mod rollup;
+pub mod route_finder;
 pub mod snapshot;
+pub mod snapshot_scheduler;
+pub mod sep10_client;
+pub mod sep10_server;
+pub mod sep38_client;
+pub mod sep_endpoint_prober;
+pub mod signer;
+pub mod stellar_toml;
+pub mod stellar_toml_crawler;
+pub mod transfer_tracker;
 pub mod trustline_analyzer;
 
 #[cfg(test)]
@@ -195,6 +195,15 @@ impl PriceFeedClient {
             }
         };
 
+        Self::with_provider(provider, config, asset_mapping)
+    }
+
+    /// Create a client backed by a specific provider
+    pub fn with_provider(
+        provider: Arc<dyn PriceFeedProvider>,
+        config: PriceFeedConfig,
+        asset_mapping: HashMap<String, String>,
+    ) -> Self {
         info!("Initialized price feed client with provider: {}", provider.name());
 
         Self {
@@ -284,23 +293,29 @@ impl PriceFeedClient {
             return result;
         }
 
-        // Map to provider asset IDs
-        let provider_ids: Vec<String> = to_fetch
+        // Map to provider asset IDs, skipping assets without a mapping
+        let mapped: Vec<(String, String)> = to_fetch
             .iter()
-            .filter_map(|asset| self.asset_mapping.get(asset).cloned())
+            .filter_map(|asset| {
+                self.asset_mapping
+                    .get(asset)
+                    .map(|id| (asset.clone(), id.clone()))
+            })
             .collect();
 
-        if provider_ids.is_empty() {
+        if mapped.is_empty() {
             return result;
         }
 
+        let provider_ids: Vec<String> = mapped.iter().map(|(_, id)| id.clone()).collect();
+
         // Fetch from provider
         match self.provider.fetch_prices(&provider_ids).await {
             Ok(prices) => {
                 let mut cache = self.cache.write().await;
                 
                 // Map back to Stellar assets and update cache
-                for (stellar_asset, provider_id) in to_fetch.iter().zip(provider_ids.iter()) {
+                for (stellar_asset, provider_id) in &mapped {
                     if let Some(&price) = prices.get(provider_id) {
                         cache.insert(
                             stellar_asset.clone(),
@@ -0,0 +1,242 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::models::Anchor;
+use crate::services::sep10_client::Sep10Client;
+use crate::services::sep38_client::{PriceRequest, QuoteRequest, QuoteResponse, Sep38Client};
+use crate::services::sep_endpoint_prober::{resolve_endpoints, SepEndpoint};
+
+/// What to exchange, in SEP-38 asset identification format
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteComparisonRequest {
+    pub sell_asset: String,
+    pub buy_asset: String,
+    /// Decimal amount of `sell_asset`
+    pub sell_amount: String,
+    /// `sep6`, `sep24` or `sep31`
+    pub context: String,
+    /// Request firm quotes (authenticating through SEP-10) instead of
+    /// indicative prices
+    pub firm: bool,
+}
+
+/// One anchor's answer for the requested exchange
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnchorQuote {
+    pub anchor_id: String,
+    pub anchor_name: String,
+    pub quote_server: String,
+    pub firm: bool,
+    /// Set for firm quotes
+    pub quote_id: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub price: Option<String>,
+    /// Price including fees
+    pub total_price: Option<String>,
+    pub sell_amount: Option<String>,
+    /// Amount delivered after fees
+    pub buy_amount: Option<String>,
+    pub fee_total: Option<String>,
+    pub fee_asset: Option<String>,
+    pub error: Option<String>,
+}
+
+impl AnchorQuote {
+    fn delivered(&self) -> Option<f64> {
+        self.buy_amount
+            .as_deref()
+            .and_then(|amount| amount.parse::<f64>().ok())
+            .filter(|amount| amount.is_finite())
+    }
+}
+
+/// Requests SEP-38 prices or firm quotes for one exchange from every anchor
+/// whose quote server offers the pair, and ranks them by the amount they
+/// deliver after fees.
+pub struct QuoteComparer {
+    db: Arc<Database>,
+    sep38: Arc<Sep38Client>,
+    sep10: Arc<Sep10Client>,
+}
+
+impl QuoteComparer {
+    pub fn new(db: Arc<Database>, sep38: Arc<Sep38Client>, sep10: Arc<Sep10Client>) -> Self {
+        Self { db, sep38, sep10 }
+    }
+
+    /// Quotes of all anchors offering the pair, largest delivered amount
+    /// first; anchors that failed to quote follow.
+    pub async fn compare(&self, request: &QuoteComparisonRequest) -> Result<Vec<AnchorQuote>> {
+        let mut servers = Vec::new();
+        for anchor in self.db.list_anchors(i64::MAX, 0).await? {
+            let quote_server = resolve_endpoints(&self.db, &anchor.id)
+                .await?
+                .into_iter()
+                .find(|(sep, _)| *sep == SepEndpoint::Sep38)
+                .map(|(_, url)| url);
+            if let Some(quote_server) = quote_server {
+                servers.push((anchor, quote_server));
+            }
+        }
+
+        let mut quotes: Vec<AnchorQuote> = join_all(
+            servers
+                .iter()
+                .map(|(anchor, quote_server)| self.quote_anchor(anchor, quote_server, request)),
+        )
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+
+        rank(&mut quotes);
+        Ok(quotes)
+    }
+
+    /// Ask one anchor, or `None` if its quote server does not offer the pair
+    async fn quote_anchor(
+        &self,
+        anchor: &Anchor,
+        quote_server: &str,
+        request: &QuoteComparisonRequest,
+    ) -> Option<AnchorQuote> {
+        let mut quote = AnchorQuote {
+            anchor_id: anchor.id.clone(),
+            anchor_name: anchor.name.clone(),
+            quote_server: quote_server.to_string(),
+            firm: request.firm,
+            quote_id: None,
+            expires_at: None,
+            price: None,
+            total_price: None,
+            sell_amount: None,
+            buy_amount: None,
+            fee_total: None,
+            fee_asset: None,
+            error: None,
+        };
+
+        match self.sep38.info(quote_server).await {
+            Ok(info) if !info.supports(&request.sell_asset, &request.buy_asset) => return None,
+            Ok(_) => {}
+            Err(e) => {
+                quote.error = Some(format!("/info: {}", e));
+                return Some(quote);
+            }
+        }
+
+        let price_request = PriceRequest {
+            context: request.context.clone(),
+            sell_asset: request.sell_asset.clone(),
+            buy_asset: request.buy_asset.clone(),
+            sell_amount: Some(request.sell_amount.clone()),
+            ..PriceRequest::default()
+        };
+        if request.firm {
+            match self.firm_quote(anchor, quote_server, price_request).await {
+                Ok(firm) => {
+                    quote.quote_id = Some(firm.id);
+                    quote.expires_at = Some(firm.expires_at);
+                    quote.price = Some(firm.price);
+                    quote.total_price = Some(firm.total_price);
+                    quote.sell_amount = Some(firm.sell_amount);
+                    quote.buy_amount = Some(firm.buy_amount);
+                    quote.fee_total = Some(firm.fee.total);
+                    quote.fee_asset = Some(firm.fee.asset);
+                }
+                Err(e) => quote.error = Some(e),
+            }
+        } else {
+            match self.sep38.price(quote_server, &price_request, None).await {
+                Ok(price) => {
+                    quote.price = Some(price.price);
+                    quote.total_price = Some(price.total_price);
+                    quote.sell_amount = Some(price.sell_amount);
+                    quote.buy_amount = Some(price.buy_amount);
+                    quote.fee_total = Some(price.fee.total);
+                    quote.fee_asset = Some(price.fee.asset);
+                }
+                Err(e) => quote.error = Some(format!("/price: {}", e)),
+            }
+        }
+
+        Some(quote)
+    }
+
+    async fn firm_quote(
+        &self,
+        anchor: &Anchor,
+        quote_server: &str,
+        price: PriceRequest,
+    ) -> std::result::Result<QuoteResponse, String> {
+        let home_domain = anchor
+            .home_domain
+            .as_deref()
+            .ok_or_else(|| "anchor has no home domain to authenticate with".to_string())?;
+        let token = self
+            .sep10
+            .token(home_domain, None)
+            .await
+            .map_err(|e| format!("SEP-10: {}", e))?;
+        let request = QuoteRequest {
+            price,
+            expire_after: None,
+        };
+
+        self.sep38
+            .post_quote(quote_server, &request, Some(&token.token))
+            .await
+            .map_err(|e| format!("/quote: {}", e))
+    }
+}
+
+/// Order by delivered amount, largest first, with unusable quotes last
+fn rank(quotes: &mut [AnchorQuote]) {
+    quotes.sort_by(|a, b| match (a.delivered(), b.delivered()) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(anchor_id: &str, buy_amount: Option<&str>) -> AnchorQuote {
+        AnchorQuote {
+            anchor_id: anchor_id.to_string(),
+            anchor_name: anchor_id.to_string(),
+            quote_server: format!("https://{}/sep38", anchor_id),
+            firm: false,
+            quote_id: None,
+            expires_at: None,
+            price: None,
+            total_price: None,
+            sell_amount: Some("100".to_string()),
+            buy_amount: buy_amount.map(str::to_string),
+            fee_total: None,
+            fee_asset: None,
+            error: buy_amount.is_none().then(|| "/price: HTTP 503".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_rank_by_delivered_amount() {
+        let mut quotes = vec![
+            quote("down", None),
+            quote("cheap", Some("540.25")),
+            quote("garbled", Some("n/a")),
+            quote("best", Some("545.10")),
+        ];
+        rank(&mut quotes);
+
+        let order: Vec<&str> = quotes.iter().map(|q| q.anchor_id.as_str()).collect();
+        assert_eq!(order, vec!["best", "cheap", "down", "garbled"]);
+    }
+}
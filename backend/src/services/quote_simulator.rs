@@ -0,0 +1,422 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::models::asset::AssetId;
+use crate::rpc::{Asset, OrderBook, StellarRpcClient};
+use crate::services::route_finder::{constant_product_out, find_pools_for_pair, oriented_reserves};
+
+/// Order book levels requested from Horizon per quote
+const ORDER_BOOK_LIMIT: u32 = 50;
+/// Number of slices an amount is split into when routing between venues
+const FILL_STEPS: usize = 200;
+/// Bisection iterations used to find the largest size under a tolerance
+const MAX_SIZE_ITERATIONS: usize = 50;
+/// Sizes, as multiples of the requested amount, sampled for the slippage curve
+const CURVE_MULTIPLIERS: [f64; 7] = [0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0];
+
+/// A resting bid the taker can sell into
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookLevel {
+    /// Destination units received per source unit
+    pub price: f64,
+    /// Source units this level absorbs
+    pub capacity: f64,
+}
+
+/// Constant product pool reserves oriented in the direction of the trade
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolReserves {
+    pub pool_id: String,
+    pub reserve_in: f64,
+    pub reserve_out: f64,
+    pub fee_bp: i32,
+}
+
+/// Liquidity available for selling the source asset into the destination asset
+#[derive(Debug, Clone, Default)]
+pub struct LiquiditySources {
+    /// Bids ordered best price first
+    pub book: Vec<BookLevel>,
+    pub pools: Vec<PoolReserves>,
+}
+
+impl LiquiditySources {
+    /// Best marginal price available for an infinitesimal trade
+    pub fn best_price(&self) -> Option<f64> {
+        let book_best = self.book.first().map(|level| level.price);
+        let pool_best = self
+            .pools
+            .iter()
+            .filter(|p| p.reserve_in > 0.0 && p.reserve_out > 0.0)
+            .map(|p| p.reserve_out / p.reserve_in * (1.0 - p.fee_bp as f64 / 10_000.0))
+            .fold(None, |best: Option<f64>, price| {
+                Some(best.map_or(price, |b| b.max(price)))
+            });
+
+        match (book_best, pool_best) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Upper bound on the size worth searching when looking for a max size
+    fn search_ceiling(&self) -> f64 {
+        self.book.iter().map(|l| l.capacity).sum::<f64>()
+            + self.pools.iter().map(|p| p.reserve_in).sum::<f64>()
+    }
+}
+
+/// Outcome of filling an amount against the available liquidity
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulatedFill {
+    pub amount_in: f64,
+    /// Part of `amount_in` that found liquidity
+    pub filled_amount: f64,
+    pub amount_out: f64,
+    /// Destination units received per source unit filled
+    pub average_price: f64,
+    /// Shortfall of `average_price` against the best available price
+    pub slippage_percent: f64,
+    /// Source units routed to the order book
+    pub order_book_amount: f64,
+    /// Source units routed to liquidity pools
+    pub pool_amount: f64,
+    pub fully_filled: bool,
+}
+
+/// A single point on the slippage curve
+#[derive(Debug, Clone, Serialize)]
+pub struct SlippagePoint {
+    pub amount: f64,
+    pub expected_output: f64,
+    pub average_price: f64,
+    pub slippage_percent: f64,
+    pub fully_filled: bool,
+}
+
+/// Simulated quote for sending an amount through a corridor
+#[derive(Debug, Clone, Serialize)]
+pub struct CorridorQuote {
+    pub source_asset: AssetId,
+    pub destination_asset: AssetId,
+    pub best_price: Option<f64>,
+    pub fill: SimulatedFill,
+    pub slippage_curve: Vec<SlippagePoint>,
+    pub tolerance_percent: f64,
+    /// Largest amount that fills completely within `tolerance_percent`
+    pub max_size_within_tolerance: f64,
+    pub order_book_levels: usize,
+    pub pool_ids: Vec<String>,
+}
+
+pub struct QuoteSimulator {
+    pool: Pool<Sqlite>,
+    rpc_client: Arc<StellarRpcClient>,
+}
+
+impl QuoteSimulator {
+    pub fn new(pool: Pool<Sqlite>, rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self { pool, rpc_client }
+    }
+
+    /// Simulate selling `amount` of `from` for `to` against the live order
+    /// book and any synced liquidity pools for the pair.
+    pub async fn quote(
+        &self,
+        from: &AssetId,
+        to: &AssetId,
+        amount: f64,
+        tolerance_percent: f64,
+    ) -> Result<CorridorQuote> {
+        if amount <= 0.0 || !amount.is_finite() {
+            bail!("amount must be a positive number");
+        }
+        if !(0.0..100.0).contains(&tolerance_percent) {
+            bail!("tolerance_percent must be between 0 and 100");
+        }
+
+        let sources = self.load_sources(from, to).await?;
+        let fill = simulate_fill(&sources, amount);
+        let slippage_curve = CURVE_MULTIPLIERS
+            .iter()
+            .map(|multiplier| {
+                let size = amount * multiplier;
+                let point = simulate_fill(&sources, size);
+                SlippagePoint {
+                    amount: size,
+                    expected_output: point.amount_out,
+                    average_price: point.average_price,
+                    slippage_percent: point.slippage_percent,
+                    fully_filled: point.fully_filled,
+                }
+            })
+            .collect();
+
+        Ok(CorridorQuote {
+            source_asset: from.clone(),
+            destination_asset: to.clone(),
+            best_price: sources.best_price(),
+            fill,
+            slippage_curve,
+            tolerance_percent,
+            max_size_within_tolerance: max_size_within_tolerance(&sources, tolerance_percent),
+            order_book_levels: sources.book.len(),
+            pool_ids: sources.pools.iter().map(|p| p.pool_id.clone()).collect(),
+        })
+    }
+
+    async fn load_sources(&self, from: &AssetId, to: &AssetId) -> Result<LiquiditySources> {
+        let (selling, buying) = (Asset::try_from(from)?, Asset::try_from(to)?);
+
+        let book = match self
+            .rpc_client
+            .fetch_order_book(&selling, &buying, ORDER_BOOK_LIMIT)
+            .await
+        {
+            Ok(book) => book_levels(&book),
+            Err(e) => {
+                warn!("Order book unavailable for {} -> {}: {}", from, to, e);
+                Vec::new()
+            }
+        };
+
+        let pools = find_pools_for_pair(&self.pool, from, to)
+            .await?
+            .iter()
+            .map(|pool| {
+                let (reserve_in, reserve_out, _) = oriented_reserves(pool, from)?;
+                Ok(PoolReserves {
+                    pool_id: pool.pool_id.clone(),
+                    reserve_in,
+                    reserve_out,
+                    fee_bp: pool.fee_bp,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(LiquiditySources { book, pools })
+    }
+}
+
+/// Bids from an order book whose base is the asset being sold.
+///
+/// Horizon quotes bid amounts in the counter asset, so each level's capacity
+/// in source units is `amount / price`.
+pub fn book_levels(book: &OrderBook) -> Vec<BookLevel> {
+    let mut levels: Vec<BookLevel> = book
+        .bids
+        .iter()
+        .filter_map(|bid| {
+            let price: f64 = bid.price.parse().ok()?;
+            let amount: f64 = bid.amount.parse().ok()?;
+            (price > 0.0 && amount > 0.0).then(|| BookLevel {
+                price,
+                capacity: amount / price,
+            })
+        })
+        .collect();
+    levels.sort_by(|a, b| b.price.total_cmp(&a.price));
+    levels
+}
+
+/// Fill `amount` by routing each slice to whichever venue pays most for it
+pub fn simulate_fill(sources: &LiquiditySources, amount: f64) -> SimulatedFill {
+    let mut book = sources.book.clone();
+    let mut pools = sources.pools.clone();
+    let mut level_index = 0;
+    let mut filled = 0.0;
+    let mut amount_out = 0.0;
+    let mut order_book_amount = 0.0;
+    let mut pool_amount = 0.0;
+
+    let slice = amount / FILL_STEPS as f64;
+    for _ in 0..FILL_STEPS {
+        let book_out = book_output(&book[level_index..], slice);
+        let best_pool = pools
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| {
+                constant_product_out(p.reserve_in, p.reserve_out, slice, p.fee_bp).map(|o| (i, o))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let use_book = match (book_out, best_pool) {
+            (Some(book_out), Some((_, pool_out))) => book_out >= pool_out,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
+        };
+
+        if use_book {
+            level_index += consume_book(&mut book[level_index..], slice);
+            amount_out += book_out.unwrap_or_default();
+            order_book_amount += slice;
+        } else if let Some((i, pool_out)) = best_pool {
+            pools[i].reserve_in += slice;
+            pools[i].reserve_out -= pool_out;
+            amount_out += pool_out;
+            pool_amount += slice;
+        }
+        filled += slice;
+    }
+
+    let average_price = if filled > 0.0 {
+        amount_out / filled
+    } else {
+        0.0
+    };
+    let slippage_percent = match sources.best_price() {
+        Some(best) if filled > 0.0 => ((best - average_price) / best * 100.0).max(0.0),
+        _ => 0.0,
+    };
+
+    SimulatedFill {
+        amount_in: amount,
+        filled_amount: filled,
+        amount_out,
+        average_price,
+        slippage_percent,
+        order_book_amount,
+        pool_amount,
+        fully_filled: filled >= amount * (1.0 - 1e-9),
+    }
+}
+
+/// Output from selling `amount` into the book; `None` if the book cannot absorb it all
+fn book_output(levels: &[BookLevel], amount: f64) -> Option<f64> {
+    let mut remaining = amount;
+    let mut out = 0.0;
+    for level in levels {
+        let take = remaining.min(level.capacity);
+        out += take * level.price;
+        remaining -= take;
+        if remaining <= 0.0 {
+            return Some(out);
+        }
+    }
+    None
+}
+
+/// Remove `amount` from the book, returning how many levels were exhausted
+fn consume_book(levels: &mut [BookLevel], amount: f64) -> usize {
+    let mut remaining = amount;
+    let mut exhausted = 0;
+    for level in levels.iter_mut() {
+        let take = remaining.min(level.capacity);
+        level.capacity -= take;
+        remaining -= take;
+        if level.capacity <= f64::EPSILON {
+            exhausted += 1;
+        }
+        if remaining <= 0.0 {
+            break;
+        }
+    }
+    exhausted
+}
+
+/// Largest amount that fills completely with slippage at or below `tolerance_percent`
+pub fn max_size_within_tolerance(sources: &LiquiditySources, tolerance_percent: f64) -> f64 {
+    let within = |size: f64| {
+        let fill = simulate_fill(sources, size);
+        fill.fully_filled && fill.slippage_percent <= tolerance_percent
+    };
+
+    let mut high = sources.search_ceiling();
+    if high <= 0.0 {
+        return 0.0;
+    }
+    if within(high) {
+        return high;
+    }
+
+    let mut low = 0.0;
+    for _ in 0..MAX_SIZE_ITERATIONS {
+        let mid = (low + high) / 2.0;
+        if within(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> Vec<BookLevel> {
+        vec![
+            BookLevel {
+                price: 1.00,
+                capacity: 100.0,
+            },
+            BookLevel {
+                price: 0.98,
+                capacity: 100.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_fill_walks_order_book_levels() {
+        let sources = LiquiditySources {
+            book: book(),
+            pools: vec![],
+        };
+
+        let fill = simulate_fill(&sources, 150.0);
+        assert!(fill.fully_filled);
+        assert!((fill.amount_out - (100.0 + 50.0 * 0.98)).abs() < 1e-6);
+        assert!((fill.slippage_percent - (1.0 - 149.0 / 150.0) * 100.0).abs() < 1e-6);
+        assert_eq!(fill.pool_amount, 0.0);
+
+        let too_big = simulate_fill(&sources, 500.0);
+        assert!(!too_big.fully_filled);
+        assert!((too_big.filled_amount - 200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fill_splits_between_book_and_pool() {
+        let sources = LiquiditySources {
+            book: book(),
+            pools: vec![PoolReserves {
+                pool_id: "pool".to_string(),
+                reserve_in: 10_000.0,
+                reserve_out: 10_000.0,
+                fee_bp: 30,
+            }],
+        };
+
+        let fill = simulate_fill(&sources, 1_000.0);
+        assert!(fill.fully_filled);
+        assert!(fill.order_book_amount > 0.0);
+        assert!(fill.pool_amount > 0.0);
+        // Routing across both venues beats either venue alone
+        let pool_only = constant_product_out(10_000.0, 10_000.0, 1_000.0, 30).unwrap();
+        assert!(fill.amount_out > pool_only);
+    }
+
+    #[test]
+    fn test_max_size_respects_tolerance() {
+        let sources = LiquiditySources {
+            book: book(),
+            pools: vec![],
+        };
+
+        // Only the first level fits within a tiny tolerance
+        let max = max_size_within_tolerance(&sources, 0.01);
+        assert!((100.0..101.0).contains(&max));
+
+        let fill = simulate_fill(&sources, max);
+        assert!(fill.slippage_percent <= 0.01);
+        assert_eq!(
+            max_size_within_tolerance(&LiquiditySources::default(), 1.0),
+            0.0
+        );
+    }
+}
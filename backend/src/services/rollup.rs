@@ -0,0 +1,801 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::time::{interval, Duration as TokioDuration};
+use tracing::{error, info, warn};
+
+use crate::database::Database;
+use crate::models::asset::AssetId;
+
+/// Raw events are rolled up in windows of this many hours so a backfill
+/// never reads more than one window of payments at a time
+const RAW_WINDOW_HOURS: i64 = 24;
+
+/// Bucket width of a rollup series
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resolution {
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    Hour,
+    #[serde(rename = "1d")]
+    Day,
+    #[serde(rename = "1w")]
+    Week,
+}
+
+impl Resolution {
+    /// All resolutions, finest first
+    pub const ALL: [Resolution; 4] = [Self::FiveMinutes, Self::Hour, Self::Day, Self::Week];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FiveMinutes => "5m",
+            Self::Hour => "1h",
+            Self::Day => "1d",
+            Self::Week => "1w",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::FiveMinutes => Duration::minutes(5),
+            Self::Hour => Duration::hours(1),
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::weeks(1),
+        }
+    }
+
+    /// Resolution this one is derived from, `None` for raw-event buckets
+    pub fn source(&self) -> Option<Resolution> {
+        match self {
+            Self::FiveMinutes => None,
+            Self::Hour => Some(Self::FiveMinutes),
+            Self::Day => Some(Self::Hour),
+            Self::Week => Some(Self::Day),
+        }
+    }
+
+    /// Start of the bucket containing `ts`. Weeks start on Monday (UTC).
+    pub fn bucket_start(&self, ts: DateTime<Utc>) -> DateTime<Utc> {
+        let step = match self {
+            Self::Week => Duration::days(1),
+            other => other.duration(),
+        }
+        .num_seconds();
+        let floored = Utc
+            .timestamp_opt(ts.timestamp().div_euclid(step) * step, 0)
+            .single()
+            .unwrap_or(ts);
+
+        match self {
+            Self::Week => floored - Duration::days(floored.weekday().num_days_from_monday() as i64),
+            _ => floored,
+        }
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|r| r.as_str() == s)
+            .ok_or_else(|| anyhow!("unknown resolution '{}' (expected 5m, 1h, 1d or 1w)", s))
+    }
+}
+
+/// Kind of entity a rollup series describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupEntity {
+    /// Keyed by corridor key (e.g. `USDC:GA5Z...->XLM:native`)
+    Corridor,
+    /// Keyed by anchor id, covering payments in assets the anchor issues
+    Anchor,
+    /// Keyed by liquidity pool id; carries TVL as a gauge
+    Pool,
+    /// Keyed by `CODE:ISSUER` (`XLM:native` for lumens), covering payments
+    /// sent or received in the asset. Payments carry a single amount, so both
+    /// legs record it as volume, as corridors do.
+    Asset,
+}
+
+impl RollupEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Corridor => "corridor",
+            Self::Anchor => "anchor",
+            Self::Pool => "pool",
+            Self::Asset => "asset",
+        }
+    }
+}
+
+impl FromStr for RollupEntity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "corridor" => Ok(Self::Corridor),
+            "anchor" => Ok(Self::Anchor),
+            "pool" => Ok(Self::Pool),
+            "asset" => Ok(Self::Asset),
+            _ => Err(anyhow!(
+                "unknown entity type '{}' (expected corridor, anchor, pool or asset)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RollupConfig {
+    pub interval_minutes: u64,
+    /// Raw events older than this are not re-read on each run
+    pub lookback_minutes: i64,
+    pub batch_size: i64,
+    /// Retention per resolution; `None` keeps buckets forever
+    pub five_minute_retention: Option<Duration>,
+    pub hourly_retention: Option<Duration>,
+    pub daily_retention: Option<Duration>,
+    pub weekly_retention: Option<Duration>,
+    /// Upper bound on points returned when the resolution is chosen automatically
+    pub max_points: i64,
+}
+
+impl Default for RollupConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 5,
+            lookback_minutes: 60,
+            batch_size: 50_000,
+            five_minute_retention: Some(Duration::days(2)),
+            hourly_retention: Some(Duration::days(30)),
+            daily_retention: Some(Duration::days(730)),
+            weekly_retention: None,
+            max_points: 500,
+        }
+    }
+}
+
+impl RollupConfig {
+    pub fn retention(&self, resolution: Resolution) -> Option<Duration> {
+        match resolution {
+            Resolution::FiveMinutes => self.five_minute_retention,
+            Resolution::Hour => self.hourly_retention,
+            Resolution::Day => self.daily_retention,
+            Resolution::Week => self.weekly_retention,
+        }
+    }
+
+    /// Finest resolution that still holds data at `start` and covers
+    /// `[start, end]` in at most `max_points` buckets.
+    pub fn select_resolution(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+        max_points: i64,
+    ) -> Resolution {
+        let span = (end - start).num_seconds().max(0);
+
+        Resolution::ALL
+            .into_iter()
+            .find(|resolution| {
+                let retained = self
+                    .retention(*resolution)
+                    .is_none_or(|keep| start >= now - keep);
+                let points = span / resolution.duration().num_seconds() + 1;
+                retained && points <= max_points
+            })
+            .unwrap_or(Resolution::Week)
+    }
+}
+
+/// One bucket of a rollup series.
+///
+/// Values are stored as sums and sample counts so buckets merge exactly
+/// into coarser resolutions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RollupBucket {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub resolution: String,
+    pub bucket_start: DateTime<Utc>,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub volume_usd: f64,
+    pub latency_sum_ms: i64,
+    pub latency_samples: i64,
+    pub gauge_sum: f64,
+    pub gauge_samples: i64,
+    pub gauge_min: Option<f64>,
+    pub gauge_max: Option<f64>,
+}
+
+impl RollupBucket {
+    pub fn empty(
+        entity: RollupEntity,
+        entity_id: &str,
+        resolution: Resolution,
+        bucket_start: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            entity_type: entity.as_str().to_string(),
+            entity_id: entity_id.to_string(),
+            resolution: resolution.as_str().to_string(),
+            bucket_start,
+            total_transactions: 0,
+            successful_transactions: 0,
+            volume_usd: 0.0,
+            latency_sum_ms: 0,
+            latency_samples: 0,
+            gauge_sum: 0.0,
+            gauge_samples: 0,
+            gauge_min: None,
+            gauge_max: None,
+        }
+    }
+
+    /// Fold a finer bucket of the same entity into this one
+    pub fn merge(&mut self, other: &RollupBucket) {
+        self.total_transactions += other.total_transactions;
+        self.successful_transactions += other.successful_transactions;
+        self.volume_usd += other.volume_usd;
+        self.latency_sum_ms += other.latency_sum_ms;
+        self.latency_samples += other.latency_samples;
+        self.gauge_sum += other.gauge_sum;
+        self.gauge_samples += other.gauge_samples;
+        self.gauge_min = min_option(self.gauge_min, other.gauge_min);
+        self.gauge_max = max_option(self.gauge_max, other.gauge_max);
+    }
+
+    fn record_payment(&mut self, successful: bool, amount: f64, latency_ms: Option<i64>) {
+        self.total_transactions += 1;
+        if successful {
+            self.successful_transactions += 1;
+            self.volume_usd += amount;
+            if let Some(latency) = latency_ms.filter(|ms| *ms >= 0) {
+                self.latency_sum_ms += latency;
+                self.latency_samples += 1;
+            }
+        }
+    }
+
+    fn record_gauge(&mut self, value: f64) {
+        self.gauge_sum += value;
+        self.gauge_samples += 1;
+        self.gauge_min = min_option(self.gauge_min, Some(value));
+        self.gauge_max = max_option(self.gauge_max, Some(value));
+    }
+
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.total_transactions > 0)
+            .then(|| self.successful_transactions as f64 / self.total_transactions as f64 * 100.0)
+    }
+
+    pub fn avg_latency_ms(&self) -> Option<f64> {
+        (self.latency_samples > 0).then(|| self.latency_sum_ms as f64 / self.latency_samples as f64)
+    }
+
+    pub fn gauge_avg(&self) -> Option<f64> {
+        (self.gauge_samples > 0).then(|| self.gauge_sum / self.gauge_samples as f64)
+    }
+}
+
+fn min_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn max_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Merge finer buckets into buckets of `resolution`
+pub fn downsample(buckets: &[RollupBucket], resolution: Resolution) -> Vec<RollupBucket> {
+    let mut merged: HashMap<(String, String, DateTime<Utc>), RollupBucket> = HashMap::new();
+
+    for bucket in buckets {
+        let start = resolution.bucket_start(bucket.bucket_start);
+        match merged.entry((bucket.entity_type.clone(), bucket.entity_id.clone(), start)) {
+            Entry::Occupied(mut existing) => existing.get_mut().merge(bucket),
+            Entry::Vacant(slot) => {
+                slot.insert(RollupBucket {
+                    resolution: resolution.as_str().to_string(),
+                    bucket_start: start,
+                    ..bucket.clone()
+                });
+            }
+        }
+    }
+
+    merged.into_values().collect()
+}
+
+type RawBuckets = HashMap<(RollupEntity, String, DateTime<Utc>), RollupBucket>;
+
+/// 5-minute bucket of `entity` containing `ts`, created empty if missing
+fn raw_bucket<'a>(
+    buckets: &'a mut RawBuckets,
+    entity: RollupEntity,
+    entity_id: &str,
+    ts: DateTime<Utc>,
+) -> &'a mut RollupBucket {
+    let resolution = Resolution::FiveMinutes;
+    let bucket_start = resolution.bucket_start(ts);
+    buckets
+        .entry((entity, entity_id.to_string(), bucket_start))
+        .or_insert_with(|| RollupBucket::empty(entity, entity_id, resolution, bucket_start))
+}
+
+/// Series returned for a range query
+#[derive(Debug, Clone, Serialize)]
+pub struct RollupSeries {
+    pub entity_type: RollupEntity,
+    pub entity_id: String,
+    pub resolution: Resolution,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub buckets: Vec<RollupBucket>,
+}
+
+/// Maintains 5m/1h/1d/1w rollups for corridors, anchors, pools and assets.
+///
+/// 5-minute buckets are rebuilt from raw payments and pool snapshots; each
+/// coarser resolution is rebuilt from the one below it, so all resolutions
+/// agree regardless of which one a query is served from.
+pub struct RollupEngine {
+    db: Arc<Database>,
+    config: RollupConfig,
+}
+
+impl RollupEngine {
+    pub fn new(db: Arc<Database>, config: RollupConfig) -> Self {
+        Self { db, config }
+    }
+
+    pub fn config(&self) -> &RollupConfig {
+        &self.config
+    }
+
+    /// Start the rollup and retention scheduler
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting metric rollup scheduler (interval: {} minutes)",
+            self.config.interval_minutes
+        );
+
+        let mut ticker = interval(TokioDuration::from_secs(self.config.interval_minutes * 60));
+
+        loop {
+            ticker.tick().await;
+
+            let now = Utc::now();
+            if let Err(e) = self.run_rollup(now).await {
+                error!("Metric rollup failed: {}", e);
+            }
+            if let Err(e) = self.apply_retention(now).await {
+                error!("Metric rollup retention failed: {}", e);
+            }
+        }
+    }
+
+    /// Rebuild every bucket touched by raw events since the previous run.
+    ///
+    /// Raw events are re-read from the start of the lookback window or the
+    /// newest stored bucket, whichever is earlier. With no buckets stored yet
+    /// the run backfills from the oldest raw event. Returns the number of
+    /// buckets written across all resolutions.
+    pub async fn run_rollup(&self, now: DateTime<Utc>) -> Result<usize> {
+        let lookback = now - Duration::minutes(self.config.lookback_minutes);
+        let resume_from = match self.latest_bucket_start().await? {
+            Some(latest) => Some(latest),
+            None => self.earliest_raw_event().await?,
+        };
+        let start = Resolution::FiveMinutes
+            .bucket_start(resume_from.map_or(lookback, |ts| ts.min(lookback)));
+
+        let mut written = 0;
+        let mut window_start = start;
+        while window_start <= now {
+            let window_end = (window_start + Duration::hours(RAW_WINDOW_HOURS)).min(now);
+            written += self.rollup_window(window_start, window_end).await?;
+            window_start += Duration::hours(RAW_WINDOW_HOURS);
+        }
+
+        info!("Wrote {} metric rollup buckets", written);
+        Ok(written)
+    }
+
+    /// Rebuild the 5-minute buckets of `[start, end]` and every coarser
+    /// bucket overlapping it
+    async fn rollup_window(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<usize> {
+        let raw = self.rollup_raw_events(start, end).await?;
+        let mut written = self.upsert_buckets(&raw).await?;
+
+        for resolution in [Resolution::Hour, Resolution::Day, Resolution::Week] {
+            let Some(source) = resolution.source() else {
+                continue;
+            };
+            let from = resolution.bucket_start(start);
+            let finer = self.fetch_resolution(source, None, from, end).await?;
+            written += self.upsert_buckets(&downsample(&finer, resolution)).await?;
+        }
+
+        Ok(written)
+    }
+
+    /// Start of the newest bucket at any resolution
+    async fn latest_bucket_start(&self) -> Result<Option<DateTime<Utc>>> {
+        sqlx::query_scalar("SELECT MAX(bucket_start) FROM metric_rollups")
+            .fetch_one(self.db.pool())
+            .await
+            .context("Failed to read the latest metric rollup")
+    }
+
+    /// Timestamp of the oldest payment or pool snapshot
+    async fn earliest_raw_event(&self) -> Result<Option<DateTime<Utc>>> {
+        let payment: Option<DateTime<Utc>> =
+            sqlx::query_scalar("SELECT MIN(created_at) FROM payments")
+                .fetch_one(self.db.pool())
+                .await
+                .context("Failed to read the oldest payment")?;
+        let snapshot: Option<DateTime<Utc>> =
+            sqlx::query_scalar("SELECT MIN(snapshot_at) FROM liquidity_pool_snapshots")
+                .fetch_one(self.db.pool())
+                .await
+                .context("Failed to read the oldest pool snapshot")?;
+
+        Ok(payment.into_iter().chain(snapshot).min())
+    }
+
+    /// Build 5-minute buckets from payments and pool snapshots in `[start, end]`
+    async fn rollup_raw_events(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<RollupBucket>> {
+        let mut buckets: RawBuckets = HashMap::new();
+
+        let anchors = self.asset_anchors().await?;
+        let payments = self
+            .db
+            .fetch_payments_by_timerange(start, end, self.config.batch_size)
+            .await
+            .context("Failed to fetch payments for rollup")?;
+        if payments.len() as i64 >= self.config.batch_size {
+            warn!(
+                "Rollup of {} to {} hit the batch size of {} payments; later payments are skipped",
+                start, end, self.config.batch_size
+            );
+        }
+
+        for payment in &payments {
+            let latency = payment.settlement_latency_ms();
+            let corridor = match payment.corridor_key() {
+                Ok(corridor) => corridor,
+                Err(e) => {
+                    warn!("Skipping payment {} in rollup: {}", payment.id, e);
+                    continue;
+                }
+            };
+
+            let mut keys = vec![(
+                RollupEntity::Corridor,
+                corridor.clone().normalized().to_string(),
+            )];
+            for asset in [&corridor.source, &corridor.destination] {
+                let mut asset_keys = vec![(RollupEntity::Asset, asset.key())];
+                if let Some(anchor_id) = anchors.get(asset) {
+                    asset_keys.push((RollupEntity::Anchor, anchor_id.clone()));
+                }
+                for key in asset_keys {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                }
+            }
+
+            for (entity, id) in keys {
+                raw_bucket(&mut buckets, entity, &id, payment.timestamp).record_payment(
+                    payment.successful,
+                    payment.amount,
+                    latency,
+                );
+            }
+        }
+
+        let snapshots = sqlx::query_as::<_, (String, f64, DateTime<Utc>)>(
+            r#"
+            SELECT pool_id, total_value_usd, snapshot_at
+            FROM liquidity_pool_snapshots
+            WHERE snapshot_at >= $1 AND snapshot_at <= $2
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to fetch pool snapshots for rollup")?;
+
+        for (pool_id, tvl, snapshot_at) in snapshots {
+            raw_bucket(&mut buckets, RollupEntity::Pool, &pool_id, snapshot_at).record_gauge(tvl);
+        }
+
+        Ok(buckets.into_values().collect())
+    }
+
+    /// Map of issued assets to the id of the anchor issuing them
+    async fn asset_anchors(&self) -> Result<HashMap<AssetId, String>> {
+        let rows = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT asset_code, asset_issuer, anchor_id FROM assets",
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(code, issuer, anchor_id)| {
+                AssetId::credit(&code, &issuer)
+                    .map_err(|e| warn!("Ignoring anchor asset {}:{}: {}", code, issuer, e))
+                    .ok()
+                    .map(|asset| (asset, anchor_id))
+            })
+            .collect())
+    }
+
+    async fn upsert_buckets(&self, buckets: &[RollupBucket]) -> Result<usize> {
+        let mut tx = self.db.pool().begin().await?;
+
+        for bucket in buckets {
+            sqlx::query(
+                r#"
+                INSERT INTO metric_rollups (
+                    entity_type, entity_id, resolution, bucket_start,
+                    total_transactions, successful_transactions, volume_usd,
+                    latency_sum_ms, latency_samples,
+                    gauge_sum, gauge_samples, gauge_min, gauge_max
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                ON CONFLICT (entity_type, entity_id, resolution, bucket_start) DO UPDATE SET
+                    total_transactions = excluded.total_transactions,
+                    successful_transactions = excluded.successful_transactions,
+                    volume_usd = excluded.volume_usd,
+                    latency_sum_ms = excluded.latency_sum_ms,
+                    latency_samples = excluded.latency_samples,
+                    gauge_sum = excluded.gauge_sum,
+                    gauge_samples = excluded.gauge_samples,
+                    gauge_min = excluded.gauge_min,
+                    gauge_max = excluded.gauge_max,
+                    updated_at = CURRENT_TIMESTAMP
+                "#,
+            )
+            .bind(&bucket.entity_type)
+            .bind(&bucket.entity_id)
+            .bind(&bucket.resolution)
+            .bind(bucket.bucket_start)
+            .bind(bucket.total_transactions)
+            .bind(bucket.successful_transactions)
+            .bind(bucket.volume_usd)
+            .bind(bucket.latency_sum_ms)
+            .bind(bucket.latency_samples)
+            .bind(bucket.gauge_sum)
+            .bind(bucket.gauge_samples)
+            .bind(bucket.gauge_min)
+            .bind(bucket.gauge_max)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to store metric rollup")?;
+        }
+
+        tx.commit().await?;
+        Ok(buckets.len())
+    }
+
+    /// Buckets of every `entity` series at `resolution` starting in `[start, end]`
+    pub async fn buckets(
+        &self,
+        entity: RollupEntity,
+        resolution: Resolution,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<RollupBucket>> {
+        self.fetch_resolution(resolution, Some(entity), start, end)
+            .await
+    }
+
+    async fn fetch_resolution(
+        &self,
+        resolution: Resolution,
+        entity: Option<RollupEntity>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<RollupBucket>> {
+        let buckets = sqlx::query_as::<_, RollupBucket>(
+            r#"
+            SELECT entity_type, entity_id, resolution, bucket_start,
+                   total_transactions, successful_transactions, volume_usd,
+                   latency_sum_ms, latency_samples,
+                   gauge_sum, gauge_samples, gauge_min, gauge_max
+            FROM metric_rollups
+            WHERE resolution = $1 AND ($2 IS NULL OR entity_type = $2)
+              AND bucket_start >= $3 AND bucket_start <= $4
+            "#,
+        )
+        .bind(resolution.as_str())
+        .bind(entity.map(|e| e.as_str()))
+        .bind(start)
+        .bind(end)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(buckets)
+    }
+
+    /// Get a series for `[start, end]`.
+    ///
+    /// When `resolution` is `None` the finest retained resolution that fits in
+    /// `max_points` buckets is used.
+    pub async fn query(
+        &self,
+        entity: RollupEntity,
+        entity_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        resolution: Option<Resolution>,
+        max_points: Option<i64>,
+    ) -> Result<RollupSeries> {
+        let resolution = resolution.unwrap_or_else(|| {
+            self.config.select_resolution(
+                start,
+                end,
+                Utc::now(),
+                max_points.unwrap_or(self.config.max_points),
+            )
+        });
+
+        let buckets = sqlx::query_as::<_, RollupBucket>(
+            r#"
+            SELECT entity_type, entity_id, resolution, bucket_start,
+                   total_transactions, successful_transactions, volume_usd,
+                   latency_sum_ms, latency_samples,
+                   gauge_sum, gauge_samples, gauge_min, gauge_max
+            FROM metric_rollups
+            WHERE entity_type = $1 AND entity_id = $2 AND resolution = $3
+              AND bucket_start >= $4 AND bucket_start <= $5
+            ORDER BY bucket_start ASC
+            "#,
+        )
+        .bind(entity.as_str())
+        .bind(entity_id)
+        .bind(resolution.as_str())
+        .bind(resolution.bucket_start(start))
+        .bind(end)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(RollupSeries {
+            entity_type: entity,
+            entity_id: entity_id.to_string(),
+            resolution,
+            start,
+            end,
+            buckets,
+        })
+    }
+
+    /// Delete buckets older than their resolution's retention
+    pub async fn apply_retention(&self, now: DateTime<Utc>) -> Result<u64> {
+        let mut deleted = 0;
+
+        for resolution in Resolution::ALL {
+            let Some(keep) = self.config.retention(resolution) else {
+                continue;
+            };
+            deleted += sqlx::query(
+                "DELETE FROM metric_rollups WHERE resolution = $1 AND bucket_start < $2",
+            )
+            .bind(resolution.as_str())
+            .bind(resolution.bucket_start(now - keep))
+            .execute(self.db.pool())
+            .await?
+            .rows_affected();
+        }
+
+        if deleted > 0 {
+            info!("Pruned {} expired metric rollup buckets", deleted);
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_bucket_start() {
+        let t = ts("2024-03-07T13:47:31Z"); // Thursday
+        assert_eq!(
+            Resolution::FiveMinutes.bucket_start(t),
+            ts("2024-03-07T13:45:00Z")
+        );
+        assert_eq!(Resolution::Hour.bucket_start(t), ts("2024-03-07T13:00:00Z"));
+        assert_eq!(Resolution::Day.bucket_start(t), ts("2024-03-07T00:00:00Z"));
+        assert_eq!(Resolution::Week.bucket_start(t), ts("2024-03-04T00:00:00Z"));
+        assert_eq!(
+            Resolution::Week.bucket_start(ts("2024-03-04T00:00:00Z")),
+            ts("2024-03-04T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_downsample_merges_sums() {
+        let mut a = RollupBucket::empty(
+            RollupEntity::Pool,
+            "pool-1",
+            Resolution::FiveMinutes,
+            ts("2024-03-07T13:00:00Z"),
+        );
+        a.record_payment(true, 10.0, Some(100));
+        a.record_gauge(5.0);
+        let mut b = RollupBucket::empty(
+            RollupEntity::Pool,
+            "pool-1",
+            Resolution::FiveMinutes,
+            ts("2024-03-07T13:55:00Z"),
+        );
+        b.record_payment(false, 99.0, None);
+        b.record_gauge(9.0);
+
+        let hourly = downsample(&[a, b], Resolution::Hour);
+
+        assert_eq!(hourly.len(), 1);
+        let h = &hourly[0];
+        assert_eq!(h.entity_type, "pool");
+        assert_eq!(h.resolution, "1h");
+        assert_eq!(h.bucket_start, ts("2024-03-07T13:00:00Z"));
+        assert_eq!(h.total_transactions, 2);
+        assert_eq!(h.volume_usd, 10.0);
+        assert_eq!(h.success_rate(), Some(50.0));
+        assert_eq!(h.avg_latency_ms(), Some(100.0));
+        assert_eq!(h.gauge_avg(), Some(7.0));
+        assert_eq!((h.gauge_min, h.gauge_max), (Some(5.0), Some(9.0)));
+    }
+
+    #[test]
+    fn test_select_resolution() {
+        let config = RollupConfig::default();
+        let now = ts("2024-03-07T12:00:00Z");
+
+        let day = config.select_resolution(now - Duration::hours(24), now, now, 500);
+        assert_eq!(day, Resolution::FiveMinutes);
+
+        // 5m buckets exist but a week needs ~2000 of them
+        let week = config.select_resolution(now - Duration::days(7), now, now, 500);
+        assert_eq!(week, Resolution::Hour);
+
+        // Hourly buckets are pruned after 30 days
+        let quarter = config.select_resolution(now - Duration::days(90), now, now, 5000);
+        assert_eq!(quarter, Resolution::Day);
+
+        let decade = config.select_resolution(now - Duration::days(3650), now, now, 500);
+        assert_eq!(decade, Resolution::Week);
+    }
+}
@@ -0,0 +1,676 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::models::asset::{AssetId, CorridorKey};
+use crate::models::LiquidityPool;
+use crate::rpc::{Asset, OrderBook, PaymentPath, StellarRpcClient};
+use crate::services::analytics::{
+    compute_liquidity_depth, OrderBookEntry as AnalyticsOrderBookEntry, OrderBookSnapshot,
+};
+use crate::services::price_feed::PriceFeedClient;
+
+/// Days of corridor history used for a hop's success rate
+const HISTORY_WINDOW_DAYS: i64 = 30;
+/// Slippage band used when measuring order book depth for a hop
+const ORDER_BOOK_SLIPPAGE_PERCENT: f64 = 1.0;
+
+/// Direction of the amount the caller fixes when asking for a route
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteMode {
+    /// `amount` is what the sender spends; maximise what is delivered
+    #[default]
+    StrictSend,
+    /// `amount` is what the recipient gets; minimise what is spent
+    StrictReceive,
+}
+
+/// Where a candidate route came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteSource {
+    HorizonPath,
+    LiquidityPool,
+}
+
+/// Weights used to blend the ranking components into a single score
+#[derive(Debug, Clone)]
+pub struct RouteScoringWeights {
+    pub effective_rate: f64,
+    pub hop_count: f64,
+    pub success_rate: f64,
+    pub liquidity_depth: f64,
+    /// Success rate assumed for hops with no corridor history
+    pub default_success_rate: f64,
+}
+
+impl Default for RouteScoringWeights {
+    fn default() -> Self {
+        Self {
+            effective_rate: 0.5,
+            hop_count: 0.1,
+            success_rate: 0.25,
+            liquidity_depth: 0.15,
+            default_success_rate: 95.0,
+        }
+    }
+}
+
+/// A route before it is scored
+#[derive(Debug, Clone)]
+pub struct RouteCandidate {
+    pub source: RouteSource,
+    /// Assets from source to destination inclusive
+    pub hops: Vec<AssetId>,
+    pub source_amount: f64,
+    pub destination_amount: f64,
+    pub pool_id: Option<String>,
+    /// Historical success rate of the weakest hop, if any hop has history
+    pub success_rate: Option<f64>,
+    /// Liquidity depth in USD of the shallowest hop
+    pub liquidity_depth_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedRoute {
+    pub rank: usize,
+    pub source: RouteSource,
+    pub hops: Vec<AssetId>,
+    pub hop_count: usize,
+    pub source_amount: f64,
+    pub destination_amount: f64,
+    /// Destination units received per source unit spent
+    pub effective_rate: f64,
+    pub success_rate: Option<f64>,
+    pub liquidity_depth_usd: f64,
+    pub pool_id: Option<String>,
+    pub score: f64,
+}
+
+pub struct RouteFinder {
+    pool: Pool<Sqlite>,
+    rpc_client: Arc<StellarRpcClient>,
+    price_feed: Option<Arc<PriceFeedClient>>,
+    weights: RouteScoringWeights,
+}
+
+impl RouteFinder {
+    pub fn new(pool: Pool<Sqlite>, rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self {
+            pool,
+            rpc_client,
+            price_feed: None,
+            weights: RouteScoringWeights::default(),
+        }
+    }
+
+    /// Price order book liquidity in USD; without a price feed only pool
+    /// TVL counts towards a hop's depth
+    pub fn with_price_feed(mut self, price_feed: Arc<PriceFeedClient>) -> Self {
+        self.price_feed = Some(price_feed);
+        self
+    }
+
+    pub fn with_weights(mut self, weights: RouteScoringWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Find and rank routes between two assets for the given amount.
+    ///
+    /// Candidates come from Horizon path finding and from direct hops through
+    /// the liquidity pools we have synced; each hop is enriched with corridor
+    /// history and liquidity depth from the database before ranking.
+    pub async fn find_routes(
+        &self,
+        from: &AssetId,
+        to: &AssetId,
+        amount: f64,
+        mode: RouteMode,
+    ) -> Result<Vec<RankedRoute>> {
+        if amount <= 0.0 || !amount.is_finite() {
+            anyhow::bail!("Amount must be a positive number");
+        }
+        let (from_asset, to_asset) = (Asset::try_from(from)?, Asset::try_from(to)?);
+
+        let amount_str = format!("{:.7}", amount);
+        let paths = match mode {
+            RouteMode::StrictSend => {
+                self.rpc_client
+                    .fetch_strict_send_paths(&from_asset, &amount_str, &to_asset)
+                    .await
+            }
+            RouteMode::StrictReceive => {
+                self.rpc_client
+                    .fetch_strict_receive_paths(&from_asset, &to_asset, &amount_str)
+                    .await
+            }
+        };
+
+        let mut candidates: Vec<RouteCandidate> = match paths {
+            Ok(paths) => candidates_from_paths(&paths),
+            Err(e) => {
+                warn!("Horizon path finding failed, using pools only: {}", e);
+                Vec::new()
+            }
+        };
+
+        let pools = PoolSet::load(&self.pool).await?;
+        for pool in pools.for_pair(from, to) {
+            if let Some(candidate) = candidate_from_pool(pool, from, amount, mode)? {
+                candidates.push(candidate);
+            }
+        }
+
+        for candidate in &mut candidates {
+            self.enrich_candidate(candidate, &pools).await?;
+        }
+
+        Ok(rank_routes(candidates, &self.weights))
+    }
+
+    /// Fill in the weakest-hop success rate and shallowest-hop liquidity depth
+    async fn enrich_candidate(
+        &self,
+        candidate: &mut RouteCandidate,
+        pools: &PoolSet,
+    ) -> Result<()> {
+        let mut min_success: Option<f64> = None;
+        let mut min_depth: Option<f64> = None;
+
+        for pair in candidate.hops.windows(2) {
+            if let Some(rate) = self.hop_success_rate(&pair[0], &pair[1]).await? {
+                min_success = Some(min_success.map_or(rate, |m: f64| m.min(rate)));
+            }
+
+            let depth = pools.depth(&pair[0], &pair[1])
+                + self.hop_order_book_depth(&pair[0], &pair[1]).await?;
+            min_depth = Some(min_depth.map_or(depth, |m: f64| m.min(depth)));
+        }
+
+        candidate.success_rate = min_success;
+        candidate.liquidity_depth_usd = min_depth.unwrap_or(0.0);
+        Ok(())
+    }
+
+    /// Success rate of the corridor serving a hop over the last 30 days
+    async fn hop_success_rate(&self, from: &AssetId, to: &AssetId) -> Result<Option<f64>> {
+        let corridor_key = CorridorKey::new(from.clone(), to.clone()).normalized();
+        let since = Utc::now() - Duration::days(HISTORY_WINDOW_DAYS);
+
+        let (total, successful): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(total_transactions), 0),
+                COALESCE(SUM(successful_transactions), 0)
+            FROM corridor_metrics
+            WHERE corridor_key = $1 AND date >= $2
+            "#,
+        )
+        .bind(&corridor_key)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to query corridor history for route hop")?;
+
+        Ok((total > 0).then(|| successful as f64 / total as f64 * 100.0))
+    }
+
+    /// Order book liquidity in USD for a hop within `ORDER_BOOK_SLIPPAGE_PERCENT` of mid.
+    ///
+    /// Book amounts are in asset units, so the book only counts when both
+    /// assets have a USD price; otherwise the hop's depth is its pool TVL.
+    async fn hop_order_book_depth(&self, from: &AssetId, to: &AssetId) -> Result<f64> {
+        if !from.is_classic() || !to.is_classic() {
+            return Ok(0.0);
+        }
+        let Some(price_feed) = &self.price_feed else {
+            return Ok(0.0);
+        };
+        let (base_usd, counter_usd) = match (
+            price_feed.get_price(&from.key()).await,
+            price_feed.get_price(&to.key()).await,
+        ) {
+            (Ok(base_usd), Ok(counter_usd)) => (base_usd, counter_usd),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!("No USD price for order book {} -> {}: {}", from, to, e);
+                return Ok(0.0);
+            }
+        };
+        let (selling, buying) = (Asset::try_from(from)?, Asset::try_from(to)?);
+
+        let depth = match self
+            .rpc_client
+            .fetch_order_book(&selling, &buying, 20)
+            .await
+        {
+            Ok(book) => {
+                let snapshot = order_book_snapshot(&book, base_usd, counter_usd);
+                compute_liquidity_depth(&snapshot, ORDER_BOOK_SLIPPAGE_PERCENT)
+            }
+            Err(e) => {
+                warn!("Order book unavailable for {} -> {}: {}", from, to, e);
+                0.0
+            }
+        };
+        Ok(depth)
+    }
+}
+
+/// Order book valued in USD.
+///
+/// Horizon quotes ask amounts in the base asset and bid amounts in the
+/// counter asset, so each side is priced with its own asset.
+fn order_book_snapshot(book: &OrderBook, base_usd: f64, counter_usd: f64) -> OrderBookSnapshot {
+    let entries = |levels: &[crate::rpc::OrderBookEntry], unit_usd: f64| {
+        levels
+            .iter()
+            .filter_map(|level| {
+                Some(AnalyticsOrderBookEntry {
+                    price: level.price.parse().ok()?,
+                    amount_usd: level.amount.parse::<f64>().ok()? * unit_usd,
+                })
+            })
+            .collect()
+    };
+
+    OrderBookSnapshot {
+        bids: entries(&book.bids, counter_usd),
+        asks: entries(&book.asks, base_usd),
+    }
+}
+
+/// Synced liquidity pools by asset pair, loaded once per route request
+struct PoolSet {
+    by_pair: HashMap<CorridorKey, Vec<LiquidityPool>>,
+}
+
+impl PoolSet {
+    async fn load(pool: &Pool<Sqlite>) -> Result<Self> {
+        let pools = sqlx::query_as::<_, LiquidityPool>("SELECT * FROM liquidity_pools")
+            .fetch_all(pool)
+            .await
+            .context("Failed to query liquidity pools")?;
+
+        let mut by_pair: HashMap<CorridorKey, Vec<LiquidityPool>> = HashMap::new();
+        for pool in pools {
+            match pool.reserve_assets() {
+                Ok((a, b)) => by_pair
+                    .entry(CorridorKey::new(a, b).normalized())
+                    .or_default()
+                    .push(pool),
+                Err(e) => warn!(
+                    "Skipping pool {} with an invalid reserve asset: {}",
+                    pool.pool_id, e
+                ),
+            }
+        }
+
+        Ok(Self { by_pair })
+    }
+
+    /// Pools holding exactly the two given assets, in either reserve order
+    fn for_pair(&self, from: &AssetId, to: &AssetId) -> &[LiquidityPool] {
+        self.by_pair
+            .get(&CorridorKey::new(from.clone(), to.clone()).normalized())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Total value locked in pools serving a hop
+    fn depth(&self, from: &AssetId, to: &AssetId) -> f64 {
+        self.for_pair(from, to)
+            .iter()
+            .map(|p| p.total_value_usd)
+            .sum()
+    }
+}
+
+/// Liquidity pools holding exactly the two given assets, in either reserve order.
+///
+/// Contract assets have no classic pools, so they never match.
+pub async fn find_pools_for_pair(
+    pool: &Pool<Sqlite>,
+    from: &AssetId,
+    to: &AssetId,
+) -> Result<Vec<LiquidityPool>> {
+    let (Ok((from_code, from_issuer)), Ok((to_code, to_issuer))) =
+        (from.classic_parts(), to.classic_parts())
+    else {
+        return Ok(Vec::new());
+    };
+
+    let pools = sqlx::query_as::<_, LiquidityPool>(
+        r#"
+        SELECT * FROM liquidity_pools
+        WHERE (reserve_a_asset_code = $1 AND COALESCE(reserve_a_asset_issuer, 'native') = $2
+               AND reserve_b_asset_code = $3 AND COALESCE(reserve_b_asset_issuer, 'native') = $4)
+           OR (reserve_a_asset_code = $3 AND COALESCE(reserve_a_asset_issuer, 'native') = $4
+               AND reserve_b_asset_code = $1 AND COALESCE(reserve_b_asset_issuer, 'native') = $2)
+        "#,
+    )
+    .bind(from_code)
+    .bind(from_issuer)
+    .bind(to_code)
+    .bind(to_issuer)
+    .fetch_all(pool)
+    .await
+    .context("Failed to query liquidity pools for asset pair")?;
+
+    Ok(pools)
+}
+
+/// Reserves of `pool` oriented as (reserve of `from`, reserve of the other asset),
+/// with the other asset
+pub fn oriented_reserves(pool: &LiquidityPool, from: &AssetId) -> Result<(f64, f64, AssetId)> {
+    let (asset_a, asset_b) = pool.reserve_assets()?;
+    if &asset_a == from {
+        Ok((pool.reserve_a_amount, pool.reserve_b_amount, asset_b))
+    } else {
+        Ok((pool.reserve_b_amount, pool.reserve_a_amount, asset_a))
+    }
+}
+
+/// Candidates for Horizon paths; malformed path records are logged and skipped
+fn candidates_from_paths(paths: &[PaymentPath]) -> Vec<RouteCandidate> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            candidate_from_path(path)
+                .map_err(|e| warn!("Skipping invalid Horizon path: {}", e))
+                .ok()
+        })
+        .collect()
+}
+
+fn candidate_from_path(path: &PaymentPath) -> Result<RouteCandidate> {
+    let source_amount: f64 = path
+        .source_amount
+        .parse()
+        .with_context(|| format!("Invalid path source amount: {}", path.source_amount))?;
+    let destination_amount: f64 = path.destination_amount.parse().with_context(|| {
+        format!(
+            "Invalid path destination amount: {}",
+            path.destination_amount
+        )
+    })?;
+
+    let mut hops = vec![AssetId::from_horizon(
+        &path.source_asset_type,
+        path.source_asset_code.as_deref(),
+        path.source_asset_issuer.as_deref(),
+    )?];
+    for asset in &path.path {
+        hops.push(AssetId::try_from(asset)?);
+    }
+    hops.push(AssetId::from_horizon(
+        &path.destination_asset_type,
+        path.destination_asset_code.as_deref(),
+        path.destination_asset_issuer.as_deref(),
+    )?);
+
+    Ok(RouteCandidate {
+        source: RouteSource::HorizonPath,
+        hops,
+        source_amount,
+        destination_amount,
+        pool_id: None,
+        success_rate: None,
+        liquidity_depth_usd: 0.0,
+    })
+}
+
+fn candidate_from_pool(
+    pool: &LiquidityPool,
+    from: &AssetId,
+    amount: f64,
+    mode: RouteMode,
+) -> Result<Option<RouteCandidate>> {
+    let (reserve_in, reserve_out, to) = oriented_reserves(pool, from)?;
+
+    let amounts = match mode {
+        RouteMode::StrictSend => constant_product_out(reserve_in, reserve_out, amount, pool.fee_bp)
+            .map(|out| (amount, out)),
+        RouteMode::StrictReceive => {
+            constant_product_in(reserve_in, reserve_out, amount, pool.fee_bp)
+                .map(|input| (input, amount))
+        }
+    };
+
+    Ok(
+        amounts.map(|(source_amount, destination_amount)| RouteCandidate {
+            source: RouteSource::LiquidityPool,
+            hops: vec![from.clone(), to],
+            source_amount,
+            destination_amount,
+            pool_id: Some(pool.pool_id.clone()),
+            success_rate: None,
+            liquidity_depth_usd: 0.0,
+        }),
+    )
+}
+
+/// Amount received for `amount_in` from a constant product pool charging `fee_bp`
+pub fn constant_product_out(
+    reserve_in: f64,
+    reserve_out: f64,
+    amount_in: f64,
+    fee_bp: i32,
+) -> Option<f64> {
+    if reserve_in <= 0.0 || reserve_out <= 0.0 || amount_in <= 0.0 {
+        return None;
+    }
+    let effective_in = amount_in * (1.0 - fee_bp as f64 / 10_000.0);
+    Some(reserve_out * effective_in / (reserve_in + effective_in))
+}
+
+/// Amount that must be sent to receive `amount_out` from a constant product pool
+pub fn constant_product_in(
+    reserve_in: f64,
+    reserve_out: f64,
+    amount_out: f64,
+    fee_bp: i32,
+) -> Option<f64> {
+    if reserve_in <= 0.0 || reserve_out <= 0.0 || amount_out <= 0.0 || amount_out >= reserve_out {
+        return None;
+    }
+    let fee_factor = 1.0 - fee_bp as f64 / 10_000.0;
+    Some(reserve_in * amount_out / ((reserve_out - amount_out) * fee_factor))
+}
+
+/// Score and order candidates, best first.
+///
+/// The effective rate component is relative to the best rate among the
+/// candidates, so it always lies in (0, 1]; the other components are
+/// absolute and also normalised to [0, 1].
+pub fn rank_routes(
+    candidates: Vec<RouteCandidate>,
+    weights: &RouteScoringWeights,
+) -> Vec<RankedRoute> {
+    let rate = |c: &RouteCandidate| {
+        if c.source_amount > 0.0 {
+            c.destination_amount / c.source_amount
+        } else {
+            0.0
+        }
+    };
+
+    let best_rate = candidates.iter().map(rate).fold(0.0_f64, f64::max);
+
+    let mut ranked: Vec<RankedRoute> = candidates
+        .into_iter()
+        .filter(|c| rate(c) > 0.0)
+        .map(|c| {
+            // Destination per source unit is "higher is better" in both modes:
+            // strict-send delivers more, strict-receive spends less
+            let effective_rate = rate(&c);
+            let rate_score = effective_rate / best_rate;
+
+            let hop_count = c.hops.len().saturating_sub(2);
+            let hop_score = 1.0 / (1.0 + hop_count as f64);
+
+            let success_score = c.success_rate.unwrap_or(weights.default_success_rate) / 100.0;
+
+            let depth_score = if c.liquidity_depth_usd > 1.0 {
+                (c.liquidity_depth_usd.ln() / 15.0).min(1.0)
+            } else {
+                0.0
+            };
+
+            let score = (rate_score * weights.effective_rate
+                + hop_score * weights.hop_count
+                + success_score * weights.success_rate
+                + depth_score * weights.liquidity_depth)
+                * 100.0;
+
+            RankedRoute {
+                rank: 0,
+                source: c.source,
+                hop_count,
+                hops: c.hops,
+                source_amount: c.source_amount,
+                destination_amount: c.destination_amount,
+                effective_rate,
+                success_rate: c.success_rate,
+                liquidity_depth_usd: c.liquidity_depth_usd,
+                pool_id: c.pool_id,
+                score,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    for (i, route) in ranked.iter_mut().enumerate() {
+        route.rank = i + 1;
+    }
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const EURC: &str = "EURC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn candidate(hops: &[&str], source_amount: f64, destination_amount: f64) -> RouteCandidate {
+        RouteCandidate {
+            source: RouteSource::HorizonPath,
+            hops: hops.iter().map(|h| h.parse().unwrap()).collect(),
+            source_amount,
+            destination_amount,
+            pool_id: None,
+            success_rate: None,
+            liquidity_depth_usd: 0.0,
+        }
+    }
+
+    fn path(source_amount: &str, destination_code: &str) -> PaymentPath {
+        PaymentPath {
+            source_asset_type: "native".to_string(),
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: source_amount.to_string(),
+            destination_asset_type: "credit_alphanum4".to_string(),
+            destination_asset_code: Some(destination_code.to_string()),
+            destination_asset_issuer: Some(
+                "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string(),
+            ),
+            destination_amount: "95.0".to_string(),
+            path: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_invalid_paths_are_skipped() {
+        let paths = [
+            path("100.0", "USDC"),
+            path("not a number", "USDC"),
+            path("100.0", "NOT-A-VALID-CODE"),
+            path("100.0", "EURC"),
+        ];
+
+        let candidates = candidates_from_paths(&paths);
+
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[1].hops[1].code(), "EURC");
+    }
+
+    #[test]
+    fn test_constant_product_round_trip() {
+        let out = constant_product_out(1000.0, 2000.0, 10.0, 30).unwrap();
+        assert!(out > 19.0 && out < 20.0);
+
+        let back = constant_product_in(1000.0, 2000.0, out, 30).unwrap();
+        assert!((back - 10.0).abs() < 1e-9);
+
+        assert!(constant_product_in(1000.0, 2000.0, 2000.0, 30).is_none());
+    }
+
+    #[test]
+    fn test_rank_routes_prefers_better_rate() {
+        let ranked = rank_routes(
+            vec![
+                candidate(&[USDC, EURC], 100.0, 95.0),
+                candidate(&[USDC, EURC], 100.0, 99.0),
+            ],
+            &RouteScoringWeights::default(),
+        );
+
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].rank, 1);
+        assert_eq!(ranked[0].destination_amount, 99.0);
+    }
+
+    #[test]
+    fn test_rank_routes_penalises_hops_and_failures() {
+        let mut direct = candidate(&[USDC, EURC], 100.0, 99.0);
+        direct.success_rate = Some(99.0);
+        let mut via_xlm = candidate(&[USDC, "XLM:native", EURC], 100.0, 99.0);
+        via_xlm.success_rate = Some(80.0);
+
+        let ranked = rank_routes(vec![via_xlm, direct], &RouteScoringWeights::default());
+
+        assert_eq!(ranked[0].hop_count, 0);
+        assert_eq!(ranked[1].hop_count, 1);
+        assert!(ranked[0].score > ranked[1].score);
+    }
+
+    #[test]
+    fn test_rank_routes_drops_empty_quotes() {
+        let ranked = rank_routes(
+            vec![candidate(&[USDC, EURC], 100.0, 0.0)],
+            &RouteScoringWeights::default(),
+        );
+        assert!(ranked.is_empty());
+    }
+
+    #[test]
+    fn test_order_book_snapshot_prices_each_side() {
+        let level = |price: &str, amount: &str| crate::rpc::OrderBookEntry {
+            price: price.to_string(),
+            amount: amount.to_string(),
+            price_r: crate::rpc::Price { n: 1, d: 1 },
+        };
+        let book = OrderBook {
+            bids: vec![level("0.9", "90")],
+            asks: vec![level("1.1", "100")],
+            base: Asset::try_from(&"native".parse::<AssetId>().unwrap()).unwrap(),
+            counter: Asset::try_from(&USDC.parse::<AssetId>().unwrap()).unwrap(),
+        };
+
+        let snapshot = order_book_snapshot(&book, 0.1, 1.0);
+
+        // Asks are lumens at $0.10, bids are USDC at $1
+        assert_eq!(snapshot.asks[0].amount_usd, 10.0);
+        assert_eq!(snapshot.bids[0].amount_usd, 90.0);
+    }
+}
@@ -0,0 +1,597 @@
+//! SEP-10 web authentication client
+//!
+//! Obtains JWTs from an anchor's `WEB_AUTH_ENDPOINT`. Before a challenge is
+//! signed it is checked against the anchor's stellar.toml: it must be issued
+//! and signed by the `SIGNING_KEY`, name the anchor's home domain, be within
+//! its time bounds and consist only of `manage_data` operations. Tokens are
+//! cached per home domain and account until shortly before they expire.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_xdr::curr as xdr;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+use xdr::{ReadXdr, WriteXdr};
+
+use crate::services::sep_endpoint_prober::SepEndpoint;
+use crate::services::signer::{is_signed_by, transaction_hash, Keypair};
+use crate::services::stellar_toml::StellarToml;
+use crate::services::stellar_toml_crawler::StellarTomlCrawler;
+
+pub const PUBLIC_NETWORK_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+
+/// Allowed clock skew when checking a challenge's time bounds
+const TIME_BOUNDS_GRACE_SECS: u64 = 300;
+/// A challenge nonce is 48 random bytes, base64 encoded
+const NONCE_LEN: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct Sep10ClientConfig {
+    /// Network the challenges are signed for
+    pub network_passphrase: String,
+    pub timeout_secs: u64,
+    /// Lifetime assumed for tokens without an `exp` claim
+    pub default_token_ttl_secs: i64,
+    /// Cached tokens are renewed this long before they expire
+    pub renew_before_secs: i64,
+}
+
+impl Default for Sep10ClientConfig {
+    fn default() -> Self {
+        Self {
+            network_passphrase: PUBLIC_NETWORK_PASSPHRASE.to_string(),
+            timeout_secs: 30,
+            default_token_ttl_secs: 900,
+            renew_before_secs: 60,
+        }
+    }
+}
+
+/// What a challenge must match before it is signed
+#[derive(Debug, Clone)]
+pub struct ChallengeExpectations<'a> {
+    /// The anchor's `SIGNING_KEY`
+    pub server_key: [u8; 32],
+    /// The account being authenticated
+    pub client_key: [u8; 32],
+    pub home_domain: &'a str,
+    /// Host of the anchor's `WEB_AUTH_ENDPOINT`
+    pub web_auth_domain: &'a str,
+    pub network_passphrase: &'a str,
+    pub now: DateTime<Utc>,
+}
+
+/// Check a base64 challenge transaction, returning its envelope
+pub fn validate_challenge(
+    transaction: &str,
+    expected: &ChallengeExpectations<'_>,
+) -> Result<xdr::TransactionV1Envelope, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(transaction.trim())
+        .map_err(|e| format!("challenge is not base64: {}", e))?;
+    let envelope = xdr::TransactionEnvelope::from_xdr(bytes, xdr::Limits::none())
+        .map_err(|e| format!("challenge is not a transaction envelope: {}", e))?;
+    let xdr::TransactionEnvelope::Tx(envelope) = envelope else {
+        return Err("challenge is not a v1 transaction envelope".to_string());
+    };
+    let tx = &envelope.tx;
+
+    if tx.seq_num.0 != 0 {
+        return Err("challenge transaction has a non-zero sequence number".to_string());
+    }
+    let server = xdr::MuxedAccount::Ed25519(xdr::Uint256(expected.server_key));
+    if tx.source_account != server {
+        return Err("challenge is not issued by the anchor's signing key".to_string());
+    }
+
+    let time_bounds = match &tx.cond {
+        xdr::Preconditions::Time(time_bounds) => Some(time_bounds),
+        xdr::Preconditions::V2(preconditions) => preconditions.time_bounds.as_ref(),
+        xdr::Preconditions::None => None,
+    }
+    .ok_or("challenge has no time bounds")?;
+    if time_bounds.max_time.0 == 0 {
+        return Err("challenge has no upper time bound".to_string());
+    }
+    let now = expected.now.timestamp().max(0) as u64;
+    if time_bounds.min_time.0 > now + TIME_BOUNDS_GRACE_SECS
+        || time_bounds.max_time.0 + TIME_BOUNDS_GRACE_SECS < now
+    {
+        return Err("challenge is outside its time bounds".to_string());
+    }
+
+    let (first, rest) = tx
+        .operations
+        .split_first()
+        .ok_or("challenge has no operations")?;
+    let auth = manage_data(first)?;
+    let client = xdr::MuxedAccount::Ed25519(xdr::Uint256(expected.client_key));
+    if first.source_account.as_ref() != Some(&client) {
+        return Err("first operation is not sourced by the client account".to_string());
+    }
+    if auth.data_name.as_slice() != format!("{} auth", expected.home_domain).as_bytes() {
+        return Err(format!(
+            "challenge is not for home domain {}",
+            expected.home_domain
+        ));
+    }
+    if auth.data_value.as_ref().map_or(0, |nonce| nonce.len()) != NONCE_LEN {
+        return Err(format!("challenge nonce is not {} bytes", NONCE_LEN));
+    }
+
+    for op in rest {
+        let data = manage_data(op)?;
+        // Sourced by the client's domain account, which is not checked here
+        if data.data_name.as_slice() == b"client_domain" {
+            continue;
+        }
+        if op.source_account.as_ref() != Some(&server) {
+            return Err(format!(
+                "operation {} is not sourced by the anchor's signing key",
+                data.data_name.to_utf8_string_lossy()
+            ));
+        }
+        if data.data_name.as_slice() == b"web_auth_domain"
+            && data.data_value.as_ref().map(|value| value.as_slice())
+                != Some(expected.web_auth_domain.as_bytes())
+        {
+            return Err(format!(
+                "web_auth_domain does not match {}",
+                expected.web_auth_domain
+            ));
+        }
+    }
+
+    let hash = transaction_hash(tx, expected.network_passphrase).map_err(|e| e.to_string())?;
+    if !is_signed_by(&expected.server_key, &hash, &envelope.signatures) {
+        return Err("challenge is not signed by the anchor's signing key".to_string());
+    }
+
+    Ok(envelope)
+}
+
+fn manage_data(op: &xdr::Operation) -> Result<&xdr::ManageDataOp, String> {
+    match &op.body {
+        xdr::OperationBody::ManageData(data) => Ok(data),
+        other => Err(format!(
+            "challenge contains a non-manage_data operation: {}",
+            other.name()
+        )),
+    }
+}
+
+/// Expiry from a JWT's `exp` claim; the signature is the anchor's to check
+fn token_expiry(token: &str) -> Option<DateTime<Utc>> {
+    let payload = token.split('.').nth(1)?;
+    let claims: serde_json::Value = serde_json::from_slice(
+        &base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(payload.trim_end_matches('='))
+            .ok()?,
+    )
+    .ok()?;
+    DateTime::from_timestamp(claims.get("exp")?.as_i64()?, 0)
+}
+
+/// Host (and port) of a web auth endpoint, as used in `web_auth_domain`
+fn web_auth_domain(endpoint: &str) -> Result<String> {
+    let url = reqwest::Url::parse(endpoint)
+        .with_context(|| format!("Invalid WEB_AUTH_ENDPOINT {}", endpoint))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("WEB_AUTH_ENDPOINT {} has no host", endpoint))?;
+    Ok(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+/// Where and by whom an anchor's challenges are issued
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebAuthInfo {
+    pub endpoint: String,
+    pub signing_key: [u8; 32],
+}
+
+/// A JWT an anchor issued to an account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sep10Token {
+    pub token: String,
+    pub account: String,
+    pub home_domain: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChallengeResponse {
+    transaction: String,
+    #[serde(default)]
+    network_passphrase: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    token: String,
+}
+
+/// Authenticates with anchors on behalf of the backend's own account or of a
+/// key supplied per request
+pub struct Sep10Client {
+    crawler: Arc<StellarTomlCrawler>,
+    client: Client,
+    config: Sep10ClientConfig,
+    signing_key: Option<Keypair>,
+    tokens: RwLock<HashMap<(String, String), Sep10Token>>,
+}
+
+impl Sep10Client {
+    pub fn new(
+        crawler: Arc<StellarTomlCrawler>,
+        config: Sep10ClientConfig,
+        signing_key: Option<Keypair>,
+    ) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            crawler,
+            client,
+            config,
+            signing_key,
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Account of the configured key
+    pub fn account(&self) -> Option<&str> {
+        self.signing_key.as_ref().map(Keypair::account)
+    }
+
+    /// `WEB_AUTH_ENDPOINT` and `SIGNING_KEY` from a home domain's stellar.toml
+    pub async fn web_auth_info(&self, home_domain: &str) -> Result<WebAuthInfo> {
+        let toml = StellarToml::parse(&self.crawler.fetch_toml(home_domain).await?)
+            .with_context(|| format!("Invalid stellar.toml of {}", home_domain))?;
+        if let Some(passphrase) = &toml.network_passphrase {
+            if passphrase != &self.config.network_passphrase {
+                bail!("{} is on network '{}'", home_domain, passphrase);
+            }
+        }
+        let endpoint = toml
+            .web_auth_endpoint
+            .ok_or_else(|| anyhow!("{} publishes no WEB_AUTH_ENDPOINT", home_domain))?;
+        let signing_key = toml
+            .signing_key
+            .ok_or_else(|| anyhow!("{} publishes no SIGNING_KEY", home_domain))?;
+        let signing_key = stellar_strkey::ed25519::PublicKey::from_string(&signing_key)
+            .map_err(|_| anyhow!("{} publishes an invalid SIGNING_KEY", home_domain))?;
+
+        Ok(WebAuthInfo {
+            endpoint,
+            signing_key: signing_key.0,
+        })
+    }
+
+    /// Home domain that serves `url` as its `sep` endpoint: the host of the
+    /// URL, whose stellar.toml must publish it. Tokens obtained for the
+    /// domain are thereby only sent to that anchor's own servers.
+    pub async fn server_home_domain(&self, sep: SepEndpoint, url: &str) -> Result<String> {
+        let parsed =
+            reqwest::Url::parse(url.trim()).with_context(|| format!("Invalid URL {}", url))?;
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| anyhow!("{} has no host", url))?
+            .to_ascii_lowercase();
+        let host = match parsed.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host,
+        };
+        let toml = StellarToml::parse(&self.crawler.fetch_toml(&host).await?)
+            .with_context(|| format!("Invalid stellar.toml of {}", host))?;
+        let published = sep
+            .published_url(&toml)
+            .ok_or_else(|| anyhow!("{} publishes no {} endpoint", host, sep))?;
+        if published.trim().trim_end_matches('/') != url.trim().trim_end_matches('/') {
+            bail!("{} is not the {} endpoint published by {}", url, sep, host);
+        }
+
+        Ok(host)
+    }
+
+    /// Token for an account at an anchor, reused until it nears expiry.
+    ///
+    /// Without a key the configured one is used.
+    pub async fn token(&self, home_domain: &str, key: Option<&Keypair>) -> Result<Sep10Token> {
+        let key = key
+            .or(self.signing_key.as_ref())
+            .ok_or_else(|| anyhow!("No SEP-10 signing key configured"))?;
+        let home_domain = home_domain.trim().to_ascii_lowercase();
+        let cache_key = (home_domain.clone(), key.account().to_string());
+
+        let renew_at = Utc::now() + ChronoDuration::seconds(self.config.renew_before_secs);
+        if let Some(token) = self.tokens.read().await.get(&cache_key) {
+            if token.expires_at > renew_at {
+                debug!(
+                    "Using cached SEP-10 token for {} at {}",
+                    key.account(),
+                    home_domain
+                );
+                return Ok(token.clone());
+            }
+        }
+
+        let token = self.authenticate(&home_domain, key).await?;
+        self.tokens.write().await.insert(cache_key, token.clone());
+        Ok(token)
+    }
+
+    /// Run the challenge flow with an anchor, bypassing the cache
+    pub async fn authenticate(&self, home_domain: &str, key: &Keypair) -> Result<Sep10Token> {
+        let info = self.web_auth_info(home_domain).await?;
+        let web_auth_domain = web_auth_domain(&info.endpoint)?;
+
+        let response = self
+            .client
+            .get(&info.endpoint)
+            .query(&[("account", key.account()), ("home_domain", home_domain)])
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch challenge from {}", info.endpoint))?;
+        if !response.status().is_success() {
+            bail!(
+                "{} returned {} for the challenge",
+                info.endpoint,
+                response.status()
+            );
+        }
+        let challenge: ChallengeResponse = response
+            .json()
+            .await
+            .with_context(|| format!("Invalid challenge response from {}", info.endpoint))?;
+        if let Some(passphrase) = &challenge.network_passphrase {
+            if passphrase != &self.config.network_passphrase {
+                bail!(
+                    "{} issued a challenge for network '{}'",
+                    home_domain,
+                    passphrase
+                );
+            }
+        }
+
+        let mut envelope = validate_challenge(
+            &challenge.transaction,
+            &ChallengeExpectations {
+                server_key: info.signing_key,
+                client_key: key.public_key(),
+                home_domain,
+                web_auth_domain: &web_auth_domain,
+                network_passphrase: &self.config.network_passphrase,
+                now: Utc::now(),
+            },
+        )
+        .map_err(|e| anyhow!("Rejected challenge from {}: {}", home_domain, e))?;
+
+        let hash = transaction_hash(&envelope.tx, &self.config.network_passphrase)?;
+        let mut signatures = envelope.signatures.to_vec();
+        signatures.push(key.sign_decorated(&hash));
+        envelope.signatures = signatures
+            .try_into()
+            .map_err(|_| anyhow!("Challenge from {} has too many signatures", home_domain))?;
+        let signed = base64::engine::general_purpose::STANDARD
+            .encode(xdr::TransactionEnvelope::Tx(envelope).to_xdr(xdr::Limits::none())?);
+
+        let response = self
+            .client
+            .post(&info.endpoint)
+            .json(&serde_json::json!({ "transaction": signed }))
+            .send()
+            .await
+            .with_context(|| format!("Failed to submit challenge to {}", info.endpoint))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!(
+                "{} rejected the signed challenge ({}): {}",
+                info.endpoint,
+                status,
+                body
+            );
+        }
+        let TokenResponse { token } = response
+            .json()
+            .await
+            .with_context(|| format!("Invalid token response from {}", info.endpoint))?;
+
+        let expires_at = token_expiry(&token).unwrap_or_else(|| {
+            Utc::now() + ChronoDuration::seconds(self.config.default_token_ttl_secs)
+        });
+        info!(
+            "Obtained SEP-10 token for {} from {}",
+            key.account(),
+            home_domain
+        );
+
+        Ok(Sep10Token {
+            token,
+            account: key.account().to_string(),
+            home_domain: home_domain.to_string(),
+            expires_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETWORK: &str = "Test SDF Network ; September 2015";
+
+    fn keypair(seed: u8) -> Keypair {
+        Keypair::from_secret(&stellar_strkey::ed25519::PrivateKey([seed; 32]).to_string()).unwrap()
+    }
+
+    fn data_op(source: &Keypair, name: &str, value: &[u8]) -> xdr::Operation {
+        xdr::Operation {
+            source_account: Some(xdr::MuxedAccount::Ed25519(xdr::Uint256(
+                source.public_key(),
+            ))),
+            body: xdr::OperationBody::ManageData(xdr::ManageDataOp {
+                data_name: xdr::String64(name.try_into().unwrap()),
+                data_value: Some(xdr::DataValue(value.to_vec().try_into().unwrap())),
+            }),
+        }
+    }
+
+    fn challenge(
+        server: &Keypair,
+        operations: Vec<xdr::Operation>,
+        max_time: u64,
+        sign: bool,
+    ) -> String {
+        let tx = xdr::Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(server.public_key())),
+            fee: 100 * operations.len() as u32,
+            seq_num: xdr::SequenceNumber(0),
+            cond: xdr::Preconditions::Time(xdr::TimeBounds {
+                min_time: xdr::TimePoint(0),
+                max_time: xdr::TimePoint(max_time),
+            }),
+            memo: xdr::Memo::None,
+            operations: operations.try_into().unwrap(),
+            ext: xdr::TransactionExt::V0,
+        };
+        let signatures = if sign {
+            vec![server.sign_decorated(&transaction_hash(&tx, NETWORK).unwrap())]
+        } else {
+            Vec::new()
+        };
+        let envelope = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx,
+            signatures: signatures.try_into().unwrap(),
+        });
+        base64::engine::general_purpose::STANDARD
+            .encode(envelope.to_xdr(xdr::Limits::none()).unwrap())
+    }
+
+    #[test]
+    fn test_validate_challenge() {
+        let server = keypair(1);
+        let client = keypair(2);
+        let now = Utc::now();
+        let max_time = now.timestamp() as u64 + 900;
+        let nonce = [b'n'; NONCE_LEN];
+        let expected = ChallengeExpectations {
+            server_key: server.public_key(),
+            client_key: client.public_key(),
+            home_domain: "anchor.example",
+            web_auth_domain: "auth.anchor.example",
+            network_passphrase: NETWORK,
+            now,
+        };
+        let operations = || {
+            vec![
+                data_op(&client, "anchor.example auth", &nonce),
+                data_op(&server, "web_auth_domain", b"auth.anchor.example"),
+            ]
+        };
+
+        let envelope =
+            validate_challenge(&challenge(&server, operations(), max_time, true), &expected)
+                .unwrap();
+        assert_eq!(envelope.tx.operations.len(), 2);
+
+        let rejected =
+            |transaction: String| validate_challenge(&transaction, &expected).unwrap_err();
+        assert_eq!(
+            rejected(challenge(&server, operations(), max_time, false)),
+            "challenge is not signed by the anchor's signing key"
+        );
+        assert_eq!(
+            rejected(challenge(&client, operations(), max_time, true)),
+            "challenge is not issued by the anchor's signing key"
+        );
+        assert_eq!(
+            rejected(challenge(
+                &server,
+                operations(),
+                now.timestamp() as u64 - 3600,
+                true
+            )),
+            "challenge is outside its time bounds"
+        );
+        assert_eq!(
+            rejected(challenge(
+                &server,
+                vec![data_op(&client, "evil.example auth", &nonce)],
+                max_time,
+                true
+            )),
+            "challenge is not for home domain anchor.example"
+        );
+        assert_eq!(
+            rejected(challenge(
+                &server,
+                vec![
+                    data_op(&client, "anchor.example auth", &nonce),
+                    data_op(&client, "web_auth_domain", b"auth.anchor.example"),
+                ],
+                max_time,
+                true
+            )),
+            "operation web_auth_domain is not sourced by the anchor's signing key"
+        );
+        assert_eq!(
+            rejected(challenge(
+                &server,
+                vec![
+                    data_op(&client, "anchor.example auth", &nonce),
+                    data_op(&server, "web_auth_domain", b"evil.example"),
+                ],
+                max_time,
+                true
+            )),
+            "web_auth_domain does not match auth.anchor.example"
+        );
+
+        let mut payment = data_op(&client, "anchor.example auth", &nonce);
+        payment.body = xdr::OperationBody::Inflation;
+        assert_eq!(
+            rejected(challenge(&server, vec![payment], max_time, true)),
+            "challenge contains a non-manage_data operation: Inflation"
+        );
+    }
+
+    #[test]
+    fn test_token_expiry() {
+        let encode =
+            |json: &str| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json.as_bytes());
+        let token = format!(
+            "{}.{}.signature",
+            encode(r#"{"alg":"HS256"}"#),
+            encode(r#"{"sub":"G...","exp":1700000000}"#)
+        );
+        assert_eq!(
+            token_expiry(&token),
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+        assert_eq!(token_expiry("opaque-token"), None);
+    }
+
+    #[test]
+    fn test_web_auth_domain() {
+        assert_eq!(
+            web_auth_domain("https://auth.anchor.example/sep10").unwrap(),
+            "auth.anchor.example"
+        );
+        assert_eq!(
+            web_auth_domain("http://127.0.0.1:8000/auth").unwrap(),
+            "127.0.0.1:8000"
+        );
+    }
+}
@@ -0,0 +1,521 @@
+//! SEP-10 web authentication server
+//!
+//! Issues challenge transactions signed by the server key and verifies the
+//! signed challenges clients return. A challenge must carry signatures from
+//! the account's signers worth at least its medium threshold, so multisig
+//! accounts authenticate with several keys. Accounts that do not exist yet
+//! must sign with their master key.
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use stellar_xdr::curr as xdr;
+use tokio::sync::RwLock;
+use xdr::WriteXdr;
+
+use crate::rpc::{HorizonAccount, StellarRpcClient};
+use crate::services::sep10_client::{
+    validate_challenge, ChallengeExpectations, PUBLIC_NETWORK_PASSPHRASE,
+};
+use crate::services::signer::{is_signed_by, transaction_hash, Keypair};
+
+/// Random bytes in a challenge nonce, 64 once base64 encoded
+const NONCE_BYTES: usize = 48;
+
+#[derive(Debug, Clone)]
+pub struct Sep10ServerConfig {
+    pub network_passphrase: String,
+    /// Domain clients authenticate with, named in the first operation
+    pub home_domain: String,
+    /// Host serving the web auth endpoint
+    pub web_auth_domain: String,
+    /// How long a challenge can be signed and returned
+    pub challenge_ttl_secs: u64,
+}
+
+impl Default for Sep10ServerConfig {
+    fn default() -> Self {
+        Self {
+            network_passphrase: PUBLIC_NETWORK_PASSPHRASE.to_string(),
+            home_domain: "localhost".to_string(),
+            web_auth_domain: "localhost".to_string(),
+            challenge_ttl_secs: 900,
+        }
+    }
+}
+
+/// A challenge for the client to sign
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sep10Challenge {
+    /// Base64 transaction envelope
+    pub transaction: String,
+    pub network_passphrase: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sep10ServerError {
+    /// The request or challenge is malformed or not one of ours
+    InvalidRequest(String),
+    /// The challenge is not signed with enough weight
+    Unauthorized(String),
+    /// The account's signers could not be looked up
+    Horizon(String),
+}
+
+impl fmt::Display for Sep10ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sep10ServerError::InvalidRequest(msg)
+            | Sep10ServerError::Unauthorized(msg)
+            | Sep10ServerError::Horizon(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Sep10ServerError {}
+
+/// Weight a signer carries and the weight the client's signatures must reach
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SignerSet {
+    signers: Vec<([u8; 32], u8)>,
+    threshold: u8,
+}
+
+impl SignerSet {
+    /// Ed25519 signers of an existing account, against its medium threshold
+    fn of_account(account: &HorizonAccount) -> Self {
+        let signers = account
+            .signers
+            .iter()
+            .filter(|signer| signer.signer_type == "ed25519_public_key" && signer.weight > 0)
+            .filter_map(|signer| {
+                stellar_strkey::ed25519::PublicKey::from_string(&signer.key)
+                    .ok()
+                    .map(|key| (key.0, signer.weight))
+            })
+            .collect();
+        Self {
+            signers,
+            threshold: account.thresholds.med_threshold,
+        }
+    }
+
+    /// An account that does not exist is controlled by its master key
+    fn master_key(key: [u8; 32]) -> Self {
+        Self {
+            signers: vec![(key, 1)],
+            threshold: 1,
+        }
+    }
+
+    /// Combined weight of the client signatures, rejecting any signature
+    /// that is not from a signer or repeats one
+    fn weigh(
+        &self,
+        hash: &[u8; 32],
+        signatures: &[xdr::DecoratedSignature],
+    ) -> Result<u32, Sep10ServerError> {
+        let mut used: Vec<[u8; 32]> = Vec::new();
+        let mut weight = 0u32;
+        for signature in signatures {
+            let signer = self
+                .signers
+                .iter()
+                .find(|(key, _)| is_signed_by(key, hash, std::slice::from_ref(signature)))
+                .ok_or_else(|| {
+                    Sep10ServerError::Unauthorized(
+                        "challenge has a signature that is not from an account signer".to_string(),
+                    )
+                })?;
+            if used.contains(&signer.0) {
+                return Err(Sep10ServerError::Unauthorized(
+                    "challenge is signed twice by the same signer".to_string(),
+                ));
+            }
+            used.push(signer.0);
+            weight += u32::from(signer.1);
+        }
+        Ok(weight)
+    }
+}
+
+/// Authenticates Stellar accounts for our own API through SEP-10
+pub struct Sep10Server {
+    signing_key: Keypair,
+    rpc_client: Arc<StellarRpcClient>,
+    config: Sep10ServerConfig,
+    rng: SystemRandom,
+    /// Hashes of redeemed challenges and when they expire
+    redeemed: RwLock<HashMap<[u8; 32], u64>>,
+}
+
+impl Sep10Server {
+    pub fn new(
+        signing_key: Keypair,
+        rpc_client: Arc<StellarRpcClient>,
+        config: Sep10ServerConfig,
+    ) -> Self {
+        Self {
+            signing_key,
+            rpc_client,
+            config,
+            rng: SystemRandom::new(),
+            redeemed: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Account whose key signs challenges, to be published as `SIGNING_KEY`
+    pub fn signing_account(&self) -> &str {
+        self.signing_key.account()
+    }
+
+    /// Build and sign a challenge for `account`
+    pub fn challenge(
+        &self,
+        account: &str,
+        home_domain: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Sep10Challenge, Sep10ServerError> {
+        let client = stellar_strkey::ed25519::PublicKey::from_string(account)
+            .map_err(|_| {
+                Sep10ServerError::InvalidRequest(format!("{} is not a Stellar account", account))
+            })?
+            .0;
+        if let Some(home_domain) = home_domain {
+            if home_domain != self.config.home_domain {
+                return Err(Sep10ServerError::InvalidRequest(format!(
+                    "home_domain must be {}",
+                    self.config.home_domain
+                )));
+            }
+        }
+
+        let mut nonce = [0u8; NONCE_BYTES];
+        self.rng.fill(&mut nonce).map_err(|_| {
+            Sep10ServerError::InvalidRequest("failed to generate a nonce".to_string())
+        })?;
+        let nonce = base64::engine::general_purpose::STANDARD.encode(nonce);
+
+        let server = self.signing_key.public_key();
+        let now = now.timestamp().max(0) as u64;
+        let tx = xdr::Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(server)),
+            fee: 200,
+            seq_num: xdr::SequenceNumber(0),
+            cond: xdr::Preconditions::Time(xdr::TimeBounds {
+                min_time: xdr::TimePoint(now),
+                max_time: xdr::TimePoint(now + self.config.challenge_ttl_secs),
+            }),
+            memo: xdr::Memo::None,
+            operations: vec![
+                manage_data(
+                    client,
+                    &format!("{} auth", self.config.home_domain),
+                    nonce.as_bytes(),
+                )?,
+                manage_data(
+                    server,
+                    "web_auth_domain",
+                    self.config.web_auth_domain.as_bytes(),
+                )?,
+            ]
+            .try_into()
+            .map_err(|_| Sep10ServerError::InvalidRequest("too many operations".to_string()))?,
+            ext: xdr::TransactionExt::V0,
+        };
+
+        let hash = transaction_hash(&tx, &self.config.network_passphrase)
+            .map_err(|e| Sep10ServerError::InvalidRequest(e.to_string()))?;
+        let envelope = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx,
+            signatures: vec![self.signing_key.sign_decorated(&hash)]
+                .try_into()
+                .map_err(|_| Sep10ServerError::InvalidRequest("too many signatures".to_string()))?,
+        });
+        let transaction = envelope
+            .to_xdr(xdr::Limits::none())
+            .map_err(|e| Sep10ServerError::InvalidRequest(e.to_string()))?;
+
+        Ok(Sep10Challenge {
+            transaction: base64::engine::general_purpose::STANDARD.encode(transaction),
+            network_passphrase: self.config.network_passphrase.clone(),
+        })
+    }
+
+    /// Verify a signed challenge, returning the authenticated account.
+    ///
+    /// Each challenge can be redeemed once.
+    pub async fn verify(
+        &self,
+        transaction: &str,
+        now: DateTime<Utc>,
+    ) -> Result<String, Sep10ServerError> {
+        let client = challenge_client(transaction)?;
+        let envelope = validate_challenge(
+            transaction,
+            &ChallengeExpectations {
+                server_key: self.signing_key.public_key(),
+                client_key: client,
+                home_domain: &self.config.home_domain,
+                web_auth_domain: &self.config.web_auth_domain,
+                network_passphrase: &self.config.network_passphrase,
+                now,
+            },
+        )
+        .map_err(Sep10ServerError::InvalidRequest)?;
+
+        // Our own challenges get no grace past their upper bound
+        let now = now.timestamp().max(0) as u64;
+        let max_time = match &envelope.tx.cond {
+            xdr::Preconditions::Time(time_bounds) => time_bounds.max_time.0,
+            _ => 0,
+        };
+        if now > max_time {
+            return Err(Sep10ServerError::InvalidRequest(
+                "challenge has expired".to_string(),
+            ));
+        }
+
+        let hash = transaction_hash(&envelope.tx, &self.config.network_passphrase)
+            .map_err(|e| Sep10ServerError::InvalidRequest(e.to_string()))?;
+        let server = self.signing_key.public_key();
+        let client_signatures: Vec<xdr::DecoratedSignature> = envelope
+            .signatures
+            .iter()
+            .filter(|signature| !is_signed_by(&server, &hash, std::slice::from_ref(*signature)))
+            .cloned()
+            .collect();
+        if client_signatures.is_empty() {
+            return Err(Sep10ServerError::Unauthorized(
+                "challenge is not signed by the client".to_string(),
+            ));
+        }
+
+        let account = stellar_strkey::ed25519::PublicKey(client).to_string();
+        let signers = match self
+            .rpc_client
+            .fetch_account_if_exists(&account)
+            .await
+            .map_err(|e| Sep10ServerError::Horizon(e.to_string()))?
+        {
+            Some(horizon_account) => SignerSet::of_account(&horizon_account),
+            None => SignerSet::master_key(client),
+        };
+        let weight = signers.weigh(&hash, &client_signatures)?;
+        if weight < u32::from(signers.threshold.max(1)) {
+            return Err(Sep10ServerError::Unauthorized(format!(
+                "signature weight {} is below the threshold {}",
+                weight, signers.threshold
+            )));
+        }
+
+        let mut redeemed = self.redeemed.write().await;
+        redeemed.retain(|_, expires| *expires >= now);
+        if redeemed.insert(hash, max_time).is_some() {
+            return Err(Sep10ServerError::Unauthorized(
+                "challenge has already been used".to_string(),
+            ));
+        }
+
+        Ok(account)
+    }
+}
+
+fn manage_data(
+    source: [u8; 32],
+    name: &str,
+    value: &[u8],
+) -> Result<xdr::Operation, Sep10ServerError> {
+    let invalid = |what: &str| Sep10ServerError::InvalidRequest(format!("{} is too long", what));
+    Ok(xdr::Operation {
+        source_account: Some(xdr::MuxedAccount::Ed25519(xdr::Uint256(source))),
+        body: xdr::OperationBody::ManageData(xdr::ManageDataOp {
+            data_name: xdr::String64(name.try_into().map_err(|_| invalid(name))?),
+            data_value: Some(xdr::DataValue(
+                value.to_vec().try_into().map_err(|_| invalid(name))?,
+            )),
+        }),
+    })
+}
+
+/// Account named as the source of a challenge's first operation
+fn challenge_client(transaction: &str) -> Result<[u8; 32], Sep10ServerError> {
+    use xdr::ReadXdr;
+
+    let invalid = || Sep10ServerError::InvalidRequest("challenge is malformed".to_string());
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(transaction.trim())
+        .map_err(|_| invalid())?;
+    let xdr::TransactionEnvelope::Tx(envelope) =
+        xdr::TransactionEnvelope::from_xdr(bytes, xdr::Limits::none()).map_err(|_| invalid())?
+    else {
+        return Err(invalid());
+    };
+    match envelope.tx.operations.first().map(|op| &op.source_account) {
+        Some(Some(xdr::MuxedAccount::Ed25519(xdr::Uint256(key)))) => Ok(*key),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{HorizonSigner, HorizonThresholds};
+    use xdr::ReadXdr;
+
+    const NETWORK: &str = "Test SDF Network ; September 2015";
+
+    fn keypair(seed: u8) -> Keypair {
+        Keypair::from_secret(&stellar_strkey::ed25519::PrivateKey([seed; 32]).to_string()).unwrap()
+    }
+
+    fn server() -> Sep10Server {
+        Sep10Server::new(
+            keypair(1),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            Sep10ServerConfig {
+                network_passphrase: NETWORK.to_string(),
+                home_domain: "insights.example".to_string(),
+                web_auth_domain: "api.insights.example".to_string(),
+                ..Sep10ServerConfig::default()
+            },
+        )
+    }
+
+    /// Add signatures to a challenge the way a wallet would
+    fn sign(transaction: &str, keys: &[&Keypair]) -> String {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(transaction)
+            .unwrap();
+        let xdr::TransactionEnvelope::Tx(mut envelope) =
+            xdr::TransactionEnvelope::from_xdr(bytes, xdr::Limits::none()).unwrap()
+        else {
+            panic!("not a v1 envelope");
+        };
+        let hash = transaction_hash(&envelope.tx, NETWORK).unwrap();
+        let mut signatures = envelope.signatures.to_vec();
+        signatures.extend(keys.iter().map(|key| key.sign_decorated(&hash)));
+        envelope.signatures = signatures.try_into().unwrap();
+        let signed = xdr::TransactionEnvelope::Tx(envelope)
+            .to_xdr(xdr::Limits::none())
+            .unwrap();
+        base64::engine::general_purpose::STANDARD.encode(signed)
+    }
+
+    #[test]
+    fn test_challenge_is_valid_for_client() {
+        let server = server();
+        let client = keypair(2);
+        let now = Utc::now();
+        let challenge = server
+            .challenge(client.account(), Some("insights.example"), now)
+            .unwrap();
+        assert_eq!(challenge.network_passphrase, NETWORK);
+
+        // What a SEP-10 client checks before signing
+        validate_challenge(
+            &challenge.transaction,
+            &ChallengeExpectations {
+                server_key: keypair(1).public_key(),
+                client_key: client.public_key(),
+                home_domain: "insights.example",
+                web_auth_domain: "api.insights.example",
+                network_passphrase: NETWORK,
+                now,
+            },
+        )
+        .unwrap();
+
+        assert!(matches!(
+            server.challenge(client.account(), Some("other.example"), now),
+            Err(Sep10ServerError::InvalidRequest(_))
+        ));
+        assert!(server.challenge("GNOTANACCOUNT", None, now).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_master_key_once() {
+        let server = server();
+        let client = keypair(2);
+        let now = Utc::now();
+        let challenge = server.challenge(client.account(), None, now).unwrap();
+
+        let unsigned = sign(&challenge.transaction, &[]);
+        assert_eq!(
+            server.verify(&unsigned, now).await,
+            Err(Sep10ServerError::Unauthorized(
+                "challenge is not signed by the client".to_string()
+            ))
+        );
+        let wrong = sign(&challenge.transaction, &[&keypair(3)]);
+        assert!(matches!(
+            server.verify(&wrong, now).await,
+            Err(Sep10ServerError::Unauthorized(_))
+        ));
+
+        let signed = sign(&challenge.transaction, &[&client]);
+        assert_eq!(server.verify(&signed, now).await.unwrap(), client.account());
+        assert_eq!(
+            server.verify(&signed, now).await,
+            Err(Sep10ServerError::Unauthorized(
+                "challenge has already been used".to_string()
+            ))
+        );
+
+        let later = now + chrono::Duration::seconds(901);
+        let challenge = server.challenge(client.account(), None, now).unwrap();
+        let signed = sign(&challenge.transaction, &[&client]);
+        assert_eq!(
+            server.verify(&signed, later).await,
+            Err(Sep10ServerError::InvalidRequest(
+                "challenge has expired".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_signer_weights() {
+        let account = HorizonAccount {
+            account_id: keypair(2).account().to_string(),
+            sequence: "1".to_string(),
+            home_domain: None,
+            balances: Vec::new(),
+            signers: vec![
+                HorizonSigner {
+                    key: keypair(2).account().to_string(),
+                    weight: 0,
+                    signer_type: "ed25519_public_key".to_string(),
+                },
+                HorizonSigner {
+                    key: keypair(3).account().to_string(),
+                    weight: 1,
+                    signer_type: "ed25519_public_key".to_string(),
+                },
+                HorizonSigner {
+                    key: keypair(4).account().to_string(),
+                    weight: 2,
+                    signer_type: "ed25519_public_key".to_string(),
+                },
+            ],
+            thresholds: HorizonThresholds {
+                low_threshold: 1,
+                med_threshold: 3,
+                high_threshold: 3,
+            },
+        };
+        let signers = SignerSet::of_account(&account);
+        assert_eq!(signers.threshold, 3);
+        assert_eq!(signers.signers.len(), 2);
+
+        let hash = [7u8; 32];
+        let signature = |seed: u8| keypair(seed).sign_decorated(&hash);
+        assert_eq!(signers.weigh(&hash, &[signature(3), signature(4)]), Ok(3));
+        // The master key was given weight 0
+        assert!(signers.weigh(&hash, &[signature(2)]).is_err());
+        assert!(signers.weigh(&hash, &[signature(4), signature(4)]).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! SEP-38 (Anchor RFQ API) client
+//!
+//! Typed requests against an anchor's `ANCHOR_QUOTE_SERVER`: the assets it
+//! exchanges (`/info`), indicative prices (`/prices`, `/price`) and firm
+//! quotes (`/quote`). Amounts and prices are decimal strings, as on the wire.
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// Contexts a price or quote can be requested for
+pub const QUOTE_CONTEXTS: [&str; 3] = ["sep6", "sep24", "sep31"];
+
+#[derive(Debug, Clone)]
+pub struct Sep38ClientConfig {
+    pub timeout_secs: u64,
+}
+
+impl Default for Sep38ClientConfig {
+    fn default() -> Self {
+        Self { timeout_secs: 30 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryMethod {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// An asset the anchor exchanges, in SEP-38 asset identification format
+/// (`stellar:USDC:G...` or `iso4217:BRL`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sep38Asset {
+    pub asset: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_delivery_methods: Option<Vec<DeliveryMethod>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_delivery_methods: Option<Vec<DeliveryMethod>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_codes: Option<Vec<String>>,
+}
+
+/// `GET /info`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sep38Info {
+    pub assets: Vec<Sep38Asset>,
+}
+
+impl Sep38Info {
+    /// Whether both assets of a pair are offered
+    pub fn supports(&self, sell_asset: &str, buy_asset: &str) -> bool {
+        let listed = |asset: &str| self.assets.iter().any(|a| a.asset == asset);
+        listed(sell_asset) && listed(buy_asset)
+    }
+}
+
+/// `GET /prices` parameters; either the sell or the buy side is given
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PricesRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_asset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_amount: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_asset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_amount: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_delivery_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_delivery_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetPrice {
+    pub asset: String,
+    pub price: String,
+    pub decimals: u8,
+}
+
+/// `GET /prices`: what the given asset buys, or what buys it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PricesResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_assets: Option<Vec<AssetPrice>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_assets: Option<Vec<AssetPrice>>,
+}
+
+/// `GET /price` parameters; exactly one of the amounts is given
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceRequest {
+    /// `sep6`, `sep24` or `sep31`
+    pub context: String,
+    pub sell_asset: String,
+    pub buy_asset: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_amount: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_amount: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_delivery_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_delivery_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeDetail {
+    pub name: String,
+    pub amount: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Fee charged on a price or quote
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sep38Fee {
+    pub total: String,
+    pub asset: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Vec<FeeDetail>>,
+}
+
+/// `GET /price`: an indicative price. `total_price` includes the fee, so
+/// `buy_amount` is what is delivered for `sell_amount`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceResponse {
+    pub total_price: String,
+    pub price: String,
+    pub sell_amount: String,
+    pub buy_amount: String,
+    pub fee: Sep38Fee,
+}
+
+/// `POST /quote` body
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuoteRequest {
+    #[serde(flatten)]
+    pub price: PriceRequest,
+    /// Earliest expiry the client accepts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expire_after: Option<DateTime<Utc>>,
+}
+
+/// A firm quote, honoured by the anchor until `expires_at`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteResponse {
+    pub id: String,
+    pub expires_at: DateTime<Utc>,
+    pub total_price: String,
+    pub price: String,
+    pub sell_asset: String,
+    pub sell_amount: String,
+    pub buy_asset: String,
+    pub buy_amount: String,
+    pub fee: Sep38Fee,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_delivery_method: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_delivery_method: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Sep38Error {
+    /// The request failed or the response was not a SEP-38 body
+    Request(String),
+    /// The anchor answered with an error status
+    Anchor(u16, Value),
+}
+
+impl fmt::Display for Sep38Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sep38Error::Request(msg) => f.write_str(msg),
+            Sep38Error::Anchor(status, body) => match body.get("error").and_then(Value::as_str) {
+                Some(error) => write!(f, "HTTP {}: {}", status, error),
+                None => write!(f, "HTTP {}", status),
+            },
+        }
+    }
+}
+
+impl std::error::Error for Sep38Error {}
+
+/// Client for anchors' SEP-38 quote servers
+pub struct Sep38Client {
+    client: Client,
+}
+
+impl Sep38Client {
+    pub fn new(config: Sep38ClientConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self { client }
+    }
+
+    /// Assets exchanged by the quote server
+    pub async fn info(&self, quote_server: &str) -> Result<Sep38Info, Sep38Error> {
+        self.send(self.client.get(url(quote_server, "/info")), None)
+            .await
+    }
+
+    /// Indicative prices of every asset the given amount can be exchanged for
+    pub async fn prices(
+        &self,
+        quote_server: &str,
+        request: &PricesRequest,
+        jwt: Option<&str>,
+    ) -> Result<PricesResponse, Sep38Error> {
+        let request = self.client.get(url(quote_server, "/prices")).query(request);
+        self.send(request, jwt).await
+    }
+
+    /// Indicative price of one pair
+    pub async fn price(
+        &self,
+        quote_server: &str,
+        request: &PriceRequest,
+        jwt: Option<&str>,
+    ) -> Result<PriceResponse, Sep38Error> {
+        let request = self.client.get(url(quote_server, "/price")).query(request);
+        self.send(request, jwt).await
+    }
+
+    /// Request a firm quote; anchors require a SEP-10 token
+    pub async fn post_quote(
+        &self,
+        quote_server: &str,
+        request: &QuoteRequest,
+        jwt: Option<&str>,
+    ) -> Result<QuoteResponse, Sep38Error> {
+        let request = self.client.post(url(quote_server, "/quote")).json(request);
+        self.send(request, jwt).await
+    }
+
+    /// Look up a firm quote issued earlier
+    pub async fn get_quote(
+        &self,
+        quote_server: &str,
+        id: &str,
+        jwt: Option<&str>,
+    ) -> Result<QuoteResponse, Sep38Error> {
+        let path = format!("/quote/{}", urlencoding::encode(id));
+        self.send(self.client.get(url(quote_server, &path)), jwt)
+            .await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        mut request: RequestBuilder,
+        jwt: Option<&str>,
+    ) -> Result<T, Sep38Error> {
+        if let Some(jwt) = jwt {
+            request = request.bearer_auth(jwt);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Sep38Error::Request(e.to_string()))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| Sep38Error::Request(e.to_string()))?;
+        if !status.is_success() {
+            // Keep the anchor's status even when its error body is not JSON
+            let body = serde_json::from_str(&text).unwrap_or_else(|_| {
+                let reason = status.canonical_reason().unwrap_or_default();
+                serde_json::json!({ "error": if text.trim().is_empty() { reason } else { text.trim() } })
+            });
+            return Err(Sep38Error::Anchor(status.as_u16(), body));
+        }
+
+        serde_json::from_str(&text)
+            .map_err(|e| Sep38Error::Request(format!("invalid SEP-38 response: {}", e)))
+    }
+}
+
+fn url(quote_server: &str, path: &str) -> String {
+    format!("{}{}", quote_server.trim().trim_end_matches('/'), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_info_supports_pair() {
+        let info: Sep38Info = serde_json::from_value(json!({
+            "assets": [
+                { "asset": "stellar:USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN" },
+                {
+                    "asset": "iso4217:BRL",
+                    "country_codes": ["BRA"],
+                    "buy_delivery_methods": [{ "name": "PIX", "description": "Instant transfer" }]
+                }
+            ]
+        }))
+        .unwrap();
+        let usdc = "stellar:USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+        assert!(info.supports(usdc, "iso4217:BRL"));
+        assert!(!info.supports(usdc, "iso4217:ARS"));
+        assert_eq!(
+            info.assets[1].buy_delivery_methods.as_ref().unwrap()[0].name,
+            "PIX"
+        );
+    }
+
+    #[test]
+    fn test_quote_request_is_flat() {
+        let request = QuoteRequest {
+            price: PriceRequest {
+                context: "sep31".to_string(),
+                sell_asset: "iso4217:USD".to_string(),
+                buy_asset: "iso4217:BRL".to_string(),
+                sell_amount: Some("100".to_string()),
+                ..PriceRequest::default()
+            },
+            expire_after: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "context": "sep31",
+                "sell_asset": "iso4217:USD",
+                "buy_asset": "iso4217:BRL",
+                "sell_amount": "100"
+            })
+        );
+    }
+
+    #[test]
+    fn test_anchor_error_display() {
+        let error = Sep38Error::Anchor(400, json!({ "error": "unsupported pair" }));
+        assert_eq!(error.to_string(), "HTTP 400: unsupported pair");
+        assert_eq!(Sep38Error::Anchor(503, json!({})).to_string(), "HTTP 503");
+    }
+}
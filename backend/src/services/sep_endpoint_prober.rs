@@ -0,0 +1,650 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use stellar_xdr::curr as xdr;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::database::Database;
+use crate::models::Anchor;
+use crate::services::stellar_toml::StellarToml;
+use crate::services::stellar_toml_crawler::AnchorTomlInfo;
+
+/// Largest response body read from a probed endpoint
+const MAX_PROBE_RESPONSE_BYTES: usize = 256 * 1024;
+
+/// SEP endpoint kinds that are probed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SepEndpoint {
+    /// SEP-6 `TRANSFER_SERVER`
+    Sep6,
+    /// SEP-10 `WEB_AUTH_ENDPOINT`
+    Sep10,
+    /// SEP-24 `TRANSFER_SERVER_SEP0024`
+    Sep24,
+    /// SEP-31 `DIRECT_PAYMENT_SERVER`
+    Sep31,
+    /// SEP-38 `ANCHOR_QUOTE_SERVER`
+    Sep38,
+}
+
+impl SepEndpoint {
+    pub const ALL: [SepEndpoint; 5] = [
+        SepEndpoint::Sep6,
+        SepEndpoint::Sep10,
+        SepEndpoint::Sep24,
+        SepEndpoint::Sep31,
+        SepEndpoint::Sep38,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SepEndpoint::Sep6 => "sep6",
+            SepEndpoint::Sep10 => "sep10",
+            SepEndpoint::Sep24 => "sep24",
+            SepEndpoint::Sep31 => "sep31",
+            SepEndpoint::Sep38 => "sep38",
+        }
+    }
+
+    /// Base URL of this endpoint published in an anchor's stellar.toml
+    fn toml_url(self, info: &AnchorTomlInfo) -> Option<&str> {
+        match self {
+            SepEndpoint::Sep6 => info.transfer_server.as_deref(),
+            SepEndpoint::Sep10 => info.web_auth_endpoint.as_deref(),
+            SepEndpoint::Sep24 => info.transfer_server_sep0024.as_deref(),
+            SepEndpoint::Sep31 => info.direct_payment_server.as_deref(),
+            SepEndpoint::Sep38 => info.anchor_quote_server.as_deref(),
+        }
+    }
+
+    /// Base URL of this endpoint in a parsed stellar.toml
+    pub fn published_url(self, toml: &StellarToml) -> Option<&str> {
+        match self {
+            SepEndpoint::Sep6 => toml.transfer_server.as_deref(),
+            SepEndpoint::Sep10 => toml.web_auth_endpoint.as_deref(),
+            SepEndpoint::Sep24 => toml.transfer_server_sep0024.as_deref(),
+            SepEndpoint::Sep31 => toml.direct_payment_server.as_deref(),
+            SepEndpoint::Sep38 => toml.anchor_quote_server.as_deref(),
+        }
+    }
+
+    /// URL requested when probing an endpoint at `base`: the `/info`
+    /// endpoint, or a SEP-10 challenge for `account`
+    pub fn probe_url(self, base: &str, account: &str) -> String {
+        let base = base.trim_end_matches('/');
+        match self {
+            SepEndpoint::Sep10 => format!("{}?account={}", base, account),
+            _ => format!("{}/info", base),
+        }
+    }
+
+    /// Check a response body against the fields the SEP requires
+    pub fn validate_response(self, body: &Value) -> Result<(), String> {
+        let require_object = |field: &str| match body.get(field) {
+            Some(Value::Object(_)) => Ok(()),
+            _ => Err(format!("missing `{}` object", field)),
+        };
+
+        match self {
+            SepEndpoint::Sep6 | SepEndpoint::Sep24 => {
+                require_object("deposit")?;
+                require_object("withdraw")
+            }
+            SepEndpoint::Sep31 => require_object("receive"),
+            SepEndpoint::Sep38 => match body.get("assets") {
+                Some(Value::Array(_)) => Ok(()),
+                _ => Err("missing `assets` array".to_string()),
+            },
+            SepEndpoint::Sep10 => {
+                let transaction = body
+                    .get("transaction")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| "missing `transaction` string".to_string())?;
+                validate_challenge(transaction)
+            }
+        }
+    }
+}
+
+impl fmt::Display for SepEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SepEndpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SepEndpoint::ALL
+            .into_iter()
+            .find(|sep| sep.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown SEP endpoint: {}", s))
+    }
+}
+
+/// A SEP-10 challenge must be a v1 transaction envelope with sequence number 0
+fn validate_challenge(transaction: &str) -> Result<(), String> {
+    use base64::Engine;
+    use xdr::ReadXdr;
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(transaction.trim())
+        .map_err(|e| format!("challenge is not base64: {}", e))?;
+    let envelope = xdr::TransactionEnvelope::from_xdr(bytes, xdr::Limits::none())
+        .map_err(|e| format!("challenge is not a transaction envelope: {}", e))?;
+
+    match envelope {
+        xdr::TransactionEnvelope::Tx(env) if env.tx.seq_num.0 == 0 => Ok(()),
+        xdr::TransactionEnvelope::Tx(_) => {
+            Err("challenge transaction has a non-zero sequence number".to_string())
+        }
+        _ => Err("challenge is not a v1 transaction envelope".to_string()),
+    }
+}
+
+/// Base URLs of an anchor's SEP endpoints.
+///
+/// Endpoints configured on the anchor take precedence over those crawled
+/// from its stellar.toml.
+pub async fn resolve_endpoints(
+    db: &Database,
+    anchor_id: &str,
+) -> Result<Vec<(SepEndpoint, String)>> {
+    let configured: Vec<(String, String)> =
+        sqlx::query_as("SELECT sep, url FROM anchor_sep_endpoints WHERE anchor_id = $1")
+            .bind(anchor_id)
+            .fetch_all(db.pool())
+            .await?;
+    let mut endpoints = BTreeMap::new();
+    for (sep, url) in configured {
+        match sep.parse::<SepEndpoint>() {
+            Ok(sep) => {
+                endpoints.insert(sep, url);
+            }
+            Err(e) => warn!("Ignoring endpoint of anchor {}: {}", anchor_id, e),
+        }
+    }
+
+    let toml = sqlx::query_as::<_, AnchorTomlInfo>(
+        "SELECT * FROM anchor_stellar_toml WHERE anchor_id = $1",
+    )
+    .bind(anchor_id)
+    .fetch_optional(db.pool())
+    .await?;
+    if let Some(toml) = toml {
+        for sep in SepEndpoint::ALL {
+            if let Some(url) = sep.toml_url(&toml) {
+                endpoints.entry(sep).or_insert_with(|| url.to_string());
+            }
+        }
+    }
+
+    Ok(endpoints.into_iter().collect())
+}
+
+#[derive(Debug, Clone)]
+pub struct SepEndpointProberConfig {
+    /// How often every anchor's endpoints are probed
+    pub interval_secs: u64,
+    pub timeout_secs: u64,
+    /// Probes older than this are deleted after each run
+    pub retention_days: i64,
+}
+
+impl Default for SepEndpointProberConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 300,
+            timeout_secs: 10,
+            retention_days: 30,
+        }
+    }
+}
+
+/// A single probe of an anchor's SEP endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct EndpointProbe {
+    pub id: i64,
+    pub anchor_id: String,
+    pub sep: String,
+    pub url: String,
+    /// The endpoint responded with a 2xx status
+    pub available: bool,
+    /// The response body matched the SEP
+    pub schema_valid: bool,
+    pub status_code: Option<i64>,
+    pub latency_ms: Option<i64>,
+    pub error: Option<String>,
+    pub probed_at: DateTime<Utc>,
+}
+
+/// Availability and latency of one endpoint over a window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointUptime {
+    pub sep: String,
+    /// URL of the most recent probe
+    pub url: String,
+    pub probes: i64,
+    pub available_probes: i64,
+    /// Probes that were available and returned a valid response
+    pub healthy_probes: i64,
+    pub uptime_percent: f64,
+    pub avg_latency_ms: Option<f64>,
+    pub last_probed_at: DateTime<Utc>,
+    pub last_healthy: bool,
+    pub last_error: Option<String>,
+}
+
+/// Uptime of all of an anchor's probed endpoints over a window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorUptime {
+    pub anchor_id: String,
+    pub window_hours: i64,
+    /// Healthy share of all probes in the window; `None` if nothing was probed
+    pub uptime_percent: Option<f64>,
+    pub endpoints: Vec<EndpointUptime>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProbeSummary {
+    pub anchors: usize,
+    pub probes: usize,
+    pub unhealthy: usize,
+}
+
+#[derive(sqlx::FromRow)]
+struct EndpointUptimeRow {
+    sep: String,
+    probes: i64,
+    available_probes: i64,
+    healthy_probes: i64,
+    avg_latency_ms: Option<f64>,
+}
+
+/// Probes anchors' SEP-6/24/31/38 `/info` and SEP-10 challenge endpoints.
+///
+/// Endpoints configured on the anchor take precedence over those crawled
+/// from its stellar.toml. Every probe records availability, latency and
+/// whether the response matched the SEP; after each run the anchor's status
+/// is recomputed so that endpoint uptime counts towards it.
+pub struct SepEndpointProber {
+    db: Arc<Database>,
+    client: Client,
+    config: SepEndpointProberConfig,
+}
+
+impl SepEndpointProber {
+    pub fn new(db: Arc<Database>, config: SepEndpointProberConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self { db, client, config }
+    }
+
+    /// Start the probe scheduler
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting SEP endpoint prober (interval: {} seconds)",
+            self.config.interval_secs
+        );
+
+        let mut ticker = interval(Duration::from_secs(self.config.interval_secs));
+
+        loop {
+            ticker.tick().await;
+            match self.run_probes().await {
+                Ok(summary) => info!(
+                    "SEP endpoint probes finished: {} anchors, {} probes, {} unhealthy",
+                    summary.anchors, summary.probes, summary.unhealthy
+                ),
+                Err(e) => error!("SEP endpoint probing failed: {}", e),
+            }
+        }
+    }
+
+    /// Probe every known anchor, refresh their status and prune old probes
+    pub async fn run_probes(&self) -> Result<ProbeSummary> {
+        let mut summary = ProbeSummary::default();
+
+        let anchors = self.db.list_anchors(i64::MAX, 0).await?;
+        for anchor in &anchors {
+            let probes = match self.probe_anchor(anchor).await {
+                Ok(probes) => probes,
+                Err(e) => {
+                    warn!("Failed to probe endpoints of anchor {}: {}", anchor.id, e);
+                    continue;
+                }
+            };
+            if probes.is_empty() {
+                continue;
+            }
+
+            summary.anchors += 1;
+            summary.probes += probes.len();
+            summary.unhealthy += probes
+                .iter()
+                .filter(|p| !(p.available && p.schema_valid))
+                .count();
+
+            if let Err(e) = self.db.refresh_anchor_status(anchor).await {
+                warn!("Failed to refresh status of anchor {}: {}", anchor.id, e);
+            }
+        }
+
+        let cutoff = Utc::now() - ChronoDuration::days(self.config.retention_days);
+        sqlx::query("DELETE FROM anchor_endpoint_probes WHERE probed_at < $1")
+            .bind(cutoff)
+            .execute(self.db.pool())
+            .await?;
+
+        Ok(summary)
+    }
+
+    /// Probe and record each of an anchor's known endpoints
+    pub async fn probe_anchor(&self, anchor: &Anchor) -> Result<Vec<EndpointProbe>> {
+        let mut probes = Vec::new();
+
+        for (sep, base) in self.endpoints(&anchor.id).await? {
+            let url = sep.probe_url(&base, &anchor.stellar_account);
+            let probed_at = Utc::now();
+            let result = self.probe(sep, &url).await;
+
+            let probe = sqlx::query_as::<_, EndpointProbe>(
+                r#"
+                INSERT INTO anchor_endpoint_probes (
+                    anchor_id, sep, url, available, schema_valid, status_code,
+                    latency_ms, error, probed_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING *
+                "#,
+            )
+            .bind(&anchor.id)
+            .bind(sep.as_str())
+            .bind(&url)
+            .bind(result.available)
+            .bind(result.schema_valid)
+            .bind(result.status_code)
+            .bind(result.latency_ms)
+            .bind(&result.error)
+            .bind(probed_at)
+            .fetch_one(self.db.pool())
+            .await?;
+            probes.push(probe);
+        }
+
+        Ok(probes)
+    }
+
+    /// Base URLs of an anchor's endpoints, configured ones first
+    pub async fn endpoints(&self, anchor_id: &str) -> Result<Vec<(SepEndpoint, String)>> {
+        resolve_endpoints(&self.db, anchor_id).await
+    }
+
+    /// Configure an endpoint on an anchor, overriding its stellar.toml.
+    ///
+    /// Returns `false` if the anchor does not exist.
+    pub async fn set_endpoint(&self, anchor_id: &str, sep: SepEndpoint, url: &str) -> Result<bool> {
+        let exists: Option<(String,)> = sqlx::query_as("SELECT id FROM anchors WHERE id = $1")
+            .bind(anchor_id)
+            .fetch_optional(self.db.pool())
+            .await?;
+        if exists.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_sep_endpoints (anchor_id, sep, url, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (anchor_id, sep) DO UPDATE
+            SET url = excluded.url,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(sep.as_str())
+        .bind(url)
+        .bind(Utc::now())
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(true)
+    }
+
+    /// Remove a configured endpoint; returns whether one was configured
+    pub async fn remove_endpoint(&self, anchor_id: &str, sep: SepEndpoint) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM anchor_sep_endpoints WHERE anchor_id = $1 AND sep = $2")
+                .bind(anchor_id)
+                .bind(sep.as_str())
+                .execute(self.db.pool())
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Most recent probes of an anchor, newest first
+    pub async fn get_probes(&self, anchor_id: &str, limit: i64) -> Result<Vec<EndpointProbe>> {
+        let probes = sqlx::query_as::<_, EndpointProbe>(
+            r#"
+            SELECT * FROM anchor_endpoint_probes
+            WHERE anchor_id = $1
+            ORDER BY probed_at DESC, id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(probes)
+    }
+
+    /// Per-endpoint and overall uptime of an anchor over the last `window_hours`
+    pub async fn get_uptime(&self, anchor_id: &str, window_hours: i64) -> Result<AnchorUptime> {
+        let since = Utc::now() - ChronoDuration::hours(window_hours);
+
+        let rows = sqlx::query_as::<_, EndpointUptimeRow>(
+            r#"
+            SELECT sep,
+                   COUNT(*) AS probes,
+                   SUM(available) AS available_probes,
+                   SUM(available AND schema_valid) AS healthy_probes,
+                   AVG(latency_ms) AS avg_latency_ms
+            FROM anchor_endpoint_probes
+            WHERE anchor_id = $1 AND probed_at >= $2
+            GROUP BY sep
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(since)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let latest = sqlx::query_as::<_, EndpointProbe>(
+            r#"
+            SELECT * FROM anchor_endpoint_probes p
+            WHERE anchor_id = $1
+              AND probed_at >= $2
+              AND id = (
+                  SELECT MAX(id) FROM anchor_endpoint_probes
+                  WHERE anchor_id = p.anchor_id AND sep = p.sep
+              )
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(since)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut endpoints: Vec<EndpointUptime> = rows
+            .into_iter()
+            .filter_map(|row| {
+                let last = latest.iter().find(|p| p.sep == row.sep)?;
+                Some(EndpointUptime {
+                    uptime_percent: row.healthy_probes as f64 / row.probes as f64 * 100.0,
+                    url: last.url.clone(),
+                    last_probed_at: last.probed_at,
+                    last_healthy: last.available && last.schema_valid,
+                    last_error: last.error.clone(),
+                    sep: row.sep,
+                    probes: row.probes,
+                    available_probes: row.available_probes,
+                    healthy_probes: row.healthy_probes,
+                    avg_latency_ms: row.avg_latency_ms,
+                })
+            })
+            .collect();
+        endpoints.sort_by_key(|e| e.sep.parse::<SepEndpoint>().ok());
+
+        let probes: i64 = endpoints.iter().map(|e| e.probes).sum();
+        let healthy: i64 = endpoints.iter().map(|e| e.healthy_probes).sum();
+
+        Ok(AnchorUptime {
+            anchor_id: anchor_id.to_string(),
+            window_hours,
+            uptime_percent: (probes > 0).then(|| healthy as f64 / probes as f64 * 100.0),
+            endpoints,
+        })
+    }
+
+    async fn probe(&self, sep: SepEndpoint, url: &str) -> ProbeResult {
+        let started = Instant::now();
+        let response = match self.client.get(url).send().await {
+            Ok(response) => response,
+            Err(e) => return ProbeResult::failed(None, None, e.to_string()),
+        };
+        let status = response.status();
+        let body = response.bytes().await;
+        let latency_ms = started.elapsed().as_millis() as i64;
+
+        if !status.is_success() {
+            return ProbeResult::failed(
+                Some(status.as_u16().into()),
+                Some(latency_ms),
+                format!("HTTP {}", status),
+            );
+        }
+
+        let validation = match body {
+            Ok(body) if body.len() > MAX_PROBE_RESPONSE_BYTES => Err(format!(
+                "response exceeds {} bytes",
+                MAX_PROBE_RESPONSE_BYTES
+            )),
+            Ok(body) => serde_json::from_slice::<Value>(&body)
+                .map_err(|e| format!("response is not JSON: {}", e))
+                .and_then(|body| sep.validate_response(&body)),
+            Err(e) => Err(format!("failed to read response: {}", e)),
+        };
+
+        ProbeResult {
+            available: true,
+            schema_valid: validation.is_ok(),
+            status_code: Some(status.as_u16().into()),
+            latency_ms: Some(latency_ms),
+            error: validation.err(),
+        }
+    }
+}
+
+struct ProbeResult {
+    available: bool,
+    schema_valid: bool,
+    status_code: Option<i64>,
+    latency_ms: Option<i64>,
+    error: Option<String>,
+}
+
+impl ProbeResult {
+    fn failed(status_code: Option<i64>, latency_ms: Option<i64>, error: String) -> Self {
+        Self {
+            available: false,
+            schema_valid: false,
+            status_code,
+            latency_ms,
+            error: Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_probe_url() {
+        let account = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        assert_eq!(
+            SepEndpoint::Sep24.probe_url("https://anchor.example/sep24/", account),
+            "https://anchor.example/sep24/info"
+        );
+        assert_eq!(
+            SepEndpoint::Sep10.probe_url("https://anchor.example/auth", account),
+            format!("https://anchor.example/auth?account={}", account)
+        );
+        assert_eq!("SEP31".parse::<SepEndpoint>(), Ok(SepEndpoint::Sep31));
+        assert!("sep12".parse::<SepEndpoint>().is_err());
+    }
+
+    #[test]
+    fn test_validate_response() {
+        let info = json!({ "deposit": {}, "withdraw": {}, "fee": { "enabled": false } });
+        assert!(SepEndpoint::Sep6.validate_response(&info).is_ok());
+        assert!(SepEndpoint::Sep24.validate_response(&info).is_ok());
+        assert_eq!(
+            SepEndpoint::Sep31.validate_response(&info),
+            Err("missing `receive` object".to_string())
+        );
+        assert!(SepEndpoint::Sep38
+            .validate_response(&json!({ "assets": [] }))
+            .is_ok());
+        assert!(SepEndpoint::Sep10
+            .validate_response(&json!({ "transaction": "not xdr!" }))
+            .unwrap_err()
+            .starts_with("challenge is not base64"));
+    }
+
+    #[test]
+    fn test_validate_challenge() {
+        use base64::Engine;
+        use xdr::WriteXdr;
+
+        let challenge = |seq_num: i64| {
+            let envelope = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+                tx: xdr::Transaction {
+                    source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256([7; 32])),
+                    fee: 100,
+                    seq_num: xdr::SequenceNumber(seq_num),
+                    cond: xdr::Preconditions::None,
+                    memo: xdr::Memo::None,
+                    operations: Vec::new().try_into().unwrap(),
+                    ext: xdr::TransactionExt::V0,
+                },
+                signatures: Vec::new().try_into().unwrap(),
+            });
+            let bytes = envelope.to_xdr(xdr::Limits::none()).unwrap();
+            json!({ "transaction": base64::engine::general_purpose::STANDARD.encode(bytes) })
+        };
+
+        assert!(SepEndpoint::Sep10.validate_response(&challenge(0)).is_ok());
+        assert_eq!(
+            SepEndpoint::Sep10.validate_response(&challenge(42)),
+            Err("challenge transaction has a non-zero sequence number".to_string())
+        );
+    }
+}
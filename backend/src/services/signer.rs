@@ -0,0 +1,566 @@
+//! Keys, transaction hashes and signers of the transactions the backend submits
+//!
+//! A signer holds the Ed25519 key of a Stellar account. Keys can be held in
+//! memory, in a password-encrypted keystore file or by a remote signing
+//! service (such as a front end to a KMS or HSM), so that servers do not need
+//! the secret seed in plain environment variables.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use reqwest::Client;
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_xdr::curr as xdr;
+use tracing::{info, warn};
+use xdr::WriteXdr;
+
+pub const KEYSTORE_VERSION: u32 = 1;
+pub const KEYSTORE_KDF: &str = "pbkdf2-hmac-sha256";
+/// PBKDF2 iterations of new keystores
+pub const DEFAULT_KEYSTORE_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Ed25519 key of a Stellar account
+pub struct Keypair {
+    key_pair: Ed25519KeyPair,
+    account: String,
+}
+
+impl Keypair {
+    /// Key of a secret seed (`S...`)
+    pub fn from_secret(secret: &str) -> Result<Self> {
+        let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret.trim())
+            .map_err(|_| anyhow!("invalid Stellar secret seed"))?;
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&seed.0)
+            .map_err(|_| anyhow!("invalid Stellar secret seed"))?;
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(key_pair.public_key().as_ref());
+        let account = stellar_strkey::ed25519::PublicKey(public_key).to_string();
+
+        Ok(Self { key_pair, account })
+    }
+
+    /// Account (`G...`) of the key
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(self.key_pair.public_key().as_ref());
+        public_key
+    }
+
+    /// Ed25519 signature of a message
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.key_pair
+            .sign(message)
+            .as_ref()
+            .try_into()
+            .expect("ed25519 signatures are 64 bytes")
+    }
+
+    /// Signature of a transaction hash, with the hint of this key
+    pub fn sign_decorated(&self, hash: &[u8; 32]) -> xdr::DecoratedSignature {
+        decorated_signature(&self.public_key(), self.sign(hash))
+    }
+}
+
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("account", &self.account)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Signature with the hint of the key that made it
+pub fn decorated_signature(public_key: &[u8; 32], signature: [u8; 64]) -> xdr::DecoratedSignature {
+    xdr::DecoratedSignature {
+        hint: xdr::SignatureHint([
+            public_key[28],
+            public_key[29],
+            public_key[30],
+            public_key[31],
+        ]),
+        signature: xdr::Signature(
+            signature
+                .to_vec()
+                .try_into()
+                .expect("ed25519 signatures are 64 bytes"),
+        ),
+    }
+}
+
+/// Hash a transaction's signers sign on the given network
+pub fn transaction_hash(tx: &xdr::Transaction, network_passphrase: &str) -> Result<[u8; 32]> {
+    let payload = xdr::TransactionSignaturePayload {
+        network_id: xdr::Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+        tagged_transaction: xdr::TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    };
+    let bytes = payload.to_xdr(xdr::Limits::none())?;
+    Ok(Sha256::digest(bytes).into())
+}
+
+/// Whether one of the signatures is a valid signature of `hash` by `public_key`
+pub fn is_signed_by(
+    public_key: &[u8; 32],
+    hash: &[u8; 32],
+    signatures: &[xdr::DecoratedSignature],
+) -> bool {
+    let key = UnparsedPublicKey::new(&ED25519, public_key);
+    signatures.iter().any(|decorated| {
+        decorated.hint.0 == public_key[28..] && key.verify(hash, &decorated.signature.0).is_ok()
+    })
+}
+
+/// Signs transaction hashes with the key of one Stellar account
+#[async_trait::async_trait]
+pub trait Signer: Send + Sync {
+    /// Ed25519 public key of the account
+    fn public_key(&self) -> [u8; 32];
+
+    /// Ed25519 signature of a transaction hash
+    async fn sign(&self, hash: &[u8; 32]) -> Result<[u8; 64]>;
+
+    /// Kind of signer, for logs
+    fn name(&self) -> &str;
+
+    /// Account (`G...`) of the key
+    fn account(&self) -> String {
+        stellar_strkey::ed25519::PublicKey(self.public_key()).to_string()
+    }
+
+    /// Signature of a transaction hash, with the hint of the key
+    async fn sign_decorated(&self, hash: &[u8; 32]) -> Result<xdr::DecoratedSignature> {
+        Ok(decorated_signature(
+            &self.public_key(),
+            self.sign(hash).await?,
+        ))
+    }
+}
+
+/// Key held in memory, for tests and development
+pub struct InMemorySigner {
+    keypair: Keypair,
+}
+
+impl InMemorySigner {
+    /// Signer of a secret seed (`S...`)
+    pub fn from_secret(secret: &str) -> Result<Self> {
+        Ok(Self {
+            keypair: Keypair::from_secret(secret)?,
+        })
+    }
+
+    pub fn from_seed(seed: [u8; 32]) -> Result<Self> {
+        Self::from_secret(&stellar_strkey::ed25519::PrivateKey(seed).to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl Signer for InMemorySigner {
+    fn public_key(&self) -> [u8; 32] {
+        self.keypair.public_key()
+    }
+
+    async fn sign(&self, hash: &[u8; 32]) -> Result<[u8; 64]> {
+        Ok(self.keypair.sign(hash))
+    }
+
+    fn name(&self) -> &str {
+        "in-memory"
+    }
+}
+
+impl fmt::Debug for InMemorySigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemorySigner")
+            .field("account", &self.keypair.account())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Keystore file: a secret seed encrypted with AES-256-GCM under a key
+/// derived from a password with PBKDF2
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    /// Account of the key; authenticated along with the seed
+    pub account: String,
+    pub kdf: String,
+    pub iterations: u32,
+    /// Base64 encoded
+    pub salt: String,
+    /// Base64 encoded
+    pub nonce: String,
+    /// Encrypted seed and authentication tag, base64 encoded
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt a secret seed (`S...`) with a password
+    pub fn encrypt(secret: &str, password: &str, iterations: u32) -> Result<Self> {
+        let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret.trim())
+            .map_err(|_| anyhow!("invalid Stellar secret seed"))?;
+        let account = InMemorySigner::from_seed(seed.0)?.account();
+
+        let rng = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut salt)
+            .and_then(|_| rng.fill(&mut nonce))
+            .map_err(|_| anyhow!("Failed to generate random bytes"))?;
+
+        let key = keystore_key(password, &salt, iterations)?;
+        let mut ciphertext = seed.0.to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(account.as_bytes()),
+            &mut ciphertext,
+        )
+        .map_err(|_| anyhow!("Failed to encrypt keystore"))?;
+
+        let base64 = base64::engine::general_purpose::STANDARD;
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            account,
+            kdf: KEYSTORE_KDF.to_string(),
+            iterations,
+            salt: base64.encode(salt),
+            nonce: base64.encode(nonce),
+            ciphertext: base64.encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the seed with the password
+    pub fn decrypt(&self, password: &str) -> Result<InMemorySigner> {
+        if self.version != KEYSTORE_VERSION || self.kdf != KEYSTORE_KDF {
+            bail!(
+                "Unsupported keystore version {} ({})",
+                self.version,
+                self.kdf
+            );
+        }
+
+        let base64 = base64::engine::general_purpose::STANDARD;
+        let salt = base64.decode(&self.salt).context("Invalid keystore salt")?;
+        let nonce: [u8; NONCE_LEN] = base64
+            .decode(&self.nonce)
+            .ok()
+            .and_then(|nonce| nonce.try_into().ok())
+            .context("Invalid keystore nonce")?;
+        let mut ciphertext = base64
+            .decode(&self.ciphertext)
+            .context("Invalid keystore ciphertext")?;
+
+        let key = keystore_key(password, &salt, self.iterations)?;
+        let seed: [u8; 32] = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(self.account.as_bytes()),
+                &mut ciphertext,
+            )
+            .ok()
+            .and_then(|seed| <[u8; 32]>::try_from(&*seed).ok())
+            .context("Wrong keystore password or corrupted keystore")?;
+
+        let signer = InMemorySigner::from_seed(seed)?;
+        if signer.account() != self.account {
+            bail!("Keystore key does not belong to {}", self.account);
+        }
+        Ok(signer)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid keystore {}", path.display()))
+    }
+
+    /// Write the keystore, readable by the owner only
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let file = options
+            .open(path)
+            .with_context(|| format!("Failed to create keystore {}", path.display()))?;
+        serde_json::to_writer_pretty(file, self)
+            .with_context(|| format!("Failed to write keystore {}", path.display()))
+    }
+}
+
+fn keystore_key(password: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).context("Keystore iterations must be positive")?;
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        password.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(&aead::AES_256_GCM, &key)
+        .map_err(|_| anyhow!("Failed to derive keystore key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Key decrypted from a keystore file
+#[derive(Debug)]
+pub struct KeystoreSigner {
+    inner: InMemorySigner,
+}
+
+impl KeystoreSigner {
+    pub fn open(path: &Path, password: &str) -> Result<Self> {
+        let inner = Keystore::load(path)?.decrypt(password)?;
+        Ok(Self { inner })
+    }
+}
+
+#[async_trait::async_trait]
+impl Signer for KeystoreSigner {
+    fn public_key(&self) -> [u8; 32] {
+        self.inner.public_key()
+    }
+
+    async fn sign(&self, hash: &[u8; 32]) -> Result<[u8; 64]> {
+        self.inner.sign(hash).await
+    }
+
+    fn name(&self) -> &str {
+        "keystore"
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteSignerConfig {
+    /// Signing endpoint
+    pub url: String,
+    /// Account (`G...`) the service signs for
+    pub account: String,
+    /// Bearer token sent with every request
+    pub token: Option<String>,
+    pub timeout_secs: u64,
+}
+
+/// Key held by a remote signing service
+///
+/// The service is sent `POST {"account": "G...", "hash": "<hex>"}` and must
+/// answer `{"signature": "<hex>"}`. Signatures are checked against the
+/// configured account before they are used.
+pub struct RemoteSigner {
+    client: Client,
+    config: RemoteSignerConfig,
+    public_key: [u8; 32],
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteSignature {
+    signature: String,
+}
+
+impl RemoteSigner {
+    pub fn new(config: RemoteSignerConfig) -> Result<Self> {
+        let public_key = stellar_strkey::ed25519::PublicKey::from_string(&config.account)
+            .map_err(|_| anyhow!("Invalid remote signer account {}", config.account))?
+            .0;
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            config,
+            public_key,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> [u8; 32] {
+        self.public_key
+    }
+
+    async fn sign(&self, hash: &[u8; 32]) -> Result<[u8; 64]> {
+        let mut request = self.client.post(&self.config.url).json(&json!({
+            "account": self.config.account,
+            "hash": hex::encode(hash),
+        }));
+        if let Some(token) = &self.config.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to reach remote signer")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            bail!("Remote signer error: {} - {}", status, body);
+        }
+        let body: RemoteSignature = response
+            .json()
+            .await
+            .context("Failed to parse remote signer response")?;
+
+        let signature: [u8; 64] = hex::decode(&body.signature)
+            .ok()
+            .and_then(|signature| signature.try_into().ok())
+            .context("Remote signer returned a malformed signature")?;
+        UnparsedPublicKey::new(&ED25519, self.public_key)
+            .verify(hash, &signature)
+            .map_err(|_| anyhow!("Remote signer returned an invalid signature"))?;
+        Ok(signature)
+    }
+
+    fn name(&self) -> &str {
+        "remote"
+    }
+}
+
+/// Signer configured by environment variables, in this order:
+///
+/// - `STELLAR_REMOTE_SIGNER_URL` with `STELLAR_REMOTE_SIGNER_ACCOUNT` and
+///   optionally `STELLAR_REMOTE_SIGNER_TOKEN`
+/// - `STELLAR_KEYSTORE_PATH` with `STELLAR_KEYSTORE_PASSWORD_FILE` or
+///   `STELLAR_KEYSTORE_PASSWORD`
+/// - `STELLAR_SOURCE_SECRET_KEY`, a plain secret seed for development
+pub fn signer_from_env() -> Result<Arc<dyn Signer>> {
+    let signer: Arc<dyn Signer> = if let Ok(url) = std::env::var("STELLAR_REMOTE_SIGNER_URL") {
+        Arc::new(RemoteSigner::new(RemoteSignerConfig {
+            url,
+            account: std::env::var("STELLAR_REMOTE_SIGNER_ACCOUNT")
+                .context("STELLAR_REMOTE_SIGNER_ACCOUNT environment variable not set")?,
+            token: std::env::var("STELLAR_REMOTE_SIGNER_TOKEN").ok(),
+            timeout_secs: std::env::var("STELLAR_REMOTE_SIGNER_TIMEOUT_SECS")
+                .ok()
+                .and_then(|secs| secs.parse().ok())
+                .unwrap_or(10),
+        })?)
+    } else if let Ok(path) = std::env::var("STELLAR_KEYSTORE_PATH") {
+        let password = match std::env::var("STELLAR_KEYSTORE_PASSWORD_FILE") {
+            Ok(file) => std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read keystore password file {}", file))?
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+            Err(_) => std::env::var("STELLAR_KEYSTORE_PASSWORD").context(
+                "STELLAR_KEYSTORE_PASSWORD_FILE or STELLAR_KEYSTORE_PASSWORD environment variable not set",
+            )?,
+        };
+        Arc::new(KeystoreSigner::open(Path::new(&path), &password)?)
+    } else if let Ok(secret) = std::env::var("STELLAR_SOURCE_SECRET_KEY") {
+        warn!(
+            "Signing with STELLAR_SOURCE_SECRET_KEY; use a keystore or remote signer in production"
+        );
+        Arc::new(InMemorySigner::from_secret(&secret)?)
+    } else {
+        bail!("No transaction signer configured (STELLAR_REMOTE_SIGNER_URL, STELLAR_KEYSTORE_PATH or STELLAR_SOURCE_SECRET_KEY)");
+    };
+
+    info!(
+        "Initialized {} transaction signer for {}",
+        signer.name(),
+        signer.account()
+    );
+    Ok(signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITERATIONS: u32 = 1000;
+
+    fn secret(seed: u8) -> String {
+        stellar_strkey::ed25519::PrivateKey([seed; 32]).to_string()
+    }
+
+    #[test]
+    fn test_keypair_from_secret() {
+        let key = Keypair::from_secret(&secret(1)).unwrap();
+        assert!(key.account().starts_with('G'));
+        assert_eq!(
+            stellar_strkey::ed25519::PublicKey::from_string(key.account())
+                .unwrap()
+                .0,
+            key.public_key()
+        );
+        assert!(Keypair::from_secret(key.account()).is_err());
+        assert!(!format!("{:?}", key).contains("key_pair"));
+    }
+
+    #[tokio::test]
+    async fn test_keystore_round_trip() {
+        let keystore = Keystore::encrypt(&secret(4), "correct horse", ITERATIONS).unwrap();
+        let expected = InMemorySigner::from_secret(&secret(4)).unwrap();
+        assert_eq!(keystore.account, expected.account());
+        assert!(!keystore.ciphertext.contains(&secret(4)));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signer.json");
+        keystore.save(&path).unwrap();
+        assert_eq!(Keystore::load(&path).unwrap(), keystore);
+        // Keystores are never overwritten
+        assert!(keystore.save(&path).is_err());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let signer = KeystoreSigner::open(&path, "correct horse").unwrap();
+        assert_eq!(signer.public_key(), expected.public_key());
+        let hash = [7u8; 32];
+        assert_eq!(
+            signer.sign(&hash).await.unwrap(),
+            expected.sign(&hash).await.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_keystore_rejects_wrong_passwords_and_tampering() {
+        let keystore = Keystore::encrypt(&secret(4), "correct horse", ITERATIONS).unwrap();
+        let error = keystore.decrypt("battery staple").unwrap_err();
+        assert!(error.to_string().contains("Wrong keystore password"));
+
+        // The account is authenticated with the seed
+        let mut tampered = keystore.clone();
+        tampered.account = InMemorySigner::from_secret(&secret(5)).unwrap().account();
+        assert!(tampered.decrypt("correct horse").is_err());
+
+        let mut tampered = keystore;
+        tampered.kdf = "scrypt".to_string();
+        assert!(tampered.decrypt("correct horse").is_err());
+
+        assert!(Keystore::encrypt("S...", "password", ITERATIONS).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_signatures_verify_with_the_account_key() {
+        let signer = InMemorySigner::from_seed([6u8; 32]).unwrap();
+        let hash = [1u8; 32];
+        let signature = signer.sign_decorated(&hash).await.unwrap();
+        assert!(is_signed_by(&signer.public_key(), &hash, &[signature]));
+        assert!(signer.account().starts_with('G'));
+    }
+}
@@ -1,19 +1,24 @@
 use crate::database::Database;
+use crate::snapshot::diff::{diff_snapshots, DiffThresholds, SnapshotDiff};
+use crate::snapshot::merkle::{self, hex_hash, Hash, MerkleTree, ProofNode};
 use crate::snapshot::schema::{
     AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use serde::Serialize;
-use serde_json::{Map, Value};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use sha2::{Digest, Sha256};
+use sqlx::sqlite::SqliteRow;
 use sqlx::Row;
 use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use super::contract::{ContractService, SubmissionResult};
+use super::contract::{ContractService, SubmissionResult, TRANSACTION_TIMEOUT_SECS};
 
 /// Result of snapshot generation and submission process
 #[derive(Debug, Clone, Serialize)]
@@ -21,22 +26,286 @@ pub struct SnapshotGenerationResult {
     pub snapshot_id: String,
     pub epoch: u64,
     pub hash: String,
+    /// Root of the Merkle tree over the snapshot's metrics, as submitted
+    pub merkle_root: String,
     pub canonical_json: String,
     pub anchor_count: usize,
     pub corridor_count: usize,
     pub submission_result: Option<SubmissionResult>,
+    /// Where the epoch's submission stands after this run
+    pub submission_status: SubmissionStatus,
+    /// Why the submission failed; the scheduler retries it
+    pub submission_error: Option<String>,
     pub verification_successful: bool,
     pub timestamp: DateTime<Utc>,
 }
 
+/// State of an epoch's on-chain submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionStatus {
+    /// Stored, not yet sent to the contract
+    Generated,
+    /// Sent to the contract, not yet seen on-chain
+    Submitted,
+    /// The contract holds the epoch's root
+    Confirmed,
+    /// The latest attempt failed
+    Failed,
+}
+
+impl SubmissionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Generated => "generated",
+            SubmissionStatus::Submitted => "submitted",
+            SubmissionStatus::Confirmed => "confirmed",
+            SubmissionStatus::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for SubmissionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SubmissionStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "generated" => Ok(SubmissionStatus::Generated),
+            "submitted" => Ok(SubmissionStatus::Submitted),
+            "confirmed" => Ok(SubmissionStatus::Confirmed),
+            "failed" => Ok(SubmissionStatus::Failed),
+            _ => Err(format!(
+                "unknown submission status: {} (expected generated, submitted, confirmed or failed)",
+                s
+            )),
+        }
+    }
+}
+
+/// An epoch's on-chain submission, as stored in `snapshot_submissions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSubmission {
+    pub epoch: u64,
+    pub snapshot_id: String,
+    pub merkle_root: String,
+    pub status: SubmissionStatus,
+    /// Failed submissions so far
+    pub attempts: u32,
+    pub transaction_hash: Option<String>,
+    pub ledger: Option<u64>,
+    pub contract_timestamp: Option<u64>,
+    pub last_error: Option<String>,
+    /// Outcome of the latest comparison with the contract
+    pub reconciliation: Option<VerificationStatus>,
+    pub on_chain_root: Option<String>,
+    pub reconciled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SnapshotSubmission {
+    fn from_row(row: &SqliteRow) -> Result<Self> {
+        let status: String = row.get("status");
+        let reconciliation: Option<String> = row.get("reconciliation");
+        Ok(Self {
+            epoch: row.get::<i64, _>("epoch") as u64,
+            snapshot_id: row.get("snapshot_id"),
+            merkle_root: row.get("merkle_root"),
+            status: status.parse().map_err(anyhow::Error::msg)?,
+            attempts: row.get::<i64, _>("attempts") as u32,
+            transaction_hash: row.get("transaction_hash"),
+            ledger: row.get::<Option<i64>, _>("ledger").map(|l| l as u64),
+            contract_timestamp: row
+                .get::<Option<i64>, _>("contract_timestamp")
+                .map(|t| t as u64),
+            last_error: row.get("last_error"),
+            reconciliation: reconciliation
+                .map(|r| r.parse())
+                .transpose()
+                .map_err(anyhow::Error::msg)?,
+            on_chain_root: row.get("on_chain_root"),
+            reconciled_at: row.get("reconciled_at"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    /// Submission result of a confirmed or submitted epoch
+    fn submission_result(&self) -> Option<SubmissionResult> {
+        Some(SubmissionResult {
+            transaction_hash: self.transaction_hash.clone()?,
+            epoch: self.epoch,
+            ledger: self.ledger.unwrap_or_default(),
+            timestamp: self.contract_timestamp.unwrap_or_default(),
+        })
+    }
+}
+
+/// Kind of metrics committed by a snapshot leaf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotLeafKind {
+    Anchor,
+    Corridor,
+}
+
+impl SnapshotLeafKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotLeafKind::Anchor => "anchor",
+            SnapshotLeafKind::Corridor => "corridor",
+        }
+    }
+}
+
+impl fmt::Display for SnapshotLeafKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SnapshotLeafKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "anchor" => Ok(SnapshotLeafKind::Anchor),
+            "corridor" => Ok(SnapshotLeafKind::Corridor),
+            _ => Err(format!(
+                "unknown leaf kind: {} (expected anchor or corridor)",
+                s
+            )),
+        }
+    }
+}
+
+/// One anchor's or corridor's metrics, committed as a leaf of the snapshot's
+/// Merkle tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotLeaf {
+    pub kind: SnapshotLeafKind,
+    /// Anchor ID or corridor metrics ID
+    pub id: String,
+    /// Canonical JSON `{"epoch", "kind", "metrics"}` the leaf hash is computed from
+    pub data: String,
+}
+
+impl SnapshotLeaf {
+    pub fn hash(&self) -> Hash {
+        merkle::leaf_hash(self.data.as_bytes())
+    }
+}
+
+/// A snapshot leaf with the proof of its inclusion under the snapshot's root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub snapshot_id: String,
+    pub epoch: u64,
+    #[serde(with = "hex_hash")]
+    pub merkle_root: Hash,
+    pub kind: SnapshotLeafKind,
+    pub id: String,
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    /// Canonical JSON of the leaf
+    pub leaf: String,
+    #[serde(with = "hex_hash")]
+    pub leaf_hash: Hash,
+    /// Siblings from the leaf up to the root
+    pub proof: Vec<ProofNode>,
+}
+
+/// Outcome of checking a snapshot against the contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    /// The recomputed Merkle root is the one submitted for the epoch
+    Verified,
+    /// The contract holds a different root for the epoch
+    RootMismatch,
+    /// Nothing was submitted for the epoch
+    NotOnChain,
+    /// The stored snapshot no longer matches the hash or root recorded when it
+    /// was generated
+    StoredMismatch,
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationStatus::Verified => "verified",
+            VerificationStatus::RootMismatch => "root_mismatch",
+            VerificationStatus::NotOnChain => "not_on_chain",
+            VerificationStatus::StoredMismatch => "stored_mismatch",
+        }
+    }
+}
+
+impl FromStr for VerificationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "verified" => Ok(VerificationStatus::Verified),
+            "root_mismatch" => Ok(VerificationStatus::RootMismatch),
+            "not_on_chain" => Ok(VerificationStatus::NotOnChain),
+            "stored_mismatch" => Ok(VerificationStatus::StoredMismatch),
+            _ => Err(format!("unknown verification status: {}", s)),
+        }
+    }
+}
+
+/// Report of a snapshot's verification against the contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotVerificationReport {
+    pub epoch: u64,
+    pub contract_id: String,
+    pub status: VerificationStatus,
+    pub verified: bool,
+    /// SHA-256 of the canonical JSON, recomputed
+    pub hash: String,
+    /// Merkle root over the metrics, recomputed
+    pub merkle_root: String,
+    pub leaf_count: usize,
+    /// Root held by the contract for the epoch
+    pub on_chain_root: Option<String>,
+    /// ID, hash and root recorded when the snapshot was generated; absent
+    /// when verifying a snapshot file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_merkle_root: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Snapshot as stored in the `snapshots` table
+#[derive(Debug, Clone)]
+pub struct StoredSnapshot {
+    pub id: String,
+    pub epoch: u64,
+    pub canonical_json: String,
+    pub hash: String,
+    /// Absent for snapshots generated before roots were recorded
+    pub merkle_root: Option<String>,
+}
+
 /// Service for creating cryptographically verifiable analytics snapshots
 ///
 /// This service ensures that:
 /// 1. Metrics are aggregated from all data sources
 /// 2. Snapshots are serialized deterministically (same input = same output)
-/// 3. SHA-256 hashes are computed and stored
-/// 4. Hashes are submitted to smart contracts
+/// 3. SHA-256 hashes and Merkle roots over the metrics are computed and stored
+/// 4. Merkle roots are submitted to smart contracts
 /// 5. Submission success is verified
+/// 6. Single metrics can be proven against the submitted root
 pub struct SnapshotService {
     db: Arc<Database>,
     contract_service: Option<Arc<ContractService>>,
@@ -51,21 +320,66 @@ impl SnapshotService {
         }
     }
 
+    /// Contract the snapshots are submitted to, if configured
+    pub fn contract_service(&self) -> Option<&Arc<ContractService>> {
+        self.contract_service.as_ref()
+    }
+
     /// Generate a complete analytics snapshot with hash generation and submission
     ///
     /// This is the main entry point that fulfills all acceptance criteria:
     /// 1. Aggregate all metrics
     /// 2. Serialize to deterministic JSON
-    /// 3. Compute SHA-256 hash
-    /// 4. Store hash in database
-    /// 5. Submit to smart contract
+    /// 3. Compute SHA-256 hash and Merkle root
+    /// 4. Store hash and root in database
+    /// 5. Submit root to smart contract
     /// 6. Verify submission success
+    ///
+    /// A failed submission does not fail the snapshot: the epoch is left
+    /// `failed` with the error, for the scheduler to retry.
     pub async fn generate_and_submit_snapshot(
         &self,
         epoch: u64,
     ) -> Result<SnapshotGenerationResult> {
+        let mut result = self.generate_snapshot(epoch).await?;
+        if self.contract_service.is_none() {
+            warn!("Contract service not configured, skipping on-chain submission");
+            return Ok(result);
+        }
+
+        // Steps 5 and 6: Submit to smart contract and verify
+        let submission = self.submit_epoch(epoch).await?;
+        result.submission_result = submission.submission_result();
+        result.submission_status = submission.status;
+        result.verification_successful = submission.status == SubmissionStatus::Confirmed;
+        if submission.status == SubmissionStatus::Failed {
+            result.submission_error = submission.last_error;
+        }
+
+        Ok(result)
+    }
+
+    /// Generate and store a snapshot for an epoch without submitting it
+    ///
+    /// The epoch is recorded as `generated`. An epoch that was already
+    /// submitted or confirmed cannot be generated again, as the contract
+    /// keeps the first root of an epoch.
+    pub async fn generate_snapshot(&self, epoch: u64) -> Result<SnapshotGenerationResult> {
         info!("Starting snapshot generation for epoch {}", epoch);
 
+        if let Some(submission) = self.submission(epoch).await? {
+            if matches!(
+                submission.status,
+                SubmissionStatus::Submitted | SubmissionStatus::Confirmed
+            ) {
+                anyhow::bail!(
+                    "Snapshot of epoch {} was already {}",
+                    epoch,
+                    submission.status
+                );
+            }
+        }
+
         // Step 1: Aggregate all metrics
         let snapshot = self
             .aggregate_all_metrics(epoch)
@@ -84,53 +398,39 @@ impl SnapshotService {
 
         // Step 3: Compute SHA-256 hash
         let hash = Self::compute_sha256_hash_bytes(&canonical_json);
-        let hash_hex = hex::encode(&hash);
+        let hash_hex = hex::encode(hash);
 
-        info!("Generated snapshot hash: {}", hash_hex);
+        let root = Self::merkle_root(&canonical_json)?;
+        let root_hex = hex::encode(root);
 
-        // Step 4: Store hash in database
+        info!(
+            "Generated snapshot hash: {}, Merkle root: {}",
+            hash_hex, root_hex
+        );
+
+        // Step 4: Store hash and root in database
         let snapshot_id = self
-            .store_snapshot_in_database(&snapshot, &hash_hex, &canonical_json)
+            .store_snapshot_in_database(&snapshot, &hash_hex, &root_hex, &canonical_json)
             .await
             .context("Failed to store snapshot in database")?;
+        self.record_generated(epoch, &snapshot_id, &root_hex)
+            .await
+            .context("Failed to record snapshot submission")?;
 
         info!("Stored snapshot in database with ID: {}", snapshot_id);
 
-        // Step 5: Submit to smart contract (if configured)
-        let submission_result = if let Some(contract_service) = &self.contract_service {
-            match contract_service.submit_snapshot(hash, epoch).await {
-                Ok(result) => {
-                    info!("Successfully submitted snapshot to contract: {:?}", result);
-                    Some(result)
-                }
-                Err(e) => {
-                    error!("Failed to submit snapshot to contract: {}", e);
-                    return Err(e.context("Contract submission failed"));
-                }
-            }
-        } else {
-            warn!("Contract service not configured, skipping on-chain submission");
-            None
-        };
-
-        // Step 6: Verify submission success (if submitted)
-        let verification_result = if let Some(ref submission) = submission_result {
-            self.verify_submission_success(&hash_hex, epoch, submission)
-                .await
-                .context("Failed to verify submission success")?
-        } else {
-            false
-        };
-
         Ok(SnapshotGenerationResult {
             snapshot_id,
             epoch,
             hash: hash_hex,
+            merkle_root: root_hex,
             canonical_json,
             anchor_count: snapshot.anchor_metrics.len(),
             corridor_count: snapshot.corridor_metrics.len(),
-            submission_result,
-            verification_successful: verification_result,
+            submission_result: None,
+            submission_status: SubmissionStatus::Generated,
+            submission_error: None,
+            verification_successful: false,
             timestamp: snapshot.timestamp,
         })
     }
@@ -290,14 +590,15 @@ impl SnapshotService {
         &self,
         snapshot: &AnalyticsSnapshot,
         hash: &str,
+        merkle_root: &str,
         canonical_json: &str,
     ) -> Result<String> {
         let snapshot_id = Uuid::new_v4().to_string();
 
         let query = r#"
             INSERT INTO snapshots (
-                id, entity_id, entity_type, data, hash, epoch, timestamp, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                id, entity_id, entity_type, data, hash, merkle_root, epoch, timestamp, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
         sqlx::query(query)
@@ -306,6 +607,7 @@ impl SnapshotService {
             .bind("analytics_snapshot") // entity_type
             .bind(canonical_json)
             .bind(hash)
+            .bind(merkle_root)
             .bind(snapshot.epoch as i64)
             .bind(snapshot.timestamp)
             .bind(Utc::now())
@@ -316,41 +618,418 @@ impl SnapshotService {
         Ok(snapshot_id)
     }
 
-    /// Verify that the submission was successful by querying the contract
-    async fn verify_submission_success(
+    /// Record a freshly generated snapshot as its epoch's submission
+    async fn record_generated(
         &self,
-        hash: &str,
         epoch: u64,
-        _submission: &SubmissionResult,
-    ) -> Result<bool> {
-        if let Some(contract_service) = &self.contract_service {
-            // Wait a moment for the transaction to be confirmed
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-
-            match contract_service.verify_snapshot_exists(hash, epoch).await {
-                Ok(exists) => {
-                    if exists {
-                        info!(
-                            "Verification successful: snapshot exists on-chain for epoch {}",
-                            epoch
-                        );
-                    } else {
-                        warn!(
-                            "Verification failed: snapshot not found on-chain for epoch {}",
-                            epoch
-                        );
+        snapshot_id: &str,
+        merkle_root: &str,
+    ) -> Result<()> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO snapshot_submissions (
+                epoch, snapshot_id, merkle_root, status, attempts, created_at, updated_at
+            ) VALUES (?, ?, ?, 'generated', 0, ?, ?)
+            ON CONFLICT(epoch) DO UPDATE SET
+                snapshot_id = excluded.snapshot_id,
+                merkle_root = excluded.merkle_root,
+                status = 'generated',
+                attempts = 0,
+                transaction_hash = NULL,
+                ledger = NULL,
+                contract_timestamp = NULL,
+                last_error = NULL,
+                reconciliation = NULL,
+                on_chain_root = NULL,
+                reconciled_at = NULL,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(epoch as i64)
+        .bind(snapshot_id)
+        .bind(merkle_root)
+        .bind(now)
+        .bind(now)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Submission state of an epoch
+    pub async fn submission(&self, epoch: u64) -> Result<Option<SnapshotSubmission>> {
+        let row = sqlx::query("SELECT * FROM snapshot_submissions WHERE epoch = ?")
+            .bind(epoch as i64)
+            .fetch_optional(self.db.pool())
+            .await
+            .context("Failed to fetch snapshot submission")?;
+
+        row.as_ref().map(SnapshotSubmission::from_row).transpose()
+    }
+
+    /// Submissions, newest epoch first
+    pub async fn list_submissions(
+        &self,
+        status: Option<SubmissionStatus>,
+        limit: i64,
+    ) -> Result<Vec<SnapshotSubmission>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM snapshot_submissions
+            WHERE ?1 IS NULL OR status = ?1
+            ORDER BY epoch DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(status.map(|s| s.as_str()))
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to list snapshot submissions")?;
+
+        rows.iter().map(SnapshotSubmission::from_row).collect()
+    }
+
+    /// Epochs still to be submitted, oldest first: generated ones, failed
+    /// ones with fewer than `max_attempts` attempts, and ones submitted
+    /// before `submitted_before` that may never have reached the contract
+    pub async fn pending_submissions(
+        &self,
+        max_attempts: u32,
+        submitted_before: DateTime<Utc>,
+    ) -> Result<Vec<SnapshotSubmission>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM snapshot_submissions
+            WHERE status = 'generated'
+                OR (status = 'failed' AND attempts < ?)
+                OR (status = 'submitted' AND updated_at < ?)
+            ORDER BY epoch
+            "#,
+        )
+        .bind(max_attempts as i64)
+        .bind(submitted_before)
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to fetch pending snapshot submissions")?;
+
+        rows.iter().map(SnapshotSubmission::from_row).collect()
+    }
+
+    /// Move an epoch's submission forward
+    ///
+    /// The contract keeps the first root submitted for an epoch, so it is read
+    /// first. If it already holds the epoch's root, e.g. from an attempt
+    /// interrupted by a restart, the epoch is confirmed. If it holds another
+    /// root, the attempt fails. A submitted epoch whose transaction has timed
+    /// out without the root reaching the contract fails too, so that it is
+    /// submitted again. Otherwise the root is submitted, and confirmed once
+    /// the contract returns it.
+    pub async fn submit_epoch(&self, epoch: u64) -> Result<SnapshotSubmission> {
+        let contract_service = self
+            .contract_service
+            .as_ref()
+            .context("Contract service not configured")?;
+        let submission = self
+            .submission(epoch)
+            .await?
+            .with_context(|| format!("No snapshot generated for epoch {}", epoch))?;
+        let root = hex_hash::parse(&submission.merkle_root).map_err(anyhow::Error::msg)?;
+
+        match contract_service.get_snapshot_root(epoch).await {
+            Ok(Some(on_chain)) if on_chain == root => {
+                info!("Snapshot of epoch {} is confirmed on-chain", epoch);
+                self.set_submission_status(epoch, SubmissionStatus::Confirmed)
+                    .await?;
+            }
+            Ok(Some(on_chain)) => {
+                let message = format!(
+                    "Contract holds a different root for epoch {}: {}",
+                    epoch,
+                    hex::encode(on_chain)
+                );
+                error!("{}", message);
+                self.record_failure(epoch, &message).await?;
+            }
+            Err(e) => {
+                error!(
+                    "Failed to read snapshot of epoch {} from the contract: {}",
+                    epoch, e
+                );
+                self.record_failure(epoch, &format!("{:#}", e)).await?;
+            }
+            Ok(None) if submission.status == SubmissionStatus::Submitted => {
+                let expired_at = submission.updated_at
+                    + chrono::Duration::seconds(TRANSACTION_TIMEOUT_SECS as i64);
+                // Otherwise the contract may still catch up
+                if Utc::now() >= expired_at {
+                    let message = format!(
+                        "Transaction {} timed out without the root of epoch {} reaching the contract",
+                        submission.transaction_hash.as_deref().unwrap_or("(unknown)"),
+                        epoch
+                    );
+                    warn!("{}", message);
+                    self.record_failure(epoch, &message).await?;
+                }
+            }
+            Ok(None) => match contract_service.submit_snapshot(root, epoch).await {
+                Ok(result) => {
+                    info!("Successfully submitted snapshot to contract: {:?}", result);
+                    self.record_submitted(&result).await?;
+                    if let Ok(Some(on_chain)) = contract_service.get_snapshot_root(epoch).await {
+                        if on_chain == root {
+                            self.set_submission_status(epoch, SubmissionStatus::Confirmed)
+                                .await?;
+                        }
                     }
-                    Ok(exists)
                 }
                 Err(e) => {
-                    error!("Verification error: {}", e);
-                    Ok(false) // Don't fail the entire process for verification errors
+                    error!("Failed to submit snapshot to contract: {:#}", e);
+                    self.record_failure(epoch, &format!("{:#}", e)).await?;
                 }
+            },
+        }
+
+        self.submission(epoch)
+            .await?
+            .with_context(|| format!("Snapshot submission of epoch {} disappeared", epoch))
+    }
+
+    async fn set_submission_status(&self, epoch: u64, status: SubmissionStatus) -> Result<()> {
+        sqlx::query(
+            "UPDATE snapshot_submissions SET status = ?, last_error = NULL, updated_at = ? WHERE epoch = ?",
+        )
+        .bind(status.as_str())
+        .bind(Utc::now())
+        .bind(epoch as i64)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn record_submitted(&self, result: &SubmissionResult) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE snapshot_submissions
+            SET status = 'submitted', transaction_hash = ?, ledger = ?, contract_timestamp = ?,
+                last_error = NULL, updated_at = ?
+            WHERE epoch = ?
+            "#,
+        )
+        .bind(&result.transaction_hash)
+        .bind(result.ledger as i64)
+        .bind(result.timestamp as i64)
+        .bind(Utc::now())
+        .bind(result.epoch as i64)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn record_failure(&self, epoch: u64, error: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE snapshot_submissions
+            SET status = 'failed', attempts = attempts + 1, last_error = ?, updated_at = ?
+            WHERE epoch = ?
+            "#,
+        )
+        .bind(error)
+        .bind(Utc::now())
+        .bind(epoch as i64)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Compare an epoch's stored snapshot with the contract and record the
+    /// outcome on its submission
+    ///
+    /// A submitted epoch whose root is found on-chain is confirmed. Any other
+    /// outcome for a confirmed epoch is a mismatch between the contract and
+    /// the database, and is logged as an error.
+    pub async fn reconcile_epoch(&self, epoch: u64) -> Result<Option<SnapshotVerificationReport>> {
+        let Some(submission) = self.submission(epoch).await? else {
+            return Ok(None);
+        };
+        let Some(report) = self.verify_snapshot(epoch).await? else {
+            return Ok(None);
+        };
+
+        if report.status != VerificationStatus::Verified
+            && submission.status == SubmissionStatus::Confirmed
+        {
+            error!(
+                "Snapshot of epoch {} no longer matches the contract: {} (on-chain root: {:?})",
+                epoch,
+                report.status.as_str(),
+                report.on_chain_root
+            );
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE snapshot_submissions
+            SET reconciliation = ?, on_chain_root = ?, reconciled_at = ?,
+                status = CASE WHEN ? AND status = 'submitted' THEN 'confirmed' ELSE status END
+            WHERE epoch = ?
+            "#,
+        )
+        .bind(report.status.as_str())
+        .bind(&report.on_chain_root)
+        .bind(report.checked_at)
+        .bind(report.status == VerificationStatus::Verified)
+        .bind(epoch as i64)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(Some(report))
+    }
+
+    /// Allow a failed epoch its full number of attempts again
+    pub async fn retry_submission(&self, epoch: u64) -> Result<Option<SnapshotSubmission>> {
+        sqlx::query(
+            "UPDATE snapshot_submissions SET attempts = 0, updated_at = ? WHERE epoch = ? AND status = 'failed'",
+        )
+        .bind(Utc::now())
+        .bind(epoch as i64)
+        .execute(self.db.pool())
+        .await?;
+
+        if self.submission(epoch).await?.is_none() {
+            return Ok(None);
+        }
+        self.submit_epoch(epoch).await.map(Some)
+    }
+
+    /// Prove that an anchor's or corridor's metrics are part of the latest
+    /// snapshot of `epoch`; `None` if that snapshot has no such leaf
+    pub async fn inclusion_proof(
+        &self,
+        epoch: u64,
+        kind: SnapshotLeafKind,
+        id: &str,
+    ) -> Result<Option<InclusionProof>> {
+        let Some(snapshot) = self.stored_snapshot(epoch).await? else {
+            return Ok(None);
+        };
+        if snapshot.merkle_root.is_none() {
+            return Ok(None);
+        }
+
+        let (leaves, tree) = Self::merkle_tree(&snapshot.canonical_json)?;
+        let Some(leaf_index) = leaves
+            .iter()
+            .position(|leaf| leaf.kind == kind && leaf.id == id)
+        else {
+            return Ok(None);
+        };
+        let leaf = &leaves[leaf_index];
+
+        Ok(Some(InclusionProof {
+            snapshot_id: snapshot.id,
+            epoch,
+            merkle_root: tree.root(),
+            kind,
+            id: leaf.id.clone(),
+            leaf_index,
+            leaf_count: tree.leaf_count(),
+            leaf: leaf.data.clone(),
+            leaf_hash: leaf.hash(),
+            proof: tree.proof(leaf_index).unwrap_or_default(),
+        }))
+    }
+
+    /// Check an inclusion proof against the root submitted on-chain for its
+    /// epoch
+    pub async fn verify_inclusion_on_chain(&self, proof: &InclusionProof) -> Result<bool> {
+        let contract_service = self
+            .contract_service
+            .as_ref()
+            .context("Contract service not configured")?;
+
+        match contract_service.get_snapshot_root(proof.epoch).await? {
+            Some(root) => Ok(Self::verify_inclusion(proof, root)),
+            None => {
+                warn!("No snapshot root on-chain for epoch {}", proof.epoch);
+                Ok(false)
             }
-        } else {
-            Ok(false)
         }
     }
+
+    /// Latest snapshot stored for an epoch
+    pub async fn stored_snapshot(&self, epoch: u64) -> Result<Option<StoredSnapshot>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, data, hash, merkle_root FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to fetch snapshot")?;
+
+        Ok(row.map(|row| StoredSnapshot {
+            id: row.get("id"),
+            epoch,
+            canonical_json: row.get("data"),
+            hash: row.get("hash"),
+            merkle_root: row.get("merkle_root"),
+        }))
+    }
+
+    /// Recompute the latest snapshot of an epoch from its canonical JSON and
+    /// compare it with what was recorded and with the root on-chain
+    pub async fn verify_snapshot(&self, epoch: u64) -> Result<Option<SnapshotVerificationReport>> {
+        let contract_service = self
+            .contract_service
+            .as_ref()
+            .context("Contract service not configured")?;
+        let Some(snapshot) = self.stored_snapshot(epoch).await? else {
+            return Ok(None);
+        };
+
+        let on_chain_root = contract_service.get_snapshot_root(epoch).await?;
+        let mut report = Self::verification_report(
+            &snapshot.canonical_json,
+            contract_service.contract_id(),
+            on_chain_root,
+        )?;
+        if report.epoch != epoch {
+            anyhow::bail!(
+                "Snapshot {} is stored for epoch {} but is for epoch {}",
+                snapshot.id,
+                epoch,
+                report.epoch
+            );
+        }
+
+        if report.hash != snapshot.hash
+            || snapshot
+                .merkle_root
+                .as_ref()
+                .is_some_and(|root| *root != report.merkle_root)
+        {
+            warn!(
+                "Snapshot {} of epoch {} does not match its recorded hash or root",
+                snapshot.id, epoch
+            );
+            report.status = VerificationStatus::StoredMismatch;
+            report.verified = false;
+        }
+        report.snapshot_id = Some(snapshot.id);
+        report.stored_hash = Some(snapshot.hash);
+        report.stored_merkle_root = snapshot.merkle_root;
+
+        Ok(Some(report))
+    }
 }
 
 impl SnapshotService {
@@ -638,6 +1317,130 @@ impl SnapshotService {
         Ok((hash, hash_hex, SCHEMA_VERSION))
     }
 
+    /// Epoch of a canonical snapshot
+    pub fn snapshot_epoch(canonical_json: &str) -> Result<u64> {
+        serde_json::from_str::<Value>(canonical_json)
+            .context("Snapshot is not valid JSON")?
+            .get("epoch")
+            .and_then(Value::as_u64)
+            .context("Snapshot has no epoch")
+    }
+
+    /// Read a canonical snapshot back into its metrics
+    pub fn parse_canonical(canonical_json: &str) -> Result<AnalyticsSnapshot> {
+        serde_json::from_str(canonical_json).context("Snapshot does not match the schema")
+    }
+
+    /// Differences between the metrics of two canonical snapshots
+    pub fn diff_canonical(
+        from_json: &str,
+        to_json: &str,
+        thresholds: &DiffThresholds,
+    ) -> Result<SnapshotDiff> {
+        let from = Self::parse_canonical(from_json)?;
+        let to = Self::parse_canonical(to_json)?;
+        Ok(diff_snapshots(&from, &to, thresholds))
+    }
+
+    /// Split a canonical snapshot into Merkle leaves
+    ///
+    /// There is one leaf per anchor metrics entry followed by one per corridor
+    /// metrics entry, in the order of the canonical JSON. Leaves are derived
+    /// from the canonical JSON so that anyone holding it can rebuild the tree.
+    pub fn merkle_leaves(canonical_json: &str) -> Result<Vec<SnapshotLeaf>> {
+        let snapshot: Value =
+            serde_json::from_str(canonical_json).context("Snapshot is not valid JSON")?;
+        let epoch = snapshot
+            .get("epoch")
+            .and_then(Value::as_u64)
+            .context("Snapshot has no epoch")?;
+
+        let mut leaves = Vec::new();
+        for (kind, field) in [
+            (SnapshotLeafKind::Anchor, "anchor_metrics"),
+            (SnapshotLeafKind::Corridor, "corridor_metrics"),
+        ] {
+            let metrics = snapshot
+                .get(field)
+                .and_then(Value::as_array)
+                .with_context(|| format!("Snapshot has no {}", field))?;
+            for metric in metrics {
+                let id = metric
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .with_context(|| format!("Entry of {} has no id", field))?;
+                // serde_json objects keep their keys sorted
+                let data = serde_json::to_string(&json!({
+                    "epoch": epoch,
+                    "kind": kind,
+                    "metrics": metric,
+                }))?;
+                leaves.push(SnapshotLeaf {
+                    kind,
+                    id: id.to_string(),
+                    data,
+                });
+            }
+        }
+
+        Ok(leaves)
+    }
+
+    /// Build the Merkle tree of a canonical snapshot
+    pub fn merkle_tree(canonical_json: &str) -> Result<(Vec<SnapshotLeaf>, MerkleTree)> {
+        let leaves = Self::merkle_leaves(canonical_json)?;
+        let tree = MerkleTree::new(leaves.iter().map(SnapshotLeaf::hash).collect());
+        Ok((leaves, tree))
+    }
+
+    /// Merkle root of a canonical snapshot
+    pub fn merkle_root(canonical_json: &str) -> Result<Hash> {
+        Ok(Self::merkle_tree(canonical_json)?.1.root())
+    }
+
+    /// Check that a proof's leaf hashes to its `leaf_hash` and is included
+    /// under `root`
+    pub fn verify_inclusion(proof: &InclusionProof, root: Hash) -> bool {
+        merkle::leaf_hash(proof.leaf.as_bytes()) == proof.leaf_hash
+            && merkle::verify_proof(proof.leaf_hash, &proof.proof, root)
+    }
+
+    /// Verify a canonical snapshot against the root the contract holds for
+    /// its epoch
+    ///
+    /// This needs nothing but the canonical JSON, so it works the same on a
+    /// downloaded snapshot file as on a stored snapshot.
+    pub fn verification_report(
+        canonical_json: &str,
+        contract_id: &str,
+        on_chain_root: Option<Hash>,
+    ) -> Result<SnapshotVerificationReport> {
+        let (leaves, tree) = Self::merkle_tree(canonical_json)?;
+        let epoch = Self::snapshot_epoch(canonical_json)?;
+        let root = tree.root();
+
+        let status = match on_chain_root {
+            Some(on_chain) if on_chain == root => VerificationStatus::Verified,
+            Some(_) => VerificationStatus::RootMismatch,
+            None => VerificationStatus::NotOnChain,
+        };
+
+        Ok(SnapshotVerificationReport {
+            epoch,
+            contract_id: contract_id.to_string(),
+            status,
+            verified: status == VerificationStatus::Verified,
+            hash: hex::encode(Self::compute_sha256_hash_bytes(canonical_json)),
+            merkle_root: hex::encode(root),
+            leaf_count: leaves.len(),
+            on_chain_root: on_chain_root.map(hex::encode),
+            snapshot_id: None,
+            stored_hash: None,
+            stored_merkle_root: None,
+            checked_at: Utc::now(),
+        })
+    }
+
     /// Create snapshot, hash it, and submit to on-chain contract
     ///
     /// This method combines snapshot creation with automatic submission to the
@@ -907,6 +1710,95 @@ mod tests {
         assert_eq!(json1, json2);
     }
 
+    #[test]
+    fn test_merkle_leaves_follow_canonical_order() {
+        let now = Utc::now();
+        let mut snapshot = AnalyticsSnapshot::new(7, now);
+        snapshot.add_corridor_metrics(create_test_corridor_metrics(Uuid::from_u128(3), "c"));
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(2), "Anchor2"));
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(1), "Anchor1"));
+        let json = SnapshotService::serialize_deterministically(snapshot).unwrap();
+
+        let leaves = SnapshotService::merkle_leaves(&json).unwrap();
+        let ids: Vec<(SnapshotLeafKind, String)> =
+            leaves.iter().map(|l| (l.kind, l.id.clone())).collect();
+        assert_eq!(
+            ids,
+            vec![
+                (SnapshotLeafKind::Anchor, Uuid::from_u128(1).to_string()),
+                (SnapshotLeafKind::Anchor, Uuid::from_u128(2).to_string()),
+                (SnapshotLeafKind::Corridor, Uuid::from_u128(3).to_string()),
+            ]
+        );
+        let leaf: Value = serde_json::from_str(&leaves[0].data).unwrap();
+        assert_eq!(leaf["epoch"], 7);
+        assert_eq!(leaf["kind"], "anchor");
+        assert_eq!(leaf["metrics"]["name"], "Anchor1");
+        assert!(leaves[0]
+            .data
+            .starts_with(r#"{"epoch":7,"kind":"anchor","metrics":{"#));
+    }
+
+    #[test]
+    fn test_inclusion_proofs_verify_against_the_root() {
+        let now = Utc::now();
+        let mut snapshot = AnalyticsSnapshot::new(7, now);
+        for i in 1..=3 {
+            let id = Uuid::from_u128(i);
+            snapshot.add_anchor_metrics(create_test_anchor_metrics(id, &format!("Anchor{}", i)));
+        }
+        snapshot.add_corridor_metrics(create_test_corridor_metrics(Uuid::from_u128(9), "c"));
+        let json = SnapshotService::serialize_deterministically(snapshot).unwrap();
+        let (leaves, tree) = SnapshotService::merkle_tree(&json).unwrap();
+        assert_eq!(SnapshotService::merkle_root(&json).unwrap(), tree.root());
+
+        let mut proof = InclusionProof {
+            snapshot_id: "s".to_string(),
+            epoch: 7,
+            merkle_root: tree.root(),
+            kind: leaves[3].kind,
+            id: leaves[3].id.clone(),
+            leaf_index: 3,
+            leaf_count: tree.leaf_count(),
+            leaf: leaves[3].data.clone(),
+            leaf_hash: leaves[3].hash(),
+            proof: tree.proof(3).unwrap(),
+        };
+        assert!(SnapshotService::verify_inclusion(&proof, tree.root()));
+        assert!(!SnapshotService::verify_inclusion(&proof, [0u8; 32]));
+
+        // Altered metrics no longer match the leaf hash
+        proof.leaf = proof.leaf.replace("50000", "50001");
+        assert!(!SnapshotService::verify_inclusion(&proof, tree.root()));
+    }
+
+    #[test]
+    fn test_verification_report_compares_with_the_on_chain_root() {
+        let mut snapshot = AnalyticsSnapshot::new(5, Utc::now());
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(1), "Anchor1"));
+        let json = SnapshotService::serialize_deterministically(snapshot).unwrap();
+        let root = SnapshotService::merkle_root(&json).unwrap();
+
+        let report = SnapshotService::verification_report(&json, "CSNAP", Some(root)).unwrap();
+        assert_eq!(report.epoch, 5);
+        assert_eq!(report.status, VerificationStatus::Verified);
+        assert!(report.verified);
+        assert_eq!(report.merkle_root, hex::encode(root));
+        assert_eq!(report.on_chain_root, Some(hex::encode(root)));
+        assert_eq!(report.leaf_count, 1);
+        assert_eq!(
+            report.hash,
+            hex::encode(SnapshotService::compute_sha256_hash_bytes(&json))
+        );
+
+        let report = SnapshotService::verification_report(&json, "CSNAP", Some([0u8; 32])).unwrap();
+        assert_eq!(report.status, VerificationStatus::RootMismatch);
+        assert!(!report.verified);
+        let report = SnapshotService::verification_report(&json, "CSNAP", None).unwrap();
+        assert_eq!(report.status, VerificationStatus::NotOnChain);
+        assert!(SnapshotService::verification_report("{}", "CSNAP", None).is_err());
+    }
+
     #[test]
     fn test_json_key_ordering() {
         let now = Utc::now();
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::services::contract::TRANSACTION_TIMEOUT_SECS;
+use crate::services::snapshot::{SnapshotService, SubmissionStatus, VerificationStatus};
+
+#[derive(Debug, Clone)]
+pub struct SnapshotSchedulerConfig {
+    /// Length of an epoch: epoch `n` starts `n * epoch_secs` seconds after the
+    /// Unix epoch
+    pub epoch_secs: u64,
+    /// How often the scheduler looks for a new epoch and pending submissions
+    pub interval_secs: u64,
+    /// Failed submissions are retried until they failed this often
+    pub max_attempts: u32,
+    /// How often recent epochs are compared with the contract
+    pub reconcile_interval_secs: u64,
+    /// Number of most recent epochs compared with the contract
+    pub reconcile_epochs: i64,
+}
+
+impl Default for SnapshotSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            epoch_secs: 24 * 60 * 60,
+            interval_secs: 60,
+            max_attempts: 5,
+            reconcile_interval_secs: 60 * 60,
+            reconcile_epochs: 30,
+        }
+    }
+}
+
+/// Outcome of one scheduler run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SchedulerRunSummary {
+    /// Epoch generated in this run
+    pub generated: Option<u64>,
+    /// Pending epochs submission was attempted for
+    pub attempted: usize,
+    pub confirmed: usize,
+    pub failed: usize,
+    pub reconciled: usize,
+    /// Reconciled epochs that do not match the contract
+    pub mismatches: usize,
+}
+
+/// Generates a snapshot for every epoch and drives its submission to the
+/// contract: pending epochs are (re)submitted on every run, including the
+/// first one after a restart, submissions whose transaction timed out are
+/// retried, and recent epochs are regularly compared with the contract
+pub struct SnapshotScheduler {
+    snapshot_service: Arc<SnapshotService>,
+    config: SnapshotSchedulerConfig,
+}
+
+impl SnapshotScheduler {
+    pub fn new(snapshot_service: Arc<SnapshotService>, config: SnapshotSchedulerConfig) -> Self {
+        Self {
+            snapshot_service,
+            config,
+        }
+    }
+
+    /// Epoch that `now` falls in
+    pub fn epoch_at(&self, now: DateTime<Utc>) -> u64 {
+        now.timestamp().max(0) as u64 / self.config.epoch_secs.max(1)
+    }
+
+    /// Start the epoch scheduler
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting snapshot scheduler (epoch: {} seconds, interval: {} seconds)",
+            self.config.epoch_secs, self.config.interval_secs
+        );
+
+        let mut ticker = interval(Duration::from_secs(self.config.interval_secs));
+        let reconcile_interval = Duration::from_secs(self.config.reconcile_interval_secs);
+        let mut last_reconciled: Option<Instant> = None;
+
+        loop {
+            ticker.tick().await;
+            let reconcile = last_reconciled.is_none_or(|at| at.elapsed() >= reconcile_interval);
+            match self.run_once(Utc::now(), reconcile).await {
+                Ok(summary) => {
+                    if summary != SchedulerRunSummary::default() {
+                        info!(
+                            "Snapshot scheduler run finished: generated {:?}, {} attempted, {} confirmed, {} failed, {} reconciled, {} mismatches",
+                            summary.generated,
+                            summary.attempted,
+                            summary.confirmed,
+                            summary.failed,
+                            summary.reconciled,
+                            summary.mismatches
+                        );
+                    }
+                }
+                Err(e) => error!("Snapshot scheduler run failed: {}", e),
+            }
+            if reconcile {
+                last_reconciled = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Generate the current epoch if needed, submit pending epochs and, if
+    /// `reconcile` is set, compare recent epochs with the contract
+    pub async fn run_once(
+        &self,
+        now: DateTime<Utc>,
+        reconcile: bool,
+    ) -> Result<SchedulerRunSummary> {
+        let mut summary = SchedulerRunSummary::default();
+
+        let epoch = self.epoch_at(now);
+        // The contract does not accept epoch 0
+        if epoch > 0 && self.snapshot_service.submission(epoch).await?.is_none() {
+            match self.snapshot_service.generate_snapshot(epoch).await {
+                Ok(_) => summary.generated = Some(epoch),
+                Err(e) => error!("Failed to generate snapshot of epoch {}: {:#}", epoch, e),
+            }
+        }
+
+        if self.snapshot_service.contract_service().is_none() {
+            return Ok(summary);
+        }
+
+        for pending in self
+            .snapshot_service
+            .pending_submissions(
+                self.config.max_attempts,
+                now - chrono::Duration::seconds(TRANSACTION_TIMEOUT_SECS as i64),
+            )
+            .await?
+        {
+            summary.attempted += 1;
+            match self.snapshot_service.submit_epoch(pending.epoch).await {
+                Ok(submission) => match submission.status {
+                    SubmissionStatus::Confirmed => summary.confirmed += 1,
+                    SubmissionStatus::Failed => {
+                        summary.failed += 1;
+                        if submission.attempts >= self.config.max_attempts {
+                            warn!(
+                                "Giving up on snapshot of epoch {} after {} attempts",
+                                submission.epoch, submission.attempts
+                            );
+                        }
+                    }
+                    SubmissionStatus::Generated | SubmissionStatus::Submitted => {}
+                },
+                Err(e) => {
+                    summary.failed += 1;
+                    error!(
+                        "Failed to submit snapshot of epoch {}: {:#}",
+                        pending.epoch, e
+                    );
+                }
+            }
+        }
+
+        if reconcile {
+            self.reconcile(&mut summary).await?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Compare the most recent epochs that were sent to the contract with it
+    async fn reconcile(&self, summary: &mut SchedulerRunSummary) -> Result<()> {
+        let submissions = self
+            .snapshot_service
+            .list_submissions(None, self.config.reconcile_epochs)
+            .await?;
+
+        for submission in submissions
+            .iter()
+            .filter(|s| s.status != SubmissionStatus::Generated)
+        {
+            match self
+                .snapshot_service
+                .reconcile_epoch(submission.epoch)
+                .await
+            {
+                Ok(Some(report)) => {
+                    summary.reconciled += 1;
+                    // An epoch that never made it on-chain is merely pending
+                    let pending = report.status == VerificationStatus::NotOnChain
+                        && submission.status != SubmissionStatus::Confirmed;
+                    if report.status != VerificationStatus::Verified && !pending {
+                        summary.mismatches += 1;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => error!(
+                    "Failed to reconcile snapshot of epoch {}: {:#}",
+                    submission.epoch, e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+//! SEP-1 `stellar.toml` parsing.
+//!
+//! Documents are parsed with the `toml` crate; only the keys this service
+//! keeps track of are read and everything else is ignored.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::models::asset::{AssetId, AssetIdError};
+
+/// Largest `stellar.toml` accepted, as recommended by SEP-1
+pub const MAX_STELLAR_TOML_BYTES: usize = 100 * 1024;
+
+/// Organization details from the `[DOCUMENTATION]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct OrgDocumentation {
+    pub org_name: Option<String>,
+    pub org_dba: Option<String>,
+    pub org_url: Option<String>,
+    pub org_logo: Option<String>,
+    pub org_description: Option<String>,
+    pub org_physical_address: Option<String>,
+    pub org_official_email: Option<String>,
+    pub org_support_email: Option<String>,
+    pub org_twitter: Option<String>,
+    pub org_github: Option<String>,
+}
+
+/// One `[[CURRENCIES]]` entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TomlCurrency {
+    pub code: Option<String>,
+    pub issuer: Option<String>,
+    pub contract: Option<String>,
+    pub status: Option<String>,
+    pub display_decimals: Option<i64>,
+    pub name: Option<String>,
+    pub desc: Option<String>,
+    pub conditions: Option<String>,
+    pub is_asset_anchored: Option<bool>,
+    pub anchor_asset_type: Option<String>,
+    pub anchor_asset: Option<String>,
+    pub redemption_instructions: Option<String>,
+}
+
+impl TomlCurrency {
+    /// The on-chain asset this entry describes
+    pub fn asset_id(&self) -> Result<AssetId, AssetIdError> {
+        if let Some(contract) = &self.contract {
+            return AssetId::contract(contract);
+        }
+        let code = self.code.as_deref().ok_or(AssetIdError::Empty)?;
+        let issuer = self
+            .issuer
+            .as_deref()
+            .ok_or_else(|| AssetIdError::MissingIssuer(code.to_string()))?;
+        AssetId::credit(code, issuer)
+    }
+}
+
+/// The parts of a SEP-1 `stellar.toml` this service keeps track of
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct StellarToml {
+    pub version: Option<String>,
+    pub network_passphrase: Option<String>,
+    pub accounts: Vec<String>,
+    pub signing_key: Option<String>,
+    pub horizon_url: Option<String>,
+    pub federation_server: Option<String>,
+    pub auth_server: Option<String>,
+    /// SEP-6 deposit and withdrawal server
+    pub transfer_server: Option<String>,
+    /// SEP-24 interactive deposit and withdrawal server
+    pub transfer_server_sep0024: Option<String>,
+    /// SEP-12 KYC server
+    pub kyc_server: Option<String>,
+    /// SEP-10 web authentication endpoint
+    pub web_auth_endpoint: Option<String>,
+    /// SEP-31 cross-border payments server
+    pub direct_payment_server: Option<String>,
+    /// SEP-38 quote server
+    pub anchor_quote_server: Option<String>,
+    pub documentation: OrgDocumentation,
+    pub currencies: Vec<TomlCurrency>,
+}
+
+impl StellarToml {
+    /// Parse a `stellar.toml`; blank strings are treated as absent
+    pub fn parse(input: &str) -> Result<Self, toml::de::Error> {
+        let mut toml: Self = toml::from_str(input)?;
+
+        let doc = &mut toml.documentation;
+        for field in [
+            &mut toml.version,
+            &mut toml.network_passphrase,
+            &mut toml.signing_key,
+            &mut toml.horizon_url,
+            &mut toml.federation_server,
+            &mut toml.auth_server,
+            &mut toml.transfer_server,
+            &mut toml.transfer_server_sep0024,
+            &mut toml.kyc_server,
+            &mut toml.web_auth_endpoint,
+            &mut toml.direct_payment_server,
+            &mut toml.anchor_quote_server,
+            &mut doc.org_name,
+            &mut doc.org_dba,
+            &mut doc.org_url,
+            &mut doc.org_logo,
+            &mut doc.org_description,
+            &mut doc.org_physical_address,
+            &mut doc.org_official_email,
+            &mut doc.org_support_email,
+            &mut doc.org_twitter,
+            &mut doc.org_github,
+        ] {
+            trim_field(field);
+        }
+        for account in &mut toml.accounts {
+            *account = account.trim().to_string();
+        }
+        for currency in &mut toml.currencies {
+            for field in [
+                &mut currency.code,
+                &mut currency.issuer,
+                &mut currency.contract,
+                &mut currency.status,
+                &mut currency.name,
+                &mut currency.desc,
+                &mut currency.conditions,
+                &mut currency.anchor_asset_type,
+                &mut currency.anchor_asset,
+                &mut currency.redemption_instructions,
+            ] {
+                trim_field(field);
+            }
+        }
+
+        Ok(toml)
+    }
+
+    /// Flattened `KEY -> value` view used to detect changes between crawls.
+    ///
+    /// Currency fields are keyed by asset, e.g. `CURRENCIES.USDC:GA5Z....STATUS`.
+    pub fn fields(&self) -> BTreeMap<String, String> {
+        let mut fields = BTreeMap::new();
+        let mut put = |key: String, value: Option<String>| {
+            if let Some(value) = value {
+                fields.insert(key, value);
+            }
+        };
+
+        put("VERSION".into(), self.version.clone());
+        put("NETWORK_PASSPHRASE".into(), self.network_passphrase.clone());
+        put(
+            "ACCOUNTS".into(),
+            (!self.accounts.is_empty()).then(|| self.accounts.join(",")),
+        );
+        put("SIGNING_KEY".into(), self.signing_key.clone());
+        put("HORIZON_URL".into(), self.horizon_url.clone());
+        put("FEDERATION_SERVER".into(), self.federation_server.clone());
+        put("AUTH_SERVER".into(), self.auth_server.clone());
+        put("TRANSFER_SERVER".into(), self.transfer_server.clone());
+        put(
+            "TRANSFER_SERVER_SEP0024".into(),
+            self.transfer_server_sep0024.clone(),
+        );
+        put("KYC_SERVER".into(), self.kyc_server.clone());
+        put("WEB_AUTH_ENDPOINT".into(), self.web_auth_endpoint.clone());
+        put(
+            "DIRECT_PAYMENT_SERVER".into(),
+            self.direct_payment_server.clone(),
+        );
+        put(
+            "ANCHOR_QUOTE_SERVER".into(),
+            self.anchor_quote_server.clone(),
+        );
+
+        let doc = &self.documentation;
+        for (key, value) in [
+            ("ORG_NAME", &doc.org_name),
+            ("ORG_DBA", &doc.org_dba),
+            ("ORG_URL", &doc.org_url),
+            ("ORG_LOGO", &doc.org_logo),
+            ("ORG_DESCRIPTION", &doc.org_description),
+            ("ORG_PHYSICAL_ADDRESS", &doc.org_physical_address),
+            ("ORG_OFFICIAL_EMAIL", &doc.org_official_email),
+            ("ORG_SUPPORT_EMAIL", &doc.org_support_email),
+            ("ORG_TWITTER", &doc.org_twitter),
+            ("ORG_GITHUB", &doc.org_github),
+        ] {
+            put(format!("DOCUMENTATION.{}", key), value.clone());
+        }
+
+        for (index, currency) in self.currencies.iter().enumerate() {
+            let id = currency
+                .asset_id()
+                .map(|asset| asset.key())
+                .unwrap_or_else(|_| format!("#{}", index));
+            for (key, value) in [
+                ("CODE", currency.code.clone()),
+                ("ISSUER", currency.issuer.clone()),
+                ("CONTRACT", currency.contract.clone()),
+                ("STATUS", currency.status.clone()),
+                (
+                    "DISPLAY_DECIMALS",
+                    currency.display_decimals.map(|d| d.to_string()),
+                ),
+                ("NAME", currency.name.clone()),
+                ("DESC", currency.desc.clone()),
+                ("CONDITIONS", currency.conditions.clone()),
+                (
+                    "IS_ASSET_ANCHORED",
+                    currency.is_asset_anchored.map(|b| b.to_string()),
+                ),
+                ("ANCHOR_ASSET_TYPE", currency.anchor_asset_type.clone()),
+                ("ANCHOR_ASSET", currency.anchor_asset.clone()),
+                (
+                    "REDEMPTION_INSTRUCTIONS",
+                    currency.redemption_instructions.clone(),
+                ),
+            ] {
+                put(format!("CURRENCIES.{}.{}", id, key), value);
+            }
+        }
+
+        fields
+    }
+}
+
+fn trim_field(field: &mut Option<String>) {
+    *field = field
+        .take()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let err =
+            StellarToml::parse("VERSION = \"2.0.0\"\nSIGNING_KEY = \"unterminated\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+
+        let err = StellarToml::parse("VERSION = \"1\"\nVERSION = \"2\"\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_stellar_toml_fields() {
+        let toml = StellarToml::parse(&format!(
+            r#"
+ACCOUNTS = [" {issuer} "]
+SIGNING_KEY = "  "
+TRANSFER_SERVER_SEP0024 = "https://anchor.example/sep24"
+UNKNOWN_KEY = 1979-05-27T07:32:00Z
+WEB_AUTH_ENDPOINT = "https://anchor.example/auth"
+
+[DOCUMENTATION]
+ORG_NAME = "Example Anchor"
+
+[[CURRENCIES]]
+code = "USDC"
+issuer = "{issuer}"
+display_decimals = 2
+is_asset_anchored = true
+"#,
+            issuer = ISSUER
+        ))
+        .unwrap();
+
+        assert_eq!(toml.accounts, vec![ISSUER.to_string()]);
+        assert_eq!(toml.signing_key, None);
+        assert_eq!(
+            toml.documentation.org_name.as_deref(),
+            Some("Example Anchor")
+        );
+        assert_eq!(
+            toml.currencies[0].asset_id().unwrap().to_string(),
+            format!("USDC:{}", ISSUER)
+        );
+
+        let fields = toml.fields();
+        assert_eq!(
+            fields.get(&format!("CURRENCIES.USDC:{}.DISPLAY_DECIMALS", ISSUER)),
+            Some(&"2".to_string())
+        );
+        assert_eq!(
+            fields.get("WEB_AUTH_ENDPOINT").map(String::as_str),
+            Some("https://anchor.example/auth")
+        );
+    }
+
+    #[test]
+    fn test_stellar_toml_rejects_wrong_types() {
+        let err = StellarToml::parse("[[CURRENCIES]]\ncode = \"USDC\"\ndisplay_decimals = \"2\"\n")
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid type"), "{}", err);
+
+        assert!(StellarToml::parse("DOCUMENTATION = \"Example\"\n").is_err());
+    }
+}
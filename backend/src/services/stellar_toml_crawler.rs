@@ -0,0 +1,875 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::redirect::Policy;
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::models::asset::AssetId;
+use crate::models::{Anchor, CreateAnchorRequest};
+use crate::rpc::StellarRpcClient;
+use crate::services::stellar_toml::{StellarToml, TomlCurrency, MAX_STELLAR_TOML_BYTES};
+
+#[derive(Debug, Clone)]
+pub struct StellarTomlCrawlerConfig {
+    /// How often every anchor is re-crawled
+    pub interval_secs: u64,
+    /// URL scheme used to reach home domains; only tests use plain `http`
+    pub scheme: String,
+    pub timeout_secs: u64,
+    /// Number of top-rated Horizon assets whose issuers are checked for new anchors
+    pub discovery_limit: u32,
+    /// Require currency issuers other than the anchor's own account to point
+    /// their `home_domain` back at the crawled domain
+    pub verify_issuers: bool,
+    /// Allow ports, IP literals and hosts resolving to loopback or private
+    /// addresses; only tests reach local servers
+    pub allow_private_hosts: bool,
+}
+
+impl Default for StellarTomlCrawlerConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 6 * 3600,
+            scheme: "https".to_string(),
+            timeout_secs: 10,
+            discovery_limit: 50,
+            verify_issuers: true,
+            allow_private_hosts: false,
+        }
+    }
+}
+
+/// Latest crawl state and organization info of an anchor
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnchorTomlInfo {
+    pub anchor_id: String,
+    pub home_domain: String,
+    pub org_name: Option<String>,
+    pub org_dba: Option<String>,
+    pub org_url: Option<String>,
+    pub org_logo: Option<String>,
+    pub org_description: Option<String>,
+    pub org_official_email: Option<String>,
+    pub org_support_email: Option<String>,
+    pub network_passphrase: Option<String>,
+    pub signing_key: Option<String>,
+    pub transfer_server: Option<String>,
+    pub transfer_server_sep0024: Option<String>,
+    pub kyc_server: Option<String>,
+    pub web_auth_endpoint: Option<String>,
+    pub direct_payment_server: Option<String>,
+    pub anchor_quote_server: Option<String>,
+    pub content_hash: Option<String>,
+    pub last_fetched_at: DateTime<Utc>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Currency an anchor publishes in its stellar.toml
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnchorTomlCurrency {
+    pub anchor_id: String,
+    pub asset: String,
+    pub asset_code: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub display_decimals: Option<i64>,
+    pub is_asset_anchored: Option<bool>,
+    pub anchor_asset_type: Option<String>,
+    pub anchor_asset: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A recorded change of an anchor's stellar.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorTomlChange {
+    pub id: i64,
+    pub anchor_id: String,
+    pub home_domain: String,
+    pub content_hash: String,
+    /// Fields that were added, removed or modified compared to the previous version
+    pub changed_fields: Vec<String>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrawlOutcome {
+    /// The file is byte-for-byte identical to the last crawl
+    Unchanged,
+    Updated {
+        changed_fields: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrawlSummary {
+    pub discovered: usize,
+    pub crawled: usize,
+    pub updated: usize,
+    pub failed: usize,
+}
+
+/// Crawls anchors' SEP-1 `stellar.toml` files.
+///
+/// Each anchor's home domain (resolved from Horizon when unset) is fetched at
+/// `/.well-known/stellar.toml`; organization info, SEP endpoints and
+/// `[[CURRENCIES]]` are upserted, and every content change is recorded with
+/// the list of fields that changed.
+pub struct StellarTomlCrawler {
+    db: Arc<Database>,
+    rpc_client: Arc<StellarRpcClient>,
+    client: Client,
+    config: StellarTomlCrawlerConfig,
+}
+
+impl StellarTomlCrawler {
+    pub fn new(
+        db: Arc<Database>,
+        rpc_client: Arc<StellarRpcClient>,
+        config: StellarTomlCrawlerConfig,
+    ) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            db,
+            rpc_client,
+            client,
+            config,
+        })
+    }
+
+    /// Start the crawl scheduler
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting stellar.toml crawler (interval: {} seconds)",
+            self.config.interval_secs
+        );
+
+        let mut ticker = interval(Duration::from_secs(self.config.interval_secs));
+
+        loop {
+            ticker.tick().await;
+            match self.run_crawl().await {
+                Ok(summary) => info!(
+                    "stellar.toml crawl finished: {} discovered, {} crawled, {} updated, {} failed",
+                    summary.discovered, summary.crawled, summary.updated, summary.failed
+                ),
+                Err(e) => error!("stellar.toml crawl failed: {}", e),
+            }
+        }
+    }
+
+    /// Discover new anchors, then crawl every known anchor
+    pub async fn run_crawl(&self) -> Result<CrawlSummary> {
+        let mut summary = CrawlSummary::default();
+
+        if self.config.discovery_limit > 0 {
+            match self.discover_anchors(self.config.discovery_limit).await {
+                Ok(anchors) => summary.discovered = anchors.len(),
+                Err(e) => warn!("Anchor discovery failed: {}", e),
+            }
+        }
+
+        let anchors = self.db.list_anchors(i64::MAX, 0).await?;
+        for anchor in &anchors {
+            summary.crawled += 1;
+            match self.crawl_anchor(anchor).await {
+                Ok(CrawlOutcome::Updated { .. }) => summary.updated += 1,
+                Ok(CrawlOutcome::Unchanged) => {}
+                Err(e) => {
+                    summary.failed += 1;
+                    warn!(
+                        "Failed to crawl stellar.toml for anchor {}: {}",
+                        anchor.id, e
+                    );
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Create anchors for issuers of top-rated assets whose home domain's
+    /// stellar.toml lists one of their assets.
+    pub async fn discover_anchors(&self, limit: u32) -> Result<Vec<Anchor>> {
+        let assets = self
+            .rpc_client
+            .fetch_assets(limit, true)
+            .await
+            .context("Failed to fetch assets for anchor discovery")?;
+
+        let issuers: HashSet<String> = assets.into_iter().map(|a| a.asset_issuer).collect();
+        let mut created = Vec::new();
+
+        for issuer in issuers {
+            if self
+                .db
+                .get_anchor_by_stellar_account(&issuer)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            let domain = match self.resolve_home_domain(&issuer).await {
+                Ok(Some(domain)) => domain,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("{}", e);
+                    continue;
+                }
+            };
+
+            let toml = match self.fetch_toml(&domain).await {
+                Ok(raw) => match StellarToml::parse(&raw) {
+                    Ok(toml) => toml,
+                    Err(e) => {
+                        warn!("Invalid stellar.toml at {}: {}", domain, e);
+                        continue;
+                    }
+                },
+                Err(e) => {
+                    warn!("Failed to fetch stellar.toml from {}: {}", domain, e);
+                    continue;
+                }
+            };
+
+            // The account points at the domain; the domain must claim the account back
+            let claimed = toml.accounts.contains(&issuer)
+                || toml
+                    .currencies
+                    .iter()
+                    .any(|c| c.issuer.as_deref() == Some(issuer.as_str()));
+            if !claimed {
+                continue;
+            }
+
+            let anchor = self
+                .db
+                .create_anchor(CreateAnchorRequest {
+                    name: toml
+                        .documentation
+                        .org_name
+                        .clone()
+                        .unwrap_or_else(|| domain.clone()),
+                    stellar_account: issuer.clone(),
+                    home_domain: Some(domain.clone()),
+                })
+                .await?;
+            info!(
+                "Discovered anchor {} ({}) from {}",
+                anchor.name, issuer, domain
+            );
+            created.push(anchor);
+        }
+
+        Ok(created)
+    }
+
+    /// Fetch, parse and store an anchor's stellar.toml.
+    ///
+    /// Failures are recorded on the anchor's crawl state before being returned.
+    pub async fn crawl_anchor(&self, anchor: &Anchor) -> Result<CrawlOutcome> {
+        let domain = match anchor.home_domain.as_deref().map(str::trim) {
+            Some(domain) if !domain.is_empty() => domain.to_string(),
+            _ => {
+                let domain = self
+                    .resolve_home_domain(&anchor.stellar_account)
+                    .await?
+                    .ok_or_else(|| anyhow!("anchor account has no home_domain"))?;
+                sqlx::query("UPDATE anchors SET home_domain = $1, updated_at = $2 WHERE id = $3")
+                    .bind(&domain)
+                    .bind(Utc::now())
+                    .bind(&anchor.id)
+                    .execute(self.db.pool())
+                    .await?;
+                domain
+            }
+        };
+
+        let fetched_at = Utc::now();
+        let fetched = match self.fetch_toml(&domain).await {
+            Ok(raw) => StellarToml::parse(&raw)
+                .map(|toml| (raw, toml))
+                .map_err(|e| anyhow!("invalid stellar.toml: {}", e)),
+            Err(e) => Err(e),
+        };
+
+        match fetched {
+            Ok((raw, toml)) => self.store(anchor, &domain, &raw, &toml, fetched_at).await,
+            Err(e) => {
+                self.record_failure(&anchor.id, &domain, &e.to_string(), fetched_at)
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Latest crawl state of an anchor, if it was ever crawled
+    pub async fn get_info(&self, anchor_id: &str) -> Result<Option<AnchorTomlInfo>> {
+        let info = sqlx::query_as::<_, AnchorTomlInfo>(
+            "SELECT * FROM anchor_stellar_toml WHERE anchor_id = $1",
+        )
+        .bind(anchor_id)
+        .fetch_optional(self.db.pool())
+        .await?;
+        Ok(info)
+    }
+
+    pub async fn get_currencies(&self, anchor_id: &str) -> Result<Vec<AnchorTomlCurrency>> {
+        let currencies = sqlx::query_as::<_, AnchorTomlCurrency>(
+            r#"
+            SELECT * FROM anchor_stellar_toml_currencies
+            WHERE anchor_id = $1
+            ORDER BY asset ASC
+            "#,
+        )
+        .bind(anchor_id)
+        .fetch_all(self.db.pool())
+        .await?;
+        Ok(currencies)
+    }
+
+    /// Recorded stellar.toml changes of an anchor, newest first
+    pub async fn get_history(&self, anchor_id: &str, limit: i64) -> Result<Vec<AnchorTomlChange>> {
+        let rows = sqlx::query_as::<_, (i64, String, String, String, String, DateTime<Utc>)>(
+            r#"
+            SELECT id, anchor_id, home_domain, content_hash, changed_fields, fetched_at
+            FROM anchor_stellar_toml_history
+            WHERE anchor_id = $1
+            ORDER BY fetched_at DESC, id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        rows.into_iter()
+            .map(
+                |(id, anchor_id, home_domain, content_hash, changed_fields, fetched_at)| {
+                    Ok(AnchorTomlChange {
+                        id,
+                        anchor_id,
+                        home_domain,
+                        content_hash,
+                        changed_fields: serde_json::from_str(&changed_fields)
+                            .context("Invalid changed_fields in stellar.toml history")?,
+                        fetched_at,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    async fn resolve_home_domain(&self, account_id: &str) -> Result<Option<String>> {
+        let account = self
+            .rpc_client
+            .fetch_account(account_id)
+            .await
+            .with_context(|| format!("Failed to resolve home_domain of {}", account_id))?;
+        Ok(account
+            .home_domain
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty()))
+    }
+
+    /// Download `/.well-known/stellar.toml` from a home domain
+    pub async fn fetch_toml(&self, domain: &str) -> Result<String> {
+        validate_domain(domain, self.config.allow_private_hosts)?;
+        let url = format!(
+            "{}://{}/.well-known/stellar.toml",
+            self.config.scheme, domain
+        );
+
+        let client = if self.config.allow_private_hosts {
+            self.client.clone()
+        } else {
+            self.public_client(domain).await?
+        };
+        let mut response = client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?;
+        if !response.status().is_success() {
+            bail!("{} returned {}", url, response.status());
+        }
+        if response
+            .content_length()
+            .is_some_and(|len| len as usize > MAX_STELLAR_TOML_BYTES)
+        {
+            bail!("{} exceeds {} bytes", url, MAX_STELLAR_TOML_BYTES);
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+            if body.len() > MAX_STELLAR_TOML_BYTES {
+                bail!("{} exceeds {} bytes", url, MAX_STELLAR_TOML_BYTES);
+            }
+        }
+
+        String::from_utf8(body).with_context(|| format!("{} is not valid UTF-8", url))
+    }
+
+    /// Client that reaches `domain` only at the public addresses it resolves
+    /// to now, so the name cannot be re-pointed at a private address between
+    /// the check and the connection. Redirects must stay on the same host.
+    async fn public_client(&self, domain: &str) -> Result<Client> {
+        let addrs: Vec<SocketAddr> = tokio::net::lookup_host((domain, 443))
+            .await
+            .with_context(|| format!("Failed to resolve {}", domain))?
+            .collect();
+        if addrs.is_empty() {
+            bail!("{} does not resolve", domain);
+        }
+        if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+            bail!("{} resolves to non-public address {}", domain, addr.ip());
+        }
+
+        let host = domain.to_ascii_lowercase();
+        Client::builder()
+            .timeout(Duration::from_secs(self.config.timeout_secs))
+            .resolve_to_addrs(domain, &addrs)
+            .redirect(Policy::custom(move |attempt| {
+                let url = attempt.url();
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if url.host_str() != Some(host.as_str()) || url.port().is_some() {
+                    let error = format!("redirect to another host: {}", url);
+                    attempt.error(error)
+                } else {
+                    attempt.follow()
+                }
+            }))
+            .build()
+            .context("Failed to build HTTP client")
+    }
+
+    /// Currencies of the file that may be attributed to the anchor.
+    ///
+    /// Assets issued by the anchor's own account (or contract assets) are taken
+    /// as published; other issuers must name the crawled domain as their
+    /// `home_domain` when `verify_issuers` is set.
+    async fn accepted_currencies<'t>(
+        &self,
+        anchor: &Anchor,
+        domain: &str,
+        toml: &'t StellarToml,
+    ) -> Vec<(AssetId, &'t TomlCurrency)> {
+        let mut issuer_domains: HashMap<String, Option<String>> = HashMap::new();
+        let mut accepted = Vec::new();
+
+        for currency in &toml.currencies {
+            let asset = match currency.asset_id() {
+                Ok(asset) => asset,
+                Err(e) => {
+                    warn!("Skipping currency in stellar.toml of {}: {}", domain, e);
+                    continue;
+                }
+            };
+
+            if let AssetId::Credit { issuer, .. } = &asset {
+                if self.config.verify_issuers && issuer != &anchor.stellar_account {
+                    if !issuer_domains.contains_key(issuer) {
+                        let resolved = self.resolve_home_domain(issuer).await.unwrap_or_else(|e| {
+                            warn!("{}", e);
+                            None
+                        });
+                        issuer_domains.insert(issuer.clone(), resolved);
+                    }
+                    if issuer_domains[issuer].as_deref() != Some(domain) {
+                        warn!(
+                            "Skipping {} from stellar.toml of {}: issuer home_domain does not match",
+                            asset, domain
+                        );
+                        continue;
+                    }
+                }
+            }
+
+            accepted.push((asset, currency));
+        }
+
+        accepted
+    }
+
+    async fn store(
+        &self,
+        anchor: &Anchor,
+        domain: &str,
+        raw: &str,
+        toml: &StellarToml,
+        fetched_at: DateTime<Utc>,
+    ) -> Result<CrawlOutcome> {
+        let content_hash = hex::encode(Sha256::digest(raw.as_bytes()));
+
+        let previous = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT content_hash, fields FROM anchor_stellar_toml_history
+            WHERE anchor_id = $1
+            ORDER BY fetched_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&anchor.id)
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        if previous
+            .as_ref()
+            .is_some_and(|(hash, _)| hash == &content_hash)
+        {
+            sqlx::query(
+                r#"
+                UPDATE anchor_stellar_toml
+                SET home_domain = $1, last_fetched_at = $2, last_success_at = $2,
+                    last_error = NULL, updated_at = $2
+                WHERE anchor_id = $3
+                "#,
+            )
+            .bind(domain)
+            .bind(fetched_at)
+            .bind(&anchor.id)
+            .execute(self.db.pool())
+            .await?;
+            return Ok(CrawlOutcome::Unchanged);
+        }
+
+        let fields = toml.fields();
+        let previous_fields: BTreeMap<String, String> = match &previous {
+            Some((_, fields)) => {
+                serde_json::from_str(fields).context("Invalid fields in stellar.toml history")?
+            }
+            None => BTreeMap::new(),
+        };
+        let changed_fields = changed_fields(&previous_fields, &fields);
+        let currencies = self.accepted_currencies(anchor, domain, toml).await;
+
+        let mut tx = self.db.pool().begin().await?;
+
+        let doc = &toml.documentation;
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_stellar_toml (
+                anchor_id, home_domain, org_name, org_dba, org_url, org_logo, org_description,
+                org_official_email, org_support_email, network_passphrase, signing_key,
+                transfer_server, transfer_server_sep0024, kyc_server, web_auth_endpoint,
+                direct_payment_server, anchor_quote_server, content_hash,
+                last_fetched_at, last_success_at, last_error, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $19, NULL, $19)
+            ON CONFLICT (anchor_id) DO UPDATE SET
+                home_domain = EXCLUDED.home_domain,
+                org_name = EXCLUDED.org_name,
+                org_dba = EXCLUDED.org_dba,
+                org_url = EXCLUDED.org_url,
+                org_logo = EXCLUDED.org_logo,
+                org_description = EXCLUDED.org_description,
+                org_official_email = EXCLUDED.org_official_email,
+                org_support_email = EXCLUDED.org_support_email,
+                network_passphrase = EXCLUDED.network_passphrase,
+                signing_key = EXCLUDED.signing_key,
+                transfer_server = EXCLUDED.transfer_server,
+                transfer_server_sep0024 = EXCLUDED.transfer_server_sep0024,
+                kyc_server = EXCLUDED.kyc_server,
+                web_auth_endpoint = EXCLUDED.web_auth_endpoint,
+                direct_payment_server = EXCLUDED.direct_payment_server,
+                anchor_quote_server = EXCLUDED.anchor_quote_server,
+                content_hash = EXCLUDED.content_hash,
+                last_fetched_at = EXCLUDED.last_fetched_at,
+                last_success_at = EXCLUDED.last_success_at,
+                last_error = NULL,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&anchor.id)
+        .bind(domain)
+        .bind(&doc.org_name)
+        .bind(&doc.org_dba)
+        .bind(&doc.org_url)
+        .bind(&doc.org_logo)
+        .bind(&doc.org_description)
+        .bind(&doc.org_official_email)
+        .bind(&doc.org_support_email)
+        .bind(&toml.network_passphrase)
+        .bind(&toml.signing_key)
+        .bind(&toml.transfer_server)
+        .bind(&toml.transfer_server_sep0024)
+        .bind(&toml.kyc_server)
+        .bind(&toml.web_auth_endpoint)
+        .bind(&toml.direct_payment_server)
+        .bind(&toml.anchor_quote_server)
+        .bind(&content_hash)
+        .bind(fetched_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM anchor_stellar_toml_currencies WHERE anchor_id = $1")
+            .bind(&anchor.id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (asset, currency) in &currencies {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO anchor_stellar_toml_currencies (
+                    anchor_id, asset, asset_code, name, description, status, display_decimals,
+                    is_asset_anchored, anchor_asset_type, anchor_asset, updated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(&anchor.id)
+            .bind(asset)
+            .bind(&currency.code)
+            .bind(&currency.name)
+            .bind(&currency.desc)
+            .bind(&currency.status)
+            .bind(currency.display_decimals)
+            .bind(currency.is_asset_anchored)
+            .bind(&currency.anchor_asset_type)
+            .bind(&currency.anchor_asset)
+            .bind(fetched_at)
+            .execute(&mut *tx)
+            .await?;
+
+            // Classic assets also become the anchor's tracked assets
+            if let AssetId::Credit { code, issuer } = asset {
+                sqlx::query(
+                    r#"
+                    INSERT INTO assets (id, anchor_id, asset_code, asset_issuer)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (asset_code, asset_issuer) DO UPDATE
+                    SET anchor_id = EXCLUDED.anchor_id,
+                        updated_at = CURRENT_TIMESTAMP
+                    "#,
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&anchor.id)
+                .bind(code)
+                .bind(issuer)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_stellar_toml_history (
+                anchor_id, home_domain, content_hash, fields, changed_fields, raw_toml, fetched_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(&anchor.id)
+        .bind(domain)
+        .bind(&content_hash)
+        .bind(serde_json::to_string(&fields)?)
+        .bind(serde_json::to_string(&changed_fields)?)
+        .bind(raw)
+        .bind(fetched_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        info!(
+            "Updated stellar.toml of anchor {} from {} ({} fields changed, {} currencies)",
+            anchor.id,
+            domain,
+            changed_fields.len(),
+            currencies.len()
+        );
+        Ok(CrawlOutcome::Updated { changed_fields })
+    }
+
+    async fn record_failure(
+        &self,
+        anchor_id: &str,
+        domain: &str,
+        error: &str,
+        fetched_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_stellar_toml (anchor_id, home_domain, last_fetched_at, last_error, updated_at)
+            VALUES ($1, $2, $3, $4, $3)
+            ON CONFLICT (anchor_id) DO UPDATE SET
+                home_domain = EXCLUDED.home_domain,
+                last_fetched_at = EXCLUDED.last_fetched_at,
+                last_error = EXCLUDED.last_error,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(anchor_id)
+        .bind(domain)
+        .bind(fetched_at)
+        .bind(error)
+        .execute(self.db.pool())
+        .await?;
+        Ok(())
+    }
+}
+
+/// Keys whose values were added, removed or modified between two versions
+fn changed_fields(
+    previous: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<String> {
+    let mut changed: Vec<String> = current
+        .iter()
+        .filter(|(key, value)| previous.get(*key) != Some(value))
+        .map(|(key, _)| key.clone())
+        .chain(
+            previous
+                .keys()
+                .filter(|key| !current.contains_key(*key))
+                .cloned(),
+        )
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// Home domains come from on-chain account data and API callers, so only a
+/// bare host name is accepted before building a URL from it. Ports and IP
+/// literals are only allowed with `allow_private`.
+fn validate_domain(domain: &str, allow_private: bool) -> Result<()> {
+    let valid = !domain.is_empty()
+        && domain.len() <= 253
+        && domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'))
+        && !domain.starts_with(['.', '-', ':']);
+    if !valid {
+        bail!("invalid home domain '{}'", domain);
+    }
+    if !allow_private {
+        if domain.contains(':') {
+            bail!("home domain '{}' names a port", domain);
+        }
+        // URL parsers read numeric hosts such as `2130706433` as IPv4
+        let url = Url::parse(&format!("https://{}/", domain))
+            .map_err(|_| anyhow!("invalid home domain '{}'", domain))?;
+        if url
+            .host_str()
+            .is_some_and(|host| host.parse::<IpAddr>().is_ok())
+        {
+            bail!("home domain '{}' is an IP address", domain);
+        }
+    }
+    Ok(())
+}
+
+/// Redirects followed when fetching a stellar.toml
+const MAX_REDIRECTS: usize = 5;
+
+/// Whether an address is reachable on the public internet, as opposed to
+/// loopback, private, link-local, shared, documentation or multicast ranges
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                // 100.64.0.0/10 carrier-grade NAT
+                || (a == 100 && (64..128).contains(&b))
+                // 198.18.0.0/15 benchmarking
+                || (a == 198 && (b == 18 || b == 19))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // fc00::/7 unique local
+                || (first & 0xfe00) == 0xfc00
+                // fe80::/10 link-local
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_fields() {
+        let previous = BTreeMap::from([
+            ("A".to_string(), "1".to_string()),
+            ("B".to_string(), "2".to_string()),
+            ("C".to_string(), "3".to_string()),
+        ]);
+        let current = BTreeMap::from([
+            ("A".to_string(), "1".to_string()),
+            ("B".to_string(), "changed".to_string()),
+            ("D".to_string(), "4".to_string()),
+        ]);
+
+        assert_eq!(changed_fields(&previous, &current), vec!["B", "C", "D"]);
+        assert!(changed_fields(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_validate_domain() {
+        assert!(validate_domain("anchor.example.com", false).is_ok());
+        assert!(validate_domain("127.0.0.1:8000", true).is_ok());
+        assert!(validate_domain("127.0.0.1:8000", false).is_err());
+        assert!(validate_domain("anchor.example.com:6379", false).is_err());
+        assert!(validate_domain("169.254.169.254", false).is_err());
+        // Numeric hosts are IPv4 addresses to URL parsers
+        assert!(validate_domain("2130706433", false).is_err());
+        assert!(validate_domain("evil.com/path", false).is_err());
+        assert!(validate_domain("user@evil.com", false).is_err());
+        assert!(validate_domain("", false).is_err());
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+}
@@ -0,0 +1,925 @@
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::Client;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::services::sep10_client::Sep10Client;
+
+/// Statuses after which an anchor no longer changes a transaction
+pub const TERMINAL_STATUSES: [&str; 7] = [
+    "completed",
+    "refunded",
+    "expired",
+    "error",
+    "no_market",
+    "too_small",
+    "too_large",
+];
+
+/// Terminal statuses that count as failures in the error rate
+const FAILED_STATUSES: [&str; 5] = ["error", "expired", "no_market", "too_small", "too_large"];
+
+pub fn is_terminal(status: &str) -> bool {
+    TERMINAL_STATUSES.contains(&status)
+}
+
+/// SEP through which a transfer was initiated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferSep {
+    Sep24,
+    Sep31,
+}
+
+impl TransferSep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferSep::Sep24 => "sep24",
+            TransferSep::Sep31 => "sep31",
+        }
+    }
+
+    /// Endpoint returning a single transaction of the anchor
+    fn transaction_url(self, transfer_server: &str, id: &str) -> String {
+        let base = transfer_server.trim().trim_end_matches('/');
+        match self {
+            TransferSep::Sep24 => format!("{}/transaction?id={}", base, urlencoding::encode(id)),
+            TransferSep::Sep31 => format!("{}/transactions/{}", base, urlencoding::encode(id)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TransferTrackerConfig {
+    /// How often open transfers are polled
+    pub interval_secs: u64,
+    pub timeout_secs: u64,
+    /// Public base URL of this backend; when set, anchors are given a
+    /// callback URL under it and report status changes themselves
+    pub callback_base_url: Option<String>,
+    /// Transfers still open after this many hours are no longer polled
+    pub max_age_hours: i64,
+}
+
+impl Default for TransferTrackerConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 60,
+            timeout_secs: 30,
+            callback_base_url: None,
+            max_age_hours: 24 * 7,
+        }
+    }
+}
+
+/// A SEP-24 or SEP-31 transaction initiated through the proxies
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AnchorTransfer {
+    pub id: String,
+    pub anchor_id: Option<String>,
+    pub sep: String,
+    /// `deposit`, `withdrawal` or `send`
+    pub kind: String,
+    pub transfer_server: String,
+    /// Id assigned by the anchor
+    pub anchor_transaction_id: String,
+    /// User who initiated the transfer; only they can look it up
+    #[serde(skip)]
+    pub owner: Option<String>,
+    /// Domain the backend's key authenticates with while polling
+    pub home_domain: Option<String>,
+    /// Initiated with the caller's own anchor JWT, which is not kept; only
+    /// callbacks update the transfer
+    pub caller_token: bool,
+    #[serde(skip)]
+    pub callback_token: String,
+    pub asset_code: Option<String>,
+    pub amount: Option<String>,
+    pub status: String,
+    /// The status is terminal and the transfer is no longer polled
+    pub terminal: bool,
+    pub poll_error: Option<String>,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl AnchorTransfer {
+    fn sep(&self) -> Result<TransferSep> {
+        match self.sep.as_str() {
+            "sep24" => Ok(TransferSep::Sep24),
+            "sep31" => Ok(TransferSep::Sep31),
+            other => bail!("unknown transfer SEP: {}", other),
+        }
+    }
+}
+
+/// One status a transfer passed through
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TransferStatusChange {
+    pub id: i64,
+    pub transfer_id: String,
+    /// `None` for the status the transfer was initiated with
+    pub from_status: Option<String>,
+    pub to_status: String,
+    /// `initiated`, `poll` or `callback`
+    pub source: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// A transfer about to be initiated: its id and the callback URL to hand to
+/// the anchor are known before the anchor assigns its own id
+#[derive(Debug, Clone)]
+pub struct TransferReservation {
+    pub id: String,
+    pub callback_token: String,
+    pub callback_url: Option<String>,
+}
+
+/// A transfer the anchor accepted
+#[derive(Debug, Clone)]
+pub struct NewTransfer {
+    pub sep: TransferSep,
+    pub kind: String,
+    pub transfer_server: String,
+    pub anchor_transaction_id: String,
+    /// Signed-in user who initiated the transfer
+    pub owner: Option<String>,
+    /// Domain the backend's key authenticated with, never one taken from
+    /// the request
+    pub home_domain: Option<String>,
+    /// The anchor was called with the caller's own JWT
+    pub caller_token: bool,
+    pub asset_code: Option<String>,
+    pub amount: Option<String>,
+    pub status: String,
+}
+
+/// How long transfers stayed in one status before moving on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusDuration {
+    pub status: String,
+    /// Number of times a transfer left this status
+    pub exits: i64,
+    pub avg_secs: f64,
+    pub max_secs: f64,
+}
+
+/// Operational metrics of the transfers initiated with one anchor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferMetrics {
+    /// `None` groups transfers whose transfer server belongs to no known anchor
+    pub anchor_id: Option<String>,
+    pub window_days: i64,
+    pub transfers: i64,
+    pub in_progress: i64,
+    pub completed: i64,
+    pub refunded: i64,
+    /// Ended in error, expired, no_market, too_small or too_large
+    pub failed: i64,
+    /// Shares of the transfers that reached a terminal status
+    pub completion_rate: Option<f64>,
+    pub refund_rate: Option<f64>,
+    pub error_rate: Option<f64>,
+    /// Mean time from initiation to `completed`
+    pub avg_completion_secs: Option<f64>,
+    pub time_in_status: Vec<StatusDuration>,
+}
+
+impl TransferMetrics {
+    pub fn empty(anchor_id: Option<String>, window_days: i64) -> Self {
+        Self {
+            anchor_id,
+            window_days,
+            transfers: 0,
+            in_progress: 0,
+            completed: 0,
+            refunded: 0,
+            failed: 0,
+            completion_rate: None,
+            refund_rate: None,
+            error_rate: None,
+            avg_completion_secs: None,
+            time_in_status: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PollSummary {
+    pub polled: usize,
+    pub changed: usize,
+    pub failed: usize,
+}
+
+/// Result of an anchor calling a transfer's callback URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallbackOutcome {
+    /// No transfer has this id and callback token
+    Unknown,
+    /// The body carries no status, or is about another transaction
+    Invalid(String),
+    Applied {
+        changed: bool,
+    },
+}
+
+/// Records transactions initiated through the SEP-24 and SEP-31 proxies and
+/// follows them to a terminal status.
+///
+/// Open transfers are polled only with a token obtained through SEP-10 with
+/// the backend's key. Callers' JWTs are never stored, so transfers started
+/// with one are followed through callbacks or not at all. Anchors given a
+/// callback URL report changes themselves. Every status change is stored, so
+/// per-anchor time in status and completion, refund and error rates can be
+/// computed.
+pub struct TransferTracker {
+    db: Arc<Database>,
+    sep10: Arc<Sep10Client>,
+    client: Client,
+    rng: SystemRandom,
+    config: TransferTrackerConfig,
+}
+
+impl TransferTracker {
+    pub fn new(db: Arc<Database>, sep10: Arc<Sep10Client>, config: TransferTrackerConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            db,
+            sep10,
+            client,
+            rng: SystemRandom::new(),
+            config,
+        }
+    }
+
+    /// Start the polling scheduler
+    pub async fn start_scheduler(self: Arc<Self>) {
+        info!(
+            "Starting transfer tracker (interval: {} seconds)",
+            self.config.interval_secs
+        );
+
+        let mut ticker = interval(Duration::from_secs(self.config.interval_secs));
+
+        loop {
+            ticker.tick().await;
+            match self.run_polls().await {
+                Ok(summary) => {
+                    if summary.polled > 0 {
+                        info!(
+                            "Transfer polling finished: {} polled, {} changed, {} failed",
+                            summary.polled, summary.changed, summary.failed
+                        );
+                    }
+                }
+                Err(e) => error!("Transfer polling failed: {}", e),
+            }
+        }
+    }
+
+    /// Id and callback URL for a transfer about to be initiated
+    pub fn reserve(&self) -> Result<TransferReservation> {
+        let mut secret = [0u8; 32];
+        self.rng
+            .fill(&mut secret)
+            .map_err(|_| anyhow!("Failed to generate callback token"))?;
+        let id = Uuid::new_v4().to_string();
+        let callback_token = hex::encode(secret);
+        let callback_url = self.config.callback_base_url.as_ref().map(|base| {
+            format!(
+                "{}/api/transfers/{}/callback/{}",
+                base.trim_end_matches('/'),
+                id,
+                callback_token
+            )
+        });
+
+        Ok(TransferReservation {
+            id,
+            callback_token,
+            callback_url,
+        })
+    }
+
+    /// Record a transfer the anchor accepted
+    pub async fn record(
+        &self,
+        reservation: &TransferReservation,
+        transfer: NewTransfer,
+    ) -> Result<AnchorTransfer> {
+        let transfer_server = transfer
+            .transfer_server
+            .trim()
+            .trim_end_matches('/')
+            .to_string();
+        let anchor_id = self
+            .find_anchor(
+                transfer.sep,
+                &transfer_server,
+                transfer.home_domain.as_deref(),
+            )
+            .await?;
+        let now = Utc::now();
+        let terminal = is_terminal(&transfer.status);
+
+        let mut tx = self.db.pool().begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_transfers (
+                id, anchor_id, sep, kind, transfer_server, anchor_transaction_id,
+                owner, home_domain, caller_token, callback_token, asset_code, amount,
+                status, terminal, created_at, updated_at, completed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $15, $16)
+            "#,
+        )
+        .bind(&reservation.id)
+        .bind(&anchor_id)
+        .bind(transfer.sep.as_str())
+        .bind(&transfer.kind)
+        .bind(&transfer_server)
+        .bind(&transfer.anchor_transaction_id)
+        .bind(&transfer.owner)
+        .bind(&transfer.home_domain)
+        .bind(transfer.caller_token)
+        .bind(&reservation.callback_token)
+        .bind(&transfer.asset_code)
+        .bind(&transfer.amount)
+        .bind(&transfer.status)
+        .bind(terminal)
+        .bind(now)
+        .bind(terminal.then_some(now))
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_transfer_status_changes (transfer_id, from_status, to_status, source, changed_at)
+            VALUES ($1, NULL, $2, 'initiated', $3)
+            "#,
+        )
+        .bind(&reservation.id)
+        .bind(&transfer.status)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        self.get_transfer(&reservation.id)
+            .await?
+            .ok_or_else(|| anyhow!("Transfer {} vanished after insert", reservation.id))
+    }
+
+    /// Anchor serving `transfer_server`: one with the endpoint configured or
+    /// published in its stellar.toml, else one with the home domain
+    async fn find_anchor(
+        &self,
+        sep: TransferSep,
+        transfer_server: &str,
+        home_domain: Option<&str>,
+    ) -> Result<Option<String>> {
+        let toml_column = match sep {
+            TransferSep::Sep24 => "transfer_server_sep0024",
+            TransferSep::Sep31 => "direct_payment_server",
+        };
+        let query = format!(
+            r#"
+            SELECT anchor_id FROM anchor_sep_endpoints
+            WHERE sep = $1 AND rtrim(url, '/') = $2
+            UNION ALL
+            SELECT anchor_id FROM anchor_stellar_toml
+            WHERE rtrim({}, '/') = $2
+            LIMIT 1
+            "#,
+            toml_column
+        );
+        let by_endpoint: Option<String> = sqlx::query_scalar(&query)
+            .bind(sep.as_str())
+            .bind(transfer_server)
+            .fetch_optional(self.db.pool())
+            .await?;
+        if by_endpoint.is_some() {
+            return Ok(by_endpoint);
+        }
+
+        let Some(home_domain) = home_domain else {
+            return Ok(None);
+        };
+        Ok(sqlx::query_scalar(
+            "SELECT id FROM anchors WHERE lower(home_domain) = lower($1) LIMIT 1",
+        )
+        .bind(home_domain.trim())
+        .fetch_optional(self.db.pool())
+        .await?)
+    }
+
+    /// Ask a SEP-31 anchor to call the transfer's callback URL on changes.
+    /// SEP-24 anchors receive it as `on_change_callback` when initiating.
+    pub async fn register_sep31_callback(
+        &self,
+        transfer_server: &str,
+        anchor_transaction_id: &str,
+        callback_url: &str,
+        bearer: Option<&str>,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/callback",
+            TransferSep::Sep31.transaction_url(transfer_server, anchor_transaction_id)
+        );
+        let mut request = self.client.put(&url).json(&json!({ "url": callback_url }));
+        if let Some(bearer) = bearer {
+            request = request.bearer_auth(bearer);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to register callback at {}", url))?;
+        if !response.status().is_success() {
+            bail!("{} returned {}", url, response.status());
+        }
+        Ok(())
+    }
+
+    pub async fn get_transfer(&self, id: &str) -> Result<Option<AnchorTransfer>> {
+        Ok(
+            sqlx::query_as::<_, AnchorTransfer>("SELECT * FROM anchor_transfers WHERE id = $1")
+                .bind(id)
+                .fetch_optional(self.db.pool())
+                .await?,
+        )
+    }
+
+    /// Transfers `owner` initiated newest first, optionally of one anchor or
+    /// in one status
+    pub async fn list_transfers(
+        &self,
+        owner: &str,
+        anchor_id: Option<&str>,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AnchorTransfer>> {
+        Ok(sqlx::query_as::<_, AnchorTransfer>(
+            r#"
+            SELECT * FROM anchor_transfers
+            WHERE owner = $1 AND ($2 IS NULL OR anchor_id = $2) AND ($3 IS NULL OR status = $3)
+            ORDER BY created_at DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(owner)
+        .bind(anchor_id)
+        .bind(status)
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await?)
+    }
+
+    /// Status changes of a transfer, oldest first
+    pub async fn status_changes(&self, transfer_id: &str) -> Result<Vec<TransferStatusChange>> {
+        Ok(sqlx::query_as::<_, TransferStatusChange>(
+            "SELECT * FROM anchor_transfer_status_changes WHERE transfer_id = $1 ORDER BY changed_at, id",
+        )
+        .bind(transfer_id)
+        .fetch_all(self.db.pool())
+        .await?)
+    }
+
+    /// Move a transfer to `status`, recording the change.
+    ///
+    /// Returns whether the status changed; final transfers are left alone.
+    pub async fn apply_status(
+        &self,
+        transfer: &AnchorTransfer,
+        status: &str,
+        source: &str,
+    ) -> Result<bool> {
+        if transfer.terminal || transfer.status == status {
+            return Ok(false);
+        }
+        let now = Utc::now();
+        let terminal = is_terminal(status);
+
+        let mut tx = self.db.pool().begin().await?;
+        // Guarded by the old status so concurrent polls and callbacks record
+        // each change once
+        let updated = sqlx::query(
+            r#"
+            UPDATE anchor_transfers
+            SET status = $1, terminal = $2, updated_at = $3,
+                completed_at = CASE WHEN $2 THEN $3 ELSE completed_at END
+            WHERE id = $4 AND status = $5 AND terminal = 0
+            "#,
+        )
+        .bind(status)
+        .bind(terminal)
+        .bind(now)
+        .bind(&transfer.id)
+        .bind(&transfer.status)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Ok(false);
+        }
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_transfer_status_changes (transfer_id, from_status, to_status, source, changed_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(&transfer.id)
+        .bind(&transfer.status)
+        .bind(status)
+        .bind(source)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Poll every open transfer younger than `max_age_hours`, except those
+    /// only their caller's token could read
+    pub async fn run_polls(&self) -> Result<PollSummary> {
+        let since = Utc::now() - ChronoDuration::hours(self.config.max_age_hours);
+        let open = sqlx::query_as::<_, AnchorTransfer>(
+            r#"
+            SELECT * FROM anchor_transfers
+            WHERE terminal = 0 AND caller_token = 0 AND created_at >= $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(since)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut summary = PollSummary::default();
+        for transfer in &open {
+            summary.polled += 1;
+            let result = match self.poll(transfer).await {
+                Ok(status) => self.apply_status(transfer, &status, "poll").await,
+                Err(e) => Err(e),
+            };
+            let poll_error = match result {
+                Ok(changed) => {
+                    if changed {
+                        summary.changed += 1;
+                    }
+                    None
+                }
+                Err(e) => {
+                    warn!("Failed to poll transfer {}: {:#}", transfer.id, e);
+                    summary.failed += 1;
+                    Some(format!("{:#}", e))
+                }
+            };
+            sqlx::query(
+                "UPDATE anchor_transfers SET last_polled_at = $1, poll_error = $2 WHERE id = $3",
+            )
+            .bind(Utc::now())
+            .bind(poll_error)
+            .bind(&transfer.id)
+            .execute(self.db.pool())
+            .await?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Current status of a transfer according to its anchor
+    async fn poll(&self, transfer: &AnchorTransfer) -> Result<String> {
+        let url = transfer
+            .sep()?
+            .transaction_url(&transfer.transfer_server, &transfer.anchor_transaction_id);
+        let bearer = match &transfer.home_domain {
+            Some(domain) => Some(
+                self.sep10
+                    .token(domain, None)
+                    .await
+                    .context("SEP-10 authentication failed")?
+                    .token,
+            ),
+            None => None,
+        };
+
+        let mut request = self.client.get(&url);
+        if let Some(bearer) = bearer {
+            request = request.bearer_auth(bearer);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?;
+        if !response.status().is_success() {
+            bail!("{} returned {}", url, response.status());
+        }
+        let body: Value = response
+            .json()
+            .await
+            .with_context(|| format!("Invalid response from {}", url))?;
+
+        transaction_status(&body, &transfer.anchor_transaction_id).map_err(|e| anyhow!(e))
+    }
+
+    /// Apply a status change an anchor reported to a transfer's callback URL
+    pub async fn handle_callback(
+        &self,
+        id: &str,
+        callback_token: &str,
+        body: &Value,
+    ) -> Result<CallbackOutcome> {
+        let transfer = match self.get_transfer(id).await? {
+            Some(transfer) if tokens_match(&transfer.callback_token, callback_token) => transfer,
+            _ => return Ok(CallbackOutcome::Unknown),
+        };
+        let status = match transaction_status(body, &transfer.anchor_transaction_id) {
+            Ok(status) => status,
+            Err(e) => return Ok(CallbackOutcome::Invalid(e)),
+        };
+        let changed = self.apply_status(&transfer, &status, "callback").await?;
+
+        Ok(CallbackOutcome::Applied { changed })
+    }
+
+    /// Metrics of the transfers initiated in the last `days`, per anchor.
+    /// With `anchor_id` only that anchor's transfers are considered.
+    pub async fn metrics(
+        &self,
+        anchor_id: Option<&str>,
+        days: i64,
+    ) -> Result<Vec<TransferMetrics>> {
+        let since = Utc::now() - ChronoDuration::days(days);
+        let transfers = sqlx::query_as::<_, AnchorTransfer>(
+            "SELECT * FROM anchor_transfers WHERE created_at >= $1 AND ($2 IS NULL OR anchor_id = $2)",
+        )
+        .bind(since)
+        .bind(anchor_id)
+        .fetch_all(self.db.pool())
+        .await?;
+        let changes = sqlx::query_as::<_, TransferStatusChange>(
+            r#"
+            SELECT c.* FROM anchor_transfer_status_changes c
+            JOIN anchor_transfers t ON t.id = c.transfer_id
+            WHERE t.created_at >= $1 AND ($2 IS NULL OR t.anchor_id = $2)
+            ORDER BY c.transfer_id, c.changed_at, c.id
+            "#,
+        )
+        .bind(since)
+        .bind(anchor_id)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(compute_metrics(&transfers, &changes, days))
+    }
+}
+
+/// Compare callback tokens without exiting at the first differing byte
+fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Status in an anchor's transaction body, `{"transaction": {...}}` or the
+/// bare transaction, which must be about `anchor_transaction_id`
+fn transaction_status(body: &Value, anchor_transaction_id: &str) -> Result<String, String> {
+    let transaction = body.get("transaction").unwrap_or(body);
+    if let Some(id) = transaction.get("id").and_then(Value::as_str) {
+        if id != anchor_transaction_id {
+            return Err(format!(
+                "transaction {} is not {}",
+                id, anchor_transaction_id
+            ));
+        }
+    }
+    transaction
+        .get("status")
+        .and_then(Value::as_str)
+        .filter(|status| !status.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| "missing transaction `status`".to_string())
+}
+
+fn ratio(part: i64, whole: i64) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+/// Group transfers by anchor and derive their rates and time in status.
+/// `changes` must be ordered by transfer and time.
+fn compute_metrics(
+    transfers: &[AnchorTransfer],
+    changes: &[TransferStatusChange],
+    window_days: i64,
+) -> Vec<TransferMetrics> {
+    let mut anchor_of = BTreeMap::new();
+    let mut groups: BTreeMap<Option<String>, TransferMetrics> = BTreeMap::new();
+    let mut completion_secs: BTreeMap<Option<String>, Vec<f64>> = BTreeMap::new();
+
+    for transfer in transfers {
+        anchor_of.insert(transfer.id.as_str(), transfer.anchor_id.clone());
+        let metrics = groups
+            .entry(transfer.anchor_id.clone())
+            .or_insert_with(|| TransferMetrics::empty(transfer.anchor_id.clone(), window_days));
+        metrics.transfers += 1;
+        match transfer.status.as_str() {
+            _ if !transfer.terminal => metrics.in_progress += 1,
+            "completed" => {
+                metrics.completed += 1;
+                if let Some(completed_at) = transfer.completed_at {
+                    completion_secs
+                        .entry(transfer.anchor_id.clone())
+                        .or_default()
+                        .push(
+                            (completed_at - transfer.created_at).num_milliseconds() as f64 / 1000.0,
+                        );
+                }
+            }
+            "refunded" => metrics.refunded += 1,
+            status if FAILED_STATUSES.contains(&status) => metrics.failed += 1,
+            _ => {}
+        }
+    }
+
+    // Time between entering a status and the next change
+    let mut durations: BTreeMap<Option<String>, BTreeMap<String, Vec<f64>>> = BTreeMap::new();
+    for pair in changes.windows(2) {
+        let (entered, left) = (&pair[0], &pair[1]);
+        if entered.transfer_id != left.transfer_id {
+            continue;
+        }
+        let Some(anchor_id) = anchor_of.get(entered.transfer_id.as_str()) else {
+            continue;
+        };
+        durations
+            .entry(anchor_id.clone())
+            .or_default()
+            .entry(entered.to_status.clone())
+            .or_default()
+            .push((left.changed_at - entered.changed_at).num_milliseconds() as f64 / 1000.0);
+    }
+
+    for (anchor_id, metrics) in groups.iter_mut() {
+        let finished = metrics.transfers - metrics.in_progress;
+        metrics.completion_rate = ratio(metrics.completed, finished);
+        metrics.refund_rate = ratio(metrics.refunded, finished);
+        metrics.error_rate = ratio(metrics.failed, finished);
+        metrics.avg_completion_secs = completion_secs
+            .get(anchor_id)
+            .map(|secs| secs.iter().sum::<f64>() / secs.len() as f64);
+        metrics.time_in_status = durations
+            .remove(anchor_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(status, secs)| StatusDuration {
+                status,
+                exits: secs.len() as i64,
+                avg_secs: secs.iter().sum::<f64>() / secs.len() as f64,
+                max_secs: secs.iter().cloned().fold(0.0, f64::max),
+            })
+            .collect();
+    }
+
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    fn transfer(
+        id: &str,
+        anchor_id: Option<&str>,
+        status: &str,
+        completed_secs: Option<i64>,
+    ) -> AnchorTransfer {
+        AnchorTransfer {
+            id: id.to_string(),
+            anchor_id: anchor_id.map(str::to_string),
+            sep: "sep24".to_string(),
+            kind: "deposit".to_string(),
+            transfer_server: "https://anchor.example/sep24".to_string(),
+            anchor_transaction_id: format!("anchor-{}", id),
+            owner: None,
+            home_domain: None,
+            caller_token: false,
+            callback_token: String::new(),
+            asset_code: None,
+            amount: None,
+            status: status.to_string(),
+            terminal: is_terminal(status),
+            poll_error: None,
+            last_polled_at: None,
+            created_at: at(0),
+            updated_at: at(0),
+            completed_at: completed_secs.map(at),
+        }
+    }
+
+    fn change(transfer_id: &str, to_status: &str, secs: i64) -> TransferStatusChange {
+        TransferStatusChange {
+            id: 0,
+            transfer_id: transfer_id.to_string(),
+            from_status: None,
+            to_status: to_status.to_string(),
+            source: "poll".to_string(),
+            changed_at: at(secs),
+        }
+    }
+
+    #[test]
+    fn test_compute_metrics() {
+        let transfers = vec![
+            transfer("a", Some("anchor"), "completed", Some(300)),
+            transfer("b", Some("anchor"), "refunded", Some(900)),
+            transfer("c", Some("anchor"), "error", Some(60)),
+            transfer("d", Some("anchor"), "pending_anchor", None),
+            transfer("e", None, "completed", Some(100)),
+        ];
+        let changes = vec![
+            change("a", "incomplete", 0),
+            change("a", "pending_anchor", 100),
+            change("a", "completed", 300),
+            change("b", "incomplete", 0),
+            change("b", "pending_anchor", 300),
+            change("b", "refunded", 900),
+        ];
+
+        let metrics = compute_metrics(&transfers, &changes, 30);
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0].anchor_id, None);
+        assert_eq!(metrics[0].completion_rate, Some(1.0));
+
+        let anchor = &metrics[1];
+        assert_eq!(anchor.transfers, 4);
+        assert_eq!(anchor.in_progress, 1);
+        assert_eq!(
+            (anchor.completed, anchor.refunded, anchor.failed),
+            (1, 1, 1)
+        );
+        assert_eq!(anchor.completion_rate, Some(1.0 / 3.0));
+        assert_eq!(anchor.refund_rate, Some(1.0 / 3.0));
+        assert_eq!(anchor.error_rate, Some(1.0 / 3.0));
+        assert_eq!(anchor.avg_completion_secs, Some(300.0));
+        assert_eq!(
+            anchor.time_in_status,
+            vec![
+                StatusDuration {
+                    status: "incomplete".to_string(),
+                    exits: 2,
+                    avg_secs: 200.0,
+                    max_secs: 300.0,
+                },
+                StatusDuration {
+                    status: "pending_anchor".to_string(),
+                    exits: 2,
+                    avg_secs: 400.0,
+                    max_secs: 600.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transaction_status() {
+        let body = json!({ "transaction": { "id": "1", "status": "pending_user_transfer_start" } });
+        assert_eq!(
+            transaction_status(&body, "1").unwrap(),
+            "pending_user_transfer_start"
+        );
+        assert!(transaction_status(&body, "2").is_err());
+        assert_eq!(
+            transaction_status(&json!({ "status": "completed" }), "1").unwrap(),
+            "completed"
+        );
+        assert!(transaction_status(&json!({ "transaction": {} }), "1").is_err());
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abcd", "abcd"));
+        assert!(!tokens_match("abcd", "abce"));
+        assert!(!tokens_match("abcd", "abc"));
+    }
+}
@@ -0,0 +1,347 @@
+//! Differences between the metrics of two snapshots
+//!
+//! Anchors are matched by ID. Corridors are matched by corridor key, since a
+//! corridor's metrics ID changes with every day of metrics.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::schema::{AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics};
+
+/// Minimum size of a numeric change for it to be reported
+///
+/// A change is reported when it reaches every threshold that is set. Changes
+/// of text fields and from or to null are always reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct DiffThresholds {
+    /// Absolute change
+    pub min_abs_change: Option<f64>,
+    /// Change relative to the earlier value, in percent
+    pub min_pct_change: Option<f64>,
+}
+
+impl DiffThresholds {
+    fn is_material(&self, change: &FieldChange) -> bool {
+        let Some(delta) = change.delta else {
+            return true;
+        };
+        self.min_abs_change.is_none_or(|min| delta.abs() >= min)
+            && self.min_pct_change.is_none_or(|min| {
+                // Any change from zero is material relative to it
+                change.pct_change.is_none_or(|pct| pct.abs() >= min)
+            })
+    }
+}
+
+/// Change of one field between the two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Value,
+    pub to: Value,
+    /// `to - from` for numeric fields
+    pub delta: Option<f64>,
+    /// `delta` relative to `from`, in percent; absent when `from` is zero
+    pub pct_change: Option<f64>,
+}
+
+/// An anchor or corridor present in only one of the snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityRef {
+    pub id: String,
+    /// Anchor name or corridor key
+    pub label: String,
+}
+
+/// An anchor or corridor whose metrics changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityChanges {
+    pub id: String,
+    /// Anchor name or corridor key
+    pub label: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Differences of the anchors or the corridors of two snapshots
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricsDiff {
+    pub added: Vec<EntityRef>,
+    pub removed: Vec<EntityRef>,
+    pub changed: Vec<EntityChanges>,
+    /// Entities in both snapshots without reported changes
+    pub unchanged: usize,
+}
+
+/// Differences between two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub from_epoch: u64,
+    pub to_epoch: u64,
+    pub anchors: MetricsDiff,
+    pub corridors: MetricsDiff,
+}
+
+/// Compare the metrics of two snapshots
+pub fn diff_snapshots(
+    from: &AnalyticsSnapshot,
+    to: &AnalyticsSnapshot,
+    thresholds: &DiffThresholds,
+) -> SnapshotDiff {
+    SnapshotDiff {
+        from_epoch: from.epoch,
+        to_epoch: to.epoch,
+        anchors: diff_metrics(
+            &from.anchor_metrics,
+            &to.anchor_metrics,
+            |a: &SnapshotAnchorMetrics| (a.id.to_string(), a.name.clone()),
+            &["id"],
+            thresholds,
+        ),
+        corridors: diff_metrics(
+            &from.corridor_metrics,
+            &to.corridor_metrics,
+            |c: &SnapshotCorridorMetrics| (c.corridor_key.clone(), c.corridor_key.clone()),
+            &["id", "corridor_key"],
+            thresholds,
+        ),
+    }
+}
+
+/// Diff entities matched by the key returned by `identify`, along with a label
+fn diff_metrics<T: Serialize>(
+    from: &[T],
+    to: &[T],
+    identify: impl Fn(&T) -> (String, String),
+    ignored_fields: &[&str],
+    thresholds: &DiffThresholds,
+) -> MetricsDiff {
+    let index = |entities: &[T]| -> BTreeMap<String, (String, Value)> {
+        entities
+            .iter()
+            .map(|entity| {
+                let (key, label) = identify(entity);
+                let value = serde_json::to_value(entity).unwrap_or(Value::Null);
+                (key, (label, value))
+            })
+            .collect()
+    };
+    let from = index(from);
+    let to = index(to);
+
+    let mut diff = MetricsDiff::default();
+    for (key, (label, _)) in &to {
+        if !from.contains_key(key) {
+            diff.added.push(EntityRef {
+                id: key.clone(),
+                label: label.clone(),
+            });
+        }
+    }
+    for (key, (label, before)) in &from {
+        let Some((_, after)) = to.get(key) else {
+            diff.removed.push(EntityRef {
+                id: key.clone(),
+                label: label.clone(),
+            });
+            continue;
+        };
+
+        let changes: Vec<FieldChange> = field_changes(before, after, ignored_fields)
+            .into_iter()
+            .filter(|change| thresholds.is_material(change))
+            .collect();
+        if changes.is_empty() {
+            diff.unchanged += 1;
+        } else {
+            diff.changed.push(EntityChanges {
+                id: key.clone(),
+                label: label.clone(),
+                changes,
+            });
+        }
+    }
+
+    diff
+}
+
+/// Changed fields of two serialized metrics entries, by field name
+fn field_changes(before: &Value, after: &Value, ignored_fields: &[&str]) -> Vec<FieldChange> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+
+    before
+        .iter()
+        .filter(|(field, _)| !ignored_fields.contains(&field.as_str()))
+        .filter_map(|(field, from)| {
+            let to = after.get(field).unwrap_or(&Value::Null);
+            if from == to {
+                return None;
+            }
+
+            let delta = from.as_f64().zip(to.as_f64()).map(|(a, b)| b - a);
+            let pct_change = from
+                .as_f64()
+                .zip(delta)
+                .filter(|(from, _)| *from != 0.0)
+                .map(|(from, delta)| delta / from.abs() * 100.0);
+            Some(FieldChange {
+                field: field.clone(),
+                from: from.clone(),
+                to: to.clone(),
+                delta,
+                pct_change,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn anchor(id: u128, name: &str, total: i64, status: &str) -> SnapshotAnchorMetrics {
+        SnapshotAnchorMetrics {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            stellar_account: format!("G{}", id),
+            success_rate: 95.0,
+            failure_rate: 5.0,
+            reliability_score: 0.95,
+            total_transactions: total,
+            successful_transactions: total,
+            failed_transactions: 0,
+            avg_settlement_time_ms: None,
+            volume_usd: Some(1000.0),
+            status: status.to_string(),
+        }
+    }
+
+    fn corridor(id: u128, key: &str, volume: f64) -> SnapshotCorridorMetrics {
+        SnapshotCorridorMetrics {
+            id: Uuid::from_u128(id),
+            corridor_key: key.to_string(),
+            asset_a_code: "USDC".to_string(),
+            asset_a_issuer: "A".to_string(),
+            asset_b_code: "EURC".to_string(),
+            asset_b_issuer: "B".to_string(),
+            total_transactions: 10,
+            successful_transactions: 10,
+            failed_transactions: 0,
+            success_rate: 100.0,
+            volume_usd: volume,
+            avg_settlement_latency_ms: Some(200),
+            liquidity_depth_usd: 5000.0,
+        }
+    }
+
+    fn snapshots() -> (AnalyticsSnapshot, AnalyticsSnapshot) {
+        let mut from = AnalyticsSnapshot::new(1, Utc::now());
+        from.add_anchor_metrics(anchor(1, "Kept", 100, "green"));
+        from.add_anchor_metrics(anchor(2, "Changed", 100, "green"));
+        from.add_anchor_metrics(anchor(3, "Removed", 100, "green"));
+        from.add_corridor_metrics(corridor(10, "USDC->EURC", 1000.0));
+        from.add_corridor_metrics(corridor(11, "USDC->GBPC", 1000.0));
+
+        let mut to = AnalyticsSnapshot::new(2, Utc::now());
+        to.add_anchor_metrics(anchor(1, "Kept", 101, "green"));
+        to.add_anchor_metrics(anchor(2, "Changed", 150, "red"));
+        to.add_anchor_metrics(anchor(4, "Added", 100, "green"));
+        // Same corridor, metrics of a later day
+        to.add_corridor_metrics(corridor(20, "USDC->EURC", 1500.0));
+        to.add_corridor_metrics(corridor(21, "USDC->GBPC", 1000.0));
+        (from, to)
+    }
+
+    #[test]
+    fn test_added_removed_and_changed_entities() {
+        let (from, to) = snapshots();
+        let diff = diff_snapshots(&from, &to, &DiffThresholds::default());
+
+        assert_eq!((diff.from_epoch, diff.to_epoch), (1, 2));
+        assert_eq!(
+            diff.anchors.added,
+            vec![EntityRef {
+                id: Uuid::from_u128(4).to_string(),
+                label: "Added".to_string()
+            }]
+        );
+        assert_eq!(diff.anchors.removed[0].label, "Removed");
+        assert_eq!(diff.anchors.unchanged, 0);
+        assert_eq!(diff.anchors.changed.len(), 2);
+
+        let changed = &diff.anchors.changed[1];
+        assert_eq!(changed.label, "Changed");
+        let fields: Vec<&str> = changed.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["status", "successful_transactions", "total_transactions"]
+        );
+        assert_eq!(changed.changes[0].from, "green");
+        assert_eq!(changed.changes[0].delta, None);
+        assert_eq!(changed.changes[2].delta, Some(50.0));
+        assert_eq!(changed.changes[2].pct_change, Some(50.0));
+
+        // Corridors are matched by key even though their IDs changed
+        assert!(diff.corridors.added.is_empty());
+        assert!(diff.corridors.removed.is_empty());
+        assert_eq!(diff.corridors.unchanged, 1);
+        assert_eq!(diff.corridors.changed[0].id, "USDC->EURC");
+        assert_eq!(diff.corridors.changed[0].changes[0].field, "volume_usd");
+    }
+
+    #[test]
+    fn test_thresholds_hide_small_numeric_changes() {
+        let (from, to) = snapshots();
+        let thresholds = DiffThresholds {
+            min_abs_change: Some(10.0),
+            min_pct_change: Some(5.0),
+        };
+        let diff = diff_snapshots(&from, &to, &thresholds);
+
+        // 100 -> 101 is below both thresholds
+        assert_eq!(diff.anchors.unchanged, 1);
+        assert_eq!(diff.anchors.changed.len(), 1);
+        assert_eq!(diff.anchors.changed[0].changes.len(), 3);
+
+        let thresholds = DiffThresholds {
+            min_abs_change: None,
+            min_pct_change: Some(60.0),
+        };
+        let diff = diff_snapshots(&from, &to, &thresholds);
+        // Only the status change is left
+        let changes = &diff.anchors.changed[0].changes;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "status");
+        assert_eq!(diff.corridors.unchanged, 2);
+    }
+
+    #[test]
+    fn test_changes_from_null_and_zero_are_material() {
+        let thresholds = DiffThresholds {
+            min_abs_change: None,
+            min_pct_change: Some(1000.0),
+        };
+        let mut before = anchor(1, "A", 0, "green");
+        let mut after = before.clone();
+        after.total_transactions = 1;
+        after.avg_settlement_time_ms = Some(100);
+        // 5% is not material
+        before.volume_usd = Some(950.0);
+
+        let mut from = AnalyticsSnapshot::new(1, Utc::now());
+        from.add_anchor_metrics(before);
+        let mut to = AnalyticsSnapshot::new(2, Utc::now());
+        to.add_anchor_metrics(after);
+        let diff = diff_snapshots(&from, &to, &thresholds);
+
+        let changes = &diff.anchors.changed[0].changes;
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["avg_settlement_time_ms", "total_transactions"]);
+        assert_eq!(changes[0].from, Value::Null);
+        assert_eq!(changes[1].pct_change, None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Merkle trees over snapshot metrics
+//!
+//! Leaves and inner nodes are hashed with different prefixes (as in RFC 6962),
+//! so a leaf can never be passed off as an inner node. A node without a
+//! sibling is carried up to the next level unchanged.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// SHA-256 digest of a leaf or node
+pub type Hash = [u8; 32];
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash of a leaf's data
+pub fn leaf_hash(data: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Hash of an inner node
+pub fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Root of a tree without leaves: the SHA-256 of empty input
+pub fn empty_root() -> Hash {
+    Sha256::digest([]).into()
+}
+
+/// Side of the running hash on which a proof node is combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SiblingPosition {
+    Left,
+    Right,
+}
+
+/// Sibling on the path from a leaf to the root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofNode {
+    pub position: SiblingPosition,
+    #[serde(with = "hex_hash")]
+    pub hash: Hash,
+}
+
+/// Binary Merkle tree, kept level by level from the leaves up
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    /// Build a tree from leaf hashes, in order
+    pub fn new(leaves: Vec<Hash>) -> Self {
+        let mut levels = vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks(2) yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { levels }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn root(&self) -> Hash {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => *root,
+            None => empty_root(),
+        }
+    }
+
+    /// Siblings from the leaf at `index` up to the root; `None` if there is
+    /// no such leaf
+    pub fn proof(&self, index: usize) -> Option<Vec<ProofNode>> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+
+        let mut proof = Vec::new();
+        let mut index = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if let Some(hash) = level.get(sibling) {
+                let position = if sibling < index {
+                    SiblingPosition::Left
+                } else {
+                    SiblingPosition::Right
+                };
+                proof.push(ProofNode {
+                    position,
+                    hash: *hash,
+                });
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+/// Root implied by a leaf and its proof
+pub fn root_from_proof(leaf: Hash, proof: &[ProofNode]) -> Hash {
+    proof.iter().fold(leaf, |hash, node| match node.position {
+        SiblingPosition::Left => node_hash(&node.hash, &hash),
+        SiblingPosition::Right => node_hash(&hash, &node.hash),
+    })
+}
+
+/// Check that `leaf` is included in the tree with `root`
+pub fn verify_proof(leaf: Hash, proof: &[ProofNode], root: Hash) -> bool {
+    root_from_proof(leaf, proof) == root
+}
+
+/// Serialize hashes as lowercase hex strings
+pub mod hex_hash {
+    use super::Hash;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(hash: &Hash, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Hash, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        parse(&s).map_err(D::Error::custom)
+    }
+
+    /// Parse a 32-byte hash from hex
+    pub fn parse(s: &str) -> Result<Hash, String> {
+        let bytes = hex::decode(s.trim()).map_err(|e| format!("invalid hash {}: {}", s, e))?;
+        bytes
+            .try_into()
+            .map_err(|_| format!("hash must be 32 bytes: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: usize) -> Vec<Hash> {
+        (0..count)
+            .map(|i| leaf_hash(format!("leaf-{}", i).as_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_leaf_proves_against_the_root() {
+        for count in 1..=9 {
+            let leaves = leaves(count);
+            let tree = MerkleTree::new(leaves.clone());
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(
+                    verify_proof(*leaf, &proof, tree.root()),
+                    "{}/{}",
+                    index,
+                    count
+                );
+            }
+            assert!(tree.proof(count).is_none());
+        }
+    }
+
+    #[test]
+    fn test_small_trees() {
+        assert_eq!(MerkleTree::new(vec![]).root(), empty_root());
+        let leaves = leaves(3);
+        assert_eq!(MerkleTree::new(vec![leaves[0]]).root(), leaves[0]);
+        // The third leaf has no sibling and is carried up
+        assert_eq!(
+            MerkleTree::new(leaves.clone()).root(),
+            node_hash(&node_hash(&leaves[0], &leaves[1]), &leaves[2])
+        );
+    }
+
+    #[test]
+    fn test_tampered_proofs_fail() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(leaves.clone());
+        let proof = tree.proof(1).unwrap();
+
+        assert!(!verify_proof(leaves[2], &proof, tree.root()));
+        let mut swapped = proof.clone();
+        swapped[0].position = SiblingPosition::Right;
+        assert!(!verify_proof(leaves[1], &swapped, tree.root()));
+        // Data shaped like an inner node does not hash to that node
+        let children = [leaves[0], leaves[1]].concat();
+        assert_ne!(leaf_hash(&children), node_hash(&leaves[0], &leaves[1]));
+    }
+
+    #[test]
+    fn test_proof_node_serialization() {
+        let node = ProofNode {
+            position: SiblingPosition::Left,
+            hash: [0xab; 32],
+        };
+        let json = serde_json::to_value(&node).unwrap();
+        assert_eq!(json["position"], "left");
+        assert_eq!(json["hash"], "ab".repeat(32));
+        assert_eq!(serde_json::from_value::<ProofNode>(json).unwrap(), node);
+        assert!(hex_hash::parse("abcd").is_err());
+    }
+}
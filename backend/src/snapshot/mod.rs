@@ -1,7 +1,11 @@
+pub mod diff;
 pub mod generator;
+pub mod merkle;
 pub mod schema;
 
+pub use diff::{diff_snapshots, DiffThresholds, SnapshotDiff};
 pub use generator::SnapshotGenerator;
+pub use merkle::MerkleTree;
 pub use schema::{
     AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
 };
@@ -1,6 +1,12 @@
 //! HTTP handlers for snapshot generation and submission
 
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -8,7 +14,11 @@ use tracing::{error, info};
 
 use crate::database::Database;
 use crate::services::contract::ContractService;
-use crate::services::snapshot::SnapshotService;
+use crate::services::snapshot::{
+    InclusionProof, SnapshotLeafKind, SnapshotService, SnapshotSubmission,
+    SnapshotVerificationReport, SubmissionStatus,
+};
+use crate::snapshot::diff::{DiffThresholds, SnapshotDiff};
 
 /// Response for snapshot generation
 #[derive(Debug, Serialize)]
@@ -16,11 +26,18 @@ pub struct SnapshotResponse {
     pub epoch: u64,
     pub timestamp: String,
     pub hash: String,
+    /// Root of the Merkle tree over the metrics; this is what is submitted
+    pub merkle_root: String,
     pub schema_version: u32,
     pub anchor_count: usize,
     pub corridor_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub submission: Option<SubmissionInfo>,
+    /// Where the epoch's submission stands; a failed one is retried by the
+    /// scheduler
+    pub submission_status: SubmissionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_error: Option<String>,
 }
 
 /// Submission information
@@ -59,18 +76,43 @@ pub async fn generate_snapshot(
         request.epoch, request.submit_to_contract
     );
 
-    // Use the comprehensive snapshot service to handle all requirements
-    match state
+    let submission = state
         .snapshot_service
-        .generate_and_submit_snapshot(request.epoch)
+        .submission(request.epoch)
         .await
-    {
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))?;
+    if let Some(submission) = submission.filter(|s| {
+        matches!(
+            s.status,
+            SubmissionStatus::Submitted | SubmissionStatus::Confirmed
+        )
+    }) {
+        return Err(SnapshotError::Conflict(format!(
+            "Snapshot of epoch {} was already {}",
+            request.epoch, submission.status
+        )));
+    }
+
+    // Use the comprehensive snapshot service to handle all requirements
+    let generated = if request.submit_to_contract {
+        state
+            .snapshot_service
+            .generate_and_submit_snapshot(request.epoch)
+            .await
+    } else {
+        state
+            .snapshot_service
+            .generate_snapshot(request.epoch)
+            .await
+    };
+    match generated {
         Ok(result) => {
             let hash = result.hash.clone();
             let response = SnapshotResponse {
                 epoch: result.epoch,
                 timestamp: result.timestamp.to_rfc3339(),
                 hash: result.hash,
+                merkle_root: result.merkle_root,
                 schema_version: 1, // From SCHEMA_VERSION
                 anchor_count: result.anchor_count,
                 corridor_count: result.corridor_count,
@@ -79,6 +121,8 @@ pub async fn generate_snapshot(
                     ledger: sr.ledger,
                     contract_timestamp: sr.timestamp,
                 }),
+                submission_status: result.submission_status,
+                submission_error: result.submission_error,
             };
 
             info!(
@@ -102,6 +146,218 @@ pub async fn generate_snapshot(
     }
 }
 
+/// Query for an inclusion proof
+#[derive(Debug, Deserialize)]
+pub struct InclusionProofQuery {
+    /// `anchor` or `corridor`
+    pub kind: String,
+    /// Anchor ID or corridor metrics ID
+    pub id: String,
+}
+
+/// Get one anchor's or corridor's metrics from a snapshot with the proof of
+/// their inclusion under the snapshot's Merkle root
+///
+/// GET /api/snapshots/:epoch/proof?kind=anchor&id=...
+pub async fn get_inclusion_proof(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+    Query(query): Query<InclusionProofQuery>,
+) -> Result<Json<InclusionProof>, SnapshotError> {
+    let kind: SnapshotLeafKind = query.kind.parse().map_err(SnapshotError::InvalidRequest)?;
+
+    state
+        .snapshot_service
+        .inclusion_proof(epoch, kind, &query.id)
+        .await
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| {
+            SnapshotError::NotFound(format!(
+                "No {} {} in the snapshot of epoch {}",
+                kind, query.id, epoch
+            ))
+        })
+}
+
+/// Download the canonical JSON of an epoch's latest snapshot
+///
+/// The body is byte for byte what was hashed, so it can be checked with the
+/// `verify_snapshot` binary.
+///
+/// GET /api/snapshots/:epoch
+pub async fn get_snapshot(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+) -> Result<Response, SnapshotError> {
+    let snapshot = state
+        .snapshot_service
+        .stored_snapshot(epoch)
+        .await
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))?
+        .ok_or_else(|| SnapshotError::NotFound(format!("No snapshot for epoch {}", epoch)))?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/json")],
+        snapshot.canonical_json,
+    )
+        .into_response())
+}
+
+/// Recompute an epoch's latest snapshot and compare it with the root held by
+/// the contract
+///
+/// GET /api/snapshots/:epoch/verify
+pub async fn verify_snapshot(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+) -> Result<Json<SnapshotVerificationReport>, SnapshotError> {
+    if state.contract_service.is_none() {
+        return Err(SnapshotError::ConfigError(
+            "Contract service not configured".to_string(),
+        ));
+    }
+
+    state
+        .snapshot_service
+        .verify_snapshot(epoch)
+        .await
+        .map_err(|e| SnapshotError::ConnectionError(e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| SnapshotError::NotFound(format!("No snapshot for epoch {}", epoch)))
+}
+
+/// Query for a snapshot diff
+#[derive(Debug, Deserialize)]
+pub struct SnapshotDiffQuery {
+    pub from: u64,
+    pub to: u64,
+    /// Only report numeric changes of at least this size
+    pub min_abs_change: Option<f64>,
+    /// Only report numeric changes of at least this many percent
+    pub min_pct_change: Option<f64>,
+}
+
+/// Compare the latest snapshots of two epochs: anchors and corridors added or
+/// removed, and the changed fields of the others
+///
+/// GET /api/snapshots/diff?from=1&to=2&min_pct_change=5
+pub async fn diff_snapshots(
+    State(state): State<SnapshotAppState>,
+    Query(query): Query<SnapshotDiffQuery>,
+) -> Result<Json<SnapshotDiff>, SnapshotError> {
+    let thresholds = DiffThresholds {
+        min_abs_change: query.min_abs_change,
+        min_pct_change: query.min_pct_change,
+    };
+    for (name, value) in [
+        ("min_abs_change", thresholds.min_abs_change),
+        ("min_pct_change", thresholds.min_pct_change),
+    ] {
+        if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+            return Err(SnapshotError::InvalidRequest(format!(
+                "{} must be a non-negative number",
+                name
+            )));
+        }
+    }
+
+    let mut snapshots = Vec::with_capacity(2);
+    for epoch in [query.from, query.to] {
+        let snapshot = state
+            .snapshot_service
+            .stored_snapshot(epoch)
+            .await
+            .map_err(|e| SnapshotError::GenerationError(e.to_string()))?
+            .ok_or_else(|| SnapshotError::NotFound(format!("No snapshot for epoch {}", epoch)))?;
+        snapshots.push(snapshot.canonical_json);
+    }
+
+    SnapshotService::diff_canonical(&snapshots[0], &snapshots[1], &thresholds)
+        .map(Json)
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))
+}
+
+/// Query for snapshot submissions
+#[derive(Debug, Deserialize)]
+pub struct SubmissionsQuery {
+    /// generated, submitted, confirmed or failed
+    pub status: Option<String>,
+    #[serde(default = "default_submissions_limit")]
+    pub limit: i64,
+}
+
+fn default_submissions_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmissionsResponse {
+    /// Submissions newest epoch first
+    pub submissions: Vec<SnapshotSubmission>,
+}
+
+/// List the on-chain submission state of snapshot epochs
+///
+/// GET /api/snapshots/submissions?status=failed&limit=50
+pub async fn list_submissions(
+    State(state): State<SnapshotAppState>,
+    Query(query): Query<SubmissionsQuery>,
+) -> Result<Json<SubmissionsResponse>, SnapshotError> {
+    let status = query
+        .status
+        .as_deref()
+        .map(str::parse::<SubmissionStatus>)
+        .transpose()
+        .map_err(SnapshotError::InvalidRequest)?;
+
+    let submissions = state
+        .snapshot_service
+        .list_submissions(status, query.limit.clamp(1, 500))
+        .await
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))?;
+
+    Ok(Json(SubmissionsResponse { submissions }))
+}
+
+/// Get the on-chain submission state of an epoch
+///
+/// GET /api/snapshots/submissions/:epoch
+pub async fn get_submission(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+) -> Result<Json<SnapshotSubmission>, SnapshotError> {
+    state
+        .snapshot_service
+        .submission(epoch)
+        .await
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| SnapshotError::NotFound(format!("No snapshot for epoch {}", epoch)))
+}
+
+/// Submit an epoch again now, with its full number of attempts
+///
+/// POST /api/snapshots/submissions/:epoch/retry
+pub async fn retry_submission(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+) -> Result<Json<SnapshotSubmission>, SnapshotError> {
+    if state.contract_service.is_none() {
+        return Err(SnapshotError::ConfigError(
+            "Contract service not configured".to_string(),
+        ));
+    }
+
+    state
+        .snapshot_service
+        .retry_submission(epoch)
+        .await
+        .map_err(|e| SnapshotError::SubmissionError(e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| SnapshotError::NotFound(format!("No snapshot for epoch {}", epoch)))
+}
+
 /// Health check for contract service
 ///
 /// GET /api/snapshots/contract/health
@@ -139,6 +395,9 @@ pub enum SnapshotError {
     SubmissionError(String),
     ConnectionError(String),
     ConfigError(String),
+    NotFound(String),
+    InvalidRequest(String),
+    Conflict(String),
 }
 
 impl IntoResponse for SnapshotError {
@@ -150,6 +409,9 @@ impl IntoResponse for SnapshotError {
             SnapshotError::SubmissionError(msg) => (StatusCode::BAD_GATEWAY, msg),
             SnapshotError::ConnectionError(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
             SnapshotError::ConfigError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            SnapshotError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            SnapshotError::InvalidRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            SnapshotError::Conflict(msg) => (StatusCode::CONFLICT, msg),
         };
 
         (
@@ -162,3 +424,26 @@ impl IntoResponse for SnapshotError {
             .into_response()
     }
 }
+
+/// Create public snapshot routes
+pub fn routes(state: SnapshotAppState) -> Router {
+    Router::new()
+        .route("/api/snapshots/diff", get(diff_snapshots))
+        .route("/api/snapshots/submissions", get(list_submissions))
+        .route("/api/snapshots/submissions/:epoch", get(get_submission))
+        .route("/api/snapshots/:epoch", get(get_snapshot))
+        .route("/api/snapshots/:epoch/proof", get(get_inclusion_proof))
+        .route("/api/snapshots/:epoch/verify", get(verify_snapshot))
+        .with_state(state)
+}
+
+/// Create snapshot routes that require authentication
+pub fn protected_routes(state: SnapshotAppState) -> Router {
+    Router::new()
+        .route("/api/snapshots/generate", post(generate_snapshot))
+        .route(
+            "/api/snapshots/submissions/:epoch/retry",
+            post(retry_submission),
+        )
+        .with_state(state)
+}
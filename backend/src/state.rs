@@ -1,5 +1,9 @@
+use crate::cache::CacheManager;
 use crate::database::Database;
 use crate::ingestion::DataIngestionService;
+use crate::rpc::StellarRpcClient;
+use crate::scoring::ScoringEngine;
+use crate::services::price_feed::PriceFeedClient;
 use crate::websocket::WsState;
 use std::sync::Arc;
 
@@ -9,6 +13,8 @@ pub struct AppState {
     pub db: Arc<Database>,
     pub ws_state: Arc<WsState>,
     pub ingestion: Arc<DataIngestionService>,
+    /// Models behind health and reliability scores
+    pub scoring: Arc<ScoringEngine>,
 }
 
 impl AppState {
@@ -16,11 +22,24 @@ impl AppState {
         db: Arc<Database>,
         ws_state: Arc<WsState>,
         ingestion: Arc<DataIngestionService>,
+        scoring: Arc<ScoringEngine>,
     ) -> Self {
         Self {
             db,
             ws_state,
             ingestion,
+            scoring,
         }
     }
 }
+
+/// Shared state for the cached anchor and corridor handlers
+#[derive(Clone)]
+pub struct CachedState {
+    pub db: Arc<Database>,
+    pub cache: Arc<CacheManager>,
+    pub rpc_client: Arc<StellarRpcClient>,
+    pub price_feed: Arc<PriceFeedClient>,
+    /// Models behind health and reliability scores
+    pub scoring: Arc<ScoringEngine>,
+}
@@ -69,6 +69,16 @@ pub enum WsMessage {
         reliability_score: f64,
         status: String,
     },
+    /// Anomaly detected in a corridor's hourly metrics
+    CorridorAnomaly {
+        corridor_key: String,
+        metric: String,
+        severity: String,
+        hour_bucket: String,
+        observed_value: f64,
+        expected_value: f64,
+        score: f64,
+    },
     /// Heartbeat/Ping message
     Ping { timestamp: i64 },
     /// Pong response
@@ -1,4 +1,5 @@
 use stellar_insights_backend::{analytics::compute_anchor_metrics, analytics::count_assets_per_anchor, models::AnchorStatus};
+use stellar_insights_backend::models::{volume_collapsed, StatusCause, StatusInputs};
 
 #[test]
 fn test_compute_metrics_green_status() {
@@ -62,19 +63,86 @@ fn test_compute_metrics_slow_settlement() {
 #[test]
 fn test_anchor_status_boundary_green_yellow() {
     // Exactly 98% success - should be Yellow (not Green)
-    assert_eq!(AnchorStatus::from_metrics(98.0, 2.0), AnchorStatus::Yellow);
+    assert_eq!(AnchorStatus::from_metrics(98.0, 2.0, None), AnchorStatus::Yellow);
 
     // Just above 98% with <1% failures - should be Green
-    assert_eq!(AnchorStatus::from_metrics(98.1, 0.9), AnchorStatus::Green);
+    assert_eq!(AnchorStatus::from_metrics(98.1, 0.9, None), AnchorStatus::Green);
 }
 
 #[test]
 fn test_anchor_status_boundary_yellow_red() {
     // Exactly 95% success - should be Yellow
-    assert_eq!(AnchorStatus::from_metrics(95.0, 5.0), AnchorStatus::Yellow);
+    assert_eq!(AnchorStatus::from_metrics(95.0, 5.0, None), AnchorStatus::Yellow);
 
     // Just below 95% - should be Red
-    assert_eq!(AnchorStatus::from_metrics(94.9, 5.1), AnchorStatus::Red);
+    assert_eq!(AnchorStatus::from_metrics(94.9, 5.1, None), AnchorStatus::Red);
+}
+
+#[test]
+fn test_anchor_status_endpoint_uptime() {
+    // Endpoint uptime caps the status payments alone would earn
+    assert_eq!(AnchorStatus::from_metrics(99.0, 0.5, Some(99.0)), AnchorStatus::Green);
+    assert_eq!(AnchorStatus::from_metrics(99.0, 0.5, Some(98.9)), AnchorStatus::Yellow);
+    assert_eq!(AnchorStatus::from_metrics(99.0, 0.5, Some(94.9)), AnchorStatus::Red);
+
+    // Perfect uptime does not lift poor payment rates
+    assert_eq!(AnchorStatus::from_metrics(90.0, 10.0, Some(100.0)), AnchorStatus::Red);
+}
+
+#[test]
+fn test_status_cause_diagnosis() {
+    let inputs = StatusInputs {
+        success_rate: 90.0,
+        failure_rate: 10.0,
+        uptime: Some(90.0),
+        volume_usd: Some(100.0),
+    };
+    assert_eq!(
+        StatusCause::diagnose(AnchorStatus::Green, AnchorStatus::Red, &inputs, 1000.0),
+        vec![
+            StatusCause::SuccessRateDrop,
+            StatusCause::EndpointDowntime,
+            StatusCause::VolumeCollapse
+        ]
+    );
+
+    // Healthy payments with endpoints that are only flaky enough for yellow
+    let inputs = StatusInputs {
+        success_rate: 99.5,
+        failure_rate: 0.5,
+        uptime: Some(97.0),
+        volume_usd: None,
+    };
+    assert_eq!(
+        StatusCause::diagnose(AnchorStatus::Green, AnchorStatus::Yellow, &inputs, 1000.0),
+        vec![StatusCause::EndpointDowntime]
+    );
+    // Already yellow, so the same uptime does not explain a further drop
+    assert_eq!(
+        StatusCause::diagnose(AnchorStatus::Yellow, AnchorStatus::Red, &inputs, 1000.0),
+        vec![]
+    );
+
+    assert_eq!(
+        StatusCause::diagnose(AnchorStatus::Red, AnchorStatus::Yellow, &inputs, 1000.0),
+        vec![StatusCause::Recovery]
+    );
+
+    // A volume collapse alone takes green to yellow and explains the move
+    let inputs = StatusInputs {
+        success_rate: 99.5,
+        failure_rate: 0.5,
+        uptime: None,
+        volume_usd: Some(150.0),
+    };
+    let status = AnchorStatus::from_metrics(99.5, 0.5, None)
+        .with_volume_collapse(volume_collapsed(150.0, 1000.0));
+    assert_eq!(status, AnchorStatus::Yellow);
+    assert_eq!(
+        StatusCause::diagnose(AnchorStatus::Green, status, &inputs, 1000.0),
+        vec![StatusCause::VolumeCollapse]
+    );
+    assert_eq!(AnchorStatus::Red.with_volume_collapse(true), AnchorStatus::Red);
 }
 
 #[test]
@@ -0,0 +1,196 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::services::anchor_fees::{
+    AnchorFeeCollector, AnchorFeeCollectorConfig, FeeCollectionSummary, FeeOperation,
+};
+use stellar_insights_backend::services::sep_endpoint_prober::{
+    SepEndpoint, SepEndpointProber, SepEndpointProberConfig,
+};
+use tokio::sync::RwLock;
+
+type Served = Arc<RwLock<Value>>;
+
+async fn serve_info(State(info): State<Served>) -> Json<Value> {
+    Json(info.read().await.clone())
+}
+
+/// SEP-6 `/fee` charging 0.75% on USDC deposits
+async fn serve_fee(Query(params): Query<HashMap<String, String>>) -> Json<Value> {
+    assert_eq!(params["operation"], "deposit");
+    assert_eq!(params["asset_code"], "USDC");
+    let amount: f64 = params["amount"].parse().unwrap();
+    Json(json!({ "fee": amount * 0.0075 }))
+}
+
+/// Serve `/info` and `/fee`, returning the transfer server URL
+async fn start_transfer_server(info: Served) -> String {
+    let app = Router::new()
+        .route("/info", get(serve_info))
+        .route("/fee", get(serve_fee))
+        .with_state(info);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+async fn create_anchor(
+    db: &Database,
+    prober: &SepEndpointProber,
+    name: &str,
+    account: &str,
+    sep: SepEndpoint,
+    transfer_server: &str,
+) -> String {
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: name.to_string(),
+            stellar_account: account.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    assert!(prober
+        .set_endpoint(&anchor.id, sep, transfer_server)
+        .await
+        .unwrap());
+    anchor.id
+}
+
+#[sqlx::test]
+async fn test_collect_and_compare_fees(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let prober = SepEndpointProber::new(Arc::clone(&db), SepEndpointProberConfig::default());
+
+    let sep24_info: Served = Arc::new(RwLock::new(json!({
+        "deposit": {
+            "USDC": {
+                "enabled": true,
+                "fee_fixed": 1,
+                "fee_percent": 0.5,
+                "min_amount": 10,
+                "max_amount": 1000
+            }
+        },
+        "withdraw": {
+            "USDC": { "enabled": true, "fee_fixed": 2 }
+        }
+    })));
+    let sep6_info: Served = Arc::new(RwLock::new(json!({
+        "deposit": {
+            "USDC": { "enabled": true, "min_amount": 1 }
+        },
+        "withdraw": {},
+        "fee": { "enabled": true }
+    })));
+
+    let sep24_server = start_transfer_server(Arc::clone(&sep24_info)).await;
+    let sep6_server = start_transfer_server(Arc::clone(&sep6_info)).await;
+    let hosted = create_anchor(
+        &db,
+        &prober,
+        "Hosted",
+        "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ",
+        SepEndpoint::Sep24,
+        &sep24_server,
+    )
+    .await;
+    let programmatic = create_anchor(
+        &db,
+        &prober,
+        "Programmatic",
+        "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2",
+        SepEndpoint::Sep6,
+        &sep6_server,
+    )
+    .await;
+
+    let collector = AnchorFeeCollector::new(Arc::clone(&db), AnchorFeeCollectorConfig::default());
+    assert_eq!(
+        collector.run_collection().await.unwrap(),
+        FeeCollectionSummary {
+            anchors: 2,
+            changes: 3,
+            failed: 0,
+        }
+    );
+
+    let schedules = collector.get_schedules(&hosted).await.unwrap();
+    assert_eq!(schedules.len(), 2);
+    assert_eq!(schedules[0].operation, "deposit");
+    assert_eq!(schedules[0].sep, "sep24");
+    assert_eq!(schedules[0].terms.fee_percent, Some(0.5));
+
+    // The SEP-6 anchor only quotes through /fee and comes out cheaper
+    let quotes = collector
+        .compare("USDC", FeeOperation::Deposit, 100.0)
+        .await
+        .unwrap();
+    assert_eq!(quotes.len(), 2);
+    assert_eq!(quotes[0].anchor_id, programmatic);
+    assert_eq!(quotes[0].fee_source.as_deref(), Some("fee_endpoint"));
+    assert_eq!(quotes[0].fee, Some(0.75));
+    assert_eq!(quotes[0].amount_out, Some(99.25));
+    assert_eq!(quotes[1].anchor_id, hosted);
+    assert_eq!(quotes[1].anchor_name, "Hosted");
+    assert_eq!(quotes[1].fee_source.as_deref(), Some("schedule"));
+    assert_eq!(quotes[1].fee, Some(1.5));
+
+    // Below the hosted anchor's minimum there is no quote from it
+    let quotes = collector
+        .compare("USDC", FeeOperation::Withdraw, 5.0)
+        .await
+        .unwrap();
+    assert_eq!(quotes.len(), 1);
+    assert_eq!(quotes[0].fee, Some(2.0));
+    let quotes = collector
+        .compare("USDC", FeeOperation::Deposit, 5.0)
+        .await
+        .unwrap();
+    let hosted_quote = quotes.iter().find(|q| q.anchor_id == hosted).unwrap();
+    assert!(!hosted_quote.within_limits);
+    assert_eq!(hosted_quote.fee, None);
+
+    // Unchanged terms are not recorded again
+    assert_eq!(collector.run_collection().await.unwrap().changes, 0);
+
+    *sep24_info.write().await = json!({
+        "deposit": {
+            "USDC": {
+                "enabled": true,
+                "fee_fixed": 1,
+                "fee_percent": 0.25,
+                "min_amount": 10,
+                "max_amount": 1000
+            }
+        },
+        "withdraw": {}
+    });
+    assert_eq!(collector.run_collection().await.unwrap().changes, 2);
+
+    let history = collector
+        .get_history(&hosted, Some("USDC"), Some(FeeOperation::Deposit), 10)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].terms.fee_percent, Some(0.25));
+    assert_eq!(history[1].terms.fee_percent, Some(0.5));
+
+    let withdraw = collector
+        .get_history(&hosted, None, Some(FeeOperation::Withdraw), 10)
+        .await
+        .unwrap();
+    assert_eq!(withdraw.len(), 2);
+    assert!(!withdraw[0].terms.enabled);
+    assert_eq!(withdraw[0].terms.fee_fixed, None);
+    assert_eq!(collector.get_schedules(&hosted).await.unwrap().len(), 1);
+}
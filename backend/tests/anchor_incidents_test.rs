@@ -0,0 +1,185 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::incidents::routes;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::{CreateAnchorRequest, StatusCause};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+const ANCHOR_ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+
+async fn get_json(db: &Arc<Database>, uri: &str) -> (StatusCode, Value) {
+    let response = routes(Arc::clone(db))
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_status_transitions_group_into_incidents(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Example".to_string(),
+            stellar_account: ANCHOR_ACCOUNT.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    let anchor_uuid: Uuid = anchor.id.parse().unwrap();
+
+    // Green to green is not a transition
+    let updated = db
+        .update_anchor_metrics(anchor_uuid, 1000, 995, 5, Some(2000), Some(50_000.0))
+        .await
+        .unwrap();
+    assert_eq!(updated.status, "green");
+    assert!(db
+        .get_anchor_status_transitions(&anchor.id, 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Failures pile up while volume collapses
+    db.update_anchor_metrics(anchor_uuid, 1000, 900, 100, Some(2000), Some(5_000.0))
+        .await
+        .unwrap();
+    db.update_anchor_metrics(anchor_uuid, 1000, 970, 30, Some(2000), Some(5_000.0))
+        .await
+        .unwrap();
+    let open = db.list_incidents(None, Some(true), 10).await.unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].anchor_name, "Example");
+    assert_eq!(open[0].worst_status, "red");
+    assert_eq!(
+        open[0].causes,
+        vec![StatusCause::SuccessRateDrop, StatusCause::VolumeCollapse]
+    );
+    assert_eq!(open[0].ended_at, None);
+
+    db.update_anchor_metrics(anchor_uuid, 1000, 995, 5, Some(2000), Some(5_000.0))
+        .await
+        .unwrap();
+    assert!(db
+        .list_incidents(None, Some(true), 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Endpoint downtime alone opens a second incident
+    for available in [true, false, false, false] {
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_endpoint_probes (
+                anchor_id, sep, url, available, schema_valid, probed_at
+            )
+            VALUES ($1, 'sep24', 'https://anchor.example/sep24/info', $2, $2, $3)
+            "#,
+        )
+        .bind(&anchor.id)
+        .bind(available)
+        .bind(Utc::now())
+        .execute(db.pool())
+        .await
+        .unwrap();
+    }
+    let current = db.get_anchor_by_id(anchor_uuid).await.unwrap().unwrap();
+    db.refresh_anchor_status(&current).await.unwrap();
+
+    let transitions = db
+        .get_anchor_status_transitions(&anchor.id, 10)
+        .await
+        .unwrap();
+    let moves: Vec<(&str, &str)> = transitions
+        .iter()
+        .map(|t| (t.from_status.as_str(), t.to_status.as_str()))
+        .collect();
+    assert_eq!(
+        moves,
+        vec![
+            ("green", "red"),
+            ("yellow", "green"),
+            ("red", "yellow"),
+            ("green", "red"),
+        ]
+    );
+    assert_eq!(transitions[0].causes, vec![StatusCause::EndpointDowntime]);
+    assert_eq!(transitions[0].uptime_percent, Some(25.0));
+    assert_eq!(transitions[2].causes, vec![StatusCause::Recovery]);
+
+    let (status, body) = get_json(&db, &format!("/api/anchors/{}/incidents", anchor.id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["current_status"], "red");
+    let incidents = body["incidents"].as_array().unwrap();
+    assert_eq!(incidents.len(), 2);
+    assert!(incidents[0]["ended_at"].is_null());
+    assert_eq!(incidents[0]["causes"][0], "endpoint_downtime");
+    assert!(!incidents[1]["ended_at"].is_null());
+    let timeline: Vec<&str> = incidents[1]["transitions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["to_status"].as_str().unwrap())
+        .collect();
+    assert_eq!(timeline, vec!["red", "yellow", "green"]);
+
+    let (_, body) = get_json(&db, "/api/incidents?status=resolved").await;
+    assert_eq!(body["incidents"].as_array().unwrap().len(), 1);
+    let (status, _) = get_json(&db, "/api/incidents?status=closed").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_json(&db, &format!("/api/anchors/{}/incidents", Uuid::new_v4())).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_volume_collapse_alone_opens_incident(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Example".to_string(),
+            stellar_account: ANCHOR_ACCOUNT.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    let anchor_uuid: Uuid = anchor.id.parse().unwrap();
+
+    db.update_anchor_metrics(anchor_uuid, 1000, 995, 5, Some(2000), Some(50_000.0))
+        .await
+        .unwrap();
+
+    // Healthy rates, but volume drops by 90%
+    let updated = db
+        .update_anchor_metrics(anchor_uuid, 1000, 995, 5, Some(2000), Some(5_000.0))
+        .await
+        .unwrap();
+    assert_eq!(updated.status, "yellow");
+
+    let open = db.list_incidents(None, Some(true), 10).await.unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].worst_status, "yellow");
+    assert_eq!(open[0].causes, vec![StatusCause::VolumeCollapse]);
+
+    // Volume holds at the new level, so the anchor recovers
+    let updated = db
+        .update_anchor_metrics(anchor_uuid, 1000, 995, 5, Some(2000), Some(5_000.0))
+        .await
+        .unwrap();
+    assert_eq!(updated.status, "green");
+    assert!(db
+        .list_incidents(None, Some(true), 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
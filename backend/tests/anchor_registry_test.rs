@@ -0,0 +1,259 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{HeaderMap, Request, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::anchor_registry::{AnchorRegistry, ProxyAccess};
+use stellar_insights_backend::services::sep10_client::{Sep10Client, Sep10ClientConfig};
+use stellar_insights_backend::services::sep_endpoint_prober::{
+    SepEndpoint, SepEndpointProber, SepEndpointProberConfig,
+};
+use stellar_insights_backend::services::stellar_toml_crawler::{
+    StellarTomlCrawler, StellarTomlCrawlerConfig,
+};
+use stellar_insights_backend::services::transfer_tracker::{
+    TransferTracker, TransferTrackerConfig,
+};
+use tower::util::ServiceExt;
+
+/// A SEP-24 server whose `/info` can be made slower than the proxy timeout
+#[derive(Default)]
+struct FakeAnchor {
+    slow: AtomicBool,
+}
+
+async fn info(State(anchor): State<Arc<FakeAnchor>>) -> Json<Value> {
+    if anchor.slow.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+    Json(json!({ "deposit": {}, "withdraw": {} }))
+}
+
+async fn transaction(
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    let auth = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    Json(json!({ "transaction": { "id": params["id"], "authorization": auth } }))
+}
+
+async fn start_anchor(anchor: Arc<FakeAnchor>) -> String {
+    let app = Router::new()
+        .route("/sep24/info", get(info))
+        .route("/sep24/transaction", get(transaction))
+        .route("/other/info", get(info))
+        .with_state(anchor);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn sep24_routes(db: Arc<Database>, registry: Arc<AnchorRegistry>) -> Router {
+    let rpc_client = Arc::new(StellarRpcClient::new(
+        "http://127.0.0.1:1".to_string(),
+        "http://127.0.0.1:1".to_string(),
+        true,
+    ));
+    let crawler = Arc::new(
+        StellarTomlCrawler::new(
+            Arc::clone(&db),
+            rpc_client,
+            StellarTomlCrawlerConfig::default(),
+        )
+        .unwrap(),
+    );
+    let sep10 = Arc::new(Sep10Client::new(
+        crawler,
+        Sep10ClientConfig::default(),
+        None,
+    ));
+    let tracker = Arc::new(TransferTracker::new(
+        db,
+        Arc::clone(&sep10),
+        TransferTrackerConfig::default(),
+    ));
+    stellar_insights_backend::api::sep24_proxy::routes(sep10, tracker, registry)
+}
+
+async fn call(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn get_uri(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+fn put_json(uri: &str, body: Value) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_proxies_follow_the_anchor_registry(pool: SqlitePool) {
+    let fake = Arc::new(FakeAnchor::default());
+    let base = start_anchor(Arc::clone(&fake)).await;
+
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Anchor".to_string(),
+            stellar_account: "GANCHOR".to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    let prober = SepEndpointProber::new(Arc::clone(&db), SepEndpointProberConfig::default());
+    assert!(prober
+        .set_endpoint(&anchor.id, SepEndpoint::Sep24, &format!("{}/sep24", base))
+        .await
+        .unwrap());
+
+    let registry = Arc::new(AnchorRegistry::new(Arc::clone(&db)));
+    let sep24 = sep24_routes(Arc::clone(&db), Arc::clone(&registry));
+    let config = stellar_insights_backend::api::anchor_proxy::routes(Arc::clone(&registry)).merge(
+        stellar_insights_backend::api::anchor_proxy::protected_routes(Arc::clone(&registry)),
+    );
+    let proxy_uri = format!("/api/anchors/{}/proxy", anchor.id);
+    let other_info = format!("/api/sep24/info?transfer_server={}/other", base);
+    let anchor_info = format!("/api/sep24/info?transfer_server={}/sep24/", base);
+
+    // Without enabled anchors or allowed origins any transfer server is proxied
+    let (status, body) = call(&config, get_uri(&proxy_uri)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["enabled"], false);
+    assert_eq!(body["auth"], "optional");
+    let (status, _) = call(&sep24, get_uri(&other_info)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = call(
+        &config,
+        put_json(&proxy_uri, json!({ "enabled": true, "auth": "jwt" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = call(
+        &config,
+        put_json(&proxy_uri, json!({ "enabled": true, "timeout_secs": 0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = call(
+        &config,
+        put_json("/api/anchors/missing/proxy", json!({ "enabled": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = call(
+        &config,
+        put_json(
+            &proxy_uri,
+            json!({ "enabled": true, "auth": "sep10", "timeout_secs": 1 }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["timeout_secs"], 1);
+
+    // The same router now only calls the enabled anchor
+    let (status, body) = call(&sep24, get_uri(&other_info)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "forbidden");
+    let (status, _) = call(&sep24, get_uri(&anchor_info)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = call(&sep24, get_uri("/api/sep24/anchors")).await;
+    assert_eq!(
+        body["anchors"],
+        json!([{
+            "name": "Anchor",
+            "transfer_server": format!("{}/sep24", base),
+            "home_domain": null,
+            "anchor_id": anchor.id,
+            "auth": "sep10"
+        }])
+    );
+
+    // A token is required, and the anchor has no home domain to obtain one for
+    let transaction = format!("/api/sep24/transaction?transfer_server={}/sep24&id=1", base);
+    let (status, body) = call(&sep24, get_uri(&transaction)).await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(body["error"], "sep10");
+    let (status, body) = call(&sep24, get_uri(&format!("{}&jwt=mine", transaction))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["transaction"]["authorization"], "Bearer mine");
+
+    // The anchor's timeout applies until it is lifted
+    fake.slow.store(true, Ordering::SeqCst);
+    let (status, body) = call(&sep24, get_uri(&anchor_info)).await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(body["error"], "proxy");
+    call(
+        &config,
+        put_json(&proxy_uri, json!({ "enabled": true, "auth": "none" })),
+    )
+    .await;
+    let (status, _) = call(&sep24, get_uri(&anchor_info)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = call(&sep24, get_uri(&transaction)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["transaction"]["authorization"], Value::Null);
+
+    // Disabling the last anchor lifts the restriction again
+    call(&config, put_json(&proxy_uri, json!({ "enabled": false }))).await;
+    fake.slow.store(false, Ordering::SeqCst);
+    let (status, _) = call(&sep24, get_uri(&other_info)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = call(&sep24, get_uri("/api/sep24/anchors")).await;
+    assert_eq!(body["anchors"], json!([]));
+}
+
+#[sqlx::test]
+async fn test_allowed_origins_do_not_match_host_prefixes(pool: SqlitePool) {
+    let registry = AnchorRegistry::new(Arc::new(Database::new(pool)));
+    let origins = vec!["https://anchor.com".to_string()];
+
+    let access = registry
+        .authorize(
+            SepEndpoint::Sep24,
+            "https://anchor.com/sep24/info",
+            &origins,
+        )
+        .await
+        .unwrap();
+    assert!(matches!(access, ProxyAccess::Allowed));
+
+    let access = registry
+        .authorize(
+            SepEndpoint::Sep24,
+            "https://anchor.com.evil.net/sep24/info",
+            &origins,
+        )
+        .await
+        .unwrap();
+    assert!(matches!(access, ProxyAccess::Denied));
+}
@@ -0,0 +1,138 @@
+use chrono::{Duration, Timelike, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::aggregation::HourlyCorridorMetrics;
+use stellar_insights_backend::services::anomaly_detector::{
+    AnomalyDetectorConfig, CorridorAnomalyDetector,
+};
+use stellar_insights_backend::websocket::WsState;
+
+const CORRIDOR: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN->XLM:native";
+
+fn hourly(
+    hour_bucket: chrono::DateTime<Utc>,
+    total: i64,
+    successful: i64,
+    volume_usd: f64,
+) -> HourlyCorridorMetrics {
+    HourlyCorridorMetrics {
+        id: uuid::Uuid::new_v4().to_string(),
+        corridor_key: CORRIDOR.to_string(),
+        asset_a_code: "USDC".to_string(),
+        asset_a_issuer: "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string(),
+        asset_b_code: "XLM".to_string(),
+        asset_b_issuer: "native".to_string(),
+        hour_bucket,
+        total_transactions: total,
+        successful_transactions: successful,
+        failed_transactions: total - successful,
+        success_rate: successful as f64 / total as f64 * 100.0,
+        volume_usd,
+        avg_slippage_bps: 0.0,
+        avg_settlement_latency_ms: Some(2_000),
+        liquidity_depth_usd: 0.0,
+    }
+}
+
+fn current_hour() -> chrono::DateTime<Utc> {
+    Utc::now()
+        .with_minute(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_detects_success_rate_collapse_once(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let current_hour = current_hour();
+    // The collapse happens in this hour, which is still open
+    let now = current_hour + Duration::minutes(30);
+
+    // Same hour-of-week in the previous three weeks looked healthy
+    for (week, successful) in [(1, 98), (2, 97), (3, 99)] {
+        let bucket = current_hour - Duration::weeks(week);
+        db.upsert_hourly_corridor_metric(&hourly(bucket, 100, successful, 10_000.0))
+            .await
+            .unwrap();
+    }
+    db.upsert_hourly_corridor_metric(&hourly(current_hour, 100, 40, 10_100.0))
+        .await
+        .unwrap();
+
+    let ws_state = Arc::new(WsState::new());
+    let detector =
+        CorridorAnomalyDetector::new(Arc::clone(&db), ws_state, AnomalyDetectorConfig::default());
+
+    assert!(detector.run_detection(now).await.unwrap().is_empty());
+
+    // Once it is complete, the next run scores it
+    let now = now + Duration::hours(1);
+    let anomalies = detector.run_detection(now).await.unwrap();
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0].metric, "success_rate");
+    assert_eq!(anomalies[0].baseline, "seasonal");
+    assert_eq!(anomalies[0].severity, "critical");
+    assert!(anomalies[0].score < 0.0);
+
+    // A second run for the same hour records nothing new
+    assert!(detector.run_detection(now).await.unwrap().is_empty());
+
+    let stored = detector
+        .get_anomalies(&CORRIDOR.parse().unwrap(), now - Duration::days(1), 10)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+}
+
+#[sqlx::test]
+async fn test_no_anomaly_without_history(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let now = Utc::now();
+    db.upsert_hourly_corridor_metric(&hourly(now - Duration::minutes(30), 100, 10, 1.0))
+        .await
+        .unwrap();
+
+    let detector = CorridorAnomalyDetector::new(
+        db,
+        Arc::new(WsState::new()),
+        AnomalyDetectorConfig::default(),
+    );
+
+    assert!(detector.run_detection(now).await.unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn test_detects_corridor_going_silent(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let last_hour = current_hour() - Duration::hours(1);
+
+    // Busy in the same hour of the previous weeks, nothing at all this week
+    for week in 1..=3 {
+        db.upsert_hourly_corridor_metric(&hourly(
+            last_hour - Duration::weeks(week),
+            100,
+            98,
+            10_000.0,
+        ))
+        .await
+        .unwrap();
+    }
+
+    let detector = CorridorAnomalyDetector::new(
+        db,
+        Arc::new(WsState::new()),
+        AnomalyDetectorConfig::default(),
+    );
+
+    let anomalies = detector
+        .run_detection(last_hour + Duration::minutes(90))
+        .await
+        .unwrap();
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0].metric, "volume");
+    assert_eq!(anomalies[0].observed_value, 0.0);
+    assert_eq!(anomalies[0].expected_value, 10_000.0);
+    assert_eq!(anomalies[0].hour_bucket, last_hour.to_rfc3339());
+}
@@ -15,6 +15,7 @@ use stellar_insights_backend::state::AppState;
 use stellar_insights_backend::websocket::WsState;
 use stellar_insights_backend::ingestion::DataIngestionService;
 use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::scoring::ScoringEngine;
 
 async fn setup_test_db() -> SqlitePool {
     let pool = SqlitePool::connect(":memory:").await.unwrap();
@@ -29,7 +30,7 @@ fn create_test_router(db: Arc<Database>) -> Router {
     let ws_state = Arc::new(WsState::new());
     let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
     let ingestion = Arc::new(DataIngestionService::new(rpc_client, Arc::clone(&db)));
-    let state = AppState { db, ws_state, ingestion };
+    let state = AppState::new(db, ws_state, ingestion, Arc::new(ScoringEngine::default()));
     Router::new()
         .route("/api/corridors", axum::routing::get(list_corridors))
         .route(
@@ -74,7 +75,7 @@ async fn test_get_corridor_detail_success() {
     let app = create_test_router(db);
 
     // Use URL encoded corridor key
-    let corridor_key = "EURC%3Aissuer2-%3EUSDC%3Aissuer1";
+    let corridor_key = "EURC%3AGA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN-%3EUSDC%3AGA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
     let request = Request::builder()
         .uri(&format!("/api/corridors/{}", corridor_key))
         .body(Body::empty())
@@ -94,7 +95,7 @@ async fn test_get_corridor_detail_not_found() {
     let app = create_test_router(db);
 
     // Use URL encoded corridor key
-    let corridor_key = "NONEXISTENT%3Aissuer-%3EFAKE%3Aissuer";
+    let corridor_key = "NONEXISTENT%3AGDUKMGUGDZQK6YHYA5Z6AY2G4XDSZPSZ3SW5UN3ARVMO6QSRDWP5YLEX-%3EFAKE%3AGDUKMGUGDZQK6YHYA5Z6AY2G4XDSZPSZ3SW5UN3ARVMO6QSRDWP5YLEX";
     let request = Request::builder()
         .uri(&format!("/api/corridors/{}", corridor_key))
         .body(Body::empty())
@@ -0,0 +1,271 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::asset_supply::{
+    AssetSupplyMonitor, AssetSupplyMonitorConfig, SupplyEventKind, SupplySyncSummary,
+};
+use tokio::sync::RwLock;
+
+const ISSUER: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+const ANCHOR_ACCOUNT: &str = "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+const TREASURY: &str = "GA7FCCMTTSUIC37PODEL6EOOSPDRILP6OQI5FWCWDDVDBLJV72W6RINZ";
+const HOLDER: &str = "GDUKMGUGDZQK6YHYA5Z6AY2G4XDSZPSZ3SW5UN3ARVMO6QSRDWP5YLEX";
+
+type Payments = Arc<RwLock<Vec<Value>>>;
+
+fn embedded(records: Vec<Value>) -> Json<Value> {
+    Json(json!({ "_embedded": { "records": records } }))
+}
+
+/// `/assets` knows only the test asset
+async fn serve_assets(Query(params): Query<HashMap<String, String>>) -> Json<Value> {
+    if params.get("asset_issuer").map(String::as_str) != Some(ISSUER) {
+        return embedded(vec![]);
+    }
+    embedded(vec![json!({
+        "asset_type": "credit_alphanum4",
+        "asset_code": "USDT",
+        "asset_issuer": ISSUER,
+        "num_claimable_balances": 1,
+        "num_liquidity_pools": 0,
+        "num_contracts": 0,
+        "accounts": {
+            "authorized": 3,
+            "authorized_to_maintain_liabilities": 0,
+            "unauthorized": 0
+        },
+        "claimable_balances_amount": "100.0000000",
+        "liquidity_pools_amount": "0.0000000",
+        "contracts_amount": "0.0000000",
+        "balances": {
+            "authorized": "900.0000000",
+            "authorized_to_maintain_liabilities": "0.0000000",
+            "unauthorized": "0.0000000"
+        },
+        "flags": {
+            "auth_required": false,
+            "auth_revocable": false,
+            "auth_immutable": false,
+            "auth_clawback_enabled": false
+        }
+    })])
+}
+
+/// Issuer payments after the cursor, oldest first
+async fn serve_payments(
+    State(payments): State<Payments>,
+    Path(account): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    if account != ISSUER {
+        return embedded(vec![]);
+    }
+    assert_eq!(params["order"], "asc");
+    let cursor: u64 = params.get("cursor").map_or(0, |c| c.parse().unwrap());
+    let limit: usize = params["limit"].parse().unwrap();
+    let records = payments
+        .read()
+        .await
+        .iter()
+        .filter(|p| p["paging_token"].as_str().unwrap().parse::<u64>().unwrap() > cursor)
+        .take(limit)
+        .cloned()
+        .collect();
+    embedded(records)
+}
+
+async fn serve_account(Path(account): Path<String>) -> Json<Value> {
+    let held = match account.as_str() {
+        ANCHOR_ACCOUNT => "300.0000000",
+        TREASURY => "200.0000000",
+        _ => "0.0000000",
+    };
+    Json(json!({
+        "account_id": account,
+        "sequence": "1",
+        "balances": [
+            { "asset_type": "credit_alphanum4", "asset_code": "USDT", "asset_issuer": ISSUER, "balance": held },
+            { "asset_type": "native", "balance": "50.0000000" }
+        ]
+    }))
+}
+
+async fn start_horizon(payments: Payments) -> String {
+    let app = Router::new()
+        .route("/assets", get(serve_assets))
+        .route("/accounts/:account", get(serve_account))
+        .route("/accounts/:account/payments", get(serve_payments))
+        .with_state(payments);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn payment(
+    token: u64,
+    from: &str,
+    to: &str,
+    asset_code: Option<&str>,
+    amount: &str,
+    days_ago: i64,
+) -> Value {
+    let created_at = (Utc::now() - Duration::days(days_ago)).to_rfc3339();
+    let mut payment = json!({
+        "id": token.to_string(),
+        "paging_token": token.to_string(),
+        "transaction_hash": format!("tx{}", token),
+        "source_account": from,
+        "type": "payment",
+        "from": from,
+        "to": to,
+        "amount": amount,
+        "created_at": created_at,
+        "asset_type": "native"
+    });
+    if let Some(code) = asset_code {
+        payment["asset_type"] = json!("credit_alphanum4");
+        payment["asset_code"] = json!(code);
+        payment["asset_issuer"] = json!(ISSUER);
+    }
+    payment
+}
+
+#[sqlx::test]
+async fn test_supply_sync_classifies_mints_and_burns(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+
+    let payments: Payments = Arc::new(RwLock::new(vec![
+        // The issuer's creation shares the payments endpoint
+        json!({
+            "id": "1",
+            "paging_token": "1",
+            "transaction_hash": "tx1",
+            "source_account": TREASURY,
+            "type": "create_account",
+            "created_at": (Utc::now() - Duration::days(3)).to_rfc3339(),
+            "funder": TREASURY,
+            "account": ISSUER,
+            "starting_balance": "10.0000000"
+        }),
+        payment(2, ISSUER, ANCHOR_ACCOUNT, Some("USDT"), "500.0000000", 2),
+        payment(3, TREASURY, ISSUER, None, "5.0000000", 1),
+        payment(4, ISSUER, HOLDER, Some("USDT"), "700.0000000", 0),
+        payment(5, HOLDER, ISSUER, Some("USDT"), "200.0000000", 0),
+    ]));
+    let horizon = start_horizon(Arc::clone(&payments)).await;
+    let rpc_client = Arc::new(StellarRpcClient::new(horizon.clone(), horizon, false));
+
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Tether".to_string(),
+            stellar_account: ANCHOR_ACCOUNT.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    db.create_asset(
+        anchor.id.parse().unwrap(),
+        "USDT".to_string(),
+        ISSUER.to_string(),
+    )
+    .await
+    .unwrap();
+
+    let monitor = AssetSupplyMonitor::new(
+        Arc::clone(&db),
+        rpc_client,
+        AssetSupplyMonitorConfig {
+            page_limit: 2,
+            ..AssetSupplyMonitorConfig::default()
+        },
+    );
+    assert!(monitor
+        .add_distribution_account("USDT", ISSUER, TREASURY)
+        .await
+        .unwrap());
+    assert!(!monitor
+        .add_distribution_account("USDT", HOLDER, TREASURY)
+        .await
+        .unwrap());
+
+    // Seeded assets are unknown to this Horizon and skipped
+    assert_eq!(
+        monitor.run_sync().await.unwrap(),
+        SupplySyncSummary {
+            assets: 1,
+            mints: 2,
+            burns: 1,
+            failed: 0,
+        }
+    );
+
+    let supply = monitor.get_supply("USDT", ISSUER).await.unwrap().unwrap();
+    assert_eq!(supply.anchor_id, anchor.id);
+    assert_eq!(supply.total_supply, 1000.0);
+    assert_eq!(supply.distribution_balance, 500.0);
+    assert_eq!(supply.circulating_supply, 500.0);
+
+    let events = monitor.get_events("USDT", ISSUER, None, 10).await.unwrap();
+    let ids: Vec<&str> = events.iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(ids, vec!["5", "4", "2"]);
+    assert_eq!(events[0].kind, "burn");
+    assert_eq!(events[0].counterparty, HOLDER);
+    let burns = monitor
+        .get_events("USDT", ISSUER, Some(SupplyEventKind::Burn), 10)
+        .await
+        .unwrap();
+    assert_eq!(burns.len(), 1);
+
+    // Only payments after the stored cursor are classified again
+    assert_eq!(monitor.run_sync().await.unwrap().mints, 0);
+    payments
+        .write()
+        .await
+        .push(payment(6, ISSUER, HOLDER, Some("USDT"), "50.0000000", 0));
+    let summary = monitor.run_sync().await.unwrap();
+    assert_eq!((summary.mints, summary.burns), (1, 0));
+
+    let deltas = monitor.get_daily_deltas("USDT", ISSUER, 30).await.unwrap();
+    assert_eq!(deltas.len(), 2);
+    assert_eq!(deltas[0].mints, 1);
+    assert_eq!(deltas[0].minted, 500.0);
+    assert_eq!(deltas[0].total_supply, None);
+    let today = &deltas[1];
+    assert_eq!(today.date, Utc::now().format("%Y-%m-%d").to_string());
+    assert_eq!((today.mints, today.burns), (2, 1));
+    assert_eq!(today.net_issuance, 550.0);
+    assert_eq!(today.total_supply, Some(1000.0));
+    assert_eq!(today.circulating_supply, Some(500.0));
+
+    let anchor_supply = monitor.get_anchor_supply(&anchor.id, 1).await.unwrap();
+    assert_eq!(anchor_supply.assets.len(), 1);
+    assert_eq!(anchor_supply.assets[0].minted, 750.0);
+    assert_eq!(anchor_supply.assets[0].burned, 200.0);
+    assert_eq!(anchor_supply.assets[0].net_issuance, 550.0);
+
+    let asset = db
+        .get_assets_by_anchor(anchor.id.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(asset[0].total_supply, Some(1000.0));
+
+    // Treasury holdings count as circulating once it is removed
+    assert!(monitor
+        .remove_distribution_account("USDT", ISSUER, TREASURY)
+        .await
+        .unwrap());
+    monitor.run_sync().await.unwrap();
+    let supply = monitor.get_supply("USDT", ISSUER).await.unwrap().unwrap();
+    assert_eq!(supply.circulating_supply, 700.0);
+}
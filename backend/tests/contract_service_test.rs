@@ -0,0 +1,447 @@
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use base64::Engine;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::signer::{
+    is_signed_by, transaction_hash, InMemorySigner, KeystoreSigner, RemoteSigner,
+    RemoteSignerConfig, Signer,
+};
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+use tokio::io::AsyncWriteExt;
+
+const NETWORK: &str = "Standalone Network ; February 2017";
+const CONTRACT: [u8; 32] = [5u8; 32];
+const SOURCE_SEED: [u8; 32] = [9u8; 32];
+const RESOURCE_FEE: u32 = 5000;
+const LEDGER: u64 = 42;
+const LEDGER_TIMESTAMP: u64 = 1_760_000_000;
+
+/// State of the local stand-in for a Soroban RPC with the snapshot contract
+#[derive(Default)]
+struct Network {
+    /// Sequence numbers of existing accounts
+    accounts: HashMap<[u8; 32], i64>,
+    /// Roots held by the contract, by epoch
+    roots: HashMap<u64, Vec<u8>>,
+    /// Applied transactions by hash, with the contract's return value
+    transactions: HashMap<String, u64>,
+    rejected: Vec<String>,
+}
+
+type Shared = Arc<Mutex<Network>>;
+
+fn encode<T: WriteXdr>(value: &T) -> String {
+    base64::engine::general_purpose::STANDARD.encode(value.to_xdr(xdr::Limits::none()).unwrap())
+}
+
+fn decode<T: ReadXdr>(value: &Value) -> T {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(value.as_str().unwrap())
+        .unwrap();
+    T::from_xdr(bytes, xdr::Limits::none()).unwrap()
+}
+
+fn envelope(request: &Value) -> xdr::TransactionV1Envelope {
+    match decode(&request["params"]["transaction"]) {
+        xdr::TransactionEnvelope::Tx(envelope) => envelope,
+        other => panic!("unexpected envelope {:?}", other),
+    }
+}
+
+fn invocation(tx: &xdr::Transaction) -> (xdr::InvokeContractArgs, usize) {
+    match &tx.operations[0].body {
+        xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+            host_function: xdr::HostFunction::InvokeContract(args),
+            auth,
+        }) => (args.clone(), auth.len()),
+        other => panic!("unexpected operation {:?}", other),
+    }
+}
+
+fn source(tx: &xdr::Transaction) -> [u8; 32] {
+    match &tx.source_account {
+        xdr::MuxedAccount::Ed25519(key) => key.0,
+        other => panic!("unexpected source {:?}", other),
+    }
+}
+
+fn bytes(value: &xdr::ScVal) -> Vec<u8> {
+    match value {
+        xdr::ScVal::Bytes(bytes) => bytes.to_vec(),
+        other => panic!("expected bytes, got {:?}", other),
+    }
+}
+
+fn epoch(value: &xdr::ScVal) -> u64 {
+    match value {
+        xdr::ScVal::U64(epoch) => *epoch,
+        other => panic!("expected an epoch, got {:?}", other),
+    }
+}
+
+/// Footprint and resources the stand-in expects on submitted transactions
+fn transaction_data() -> xdr::SorobanTransactionData {
+    let key = xdr::LedgerKey::ContractData(xdr::LedgerKeyContractData {
+        contract: xdr::ScAddress::Contract(xdr::Hash(CONTRACT)),
+        key: xdr::ScVal::LedgerKeyContractInstance,
+        durability: xdr::ContractDataDurability::Persistent,
+    });
+    xdr::SorobanTransactionData {
+        ext: xdr::ExtensionPoint::V0,
+        resources: xdr::SorobanResources {
+            footprint: xdr::LedgerFootprint {
+                read_only: xdr::VecM::default(),
+                read_write: vec![key].try_into().unwrap(),
+            },
+            instructions: 2_000_000,
+            read_bytes: 1000,
+            write_bytes: 500,
+        },
+        resource_fee: RESOURCE_FEE as i64,
+    }
+}
+
+fn simulate(network: &Network, request: &Value) -> Value {
+    let envelope = envelope(request);
+    let (args, _) = invocation(&envelope.tx);
+    assert_eq!(
+        args.contract_address,
+        xdr::ScAddress::Contract(xdr::Hash(CONTRACT))
+    );
+
+    let result = match args.function_name.0.to_string().as_str() {
+        "submit_snapshot" if network.roots.contains_key(&epoch(&args.args[1])) => None,
+        "submit_snapshot" => {
+            let auth = xdr::SorobanAuthorizationEntry {
+                credentials: xdr::SorobanCredentials::SourceAccount,
+                root_invocation: xdr::SorobanAuthorizedInvocation {
+                    function: xdr::SorobanAuthorizedFunction::ContractFn(args.clone()),
+                    sub_invocations: xdr::VecM::default(),
+                },
+            };
+            return json!({
+                "transactionData": encode(&transaction_data()),
+                "minResourceFee": RESOURCE_FEE.to_string(),
+                "results": [{ "auth": [encode(&auth)], "xdr": encode(&xdr::ScVal::U64(0)) }],
+                "latestLedger": LEDGER,
+            });
+        }
+        "get_snapshot" => network
+            .roots
+            .get(&epoch(&args.args[0]))
+            .map(|root| xdr::ScVal::Bytes(xdr::ScBytes(root.clone().try_into().unwrap()))),
+        "verify_snapshot" => {
+            let hash = bytes(&args.args[0]);
+            Some(xdr::ScVal::Bool(
+                network.roots.values().any(|root| *root == hash),
+            ))
+        }
+        other => panic!("unexpected function {}", other),
+    };
+
+    match result {
+        Some(value) => json!({
+            "results": [{ "auth": [], "xdr": encode(&value) }],
+            "latestLedger": LEDGER,
+        }),
+        // The contract panics; release builds leave only the trap
+        None => {
+            json!({ "error": "HostError: Error(WasmVm, InvalidAction)", "latestLedger": LEDGER })
+        }
+    }
+}
+
+fn send(network: &mut Network, request: &Value) -> Value {
+    let envelope = envelope(request);
+    let tx = &envelope.tx;
+    let hash = transaction_hash(tx, NETWORK).unwrap();
+    let hash_hex = hex::encode(hash);
+    let (args, auth) = invocation(tx);
+
+    let sequence = network.accounts.get(&source(tx)).copied();
+    let error = if !is_signed_by(&source(tx), &hash, &envelope.signatures) {
+        Some("txBAD_AUTH")
+    } else if sequence.map(|sequence| sequence + 1) != Some(tx.seq_num.0) {
+        Some("txBAD_SEQ")
+    } else if tx.fee < 100 + RESOURCE_FEE
+        || tx.ext != xdr::TransactionExt::V1(transaction_data())
+        || auth != 1
+    {
+        Some("txSOROBAN_INVALID")
+    } else if network.roots.contains_key(&epoch(&args.args[1])) {
+        Some("txFAILED")
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        network.rejected.push(error.to_string());
+        return json!({ "status": "ERROR", "hash": hash_hex, "errorResultXdr": error });
+    }
+
+    network.accounts.insert(source(tx), tx.seq_num.0);
+    network
+        .roots
+        .insert(epoch(&args.args[1]), bytes(&args.args[0]));
+    network
+        .transactions
+        .insert(hash_hex.clone(), LEDGER_TIMESTAMP);
+    json!({ "status": "PENDING", "hash": hash_hex, "latestLedger": LEDGER })
+}
+
+fn get_transaction(network: &Network, request: &Value) -> Value {
+    let Some(return_value) = network
+        .transactions
+        .get(request["params"]["hash"].as_str().unwrap())
+    else {
+        return json!({ "status": "NOT_FOUND", "latestLedger": LEDGER });
+    };
+
+    let meta = xdr::TransactionMeta::V3(xdr::TransactionMetaV3 {
+        ext: xdr::ExtensionPoint::V0,
+        tx_changes_before: xdr::LedgerEntryChanges(xdr::VecM::default()),
+        operations: xdr::VecM::default(),
+        tx_changes_after: xdr::LedgerEntryChanges(xdr::VecM::default()),
+        soroban_meta: Some(xdr::SorobanTransactionMeta {
+            ext: xdr::SorobanTransactionMetaExt::V0,
+            events: xdr::VecM::default(),
+            return_value: xdr::ScVal::U64(*return_value),
+            diagnostic_events: xdr::VecM::default(),
+        }),
+    });
+    json!({ "status": "SUCCESS", "ledger": LEDGER, "resultMetaXdr": encode(&meta) })
+}
+
+fn get_ledger_entries(network: &Network, request: &Value) -> Value {
+    let key: xdr::LedgerKey = decode(&request["params"]["keys"][0]);
+    let xdr::LedgerKey::Account(xdr::LedgerKeyAccount {
+        account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(key)),
+    }) = key
+    else {
+        panic!("expected an account key");
+    };
+
+    let entries: Vec<Value> = network
+        .accounts
+        .get(&key.0)
+        .map(|sequence| {
+            let entry = xdr::LedgerEntryData::Account(xdr::AccountEntry {
+                account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(key.clone())),
+                balance: 100_0000000,
+                seq_num: xdr::SequenceNumber(*sequence),
+                num_sub_entries: 0,
+                inflation_dest: None,
+                flags: 0,
+                home_domain: xdr::String32::default(),
+                thresholds: xdr::Thresholds([1, 0, 0, 0]),
+                signers: xdr::VecM::default(),
+                ext: xdr::AccountEntryExt::V0,
+            });
+            json!({ "xdr": encode(&entry), "lastModifiedLedgerSeq": LEDGER })
+        })
+        .into_iter()
+        .collect();
+    json!({ "entries": entries, "latestLedger": LEDGER })
+}
+
+async fn rpc(State(network): State<Shared>, Json(request): Json<Value>) -> Json<Value> {
+    let mut network = network.lock().unwrap();
+    let result = match request["method"].as_str().unwrap() {
+        "simulateTransaction" => simulate(&network, &request),
+        "sendTransaction" => send(&mut network, &request),
+        "getTransaction" => get_transaction(&network, &request),
+        "getLedgerEntries" => get_ledger_entries(&network, &request),
+        other => panic!("unexpected method {}", other),
+    };
+    Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+}
+
+async fn start_rpc(network: Shared) -> String {
+    let app = Router::new().route("/", post(rpc)).with_state(network);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}/", addr)
+}
+
+async fn setup(network_passphrase: &str, signer: Arc<dyn Signer>) -> (Shared, ContractService) {
+    let network = Shared::default();
+    network
+        .lock()
+        .unwrap()
+        .accounts
+        .insert(signer.public_key(), 7 << 32);
+
+    let service = ContractService::with_signer(
+        ContractConfig {
+            rpc_url: start_rpc(Arc::clone(&network)).await,
+            contract_id: stellar_strkey::Contract(CONTRACT).to_string(),
+            network_passphrase: network_passphrase.to_string(),
+        },
+        signer,
+    )
+    .unwrap();
+    (network, service)
+}
+
+fn in_memory_signer() -> Arc<dyn Signer> {
+    Arc::new(InMemorySigner::from_seed(SOURCE_SEED).unwrap())
+}
+
+/// Remote signing service holding `SOURCE_SEED`; with `forge` set it signs
+/// with a different key
+#[derive(Clone)]
+struct SigningService {
+    forge: Arc<Mutex<bool>>,
+    requests: Arc<Mutex<Vec<Value>>>,
+}
+
+async fn sign_remotely(
+    State(service): State<SigningService>,
+    headers: HeaderMap,
+    Json(request): Json<Value>,
+) -> Result<Json<Value>, StatusCode> {
+    if headers.get("authorization").and_then(|v| v.to_str().ok()) != Some("Bearer s3cret") {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    service.requests.lock().unwrap().push(request.clone());
+
+    let seed = if *service.forge.lock().unwrap() {
+        [1u8; 32]
+    } else {
+        SOURCE_SEED
+    };
+    let hash: [u8; 32] = hex::decode(request["hash"].as_str().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let signature = InMemorySigner::from_seed(seed)
+        .unwrap()
+        .sign(&hash)
+        .await
+        .unwrap();
+    Ok(Json(json!({ "signature": hex::encode(signature) })))
+}
+
+async fn start_signing_service(service: SigningService) -> String {
+    let app = Router::new()
+        .route("/sign", post(sign_remotely))
+        .with_state(service);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}/sign", addr)
+}
+
+#[tokio::test]
+async fn test_snapshot_roots_are_submitted_as_signed_transactions() {
+    let (network, service) = setup(NETWORK, in_memory_signer()).await;
+    assert_eq!(service.get_snapshot_root(3).await.unwrap(), None);
+
+    let result = service.submit_snapshot([1u8; 32], 3).await.unwrap();
+    assert_eq!(result.epoch, 3);
+    assert_eq!(result.ledger, LEDGER);
+    assert_eq!(result.timestamp, LEDGER_TIMESTAMP);
+    assert_eq!(result.transaction_hash.len(), 64);
+    assert_eq!(service.get_snapshot_root(3).await.unwrap(), Some([1u8; 32]));
+    assert!(service
+        .verify_snapshot_exists(&hex::encode([1u8; 32]), 3)
+        .await
+        .unwrap());
+    assert!(!service
+        .verify_snapshot_exists(&hex::encode([2u8; 32]), 3)
+        .await
+        .unwrap());
+
+    // The next submission uses the next sequence number
+    service.submit_snapshot([2u8; 32], 4).await.unwrap();
+    let network = network.lock().unwrap();
+    assert_eq!(network.transactions.len(), 2);
+    assert!(network.rejected.is_empty());
+    assert_eq!(network.roots[&4], vec![2u8; 32]);
+    assert_eq!(network.accounts.values().next(), Some(&((7 << 32) + 2)));
+}
+
+#[tokio::test]
+async fn test_transactions_signed_for_another_network_are_rejected() {
+    let (network, service) = setup("Test SDF Network ; September 2015", in_memory_signer()).await;
+
+    let error = service.submit_snapshot([1u8; 32], 3).await.unwrap_err();
+    assert!(format!("{:#}", error).contains("txBAD_AUTH"));
+    let network = network.lock().unwrap();
+    assert!(network.roots.is_empty());
+    assert_eq!(network.rejected, vec!["txBAD_AUTH"; 3]);
+}
+
+#[tokio::test]
+async fn test_submissions_signed_by_a_remote_signer() {
+    let signing_service = SigningService {
+        forge: Arc::default(),
+        requests: Arc::default(),
+    };
+    let config = RemoteSignerConfig {
+        url: start_signing_service(signing_service.clone()).await,
+        account: in_memory_signer().account(),
+        token: Some("s3cret".to_string()),
+        timeout_secs: 5,
+    };
+    let signer = Arc::new(RemoteSigner::new(config.clone()).unwrap());
+    let (network, service) = setup(NETWORK, signer.clone()).await;
+
+    service.submit_snapshot([1u8; 32], 3).await.unwrap();
+    assert_eq!(network.lock().unwrap().roots[&3], vec![1u8; 32]);
+    let requests = signing_service.requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["account"], config.account.as_str());
+
+    // Signatures by other keys are caught before they are submitted
+    *signing_service.forge.lock().unwrap() = true;
+    let error = signer.sign(&[2u8; 32]).await.unwrap_err();
+    assert!(error.to_string().contains("invalid signature"));
+
+    let unauthorized = RemoteSigner::new(RemoteSignerConfig {
+        token: None,
+        ..config
+    })
+    .unwrap();
+    let error = unauthorized.sign(&[2u8; 32]).await.unwrap_err();
+    assert!(error.to_string().contains("401"));
+}
+
+#[tokio::test]
+async fn test_submissions_signed_with_a_keystore() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("source.json");
+    let mut child = tokio::process::Command::new(env!("CARGO_BIN_EXE_create_keystore"))
+        .arg(&path)
+        .args(["--iterations", "1000"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let secret = stellar_strkey::ed25519::PrivateKey(SOURCE_SEED).to_string();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(format!("{}\ncorrect horse\n", secret).as_bytes())
+        .await
+        .unwrap();
+    let output = child.wait_with_output().await.unwrap();
+    assert!(output.status.success());
+    let account = in_memory_signer().account();
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), account);
+    assert!(!std::fs::read_to_string(&path).unwrap().contains(&secret));
+
+    assert!(KeystoreSigner::open(&path, "battery staple").is_err());
+    let signer = Arc::new(KeystoreSigner::open(&path, "correct horse").unwrap());
+    let (network, service) = setup(NETWORK, signer).await;
+    service.submit_snapshot([3u8; 32], 5).await.unwrap();
+    assert_eq!(network.lock().unwrap().roots[&5], vec![3u8; 32]);
+}
@@ -0,0 +1,134 @@
+use anyhow::Result;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use stellar_insights_backend::rpc::{Payment, StellarRpcClient};
+use stellar_insights_backend::services::fx_spread::{FxSpreadService, SpreadInterval};
+use stellar_insights_backend::services::price_feed::{
+    PriceFeedClient, PriceFeedConfig, PriceFeedProvider,
+};
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+/// Seeded anchor issuing USDC
+const CIRCLE_ANCHOR_ID: &str = "c1b1f1a1-1111-4111-a111-111111111111";
+
+/// Provider returning fixed USD prices
+struct FixedPrices(HashMap<String, f64>);
+
+#[async_trait::async_trait]
+impl PriceFeedProvider for FixedPrices {
+    async fn fetch_price(&self, asset_id: &str) -> Result<f64> {
+        self.0
+            .get(asset_id)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("no price for {}", asset_id))
+    }
+
+    async fn fetch_prices(&self, asset_ids: &[String]) -> Result<HashMap<String, f64>> {
+        Ok(asset_ids
+            .iter()
+            .filter_map(|id| self.0.get(id).map(|p| (id.clone(), *p)))
+            .collect())
+    }
+
+    fn name(&self) -> &str {
+        "fixed"
+    }
+}
+
+fn service(pool: SqlitePool) -> FxSpreadService {
+    let provider = Arc::new(FixedPrices(HashMap::from([
+        ("usd-coin".to_string(), 1.0),
+        ("stellar".to_string(), 0.10),
+    ])));
+    let mapping = HashMap::from([
+        (format!("USDC:{}", USDC_ISSUER), "usd-coin".to_string()),
+        ("XLM:native".to_string(), "stellar".to_string()),
+    ]);
+    let price_feed = Arc::new(PriceFeedClient::with_provider(
+        provider,
+        PriceFeedConfig::default(),
+        mapping,
+    ));
+
+    FxSpreadService::new(
+        pool,
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+        price_feed,
+    )
+}
+
+fn usdc_to_xlm(id: &str, source_amount: f64, amount: f64, created_at: &str) -> Payment {
+    Payment {
+        id: id.to_string(),
+        paging_token: id.to_string(),
+        transaction_hash: format!("tx_{}", id),
+        source_account: "GSOURCE".to_string(),
+        destination: "GDEST".to_string(),
+        asset_type: "native".to_string(),
+        asset_code: None,
+        asset_issuer: None,
+        amount: amount.to_string(),
+        created_at: created_at.to_string(),
+        operation_type: Some("path_payment_strict_send".to_string()),
+        source_asset_type: Some("credit_alphanum4".to_string()),
+        source_asset_code: Some("USDC".to_string()),
+        source_asset_issuer: Some(USDC_ISSUER.to_string()),
+        source_amount: Some(source_amount.to_string()),
+        from: None,
+        to: None,
+    }
+}
+
+#[sqlx::test]
+async fn test_corridor_spread_series(pool: SqlitePool) {
+    let service = service(pool);
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+    let payments = vec![
+        // 1% markup on $100, 3% markup on $300
+        usdc_to_xlm("op1", 100.0, 990.0, &format!("{}T00:05:00Z", today)),
+        usdc_to_xlm("op2", 300.0, 2910.0, &format!("{}T00:45:00Z", today)),
+    ];
+    assert_eq!(service.record_payments(&payments).await.unwrap(), 2);
+    // Replaying the same payments does not double count
+    assert_eq!(service.record_payments(&payments).await.unwrap(), 0);
+
+    let corridor_key = format!("USDC:{}->XLM:native", USDC_ISSUER).parse().unwrap();
+    let since = chrono::Utc::now() - chrono::Duration::days(2);
+    let points = service
+        .get_corridor_spreads(&corridor_key, since, SpreadInterval::Day)
+        .await
+        .unwrap();
+
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].sample_count, 2);
+    assert!((points[0].volume_usd - 400.0).abs() < 1e-6);
+    assert!((points[0].avg_spread_bps - 250.0).abs() < 1e-6);
+    assert!((points[0].min_spread_bps - 100.0).abs() < 1e-6);
+    assert!((points[0].max_spread_bps - 300.0).abs() < 1e-6);
+}
+
+#[sqlx::test]
+async fn test_anchor_spreads_follow_issued_assets(pool: SqlitePool) {
+    let service = service(pool);
+    let now = chrono::Utc::now().to_rfc3339();
+    service
+        .record_payments(&[usdc_to_xlm("op1", 100.0, 990.0, &now)])
+        .await
+        .unwrap();
+
+    let since = chrono::Utc::now() - chrono::Duration::days(1);
+    let points = service
+        .get_anchor_spreads(CIRCLE_ANCHOR_ID, since, SpreadInterval::Hour)
+        .await
+        .unwrap();
+    assert_eq!(points.len(), 1);
+    assert!((points[0].avg_spread_bps - 100.0).abs() < 1e-6);
+
+    assert!(service
+        .get_anchor_spreads("unknown", since, SpreadInterval::Hour)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,50 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::models::asset::AssetId;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
+use stellar_insights_backend::services::quote_simulator::QuoteSimulator;
+
+const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+const EURC: &str = "EURC:GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+
+#[sqlx::test]
+async fn test_quote_from_order_book_only(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let simulator = QuoteSimulator::new(pool, rpc_client);
+
+    let from = USDC.parse::<AssetId>().unwrap();
+    let to = "native".parse::<AssetId>().unwrap();
+
+    let quote = simulator.quote(&from, &to, 500.0, 1.0).await.unwrap();
+
+    assert!(quote.pool_ids.is_empty());
+    assert!(quote.order_book_levels > 0);
+    assert!(quote.fill.fully_filled);
+    assert!(quote.fill.amount_out > 0.0);
+    assert_eq!(quote.slippage_curve.len(), 7);
+    // Slippage never improves as size grows
+    assert!(quote
+        .slippage_curve
+        .windows(2)
+        .all(|w| w[1].slippage_percent >= w[0].slippage_percent - 1e-9));
+    assert!(quote.max_size_within_tolerance > 0.0);
+}
+
+#[sqlx::test]
+async fn test_quote_uses_synced_pools(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let analyzer = LiquidityPoolAnalyzer::new(pool.clone(), Arc::clone(&rpc_client));
+    analyzer.sync_pools().await.unwrap();
+
+    let simulator = QuoteSimulator::new(pool, rpc_client);
+    let from = USDC.parse::<AssetId>().unwrap();
+    let to = EURC.parse::<AssetId>().unwrap();
+
+    let quote = simulator.quote(&from, &to, 50_000.0, 1.0).await.unwrap();
+
+    assert!(!quote.pool_ids.is_empty());
+    assert!(quote.fill.pool_amount > 0.0);
+    assert!(simulator.quote(&from, &to, -1.0, 1.0).await.is_err());
+    assert!(simulator.quote(&from, &to, 10.0, 150.0).await.is_err());
+}
@@ -0,0 +1,209 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::PaymentRecord;
+use stellar_insights_backend::services::aggregation::{AggregationConfig, AggregationService};
+use stellar_insights_backend::services::rollup::{
+    Resolution, RollupConfig, RollupEngine, RollupEntity,
+};
+
+const CIRCLE_ANCHOR_ID: &str = "c1b1f1a1-1111-4111-a111-111111111111";
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+fn usdc_payment(amount: f64, created_at: chrono::DateTime<Utc>) -> PaymentRecord {
+    PaymentRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        transaction_hash: format!("tx-{}", amount),
+        source_account: "GSOURCE".to_string(),
+        destination_account: "GDEST".to_string(),
+        asset_type: "credit_alphanum4".to_string(),
+        asset_code: Some("USDC".to_string()),
+        asset_issuer: Some(USDC_ISSUER.to_string()),
+        amount,
+        created_at,
+    }
+}
+
+#[sqlx::test]
+async fn test_rollups_agree_across_resolutions(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let now = Utc::now();
+    db.save_payments(vec![
+        usdc_payment(100.0, now - Duration::minutes(40)),
+        usdc_payment(50.0, now - Duration::minutes(2)),
+        usdc_payment(25.0, now - Duration::minutes(1)),
+    ])
+    .await
+    .unwrap();
+
+    let engine = RollupEngine::new(Arc::clone(&db), RollupConfig::default());
+    engine.run_rollup(now).await.unwrap();
+
+    let corridor = format!("USDC:{}->USDC:{}", USDC_ISSUER, USDC_ISSUER);
+    let start = now - Duration::hours(2);
+    for resolution in Resolution::ALL {
+        let series = engine
+            .query(
+                RollupEntity::Corridor,
+                &corridor,
+                start,
+                now,
+                Some(resolution),
+                None,
+            )
+            .await
+            .unwrap();
+        let total: i64 = series.buckets.iter().map(|b| b.total_transactions).sum();
+        let volume: f64 = series.buckets.iter().map(|b| b.volume_usd).sum();
+        assert_eq!(total, 3, "resolution {}", resolution.as_str());
+        assert_eq!(volume, 175.0, "resolution {}", resolution.as_str());
+    }
+
+    // Payments in an anchor-issued asset roll up to the anchor and the asset
+    let anchor = engine
+        .query(
+            RollupEntity::Anchor,
+            CIRCLE_ANCHOR_ID,
+            start,
+            now,
+            Some(Resolution::Day),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(anchor.buckets[0].volume_usd, 175.0);
+
+    let asset = engine
+        .query(
+            RollupEntity::Asset,
+            &format!("USDC:{}", USDC_ISSUER),
+            start,
+            now,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(asset.resolution, Resolution::FiveMinutes);
+    assert!(asset.buckets.len() >= 2);
+
+    // Re-running over the same window rewrites buckets instead of double counting
+    engine.run_rollup(now).await.unwrap();
+    let hourly = engine
+        .query(
+            RollupEntity::Corridor,
+            &corridor,
+            start,
+            now,
+            Some(Resolution::Hour),
+            None,
+        )
+        .await
+        .unwrap();
+    let total: i64 = hourly.buckets.iter().map(|b| b.total_transactions).sum();
+    assert_eq!(total, 3);
+}
+
+#[sqlx::test]
+async fn test_retention_prunes_fine_resolutions_first(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let now = Utc::now();
+    db.save_payments(vec![usdc_payment(10.0, now - Duration::minutes(5))])
+        .await
+        .unwrap();
+
+    let engine = RollupEngine::new(Arc::clone(&db), RollupConfig::default());
+    engine.run_rollup(now).await.unwrap();
+
+    // Three days later only the 5-minute buckets have expired
+    let later = now + Duration::days(3);
+    let deleted = engine.apply_retention(later).await.unwrap();
+    assert_eq!(deleted, 3); // corridor, asset and anchor
+
+    let remaining: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT resolution, COUNT(*) FROM metric_rollups GROUP BY resolution ORDER BY resolution",
+    )
+    .fetch_all(db.pool())
+    .await
+    .unwrap();
+    assert_eq!(
+        remaining,
+        vec![
+            ("1d".to_string(), 3),
+            ("1h".to_string(), 3),
+            ("1w".to_string(), 3)
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn test_first_run_backfills_history(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let now = Utc::now();
+    db.save_payments(vec![
+        usdc_payment(40.0, now - Duration::days(3)),
+        usdc_payment(2.0, now - Duration::minutes(10)),
+    ])
+    .await
+    .unwrap();
+
+    let engine = RollupEngine::new(Arc::clone(&db), RollupConfig::default());
+    engine.run_rollup(now).await.unwrap();
+
+    let corridor = format!("USDC:{}->USDC:{}", USDC_ISSUER, USDC_ISSUER);
+    let daily = engine
+        .query(
+            RollupEntity::Corridor,
+            &corridor,
+            now - Duration::days(7),
+            now,
+            Some(Resolution::Day),
+            None,
+        )
+        .await
+        .unwrap();
+    let volume: f64 = daily.buckets.iter().map(|b| b.volume_usd).sum();
+    assert_eq!(volume, 42.0);
+}
+
+#[sqlx::test]
+async fn test_aggregation_copies_corridor_rollups(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let now = Utc::now();
+    db.save_payments(vec![
+        usdc_payment(10.0, now - Duration::minutes(20)),
+        usdc_payment(5.0, now - Duration::minutes(15)),
+    ])
+    .await
+    .unwrap();
+
+    let engine = Arc::new(RollupEngine::new(Arc::clone(&db), RollupConfig::default()));
+    engine.run_rollup(now).await.unwrap();
+
+    let aggregation = AggregationService::new(
+        Arc::clone(&db),
+        Arc::clone(&engine),
+        AggregationConfig::default(),
+    );
+    // Running twice over the same window replaces rather than adds
+    aggregation.run_hourly_aggregation().await.unwrap();
+    aggregation.run_hourly_aggregation().await.unwrap();
+
+    let hourly = db
+        .fetch_hourly_metrics_by_timerange(now - Duration::hours(2), now)
+        .await
+        .unwrap();
+    let total: i64 = hourly.iter().map(|m| m.total_transactions).sum();
+    let volume: f64 = hourly.iter().map(|m| m.volume_usd).sum();
+    assert_eq!(total, 2);
+    assert_eq!(volume, 15.0);
+
+    let daily: Vec<(i64, f64)> =
+        sqlx::query_as("SELECT total_transactions, volume_usd FROM corridor_metrics")
+            .fetch_all(db.pool())
+            .await
+            .unwrap();
+    let total: i64 = daily.iter().map(|(total, _)| total).sum();
+    assert_eq!(total, 2);
+}
@@ -0,0 +1,77 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::models::asset::AssetId;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
+use stellar_insights_backend::services::route_finder::{RouteFinder, RouteMode, RouteSource};
+
+const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+const EURC: &str = "EURC:GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+
+#[sqlx::test]
+async fn test_routes_from_horizon_paths(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let finder = RouteFinder::new(pool, rpc_client);
+
+    let from = "native".parse::<AssetId>().unwrap();
+    let to = EURC.parse::<AssetId>().unwrap();
+
+    let routes = finder
+        .find_routes(&from, &to, 100.0, RouteMode::StrictSend)
+        .await
+        .unwrap();
+
+    // Mock Horizon returns three paths and no pools are synced yet
+    assert_eq!(routes.len(), 3);
+    assert_eq!(routes[0].rank, 1);
+    assert!(routes[0].score >= routes[1].score);
+    assert!(routes
+        .iter()
+        .all(|r| r.source == RouteSource::HorizonPath && r.source_amount == 100.0));
+}
+
+#[sqlx::test]
+async fn test_routes_include_synced_pools(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let analyzer = LiquidityPoolAnalyzer::new(pool.clone(), Arc::clone(&rpc_client));
+    analyzer.sync_pools().await.unwrap();
+
+    let finder = RouteFinder::new(pool, rpc_client);
+    let from = USDC.parse::<AssetId>().unwrap();
+    let to = EURC.parse::<AssetId>().unwrap();
+
+    let routes = finder
+        .find_routes(&from, &to, 1000.0, RouteMode::StrictSend)
+        .await
+        .unwrap();
+
+    let pool_route = routes
+        .iter()
+        .find(|r| r.source == RouteSource::LiquidityPool)
+        .expect("USDC/EURC pool should produce a direct route");
+    assert_eq!(pool_route.hop_count, 0);
+    assert!(pool_route.pool_id.is_some());
+    assert!(pool_route.liquidity_depth_usd > 0.0);
+    assert!(pool_route.destination_amount > 0.0);
+}
+
+#[sqlx::test]
+async fn test_strict_receive_fixes_destination_amount(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let finder = RouteFinder::new(pool, rpc_client);
+
+    let from = "native".parse::<AssetId>().unwrap();
+    let to = USDC.parse::<AssetId>().unwrap();
+
+    let routes = finder
+        .find_routes(&from, &to, 250.0, RouteMode::StrictReceive)
+        .await
+        .unwrap();
+
+    assert!(!routes.is_empty());
+    assert!(routes.iter().all(|r| r.destination_amount == 250.0));
+    assert!(finder
+        .find_routes(&from, &to, 0.0, RouteMode::StrictReceive)
+        .await
+        .is_err());
+}
@@ -0,0 +1,342 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{HeaderMap, Request, StatusCode},
+    routing::get,
+    Extension, Json, Router,
+};
+use base64::Engine;
+use chrono::Utc;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use stellar_insights_backend::auth_middleware::AuthUser;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::anchor_registry::AnchorRegistry;
+use stellar_insights_backend::services::sep10_client::{Sep10Client, Sep10ClientConfig};
+use stellar_insights_backend::services::signer::{is_signed_by, transaction_hash, Keypair};
+use stellar_insights_backend::services::stellar_toml_crawler::{
+    StellarTomlCrawler, StellarTomlCrawlerConfig,
+};
+use stellar_insights_backend::services::transfer_tracker::{
+    TransferTracker, TransferTrackerConfig,
+};
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+use tower::util::ServiceExt;
+
+const NETWORK: &str = "Test SDF Network ; September 2015";
+
+fn keypair(seed: u8) -> Keypair {
+    Keypair::from_secret(&secret(seed)).unwrap()
+}
+
+fn secret(seed: u8) -> String {
+    stellar_strkey::ed25519::PrivateKey([seed; 32]).to_string()
+}
+
+struct Anchor {
+    domain: String,
+    server: Keypair,
+    challenges: AtomicUsize,
+    /// Issue challenges for another home domain
+    impersonate: AtomicBool,
+}
+
+fn data_op(source: [u8; 32], name: &str, value: &[u8]) -> xdr::Operation {
+    xdr::Operation {
+        source_account: Some(xdr::MuxedAccount::Ed25519(xdr::Uint256(source))),
+        body: xdr::OperationBody::ManageData(xdr::ManageDataOp {
+            data_name: xdr::String64(name.try_into().unwrap()),
+            data_value: Some(xdr::DataValue(value.to_vec().try_into().unwrap())),
+        }),
+    }
+}
+
+async fn serve_toml(State(anchor): State<Arc<Anchor>>) -> String {
+    format!(
+        "NETWORK_PASSPHRASE = \"{}\"\nSIGNING_KEY = \"{}\"\nWEB_AUTH_ENDPOINT = \"http://{domain}/auth\"\nTRANSFER_SERVER_SEP0024 = \"http://{domain}/sep24\"\n",
+        NETWORK,
+        anchor.server.account(),
+        domain = anchor.domain
+    )
+}
+
+async fn serve_challenge(
+    State(anchor): State<Arc<Anchor>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<Value> {
+    anchor.challenges.fetch_add(1, Ordering::SeqCst);
+    let client = stellar_strkey::ed25519::PublicKey::from_string(&params["account"])
+        .unwrap()
+        .0;
+    let home_domain = if anchor.impersonate.load(Ordering::SeqCst) {
+        "bank.example"
+    } else {
+        params["home_domain"].as_str()
+    };
+    let now = Utc::now().timestamp() as u64;
+    let tx = xdr::Transaction {
+        source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(anchor.server.public_key())),
+        fee: 200,
+        seq_num: xdr::SequenceNumber(0),
+        cond: xdr::Preconditions::Time(xdr::TimeBounds {
+            min_time: xdr::TimePoint(now),
+            max_time: xdr::TimePoint(now + 900),
+        }),
+        memo: xdr::Memo::None,
+        operations: vec![
+            data_op(client, &format!("{} auth", home_domain), &[b'a'; 64]),
+            data_op(
+                anchor.server.public_key(),
+                "web_auth_domain",
+                anchor.domain.as_bytes(),
+            ),
+        ]
+        .try_into()
+        .unwrap(),
+        ext: xdr::TransactionExt::V0,
+    };
+    let signature = anchor
+        .server
+        .sign_decorated(&transaction_hash(&tx, NETWORK).unwrap());
+    let envelope = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+        tx,
+        signatures: vec![signature].try_into().unwrap(),
+    });
+
+    Json(json!({
+        "transaction": base64::engine::general_purpose::STANDARD
+            .encode(envelope.to_xdr(xdr::Limits::none()).unwrap()),
+        "network_passphrase": NETWORK
+    }))
+}
+
+/// Issue a token when the challenge carries the client's signature
+async fn serve_token(Json(body): Json<Value>) -> Result<Json<Value>, StatusCode> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(body["transaction"].as_str().unwrap())
+        .unwrap();
+    let xdr::TransactionEnvelope::Tx(envelope) =
+        xdr::TransactionEnvelope::from_xdr(bytes, xdr::Limits::none()).unwrap()
+    else {
+        return Err(StatusCode::BAD_REQUEST);
+    };
+    let Some(xdr::MuxedAccount::Ed25519(xdr::Uint256(client))) =
+        envelope.tx.operations[0].source_account.clone()
+    else {
+        return Err(StatusCode::BAD_REQUEST);
+    };
+    let hash = transaction_hash(&envelope.tx, NETWORK).unwrap();
+    if !is_signed_by(&client, &hash, &envelope.signatures) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let encode =
+        |claims: Value| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(claims.to_string());
+    let account = stellar_strkey::ed25519::PublicKey(client).to_string();
+    Ok(Json(json!({
+        "token": format!(
+            "{}.{}.sig",
+            encode(json!({ "alg": "HS256" })),
+            encode(json!({ "sub": account, "exp": Utc::now().timestamp() + 3600 }))
+        )
+    })))
+}
+
+/// SEP-24 transaction lookup echoing the bearer token it was called with
+async fn serve_transaction(headers: HeaderMap) -> Json<Value> {
+    Json(json!({
+        "authorization": headers
+            .get("authorization")
+            .map(|value| value.to_str().unwrap().to_string())
+    }))
+}
+
+async fn start_anchor() -> Arc<Anchor> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let anchor = Arc::new(Anchor {
+        domain: listener.local_addr().unwrap().to_string(),
+        server: keypair(1),
+        challenges: AtomicUsize::new(0),
+        impersonate: AtomicBool::new(false),
+    });
+    let app = Router::new()
+        .route("/.well-known/stellar.toml", get(serve_toml))
+        .route("/auth", get(serve_challenge).post(serve_token))
+        .route("/sep24/transaction", get(serve_transaction))
+        .with_state(Arc::clone(&anchor));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    anchor
+}
+
+fn sep10_client(pool: SqlitePool, signing_key: Option<Keypair>) -> Arc<Sep10Client> {
+    let db = Arc::new(Database::new(pool));
+    let rpc_client = Arc::new(StellarRpcClient::new(
+        "http://127.0.0.1:1".to_string(),
+        "http://127.0.0.1:1".to_string(),
+        true,
+    ));
+    let crawler = Arc::new(
+        StellarTomlCrawler::new(
+            db,
+            rpc_client,
+            StellarTomlCrawlerConfig {
+                scheme: "http".to_string(),
+                allow_private_hosts: true,
+                ..StellarTomlCrawlerConfig::default()
+            },
+        )
+        .unwrap(),
+    );
+    Arc::new(Sep10Client::new(
+        crawler,
+        Sep10ClientConfig {
+            network_passphrase: NETWORK.to_string(),
+            ..Sep10ClientConfig::default()
+        },
+        signing_key,
+    ))
+}
+
+async fn call(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_sep10_tokens_are_obtained_and_cached(pool: SqlitePool) {
+    let anchor = start_anchor().await;
+    let client = sep10_client(pool, Some(keypair(2)));
+
+    let token = client.token(&anchor.domain, None).await.unwrap();
+    assert_eq!(token.account, keypair(2).account());
+    assert_eq!(token.home_domain, anchor.domain);
+    assert!(token.expires_at > Utc::now() + chrono::Duration::minutes(55));
+
+    // Reused until it nears expiry, separately per account
+    assert_eq!(client.token(&anchor.domain, None).await.unwrap(), token);
+    assert_eq!(anchor.challenges.load(Ordering::SeqCst), 1);
+    let other = client
+        .token(&anchor.domain, Some(&keypair(3)))
+        .await
+        .unwrap();
+    assert_eq!(other.account, keypair(3).account());
+    assert_eq!(anchor.challenges.load(Ordering::SeqCst), 2);
+
+    anchor.impersonate.store(true, Ordering::SeqCst);
+    let error = client
+        .authenticate(&anchor.domain, &keypair(2))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains(&format!(
+            "challenge is not for home domain {}",
+            anchor.domain
+        )),
+        "{}",
+        error
+    );
+}
+
+#[sqlx::test]
+async fn test_proxies_authenticate_with_configured_key(pool: SqlitePool) {
+    let anchor = start_anchor().await;
+    let client = sep10_client(pool.clone(), Some(keypair(2)));
+    let db = Arc::new(Database::new(pool));
+    let tracker = Arc::new(TransferTracker::new(
+        Arc::clone(&db),
+        Arc::clone(&client),
+        TransferTrackerConfig::default(),
+    ));
+    let registry = Arc::new(AnchorRegistry::new(db));
+
+    let proxy = || {
+        stellar_insights_backend::api::sep24_proxy::routes(
+            Arc::clone(&client),
+            Arc::clone(&tracker),
+            Arc::clone(&registry),
+        )
+    };
+    let signed_in = || {
+        proxy().layer(Extension(AuthUser {
+            user_id: "user".to_string(),
+            username: "user".to_string(),
+        }))
+    };
+    let uri = format!(
+        "/api/sep24/transaction?transfer_server=http://{domain}/sep24&id=1&home_domain={domain}",
+        domain = anchor.domain
+    );
+
+    // The backend's key is only used for signed-in callers
+    let (status, _) = call(
+        proxy(),
+        Request::builder().uri(&uri).body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, body) = call(
+        signed_in(),
+        Request::builder().uri(&uri).body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let token = client.token(&anchor.domain, None).await.unwrap();
+    assert_eq!(body["authorization"], format!("Bearer {}", token.token));
+
+    // A token is only obtained for the domain publishing the transfer server
+    let uri = format!(
+        "/api/sep24/transaction?transfer_server=http://{}/sep24&id=1&home_domain=other.example",
+        anchor.domain
+    );
+    let (status, _) = call(
+        signed_in(),
+        Request::builder().uri(uri).body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A caller's own JWT is forwarded untouched
+    let uri = format!(
+        "/api/sep24/transaction?transfer_server=http://{domain}/sep24&id=1&home_domain={domain}&jwt=mine",
+        domain = anchor.domain
+    );
+    let (_, body) = call(
+        proxy(),
+        Request::builder().uri(uri).body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(body["authorization"], "Bearer mine");
+
+    let token_request = |secret_key: String| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/sep10/token")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({ "home_domain": anchor.domain, "secret_key": secret_key }).to_string(),
+            ))
+            .unwrap()
+    };
+    let (status, body) = call(
+        stellar_insights_backend::api::sep10::protected_routes(Arc::clone(&client)),
+        token_request(secret(4)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["account"], keypair(4).account());
+    let (status, _) = call(
+        stellar_insights_backend::api::sep10::protected_routes(Arc::clone(&client)),
+        token_request(keypair(4).account().to_string()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
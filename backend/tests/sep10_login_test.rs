@@ -0,0 +1,213 @@
+use axum::{
+    body::Body,
+    extract::Path,
+    http::{Request, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use base64::Engine;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use stellar_insights_backend::auth::AuthService;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::sep10_server::{Sep10Server, Sep10ServerConfig};
+use stellar_insights_backend::services::signer::{transaction_hash, Keypair};
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+use tower::util::ServiceExt;
+
+const NETWORK: &str = "Test SDF Network ; September 2015";
+
+fn keypair(seed: u8) -> Keypair {
+    Keypair::from_secret(&stellar_strkey::ed25519::PrivateKey([seed; 32]).to_string()).unwrap()
+}
+
+/// Horizon knowing one multisig account: its master key was disabled and two
+/// cosigners of weight 1 and 2 must both sign for the medium threshold of 3
+async fn serve_account(Path(id): Path<String>) -> Result<Json<Value>, StatusCode> {
+    if id != keypair(2).account() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(json!({
+        "account_id": id,
+        "sequence": "1",
+        "thresholds": { "low_threshold": 1, "med_threshold": 3, "high_threshold": 3 },
+        "signers": [
+            { "key": keypair(3).account(), "weight": 1, "type": "ed25519_public_key" },
+            { "key": keypair(4).account(), "weight": 2, "type": "ed25519_public_key" },
+            { "key": id, "weight": 0, "type": "ed25519_public_key" }
+        ]
+    })))
+}
+
+async fn app() -> (Router, Arc<AuthService>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let horizon = format!("http://{}", listener.local_addr().unwrap());
+    let horizon_app = Router::new().route("/accounts/:id", get(serve_account));
+    tokio::spawn(async move { axum::serve(listener, horizon_app).await.unwrap() });
+
+    // keypair(6) proves control of its account but is not allowed in
+    let auth_service = Arc::new(
+        AuthService::new(Arc::new(tokio::sync::RwLock::new(None))).with_sep10_accounts([
+            keypair(2).account().to_string(),
+            keypair(5).account().to_string(),
+        ]),
+    );
+    let server = Arc::new(Sep10Server::new(
+        keypair(1),
+        Arc::new(StellarRpcClient::new(
+            "http://127.0.0.1:1".to_string(),
+            horizon,
+            false,
+        )),
+        Sep10ServerConfig {
+            network_passphrase: NETWORK.to_string(),
+            home_domain: "insights.example".to_string(),
+            web_auth_domain: "insights.example".to_string(),
+            ..Sep10ServerConfig::default()
+        },
+    ));
+    let app = stellar_insights_backend::api::auth::sep10_routes(Arc::clone(&auth_service), server)
+        .merge(stellar_insights_backend::api::auth::routes(Arc::clone(
+            &auth_service,
+        )));
+    (app, auth_service)
+}
+
+async fn call(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+/// Fetch a challenge for `account`, sign it with `keys` and submit it
+async fn sign_in(app: &Router, account: &str, keys: &[Keypair]) -> (StatusCode, Value) {
+    let (status, challenge) = call(
+        app,
+        Request::builder()
+            .uri(format!("/api/auth/sep10?account={}", account))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(challenge["network_passphrase"], NETWORK);
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(challenge["transaction"].as_str().unwrap())
+        .unwrap();
+    let xdr::TransactionEnvelope::Tx(mut envelope) =
+        xdr::TransactionEnvelope::from_xdr(bytes, xdr::Limits::none()).unwrap()
+    else {
+        panic!("challenge is not a v1 envelope");
+    };
+    let hash = transaction_hash(&envelope.tx, NETWORK).unwrap();
+    let mut signatures = envelope.signatures.to_vec();
+    signatures.extend(keys.iter().map(|key| key.sign_decorated(&hash)));
+    envelope.signatures = signatures.try_into().unwrap();
+    let signed = xdr::TransactionEnvelope::Tx(envelope)
+        .to_xdr(xdr::Limits::none())
+        .unwrap();
+
+    call(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri("/api/auth/sep10")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({
+                    "transaction": base64::engine::general_purpose::STANDARD.encode(signed)
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_sep10_login_issues_api_tokens() {
+    let (app, auth_service) = app().await;
+
+    // An account that does not exist yet signs with its master key
+    let client = keypair(5);
+    let (status, body) = sign_in(&app, client.account(), &[keypair(5)]).await;
+    assert_eq!(status, StatusCode::OK);
+    let claims = auth_service
+        .validate_token(body["access_token"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(claims.sub, client.account());
+    assert_eq!(claims.token_type, "access");
+    assert_eq!(body["expires_in"], 3600);
+
+    // Its refresh token works like a password login's
+    let (status, refreshed) = call(
+        &app,
+        Request::builder()
+            .method("POST")
+            .uri("/api/auth/refresh")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({ "refresh_token": body["refresh_token"] }).to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let claims = auth_service
+        .validate_token(refreshed["access_token"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(claims.sub, client.account());
+
+    let (status, body) = sign_in(&app, client.account(), &[keypair(6)]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        body["error"],
+        "challenge has a signature that is not from an account signer"
+    );
+
+    // A valid signature from an account that is not allowed gets no tokens
+    let outsider = keypair(6);
+    let (status, body) = sign_in(&app, outsider.account(), &[keypair(6)]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "account is not allowed to sign in");
+    assert!(body.get("access_token").is_none());
+    assert!(auth_service
+        .login_account(outsider.account())
+        .await
+        .is_err());
+
+    let (status, _) = call(
+        &app,
+        Request::builder()
+            .uri("/api/auth/sep10?account=GNOTANACCOUNT")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_sep10_login_requires_multisig_threshold() {
+    let (app, auth_service) = app().await;
+    let account = keypair(2);
+
+    let (status, body) = sign_in(&app, account.account(), &[keypair(4)]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "signature weight 2 is below the threshold 3");
+
+    // The disabled master key does not count
+    let (status, _) = sign_in(&app, account.account(), &[keypair(2), keypair(4)]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, body) = sign_in(&app, account.account(), &[keypair(3), keypair(4)]).await;
+    assert_eq!(status, StatusCode::OK);
+    let claims = auth_service
+        .validate_token(body["access_token"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(claims.sub, account.account());
+}
@@ -0,0 +1,348 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, Request, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::quote_comparison::{QuoteComparer, QuoteComparisonRequest};
+use stellar_insights_backend::services::sep10_client::{Sep10Client, Sep10ClientConfig};
+use stellar_insights_backend::services::sep38_client::{Sep38Client, Sep38ClientConfig};
+use stellar_insights_backend::services::sep_endpoint_prober::{
+    SepEndpoint, SepEndpointProber, SepEndpointProberConfig,
+};
+use stellar_insights_backend::services::stellar_toml_crawler::{
+    StellarTomlCrawler, StellarTomlCrawlerConfig,
+};
+use tower::util::ServiceExt;
+
+const USD: &str = "iso4217:USD";
+const BRL: &str = "iso4217:BRL";
+
+/// A quote server exchanging USD for `buy_asset` at `rate`, charging a flat
+/// 1 USD fee
+struct QuoteServer {
+    buy_asset: &'static str,
+    rate: f64,
+    available: bool,
+}
+
+async fn serve_info(State(server): State<Arc<QuoteServer>>) -> Json<Value> {
+    Json(json!({
+        "assets": [
+            { "asset": USD },
+            { "asset": server.buy_asset, "country_codes": ["BRA"] }
+        ]
+    }))
+}
+
+fn priced(server: &QuoteServer, sell_amount: &str) -> Value {
+    let sell: f64 = sell_amount.parse().unwrap();
+    let buy = (sell - 1.0) * server.rate;
+    json!({
+        "total_price": format!("{:.6}", sell / buy),
+        "price": format!("{:.6}", 1.0 / server.rate),
+        "sell_amount": sell_amount,
+        "buy_amount": format!("{:.2}", buy),
+        "fee": { "total": "1.00", "asset": USD }
+    })
+}
+
+async fn serve_price(
+    State(server): State<Arc<QuoteServer>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    if !server.available {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "error": "pricing unavailable" })),
+        ));
+    }
+    assert_eq!(params["context"], "sep31");
+    assert_eq!(params["sell_asset"], USD);
+    Ok(Json(priced(&server, &params["sell_amount"])))
+}
+
+/// Firm quotes for authenticated clients, echoing the token as the quote id
+async fn serve_quote(
+    State(server): State<Arc<QuoteServer>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<Json<Value>, StatusCode> {
+    let token = headers
+        .get("authorization")
+        .ok_or(StatusCode::FORBIDDEN)?
+        .to_str()
+        .unwrap()
+        .trim_start_matches("Bearer ")
+        .to_string();
+    let mut quote = priced(&server, body["sell_amount"].as_str().unwrap());
+    quote["id"] = json!(token);
+    quote["expires_at"] = json!("2030-01-01T00:00:00Z");
+    quote["sell_asset"] = body["sell_asset"].clone();
+    quote["buy_asset"] = body["buy_asset"].clone();
+    Ok(Json(quote))
+}
+
+async fn serve_stored_quote(Path(id): Path<String>) -> Result<Json<Value>, StatusCode> {
+    if id != "q 1" {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(json!({
+        "id": id,
+        "expires_at": "2030-01-01T00:00:00Z",
+        "total_price": "0.2",
+        "price": "0.19",
+        "sell_asset": USD,
+        "sell_amount": "100",
+        "buy_asset": BRL,
+        "buy_amount": "500",
+        "fee": { "total": "5", "asset": USD, "details": [{ "name": "Service fee", "amount": "5" }] }
+    })))
+}
+
+async fn start_quote_server(buy_asset: &'static str, rate: f64, available: bool) -> String {
+    let server = Arc::new(QuoteServer {
+        buy_asset,
+        rate,
+        available,
+    });
+    let app = Router::new()
+        .route("/info", get(serve_info))
+        .route("/price", get(serve_price))
+        .route("/quote", axum::routing::post(serve_quote))
+        .route("/quote/:id", get(serve_stored_quote))
+        .with_state(server);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+async fn create_anchor(
+    db: &Database,
+    prober: &SepEndpointProber,
+    name: &str,
+    account: &str,
+    quote_server: &str,
+) {
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: name.to_string(),
+            stellar_account: account.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    assert!(prober
+        .set_endpoint(&anchor.id, SepEndpoint::Sep38, quote_server)
+        .await
+        .unwrap());
+}
+
+fn sep10_client(db: Arc<Database>) -> Arc<Sep10Client> {
+    let rpc_client = Arc::new(StellarRpcClient::new(
+        "http://127.0.0.1:1".to_string(),
+        "http://127.0.0.1:1".to_string(),
+        true,
+    ));
+    let crawler = Arc::new(
+        StellarTomlCrawler::new(db, rpc_client, StellarTomlCrawlerConfig::default()).unwrap(),
+    );
+    Arc::new(Sep10Client::new(
+        crawler,
+        Sep10ClientConfig::default(),
+        None,
+    ))
+}
+
+async fn call(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_compare_ranks_anchors_by_delivered_amount(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let prober = SepEndpointProber::new(Arc::clone(&db), SepEndpointProberConfig::default());
+    create_anchor(
+        &db,
+        &prober,
+        "Fair",
+        "GFAIR",
+        &start_quote_server(BRL, 5.0, true).await,
+    )
+    .await;
+    create_anchor(
+        &db,
+        &prober,
+        "Best",
+        "GBEST",
+        &start_quote_server(BRL, 5.2, true).await,
+    )
+    .await;
+    create_anchor(
+        &db,
+        &prober,
+        "Down",
+        "GDOWN",
+        &start_quote_server(BRL, 6.0, false).await,
+    )
+    .await;
+    create_anchor(
+        &db,
+        &prober,
+        "Elsewhere",
+        "GELSE",
+        &start_quote_server("iso4217:ARS", 900.0, true).await,
+    )
+    .await;
+
+    let comparer = Arc::new(QuoteComparer::new(
+        Arc::clone(&db),
+        Arc::new(Sep38Client::new(Sep38ClientConfig::default())),
+        sep10_client(Arc::clone(&db)),
+    ));
+    let app = stellar_insights_backend::api::quote_comparison::routes(Arc::clone(&comparer));
+
+    let (status, body) = call(
+        app.clone(),
+        Request::builder()
+            .uri("/api/sep38/compare?sell_asset=iso4217%3AUSD&buy_asset=iso4217%3ABRL&sell_amount=101")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["context"], "sep31");
+    let quotes = body["quotes"].as_array().unwrap();
+    let names: Vec<&str> = quotes
+        .iter()
+        .map(|q| q["anchor_name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Best", "Fair", "Down"]);
+    assert_eq!(quotes[0]["buy_amount"], "520.00");
+    assert_eq!(quotes[0]["fee_total"], "1.00");
+    assert_eq!(quotes[0]["fee_asset"], USD);
+    assert_eq!(quotes[1]["buy_amount"], "500.00");
+    assert_eq!(quotes[2]["error"], "/price: HTTP 503: pricing unavailable");
+
+    // Firm quotes need the anchor's home domain for SEP-10
+    let quotes = comparer
+        .compare(&QuoteComparisonRequest {
+            sell_asset: USD.to_string(),
+            buy_asset: BRL.to_string(),
+            sell_amount: "101".to_string(),
+            context: "sep31".to_string(),
+            firm: true,
+        })
+        .await
+        .unwrap();
+    assert_eq!(quotes.len(), 3);
+    assert!(quotes.iter().all(|q| q.firm && q.buy_amount.is_none()));
+    assert!(quotes
+        .iter()
+        .any(|q| q.error.as_deref() == Some("anchor has no home domain to authenticate with")));
+
+    let (status, _) = call(
+        app,
+        Request::builder()
+            .uri("/api/sep38/compare?sell_asset=iso4217%3AUSD&buy_asset=iso4217%3ABRL&sell_amount=101&context=sep12")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_sep38_proxy_forwards_typed_requests(pool: SqlitePool) {
+    let quote_server = start_quote_server(BRL, 5.0, true).await;
+    let app = stellar_insights_backend::api::sep38_proxy::routes(
+        Arc::new(Sep38Client::new(Sep38ClientConfig::default())),
+        sep10_client(Arc::new(Database::new(pool))),
+    );
+
+    let (status, body) = call(
+        app.clone(),
+        Request::builder()
+            .uri(format!("/api/sep38/info?quote_server={}", quote_server))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["assets"][1]["asset"], BRL);
+
+    let (status, body) = call(
+        app.clone(),
+        Request::builder()
+            .method("POST")
+            .uri("/api/sep38/quote")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({
+                    "quote_server": quote_server,
+                    "jwt": "token",
+                    "context": "sep31",
+                    "sell_asset": USD,
+                    "buy_asset": BRL,
+                    "sell_amount": "101"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], "token");
+    assert_eq!(body["buy_amount"], "500.00");
+
+    // The anchor's status reaches the caller
+    let (status, body) = call(
+        app.clone(),
+        Request::builder()
+            .method("POST")
+            .uri("/api/sep38/quote")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                json!({
+                    "quote_server": quote_server,
+                    "context": "sep31",
+                    "sell_asset": USD,
+                    "buy_asset": BRL,
+                    "sell_amount": "101"
+                })
+                .to_string(),
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "Forbidden");
+
+    let (status, body) = call(
+        app,
+        Request::builder()
+            .uri(format!(
+                "/api/sep38/quote/q%201?quote_server={}&jwt=token",
+                quote_server
+            ))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["fee"]["details"][0]["name"], "Service fee");
+}
@@ -0,0 +1,92 @@
+use axum::{
+    body::Body,
+    extract::Query,
+    http::{HeaderMap, Request, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use stellar_insights_backend::api::sep6_proxy::{get_deposit, get_withdraw_exchange, Sep6State};
+use tower::util::ServiceExt;
+
+/// Deposit instructions echoing the query and bearer token they were asked with
+async fn serve_deposit(
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    if !params.contains_key("email_address") {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "type": "non_interactive_customer_info_needed",
+                "fields": ["email_address"]
+            })),
+        ));
+    }
+    Ok(Json(json!({
+        "how": "Make a payment to account 123",
+        "params": params,
+        "authorization": headers
+            .get("authorization")
+            .map(|value| value.to_str().unwrap().to_string())
+    })))
+}
+
+async fn start_anchor() -> String {
+    let app = Router::new().route("/sep6/deposit", get(serve_deposit));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}/sep6", addr)
+}
+
+async fn get_json(uri: String) -> (StatusCode, Value) {
+    let app = Router::new()
+        .route("/api/sep6/deposit", get(get_deposit))
+        .route("/api/sep6/withdraw-exchange", get(get_withdraw_exchange))
+        .with_state(Sep6State::new());
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_sep6_proxy_forwards_query_and_anchor_errors() {
+    let transfer_server = start_anchor().await;
+
+    let (status, body) = get_json(format!(
+        "/api/sep6/deposit?transfer_server={}&jwt=token&asset_code=USDC&account=GABC&email_address=a%40b.example",
+        transfer_server
+    ))
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["authorization"], "Bearer token");
+    assert_eq!(
+        body["params"],
+        json!({ "asset_code": "USDC", "account": "GABC", "email_address": "a@b.example" })
+    );
+
+    // KYC requirements reach the caller with the anchor's status
+    let (status, body) = get_json(format!(
+        "/api/sep6/deposit?transfer_server={}/&asset_code=USDC&account=GABC",
+        transfer_server
+    ))
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["type"], "non_interactive_customer_info_needed");
+
+    let (status, body) = get_json(format!(
+        "/api/sep6/withdraw-exchange?transfer_server={}&source_asset=USDC",
+        "http://127.0.0.1:1/sep6"
+    ))
+    .await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(body["error"], "proxy");
+}
@@ -0,0 +1,164 @@
+use axum::{http::StatusCode, routing::get, Json, Router};
+use chrono::Utc;
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::services::sep_endpoint_prober::{
+    ProbeSummary, SepEndpoint, SepEndpointProber, SepEndpointProberConfig,
+};
+
+const ANCHOR_ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+
+/// Serve a healthy SEP-6 and SEP-24, a SEP-31 with an invalid `/info`
+/// response and a failing SEP-10 endpoint, returning the base URL
+async fn start_anchor_server() -> String {
+    let info = || async { Json(json!({ "deposit": {}, "withdraw": {} })) };
+    let app = Router::new()
+        .route("/sep6/info", get(info))
+        .route("/sep24/info", get(info))
+        .route("/sep31/info", get(|| async { Json(json!({ "send": {} })) }))
+        .route("/auth", get(|| async { StatusCode::INTERNAL_SERVER_ERROR }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+#[sqlx::test]
+async fn test_probe_records_uptime_and_downgrades_status(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let base = start_anchor_server().await;
+
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Example".to_string(),
+            stellar_account: ANCHOR_ACCOUNT.to_string(),
+            home_domain: Some("anchor.example".to_string()),
+        })
+        .await
+        .unwrap();
+    let anchor_uuid = anchor.id.parse().unwrap();
+    let anchor = db
+        .update_anchor_metrics(anchor_uuid, 1000, 995, 5, Some(2000), None)
+        .await
+        .unwrap();
+    assert_eq!(anchor.status, "green");
+
+    // SEP-6 and a stale SEP-24 come from stellar.toml
+    sqlx::query(
+        r#"
+        INSERT INTO anchor_stellar_toml (
+            anchor_id, home_domain, transfer_server, transfer_server_sep0024, last_fetched_at
+        )
+        VALUES ($1, 'anchor.example', $2, 'https://stale.example/sep24', $3)
+        "#,
+    )
+    .bind(&anchor.id)
+    .bind(format!("{}/sep6", base))
+    .bind(Utc::now())
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let prober = SepEndpointProber::new(Arc::clone(&db), SepEndpointProberConfig::default());
+    for (sep, path) in [
+        (SepEndpoint::Sep24, "/sep24/"),
+        (SepEndpoint::Sep31, "/sep31"),
+        (SepEndpoint::Sep10, "/auth"),
+    ] {
+        let url = format!("{}{}", base, path);
+        assert!(prober.set_endpoint(&anchor.id, sep, &url).await.unwrap());
+    }
+
+    let endpoints = prober.endpoints(&anchor.id).await.unwrap();
+    assert_eq!(
+        endpoints,
+        vec![
+            (SepEndpoint::Sep6, format!("{}/sep6", base)),
+            (SepEndpoint::Sep10, format!("{}/auth", base)),
+            (SepEndpoint::Sep24, format!("{}/sep24/", base)),
+            (SepEndpoint::Sep31, format!("{}/sep31", base)),
+        ]
+    );
+
+    // Seeded anchors have no endpoints and are not counted
+    let summary = prober.run_probes().await.unwrap();
+    assert_eq!(
+        summary,
+        ProbeSummary {
+            anchors: 1,
+            probes: 4,
+            unhealthy: 2,
+        }
+    );
+
+    let uptime = prober.get_uptime(&anchor.id, 24).await.unwrap();
+    assert_eq!(uptime.uptime_percent, Some(50.0));
+    let seps: Vec<&str> = uptime.endpoints.iter().map(|e| e.sep.as_str()).collect();
+    assert_eq!(seps, vec!["sep6", "sep10", "sep24", "sep31"]);
+
+    let sep10 = &uptime.endpoints[1];
+    assert_eq!(sep10.available_probes, 0);
+    assert_eq!(
+        sep10.url,
+        format!("{}/auth?account={}", base, ANCHOR_ACCOUNT)
+    );
+    let sep24 = &uptime.endpoints[2];
+    assert_eq!(sep24.url, format!("{}/sep24/info", base));
+    assert_eq!(sep24.uptime_percent, 100.0);
+    assert!(sep24.avg_latency_ms.is_some());
+    let sep31 = &uptime.endpoints[3];
+    assert_eq!(sep31.available_probes, 1);
+    assert_eq!(sep31.healthy_probes, 0);
+    assert_eq!(
+        sep31.last_error.as_deref(),
+        Some("missing `receive` object")
+    );
+
+    let probes = prober.get_probes(&anchor.id, 10).await.unwrap();
+    assert_eq!(probes.len(), 4);
+    let auth = probes.iter().find(|p| p.sep == "sep10").unwrap();
+    assert_eq!(auth.status_code, Some(500));
+    assert!(!auth.available);
+
+    // Healthy payments no longer keep an anchor with flaky endpoints green
+    let refreshed = db.get_anchor_by_id(anchor_uuid).await.unwrap().unwrap();
+    assert_eq!(refreshed.status, "red");
+    assert!(refreshed.reliability_score < anchor.reliability_score);
+    assert_eq!(
+        db.get_anchor_endpoint_uptime(&anchor.id).await.unwrap(),
+        Some(50.0)
+    );
+
+    // Removing the override falls back to stellar.toml
+    assert!(prober
+        .remove_endpoint(&anchor.id, SepEndpoint::Sep24)
+        .await
+        .unwrap());
+    assert!(!prober
+        .remove_endpoint(&anchor.id, SepEndpoint::Sep24)
+        .await
+        .unwrap());
+    let endpoints = prober.endpoints(&anchor.id).await.unwrap();
+    assert!(endpoints.contains(&(
+        SepEndpoint::Sep24,
+        "https://stale.example/sep24".to_string()
+    )));
+}
+
+#[sqlx::test]
+async fn test_set_endpoint_requires_anchor(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let prober = SepEndpointProber::new(Arc::clone(&db), SepEndpointProberConfig::default());
+
+    assert!(!prober
+        .set_endpoint("missing", SepEndpoint::Sep6, "https://anchor.example/sep6")
+        .await
+        .unwrap());
+    assert_eq!(
+        db.get_anchor_endpoint_uptime("missing").await.unwrap(),
+        None
+    );
+}
@@ -8,11 +8,20 @@
 //! 5. Submit to smart contract ✅ (mocked)
 //! 6. Verify submission success ✅ (mocked)
 
-use std::sync::Arc;
+use axum::{body::Body, http::Request, http::StatusCode, routing::post, Json, Router};
+use base64::Engine;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
 use stellar_insights_backend::database::Database;
-use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::snapshot::{
+    InclusionProof, SnapshotLeafKind, SnapshotService,
+};
 use stellar_insights_backend::snapshot::schema::AnalyticsSnapshot;
-use sqlx::Row;
+use stellar_insights_backend::snapshot_handlers::SnapshotAppState;
+use sqlx::{Executor, Row};
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+use tower::util::ServiceExt;
 
 async fn setup_test_database() -> Arc<Database> {
     let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
@@ -71,6 +80,7 @@ async fn setup_test_database() -> Arc<Database> {
             entity_type TEXT NOT NULL,
             data TEXT NOT NULL,
             hash TEXT,
+            merkle_root TEXT,
             epoch INTEGER,
             timestamp TEXT NOT NULL,
             created_at TEXT DEFAULT CURRENT_TIMESTAMP
@@ -81,6 +91,13 @@ async fn setup_test_database() -> Arc<Database> {
     .await
     .unwrap();
 
+    db.pool()
+        .execute(include_str!(
+            "../migrations/024_create_snapshot_submissions.sql"
+        ))
+        .await
+        .unwrap();
+
     // Insert test data
     let _: sqlx::sqlite::SqliteQueryResult = sqlx::query(r#"
         INSERT INTO anchors (id, name, stellar_account, total_transactions, successful_transactions, failed_transactions, total_volume_usd, avg_settlement_time_ms, reliability_score, status)
@@ -312,3 +329,320 @@ async fn test_hash_determinism_across_different_insertion_orders() {
 
     println!("✅ Hash determinism verified across insertion orders");
 }
+
+/// Soroban RPC answering `get_snapshot` with a fixed root
+async fn start_rpc(root: Arc<Mutex<String>>) -> String {
+    let app = Router::new().route(
+        "/",
+        post(move |Json(request): Json<Value>| {
+            let root = hex::decode(root.lock().unwrap().as_str()).unwrap();
+            async move {
+                assert_eq!(request["method"], "simulateTransaction");
+                assert_eq!(simulated_function(&request), "get_snapshot");
+                let value = xdr::ScVal::Bytes(xdr::ScBytes(root.try_into().unwrap()));
+                let value = base64::engine::general_purpose::STANDARD
+                    .encode(value.to_xdr(xdr::Limits::none()).unwrap());
+                Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": { "results": [{ "auth": [], "xdr": value }], "latestLedger": 1 }
+                }))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}/", addr)
+}
+
+/// Contract function invoked by the transaction of a `simulateTransaction` request
+fn simulated_function(request: &Value) -> String {
+    let envelope = base64::engine::general_purpose::STANDARD
+        .decode(request["params"]["transaction"].as_str().unwrap())
+        .unwrap();
+    let xdr::TransactionEnvelope::Tx(envelope) =
+        xdr::TransactionEnvelope::from_xdr(envelope, xdr::Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 envelope");
+    };
+    match &envelope.tx.operations[0].body {
+        xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+            host_function: xdr::HostFunction::InvokeContract(args),
+            ..
+        }) => args.function_name.0.to_string(),
+        other => panic!("unexpected operation {:?}", other),
+    }
+}
+
+fn contract_id() -> String {
+    stellar_strkey::Contract([5u8; 32]).to_string()
+}
+
+#[tokio::test]
+async fn test_inclusion_proofs_verify_against_the_submitted_root() {
+    let db = setup_test_database().await;
+    let result = SnapshotService::new(db.clone(), None)
+        .generate_and_submit_snapshot(6)
+        .await
+        .unwrap();
+
+    let stored: String = sqlx::query("SELECT merkle_root FROM snapshots WHERE id = ?")
+        .bind(&result.snapshot_id)
+        .fetch_one(db.pool())
+        .await
+        .unwrap()
+        .get("merkle_root");
+    assert_eq!(stored, result.merkle_root);
+    assert_eq!(
+        hex::encode(SnapshotService::merkle_root(&result.canonical_json).unwrap()),
+        result.merkle_root
+    );
+
+    let on_chain = Arc::new(Mutex::new(result.merkle_root.clone()));
+    let contract = Arc::new(
+        ContractService::new(ContractConfig {
+            rpc_url: start_rpc(Arc::clone(&on_chain)).await,
+            contract_id: contract_id(),
+            network_passphrase: "Test SDF Network ; September 2015".to_string(),
+        })
+        .unwrap(),
+    );
+    let service = Arc::new(SnapshotService::new(
+        db.clone(),
+        Some(Arc::clone(&contract)),
+    ));
+    let app = stellar_insights_backend::snapshot_handlers::routes(SnapshotAppState {
+        db,
+        contract_service: Some(contract),
+        snapshot_service: Arc::clone(&service),
+    });
+
+    let call = |uri: &str| {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, body)
+        }
+    };
+
+    let (status, body) =
+        call("/api/snapshots/6/proof?kind=corridor&id=00000000-0000-0000-0000-000000000004").await;
+    assert_eq!(status, StatusCode::OK);
+    let proof: InclusionProof = serde_json::from_slice(&body).unwrap();
+    assert_eq!(proof.kind, SnapshotLeafKind::Corridor);
+    assert_eq!(proof.leaf_index, 3);
+    assert_eq!(proof.leaf_count, 4);
+    assert_eq!(hex::encode(proof.merkle_root), result.merkle_root);
+    let leaf: Value = serde_json::from_str(&proof.leaf).unwrap();
+    assert_eq!(
+        leaf["metrics"]["corridor_key"],
+        "USDC:ISSUER1->GBPC:ISSUER3"
+    );
+    assert!(service.verify_inclusion_on_chain(&proof).await.unwrap());
+
+    // A proof for different metrics, or against a different root, fails
+    let mut forged = proof.clone();
+    forged.leaf = forged
+        .leaf
+        .replace("\"total_transactions\":300", "\"total_transactions\":301");
+    assert_ne!(forged.leaf, proof.leaf);
+    assert!(!service.verify_inclusion_on_chain(&forged).await.unwrap());
+    *on_chain.lock().unwrap() = hex::encode([1u8; 32]);
+    assert!(!service.verify_inclusion_on_chain(&proof).await.unwrap());
+
+    let (status, _) = call("/api/snapshots/6/proof?kind=anchor&id=missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) =
+        call("/api/snapshots/7/proof?kind=anchor&id=00000000-0000-0000-0000-000000000001").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = call("/api/snapshots/6/proof?kind=asset&id=x").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_snapshots_verify_through_the_api_and_the_cli() {
+    let db = setup_test_database().await;
+    let result = SnapshotService::new(db.clone(), None)
+        .generate_and_submit_snapshot(8)
+        .await
+        .unwrap();
+
+    let on_chain = Arc::new(Mutex::new(result.merkle_root.clone()));
+    let rpc_url = start_rpc(Arc::clone(&on_chain)).await;
+    let contract = Arc::new(
+        ContractService::new(ContractConfig {
+            rpc_url: rpc_url.clone(),
+            contract_id: contract_id(),
+            network_passphrase: "Test SDF Network ; September 2015".to_string(),
+        })
+        .unwrap(),
+    );
+    let app = stellar_insights_backend::snapshot_handlers::routes(SnapshotAppState {
+        db: db.clone(),
+        contract_service: Some(Arc::clone(&contract)),
+        snapshot_service: Arc::new(SnapshotService::new(db.clone(), Some(contract))),
+    });
+    let call = |uri: &str| {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, body)
+        }
+    };
+
+    let (status, body) = call("/api/snapshots/8/verify").await;
+    assert_eq!(status, StatusCode::OK);
+    let report: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report["status"], "verified");
+    assert_eq!(report["verified"], true);
+    assert_eq!(report["contract_id"], contract_id());
+    assert_eq!(report["snapshot_id"], result.snapshot_id.as_str());
+    assert_eq!(report["hash"], result.hash.as_str());
+    assert_eq!(report["stored_hash"], result.hash.as_str());
+    assert_eq!(report["on_chain_root"], result.merkle_root.as_str());
+    assert_eq!(report["leaf_count"], 4);
+
+    // The downloaded snapshot verifies without the database
+    let (status, body) = call("/api/snapshots/8").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, result.canonical_json.as_bytes());
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), &body).unwrap();
+    let verify_file = || {
+        tokio::process::Command::new(env!("CARGO_BIN_EXE_verify_snapshot"))
+            .arg(file.path())
+            .args(["--contract-id", &contract_id(), "--rpc-url", &rpc_url])
+            .output()
+    };
+    let output = verify_file().await.unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let cli_report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(cli_report["status"], "verified");
+    assert_eq!(cli_report["merkle_root"], result.merkle_root.as_str());
+    assert!(cli_report.get("stored_hash").is_none());
+
+    *on_chain.lock().unwrap() = hex::encode([1u8; 32]);
+    let (_, body) = call("/api/snapshots/8/verify").await;
+    let report: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report["status"], "root_mismatch");
+    assert_eq!(report["verified"], false);
+    let output = verify_file().await.unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    // Tampering with the stored snapshot is reported even if the root matches
+    *on_chain.lock().unwrap() = result.merkle_root.clone();
+    sqlx::query("UPDATE snapshots SET data = REPLACE(data, '\"total_transactions\":300', '\"total_transactions\":301')")
+        .execute(db.pool())
+        .await
+        .unwrap();
+    let (_, body) = call("/api/snapshots/8/verify").await;
+    let report: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report["status"], "stored_mismatch");
+    assert_ne!(report["merkle_root"], report["stored_merkle_root"]);
+
+    let (status, _) = call("/api/snapshots/9/verify").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = call("/api/snapshots/9").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_snapshot_diff_between_epochs() {
+    let db = setup_test_database().await;
+    let service = Arc::new(SnapshotService::new(db.clone(), None));
+    service.generate_and_submit_snapshot(1).await.unwrap();
+
+    sqlx::query("UPDATE anchors SET total_transactions = 1010, status = 'yellow' WHERE id = '00000000-0000-0000-0000-000000000001'")
+        .execute(db.pool())
+        .await
+        .unwrap();
+    sqlx::query("UPDATE anchors SET total_transactions = 3000 WHERE id = '00000000-0000-0000-0000-000000000002'")
+        .execute(db.pool())
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account, total_transactions, successful_transactions, failed_transactions, total_volume_usd, avg_settlement_time_ms, reliability_score, status) VALUES ('00000000-0000-0000-0000-000000000005', 'Test Anchor 3', 'GTEST3', 10, 10, 0, 100.0, 500, 1.0, 'green')")
+        .execute(db.pool())
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM corridor_metrics WHERE corridor_key = 'USDC:ISSUER1->GBPC:ISSUER3'")
+        .execute(db.pool())
+        .await
+        .unwrap();
+    service.generate_and_submit_snapshot(2).await.unwrap();
+
+    let app = stellar_insights_backend::snapshot_handlers::routes(SnapshotAppState {
+        db,
+        contract_service: None,
+        snapshot_service: service,
+    });
+    let call = |uri: &str| {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, serde_json::from_slice::<Value>(&body).unwrap())
+        }
+    };
+
+    let (status, diff) = call("/api/snapshots/diff?from=1&to=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(diff["from_epoch"], 1);
+    assert_eq!(diff["to_epoch"], 2);
+    assert_eq!(
+        diff["anchors"]["added"],
+        json!([{ "id": "00000000-0000-0000-0000-000000000005", "label": "Test Anchor 3" }])
+    );
+    assert_eq!(diff["anchors"]["removed"], json!([]));
+    assert_eq!(diff["anchors"]["changed"].as_array().unwrap().len(), 2);
+    let first = &diff["anchors"]["changed"][0];
+    assert_eq!(first["label"], "Test Anchor 1");
+    assert!(first["changes"]
+        .as_array()
+        .unwrap()
+        .contains(&json!({ "field": "status", "from": "green", "to": "yellow", "delta": null, "pct_change": null })));
+    assert_eq!(
+        diff["corridors"]["removed"],
+        json!([{ "id": "USDC:ISSUER1->GBPC:ISSUER3", "label": "USDC:ISSUER1->GBPC:ISSUER3" }])
+    );
+    assert_eq!(diff["corridors"]["unchanged"], 1);
+
+    // Anchor 1 moved by 1% and its status; anchor 2 by 50%
+    let (_, diff) = call("/api/snapshots/diff?from=1&to=2&min_pct_change=10").await;
+    let changed = diff["anchors"]["changed"].as_array().unwrap();
+    assert_eq!(changed.len(), 2);
+    let fields: Vec<&str> = changed[0]["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["status"]);
+    let total = changed[1]["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["field"] == "total_transactions")
+        .unwrap();
+    assert_eq!(total["delta"], 1000.0);
+    assert_eq!(total["pct_change"], 50.0);
+
+    let (status, _) = call("/api/snapshots/diff?from=1&to=3").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = call("/api/snapshots/diff?from=1&to=2&min_abs_change=-1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
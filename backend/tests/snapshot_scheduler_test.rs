@@ -0,0 +1,355 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use base64::Engine;
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use sqlx::{Executor, SqlitePool};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::snapshot::{
+    SnapshotService, SubmissionStatus, VerificationStatus,
+};
+use stellar_insights_backend::services::snapshot_scheduler::{
+    SchedulerRunSummary, SnapshotScheduler, SnapshotSchedulerConfig,
+};
+use stellar_insights_backend::snapshot_handlers::SnapshotAppState;
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+use tower::util::ServiceExt;
+
+/// Roots held by the fake contract, by epoch
+type Chain = Arc<Mutex<HashMap<u64, String>>>;
+
+/// Soroban RPC answering `get_snapshot` from `Chain`; submissions fail
+/// before reaching it as the service has no signer
+async fn rpc(State(chain): State<Chain>, Json(request): Json<Value>) -> Json<Value> {
+    assert_eq!(request["method"], "simulateTransaction");
+    let envelope = base64::engine::general_purpose::STANDARD
+        .decode(request["params"]["transaction"].as_str().unwrap())
+        .unwrap();
+    let xdr::TransactionEnvelope::Tx(envelope) =
+        xdr::TransactionEnvelope::from_xdr(envelope, xdr::Limits::none()).unwrap()
+    else {
+        panic!("expected a v1 envelope");
+    };
+    let xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+        host_function: xdr::HostFunction::InvokeContract(args),
+        ..
+    }) = &envelope.tx.operations[0].body
+    else {
+        panic!("expected a contract invocation");
+    };
+    assert_eq!(args.function_name.0.to_string(), "get_snapshot");
+    let xdr::ScVal::U64(epoch) = args.args[0] else {
+        panic!("expected an epoch");
+    };
+
+    let result = match chain.lock().unwrap().get(&epoch) {
+        Some(root) => {
+            let value =
+                xdr::ScVal::Bytes(xdr::ScBytes(hex::decode(root).unwrap().try_into().unwrap()));
+            let value = base64::engine::general_purpose::STANDARD
+                .encode(value.to_xdr(xdr::Limits::none()).unwrap());
+            json!({ "results": [{ "auth": [], "xdr": value }], "latestLedger": 1 })
+        }
+        // The contract panics for unknown epochs
+        None => json!({ "error": "HostError: Error(WasmVm, InvalidAction)", "latestLedger": 1 }),
+    };
+    Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+}
+
+async fn start_rpc(chain: Chain) -> String {
+    let app = Router::new().route("/", post(rpc)).with_state(chain);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}/", addr)
+}
+
+/// Tables read by snapshot generation, as in `snapshot_integration_test`
+async fn setup() -> (Arc<Database>, Chain, Arc<SnapshotService>) {
+    let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+    pool.execute(
+        r#"
+        CREATE TABLE anchors (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            stellar_account TEXT NOT NULL,
+            total_transactions INTEGER DEFAULT 0,
+            successful_transactions INTEGER DEFAULT 0,
+            failed_transactions INTEGER DEFAULT 0,
+            total_volume_usd REAL DEFAULT 0,
+            avg_settlement_time_ms INTEGER DEFAULT 0,
+            reliability_score REAL DEFAULT 0,
+            status TEXT DEFAULT 'green'
+        );
+        CREATE TABLE corridor_metrics (
+            id TEXT PRIMARY KEY,
+            corridor_key TEXT NOT NULL,
+            asset_a_code TEXT NOT NULL,
+            asset_a_issuer TEXT NOT NULL,
+            asset_b_code TEXT NOT NULL,
+            asset_b_issuer TEXT NOT NULL,
+            date TEXT NOT NULL,
+            total_transactions INTEGER DEFAULT 0,
+            successful_transactions INTEGER DEFAULT 0,
+            failed_transactions INTEGER DEFAULT 0,
+            success_rate REAL DEFAULT 0,
+            volume_usd REAL DEFAULT 0,
+            avg_settlement_latency_ms INTEGER,
+            liquidity_depth_usd REAL DEFAULT 0
+        );
+        CREATE TABLE snapshots (
+            id TEXT PRIMARY KEY,
+            entity_id TEXT NOT NULL,
+            entity_type TEXT NOT NULL,
+            data TEXT NOT NULL,
+            hash TEXT,
+            merkle_root TEXT,
+            epoch INTEGER,
+            timestamp TEXT NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        INSERT INTO anchors (id, name, stellar_account, total_transactions, successful_transactions)
+        VALUES ('00000000-0000-0000-0000-000000000001', 'Anchor', 'GANCHOR', 100, 95);
+    "#,
+    )
+    .await
+    .unwrap();
+    pool.execute(include_str!(
+        "../migrations/024_create_snapshot_submissions.sql"
+    ))
+    .await
+    .unwrap();
+    let db = Arc::new(Database::new(pool));
+
+    let chain = Chain::default();
+    let contract = ContractService::new(ContractConfig {
+        rpc_url: start_rpc(Arc::clone(&chain)).await,
+        contract_id: stellar_strkey::Contract([5u8; 32]).to_string(),
+        network_passphrase: "Test SDF Network ; September 2015".to_string(),
+    })
+    .unwrap();
+    let service = Arc::new(SnapshotService::new(
+        Arc::clone(&db),
+        Some(Arc::new(contract)),
+    ));
+    (db, chain, service)
+}
+
+fn scheduler(service: &Arc<SnapshotService>, max_attempts: u32) -> SnapshotScheduler {
+    SnapshotScheduler::new(
+        Arc::clone(service),
+        SnapshotSchedulerConfig {
+            epoch_secs: 3600,
+            max_attempts,
+            ..SnapshotSchedulerConfig::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn test_epochs_are_generated_submitted_and_reconciled() {
+    let (_db, chain, service) = setup().await;
+    let now = Utc.with_ymd_and_hms(2026, 10, 1, 12, 30, 0).unwrap();
+    let epoch = now.timestamp() as u64 / 3600;
+
+    let scheduler = scheduler(&service, 5);
+    assert_eq!(scheduler.epoch_at(now), epoch);
+    let summary = scheduler.run_once(now, false).await.unwrap();
+    assert_eq!(
+        summary,
+        SchedulerRunSummary {
+            generated: Some(epoch),
+            attempted: 1,
+            failed: 1,
+            ..SchedulerRunSummary::default()
+        }
+    );
+    let submission = service.submission(epoch).await.unwrap().unwrap();
+    assert_eq!(submission.status, SubmissionStatus::Failed);
+    assert_eq!(submission.attempts, 1);
+    assert!(submission.last_error.unwrap().contains("signer"));
+
+    // After a restart the pending epoch is picked up again; its root has
+    // reached the contract in the meantime
+    chain
+        .lock()
+        .unwrap()
+        .insert(epoch, submission.merkle_root.clone());
+    let scheduler = self::scheduler(&service, 5);
+    let summary = scheduler.run_once(now, true).await.unwrap();
+    assert_eq!(
+        summary,
+        SchedulerRunSummary {
+            generated: None,
+            attempted: 1,
+            confirmed: 1,
+            reconciled: 1,
+            ..SchedulerRunSummary::default()
+        }
+    );
+    let submission = service.submission(epoch).await.unwrap().unwrap();
+    assert_eq!(submission.status, SubmissionStatus::Confirmed);
+    assert_eq!(submission.last_error, None);
+    assert_eq!(
+        submission.reconciliation,
+        Some(VerificationStatus::Verified)
+    );
+
+    // Confirmed epochs are not submitted again, but still reconciled
+    chain.lock().unwrap().insert(epoch, hex::encode([7u8; 32]));
+    let summary = scheduler.run_once(now, true).await.unwrap();
+    assert_eq!(summary.attempted, 0);
+    assert_eq!(summary.mismatches, 1);
+    let submission = service.submission(epoch).await.unwrap().unwrap();
+    assert_eq!(submission.status, SubmissionStatus::Confirmed);
+    assert_eq!(
+        submission.reconciliation,
+        Some(VerificationStatus::RootMismatch)
+    );
+    assert_eq!(submission.on_chain_root, Some(hex::encode([7u8; 32])));
+}
+
+#[tokio::test]
+async fn test_failed_epochs_stop_after_max_attempts() {
+    let (db, chain, service) = setup().await;
+    let now = Utc.with_ymd_and_hms(2026, 10, 1, 12, 30, 0).unwrap();
+    let epoch = now.timestamp() as u64 / 3600;
+
+    let scheduler = scheduler(&service, 1);
+    let summary = scheduler.run_once(now, true).await.unwrap();
+    assert_eq!(summary.failed, 1);
+    // Not on-chain yet is not a mismatch
+    assert_eq!(summary.reconciled, 1);
+    assert_eq!(summary.mismatches, 0);
+    let summary = scheduler.run_once(now, false).await.unwrap();
+    assert_eq!(summary, SchedulerRunSummary::default());
+
+    let state = SnapshotAppState {
+        db,
+        contract_service: service.contract_service().cloned(),
+        snapshot_service: Arc::clone(&service),
+    };
+    let app = stellar_insights_backend::snapshot_handlers::routes(state.clone())
+        .merge(stellar_insights_backend::snapshot_handlers::protected_routes(state));
+    let call = |method: &str, uri: &str, body: Value| {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, serde_json::from_slice::<Value>(&body).unwrap())
+        }
+    };
+
+    let (status, body) = call(
+        "GET",
+        "/api/snapshots/submissions?status=failed",
+        json!(null),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["submissions"][0]["epoch"], epoch);
+    assert_eq!(body["submissions"][0]["attempts"], 1);
+    let (status, _) = call("GET", "/api/snapshots/submissions?status=lost", json!(null)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A retry is allowed all attempts again
+    let (_, stored) = call(
+        "GET",
+        &format!("/api/snapshots/submissions/{}", epoch),
+        json!(null),
+    )
+    .await;
+    chain
+        .lock()
+        .unwrap()
+        .insert(epoch, stored["merkle_root"].as_str().unwrap().to_string());
+    let (status, body) = call(
+        "POST",
+        &format!("/api/snapshots/submissions/{}/retry", epoch),
+        json!(null),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "confirmed");
+    assert_eq!(body["attempts"], 0);
+
+    // The contract keeps the first root of an epoch
+    let (status, _) = call(
+        "POST",
+        "/api/snapshots/generate",
+        json!({ "epoch": epoch, "submit_to_contract": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, body) = call(
+        "POST",
+        "/api/snapshots/generate",
+        json!({ "epoch": epoch + 1 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["submission_status"], "generated");
+    let (status, _) = call("GET", "/api/snapshots/submissions/1", json!(null)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_submissions_that_never_landed_are_retried() {
+    let (db, _chain, service) = setup().await;
+    let now = Utc.with_ymd_and_hms(2026, 10, 1, 12, 30, 0).unwrap();
+    let epoch = now.timestamp() as u64 / 3600;
+
+    let scheduler = scheduler(&service, 5);
+    scheduler.run_once(now, false).await.unwrap();
+    // As if a transaction had been sent but never made it into a ledger
+    let mark_submitted = |at| {
+        sqlx::query(
+            "UPDATE snapshot_submissions SET status = 'submitted', attempts = 0, transaction_hash = 'abc', updated_at = ? WHERE epoch = ?",
+        )
+        .bind(at)
+        .bind(epoch as i64)
+        .execute(db.pool())
+    };
+
+    // Within the transaction timeout the contract may still catch up
+    mark_submitted(now - chrono::Duration::minutes(1))
+        .await
+        .unwrap();
+    let summary = scheduler.run_once(now, false).await.unwrap();
+    assert_eq!(summary.attempted, 0);
+
+    mark_submitted(now - chrono::Duration::minutes(10))
+        .await
+        .unwrap();
+    let summary = scheduler.run_once(now, false).await.unwrap();
+    assert_eq!(summary.attempted, 1);
+    assert_eq!(summary.failed, 1);
+    let submission = service.submission(epoch).await.unwrap().unwrap();
+    assert_eq!(submission.status, SubmissionStatus::Failed);
+    assert_eq!(submission.attempts, 1);
+    assert!(submission.last_error.unwrap().contains("timed out"));
+
+    // The failed epoch is then submitted again
+    let summary = scheduler.run_once(now, false).await.unwrap();
+    assert_eq!(summary.attempted, 1);
+    let submission = service.submission(epoch).await.unwrap().unwrap();
+    assert_eq!(submission.attempts, 2);
+    assert!(submission.last_error.unwrap().contains("signer"));
+}
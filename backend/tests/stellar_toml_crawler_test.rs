@@ -0,0 +1,207 @@
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::stellar_toml_crawler::{
+    CrawlOutcome, StellarTomlCrawler, StellarTomlCrawlerConfig,
+};
+use tokio::sync::RwLock;
+
+const ANCHOR_ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+const FOREIGN_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+type Served = Arc<RwLock<Option<String>>>;
+
+async fn serve_toml(State(served): State<Served>) -> Result<String, StatusCode> {
+    served.read().await.clone().ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Serve `/.well-known/stellar.toml` locally, returning the domain to crawl
+async fn start_server(served: Served) -> String {
+    let app = Router::new()
+        .route("/.well-known/stellar.toml", get(serve_toml))
+        .with_state(served);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr.to_string()
+}
+
+fn stellar_toml(org_name: &str, quote_server: Option<&str>) -> String {
+    let mut toml = format!(
+        r#"
+ACCOUNTS = ["{anchor}"]
+TRANSFER_SERVER_SEP0024 = "https://anchor.example/sep24"
+DIRECT_PAYMENT_SERVER = "https://anchor.example/sep31"
+WEB_AUTH_ENDPOINT = "https://anchor.example/auth"
+"#,
+        anchor = ANCHOR_ACCOUNT
+    );
+    if let Some(url) = quote_server {
+        toml.push_str(&format!("ANCHOR_QUOTE_SERVER = \"{}\"\n", url));
+    }
+    toml.push_str(&format!(
+        r#"
+[DOCUMENTATION]
+ORG_NAME = "{org_name}"
+ORG_URL = "https://anchor.example"
+
+[[CURRENCIES]]
+code = "BRLT"
+issuer = "{anchor}"
+status = "live"
+display_decimals = 2
+is_asset_anchored = true
+anchor_asset_type = "fiat"
+
+# Claims an asset whose issuer does not point back at this domain
+[[CURRENCIES]]
+code = "USDC"
+issuer = "{foreign}"
+"#,
+        org_name = org_name,
+        anchor = ANCHOR_ACCOUNT,
+        foreign = FOREIGN_ISSUER
+    ));
+    toml
+}
+
+#[sqlx::test]
+async fn test_crawl_anchor_tracks_changes(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let served: Served = Arc::new(RwLock::new(Some(stellar_toml("Example Anchor", None))));
+    let domain = start_server(Arc::clone(&served)).await;
+
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Example".to_string(),
+            stellar_account: ANCHOR_ACCOUNT.to_string(),
+            home_domain: Some(domain.clone()),
+        })
+        .await
+        .unwrap();
+
+    let crawler = StellarTomlCrawler::new(
+        Arc::clone(&db),
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+        StellarTomlCrawlerConfig {
+            scheme: "http".to_string(),
+            allow_private_hosts: true,
+            discovery_limit: 0,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let CrawlOutcome::Updated { changed_fields } = crawler.crawl_anchor(&anchor).await.unwrap()
+    else {
+        panic!("first crawl should store the file");
+    };
+    assert!(changed_fields.contains(&"DOCUMENTATION.ORG_NAME".to_string()));
+
+    let info = crawler.get_info(&anchor.id).await.unwrap().unwrap();
+    assert_eq!(info.home_domain, domain);
+    assert_eq!(info.org_name.as_deref(), Some("Example Anchor"));
+    assert_eq!(
+        info.transfer_server_sep0024.as_deref(),
+        Some("https://anchor.example/sep24")
+    );
+    assert_eq!(
+        info.web_auth_endpoint.as_deref(),
+        Some("https://anchor.example/auth")
+    );
+    assert!(info.anchor_quote_server.is_none());
+    assert!(info.last_error.is_none());
+
+    // Only the anchor's own asset is attributed to it
+    let currencies = crawler.get_currencies(&anchor.id).await.unwrap();
+    assert_eq!(currencies.len(), 1);
+    assert_eq!(currencies[0].asset, format!("BRLT:{}", ANCHOR_ACCOUNT));
+    assert_eq!(currencies[0].display_decimals, Some(2));
+    assert_eq!(currencies[0].is_asset_anchored, Some(true));
+
+    let assets = db
+        .get_assets_by_anchor(anchor.id.parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].asset_code, "BRLT");
+
+    // An identical file is not recorded again
+    assert_eq!(
+        crawler.crawl_anchor(&anchor).await.unwrap(),
+        CrawlOutcome::Unchanged
+    );
+
+    *served.write().await = Some(stellar_toml(
+        "Example Anchor Ltd",
+        Some("https://anchor.example/sep38"),
+    ));
+    assert_eq!(
+        crawler.crawl_anchor(&anchor).await.unwrap(),
+        CrawlOutcome::Updated {
+            changed_fields: vec![
+                "ANCHOR_QUOTE_SERVER".to_string(),
+                "DOCUMENTATION.ORG_NAME".to_string()
+            ]
+        }
+    );
+
+    let history = crawler.get_history(&anchor.id, 10).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history[0].changed_fields,
+        vec!["ANCHOR_QUOTE_SERVER", "DOCUMENTATION.ORG_NAME"]
+    );
+
+    // A failed fetch is recorded without discarding the last good data
+    *served.write().await = None;
+    assert!(crawler.crawl_anchor(&anchor).await.is_err());
+    let info = crawler.get_info(&anchor.id).await.unwrap().unwrap();
+    assert!(info.last_error.unwrap().contains("404"));
+    assert_eq!(info.org_name.as_deref(), Some("Example Anchor Ltd"));
+    assert_eq!(
+        info.anchor_quote_server.as_deref(),
+        Some("https://anchor.example/sep38")
+    );
+}
+
+#[sqlx::test]
+async fn test_crawl_rejects_invalid_toml(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let served: Served = Arc::new(RwLock::new(Some("ORG_NAME = \"unterminated\n".to_string())));
+    let domain = start_server(Arc::clone(&served)).await;
+
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Broken".to_string(),
+            stellar_account: ANCHOR_ACCOUNT.to_string(),
+            home_domain: Some(domain),
+        })
+        .await
+        .unwrap();
+
+    let crawler = StellarTomlCrawler::new(
+        Arc::clone(&db),
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+        StellarTomlCrawlerConfig {
+            scheme: "http".to_string(),
+            allow_private_hosts: true,
+            discovery_limit: 0,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let err = crawler.crawl_anchor(&anchor).await.unwrap_err();
+    assert!(err.to_string().contains("line 1"));
+    let info = crawler.get_info(&anchor.id).await.unwrap().unwrap();
+    assert!(info.content_hash.is_none());
+    assert!(crawler
+        .get_history(&anchor.id, 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,379 @@
+use axum::{
+    body::Body,
+    extract::{Extension, Path, Query, State},
+    http::{HeaderMap, Request, StatusCode},
+    routing::{get, post, put},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use stellar_insights_backend::auth_middleware::AuthUser;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::anchor_registry::AnchorRegistry;
+use stellar_insights_backend::services::sep10_client::{Sep10Client, Sep10ClientConfig};
+use stellar_insights_backend::services::sep_endpoint_prober::{
+    SepEndpoint, SepEndpointProber, SepEndpointProberConfig,
+};
+use stellar_insights_backend::services::stellar_toml_crawler::{
+    StellarTomlCrawler, StellarTomlCrawlerConfig,
+};
+use stellar_insights_backend::services::transfer_tracker::{
+    PollSummary, TransferTracker, TransferTrackerConfig,
+};
+use tower::util::ServiceExt;
+
+/// An anchor with one SEP-24 deposit and one SEP-31 payment whose statuses
+/// the test moves along
+#[derive(Default)]
+struct FakeAnchor {
+    deposit_status: Mutex<String>,
+    payment_status: Mutex<String>,
+    /// `on_change_callback` of the deposit and callback URL of the payment
+    callbacks: Mutex<Vec<String>>,
+    /// Authorization header of the payment's callback registration
+    payment_auth: Mutex<Option<String>>,
+}
+
+async fn deposit_interactive(
+    State(anchor): State<Arc<FakeAnchor>>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    if let Some(url) = body["on_change_callback"].as_str() {
+        anchor.callbacks.lock().unwrap().push(url.to_string());
+    }
+    Json(json!({
+        "type": "interactive_customer_info_needed",
+        "url": "https://anchor.example/kyc",
+        "id": "deposit-1"
+    }))
+}
+
+async fn sep24_transaction(
+    State(anchor): State<Arc<FakeAnchor>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, StatusCode> {
+    Ok(Json(json!({
+        "transaction": {
+            "id": params["id"],
+            "kind": "deposit",
+            "status": *anchor.deposit_status.lock().unwrap()
+        }
+    })))
+}
+
+async fn create_payment() -> Json<Value> {
+    Json(json!({ "id": "payment-1" }))
+}
+
+async fn sep31_transaction(
+    State(anchor): State<Arc<FakeAnchor>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, StatusCode> {
+    if headers.get("authorization").and_then(|v| v.to_str().ok()) != Some("Bearer theirs") {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(Json(json!({
+        "transaction": { "id": id, "status": *anchor.payment_status.lock().unwrap() }
+    })))
+}
+
+async fn register_callback(
+    State(anchor): State<Arc<FakeAnchor>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> StatusCode {
+    *anchor.payment_auth.lock().unwrap() = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    anchor
+        .callbacks
+        .lock()
+        .unwrap()
+        .push(body["url"].as_str().unwrap().to_string());
+    StatusCode::NO_CONTENT
+}
+
+async fn start_anchor(anchor: Arc<FakeAnchor>) -> String {
+    let app = Router::new()
+        .route(
+            "/sep24/transactions/deposit/interactive",
+            post(deposit_interactive),
+        )
+        .route("/sep24/transaction", get(sep24_transaction))
+        .route("/sep31/transactions", post(create_payment))
+        .route("/sep31/transactions/:id", get(sep31_transaction))
+        .route("/sep31/transactions/:id/callback", put(register_callback))
+        .with_state(anchor);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn sep10_client(db: Arc<Database>) -> Arc<Sep10Client> {
+    let rpc_client = Arc::new(StellarRpcClient::new(
+        "http://127.0.0.1:1".to_string(),
+        "http://127.0.0.1:1".to_string(),
+        true,
+    ));
+    let crawler = Arc::new(
+        StellarTomlCrawler::new(db, rpc_client, StellarTomlCrawlerConfig::default()).unwrap(),
+    );
+    Arc::new(Sep10Client::new(
+        crawler,
+        Sep10ClientConfig::default(),
+        None,
+    ))
+}
+
+async fn call(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn post_json(uri: &str, body: Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn get_uri(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+fn user(id: &str) -> Extension<AuthUser> {
+    Extension(AuthUser {
+        user_id: id.to_string(),
+        username: id.to_string(),
+    })
+}
+
+#[sqlx::test]
+async fn test_proxied_transfers_are_tracked_to_a_terminal_status(pool: SqlitePool) {
+    let fake = Arc::new(FakeAnchor::default());
+    *fake.deposit_status.lock().unwrap() = "incomplete".to_string();
+    *fake.payment_status.lock().unwrap() = "pending_sender".to_string();
+    let base = start_anchor(Arc::clone(&fake)).await;
+
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Anchor".to_string(),
+            stellar_account: "GANCHOR".to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    let prober = SepEndpointProber::new(Arc::clone(&db), SepEndpointProberConfig::default());
+    assert!(prober
+        .set_endpoint(&anchor.id, SepEndpoint::Sep24, &format!("{}/sep24/", base))
+        .await
+        .unwrap());
+
+    let sep10 = sep10_client(Arc::clone(&db));
+    let tracker = Arc::new(TransferTracker::new(
+        Arc::clone(&db),
+        Arc::clone(&sep10),
+        TransferTrackerConfig {
+            callback_base_url: Some("https://insights.example/".to_string()),
+            ..TransferTrackerConfig::default()
+        },
+    ));
+    let registry = Arc::new(AnchorRegistry::new(Arc::clone(&db)));
+    let transfers = stellar_insights_backend::api::transfers::routes(Arc::clone(&tracker));
+    let mine = stellar_insights_backend::api::transfers::protected_routes(Arc::clone(&tracker))
+        .layer(user("alice"));
+    let others = stellar_insights_backend::api::transfers::protected_routes(Arc::clone(&tracker))
+        .layer(user("bob"));
+
+    let (status, body) = call(
+        stellar_insights_backend::api::sep24_proxy::routes(
+            Arc::clone(&sep10),
+            Arc::clone(&tracker),
+            Arc::clone(&registry),
+        )
+        .layer(user("alice")),
+        post_json(
+            "/api/sep24/deposit/interactive",
+            json!({
+                "transfer_server": format!("{}/sep24", base),
+                "asset_code": "USDC",
+                "amount": "100"
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], "deposit-1");
+
+    let deposit = tracker
+        .list_transfers("alice", Some(&anchor.id), None, 10)
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(deposit.anchor_transaction_id, "deposit-1");
+    assert_eq!(deposit.kind, "deposit");
+    assert_eq!(deposit.status, "incomplete");
+    assert_eq!(deposit.owner.as_deref(), Some("alice"));
+    assert!(!deposit.caller_token);
+    assert_eq!(
+        fake.callbacks.lock().unwrap()[0],
+        format!(
+            "https://insights.example/api/transfers/{}/callback/{}",
+            deposit.id, deposit.callback_token
+        )
+    );
+
+    // The anchor needs no token, so the transfer is polled until it is final
+    *fake.deposit_status.lock().unwrap() = "pending_user_transfer_start".to_string();
+    let summary = tracker.run_polls().await.unwrap();
+    assert_eq!(
+        summary,
+        PollSummary {
+            polled: 1,
+            changed: 1,
+            failed: 0
+        }
+    );
+    assert_eq!(tracker.run_polls().await.unwrap().changed, 0);
+    *fake.deposit_status.lock().unwrap() = "completed".to_string();
+    tracker.run_polls().await.unwrap();
+
+    let deposit = tracker.get_transfer(&deposit.id).await.unwrap().unwrap();
+    assert!(deposit.terminal);
+    assert!(deposit.completed_at.is_some());
+    assert_eq!(tracker.run_polls().await.unwrap().polled, 0);
+
+    // Only the caller who initiated a transfer sees it
+    let (status, _) = call(
+        others.clone(),
+        get_uri(&format!("/api/transfers/{}", deposit.id)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, body) = call(others, get_uri("/api/transfers")).await;
+    assert_eq!(body["transfers"], json!([]));
+    let (status, body) = call(
+        mine.clone(),
+        get_uri(&format!("/api/transfers/{}", deposit.id)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["transfer"].get("callback_token").is_none());
+    let statuses: Vec<&str> = body["status_changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["to_status"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        statuses,
+        vec!["incomplete", "pending_user_transfer_start", "completed"]
+    );
+    assert_eq!(body["status_changes"][0]["source"], "initiated");
+    assert_eq!(body["status_changes"][2]["source"], "poll");
+
+    // A SEP-31 payment to a transfer server of no known anchor registers its
+    // callback with the caller's JWT. The JWT is not kept, so the payment is
+    // not polled and the anchor reports the outcome through the callback.
+    let (status, _) = call(
+        stellar_insights_backend::api::sep31_proxy::routes(
+            Arc::clone(&sep10),
+            Arc::clone(&tracker),
+            Arc::clone(&registry),
+        )
+        .layer(user("alice")),
+        post_json(
+            "/api/sep31/transactions",
+            json!({
+                "transfer_server": format!("{}/sep31", base),
+                "jwt": "theirs",
+                "asset_code": "USDC",
+                "amount": 250
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        fake.payment_auth.lock().unwrap().as_deref(),
+        Some("Bearer theirs")
+    );
+    assert_eq!(tracker.run_polls().await.unwrap().polled, 0);
+    let callback = fake.callbacks.lock().unwrap()[1].clone();
+    let path = callback.trim_start_matches("https://insights.example");
+
+    let (status, _) = call(
+        transfers.clone(),
+        post_json(
+            &format!("{}x", path),
+            json!({ "transaction": { "id": "payment-1", "status": "error" } }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = call(
+        transfers.clone(),
+        post_json(
+            path,
+            json!({ "transaction": { "id": "payment-2", "status": "error" } }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = call(
+        transfers.clone(),
+        post_json(
+            path,
+            json!({ "transaction": { "id": "payment-1", "status": "error" } }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["changed"], true);
+
+    let (_, body) = call(mine, get_uri("/api/transfers?status=error")).await;
+    let payment = &body["transfers"][0];
+    assert_eq!(payment["sep"], "sep31");
+    assert_eq!(payment["caller_token"], true);
+    assert_eq!(payment["amount"], "250");
+    assert_eq!(payment["anchor_id"], Value::Null);
+    assert_eq!(payment["terminal"], true);
+
+    let (status, metrics) = call(
+        transfers.clone(),
+        get_uri(&format!("/api/anchors/{}/transfer-metrics", anchor.id)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(metrics["transfers"], 1);
+    assert_eq!(metrics["completed"], 1);
+    assert_eq!(metrics["completion_rate"], 1.0);
+    let in_status: Vec<&str> = metrics["time_in_status"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(in_status, vec!["incomplete", "pending_user_transfer_start"]);
+
+    let (_, body) = call(transfers, get_uri("/api/transfers/metrics")).await;
+    let anchors = body["anchors"].as_array().unwrap();
+    assert_eq!(anchors.len(), 2);
+    assert_eq!(anchors[0]["anchor_id"], Value::Null);
+    assert_eq!(anchors[0]["failed"], 1);
+    assert_eq!(anchors[0]["error_rate"], 1.0);
+}
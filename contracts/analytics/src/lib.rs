@@ -1,5 +1,7 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, Vec,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -18,6 +20,27 @@ pub enum DataKey {
     Snapshots,
     /// Latest epoch number (instance storage for quick access)
     LatestEpoch,
+    /// Oracle addresses that attest snapshots in quorum mode
+    Oracles,
+    /// Number of agreeing oracle attestations that finalize a snapshot
+    Threshold,
+    /// Map of oracle -> attested hash for an epoch
+    Attestations(u64),
+    /// Conflicting attestations recorded for an epoch
+    Conflicts(u64),
+}
+
+/// Attestation that disagrees with another hash attested for the same epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationConflict {
+    pub epoch: u64,
+    pub oracle: Address,
+    pub hash: BytesN<32>,
+    /// Final hash of the epoch, or the hash with the most attestations if it
+    /// was not final yet
+    pub conflicting_hash: BytesN<32>,
+    pub timestamp: u64,
 }
 
 #[contract]
@@ -69,6 +92,7 @@ impl AnalyticsContract {
     /// * If caller is not the authorized admin
     /// * If epoch is 0 (invalid)
     /// * If epoch <= latest (monotonicity violated: out-of-order or duplicate)
+    /// * If the contract is in quorum mode
     ///
     /// # Returns
     /// * Ledger timestamp when snapshot was recorded
@@ -87,6 +111,10 @@ impl AnalyticsContract {
             panic!("Unauthorized: only the admin can submit snapshots");
         }
 
+        if env.storage().instance().has(&DataKey::Oracles) {
+            panic!("Quorum mode enabled: snapshots are finalized by oracle attestations");
+        }
+
         if epoch == 0 {
             panic!("Invalid epoch: must be greater than 0");
         }
@@ -108,26 +136,210 @@ impl AnalyticsContract {
             }
         }
 
-        let timestamp = env.ledger().timestamp();
-        let metadata = SnapshotMetadata {
-            epoch,
-            timestamp,
-            hash,
-        };
+        store_snapshot(&env, epoch, hash).timestamp
+    }
 
-        let mut snapshots: Map<u64, SnapshotMetadata> = env
+    /// Switch the contract to quorum mode, or change its oracle set
+    /// In quorum mode the admin no longer submits snapshots: each oracle
+    /// attests a hash with `attest_snapshot`, and the snapshot is stored once
+    /// `threshold` current oracles agree. The threshold must be a majority
+    /// of the oracles so that two hashes can never both reach it.
+    /// Once in quorum mode, at least the current threshold of current
+    /// oracles must also authorize a change of the oracle set.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `current_admin` - Current admin address (must authenticate)
+    /// * `oracles` - Addresses allowed to attest snapshots
+    /// * `threshold` - Number of agreeing attestations that finalize a snapshot
+    /// * `approvers` - Current oracles approving the change (must
+    ///   authenticate); ignored when quorum mode is being enabled
+    ///
+    /// # Panics
+    /// * If contract is not initialized (admin not set)
+    /// * If caller is not the current admin
+    /// * If an approver is not a current oracle, or fewer than the current
+    ///   threshold of oracles approved the change
+    /// * If `oracles` is empty or has duplicates, or `threshold` is not a
+    ///   majority of the oracles or larger than their number
+    pub fn configure_oracles(
+        env: Env,
+        current_admin: Address,
+        oracles: Vec<Address>,
+        threshold: u32,
+        approvers: Vec<Address>,
+    ) {
+        current_admin.require_auth();
+
+        let admin: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::Snapshots)
-            .unwrap_or_else(|| Map::new(&env));
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized: admin not set");
+
+        if current_admin != admin {
+            panic!("Unauthorized: only the admin can configure oracles");
+        }
+
+        let current: Option<Vec<Address>> = env.storage().instance().get(&DataKey::Oracles);
+        if let Some(current) = current {
+            let current_threshold: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::Threshold)
+                .expect("Quorum not configured: threshold not set");
+            let mut approved: Vec<Address> = Vec::new(&env);
+            for approver in approvers.iter() {
+                if !current.contains(&approver) {
+                    panic!("Unauthorized: approver is not a current oracle");
+                }
+                if !approved.contains(&approver) {
+                    approver.require_auth();
+                    approved.push_back(approver);
+                }
+            }
+            if approved.len() < current_threshold {
+                panic!(
+                    "Insufficient approvals: {} of {} oracles required",
+                    approved.len(),
+                    current_threshold
+                );
+            }
+        }
+
+        if threshold > oracles.len() || threshold * 2 <= oracles.len() {
+            panic!(
+                "Invalid threshold: must be a majority of the {} oracles",
+                oracles.len()
+            );
+        }
+        for (i, oracle) in oracles.iter().enumerate() {
+            if oracles.first_index_of(&oracle) != Some(i as u32) {
+                panic!("Invalid oracle set: duplicate oracle");
+            }
+        }
+
+        env.storage().instance().set(&DataKey::Oracles, &oracles);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+
+        env.events()
+            .publish((symbol_short!("oracles"),), (oracles, threshold));
+    }
+
+    /// Attest the hash of a snapshot as one of the oracles
+    /// Each oracle attests an epoch once. Once `threshold` current oracles
+    /// attested the same hash, the snapshot is stored as `submit_snapshot`
+    /// would. An attestation is recorded as a conflict when the epoch is
+    /// final with another hash, or when another hash has at least as many
+    /// attestations.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch identifier (final, or strictly greater than latest)
+    /// * `hash` - 32-byte hash of the analytics snapshot
+    /// * `oracle` - Address of the attesting oracle
+    ///
+    /// # Panics
+    /// * If no oracle set is configured
+    /// * If `oracle` is not in the oracle set
+    /// * If epoch is 0, or not final and not greater than latest
+    /// * If the oracle already attested the epoch
+    ///
+    /// # Returns
+    /// * Number of current oracles that attested this hash for the epoch
+    pub fn attest_snapshot(env: Env, epoch: u64, hash: BytesN<32>, oracle: Address) -> u32 {
+        oracle.require_auth();
+
+        let oracles: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracles)
+            .expect("Quorum mode not configured: oracle set not set");
 
-        snapshots.set(epoch, metadata);
+        if !oracles.contains(&oracle) {
+            panic!("Unauthorized: only oracles can attest snapshots");
+        }
+
+        if epoch == 0 {
+            panic!("Invalid epoch: must be greater than 0");
+        }
+
+        let finalized = Self::get_snapshot(env.clone(), epoch).map(|s| s.hash);
+        let latest = Self::get_latest_epoch(env.clone());
+        if finalized.is_none() && epoch <= latest {
+            panic!(
+                "Epoch monotonicity violated: epoch {} must be strictly greater than latest {}",
+                epoch, latest
+            );
+        }
+
+        let mut attestations = Self::get_attestations(env.clone(), epoch);
+        if attestations.contains_key(oracle.clone()) {
+            panic!("Oracle already attested epoch {}", epoch);
+        }
+        attestations.set(oracle.clone(), hash.clone());
         env.storage()
             .persistent()
-            .set(&DataKey::Snapshots, &snapshots);
-        env.storage().instance().set(&DataKey::LatestEpoch, &epoch);
+            .set(&DataKey::Attestations(epoch), &attestations);
+
+        // Count attestations per hash, ignoring removed oracles
+        let mut tally: Map<BytesN<32>, u32> = Map::new(&env);
+        for (attester, attested) in attestations.iter() {
+            if oracles.contains(&attester) {
+                let votes = tally.get(attested.clone()).unwrap_or(0);
+                tally.set(attested, votes + 1);
+            }
+        }
+        let votes = tally.get(hash.clone()).unwrap_or(0);
+
+        env.events().publish(
+            (symbol_short!("attested"), epoch),
+            (oracle.clone(), hash.clone(), votes),
+        );
+
+        let conflicting_hash = match &finalized {
+            Some(final_hash) => (*final_hash != hash).then(|| final_hash.clone()),
+            None => {
+                // The other hash with the most attestations, if it has at
+                // least as many as this one
+                let mut leading: Option<(BytesN<32>, u32)> = None;
+                for (attested, count) in tally.iter() {
+                    if attested != hash
+                        && count >= votes
+                        && leading.as_ref().is_none_or(|(_, c)| count > *c)
+                    {
+                        leading = Some((attested, count));
+                    }
+                }
+                leading.map(|(attested, _)| attested)
+            }
+        };
+        if let Some(conflicting_hash) = conflicting_hash {
+            let conflict = AttestationConflict {
+                epoch,
+                oracle,
+                hash: hash.clone(),
+                conflicting_hash,
+                timestamp: env.ledger().timestamp(),
+            };
+            let mut conflicts = Self::get_conflicts(env.clone(), epoch);
+            conflicts.push_back(conflict.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Conflicts(epoch), &conflicts);
+            env.events()
+                .publish((symbol_short!("conflict"), epoch), conflict);
+        }
 
-        timestamp
+        if finalized.is_none() && votes >= Self::get_threshold(env.clone()) {
+            let metadata = store_snapshot(&env, epoch, hash);
+            env.events()
+                .publish((symbol_short!("finalized"), epoch), (metadata, votes));
+        }
+
+        votes
     }
 
     /// Get snapshot metadata for a specific epoch
@@ -226,6 +438,64 @@ impl AnalyticsContract {
         env.storage().instance().get(&DataKey::Admin)
     }
 
+    /// Get the oracle set
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    ///
+    /// # Returns
+    /// * Oracle addresses (empty if the contract is not in quorum mode)
+    pub fn get_oracles(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Oracles)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the number of agreeing attestations that finalize a snapshot
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    ///
+    /// # Returns
+    /// * The threshold (0 if the contract is not in quorum mode)
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .unwrap_or(0)
+    }
+
+    /// Get the attestations made for an epoch
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch to retrieve
+    ///
+    /// # Returns
+    /// * Map of oracle address -> attested hash
+    pub fn get_attestations(env: Env, epoch: u64) -> Map<Address, BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestations(epoch))
+            .unwrap_or_else(|| Map::new(&env))
+    }
+
+    /// Get the conflicting attestations recorded for an epoch
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch to retrieve
+    ///
+    /// # Returns
+    /// * Conflicts in the order the attestations were made
+    pub fn get_conflicts(env: Env, epoch: u64) -> Vec<AttestationConflict> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Conflicts(epoch))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
     /// Update the authorized admin address
     /// Only the current admin can transfer admin rights to a new address
     ///
@@ -257,5 +527,28 @@ impl AnalyticsContract {
     }
 }
 
+/// Store snapshot metadata for an epoch and make it the latest epoch
+fn store_snapshot(env: &Env, epoch: u64, hash: BytesN<32>) -> SnapshotMetadata {
+    let metadata = SnapshotMetadata {
+        epoch,
+        timestamp: env.ledger().timestamp(),
+        hash,
+    };
+
+    let mut snapshots: Map<u64, SnapshotMetadata> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Snapshots)
+        .unwrap_or_else(|| Map::new(env));
+
+    snapshots.set(epoch, metadata.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Snapshots, &snapshots);
+    env.storage().instance().set(&DataKey::LatestEpoch, &epoch);
+
+    metadata
+}
+
 #[cfg(test)]
 mod tests;
@@ -418,3 +418,217 @@ fn test_old_admin_cannot_submit_after_transfer() {
     let hash = create_test_hash(&env, 1);
     client.submit_snapshot(&epoch, &hash, &admin);
 }
+
+/// Contract in quorum mode with three oracles and a threshold of two
+fn setup_quorum(env: &Env) -> (AnalyticsContractClient<'_>, Address, Vec<Address>) {
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let oracles = soroban_sdk::vec![
+        env,
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    client.configure_oracles(&admin, &oracles, &2, &soroban_sdk::vec![env]);
+
+    (client, admin, oracles)
+}
+
+#[test]
+fn test_quorum_finalizes_snapshot() {
+    let env = Env::default();
+    let (client, _admin, oracles) = setup_quorum(&env);
+    env.ledger().set_timestamp(500);
+
+    let hash = create_test_hash(&env, 1);
+    assert_eq!(
+        client.attest_snapshot(&1, &hash, &oracles.get(0).unwrap()),
+        1
+    );
+    assert_eq!(client.get_snapshot(&1), None);
+
+    assert_eq!(
+        client.attest_snapshot(&1, &hash, &oracles.get(1).unwrap()),
+        2
+    );
+    assert_eq!(
+        client.get_snapshot(&1),
+        Some(SnapshotMetadata {
+            epoch: 1,
+            timestamp: 500,
+            hash: hash.clone(),
+        })
+    );
+    assert_eq!(client.get_latest_epoch(), 1);
+
+    // Late attestations are recorded; disagreeing ones as conflicts
+    let other = create_test_hash(&env, 2);
+    assert_eq!(
+        client.attest_snapshot(&1, &other, &oracles.get(2).unwrap()),
+        1
+    );
+    assert_eq!(client.get_snapshot(&1).unwrap().hash, hash);
+    assert_eq!(client.get_attestations(&1).len(), 3);
+    let conflicts = client.get_conflicts(&1);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts.get(0).unwrap().conflicting_hash, hash);
+}
+
+#[test]
+fn test_conflicting_attestations_are_recorded() {
+    let env = Env::default();
+    let (client, _admin, oracles) = setup_quorum(&env);
+
+    let honest = create_test_hash(&env, 1);
+    let forged = create_test_hash(&env, 2);
+    client.attest_snapshot(&1, &honest, &oracles.get(0).unwrap());
+    client.attest_snapshot(&1, &forged, &oracles.get(1).unwrap());
+
+    let conflicts = client.get_conflicts(&1);
+    assert_eq!(conflicts.len(), 1);
+    let conflict = conflicts.get(0).unwrap();
+    assert_eq!(conflict.oracle, oracles.get(1).unwrap());
+    assert_eq!(conflict.hash, forged);
+    assert_eq!(conflict.conflicting_hash, honest);
+    assert_eq!(client.get_snapshot(&1), None);
+
+    // Breaking the tie finalizes the epoch without a new conflict
+    assert_eq!(
+        client.attest_snapshot(&1, &honest, &oracles.get(2).unwrap()),
+        2
+    );
+    assert_eq!(client.get_snapshot(&1).unwrap().hash, honest);
+    assert_eq!(client.get_conflicts(&1).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Quorum mode enabled")]
+fn test_quorum_mode_rejects_admin_submissions() {
+    let env = Env::default();
+    let (client, admin, _oracles) = setup_quorum(&env);
+
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: only oracles can attest snapshots")]
+fn test_non_oracle_attestation_fails() {
+    let env = Env::default();
+    let (client, admin, _oracles) = setup_quorum(&env);
+
+    client.attest_snapshot(&1, &create_test_hash(&env, 1), &admin);
+}
+
+#[test]
+#[should_panic(expected = "Oracle already attested epoch 1")]
+fn test_duplicate_attestation_fails() {
+    let env = Env::default();
+    let (client, _admin, oracles) = setup_quorum(&env);
+
+    let oracle = oracles.get(0).unwrap();
+    client.attest_snapshot(&1, &create_test_hash(&env, 1), &oracle);
+    client.attest_snapshot(&1, &create_test_hash(&env, 2), &oracle);
+}
+
+#[test]
+#[should_panic(expected = "Epoch monotonicity violated")]
+fn test_attestation_for_skipped_epoch_fails() {
+    let env = Env::default();
+    let (client, _admin, oracles) = setup_quorum(&env);
+
+    let hash = create_test_hash(&env, 1);
+    client.attest_snapshot(&5, &hash, &oracles.get(0).unwrap());
+    client.attest_snapshot(&5, &hash, &oracles.get(1).unwrap());
+
+    // Epoch 4 can no longer be finalized after epoch 5
+    client.attest_snapshot(&4, &hash, &oracles.get(0).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Invalid threshold")]
+fn test_invalid_threshold_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let oracles = soroban_sdk::vec![&env, Address::generate(&env)];
+    client.configure_oracles(&admin, &oracles, &2, &soroban_sdk::vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid threshold")]
+fn test_minority_threshold_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let oracles = soroban_sdk::vec![&env, Address::generate(&env), Address::generate(&env)];
+    client.configure_oracles(&admin, &oracles, &1, &soroban_sdk::vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "duplicate oracle")]
+fn test_duplicate_oracle_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let oracle = Address::generate(&env);
+    let oracles = soroban_sdk::vec![&env, oracle.clone(), oracle];
+    client.configure_oracles(&admin, &oracles, &2, &soroban_sdk::vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient approvals")]
+fn test_admin_cannot_reconfigure_oracles_alone() {
+    let env = Env::default();
+    let (client, admin, _oracles) = setup_quorum(&env);
+
+    let takeover = soroban_sdk::vec![&env, admin.clone()];
+    client.configure_oracles(&admin, &takeover, &1, &soroban_sdk::vec![&env]);
+}
+
+#[test]
+#[should_panic(expected = "approver is not a current oracle")]
+fn test_admin_cannot_approve_reconfiguration() {
+    let env = Env::default();
+    let (client, admin, _oracles) = setup_quorum(&env);
+
+    let takeover = soroban_sdk::vec![&env, admin.clone()];
+    client.configure_oracles(&admin, &takeover, &1, &takeover);
+}
+
+#[test]
+fn test_oracles_approve_reconfiguration() {
+    let env = Env::default();
+    let (client, admin, oracles) = setup_quorum(&env);
+
+    let replacement = soroban_sdk::vec![&env, oracles.get(0).unwrap(), oracles.get(1).unwrap()];
+    client.configure_oracles(&admin, &replacement, &2, &replacement);
+
+    let auths = env.auths();
+    assert!(auths
+        .iter()
+        .any(|(signer, _)| *signer == oracles.get(0).unwrap()));
+    assert!(auths
+        .iter()
+        .any(|(signer, _)| *signer == oracles.get(1).unwrap()));
+    assert_eq!(client.get_oracles(), replacement);
+}
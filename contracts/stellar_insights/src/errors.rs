@@ -17,4 +17,16 @@ pub enum Error {
     AdminNotSet = 5,
     /// No snapshot found for the requested epoch
     SnapshotNotFound = 6,
+    /// Caller is not in the configured oracle set
+    NotAnOracle = 7,
+    /// Oracle already attested a snapshot for this epoch
+    DuplicateAttestation = 8,
+    /// Oracle set is empty or has duplicates, or threshold is out of range
+    InvalidOracleSet = 9,
+    /// Snapshots are finalized by oracle attestations, not submitted directly
+    QuorumModeEnabled = 10,
+    /// No oracle set configured
+    QuorumNotConfigured = 11,
+    /// Too few current oracles approved a change of the oracle set
+    InsufficientApprovals = 12,
 }
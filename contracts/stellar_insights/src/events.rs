@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
 
 // ============================================================================
 // Event Topics - Short symbols for efficient on-chain storage
@@ -10,6 +10,18 @@ pub const SNAPSHOT_SUBMITTED: Symbol = symbol_short!("SNAP_SUB");
 /// Topic for snapshot lifecycle events (for filtering)
 pub const SNAPSHOT_LIFECYCLE: Symbol = symbol_short!("SNAP_LFE");
 
+/// Topic for oracle attestation events
+pub const SNAPSHOT_ATTESTED: Symbol = symbol_short!("SNAP_ATT");
+
+/// Topic for attestations that disagree with another attested hash
+pub const ATTESTATION_CONFLICT: Symbol = symbol_short!("SNAP_CFL");
+
+/// Topic for snapshots finalized by an oracle quorum
+pub const SNAPSHOT_FINALIZED: Symbol = symbol_short!("SNAP_FIN");
+
+/// Topic for oracle set changes
+pub const ORACLES_CONFIGURED: Symbol = symbol_short!("ORCL_CFG");
+
 // ============================================================================
 // Event Structures
 // ============================================================================
@@ -67,6 +79,64 @@ impl SnapshotSubmitted {
     }
 }
 
+/// Event emitted when an oracle attests a snapshot hash for an epoch
+///
+/// # Fields
+/// - `epoch`: The epoch the attestation is for
+/// - `hash`: The attested 32-byte snapshot hash
+/// - `oracle`: Address of the attesting oracle
+/// - `votes`: Number of current oracles that attested this hash for the epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotAttested {
+    pub epoch: u64,
+    pub hash: BytesN<32>,
+    pub oracle: Address,
+    pub votes: u32,
+}
+
+/// Event emitted when an attestation disagrees with another hash attested
+/// for the same epoch
+///
+/// # Fields
+/// - `epoch`: The contested epoch
+/// - `oracle`: Address of the oracle whose attestation disagrees
+/// - `hash`: The hash that oracle attested
+/// - `conflicting_hash`: The final hash, or the leading hash if the epoch is
+///   not final yet
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationConflicted {
+    pub epoch: u64,
+    pub oracle: Address,
+    pub hash: BytesN<32>,
+    pub conflicting_hash: BytesN<32>,
+}
+
+/// Event emitted when enough oracles agree on the hash of an epoch
+///
+/// # Fields
+/// - `epoch`: The finalized epoch
+/// - `hash`: The hash the oracles agreed on
+/// - `votes`: Number of agreeing attestations
+/// - `timestamp`: Ledger timestamp when the snapshot was recorded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotFinalized {
+    pub epoch: u64,
+    pub hash: BytesN<32>,
+    pub votes: u32,
+    pub timestamp: u64,
+}
+
+/// Event emitted when the admin configures the oracle set
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OraclesConfigured {
+    pub oracles: Vec<Address>,
+    pub threshold: u32,
+}
+
 /// Legacy event structure for backwards compatibility
 ///
 /// This event emitted when an analytics snapshot is successfully submitted.
@@ -122,3 +192,65 @@ pub fn emit_snapshot_submitted(
 ) {
     SnapshotSubmitted::publish(env, hash, epoch, timestamp, submitter);
 }
+
+/// Emit an attestation event
+pub fn emit_snapshot_attested(
+    env: &Env,
+    epoch: u64,
+    hash: BytesN<32>,
+    oracle: Address,
+    votes: u32,
+) {
+    let event = SnapshotAttested {
+        epoch,
+        hash,
+        oracle,
+        votes,
+    };
+    env.events()
+        .publish((SNAPSHOT_ATTESTED, SNAPSHOT_LIFECYCLE), event);
+}
+
+/// Emit a conflicting attestation event
+pub fn emit_attestation_conflict(
+    env: &Env,
+    epoch: u64,
+    oracle: Address,
+    hash: BytesN<32>,
+    conflicting_hash: BytesN<32>,
+) {
+    let event = AttestationConflicted {
+        epoch,
+        oracle,
+        hash,
+        conflicting_hash,
+    };
+    env.events()
+        .publish((ATTESTATION_CONFLICT, SNAPSHOT_LIFECYCLE), event);
+}
+
+/// Emit a quorum finalization event
+pub fn emit_snapshot_finalized(
+    env: &Env,
+    epoch: u64,
+    hash: BytesN<32>,
+    votes: u32,
+    timestamp: u64,
+) {
+    let event = SnapshotFinalized {
+        epoch,
+        hash,
+        votes,
+        timestamp,
+    };
+    env.events()
+        .publish((SNAPSHOT_FINALIZED, SNAPSHOT_LIFECYCLE), event);
+}
+
+/// Emit an oracle set change event
+pub fn emit_oracles_configured(env: &Env, oracles: Vec<Address>, threshold: u32) {
+    env.events().publish(
+        (ORACLES_CONFIGURED,),
+        OraclesConfigured { oracles, threshold },
+    );
+}
@@ -4,8 +4,11 @@ mod errors;
 mod events;
 
 use errors::Error;
-use events::emit_snapshot_submitted;
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map};
+use events::{
+    emit_attestation_conflict, emit_oracles_configured, emit_snapshot_attested,
+    emit_snapshot_finalized, emit_snapshot_submitted,
+};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map, Vec};
 
 /// Storage keys for persistent contract data
 #[contracttype]
@@ -17,6 +20,14 @@ pub enum DataKey {
     Snapshots,
     /// Latest epoch number recorded
     LatestEpoch,
+    /// Oracle addresses that attest snapshots in quorum mode
+    Oracles,
+    /// Number of agreeing oracle attestations that finalize a snapshot
+    Threshold,
+    /// Map of oracle -> attested hash for an epoch
+    Attestations(u64),
+    /// Conflicting attestations recorded for an epoch
+    Conflicts(u64),
 }
 
 /// Analytics snapshot data structure
@@ -31,6 +42,21 @@ pub struct Snapshot {
     pub timestamp: u64,
 }
 
+/// Attestation that disagrees with another hash attested for the same epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationConflict {
+    /// Oracle whose attestation disagrees
+    pub oracle: Address,
+    /// Hash the oracle attested
+    pub hash: BytesN<32>,
+    /// Final hash of the epoch, or the hash with the most attestations if it
+    /// was not final yet
+    pub conflicting_hash: BytesN<32>,
+    /// Ledger timestamp of the attestation
+    pub timestamp: u64,
+}
+
 #[contract]
 pub struct StellarInsightsContract;
 
@@ -74,6 +100,7 @@ impl StellarInsightsContract {
     /// * `Error::UnauthorizedCaller` - If caller is not the admin
     /// * `Error::InvalidEpoch` - If epoch is 0
     /// * `Error::DuplicateEpoch` - If snapshot already exists for this epoch
+    /// * `Error::QuorumModeEnabled` - If snapshots are finalized by oracles
     ///
     /// # Returns
     /// * Ledger timestamp when the snapshot was recorded
@@ -98,13 +125,19 @@ impl StellarInsightsContract {
             return Err(Error::UnauthorizedCaller);
         }
 
+        // Once an oracle set is configured, the admin alone cannot record
+        // snapshots
+        if env.storage().instance().has(&DataKey::Oracles) {
+            return Err(Error::QuorumModeEnabled);
+        }
+
         // Validate epoch is not zero
         if epoch == 0 {
             return Err(Error::InvalidEpoch);
         }
 
         // Get existing snapshots map or create new one
-        let mut snapshots: Map<u64, Snapshot> = env
+        let snapshots: Map<u64, Snapshot> = env
             .storage()
             .persistent()
             .get(&DataKey::Snapshots)
@@ -115,42 +148,220 @@ impl StellarInsightsContract {
             return Err(Error::DuplicateEpoch);
         }
 
-        // Get current ledger timestamp
-        let timestamp = env.ledger().timestamp();
+        Ok(record_snapshot(&env, snapshots, epoch, hash, caller))
+    }
 
-        // Create snapshot entry
-        let snapshot = Snapshot {
-            hash: hash.clone(),
-            epoch,
-            timestamp,
-        };
+    /// Switch the contract to quorum mode, or change its oracle set
+    ///
+    /// In quorum mode snapshots are no longer submitted by the admin. Each
+    /// oracle attests a hash for an epoch with `attest_snapshot`, and the
+    /// snapshot is recorded once `threshold` oracles agree. Attestations
+    /// already made by oracles that are removed no longer count. The
+    /// threshold must be a majority of the oracles so that two hashes can
+    /// never both reach it.
+    ///
+    /// Once quorum mode is on, the admin cannot change the oracle set alone:
+    /// at least the current threshold of current oracles must approve the
+    /// change by authorizing it.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Admin address
+    /// * `oracles` - Addresses allowed to attest snapshots
+    /// * `threshold` - Number of agreeing attestations that finalize a snapshot
+    /// * `approvers` - Current oracles approving the change; ignored when
+    ///   quorum mode is being enabled
+    ///
+    /// # Errors
+    /// * `Error::AdminNotSet` - If admin was not initialized
+    /// * `Error::UnauthorizedCaller` - If caller is not the admin
+    /// * `Error::NotAnOracle` - If an approver is not a current oracle
+    /// * `Error::InsufficientApprovals` - If fewer than the current threshold
+    ///   of oracles approved the change
+    /// * `Error::InvalidOracleSet` - If `oracles` is empty or has duplicates,
+    ///   or `threshold` is not a majority of the oracles or larger than their
+    ///   number
+    pub fn configure_oracles(
+        env: Env,
+        caller: Address,
+        oracles: Vec<Address>,
+        threshold: u32,
+        approvers: Vec<Address>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        if caller != admin {
+            return Err(Error::UnauthorizedCaller);
+        }
+
+        let current: Option<Vec<Address>> = env.storage().instance().get(&DataKey::Oracles);
+        if let Some(current) = current {
+            let current_threshold: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::Threshold)
+                .ok_or(Error::QuorumNotConfigured)?;
+            let mut approved: Vec<Address> = Vec::new(&env);
+            for approver in approvers.iter() {
+                if !current.contains(&approver) {
+                    return Err(Error::NotAnOracle);
+                }
+                if !approved.contains(&approver) {
+                    approver.require_auth();
+                    approved.push_back(approver);
+                }
+            }
+            if approved.len() < current_threshold {
+                return Err(Error::InsufficientApprovals);
+            }
+        }
+
+        if threshold > oracles.len() || threshold * 2 <= oracles.len() {
+            return Err(Error::InvalidOracleSet);
+        }
+        for (i, oracle) in oracles.iter().enumerate() {
+            if oracles.first_index_of(&oracle) != Some(i as u32) {
+                return Err(Error::InvalidOracleSet);
+            }
+        }
 
-        // Store snapshot
-        snapshots.set(epoch, snapshot);
+        env.storage().instance().set(&DataKey::Oracles, &oracles);
         env.storage()
-            .persistent()
-            .set(&DataKey::Snapshots, &snapshots);
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
 
-        // Update latest epoch if this is newer
-        let current_latest: u64 = env
+        emit_oracles_configured(&env, oracles, threshold);
+
+        Ok(())
+    }
+
+    /// Attest the hash of an analytics snapshot as one of the oracles
+    ///
+    /// Each oracle attests an epoch once. When the number of current oracles
+    /// that attested the same hash reaches the threshold, the snapshot is
+    /// recorded exactly as `submit_snapshot` would, with the oracle that
+    /// completed the quorum as submitter. An attestation is recorded as a
+    /// conflict when the epoch is final with another hash, or when another
+    /// hash has at least as many attestations. Attestations remain accepted
+    /// after an epoch is final.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch identifier (must be positive)
+    /// * `hash` - 32-byte SHA-256 hash of the analytics snapshot
+    /// * `oracle` - Address of the attesting oracle
+    ///
+    /// # Errors
+    /// * `Error::QuorumNotConfigured` - If no oracle set is configured
+    /// * `Error::NotAnOracle` - If `oracle` is not in the oracle set
+    /// * `Error::InvalidEpoch` - If epoch is 0
+    /// * `Error::DuplicateAttestation` - If the oracle already attested the epoch
+    ///
+    /// # Returns
+    /// * Number of current oracles that attested this hash for the epoch
+    pub fn attest_snapshot(
+        env: Env,
+        epoch: u64,
+        hash: BytesN<32>,
+        oracle: Address,
+    ) -> Result<u32, Error> {
+        oracle.require_auth();
+
+        let oracles: Vec<Address> = env
             .storage()
             .instance()
-            .get(&DataKey::LatestEpoch)
-            .unwrap_or(0);
+            .get(&DataKey::Oracles)
+            .ok_or(Error::QuorumNotConfigured)?;
+        if !oracles.contains(&oracle) {
+            return Err(Error::NotAnOracle);
+        }
 
-        if epoch > current_latest {
-            env.storage().instance().set(&DataKey::LatestEpoch, &epoch);
+        if epoch == 0 {
+            return Err(Error::InvalidEpoch);
         }
 
-        // Emit structured event for off-chain indexing
-        // Event payload matches stored data exactly:
-        // - hash: same as snapshot.hash
-        // - epoch: same as snapshot.epoch
-        // - timestamp: same as snapshot.timestamp
-        // - submitter: the authenticated caller
-        emit_snapshot_submitted(&env, hash, epoch, timestamp, caller);
+        let key = DataKey::Attestations(epoch);
+        let mut attestations: Map<Address, BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(&env));
+        if attestations.contains_key(oracle.clone()) {
+            return Err(Error::DuplicateAttestation);
+        }
+        attestations.set(oracle.clone(), hash.clone());
+        env.storage().persistent().set(&key, &attestations);
 
-        Ok(timestamp)
+        // Count attestations per hash, ignoring removed oracles
+        let mut tally: Map<BytesN<32>, u32> = Map::new(&env);
+        for (attester, attested) in attestations.iter() {
+            if oracles.contains(&attester) {
+                let votes = tally.get(attested.clone()).unwrap_or(0);
+                tally.set(attested, votes + 1);
+            }
+        }
+        let votes = tally.get(hash.clone()).unwrap_or(0);
+
+        emit_snapshot_attested(&env, epoch, hash.clone(), oracle.clone(), votes);
+
+        let snapshots: Map<u64, Snapshot> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Snapshots)
+            .unwrap_or_else(|| Map::new(&env));
+        let finalized = snapshots.get(epoch).map(|s| s.hash);
+
+        let conflicting_hash = match &finalized {
+            Some(final_hash) => (*final_hash != hash).then(|| final_hash.clone()),
+            None => {
+                // The other hash with the most attestations, if it has at
+                // least as many as this one
+                let mut leading: Option<(BytesN<32>, u32)> = None;
+                for (attested, count) in tally.iter() {
+                    if attested != hash
+                        && count >= votes
+                        && leading.as_ref().is_none_or(|(_, c)| count > *c)
+                    {
+                        leading = Some((attested, count));
+                    }
+                }
+                leading.map(|(attested, _)| attested)
+            }
+        };
+        if let Some(conflicting_hash) = conflicting_hash {
+            let key = DataKey::Conflicts(epoch);
+            let mut conflicts: Vec<AttestationConflict> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or_else(|| Vec::new(&env));
+            conflicts.push_back(AttestationConflict {
+                oracle: oracle.clone(),
+                hash: hash.clone(),
+                conflicting_hash: conflicting_hash.clone(),
+                timestamp: env.ledger().timestamp(),
+            });
+            env.storage().persistent().set(&key, &conflicts);
+
+            emit_attestation_conflict(&env, epoch, oracle.clone(), hash.clone(), conflicting_hash);
+        }
+
+        let threshold: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .ok_or(Error::QuorumNotConfigured)?;
+        if finalized.is_none() && votes >= threshold {
+            let timestamp = record_snapshot(&env, snapshots, epoch, hash.clone(), oracle);
+            emit_snapshot_finalized(&env, epoch, hash, votes, timestamp);
+        }
+
+        Ok(votes)
     }
 
     /// Retrieve a snapshot hash for a specific epoch
@@ -239,6 +450,98 @@ impl StellarInsightsContract {
             .get(&DataKey::LatestEpoch)
             .unwrap_or(0)
     }
+
+    /// Get the oracle set
+    ///
+    /// # Returns
+    /// * The oracle addresses (empty if the contract is not in quorum mode)
+    pub fn get_oracles(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Oracles)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the number of agreeing attestations that finalize a snapshot
+    ///
+    /// # Returns
+    /// * The threshold (0 if the contract is not in quorum mode)
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .unwrap_or(0)
+    }
+
+    /// Get the attestations made for an epoch
+    ///
+    /// # Returns
+    /// * Map of oracle address -> attested hash
+    pub fn get_attestations(env: Env, epoch: u64) -> Map<Address, BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestations(epoch))
+            .unwrap_or_else(|| Map::new(&env))
+    }
+
+    /// Get the conflicting attestations recorded for an epoch
+    ///
+    /// # Returns
+    /// * Conflicts in the order the attestations were made
+    pub fn get_conflicts(env: Env, epoch: u64) -> Vec<AttestationConflict> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Conflicts(epoch))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+}
+
+/// Store a snapshot, update the latest epoch and emit `SnapshotSubmitted`
+///
+/// The caller has checked that `epoch` is valid and not yet in `snapshots`.
+fn record_snapshot(
+    env: &Env,
+    mut snapshots: Map<u64, Snapshot>,
+    epoch: u64,
+    hash: BytesN<32>,
+    submitter: Address,
+) -> u64 {
+    // Get current ledger timestamp
+    let timestamp = env.ledger().timestamp();
+
+    // Create snapshot entry
+    let snapshot = Snapshot {
+        hash: hash.clone(),
+        epoch,
+        timestamp,
+    };
+
+    // Store snapshot
+    snapshots.set(epoch, snapshot);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Snapshots, &snapshots);
+
+    // Update latest epoch if this is newer
+    let current_latest: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LatestEpoch)
+        .unwrap_or(0);
+
+    if epoch > current_latest {
+        env.storage().instance().set(&DataKey::LatestEpoch, &epoch);
+    }
+
+    // Emit structured event for off-chain indexing
+    // Event payload matches stored data exactly:
+    // - hash: same as snapshot.hash
+    // - epoch: same as snapshot.epoch
+    // - timestamp: same as snapshot.timestamp
+    // - submitter: the authenticated caller or quorum-completing oracle
+    emit_snapshot_submitted(env, hash, epoch, timestamp, submitter);
+
+    timestamp
 }
 
 mod test;
@@ -1,10 +1,13 @@
 #![cfg(test)]
 
 use super::*;
-use crate::events::{SnapshotSubmitted, SNAPSHOT_LIFECYCLE, SNAPSHOT_SUBMITTED};
+use crate::events::{
+    AttestationConflicted, SnapshotFinalized, SnapshotSubmitted, ATTESTATION_CONFLICT,
+    SNAPSHOT_FINALIZED, SNAPSHOT_LIFECYCLE, SNAPSHOT_SUBMITTED,
+};
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    Address, BytesN, Env,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, BytesN, Env, IntoVal,
 };
 
 /// Helper function to create a 32-byte hash for testing
@@ -390,3 +393,312 @@ fn test_admin_not_set_error() {
 
     assert_eq!(result, Err(Ok(Error::AdminNotSet)));
 }
+
+/// Contract in quorum mode with three oracles and a threshold of two
+fn setup_quorum(
+    env: &Env,
+) -> (
+    Address,
+    StellarInsightsContractClient<'_>,
+    Address,
+    [Address; 3],
+) {
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, StellarInsightsContract);
+    let client = StellarInsightsContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let oracles = [
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    client.configure_oracles(
+        &admin,
+        &vec![
+            env,
+            oracles[0].clone(),
+            oracles[1].clone(),
+            oracles[2].clone(),
+        ],
+        &2,
+        &vec![env],
+    );
+
+    (contract_id, client, admin, oracles)
+}
+
+#[test]
+fn test_quorum_finalizes_snapshot() {
+    let env = Env::default();
+    let (contract_id, client, _admin, oracles) = setup_quorum(&env);
+    env.ledger().set_timestamp(1_000);
+
+    assert_eq!(client.get_threshold(), 2);
+    assert_eq!(client.get_oracles().len(), 3);
+
+    let hash = create_test_hash(&env, 777);
+    assert_eq!(client.attest_snapshot(&1, &hash, &oracles[0]), 1);
+    assert_eq!(
+        client.try_get_snapshot(&1),
+        Err(Ok(Error::SnapshotNotFound))
+    );
+
+    assert_eq!(client.attest_snapshot(&1, &hash, &oracles[1]), 2);
+    assert_eq!(client.get_snapshot(&1), hash);
+    assert_eq!(client.latest_snapshot(), (hash.clone(), 1, 1_000));
+    assert_eq!(client.get_conflicts(&1).len(), 0);
+
+    // The snapshot is recorded as if submitted by the oracle completing the
+    // quorum, followed by the finalization event
+    let events = env.events().all();
+    let n = events.len();
+    assert_eq!(
+        events.slice(n - 2..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (SNAPSHOT_SUBMITTED, SNAPSHOT_LIFECYCLE).into_val(&env),
+                SnapshotSubmitted {
+                    hash: hash.clone(),
+                    epoch: 1,
+                    timestamp: 1_000,
+                    submitter: oracles[1].clone(),
+                }
+                .into_val(&env),
+            ),
+            (
+                contract_id,
+                (SNAPSHOT_FINALIZED, SNAPSHOT_LIFECYCLE).into_val(&env),
+                SnapshotFinalized {
+                    epoch: 1,
+                    hash: hash.clone(),
+                    votes: 2,
+                    timestamp: 1_000,
+                }
+                .into_val(&env),
+            ),
+        ]
+    );
+
+    // A late agreeing attestation is counted but changes nothing
+    assert_eq!(client.attest_snapshot(&1, &hash, &oracles[2]), 3);
+    assert_eq!(client.get_attestations(&1).len(), 3);
+    assert_eq!(client.get_conflicts(&1).len(), 0);
+}
+
+#[test]
+fn test_conflicting_attestations_are_recorded() {
+    let env = Env::default();
+    let (contract_id, client, _admin, oracles) = setup_quorum(&env);
+    env.ledger().set_timestamp(2_000);
+
+    let honest = create_test_hash(&env, 1);
+    let forged = create_test_hash(&env, 2);
+
+    client.attest_snapshot(&7, &honest, &oracles[0]);
+    assert_eq!(client.attest_snapshot(&7, &forged, &oracles[1]), 1);
+    assert_eq!(
+        client.get_conflicts(&7),
+        vec![
+            &env,
+            AttestationConflict {
+                oracle: oracles[1].clone(),
+                hash: forged.clone(),
+                conflicting_hash: honest.clone(),
+                timestamp: 2_000,
+            }
+        ]
+    );
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id,
+                (ATTESTATION_CONFLICT, SNAPSHOT_LIFECYCLE).into_val(&env),
+                AttestationConflicted {
+                    epoch: 7,
+                    oracle: oracles[1].clone(),
+                    hash: forged.clone(),
+                    conflicting_hash: honest.clone(),
+                }
+                .into_val(&env),
+            ),
+        ]
+    );
+
+    // Breaking the tie finalizes the epoch without a new conflict
+    assert_eq!(client.attest_snapshot(&7, &honest, &oracles[2]), 2);
+    assert_eq!(client.get_snapshot(&7), honest);
+    assert_eq!(client.get_conflicts(&7).len(), 1);
+
+    let attestations = client.get_attestations(&7);
+    assert_eq!(attestations.get(oracles[0].clone()), Some(honest.clone()));
+    assert_eq!(attestations.get(oracles[1].clone()), Some(forged));
+}
+
+#[test]
+fn test_attestations_after_finalization_conflict_with_final_hash() {
+    let env = Env::default();
+    let (_contract_id, client, _admin, oracles) = setup_quorum(&env);
+
+    let hash = create_test_hash(&env, 10);
+    let other = create_test_hash(&env, 20);
+    client.attest_snapshot(&3, &hash, &oracles[0]);
+    client.attest_snapshot(&3, &hash, &oracles[1]);
+
+    assert_eq!(client.attest_snapshot(&3, &other, &oracles[2]), 1);
+    assert_eq!(client.get_snapshot(&3), hash);
+    let conflicts = client.get_conflicts(&3);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts.get(0).unwrap().conflicting_hash, hash);
+}
+
+#[test]
+fn test_quorum_mode_rejects_admin_submissions() {
+    let env = Env::default();
+    let (_contract_id, client, admin, _oracles) = setup_quorum(&env);
+
+    let result = client.try_submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    assert_eq!(result, Err(Ok(Error::QuorumModeEnabled)));
+}
+
+#[test]
+fn test_attestation_errors() {
+    let env = Env::default();
+    let (_contract_id, client, _admin, oracles) = setup_quorum(&env);
+    let hash = create_test_hash(&env, 5);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_attest_snapshot(&1, &hash, &outsider),
+        Err(Ok(Error::NotAnOracle))
+    );
+    assert_eq!(
+        client.try_attest_snapshot(&0, &hash, &oracles[0]),
+        Err(Ok(Error::InvalidEpoch))
+    );
+
+    client.attest_snapshot(&1, &hash, &oracles[0]);
+    assert_eq!(
+        client.try_attest_snapshot(&1, &create_test_hash(&env, 6), &oracles[0]),
+        Err(Ok(Error::DuplicateAttestation))
+    );
+}
+
+#[test]
+fn test_attestation_requires_oracle_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, StellarInsightsContract);
+    let client = StellarInsightsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let result = client.try_attest_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    assert_eq!(result, Err(Ok(Error::QuorumNotConfigured)));
+    assert_eq!(client.get_threshold(), 0);
+    assert_eq!(client.get_oracles().len(), 0);
+}
+
+#[test]
+fn test_invalid_oracle_sets_are_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, StellarInsightsContract);
+    let client = StellarInsightsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let invalid = [
+        (vec![&env], 1),
+        (vec![&env, a.clone(), b.clone()], 0),
+        (vec![&env, a.clone(), b.clone()], 1),
+        (vec![&env, a.clone(), b.clone()], 3),
+        (vec![&env, a.clone(), b.clone(), a.clone()], 2),
+    ];
+    for (oracles, threshold) in invalid {
+        assert_eq!(
+            client.try_configure_oracles(&admin, &oracles, &threshold, &vec![&env]),
+            Err(Ok(Error::InvalidOracleSet))
+        );
+    }
+
+    assert_eq!(
+        client.try_configure_oracles(&a, &vec![&env, a.clone(), b.clone()], &2, &vec![&env]),
+        Err(Ok(Error::UnauthorizedCaller))
+    );
+}
+
+#[test]
+fn test_removed_oracles_no_longer_count() {
+    let env = Env::default();
+    let (_contract_id, client, admin, oracles) = setup_quorum(&env);
+    let hash = create_test_hash(&env, 9);
+
+    client.attest_snapshot(&4, &hash, &oracles[0]);
+    client.configure_oracles(
+        &admin,
+        &vec![&env, oracles[1].clone(), oracles[2].clone()],
+        &2,
+        &vec![&env, oracles[1].clone(), oracles[2].clone()],
+    );
+
+    assert_eq!(client.attest_snapshot(&4, &hash, &oracles[1]), 1);
+    assert_eq!(
+        client.try_get_snapshot(&4),
+        Err(Ok(Error::SnapshotNotFound))
+    );
+    assert_eq!(client.attest_snapshot(&4, &hash, &oracles[2]), 2);
+    assert_eq!(client.get_snapshot(&4), hash);
+}
+
+#[test]
+fn test_admin_cannot_reconfigure_oracles_alone() {
+    let env = Env::default();
+    let (_contract_id, client, admin, oracles) = setup_quorum(&env);
+    let takeover = vec![&env, admin.clone()];
+
+    assert_eq!(
+        client.try_configure_oracles(&admin, &takeover, &1, &vec![&env]),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    assert_eq!(
+        client.try_configure_oracles(&admin, &takeover, &1, &vec![&env, admin.clone()]),
+        Err(Ok(Error::NotAnOracle))
+    );
+    // The same oracle twice is one approval
+    assert_eq!(
+        client.try_configure_oracles(
+            &admin,
+            &takeover,
+            &1,
+            &vec![&env, oracles[0].clone(), oracles[0].clone()]
+        ),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    assert_eq!(client.get_oracles().len(), 3);
+
+    // A threshold of current oracles can approve the change
+    client.configure_oracles(
+        &admin,
+        &takeover,
+        &1,
+        &vec![&env, oracles[0].clone(), oracles[2].clone()],
+    );
+    let auths = env.auths();
+    assert!(auths.iter().any(|(signer, _)| *signer == oracles[0]));
+    assert!(auths.iter().any(|(signer, _)| *signer == oracles[2]));
+    assert_eq!(client.get_oracles(), takeover);
+    assert_eq!(client.get_threshold(), 1);
+}